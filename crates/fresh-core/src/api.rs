@@ -2064,7 +2064,8 @@ pub enum WidgetMutation {
     /// Set a `Toggle`'s checked state. Mutates the Toggle's
     /// `checked` field in the spec.
     SetChecked { widget_key: String, checked: bool },
-    /// Set a `List`'s selected index (instance state).
+    /// Set a `List`'s selected index, or a `Tree`'s absolute node index
+    /// (instance state).
     SetSelectedIndex { widget_key: String, index: i32 },
     /// Replace a `List`'s items + parallel `item_keys`. Mutates
     /// the List in the spec.
@@ -2425,6 +2426,21 @@ pub enum PluginCommand {
     /// Enable/disable line numbers for a buffer
     SetLineNumbers { buffer_id: BufferId, enabled: bool },
 
+    /// Replace the vertical rulers shown for a buffer
+    SetRulers {
+        buffer_id: BufferId,
+        columns: Vec<usize>,
+    },
+
+    /// Replace the diagnostics a plugin reports for a buffer.
+    /// Diagnostics from different `source`s are kept side by side and
+    /// merged with LSP diagnostics; an empty list clears the source.
+    SetDiagnostics {
+        buffer_id: BufferId,
+        source: String,
+        diagnostics: Vec<PluginDiagnostic>,
+    },
+
     /// Set the view mode for a buffer ("source" or "compose")
     SetViewMode { buffer_id: BufferId, mode: String },
 
@@ -3039,7 +3055,7 @@ pub enum PluginCommand {
         name: String,
         bindings: Vec<(String, String)>, // (key_string, command_name)
         read_only: bool,
-        /// When true, unbound character keys dispatch as `mode_text_input:<char>`.
+        /// When true, unbound character keys dispatch as `mode_text_input@<mode>:<char>`.
         allow_text_input: bool,
         /// When true, keys not bound by this mode fall through to the Normal
         /// context (motion, selection, copy) instead of being dropped.
@@ -3238,6 +3254,23 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Send a request to the active debug adapter (DAP) and return the raw
+    /// response body
+    DebugRequest {
+        command: String,
+        #[ts(type = "any")]
+        arguments: Option<JsonValue>,
+        request_id: u64,
+    },
+
+    /// Start a debug session for the active buffer's language, overriding
+    /// top-level keys of the configured launch/attach arguments (`null`
+    /// removes a key)
+    DebugStart {
+        #[ts(type = "any")]
+        configuration: Option<JsonValue>,
+    },
+
     /// Set the internal clipboard content
     SetClipboard { text: String },
 
@@ -3316,6 +3349,102 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Get a buffer's symbols from its tree-sitter parse or line patterns
+    /// (async). Resolves with an LSP-shaped `DocumentSymbol[]`.
+    GetSyntaxSymbols {
+        /// Buffer ID (0 for active buffer)
+        buffer_id: BufferId,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Get a buffer's undo tree (async). Resolves with
+    /// `{ nodes: [{ id, parent, timestamp, description }], current, saved }`,
+    /// or `null` for an unknown buffer.
+    GetUndoTree {
+        /// Buffer ID (0 for active buffer)
+        buffer_id: BufferId,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Get the diff from a buffer's content to a state of its undo tree
+    /// (async). Resolves with unified diff lines, or `null` when the state
+    /// doesn't exist or the buffer isn't fully loaded.
+    GetUndoStateDiff {
+        /// Buffer ID (0 for active buffer)
+        buffer_id: BufferId,
+        /// Undo tree node id (0 for the initial state)
+        node_id: u64,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Diff two texts line by line (async). Resolves with the hunks of
+    /// changed lines, as taken by `createCompositeBuffer`.
+    DiffLines {
+        /// Text before the change
+        old_text: String,
+        /// Text after the change
+        new_text: String,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Move the active buffer to a state of its undo tree. Ignored when
+    /// `buffer_id` isn't the active buffer.
+    JumpToUndoState {
+        /// Buffer ID (0 for active buffer)
+        buffer_id: BufferId,
+        /// Undo tree node id (0 for the initial state)
+        node_id: u64,
+    },
+
+    /// List the saved versions of a file in local history (async).
+    /// Resolves with `[{ id, timestamp, size }]`, newest first.
+    GetFileHistory {
+        /// File path
+        path: PathBuf,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Read one saved version of a file from local history (async).
+    /// Resolves with its text, or `null` if it doesn't exist or isn't UTF-8.
+    ReadFileHistoryVersion {
+        /// File path
+        path: PathBuf,
+        /// Version id
+        version_id: u64,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Replace the content of the active buffer with a saved version of its
+    /// file from local history. Ignored when `buffer_id` isn't the active
+    /// buffer.
+    RestoreFileHistoryVersion {
+        /// Buffer ID (0 for active buffer)
+        buffer_id: BufferId,
+        /// Version id
+        version_id: u64,
+    },
+
+    /// Apply the workspace edit under preview `id`, leaving out the
+    /// changes listed in `excluded`. Ignored when `id` is no longer pending.
+    ApplyWorkspaceEditPreview {
+        /// Preview id from the `workspace_edit_preview` hook
+        id: u64,
+        /// Ids of the changes to leave out
+        excluded: Vec<usize>,
+    },
+
+    /// Drop the workspace edit under preview `id` without applying it.
+    CancelWorkspaceEditPreview {
+        /// Preview id from the `workspace_edit_preview` hook
+        id: u64,
+    },
+
     /// Get cursor info for the active composite (side-by-side diff) buffer
     /// (async). Resolves with `{ focusedPane, paneCount, lines }` or `null`
     /// when the active buffer is not a composite buffer.
@@ -4323,6 +4452,21 @@ pub struct JsDiagnostic {
     pub source: Option<String>,
 }
 
+/// Diagnostic reported by a plugin with setDiagnostics
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct PluginDiagnostic {
+    /// Diagnostic message
+    pub message: String,
+    /// Severity: 1=Error, 2=Warning, 3=Info, 4=Hint (default: Error)
+    #[serde(default)]
+    #[ts(optional)]
+    pub severity: Option<u8>,
+    /// Range in the document
+    pub range: JsRange,
+}
+
 /// Options for createVirtualBuffer
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(deny_unknown_fields)]
@@ -4636,6 +4780,7 @@ mod fromjs_impls {
         ViewTokenStyle,
         LayoutHints,
         CompositeHunk,
+        PluginDiagnostic,
         LanguagePackConfig,
         LspServerPackConfig,
         ProcessLimitsPackConfig,
@@ -4827,6 +4972,18 @@ mod fromjs_impls {
             assert_eq!(got.new_count, 4);
         }
 
+        #[test]
+        fn plugin_diagnostic_decodes_range_and_severity() {
+            let got: PluginDiagnostic = eval_as(
+                "({message: 'too long', severity: 2, \
+                  range: {start: {line: 0, character: 50}, end: {line: 0, character: 61}}})",
+            );
+            assert_eq!(got.message, "too long");
+            assert_eq!(got.severity, Some(2));
+            assert_eq!(got.range.start.character, 50);
+            assert_eq!(got.range.end.character, 61);
+        }
+
         #[test]
         fn language_pack_config_decodes_comment_prefix_and_tab_size() {
             let got: LanguagePackConfig =
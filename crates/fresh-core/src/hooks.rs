@@ -206,6 +206,30 @@ pub enum HookArgs {
         locations: Vec<LspLocation>,
     },
 
+    /// LSP call- or type-hierarchy items resolved at the cursor
+    LspHierarchy {
+        /// "incoming", "outgoing", "supertypes" or "subtypes"
+        direction: String,
+        /// Language of the requesting buffer; pass it to `sendLspRequest`
+        /// when expanding an item
+        language: String,
+        /// `CallHierarchyItem`s or `TypeHierarchyItem`s exactly as the server
+        /// sent them
+        items: Vec<serde_json::Value>,
+    },
+
+    /// Multi-file LSP edit held back for review before it is applied
+    WorkspaceEditPreview {
+        /// Pass to `applyWorkspaceEditPreview` / `cancelWorkspaceEditPreview`
+        id: u64,
+        /// What the edit does, e.g. "Rename to `bar`"
+        title: String,
+        /// Every change of the edit, in the order it would be applied
+        changes: Vec<WorkspaceEditChange>,
+        /// Content of each file with text changes, as its edits see it
+        texts: HashMap<String, String>,
+    },
+
     /// View transform request
     ViewTransformRequest {
         buffer_id: BufferId,
@@ -309,6 +333,28 @@ pub enum HookArgs {
         user_dismissed: bool,
     },
 
+    /// A debug session stopped (breakpoint, step, pause, exception)
+    DebugStopped {
+        /// DAP stop reason: "breakpoint", "step", "pause", "exception", ...
+        reason: String,
+        /// Thread that stopped
+        thread_id: i64,
+        /// Call stack of the stopped thread, innermost frame first
+        frames: Vec<DebugStackFrame>,
+    },
+
+    /// A stopped debug session resumed
+    DebugContinued {
+        /// Thread that resumed, if the adapter reported one
+        thread_id: Option<i64>,
+    },
+
+    /// A debug session ended (debuggee exited, adapter quit, or user stopped it)
+    DebugTerminated {
+        /// Debuggee exit code, when the adapter reported one
+        exit_code: Option<i64>,
+    },
+
     /// User selected an action from an action popup
     ActionPopupResult {
         /// The popup ID
@@ -487,6 +533,21 @@ pub enum HookArgs {
     },
 }
 
+/// A stack frame reported by the DebugStopped hook
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DebugStackFrame {
+    /// Adapter frame id (pass to `scopes` / `evaluate` requests)
+    pub id: i64,
+    /// Function or frame name
+    pub name: String,
+    /// Source file, when the frame has one
+    pub path: Option<PathBuf>,
+    /// Line number (1-based)
+    pub line: i64,
+    /// Column number (1-based)
+    pub column: i64,
+}
+
 /// Information about a single line for the LinesChanged hook
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct LineInfo {
@@ -511,6 +572,23 @@ pub struct LspLocation {
     pub column: u32,
 }
 
+/// One change of a workspace edit under preview
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WorkspaceEditChange {
+    /// Index of the change, used to include or exclude it
+    pub id: usize,
+    /// "edit", "create", "rename" or "delete"
+    pub kind: String,
+    /// File the change applies to
+    pub path: String,
+    /// Target of a rename
+    pub new_path: Option<String>,
+    /// Range an edit replaces, in UTF-16 code units as LSP sends it
+    pub range: Option<lsp_types::Range>,
+    /// Text an edit writes over `range`
+    pub new_text: String,
+}
+
 /// Type for hook callbacks
 pub type HookCallback = Box<dyn Fn(&HookArgs) -> bool + Send + Sync>;
 
@@ -0,0 +1,371 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  key as widgetKey,
+  raw,
+  tree,
+  treeNode,
+  type TreeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+import { getRelativePath } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+/**
+ * Call Hierarchy Plugin
+ *
+ * Incoming/outgoing calls and supertypes/subtypes as a tree in the
+ * Utility Dock. The editor resolves the symbol under the cursor
+ * (`textDocument/prepare{Call,Type}Hierarchy`) and hands the items to
+ * the `lsp_hierarchy` hook; each node's children are fetched the first
+ * time it is expanded, through `editor.sendLspRequest`:
+ *
+ *   incoming   → callHierarchy/incomingCalls
+ *   outgoing   → callHierarchy/outgoingCalls
+ *   supertypes → typeHierarchy/supertypes
+ *   subtypes   → typeHierarchy/subtypes
+ *
+ * Enter on a node jumps to the call site (incoming/outgoing) or to the
+ * type's declaration (supertypes/subtypes).
+ */
+
+// =============================================================================
+// Types
+// =============================================================================
+
+type Direction = HookEventMap["lsp_hierarchy"]["direction"];
+
+interface LspPosition {
+  line: number;
+  character: number;
+}
+
+interface LspRange {
+  start: LspPosition;
+  end: LspPosition;
+}
+
+/** `CallHierarchyItem` / `TypeHierarchyItem` — only the fields we read.
+ *  The rest (notably `data`) must survive untouched, since the item is
+ *  sent back verbatim to expand it. */
+interface HierarchyItem {
+  name: string;
+  detail?: string;
+  uri: string;
+  range: LspRange;
+  selectionRange: LspRange;
+}
+
+interface HierarchyNode {
+  key: string;
+  item: HierarchyItem;
+  depth: number;
+  /** Where Enter jumps to. */
+  site: { uri: string; position: LspPosition };
+  /** `null` until the node is first expanded. */
+  children: HierarchyNode[] | null;
+  loading: boolean;
+}
+
+interface PanelState {
+  bufferId: number;
+  splitId: number;
+  sourceSplitId: number;
+  widget: WidgetPanel;
+  direction: Direction;
+  language: string;
+  roots: HierarchyNode[];
+}
+
+let panel: PanelState | null = null;
+
+const TREE_KEY = "hierarchy";
+
+const EXPAND_METHODS: Record<Direction, string> = {
+  incoming: "callHierarchy/incomingCalls",
+  outgoing: "callHierarchy/outgoingCalls",
+  supertypes: "typeHierarchy/supertypes",
+  subtypes: "typeHierarchy/subtypes",
+};
+
+const COLORS = {
+  title: "syntax.keyword" as OverlayColorSpec,
+  name: "syntax.function" as OverlayColorSpec,
+  location: "editor.line_number_fg" as OverlayColorSpec,
+};
+
+// =============================================================================
+// Mode
+// =============================================================================
+
+editor.defineMode(
+  "call-hierarchy",
+  [
+    ["Up", "call_hierarchy_up"],
+    ["Down", "call_hierarchy_down"],
+    ["k", "call_hierarchy_up"],
+    ["j", "call_hierarchy_down"],
+    ["PageUp", "call_hierarchy_page_up"],
+    ["PageDown", "call_hierarchy_page_down"],
+    ["Left", "call_hierarchy_left"],
+    ["Right", "call_hierarchy_right"],
+    ["Return", "call_hierarchy_enter"],
+    ["q", "call_hierarchy_close"],
+    ["Escape", "call_hierarchy_close"],
+  ],
+  true, // read-only
+);
+
+// =============================================================================
+// Model
+// =============================================================================
+
+function rootNode(item: HierarchyItem, index: number): HierarchyNode {
+  return {
+    key: `r${index}`,
+    item,
+    depth: 0,
+    site: { uri: item.uri, position: item.selectionRange.start },
+    children: null,
+    loading: false,
+  };
+}
+
+/** Turn one expansion result into child nodes of `parent`. */
+function childNodes(
+  direction: Direction,
+  parent: HierarchyNode,
+  result: unknown,
+): HierarchyNode[] {
+  if (!Array.isArray(result)) return [];
+  return result.map((entry, index) => {
+    let item: HierarchyItem;
+    let site: HierarchyNode["site"];
+    if (direction === "incoming") {
+      // `fromRanges` are the call sites inside the caller.
+      const call = entry as { from: HierarchyItem; fromRanges: LspRange[] };
+      item = call.from;
+      const range = call.fromRanges[0] ?? item.selectionRange;
+      site = { uri: item.uri, position: range.start };
+    } else if (direction === "outgoing") {
+      // `fromRanges` are the call sites inside the *parent*.
+      const call = entry as { to: HierarchyItem; fromRanges: LspRange[] };
+      item = call.to;
+      const range = call.fromRanges[0];
+      site = range
+        ? { uri: parent.item.uri, position: range.start }
+        : { uri: item.uri, position: item.selectionRange.start };
+    } else {
+      item = entry as HierarchyItem;
+      site = { uri: item.uri, position: item.selectionRange.start };
+    }
+    return {
+      key: `${parent.key}/${index}`,
+      item,
+      depth: parent.depth + 1,
+      site,
+      children: null,
+      loading: false,
+    };
+  });
+}
+
+/** Depth-first list of every loaded node; the host hides collapsed ones. */
+function flatten(nodes: HierarchyNode[], out: HierarchyNode[] = []): HierarchyNode[] {
+  for (const node of nodes) {
+    out.push(node);
+    if (node.children) flatten(node.children, out);
+  }
+  return out;
+}
+
+function findNode(key: string): HierarchyNode | undefined {
+  return panel ? flatten(panel.roots).find((n) => n.key === key) : undefined;
+}
+
+async function loadChildren(node: HierarchyNode): Promise<void> {
+  if (!panel || node.children !== null || node.loading) return;
+  const { direction, language } = panel;
+  const method = EXPAND_METHODS[direction];
+  node.loading = true;
+  render();
+  try {
+    const result = await editor.sendLspRequest(language, method, {
+      item: node.item,
+    });
+    node.children = childNodes(direction, node, result);
+  } catch (e) {
+    node.children = [];
+    editor.setStatus(editor.t("status.request_failed", { method, error: String(e) }));
+  }
+  node.loading = false;
+  render();
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function nodeEntry(node: HierarchyNode): TextPropertyEntry {
+  const { name, detail, uri, selectionRange } = node.item;
+  const file = getRelativePath(editor, editor.fileUriToPath(uri));
+  const label = detail ? `${name} ${detail}` : name;
+  const location = `  ${file}:${selectionRange.start.line + 1}`;
+  const suffix = node.loading ? `  ${editor.t("panel.loading")}` : "";
+  const text = label + location + suffix;
+  return {
+    text,
+    inlineOverlays: [
+      { start: 0, end: name.length, style: { fg: COLORS.name } },
+      { start: label.length, end: text.length, style: { fg: COLORS.location } },
+    ],
+  };
+}
+
+function render(): void {
+  if (!panel) return;
+  const nodes = flatten(panel.roots);
+  const treeNodes: TreeNode[] = nodes.map((node) =>
+    treeNode(nodeEntry(node), {
+      depth: node.depth,
+      // Unloaded nodes get a disclosure glyph so they can be expanded.
+      hasChildren: node.children === null || node.children.length > 0,
+    })
+  );
+  const title = editor.t(`panel.title_${panel.direction}`, {
+    name: panel.roots[0]?.item.name ?? "",
+  });
+  const vp = editor.getViewport();
+  const visibleRows = Math.max(3, (vp && vp.height > 0 ? vp.height : 30) - 2);
+
+  panel.widget.set(
+    col(
+      raw([{ text: `${title}\n`, style: { fg: COLORS.title, bold: true } }], "title"),
+      tree({
+        nodes: treeNodes,
+        itemKeys: nodes.map((n) => n.key),
+        selectedIndex: 0,
+        visibleRows,
+        expandedKeys: panel.roots.map((n) => n.key),
+        key: TREE_KEY,
+      }),
+    ),
+  );
+}
+
+// =============================================================================
+// Panel lifecycle
+// =============================================================================
+
+editor.on("lsp_hierarchy", async (data) => {
+  const roots = (data.items as HierarchyItem[]).map(rootNode);
+
+  if (panel) {
+    panel.direction = data.direction;
+    panel.language = data.language;
+    panel.roots = roots;
+    // A fresh tree: drop host-side expansion state of the old one.
+    panel.widget.unmount();
+  } else {
+    const sourceSplitId = editor.getActiveSplitId();
+    try {
+      const result = await editor.createVirtualBufferInSplit({
+        name: "*Hierarchy*",
+        mode: "call-hierarchy",
+        readOnly: true,
+        entries: [],
+        ratio: 0.6,
+        panelId: "call-hierarchy-panel",
+        role: "utility_dock",
+        showLineNumbers: false,
+        showCursors: false,
+        editingDisabled: true,
+      });
+      panel = {
+        bufferId: result.bufferId,
+        splitId: result.splitId ?? editor.getActiveSplitId(),
+        sourceSplitId,
+        widget: new WidgetPanel(result.bufferId),
+        direction: data.direction,
+        language: data.language,
+        roots,
+      };
+    } catch (e) {
+      editor.setStatus(editor.t("status.failed_open_panel"));
+      editor.debug(`call_hierarchy: createVirtualBufferInSplit failed: ${e}`);
+      return;
+    }
+  }
+
+  render();
+  // Roots start expanded, so fetch their children right away.
+  for (const root of roots) {
+    void loadChildren(root);
+  }
+});
+
+editor.on("buffer_closed", (args) => {
+  if (panel && args.buffer_id === panel.bufferId) {
+    panel.widget.unmount();
+    panel = null;
+  }
+});
+
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widget.id()) return;
+  if (args.widget_key !== TREE_KEY) return;
+
+  if (args.event_type === "expand") {
+    const payload = args.payload as { key?: string; expanded?: boolean } | undefined;
+    if (payload?.expanded && typeof payload.key === "string") {
+      const node = findNode(payload.key);
+      if (node) void loadChildren(node);
+    }
+    return;
+  }
+
+  if (args.event_type === "activate") {
+    const nodeKey = (args.payload as { key?: string } | undefined)?.key;
+    const node = typeof nodeKey === "string" ? findNode(nodeKey) : undefined;
+    if (!node) return;
+    const { uri, position } = node.site;
+    editor.openFileInSplit(
+      panel.sourceSplitId,
+      editor.fileUriToPath(uri),
+      position.line + 1,
+      position.character + 1,
+    );
+  }
+});
+
+// =============================================================================
+// Key handlers
+// =============================================================================
+
+function dispatch(name: string): void {
+  panel?.widget.command(widgetKey(name));
+}
+
+registerHandler("call_hierarchy_up", () => dispatch("Up"));
+registerHandler("call_hierarchy_down", () => dispatch("Down"));
+registerHandler("call_hierarchy_page_up", () => dispatch("PageUp"));
+registerHandler("call_hierarchy_page_down", () => dispatch("PageDown"));
+registerHandler("call_hierarchy_left", () => dispatch("Left"));
+registerHandler("call_hierarchy_right", () => dispatch("Right"));
+registerHandler("call_hierarchy_enter", () => dispatch("Enter"));
+
+function call_hierarchy_close(): void {
+  if (!panel) return;
+  const { bufferId, splitId, sourceSplitId, widget } = panel;
+  panel = null;
+  widget.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+}
+registerHandler("call_hierarchy_close", call_hierarchy_close);
+
+editor.debug("Call Hierarchy plugin loaded");
@@ -0,0 +1,317 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+const editor = getEditor();
+
+/**
+ * Debugger Panels Plugin
+ *
+ * Call Stack and Variables views for the built-in Debug Adapter Protocol
+ * client. The editor owns the session (breakpoints, stepping, the current
+ * frame highlight); this plugin only renders what the adapter reports:
+ *
+ *   * `debug_stopped` carries the call stack of the stopped thread.
+ *   * `editor.debugRequest("scopes" | "variables", ...)` fetches the
+ *     variables of the selected frame.
+ *
+ * Both views live in one buffer group ("call stack | variables") so they
+ * appear as a single tab. Return on a frame jumps to its source and shows
+ * that frame's variables.
+ */
+
+// =============================================================================
+// State
+// =============================================================================
+
+type StackFrame = HookEventMap["debug_stopped"]["frames"][number];
+
+interface DebugPanelState {
+  isOpen: boolean;
+  groupId: number | null;
+  stackBufferId: number | null;
+  variablesBufferId: number | null;
+  /** Frames of the last stop, innermost first. Empty while running. */
+  frames: StackFrame[];
+  /** Index into `frames` whose variables are shown. */
+  selectedFrame: number;
+  running: boolean;
+}
+
+const state: DebugPanelState = {
+  isOpen: false,
+  groupId: null,
+  stackBufferId: null,
+  variablesBufferId: null,
+  frames: [],
+  selectedFrame: 0,
+  running: false,
+};
+
+/** Scopes flagged `expensive` (e.g. globals) are not expanded. */
+const MAX_VARIABLES_PER_SCOPE = 200;
+
+const GROUP_LAYOUT = JSON.stringify({
+  type: "split",
+  direction: "h",
+  ratio: 0.45,
+  first: { type: "scrollable", id: "stack" },
+  second: { type: "scrollable", id: "variables" },
+});
+
+const COLORS = {
+  frame: "syntax.function" as OverlayColorSpec,
+  location: "editor.line_number_fg" as OverlayColorSpec,
+  selected: "editor.selection_bg" as OverlayColorSpec,
+  scope: "syntax.type" as OverlayColorSpec,
+  name: "syntax.variable" as OverlayColorSpec,
+  value: "syntax.string" as OverlayColorSpec,
+  muted: "editor.line_number_fg" as OverlayColorSpec,
+};
+
+editor.defineMode(
+  "debug-panels",
+  [
+    ["k", "move_up"],
+    ["j", "move_down"],
+    ["Up", "move_up"],
+    ["Down", "move_down"],
+    ["Return", "debug_panels_select_frame"],
+    ["Tab", "debug_panels_tab"],
+    ["q", "debug_panels_close"],
+  ],
+  true, // read-only
+  false, // allow_text_input
+  true, // inherit Normal-context bindings (step/continue keys keep working)
+);
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+interface DapVariable {
+  name: string;
+  value: string;
+  type?: string;
+}
+
+interface DapScope {
+  name: string;
+  variablesReference: number;
+  expensive?: boolean;
+}
+
+function message(text: string): TextPropertyEntry[] {
+  return [{ text: `${text}\n`, style: { fg: COLORS.muted, italic: true } }];
+}
+
+function renderStack(): void {
+  if (state.groupId === null) return;
+  if (state.running || state.frames.length === 0) {
+    const text = state.running
+      ? editor.t("panel.running")
+      : editor.t("panel.not_stopped");
+    editor.setPanelContent(state.groupId, "stack", message(text));
+    return;
+  }
+
+  const entries: TextPropertyEntry[] = state.frames.map((frame, index) => {
+    const location = frame.path
+      ? `${editor.pathBasename(frame.path)}:${frame.line}`
+      : editor.t("panel.no_source");
+    const marker = index === state.selectedFrame ? "▶ " : "  ";
+    const text = `${marker}${frame.name}  ${location}\n`;
+    const nameStart = marker.length;
+    const nameEnd = nameStart + frame.name.length;
+    return {
+      text,
+      properties: { frameIndex: index },
+      style: index === state.selectedFrame ? { bg: COLORS.selected } : undefined,
+      inlineOverlays: [
+        { start: nameStart, end: nameEnd, style: { fg: COLORS.frame } },
+        { start: nameEnd + 2, end: text.length - 1, style: { fg: COLORS.location } },
+      ],
+    };
+  });
+  editor.setPanelContent(state.groupId, "stack", entries);
+}
+
+async function renderVariables(): Promise<void> {
+  if (state.groupId === null) return;
+  const frame = state.frames[state.selectedFrame];
+  if (state.running || !frame) {
+    editor.setPanelContent(state.groupId, "variables", []);
+    return;
+  }
+
+  const entries: TextPropertyEntry[] = [];
+  try {
+    const scopes = (await editor.debugRequest("scopes", {
+      frameId: frame.id,
+    })) as { scopes: DapScope[] };
+    for (const scope of scopes.scopes) {
+      entries.push({
+        text: `${scope.name}\n`,
+        style: { fg: COLORS.scope, bold: true },
+      });
+      if (scope.expensive) {
+        entries.push(...message(`  ${editor.t("panel.expensive_scope")}`));
+        continue;
+      }
+      const result = (await editor.debugRequest("variables", {
+        variablesReference: scope.variablesReference,
+        count: MAX_VARIABLES_PER_SCOPE,
+      })) as { variables: DapVariable[] };
+      for (const variable of result.variables) {
+        const type = variable.type ? `: ${variable.type}` : "";
+        const prefix = `  ${variable.name}${type} = `;
+        entries.push({
+          text: `${prefix}${variable.value}\n`,
+          inlineOverlays: [
+            { start: 2, end: 2 + variable.name.length, style: { fg: COLORS.name } },
+            {
+              start: prefix.length,
+              end: prefix.length + variable.value.length,
+              style: { fg: COLORS.value },
+            },
+          ],
+        });
+      }
+    }
+  } catch (e) {
+    // The session may have moved on (resumed or ended) while we were
+    // waiting; a later stop re-renders with fresh data.
+    entries.push(...message(editor.t("panel.variables_failed", { error: String(e) })));
+  }
+
+  // Stale if the user picked another frame or the program resumed.
+  if (state.groupId === null || state.frames[state.selectedFrame] !== frame) return;
+  editor.setPanelContent(state.groupId, "variables", entries);
+}
+
+function render(): void {
+  renderStack();
+  void renderVariables();
+}
+
+// =============================================================================
+// Hooks
+// =============================================================================
+
+editor.on("debug_stopped", (data) => {
+  state.frames = data.frames;
+  state.selectedFrame = 0;
+  state.running = false;
+  render();
+});
+
+editor.on("debug_continued", () => {
+  state.frames = [];
+  state.running = true;
+  render();
+});
+
+editor.on("debug_terminated", () => {
+  state.frames = [];
+  state.running = false;
+  render();
+});
+
+function on_debug_panels_buffer_closed(data: { buffer_id: number }): void {
+  if (
+    data.buffer_id === state.stackBufferId ||
+    data.buffer_id === state.variablesBufferId
+  ) {
+    debug_panels_cleanup();
+  }
+}
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+async function debug_panels_open(): Promise<void> {
+  if (state.isOpen) {
+    if (state.groupId !== null) {
+      editor.focusBufferGroupPanel(state.groupId, "stack");
+    }
+    return;
+  }
+
+  // `createBufferGroup` is a runtime-only binding; see git_log.ts.
+  const group = await (editor as any).createBufferGroup(
+    editor.t("panel.title"),
+    "debug-panels",
+    GROUP_LAYOUT
+  );
+  state.groupId = group.groupId as number;
+  state.stackBufferId = (group.panels["stack"] as number | undefined) ?? null;
+  state.variablesBufferId =
+    (group.panels["variables"] as number | undefined) ?? null;
+  state.isOpen = true;
+  if (state.stackBufferId !== null) {
+    editor.setBufferShowCursors(state.stackBufferId, true);
+  }
+  editor.on("buffer_closed", on_debug_panels_buffer_closed);
+  render();
+}
+registerHandler("debug_panels_open", debug_panels_open);
+
+function debug_panels_cleanup(): void {
+  if (!state.isOpen) return;
+  editor.off("buffer_closed", on_debug_panels_buffer_closed);
+  state.isOpen = false;
+  state.groupId = null;
+  state.stackBufferId = null;
+  state.variablesBufferId = null;
+}
+
+function debug_panels_close(): void {
+  if (!state.isOpen) return;
+  const groupId = state.groupId;
+  debug_panels_cleanup();
+  if (groupId !== null) {
+    editor.closeBufferGroup(groupId);
+  }
+}
+registerHandler("debug_panels_close", debug_panels_close);
+
+/** Return on a call-stack row: show that frame's source and variables. */
+function debug_panels_select_frame(): void {
+  if (state.stackBufferId === null) return;
+  const props = editor.getTextPropertiesAtCursor(state.stackBufferId);
+  const index = props.find((p) => typeof p.frameIndex === "number")
+    ?.frameIndex as number | undefined;
+  const frame = index !== undefined ? state.frames[index] : undefined;
+  if (index === undefined || !frame) return;
+
+  state.selectedFrame = index;
+  render();
+  if (frame.path) {
+    editor.openFile(frame.path, frame.line, frame.column);
+  } else {
+    editor.setStatus(editor.t("status.no_source", { name: frame.name }));
+  }
+}
+registerHandler("debug_panels_select_frame", debug_panels_select_frame);
+
+function debug_panels_tab(): void {
+  if (state.groupId === null) return;
+  const active = editor.getActiveBufferId();
+  const target = active === state.stackBufferId ? "variables" : "stack";
+  editor.focusBufferGroupPanel(state.groupId, target);
+}
+registerHandler("debug_panels_tab", debug_panels_tab);
+
+editor.registerCommand(
+  "%cmd.show_debug_panels",
+  "%cmd.show_debug_panels_desc",
+  "debug_panels_open",
+  null
+);
+editor.registerCommand(
+  "%cmd.close_debug_panels",
+  "%cmd.close_debug_panels_desc",
+  "debug_panels_close",
+  null
+);
+
+editor.debug("Debugger panels plugin loaded");
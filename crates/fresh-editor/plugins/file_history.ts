@@ -0,0 +1,492 @@
+/// <reference path="./lib/fresh.d.ts" />
+import { col, key as widgetKey, list, raw, WidgetPanel } from "./lib/widgets.ts";
+import { getRelativePath } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+/**
+ * File History Plugin
+ *
+ * The local history of the active file in a sidebar left of the editor:
+ * every save that changed the file is kept as a version (see the
+ * `local_history_*` settings), newest first. Enter opens a side-by-side
+ * diff of the selected version against the buffer, and `r` restores it
+ * — as a single edit, so undo brings the buffer back.
+ */
+
+// =============================================================================
+// Types
+// =============================================================================
+
+type Version = Awaited<ReturnType<typeof editor.getFileHistory>>[number];
+
+interface PanelState {
+  bufferId: number;
+  splitId: number;
+  /** Split the file is shown in; diffs and restores happen there. */
+  sourceSplitId: number;
+  widget: WidgetPanel;
+  /** File whose history is shown, or `null` when there is none. */
+  source: { bufferId: number; path: string } | null;
+  /** Versions as last fetched, or `null` before they arrive. */
+  versions: Version[] | null;
+  /** Id of the selected version. */
+  selected: number | null;
+  /** Bumped on every refresh so stale answers are dropped. */
+  generation: number;
+}
+
+interface DiffState {
+  compositeBufferId: number;
+  oldBufferId: number;
+  newBufferId: number;
+  /** File buffer the diff was opened from, and the split it was in. */
+  sourceBufferId: number;
+  splitId: number;
+  version: Version;
+}
+
+let panel: PanelState | null = null;
+let diff: DiffState | null = null;
+
+const LIST_KEY = "file-history";
+
+const COLORS = {
+  title: "syntax.keyword" as OverlayColorSpec,
+  details: "editor.line_number_fg" as OverlayColorSpec,
+  help: "editor.line_number_fg" as OverlayColorSpec,
+};
+
+// =============================================================================
+// Modes
+// =============================================================================
+
+editor.defineMode(
+  "file-history",
+  [
+    ["Up", "file_history_up"],
+    ["Down", "file_history_down"],
+    ["PageUp", "file_history_page_up"],
+    ["PageDown", "file_history_page_down"],
+    ["Return", "file_history_enter"],
+    ["r", "file_history_restore"],
+    ["q", "file_history_close"],
+    ["Escape", "file_history_escape"],
+  ],
+  true, // read-only
+);
+
+editor.defineMode(
+  "file-history-diff",
+  [
+    ["n", "file_history_diff_next_hunk"],
+    ["p", "file_history_diff_prev_hunk"],
+    ["r", "file_history_diff_restore"],
+    ["q", "file_history_diff_close"],
+    ["Escape", "file_history_diff_close"],
+  ],
+  true, // read-only
+);
+
+// =============================================================================
+// Formatting
+// =============================================================================
+
+function formatDate(timestamp: number): string {
+  const date = new Date(timestamp);
+  const pad = (n: number) => String(n).padStart(2, "0");
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())} ` +
+    `${pad(date.getHours())}:${pad(date.getMinutes())}:${pad(date.getSeconds())}`;
+}
+
+function formatAge(timestamp: number): string {
+  const minutes = Math.floor((Date.now() - timestamp) / 60_000);
+  if (minutes < 1) return editor.t("age.just_now");
+  if (minutes < 60) return editor.t("age.minutes", { count: String(minutes) });
+  const hours = Math.floor(minutes / 60);
+  if (hours < 24) return editor.t("age.hours", { count: String(hours) });
+  return editor.t("age.days", { count: String(Math.floor(hours / 24)) });
+}
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+// =============================================================================
+// Diff
+// =============================================================================
+
+/** Split content into lines, without the empty "line" after a final newline. */
+function contentLines(content: string): string[] {
+  const lines = content.split("\n");
+  if (lines.length > 1 && lines[lines.length - 1] === "") lines.pop();
+  return lines;
+}
+
+function contentToEntries(lines: string[]): TextPropertyEntry[] {
+  return lines.map((line, idx) => ({
+    text: idx < lines.length - 1 ? `${line}\n` : line,
+  }));
+}
+
+async function openDiff(version: Version): Promise<void> {
+  if (!panel?.source) return;
+  const source = panel.source;
+  const oldContent = await editor.readFileHistoryVersion(source.path, version.id);
+  if (oldContent === null) {
+    editor.setStatus(editor.t("status.version_unavailable"));
+    return;
+  }
+  const newContent = await editor.getBufferText(
+    source.bufferId,
+    0,
+    editor.getBufferLength(source.bufferId),
+  );
+  const hunks = await editor.diffLines(oldContent, newContent);
+  if (!panel?.source) return;
+  closeDiff();
+
+  const file = getRelativePath(editor, source.path);
+  const oldLines = contentLines(oldContent);
+  const newLines = contentLines(newContent);
+  const oldResult = await editor.createVirtualBuffer({
+    name: `*History:${file}@${version.id}*`,
+    mode: "normal",
+    readOnly: true,
+    entries: contentToEntries(oldLines),
+    showLineNumbers: true,
+    editingDisabled: true,
+    hiddenFromTabs: true,
+  });
+  const newResult = await editor.createVirtualBuffer({
+    name: `*Current:${file}*`,
+    mode: "normal",
+    readOnly: true,
+    entries: contentToEntries(newLines),
+    showLineNumbers: true,
+    editingDisabled: true,
+    hiddenFromTabs: true,
+  });
+
+  // The diff replaces the file in its split.
+  editor.focusSplit(panel.sourceSplitId);
+  const compositeBufferId = await editor.createCompositeBuffer({
+    name: `*File History: ${file}*`,
+    mode: "file-history-diff",
+    layout: {
+      type: "side-by-side",
+      ratios: [0.5, 0.5],
+      showSeparator: true,
+    },
+    sources: [
+      {
+        bufferId: oldResult.bufferId,
+        label: editor.t("diff.old_label", { time: formatDate(version.timestamp) }),
+        editable: false,
+        style: { gutterStyle: "diff-markers" },
+      },
+      {
+        bufferId: newResult.bufferId,
+        label: editor.t("diff.new_label"),
+        editable: false,
+        style: { gutterStyle: "diff-markers" },
+      },
+    ],
+    hunks: hunks.length > 0 ? hunks : null,
+    initialFocusHunk: hunks.length > 0 ? 0 : undefined,
+  });
+  diff = {
+    compositeBufferId,
+    oldBufferId: oldResult.bufferId,
+    newBufferId: newResult.bufferId,
+    sourceBufferId: source.bufferId,
+    splitId: panel.sourceSplitId,
+    version,
+  };
+  editor.showBuffer(compositeBufferId);
+  if (hunks.length === 0) editor.setStatus(editor.t("status.no_changes"));
+}
+
+/** Close the diff view, putting its file back in the split; that split
+ *  is focused afterwards. */
+function closeDiff(): void {
+  if (!diff) return;
+  const { compositeBufferId, oldBufferId, newBufferId, sourceBufferId, splitId } = diff;
+  diff = null;
+  editor.focusSplit(splitId);
+  editor.showBuffer(sourceBufferId);
+  editor.closeCompositeBuffer(compositeBufferId);
+  editor.closeBuffer(oldBufferId);
+  editor.closeBuffer(newBufferId);
+}
+
+// =============================================================================
+// Restore
+// =============================================================================
+
+/** Replace the content of `bufferId`, shown in the active split, with `version`. */
+function restore(bufferId: number, version: Version): void {
+  editor.restoreFileHistoryVersion(bufferId, version.id);
+  editor.setStatus(editor.t("status.restored", { time: formatDate(version.timestamp) }));
+}
+
+function restoreSelected(): void {
+  if (!panel?.source || !panel.versions) return;
+  const version = panel.versions.find((v) => v.id === panel!.selected);
+  if (!version) return;
+  closeDiff();
+  // The restore applies to the active buffer, so hop over to the file.
+  editor.focusSplit(panel.sourceSplitId);
+  restore(panel.source.bufferId, version);
+  editor.focusSplit(panel.splitId);
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+/** `● 5 min ago  2024-05-01 14:03:22  1.2 KB`; the newest version is ●. */
+function versionEntry(version: Version, latest: boolean): TextPropertyEntry {
+  const age = formatAge(version.timestamp);
+  const details = `${formatDate(version.timestamp)}  ${formatSize(version.size)}`;
+  const text = `${latest ? "●" : "○"} ${age}  ${details}`;
+  const detailsStart = text.length - details.length;
+  return {
+    text,
+    inlineOverlays: [{ start: detailsStart, end: text.length, style: { fg: COLORS.details } }],
+  };
+}
+
+function render(): void {
+  if (!panel) return;
+  const { source, versions } = panel;
+
+  const file = source ? getRelativePath(editor, source.path) : "";
+  const header: TextPropertyEntry[] = [
+    {
+      text: `${editor.t("panel.title", { file })}\n`,
+      style: { fg: COLORS.title, bold: true },
+    },
+  ];
+
+  let message: string | null = null;
+  if (!source) {
+    message = editor.t("panel.no_file");
+  } else if (!versions) {
+    message = editor.t("panel.loading");
+  } else if (versions.length === 0) {
+    message = editor.t("panel.empty");
+  }
+  if (message !== null || !versions) {
+    panel.widget.set(col(raw(header, "header"), raw([{ text: message ?? "" }], "empty")));
+    return;
+  }
+
+  const vp = editor.getViewport();
+  const height = vp && vp.height > 0 ? vp.height : 30;
+  const selectedIndex = Math.max(0, versions.findIndex((v) => v.id === panel!.selected));
+  panel.widget.set(
+    col(
+      raw(header, "header"),
+      raw([{ text: `${editor.t("panel.help")}\n`, style: { fg: COLORS.help } }], "help"),
+      list({
+        items: versions.map((version, i) => versionEntry(version, i === 0)),
+        itemKeys: versions.map((version) => String(version.id)),
+        selectedIndex,
+        visibleRows: Math.max(1, height - 3),
+        key: LIST_KEY,
+      }),
+    ),
+  );
+  // Host-owned after the first render, so push it.
+  panel.widget.setSelectedIndex(LIST_KEY, selectedIndex);
+}
+
+async function refresh(): Promise<void> {
+  if (!panel) return;
+  const generation = ++panel.generation;
+  const source = panel.source;
+  if (!source) {
+    panel.versions = null;
+    render();
+    return;
+  }
+  const versions = await editor.getFileHistory(source.path);
+  if (!panel || panel.generation !== generation) return;
+  panel.versions = versions;
+  if (!versions.some((v) => v.id === panel!.selected)) {
+    panel.selected = versions.length > 0 ? versions[0].id : null;
+  }
+  render();
+}
+
+/** Show the history of `bufferId`, shown in `splitId`, if it is a file.
+ *  Returns whether the source changed. */
+function setSource(bufferId: number, splitId: number): boolean {
+  if (!panel || bufferId === panel.bufferId) return false;
+  const info = editor.getBufferInfo(bufferId);
+  if (!info || info.is_virtual || !info.path) return false;
+  if (panel.source?.bufferId === bufferId) return false;
+  panel.source = { bufferId, path: info.path };
+  panel.sourceSplitId = splitId;
+  panel.versions = null;
+  panel.selected = null;
+  return true;
+}
+
+// =============================================================================
+// Panel lifecycle
+// =============================================================================
+
+async function openFileHistory(): Promise<void> {
+  if (panel) {
+    editor.focusSplit(panel.splitId);
+    return;
+  }
+  const sourceBufferId = editor.getActiveBufferId();
+  const sourceSplitId = editor.getActiveSplitId();
+  try {
+    const result = await editor.createVirtualBufferInSplit({
+      name: "*File History*",
+      mode: "file-history",
+      readOnly: true,
+      entries: [],
+      ratio: 0.35,
+      direction: "vertical",
+      before: true,
+      panelId: "file-history-panel",
+      showLineNumbers: false,
+      showCursors: false,
+      editingDisabled: true,
+    });
+    panel = {
+      bufferId: result.bufferId,
+      splitId: result.splitId ?? editor.getActiveSplitId(),
+      sourceSplitId,
+      widget: new WidgetPanel(result.bufferId),
+      source: null,
+      versions: null,
+      selected: null,
+      generation: 0,
+    };
+  } catch (e) {
+    editor.setStatus(editor.t("status.failed_open_panel"));
+    editor.debug(`file_history: createVirtualBufferInSplit failed: ${e}`);
+    return;
+  }
+  setSource(sourceBufferId, sourceSplitId);
+  render();
+  void refresh();
+}
+
+function closeFileHistory(): void {
+  if (!panel) return;
+  closeDiff();
+  const { bufferId, splitId, sourceSplitId, widget } = panel;
+  panel = null;
+  widget.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+}
+
+function file_history(): void {
+  void openFileHistory();
+}
+registerHandler("file_history", file_history);
+
+editor.registerCommand(
+  "%cmd.file_history",
+  "%cmd.file_history_desc",
+  "file_history",
+  null,
+);
+
+editor.on("buffer_activated", (args) => {
+  if (!panel || diff) return;
+  if (setSource(args.buffer_id, editor.getActiveSplitId())) {
+    render();
+    void refresh();
+  }
+});
+
+editor.on("buffer_closed", (args) => {
+  if (diff && args.buffer_id === diff.compositeBufferId) {
+    // Closed from outside the plugin (e.g. its tab); drop the sources too.
+    const { oldBufferId, newBufferId } = diff;
+    diff = null;
+    editor.closeBuffer(oldBufferId);
+    editor.closeBuffer(newBufferId);
+  }
+  if (!panel) return;
+  if (args.buffer_id === panel.bufferId) {
+    panel.widget.unmount();
+    panel = null;
+  } else if (args.buffer_id === panel.source?.bufferId) {
+    panel.source = null;
+    void refresh();
+  }
+});
+
+editor.on("after_file_save", (args) => {
+  if (panel && args.buffer_id === panel.source?.bufferId) void refresh();
+});
+
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widget.id()) return;
+  if (args.widget_key !== LIST_KEY) return;
+
+  const itemKey = (args.payload as { key?: string } | undefined)?.key;
+  if (typeof itemKey !== "string") return;
+  const id = Number(itemKey);
+  if (args.event_type === "select") {
+    panel.selected = id;
+  } else if (args.event_type === "activate") {
+    panel.selected = id;
+    const version = panel.versions?.find((v) => v.id === id);
+    if (version) void openDiff(version);
+  }
+});
+
+// =============================================================================
+// Key handlers
+// =============================================================================
+
+function dispatch(name: string): void {
+  panel?.widget.command(widgetKey(name));
+}
+
+registerHandler("file_history_up", () => dispatch("Up"));
+registerHandler("file_history_down", () => dispatch("Down"));
+registerHandler("file_history_page_up", () => dispatch("PageUp"));
+registerHandler("file_history_page_down", () => dispatch("PageDown"));
+registerHandler("file_history_enter", () => dispatch("Enter"));
+registerHandler("file_history_restore", restoreSelected);
+registerHandler("file_history_close", closeFileHistory);
+
+registerHandler("file_history_escape", () => {
+  if (panel) editor.focusSplit(panel.sourceSplitId);
+});
+
+registerHandler("file_history_diff_next_hunk", () => {
+  if (diff) editor.compositeNextHunk(diff.compositeBufferId);
+});
+
+registerHandler("file_history_diff_prev_hunk", () => {
+  if (diff) editor.compositePrevHunk(diff.compositeBufferId);
+});
+
+registerHandler("file_history_diff_restore", () => {
+  if (!diff) return;
+  const { sourceBufferId, version } = diff;
+  closeDiff();
+  restore(sourceBufferId, version);
+});
+
+registerHandler("file_history_diff_close", closeDiff);
+
+editor.debug("File History plugin loaded");
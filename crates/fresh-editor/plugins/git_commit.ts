@@ -0,0 +1,457 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+const editor = getEditor();
+
+/**
+ * Git Commit Composer Plugin
+ *
+ * Writes commits from inside Fresh. "Git: Commit" opens the repository's
+ * COMMIT_EDITMSG as a regular buffer, pre-filled with the commit template
+ * (or the last commit's message when amending) and a comment block that
+ * summarises the staged changes. The buffer gets rulers at the subject-line
+ * limits, and Conventional Commit types complete on the first word.
+ *
+ * Ctrl+Enter saves the message and runs `git commit -F`, so hooks run as
+ * usual; when one rejects the commit its output is shown as diagnostics on
+ * the message. Amend, sign-off and fixup are toggled from the composer.
+ *
+ * Everything goes through `spawnProcess` and buffer saves, which follow the
+ * active authority — committing works the same over SSH and in
+ * devcontainers.
+ */
+
+// =============================================================================
+// Types
+// =============================================================================
+
+interface Commit {
+  sha: string;
+  subject: string;
+}
+
+interface ComposerState {
+  root: string;
+  msgPath: string;
+  /** Buffer editing `msgPath`, once it has been opened. */
+  bufferId: number | null;
+  /** `core.commentChar`: lines starting with it are stripped. */
+  commentChar: string;
+  /** Message to start with when the buffer opens. */
+  initialMessage: string;
+  /** `git diff --cached --stat` lines. */
+  staged: string[];
+  head: Commit | null;
+  amend: boolean;
+  signoff: boolean;
+  fixup: Commit | null;
+  committing: boolean;
+}
+
+let composer: ComposerState | null = null;
+
+/** Candidates offered by the fixup prompt, in suggestion order. */
+let fixupChoices: (Commit | null)[] = [];
+
+const MODE = "git-commit-composer";
+const FIXUP_PROMPT = "git-commit-fixup";
+
+/** Subject lines longer than this get a warning. */
+const SUBJECT_LIMIT = 72;
+const RULERS = [50, SUBJECT_LIMIT];
+
+/** Diagnostic sources: hook output and the message checks. */
+const HOOK_SOURCE = "git-commit-hooks";
+const LINT_SOURCE = "git-commit";
+
+// =============================================================================
+// Git helpers
+// =============================================================================
+
+async function git(args: string[], cwd: string): Promise<SpawnResult> {
+  return await editor.spawnProcess("git", args, cwd);
+}
+
+async function gitOutput(args: string[], cwd: string): Promise<string | null> {
+  const result = await git(args, cwd);
+  return result.exit_code === 0 ? result.stdout : null;
+}
+
+function parseCommit(line: string): Commit | null {
+  const tab = line.indexOf("\t");
+  if (tab < 0) return null;
+  return { sha: line.slice(0, tab), subject: line.slice(tab + 1) };
+}
+
+async function headCommit(root: string): Promise<Commit | null> {
+  const out = await gitOutput(["log", "-1", "--format=%h%x09%s"], root);
+  return out ? parseCommit(out.trim()) : null;
+}
+
+// =============================================================================
+// Message buffer
+// =============================================================================
+
+function commentBlock(c: ComposerState): string {
+  const cc = c.commentChar;
+  const flag = (on: boolean) => on ? editor.t("block.on") : editor.t("block.off");
+  const lines = [
+    editor.t("block.header", { c: cc }),
+    editor.t("block.keys"),
+    "",
+    editor.t("block.options", {
+      amend: flag(c.amend),
+      signoff: flag(c.signoff),
+      fixup: c.fixup ? `${c.fixup.sha} ${c.fixup.subject}` : editor.t("block.none"),
+    }),
+  ];
+  if (c.amend && c.head) {
+    lines.push(editor.t("block.amending", { commit: `${c.head.sha} ${c.head.subject}` }));
+  }
+  lines.push("");
+  if (c.staged.length > 0) {
+    lines.push(editor.t("block.staged"));
+    for (const line of c.staged) lines.push(line);
+  } else {
+    lines.push(editor.t("block.nothing_staged"));
+  }
+  return lines.map((l) => (l ? `${cc} ${l}` : cc)).join("\n") + "\n";
+}
+
+/** The text before the comment block (all of `text` if it was removed). */
+function textBeforeCommentBlock(c: ComposerState, text: string): string {
+  const header = `${c.commentChar} ${editor.t("block.header", { c: c.commentChar })}`;
+  const at = text.indexOf(header);
+  return at < 0 ? text : text.slice(0, at);
+}
+
+async function bufferText(bufferId: number): Promise<string> {
+  return await editor.getBufferText(bufferId, 0, editor.getBufferLength(bufferId));
+}
+
+/** Buffer positions are byte offsets. */
+function utf8Length(s: string): number {
+  let n = 0;
+  for (const ch of s) {
+    const code = ch.codePointAt(0)!;
+    n += code < 0x80 ? 1 : code < 0x800 ? 2 : code < 0x10000 ? 3 : 4;
+  }
+  return n;
+}
+
+/** The message as git will see it: comment lines removed, trimmed. */
+function messageOf(c: ComposerState, text: string): string {
+  return text
+    .split("\n")
+    .filter((line) => !line.startsWith(c.commentChar))
+    .join("\n")
+    .trim();
+}
+
+/** Re-render the comment block after an option changed. */
+async function refreshCommentBlock(): Promise<void> {
+  const c = composer;
+  if (!c || c.bufferId === null) return;
+  const before = textBeforeCommentBlock(c, await bufferText(c.bufferId));
+  const start = utf8Length(before);
+  const block = commentBlock(c);
+  editor.deleteRange(c.bufferId, start, editor.getBufferLength(c.bufferId));
+  editor.insertText(c.bufferId, start, before === "" || before.endsWith("\n") ? block : `\n${block}`);
+}
+
+function subjectOf(text: string): string {
+  return text.split("\n", 1)[0];
+}
+
+/** Warn about an overlong subject line. */
+async function lintMessage(): Promise<void> {
+  const c = composer;
+  if (!c || c.bufferId === null) return;
+  const subject = subjectOf(await bufferText(c.bufferId));
+  const diagnostics: PluginDiagnostic[] = [];
+  if (!subject.startsWith(c.commentChar) && subject.length > SUBJECT_LIMIT) {
+    diagnostics.push({
+      message: editor.t("diag.subject_too_long", {
+        count: String(subject.length),
+        limit: String(SUBJECT_LIMIT),
+      }),
+      severity: 2,
+      range: {
+        start: { line: 0, character: SUBJECT_LIMIT },
+        end: { line: 0, character: subject.length },
+      },
+    });
+  }
+  editor.setDiagnostics(c.bufferId, LINT_SOURCE, diagnostics);
+}
+
+function attach(bufferId: number): void {
+  const c = composer;
+  if (!c || c.bufferId !== null) return;
+  c.bufferId = bufferId;
+
+  const message = c.initialMessage.trimEnd();
+  const text = (message ? `${message}\n` : "\n") + "\n" + commentBlock(c);
+  editor.deleteRange(bufferId, 0, editor.getBufferLength(bufferId));
+  editor.insertText(bufferId, 0, text);
+  editor.setBufferCursor(bufferId, 0);
+  editor.setRulers(bufferId, RULERS);
+  activate(bufferId);
+  void lintMessage();
+}
+
+function activate(bufferId: number): void {
+  const active = composer !== null && composer.bufferId === bufferId;
+  editor.setContext(MODE, active);
+  const mode = editor.getEditorMode();
+  if (active && mode == null) {
+    editor.setEditorMode(MODE);
+  } else if (!active && mode === MODE) {
+    editor.setEditorMode(null);
+  }
+}
+
+function dispose(): void {
+  composer = null;
+  editor.setContext(MODE, false);
+  if (editor.getEditorMode() === MODE) editor.setEditorMode(null);
+}
+
+// =============================================================================
+// Opening the composer
+// =============================================================================
+
+async function openComposer(amend: boolean): Promise<void> {
+  if (composer) {
+    if (composer.bufferId !== null) {
+      editor.showBuffer(composer.bufferId);
+      if (amend && !composer.amend) await toggleAmend();
+    }
+    return;
+  }
+
+  const root = (await gitOutput(["rev-parse", "--show-toplevel"], editor.getCwd()))?.trim();
+  if (!root) {
+    editor.setStatus(editor.t("status.not_a_repo"));
+    return;
+  }
+
+  const head = await headCommit(root);
+  if (amend && !head) {
+    editor.setStatus(editor.t("status.no_commits"));
+    return;
+  }
+  const staged = await stagedSummary(root);
+  if (!amend && staged.length === 0) {
+    editor.setStatus(editor.t("status.nothing_staged"));
+    return;
+  }
+
+  const gitPath = (await gitOutput(["rev-parse", "--git-path", "COMMIT_EDITMSG"], root))?.trim();
+  if (!gitPath) {
+    editor.setStatus(editor.t("status.not_a_repo"));
+    return;
+  }
+  const msgPath = editor.pathIsAbsolute(gitPath) ? gitPath : editor.pathJoin(root, gitPath);
+
+  const commentChar = (await gitOutput(["config", "core.commentChar"], root))?.trim();
+
+  composer = {
+    root,
+    msgPath,
+    bufferId: null,
+    commentChar: commentChar && commentChar !== "auto" ? commentChar : "#",
+    initialMessage: amend ? await lastMessage(root) : await templateMessage(root),
+    staged,
+    head,
+    amend,
+    signoff: false,
+    fixup: null,
+    committing: false,
+  };
+
+  editor.openFile(msgPath, null, null);
+  // Already open: `openFile` only switches to it.
+  const existing = editor.findBufferByPath(msgPath);
+  if (existing) attach(existing);
+}
+
+async function stagedSummary(root: string): Promise<string[]> {
+  const out = await gitOutput(["diff", "--cached", "--stat"], root);
+  return out ? out.split("\n").filter((l) => l.trim() !== "").map((l) => l.trimEnd()) : [];
+}
+
+async function lastMessage(root: string): Promise<string> {
+  return (await gitOutput(["log", "-1", "--format=%B"], root)) ?? "";
+}
+
+/**
+ * `commit.template`, when configured. The template is read from the
+ * local filesystem; without one the message starts empty.
+ */
+async function templateMessage(root: string): Promise<string> {
+  const path = (await gitOutput(["config", "--path", "commit.template"], root))?.trim();
+  if (!path) return "";
+  const full = editor.pathIsAbsolute(path) ? path : editor.pathJoin(root, path);
+  return editor.readFile(full) ?? "";
+}
+
+// =============================================================================
+// Options
+// =============================================================================
+
+async function toggleAmend(): Promise<void> {
+  const c = composer;
+  if (!c || c.bufferId === null) return;
+  if (!c.amend && !c.head) {
+    editor.setStatus(editor.t("status.no_commits"));
+    return;
+  }
+  c.amend = !c.amend;
+  if (c.amend) c.fixup = null;
+
+  // Amending with an empty message keeps the last commit's message.
+  const text = await bufferText(c.bufferId);
+  if (c.amend && messageOf(c, text) === "") {
+    const message = (await lastMessage(c.root)).trimEnd();
+    if (message) editor.insertText(c.bufferId, 0, message);
+  }
+  await refreshCommentBlock();
+}
+
+async function toggleSignoff(): Promise<void> {
+  const c = composer;
+  if (!c || c.bufferId === null) return;
+  c.signoff = !c.signoff;
+  await refreshCommentBlock();
+}
+
+async function chooseFixup(): Promise<void> {
+  const c = composer;
+  if (!c || c.bufferId === null) return;
+  const out = await gitOutput(["log", "-n", "50", "--format=%h%x09%s"], c.root);
+  const commits = (out ?? "").split("\n").map(parseCommit).filter((x): x is Commit => x !== null);
+  if (commits.length === 0) {
+    editor.setStatus(editor.t("status.no_commits"));
+    return;
+  }
+  fixupChoices = [null, ...commits];
+  editor.startPrompt(editor.t("prompt.fixup"), FIXUP_PROMPT);
+  editor.setPromptSuggestions([
+    { text: editor.t("prompt.fixup_none"), value: "" },
+    ...commits.map((commit) => ({ text: commit.subject, description: commit.sha, value: commit.sha })),
+  ]);
+}
+
+// =============================================================================
+// Committing
+// =============================================================================
+
+async function commit(): Promise<void> {
+  const c = composer;
+  if (!c || c.bufferId === null || c.committing) return;
+  const bufferId = c.bufferId;
+  c.committing = true;
+
+  const text = await bufferText(bufferId);
+  const message = messageOf(c, text);
+  editor.saveBufferToPath(bufferId, c.msgPath);
+
+  const args = ["commit", "--cleanup=strip"];
+  // A fixup commit's message is generated by git; ours only supplements it.
+  if (!c.fixup || message !== "") args.push("-F", c.msgPath);
+  if (c.amend) args.push("--amend");
+  if (c.signoff) args.push("--signoff");
+  if (c.fixup) args.push(`--fixup=${c.fixup.sha}`);
+
+  editor.setStatus(editor.t("status.committing"));
+  const result = await git(args, c.root);
+  if (composer !== c) return;
+  c.committing = false;
+
+  if (result.exit_code !== 0) {
+    reportFailure(c, bufferId, subjectOf(text).length, `${result.stderr}\n${result.stdout}`.trim());
+    return;
+  }
+
+  editor.setDiagnostics(bufferId, HOOK_SOURCE, []);
+  editor.setDiagnostics(bufferId, LINT_SOURCE, []);
+  const head = await headCommit(c.root);
+  dispose();
+  editor.closeBuffer(bufferId);
+  editor.setStatus(editor.t("status.committed", {
+    commit: head ? `${head.sha} ${head.subject}` : "",
+  }));
+}
+
+/** Show why git (usually a hook) refused the commit, on the subject line. */
+function reportFailure(
+  c: ComposerState,
+  bufferId: number,
+  subjectLength: number,
+  output: string,
+): void {
+  editor.setDiagnostics(bufferId, HOOK_SOURCE, [{
+    message: output || editor.t("status.commit_failed"),
+    severity: 1,
+    range: { start: { line: 0, character: 0 }, end: { line: 0, character: subjectLength } },
+  }]);
+  editor.setStatus(editor.t("status.commit_failed"));
+  editor.debug(`git commit failed in ${c.root}: ${output}`);
+}
+
+// =============================================================================
+// Commands, keys and events
+// =============================================================================
+
+registerHandler("git_commit", () => { void openComposer(false); });
+registerHandler("git_commit_amend", () => { void openComposer(true); });
+registerHandler("git_commit_confirm", () => { void commit(); });
+registerHandler("git_commit_toggle_amend", () => { void toggleAmend(); });
+registerHandler("git_commit_toggle_signoff", () => { void toggleSignoff(); });
+registerHandler("git_commit_fixup", () => { void chooseFixup(); });
+
+editor.registerCommand("%cmd.git_commit", "%cmd.git_commit_desc", "git_commit", null);
+editor.registerCommand("%cmd.git_commit_amend", "%cmd.git_commit_amend_desc", "git_commit_amend", null);
+editor.registerCommand("%cmd.git_commit_confirm", "%cmd.git_commit_confirm_desc", "git_commit_confirm", MODE);
+editor.registerCommand("%cmd.git_commit_toggle_amend", "%cmd.git_commit_toggle_amend_desc", "git_commit_toggle_amend", MODE);
+editor.registerCommand("%cmd.git_commit_toggle_signoff", "%cmd.git_commit_toggle_signoff_desc", "git_commit_toggle_signoff", MODE);
+editor.registerCommand("%cmd.git_commit_fixup", "%cmd.git_commit_fixup_desc", "git_commit_fixup", MODE);
+
+// Not read-only: unbound keys edit the message as usual.
+editor.defineMode(MODE, [
+  ["C-Enter", "git_commit_confirm"],
+  ["M-a", "git_commit_toggle_amend"],
+  ["M-s", "git_commit_toggle_signoff"],
+  ["M-f", "git_commit_fixup"],
+], false);
+
+editor.on("buffer_activated", (args) => {
+  if (!composer) return;
+  if (composer.bufferId === null && editor.findBufferByPath(composer.msgPath) === args.buffer_id) {
+    attach(args.buffer_id);
+  } else {
+    activate(args.buffer_id);
+  }
+});
+
+editor.on("buffer_closed", (args) => {
+  if (composer && args.buffer_id === composer.bufferId) dispose();
+});
+
+editor.on("after_insert", (args) => {
+  if (composer && args.buffer_id === composer.bufferId) void lintMessage();
+});
+
+editor.on("after_delete", (args) => {
+  if (composer && args.buffer_id === composer.bufferId) void lintMessage();
+});
+
+editor.on("prompt_confirmed", (args) => {
+  if (args.prompt_type !== FIXUP_PROMPT) return true;
+  const c = composer;
+  if (!c || args.selected_index === null) return true;
+  c.fixup = fixupChoices[args.selected_index] ?? null;
+  if (c.fixup) c.amend = false;
+  void refreshCommentBlock();
+  return true;
+});
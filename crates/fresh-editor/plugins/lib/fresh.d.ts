@@ -520,6 +520,20 @@ type JsDiagnostic = {
 	*/
 	source?: string;
 };
+type PluginDiagnostic = {
+	/**
+	* Diagnostic message
+	*/
+	message: string;
+	/**
+	* Severity: 1=Error, 2=Warning, 3=Info, 4=Hint (default: Error)
+	*/
+	severity?: number;
+	/**
+	* Range in the document
+	*/
+	range: JsRange;
+};
 type JsRange = {
 	/**
 	* Start position
@@ -1936,6 +1950,83 @@ interface EditorAPI {
 	*/
 	getBufferLineCount(): Promise<number | null>;
 	/**
+	* Symbols of a buffer without asking a language server: declarations
+	* from its tree-sitter parse, or from per-language line patterns
+	* (Markdown headings included). Resolves with the same shape as a
+	* `textDocument/documentSymbol` answer — nested `DocumentSymbol`s with
+	* 0-indexed LSP positions — or an empty array when nothing is found.
+	*/
+	getSyntaxSymbols(bufferId: number): Promise<unknown[]>;
+	/**
+	* Undo tree of a buffer: every state reachable by undo, redo, or a
+	* redo branch abandoned by editing after an undo. Each node is the
+	* state after one undo step; `parent` is the state it was made from
+	* (0 is the initial state, which has no node). `current` is the
+	* buffer's state and `saved` the state last saved to disk, if still
+	* in the tree. Resolves with `null` for an unknown buffer.
+	*/
+	getUndoTree(bufferId: number): Promise<{
+		nodes: Array<{
+			id: number;
+			parent: number;
+			timestamp: number;
+			description: string;
+		}>;
+		current: number;
+		saved: number | null;
+	} | null>;
+	/**
+	* Unified diff (`@@` headers and ` `/`-`/`+` lines) from a buffer's
+	* current content to state `node_id` of its undo tree. Resolves with
+	* `null` when the state doesn't exist or the buffer isn't fully loaded.
+	*/
+	getUndoStateDiff(bufferId: number, nodeId: number): Promise<string[] | null>;
+	/**
+	* Line diff from `oldText` to `newText`: the hunks of changed lines,
+	* with per-line ops, ready for `createCompositeBuffer`. A final
+	* newline doesn't start an extra line. Resolves with `[]` when the
+	* texts are equal.
+	*/
+	diffLines(oldText: string, newText: string): Promise<TsCompositeHunk[]>;
+	/**
+	* Move a buffer to state `node_id` of its undo tree, as a series of
+	* undos and redos. The buffer must be the active one: focus its split
+	* first when calling from a panel.
+	*/
+	jumpToUndoState(bufferId: number, nodeId: number): boolean;
+	/**
+	* Saved versions of `path` in local history, newest first. A version
+	* is recorded on every save that changed the file; `id` identifies it
+	* and `timestamp` is its save time in milliseconds since the epoch.
+	*/
+	getFileHistory(path: string): Promise<Array<{
+		id: number;
+		timestamp: number;
+		size: number;
+	}>>;
+	/**
+	* Text of version `version_id` of `path` from local history. Resolves
+	* with `null` if the version doesn't exist or isn't valid UTF-8.
+	*/
+	readFileHistoryVersion(path: string, versionId: number): Promise<string | null>;
+	/**
+	* Replace a buffer's content with version `version_id` of its file
+	* from local history, as a single undoable edit. The buffer must be
+	* the active one: focus its split first when calling from a panel.
+	*/
+	restoreFileHistoryVersion(bufferId: number, versionId: number): boolean;
+	/**
+	* Apply the workspace edit announced by the `workspace_edit_preview`
+	* hook under `id`, leaving out the changes whose ids are in
+	* `excluded`. Text changes land as one undo step across all files.
+	*/
+	applyWorkspaceEditPreview(id: number, excluded: number[]): boolean;
+	/**
+	* Drop the workspace edit announced by the `workspace_edit_preview`
+	* hook under `id` without applying any of it.
+	*/
+	cancelWorkspaceEditPreview(id: number): boolean;
+	/**
 	* Cursor info for the active composite (side-by-side diff) buffer.
 	* 
 	* Resolves with `null` when the active buffer is not a composite
@@ -2929,6 +3020,16 @@ interface EditorAPI {
 	*/
 	setLineNumbers(bufferId: number, enabled: boolean): boolean;
 	/**
+	* Replace the vertical rulers shown for a buffer (column numbers)
+	*/
+	setRulers(bufferId: number, columns: number[]): boolean;
+	/**
+	* Replace the diagnostics this plugin reports for a buffer under
+	* `source`. They are shown like LSP diagnostics; pass an empty list to
+	* clear them.
+	*/
+	setDiagnostics(bufferId: number, source: string, diagnostics: PluginDiagnostic[]): boolean;
+	/**
 	* Set the view mode for a buffer ("source" or "compose")
 	*/
 	setViewMode(bufferId: number, mode: string): boolean;
@@ -3277,6 +3378,17 @@ interface EditorAPI {
 	*/
 	sendLspRequest(language: string, method: string, params: Record<string, unknown> | null): Promise<unknown>;
 	/**
+	* Send a request to the active debug session (async, resolves with the
+	* adapter's response body; rejects when no session is running)
+	*/
+	debugRequest(command: string, args: Record<string, unknown> | null): Promise<unknown>;
+	/**
+	* Start a debug session for the active buffer's language. Top-level
+	* keys of `configuration` replace those of the configured launch/attach
+	* arguments and `null` removes one (e.g. to debug a single test).
+	*/
+	debugStart(configuration: Record<string, unknown> | null): boolean;
+	/**
 	* Spawn a background process (async, returns request_id which is also process_id)
 	*/
 	spawnBackgroundProcess(command: string, args: string[], cwd?: string): ProcessHandle<BackgroundProcessResult>;
@@ -3575,6 +3687,33 @@ interface HookEventMap {
 			column: number;
 		}[];
 	};
+	lsp_hierarchy: {
+		direction: "incoming" | "outgoing" | "supertypes" | "subtypes";
+		language: string;
+		items: unknown[];
+	};
+	workspace_edit_preview: {
+		id: number;
+		title: string;
+		changes: {
+			id: number;
+			kind: "edit" | "create" | "rename" | "delete";
+			path: string;
+			new_path: string | null;
+			range: {
+				start: {
+					line: number;
+					character: number;
+				};
+				end: {
+					line: number;
+					character: number;
+				};
+			} | null;
+			new_text: string;
+		}[];
+		texts: Record<string, string>;
+	};
 	lsp_server_request: {
 		language: string;
 		method: string;
@@ -3593,6 +3732,24 @@ interface HookEventMap {
 		missing_servers: string[];
 		user_dismissed: boolean;
 	};
+	// ── debugger (DAP) ───────────────────────────────────────────────────────
+	debug_stopped: {
+		reason: string;
+		thread_id: number;
+		frames: {
+			id: number;
+			name: string;
+			path: string | null;
+			line: number;
+			column: number;
+		}[];
+	};
+	debug_continued: {
+		thread_id: number | null;
+	};
+	debug_terminated: {
+		exit_code: number | null;
+	};
 	// ── UI events ────────────────────────────────────────────────────────────
 	action_popup_result: {
 		popup_id: string;
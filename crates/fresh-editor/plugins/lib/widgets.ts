@@ -670,7 +670,8 @@ export class WidgetPanel {
     return this.mutate({ kind: "setChecked", widgetKey, checked });
   }
 
-  /** Set a `List`'s selected index. */
+  /** Set a `List`'s selected index, or a `Tree`'s (absolute node
+   * index; the host scrolls it into view). */
   setSelectedIndex(widgetKey: string, index: number): boolean {
     return this.mutate({ kind: "setSelectedIndex", widgetKey, index });
   }
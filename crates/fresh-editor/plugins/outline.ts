@@ -0,0 +1,611 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  key as widgetKey,
+  raw,
+  tree,
+  treeNode,
+  type TreeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+import { getRelativePath } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+/**
+ * Outline Plugin
+ *
+ * The symbol tree of the active file in a sidebar left of the editor.
+ * Symbols come from the file's language server
+ * (`textDocument/documentSymbol`) and, when there is none or it fails,
+ * from `editor.getSyntaxSymbols` — declarations in the tree-sitter
+ * parse, or per-language line patterns (Markdown headings included).
+ *
+ * The selection follows the cursor in the file. Typing filters the tree
+ * (a match keeps its ancestors), Backspace edits the filter, Escape
+ * clears it — or, with no filter, returns to the file — and Enter jumps
+ * to the symbol. The tree is rebuilt when another file becomes active,
+ * shortly after edits, and on save.
+ */
+
+// =============================================================================
+// Types
+// =============================================================================
+
+interface LspPosition {
+  line: number;
+  character: number;
+}
+
+interface LspRange {
+  start: LspPosition;
+  end: LspPosition;
+}
+
+/** `DocumentSymbol` — also the shape `getSyntaxSymbols` resolves with. */
+interface DocumentSymbol {
+  name: string;
+  detail?: string;
+  kind: number;
+  range: LspRange;
+  selectionRange: LspRange;
+  children?: DocumentSymbol[];
+}
+
+/** `SymbolInformation` — the flat answer some servers still send. */
+interface SymbolInformation {
+  name: string;
+  kind: number;
+  containerName?: string;
+  location: { uri: string; range: LspRange };
+}
+
+interface OutlineNode {
+  key: string;
+  symbol: DocumentSymbol;
+  depth: number;
+  children: OutlineNode[];
+}
+
+interface PanelState {
+  bufferId: number;
+  splitId: number;
+  /** Split the file is shown in; Enter jumps there. */
+  sourceSplitId: number;
+  widget: WidgetPanel;
+  /** File whose symbols are shown, or `null` when there is none. */
+  source: { bufferId: number; path: string; language: string } | null;
+  /** Where the symbols came from, once loaded. */
+  origin: "lsp" | "syntax" | null;
+  roots: OutlineNode[];
+  /** Nodes in the tree as last rendered, depth-first. */
+  shown: OutlineNode[];
+  filter: string;
+  /** Keys the user collapsed; everything else starts expanded. */
+  collapsed: Set<string>;
+  /** 0-indexed line of the cursor in the file. */
+  cursorLine: number;
+  /** Bumped on every refresh so stale answers are dropped. */
+  generation: number;
+}
+
+let panel: PanelState | null = null;
+
+const TREE_KEY = "outline";
+
+/** Quiet time after an edit before the symbols are fetched again. */
+const REFRESH_DELAY_MS = 500;
+
+/** `SymbolKind` labels, indexed by kind. Empty for Markdown headings. */
+const KIND_LABELS = [
+  "",
+  "file",
+  "module",
+  "namespace",
+  "package",
+  "class",
+  "method",
+  "property",
+  "field",
+  "constructor",
+  "enum",
+  "interface",
+  "function",
+  "variable",
+  "constant",
+  "",
+  "number",
+  "boolean",
+  "array",
+  "object",
+  "key",
+  "null",
+  "enum member",
+  "struct",
+  "event",
+  "operator",
+  "type parameter",
+];
+
+const COLORS = {
+  title: "syntax.keyword" as OverlayColorSpec,
+  name: "syntax.function" as OverlayColorSpec,
+  match: "syntax.string" as OverlayColorSpec,
+  detail: "editor.line_number_fg" as OverlayColorSpec,
+};
+
+// =============================================================================
+// Mode
+// =============================================================================
+
+// Printable keys reach `mode_text_input` and extend the filter.
+editor.defineMode(
+  "outline",
+  [
+    ["Up", "outline_up"],
+    ["Down", "outline_down"],
+    ["PageUp", "outline_page_up"],
+    ["PageDown", "outline_page_down"],
+    ["Left", "outline_left"],
+    ["Right", "outline_right"],
+    ["Return", "outline_enter"],
+    ["Backspace", "outline_backspace"],
+    ["Escape", "outline_escape"],
+  ],
+  true, // read-only
+  true, // allow text input
+);
+
+// =============================================================================
+// Model
+// =============================================================================
+
+function isSymbolInformation(value: unknown): value is SymbolInformation {
+  return typeof value === "object" && value !== null && "location" in value;
+}
+
+function contains(outer: LspRange, inner: LspRange): boolean {
+  const before = (a: LspPosition, b: LspPosition) =>
+    a.line < b.line || (a.line === b.line && a.character <= b.character);
+  return before(outer.start, inner.start) && before(inner.end, outer.end);
+}
+
+/** Nest a flat `SymbolInformation[]` answer by range containment. */
+function nestFlat(infos: SymbolInformation[]): DocumentSymbol[] {
+  const symbols: DocumentSymbol[] = infos
+    .map((info) => ({
+      name: info.name,
+      detail: info.containerName,
+      kind: info.kind,
+      range: info.location.range,
+      selectionRange: info.location.range,
+    }))
+    .sort((a, b) =>
+      a.range.start.line - b.range.start.line ||
+      a.range.start.character - b.range.start.character ||
+      b.range.end.line - a.range.end.line
+    );
+
+  const roots: DocumentSymbol[] = [];
+  const open: DocumentSymbol[] = [];
+  for (const symbol of symbols) {
+    while (open.length > 0 && !contains(open[open.length - 1].range, symbol.range)) {
+      open.pop();
+    }
+    const parent = open[open.length - 1];
+    if (parent) {
+      (parent.children ??= []).push(symbol);
+    } else {
+      roots.push(symbol);
+    }
+    open.push(symbol);
+  }
+  return roots;
+}
+
+function toSymbols(result: unknown): DocumentSymbol[] | null {
+  if (!Array.isArray(result)) return null;
+  if (result.length > 0 && isSymbolInformation(result[0])) {
+    return nestFlat(result as SymbolInformation[]);
+  }
+  return result as DocumentSymbol[];
+}
+
+/** Keys are name paths, so expansion survives refreshes that move lines. */
+function buildNodes(
+  symbols: DocumentSymbol[],
+  parentKey: string,
+  depth: number,
+): OutlineNode[] {
+  const seen = new Map<string, number>();
+  return symbols.map((symbol) => {
+    const count = seen.get(symbol.name) ?? 0;
+    seen.set(symbol.name, count + 1);
+    const key = `${parentKey}/${symbol.name}${count > 0 ? `~${count}` : ""}`;
+    return {
+      key,
+      symbol,
+      depth,
+      children: buildNodes(symbol.children ?? [], key, depth + 1),
+    };
+  });
+}
+
+/** Depth-first list of the nodes to show: with a filter, the matches and
+ *  their ancestors only. */
+function visibleNodes(
+  nodes: OutlineNode[],
+  filter: string,
+  out: OutlineNode[] = [],
+): OutlineNode[] {
+  for (const node of nodes) {
+    const at = out.length;
+    visibleNodes(node.children, filter, out);
+    const keep = !filter || out.length > at ||
+      node.symbol.name.toLowerCase().includes(filter);
+    if (keep) out.splice(at, 0, node);
+  }
+  return out;
+}
+
+/** Index in `panel.shown` of the innermost symbol containing `line`. */
+function nodeAtLine(line: number): number {
+  if (!panel) return -1;
+  // Depth-first order puts the innermost match last.
+  let found = -1;
+  panel.shown.forEach((node, index) => {
+    const { start, end } = node.symbol.range;
+    if (start.line <= line && line <= end.line) found = index;
+  });
+  return found;
+}
+
+async function fetchSymbols(
+  source: NonNullable<PanelState["source"]>,
+): Promise<{ symbols: DocumentSymbol[]; origin: "lsp" | "syntax" }> {
+  try {
+    const result = await editor.sendLspRequest(
+      source.language,
+      "textDocument/documentSymbol",
+      { textDocument: { uri: editor.pathToFileUri(source.path) } },
+    );
+    const symbols = toSymbols(result);
+    if (symbols) return { symbols, origin: "lsp" };
+  } catch (e) {
+    editor.debug(`outline: documentSymbol unavailable: ${e}`);
+  }
+  const symbols = toSymbols(await editor.getSyntaxSymbols(source.bufferId)) ?? [];
+  return { symbols, origin: "syntax" };
+}
+
+async function refresh(): Promise<void> {
+  if (!panel) return;
+  const generation = ++panel.generation;
+  const source = panel.source;
+  if (!source) {
+    panel.roots = [];
+    panel.origin = null;
+    render();
+    return;
+  }
+  const { symbols, origin } = await fetchSymbols(source);
+  if (!panel || panel.generation !== generation) return;
+  panel.roots = buildNodes(symbols, "", 0);
+  panel.origin = origin;
+  render();
+}
+
+async function scheduleRefresh(): Promise<void> {
+  if (!panel) return;
+  const generation = ++panel.generation;
+  await editor.delay(REFRESH_DELAY_MS);
+  if (panel && panel.generation === generation) void refresh();
+}
+
+/** Show the symbols of `bufferId`, shown in `splitId`, if it is a file.
+ *  Returns whether the source changed. */
+function setSource(bufferId: number, splitId: number, cursorLine: number): boolean {
+  if (!panel || bufferId === panel.bufferId) return false;
+  const info = editor.getBufferInfo(bufferId);
+  if (!info || info.is_virtual || !info.path) return false;
+  if (panel.source?.bufferId === bufferId) return false;
+  panel.source = { bufferId, path: info.path, language: info.language };
+  panel.sourceSplitId = splitId;
+  panel.cursorLine = cursorLine;
+  panel.filter = "";
+  panel.collapsed.clear();
+  panel.roots = [];
+  panel.origin = null;
+  return true;
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function nodeEntry(node: OutlineNode, filter: string): TextPropertyEntry {
+  const { name, detail, kind } = node.symbol;
+  const label = KIND_LABELS[kind] ?? "";
+  const suffix = [detail, label].filter((s) => s).join("  ");
+  const text = suffix ? `${name}  ${suffix}` : name;
+  const inlineOverlays: InlineOverlay[] = [
+    { start: 0, end: name.length, style: { fg: COLORS.name } },
+  ];
+  const match = filter ? name.toLowerCase().indexOf(filter) : -1;
+  if (match >= 0) {
+    inlineOverlays.push({
+      start: match,
+      end: match + filter.length,
+      style: { fg: COLORS.match, bold: true },
+    });
+  }
+  if (suffix) {
+    inlineOverlays.push({
+      start: name.length,
+      end: text.length,
+      style: { fg: COLORS.detail },
+    });
+  }
+  return { text, inlineOverlays };
+}
+
+function render(): void {
+  if (!panel) return;
+  const { source, filter } = panel;
+  panel.shown = visibleNodes(panel.roots, filter);
+
+  const file = source ? getRelativePath(editor, source.path) : "";
+  const origin = panel.origin === "syntax" ? `  ${editor.t("panel.from_syntax")}` : "";
+  const header: TextPropertyEntry[] = [
+    {
+      text: `${editor.t("panel.title", { file })}${origin}\n`,
+      style: { fg: COLORS.title, bold: true },
+    },
+  ];
+  if (filter) {
+    header.push({
+      text: `${editor.t("panel.filter", { filter })}\n`,
+      style: { fg: COLORS.match },
+    });
+  }
+
+  let message: string | null = null;
+  if (!source) {
+    message = editor.t("panel.no_file");
+  } else if (panel.origin === null) {
+    message = editor.t("panel.loading");
+  } else if (panel.shown.length === 0) {
+    message = filter
+      ? editor.t("panel.no_matches", { filter })
+      : editor.t("panel.no_symbols");
+  }
+  if (message !== null) {
+    panel.widget.set(col(raw(header, "header"), raw([{ text: message }], "empty")));
+    return;
+  }
+
+  const vp = editor.getViewport();
+  const visibleRows = Math.max(3, (vp && vp.height > 0 ? vp.height : 30) - 3);
+  const { shown, collapsed } = panel;
+  // A filter can hide every child of a node.
+  const hasChildren = (i: number) => (shown[i + 1]?.depth ?? -1) > shown[i].depth;
+  const parents = shown.filter((_, i) => hasChildren(i)).map((n) => n.key);
+  // While filtering every match is shown, whatever was collapsed.
+  const expanded = filter ? parents : parents.filter((k) => !collapsed.has(k));
+  const treeNodes: TreeNode[] = shown.map((node, i) =>
+    treeNode(nodeEntry(node, filter), {
+      depth: node.depth,
+      hasChildren: hasChildren(i),
+    })
+  );
+  panel.widget.set(
+    col(
+      raw(header, "header"),
+      tree({
+        nodes: treeNodes,
+        itemKeys: shown.map((n) => n.key),
+        selectedIndex: nodeAtLine(panel.cursorLine),
+        visibleRows,
+        expandedKeys: expanded,
+        key: TREE_KEY,
+      }),
+    ),
+  );
+  // Both are host-owned after the first render, so push them.
+  panel.widget.setExpandedKeys(TREE_KEY, expanded);
+  followCursor();
+}
+
+function followCursor(): void {
+  if (!panel || panel.shown.length === 0) return;
+  const index = nodeAtLine(panel.cursorLine);
+  if (index >= 0) panel.widget.setSelectedIndex(TREE_KEY, index);
+}
+
+// =============================================================================
+// Panel lifecycle
+// =============================================================================
+
+async function openOutline(): Promise<void> {
+  if (panel) return;
+  const sourceBufferId = editor.getActiveBufferId();
+  const sourceSplitId = editor.getActiveSplitId();
+  const cursorLine = editor.getPrimaryCursor()?.line ?? 0;
+  try {
+    const result = await editor.createVirtualBufferInSplit({
+      name: "*Outline*",
+      mode: "outline",
+      readOnly: true,
+      entries: [],
+      ratio: 0.25,
+      direction: "vertical",
+      before: true,
+      panelId: "outline-panel",
+      showLineNumbers: false,
+      showCursors: false,
+      editingDisabled: true,
+    });
+    panel = {
+      bufferId: result.bufferId,
+      splitId: result.splitId ?? editor.getActiveSplitId(),
+      sourceSplitId,
+      widget: new WidgetPanel(result.bufferId),
+      source: null,
+      origin: null,
+      roots: [],
+      shown: [],
+      filter: "",
+      collapsed: new Set(),
+      cursorLine: 0,
+      generation: 0,
+    };
+  } catch (e) {
+    editor.setStatus(editor.t("status.failed_open_panel"));
+    editor.debug(`outline: createVirtualBufferInSplit failed: ${e}`);
+    return;
+  }
+  setSource(sourceBufferId, sourceSplitId, cursorLine);
+  render();
+  void refresh();
+}
+
+function closeOutline(): void {
+  if (!panel) return;
+  const { bufferId, splitId, sourceSplitId, widget } = panel;
+  panel = null;
+  widget.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+}
+
+function toggle_outline(): void {
+  if (panel) {
+    closeOutline();
+  } else {
+    void openOutline();
+  }
+}
+registerHandler("toggle_outline", toggle_outline);
+
+editor.registerCommand(
+  "%cmd.toggle_outline",
+  "%cmd.toggle_outline_desc",
+  "toggle_outline",
+  null,
+);
+
+editor.on("buffer_activated", (args) => {
+  if (!panel) return;
+  const cursorLine = editor.getPrimaryCursor()?.line ?? 0;
+  if (setSource(args.buffer_id, editor.getActiveSplitId(), cursorLine)) {
+    render();
+    void refresh();
+  }
+});
+
+editor.on("buffer_closed", (args) => {
+  if (!panel) return;
+  if (args.buffer_id === panel.bufferId) {
+    panel.widget.unmount();
+    panel = null;
+  } else if (args.buffer_id === panel.source?.bufferId) {
+    panel.source = null;
+    void refresh();
+  }
+});
+
+editor.on("cursor_moved", (args) => {
+  if (!panel || args.buffer_id !== panel.source?.bufferId) return;
+  const line = args.line - 1;
+  if (line === panel.cursorLine) return;
+  panel.cursorLine = line;
+  followCursor();
+});
+
+editor.on("after_insert", (args) => {
+  if (panel && args.buffer_id === panel.source?.bufferId) void scheduleRefresh();
+});
+
+editor.on("after_delete", (args) => {
+  if (panel && args.buffer_id === panel.source?.bufferId) void scheduleRefresh();
+});
+
+editor.on("after_file_save", (args) => {
+  if (panel && args.buffer_id === panel.source?.bufferId) void refresh();
+});
+
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widget.id()) return;
+  if (args.widget_key !== TREE_KEY) return;
+
+  if (args.event_type === "expand") {
+    const payload = args.payload as { key?: string; expanded?: boolean } | undefined;
+    if (typeof payload?.key !== "string" || panel.filter) return;
+    if (payload.expanded) {
+      panel.collapsed.delete(payload.key);
+    } else {
+      panel.collapsed.add(payload.key);
+    }
+    return;
+  }
+
+  if (args.event_type === "activate") {
+    const nodeKey = (args.payload as { key?: string } | undefined)?.key;
+    const node = panel.shown.find((n) => n.key === nodeKey);
+    if (!node || !panel.source) return;
+    const { start } = node.symbol.selectionRange;
+    editor.openFileInSplit(
+      panel.sourceSplitId,
+      panel.source.path,
+      start.line + 1,
+      start.character + 1,
+    );
+  }
+});
+
+// =============================================================================
+// Key handlers
+// =============================================================================
+
+function dispatch(name: string): void {
+  panel?.widget.command(widgetKey(name));
+}
+
+registerHandler("outline_up", () => dispatch("Up"));
+registerHandler("outline_down", () => dispatch("Down"));
+registerHandler("outline_page_up", () => dispatch("PageUp"));
+registerHandler("outline_page_down", () => dispatch("PageDown"));
+registerHandler("outline_left", () => dispatch("Left"));
+registerHandler("outline_right", () => dispatch("Right"));
+registerHandler("outline_enter", () => dispatch("Enter"));
+
+function setFilter(filter: string): void {
+  if (!panel || filter === panel.filter) return;
+  panel.filter = filter;
+  render();
+}
+
+function mode_text_input(args: { text: string }): void {
+  if (!panel || !args?.text) return;
+  if (editor.getActiveBufferId() !== panel.bufferId) return;
+  setFilter(panel.filter + args.text.toLowerCase());
+}
+registerHandler("mode_text_input", mode_text_input);
+
+registerHandler("outline_backspace", () => {
+  if (panel) setFilter(panel.filter.slice(0, -1));
+});
+
+registerHandler("outline_escape", () => {
+  if (!panel) return;
+  if (panel.filter) {
+    setFilter("");
+  } else {
+    editor.focusSplit(panel.sourceSplitId);
+  }
+});
+
+editor.debug("Outline plugin loaded");
@@ -0,0 +1,645 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  key as widgetKey,
+  raw,
+  tree,
+  treeNode,
+  type TreeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+import { getRelativePath } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+/**
+ * Refactor Preview Plugin
+ *
+ * Multi-file LSP edits — renames, code actions, edits a server applies,
+ * import updates for files moved in the explorer — arrive through the
+ * `workspace_edit_preview` hook instead of being applied straight away.
+ * This plugin lists them in the Utility Dock, grouped by file, and shows
+ * the file of the selected row as a side-by-side diff in the split the
+ * edit came from, with the file's checked changes applied.
+ *
+ * Every change has a checkbox. Applying sends the unchecked ones back as
+ * excluded (`editor.applyWorkspaceEditPreview`); discarding drops the
+ * whole edit (`editor.cancelWorkspaceEditPreview`). The editor applies
+ * what is kept as one undoable step across all the files.
+ */
+
+// =============================================================================
+// Types
+// =============================================================================
+
+type Change = HookEventMap["workspace_edit_preview"]["changes"][number];
+
+interface ChangeState {
+  change: Change;
+  selected: boolean;
+}
+
+/** One group of rows: a file's text changes, or a single file operation. */
+interface Group {
+  key: string;
+  /** Set for text edits; file operations have no header row. */
+  path: string | null;
+  changes: ChangeState[];
+}
+
+/** What a tree row stands for, parallel to the emitted nodes. */
+type Row =
+  | { type: "file"; group: Group }
+  | { type: "change"; group: Group; state: ChangeState };
+
+/** A file with its checked changes applied, as the diff view shows it. */
+interface FileDiff {
+  newText: string;
+  hunks: TsCompositeHunk[];
+  /** Index in `hunks` of the hunk showing each change */
+  hunkOf: Map<number, number>;
+}
+
+interface DiffState {
+  compositeBufferId: number;
+  oldBufferId: number;
+  newBufferId: number;
+  path: string;
+  /** Hunk the view was opened on */
+  focusHunk: number;
+  /** Index of the hunk showing each checked change */
+  hunkOf: Map<number, number>;
+}
+
+interface PanelState {
+  bufferId: number;
+  splitId: number;
+  sourceSplitId: number;
+  /** Buffer the source split showed before the diff replaced it */
+  sourceBufferId: number;
+  widget: WidgetPanel;
+  /** Id the editor holds the edit under */
+  previewId: number;
+  title: string;
+  groups: Group[];
+  /** Content of each edited file, as the edit sees it */
+  texts: Record<string, string>;
+  rows: Row[];
+  diff: DiffState | null;
+  /** Bumped every time a diff is built so slower builds are dropped */
+  diffGeneration: number;
+}
+
+let panel: PanelState | null = null;
+
+const TREE_KEY = "changes";
+
+const COLORS = {
+  title: "syntax.keyword" as OverlayColorSpec,
+  file: "syntax.function" as OverlayColorSpec,
+  location: "editor.line_number_fg" as OverlayColorSpec,
+};
+
+// =============================================================================
+// Mode
+// =============================================================================
+
+editor.defineMode(
+  "refactor-preview",
+  [
+    ["Up", "refactor_preview_up"],
+    ["Down", "refactor_preview_down"],
+    ["k", "refactor_preview_up"],
+    ["j", "refactor_preview_down"],
+    ["PageUp", "refactor_preview_page_up"],
+    ["PageDown", "refactor_preview_page_down"],
+    ["Left", "refactor_preview_left"],
+    ["Right", "refactor_preview_right"],
+    ["Space", "refactor_preview_toggle"],
+    ["Return", "refactor_preview_apply"],
+    ["a", "refactor_preview_apply"],
+    ["q", "refactor_preview_discard"],
+    ["Escape", "refactor_preview_discard"],
+  ],
+  true, // read-only
+);
+
+editor.defineMode(
+  "refactor-preview-diff",
+  [
+    ["n", "refactor_preview_diff_next_hunk"],
+    ["p", "refactor_preview_diff_prev_hunk"],
+    ["a", "refactor_preview_apply"],
+    ["q", "refactor_preview_discard"],
+    ["Escape", "refactor_preview_diff_back"],
+  ],
+  true, // read-only
+);
+
+// =============================================================================
+// Model
+// =============================================================================
+
+/** Group text changes by file, in first-seen order; file operations
+ *  stand alone where they occur. */
+function groupChanges(changes: Change[]): Group[] {
+  const groups: Group[] = [];
+  const byPath = new Map<string, Group>();
+  for (const change of changes) {
+    const state = { change, selected: true };
+    if (change.kind !== "edit") {
+      groups.push({ key: `op:${change.id}`, path: null, changes: [state] });
+      continue;
+    }
+    let group = byPath.get(change.path);
+    if (!group) {
+      group = { key: `file:${change.path}`, path: change.path, changes: [] };
+      byPath.set(change.path, group);
+      groups.push(group);
+    }
+    group.changes.push(state);
+  }
+  return groups;
+}
+
+function changeKey(state: ChangeState): string {
+  return `change:${state.change.id}`;
+}
+
+function allSelected(group: Group): boolean {
+  return group.changes.every((s) => s.selected);
+}
+
+/** Offset in `text` of an LSP position (UTF-16 units, like JS strings),
+ *  clamped to its line; positions past the last line mean the end. */
+function offsetOf(text: string, lineStarts: number[], pos: { line: number; character: number }): number {
+  if (pos.line >= lineStarts.length) return text.length;
+  const start = lineStarts[pos.line];
+  let end = pos.line + 1 < lineStarts.length ? lineStarts[pos.line + 1] - 1 : text.length;
+  if (end > start && text[end - 1] === "\r") end--;
+  return Math.min(start + pos.character, end);
+}
+
+/** Index of the line holding `offset`. */
+function lineOf(lineStarts: number[], offset: number): number {
+  let lo = 0;
+  let hi = lineStarts.length - 1;
+  while (lo < hi) {
+    const mid = (lo + hi + 1) >> 1;
+    if (lineStarts[mid] <= offset) lo = mid;
+    else hi = mid - 1;
+  }
+  return lo;
+}
+
+/**
+ * Apply the checked changes of `group` to `text` and diff the result
+ * against it with the editor's line diff.
+ */
+async function fileDiff(text: string, group: Group): Promise<FileDiff> {
+  const lineStarts = [0];
+  for (let i = 0; i < text.length; i++) {
+    if (text[i] === "\n") lineStarts.push(i + 1);
+  }
+
+  const edits = group.changes
+    .filter((s) => s.selected && s.change.range)
+    .map((s) => {
+      const range = s.change.range!;
+      const start = offsetOf(text, lineStarts, range.start);
+      return {
+        id: s.change.id,
+        start,
+        end: Math.max(start, offsetOf(text, lineStarts, range.end)),
+        text: s.change.new_text,
+      };
+    })
+    // Stable, so inserts at one position keep their order.
+    .sort((a, b) => a.start - b.start);
+
+  let newText = "";
+  let copied = 0;
+  for (const edit of edits) {
+    newText += text.slice(copied, edit.start) + edit.text;
+    copied = Math.max(copied, edit.end);
+  }
+  newText += text.slice(copied);
+
+  const hunks = await editor.diffLines(text, newText);
+  // A change shows in the first hunk that reaches its line.
+  const hunkOf = new Map<number, number>();
+  for (const edit of edits) {
+    const line = lineOf(lineStarts, edit.start);
+    const index = hunks.findIndex((h) => h.oldStart + Math.max(h.oldCount, 1) > line);
+    hunkOf.set(edit.id, index >= 0 ? index : Math.max(0, hunks.length - 1));
+  }
+  return { newText, hunks, hunkOf };
+}
+
+function excludedIds(): number[] {
+  if (!panel) return [];
+  return panel.groups
+    .flatMap((g) => g.changes)
+    .filter((s) => !s.selected)
+    .map((s) => s.change.id);
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function relative(path: string): string {
+  return getRelativePath(editor, path);
+}
+
+function operationLabel(change: Change): string {
+  const path = relative(change.path);
+  if (change.kind === "rename") {
+    return editor.t("change.rename", { path, new_path: relative(change.new_path ?? "") });
+  }
+  return editor.t(`change.${change.kind}`, { path });
+}
+
+function buildNodes(state: PanelState): { nodes: TreeNode[]; keys: string[]; rows: Row[] } {
+  const nodes: TreeNode[] = [];
+  const keys: string[] = [];
+  const rows: Row[] = [];
+  const push = (node: TreeNode, key: string, row: Row) => {
+    nodes.push(node);
+    keys.push(key);
+    rows.push(row);
+  };
+
+  for (const group of state.groups) {
+    if (group.path === null) {
+      const change = group.changes[0];
+      const text = operationLabel(change.change);
+      push(
+        treeNode({ text, inlineOverlays: [{ start: 0, end: text.length, style: { fg: COLORS.file } }] }, {
+          checked: change.selected,
+        }),
+        changeKey(change),
+        { type: "change", group, state: change },
+      );
+      continue;
+    }
+
+    const name = relative(group.path);
+    push(
+      treeNode({ text: name, inlineOverlays: [{ start: 0, end: name.length, style: { fg: COLORS.file } }] }, {
+        hasChildren: true,
+        checked: allSelected(group),
+      }),
+      group.key,
+      { type: "file", group },
+    );
+    for (const change of group.changes) {
+      const line = change.change.range?.start.line ?? 0;
+      const text = editor.t("change.line", { line: line + 1 });
+      push(
+        treeNode({ text, style: { fg: COLORS.location } }, {
+          depth: 1,
+          checked: change.selected,
+        }),
+        changeKey(change),
+        { type: "change", group, state: change },
+      );
+    }
+  }
+  return { nodes, keys, rows };
+}
+
+function headerText(state: PanelState): string {
+  const changes = state.groups.flatMap((g) => g.changes);
+  return editor.t("panel.title", {
+    title: state.title,
+    selected: changes.filter((s) => s.selected).length,
+    count: changes.length,
+  });
+}
+
+function render(): void {
+  if (!panel) return;
+  const { nodes, keys, rows } = buildNodes(panel);
+  panel.rows = rows;
+  const vp = editor.getViewport();
+  const visibleRows = Math.max(3, (vp && vp.height > 0 ? vp.height : 30) - 3);
+
+  panel.widget.set(
+    col(
+      raw([{ text: `${headerText(panel)}\n`, style: { fg: COLORS.title, bold: true } }], "title"),
+      tree({
+        nodes,
+        itemKeys: keys,
+        selectedIndex: 0,
+        visibleRows,
+        // Everything starts open; the host owns expansion afterwards.
+        expandedKeys: keys,
+        checkable: true,
+        key: TREE_KEY,
+      }),
+      raw([{ text: editor.t("panel.hint"), style: { fg: COLORS.location } }], "hint"),
+    ),
+  );
+}
+
+/** Check or uncheck the change (or every change of the file) at row
+ *  `index`. */
+function toggleRow(index: number, value: boolean): void {
+  if (!panel) return;
+  const row = panel.rows[index];
+  if (!row) return;
+  const targets = row.type === "file" ? row.group.changes : [row.state];
+  for (const state of targets) state.selected = value;
+
+  panel.widget.setCheckedKeys(TREE_KEY, value, targets.map(changeKey));
+  if (row.group.path !== null) {
+    panel.widget.setCheckedKeys(TREE_KEY, allSelected(row.group), [row.group.key]);
+  }
+  panel.widget.setRawEntries("title", [
+    { text: `${headerText(panel)}\n`, style: { fg: COLORS.title, bold: true } },
+  ]);
+  // The diff shows checked changes only, so it changes too.
+  const diff = panel.diff;
+  if (diff && diff.path === row.group.path) void showDiff(row.group, diff.focusHunk);
+}
+
+// =============================================================================
+// Diff view
+// =============================================================================
+
+function contentToEntries(text: string): TextPropertyEntry[] {
+  const lines = text.split("\n");
+  return lines.map((line, idx) => {
+    const content = line.endsWith("\r") ? line.slice(0, -1) : line;
+    return { text: idx < lines.length - 1 ? `${content}\n` : content };
+  });
+}
+
+/** Show `group`'s file in the source split, scrolled to the hunk of
+ *  change `focusChange` if given, else hunk `focusHunk`; the panel keeps
+ *  focus. */
+async function showDiff(group: Group, focusHunk: number, focusChange?: number): Promise<void> {
+  if (!panel || group.path === null) return;
+  const generation = ++panel.diffGeneration;
+  const path = group.path;
+  const text = panel.texts[path] ?? "";
+  const { newText, hunks, hunkOf } = await fileDiff(text, group);
+  if (!panel || panel.diffGeneration !== generation) return;
+  if (focusChange !== undefined) focusHunk = hunkOf.get(focusChange) ?? 0;
+  const file = relative(path);
+
+  const oldResult = await editor.createVirtualBuffer({
+    name: `*Before:${file}*`,
+    mode: "normal",
+    readOnly: true,
+    entries: contentToEntries(text),
+    showLineNumbers: true,
+    editingDisabled: true,
+    hiddenFromTabs: true,
+  });
+  const newResult = await editor.createVirtualBuffer({
+    name: `*After:${file}*`,
+    mode: "normal",
+    readOnly: true,
+    entries: contentToEntries(newText),
+    showLineNumbers: true,
+    editingDisabled: true,
+    hiddenFromTabs: true,
+  });
+  if (!panel || panel.diffGeneration !== generation) {
+    editor.closeBuffer(oldResult.bufferId);
+    editor.closeBuffer(newResult.bufferId);
+    return;
+  }
+
+  const focus = hunks.length > 0 ? Math.max(0, Math.min(focusHunk, hunks.length - 1)) : undefined;
+  // The composite becomes the active buffer of the focused split.
+  editor.focusSplit(panel.sourceSplitId);
+  const compositeBufferId = await editor.createCompositeBuffer({
+    name: `*Refactor Preview: ${file}*`,
+    mode: "refactor-preview-diff",
+    layout: {
+      type: "side-by-side",
+      ratios: [0.5, 0.5],
+      showSeparator: true,
+    },
+    sources: [
+      {
+        bufferId: oldResult.bufferId,
+        label: editor.t("diff.old_label"),
+        editable: false,
+        style: { gutterStyle: "diff-markers" },
+      },
+      {
+        bufferId: newResult.bufferId,
+        label: editor.t("diff.new_label"),
+        editable: false,
+        style: { gutterStyle: "diff-markers" },
+      },
+    ],
+    hunks: hunks.length > 0 ? hunks : null,
+    initialFocusHunk: focus,
+  });
+  if (!panel || panel.diffGeneration !== generation) {
+    editor.closeCompositeBuffer(compositeBufferId);
+    editor.closeBuffer(oldResult.bufferId);
+    editor.closeBuffer(newResult.bufferId);
+    return;
+  }
+
+  // Swap the new view in before closing the old one, so the split never
+  // points at a closed buffer.
+  const previous = panel.diff;
+  panel.diff = {
+    compositeBufferId,
+    oldBufferId: oldResult.bufferId,
+    newBufferId: newResult.bufferId,
+    path,
+    focusHunk: focus ?? 0,
+    hunkOf,
+  };
+  editor.showBuffer(compositeBufferId);
+  editor.focusSplit(panel.splitId);
+  if (previous) closeDiffBuffers(previous);
+}
+
+/** Show the diff for tree row `index`, unless it is already on screen. */
+function showRow(index: number): void {
+  if (!panel) return;
+  const row = panel.rows[index];
+  if (!row || row.group.path === null) return;
+  const focusChange = row.type === "change" ? row.state.change.id : undefined;
+  const diff = panel.diff;
+  if (
+    diff && diff.path === row.group.path &&
+    (focusChange === undefined || diff.hunkOf.get(focusChange) === diff.focusHunk)
+  ) {
+    return;
+  }
+  void showDiff(row.group, 0, focusChange);
+}
+
+function closeDiffBuffers(diff: DiffState): void {
+  editor.closeCompositeBuffer(diff.compositeBufferId);
+  editor.closeBuffer(diff.oldBufferId);
+  editor.closeBuffer(diff.newBufferId);
+}
+
+/** Put the source split's buffer back in place of the diff. */
+function closeDiff(): void {
+  if (!panel?.diff) return;
+  const diff = panel.diff;
+  panel.diff = null;
+  panel.diffGeneration++;
+  editor.focusSplit(panel.sourceSplitId);
+  editor.showBuffer(panel.sourceBufferId);
+  closeDiffBuffers(diff);
+}
+
+// =============================================================================
+// Panel lifecycle
+// =============================================================================
+
+editor.on("workspace_edit_preview", async (data) => {
+  const groups = groupChanges(data.changes);
+
+  if (panel) {
+    // A newer edit replaces the one on screen; the editor has already
+    // dropped the old one.
+    closeDiff();
+    panel.previewId = data.id;
+    panel.title = data.title;
+    panel.groups = groups;
+    panel.texts = data.texts;
+    panel.widget.unmount();
+  } else {
+    const sourceSplitId = editor.getActiveSplitId();
+    const sourceBufferId = editor.getActiveBufferId();
+    try {
+      const result = await editor.createVirtualBufferInSplit({
+        name: "*Refactor Preview*",
+        mode: "refactor-preview",
+        readOnly: true,
+        entries: [],
+        ratio: 0.5,
+        panelId: "refactor-preview-panel",
+        role: "utility_dock",
+        showLineNumbers: false,
+        showCursors: false,
+        editingDisabled: true,
+      });
+      panel = {
+        bufferId: result.bufferId,
+        splitId: result.splitId ?? editor.getActiveSplitId(),
+        sourceSplitId,
+        sourceBufferId,
+        widget: new WidgetPanel(result.bufferId),
+        previewId: data.id,
+        title: data.title,
+        groups,
+        texts: data.texts,
+        rows: [],
+        diff: null,
+        diffGeneration: 0,
+      };
+    } catch (e) {
+      editor.setStatus(editor.t("status.failed_open_panel"));
+      editor.debug(`refactor_preview: createVirtualBufferInSplit failed: ${e}`);
+      // Nothing can be reviewed, so nothing is applied.
+      editor.cancelWorkspaceEditPreview(data.id);
+      return;
+    }
+  }
+
+  render();
+  const first = groups.find((g) => g.path !== null);
+  if (first) void showDiff(first, 0);
+});
+
+editor.on("buffer_closed", (args) => {
+  if (!panel) return;
+  if (panel.diff && args.buffer_id === panel.diff.compositeBufferId) {
+    // Closed from outside the plugin (e.g. its tab); drop the sources too.
+    const { oldBufferId, newBufferId } = panel.diff;
+    panel.diff = null;
+    editor.closeBuffer(oldBufferId);
+    editor.closeBuffer(newBufferId);
+  }
+  if (args.buffer_id === panel.bufferId) {
+    closeDiff();
+    const { previewId, widget } = panel;
+    panel = null;
+    widget.unmount();
+    // Closing the panel any other way than applying discards the edit.
+    editor.cancelWorkspaceEditPreview(previewId);
+  }
+});
+
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widget.id()) return;
+  if (args.widget_key !== TREE_KEY) return;
+  const payload = args.payload as { index?: number; checked?: boolean } | undefined;
+  if (typeof payload?.index !== "number") return;
+  if (args.event_type === "toggle" && typeof payload.checked === "boolean") {
+    toggleRow(payload.index, payload.checked);
+  } else if (args.event_type === "select") {
+    showRow(payload.index);
+  }
+});
+
+// =============================================================================
+// Key handlers
+// =============================================================================
+
+function dispatch(name: string): void {
+  panel?.widget.command(widgetKey(name));
+}
+
+registerHandler("refactor_preview_up", () => dispatch("Up"));
+registerHandler("refactor_preview_down", () => dispatch("Down"));
+registerHandler("refactor_preview_page_up", () => dispatch("PageUp"));
+registerHandler("refactor_preview_page_down", () => dispatch("PageDown"));
+registerHandler("refactor_preview_left", () => dispatch("Left"));
+registerHandler("refactor_preview_right", () => dispatch("Right"));
+// On a checkable tree the host answers Space with a `toggle` event.
+registerHandler("refactor_preview_toggle", () => dispatch("Space"));
+
+registerHandler("refactor_preview_diff_next_hunk", () => {
+  if (panel?.diff) editor.compositeNextHunk(panel.diff.compositeBufferId);
+});
+registerHandler("refactor_preview_diff_prev_hunk", () => {
+  if (panel?.diff) editor.compositePrevHunk(panel.diff.compositeBufferId);
+});
+registerHandler("refactor_preview_diff_back", () => {
+  if (panel) editor.focusSplit(panel.splitId);
+});
+
+/** Close the panel and the diff, handing the edit back to the editor
+ *  last. */
+function close(resolve: (id: number) => void): void {
+  if (!panel) return;
+  closeDiff();
+  const { bufferId, splitId, sourceSplitId, widget, previewId } = panel;
+  panel = null;
+  widget.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+  resolve(previewId);
+}
+
+function refactor_preview_apply(): void {
+  const excluded = excludedIds();
+  close((id) => editor.applyWorkspaceEditPreview(id, excluded));
+}
+registerHandler("refactor_preview_apply", refactor_preview_apply);
+
+function refactor_preview_discard(): void {
+  close((id) => editor.cancelWorkspaceEditPreview(id));
+}
+registerHandler("refactor_preview_discard", refactor_preview_discard);
+
+editor.debug("Refactor Preview plugin loaded");
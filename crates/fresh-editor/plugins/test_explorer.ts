@@ -0,0 +1,1088 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  key as widgetKey,
+  raw,
+  tree,
+  treeNode,
+  type TreeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+import { getRelativePath } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+/**
+ * Test Explorer Plugin
+ *
+ * Finds the tests of every test file you open and runs them without
+ * leaving the editor. One adapter per test framework knows how to spot
+ * tests in a file, how to run a selection of them and how to read the
+ * results back:
+ *
+ *   cargo test   `#[test]` functions, addressed by module path
+ *   pytest       `test_*` functions and `Test*` classes
+ *   go test      `func TestXxx(t *testing.T)`
+ *   jest         `test(...)` / `it(...)` inside `describe(...)`
+ *
+ * Results show as ✓ / ✗ in the gutter of the test's file, failures as
+ * inline diagnostics at the line the failure points to, and every
+ * discovered test is listed in a tree in the Utility Dock. "Debug Test
+ * at Cursor" starts the configured debugger on a single test through
+ * `editor.debugStart`.
+ */
+
+// =============================================================================
+// Types
+// =============================================================================
+
+type TestStatus = "passed" | "failed" | "skipped" | "running";
+
+interface TestItem {
+  /** Adapter-specific id handed back to the runner (e.g. `tests::adds`). */
+  id: string;
+  /** Name shown in the tree. */
+  label: string;
+  /** 0-indexed line of the declaration. */
+  line: number;
+}
+
+interface TestResult {
+  status: TestStatus;
+  /** Failure output, shown as the inline diagnostic. */
+  message?: string;
+  /** 0-indexed line in the test's file the failure points to. */
+  failureLine?: number;
+}
+
+interface RunCommand {
+  command: string;
+  args: string[];
+  cwd: string;
+}
+
+interface TestAdapter {
+  /** Whether `path` is a test file of this framework. */
+  matches(path: string): boolean;
+  /** Tests declared in `text`, the content of `path`. */
+  discover(path: string, text: string): TestItem[];
+  /** Command running exactly `tests`, all of them in `path`. */
+  command(path: string, tests: TestItem[]): RunCommand;
+  /** Results of a run, by test id. */
+  parse(path: string, result: SpawnResult): Map<string, TestResult>;
+  /** Launch configuration overrides that debug `test`; throws when the
+   *  test binary can't be prepared. */
+  debugConfiguration(path: string, test: TestItem): Promise<Record<string, unknown>>;
+}
+
+interface TestFile {
+  path: string;
+  adapter: TestAdapter;
+  tests: TestItem[];
+  /** Lines of the file when its tests were discovered, for ranges. */
+  lines: string[];
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+function escapeRegex(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+function normalize(path: string): string {
+  return path.replace(/\\/g, "/");
+}
+
+/** Whether `path` names the same file as `reported`, which may be
+ *  relative to some directory above it. */
+function samePath(path: string, reported: string): boolean {
+  const a = normalize(path);
+  const b = normalize(reported).replace(/^\.\//, "");
+  return a === b || a.endsWith(`/${b}`);
+}
+
+/** Closest directory at or above `dir` that contains `file`. */
+function findUp(dir: string, file: string): string | null {
+  let current = dir;
+  for (;;) {
+    if (editor.fileExists(editor.pathJoin(current, file))) return current;
+    const parent = editor.pathDirname(current);
+    if (!parent || parent === current) return null;
+    current = parent;
+  }
+}
+
+/** Track `{`/`}` nesting line by line; `scopes` entries opened at a
+ *  depth are dropped once the depth falls back to it. */
+function updateDepth<T extends { depth: number }>(
+  line: string,
+  depth: number,
+  scopes: T[],
+): number {
+  for (const ch of line) {
+    if (ch === "{") {
+      depth++;
+    } else if (ch === "}") {
+      depth--;
+      while (scopes.length > 0 && scopes[scopes.length - 1].depth >= depth) {
+        scopes.pop();
+      }
+    }
+  }
+  return depth;
+}
+
+/** The line of a failed run worth showing when nothing was parsed. */
+function errorSummary(result: SpawnResult): string {
+  const text = (result.stderr.trim() || result.stdout.trim());
+  const lines = text.split("\n");
+  return lines.find((l) => /\berror\b/i.test(l))?.trim() ??
+    lines[lines.length - 1]?.trim() ??
+    "";
+}
+
+// =============================================================================
+// Adapters
+// =============================================================================
+
+interface RustTarget {
+  crateDir: string;
+  /** `cargo test` arguments selecting the test binary. */
+  targetArgs: string[];
+  /** Module path of the file inside its binary. */
+  modules: string[];
+}
+
+const RUST_TEST_ATTR = /#\[(?:[\w:]+::)?test\b/;
+const RUST_ATTRS = /#\[[^\]]*\]/g;
+const RUST_FN = /^\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?(?:unsafe\s+)?fn\s+(\w+)/;
+const RUST_MOD = /^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*\{/;
+
+function rustTarget(path: string): RustTarget | null {
+  const crateDir = findUp(editor.pathDirname(path), "Cargo.toml");
+  if (!crateDir) return null;
+  const parts = normalize(path)
+    .slice(normalize(crateDir).length + 1)
+    .replace(/\.rs$/, "")
+    .split("/");
+  const modulesOf = (segments: string[]): string[] => {
+    const last = segments[segments.length - 1];
+    return last === "mod" ? segments.slice(0, -1) : segments;
+  };
+  if (parts[0] === "tests" && parts.length >= 2) {
+    return { crateDir, targetArgs: ["--test", parts[1]], modules: modulesOf(parts.slice(2)) };
+  }
+  if (parts[0] === "src" && parts[1] === "bin" && parts.length >= 3) {
+    return { crateDir, targetArgs: ["--bin", parts[2]], modules: modulesOf(parts.slice(3)) };
+  }
+  if (parts[0] === "src") {
+    const rest = parts.slice(1);
+    const top = rest.length === 1 && (rest[0] === "lib" || rest[0] === "main");
+    return { crateDir, targetArgs: [], modules: top ? [] : modulesOf(rest) };
+  }
+  return { crateDir, targetArgs: [], modules: [] };
+}
+
+interface CargoArtifact {
+  reason?: string;
+  executable?: string | null;
+  profile?: { test?: boolean };
+  target?: { name?: string; kind?: string[] };
+}
+
+const cargoAdapter: TestAdapter = {
+  matches: (path) => path.endsWith(".rs") && rustTarget(path) !== null,
+
+  discover(path, text) {
+    const prefix = rustTarget(path)?.modules ?? [];
+    const tests: TestItem[] = [];
+    const mods: { name: string; depth: number }[] = [];
+    let depth = 0;
+    let pendingTest = false;
+    text.split("\n").forEach((source, index) => {
+      const line = source.replace(/\/\/.*$/, "");
+      if (RUST_TEST_ATTR.test(line)) pendingTest = true;
+      if (pendingTest) {
+        const fn = RUST_FN.exec(line.replace(RUST_ATTRS, ""));
+        if (fn) {
+          const id = [...prefix, ...mods.map((m) => m.name), fn[1]].join("::");
+          tests.push({ id, label: fn[1], line: index });
+          pendingTest = false;
+        }
+      }
+      const mod = RUST_MOD.exec(line);
+      if (mod) mods.push({ name: mod[1], depth });
+      depth = updateDepth(line, depth, mods);
+    });
+    return tests;
+  },
+
+  command(path, tests) {
+    const target = rustTarget(path)!;
+    return {
+      command: "cargo",
+      args: ["test", ...target.targetArgs, "--", "--exact", ...tests.map((t) => t.id)],
+      cwd: target.crateDir,
+    };
+  },
+
+  parse(path, result) {
+    const results = new Map<string, TestResult>();
+    for (const m of result.stdout.matchAll(/^test (\S+) \.\.\. (ok|FAILED|ignored)/gm)) {
+      const status = m[2] === "ok" ? "passed" : m[2] === "FAILED" ? "failed" : "skipped";
+      results.set(m[1], { status });
+    }
+    const sections = /^---- (\S+) stdout ----\n([\s\S]*?)(?=^---- |^failures:)/gm;
+    for (const m of result.stdout.matchAll(sections)) {
+      const body = m[2];
+      const location = /panicked at (?:'[\s\S]*?', )?([^\s:]+):(\d+):(\d+)/.exec(body);
+      const message = body
+        .split("\n")
+        .filter((l) => l.trim() && !l.startsWith("thread '") && !l.startsWith("note: "))
+        .join("\n");
+      results.set(m[1], {
+        status: "failed",
+        message: message || undefined,
+        failureLine: location && samePath(path, location[1]) ? Number(location[2]) - 1 : undefined,
+      });
+    }
+    return results;
+  },
+
+  async debugConfiguration(path, test) {
+    const target = rustTarget(path)!;
+    const build = await editor.spawnProcess(
+      "cargo",
+      ["test", "--no-run", "--message-format=json", ...target.targetArgs],
+      target.crateDir,
+    );
+    if (build.exit_code !== 0) throw new Error(errorSummary(build));
+    const artifacts: CargoArtifact[] = [];
+    for (const line of build.stdout.split("\n")) {
+      try {
+        const artifact = JSON.parse(line) as CargoArtifact;
+        if (artifact.reason === "compiler-artifact" && artifact.executable && artifact.profile?.test) {
+          artifacts.push(artifact);
+        }
+      } catch {
+        // Not a JSON message (cargo prints progress on stderr only, but
+        // build scripts may write to stdout).
+      }
+    }
+    const [flag, name] = target.targetArgs;
+    const kinds = flag === "--test" ? ["test"] : flag === "--bin" ? ["bin"] : ["lib", "bin"];
+    const artifact = kinds
+      .map((kind) =>
+        artifacts.find((a) =>
+          (a.target?.kind ?? []).includes(kind) && (name === undefined || a.target?.name === name)
+        )
+      )
+      .find((a) => a !== undefined);
+    if (!artifact?.executable) throw new Error(editor.t("status.no_test_binary"));
+    return {
+      program: artifact.executable,
+      args: [test.id, "--exact", "--nocapture"],
+      cwd: target.crateDir,
+    };
+  },
+};
+
+const PY_CLASS = /^(\s*)class\s+(Test\w*)\b/;
+const PY_DEF = /^(\s*)(?:async\s+)?def\s+(test\w*)\s*\(/;
+
+/** pytest node id of `test` in `path`, relative to the working directory. */
+function pytestNodeId(path: string, test: TestItem): string {
+  return `${normalize(getRelativePath(editor, path))}::${test.id}`;
+}
+
+const pytestAdapter: TestAdapter = {
+  matches: (path) => /(^|[\\/])(test_[^\\/]*|[^\\/]*_test)\.py$/.test(path),
+
+  discover(_path, text) {
+    const tests: TestItem[] = [];
+    let cls: { name: string; indent: number } | null = null;
+    text.split("\n").forEach((line, index) => {
+      const trimmed = line.trim();
+      if (!trimmed || trimmed.startsWith("#") || trimmed.startsWith("@")) return;
+      const indent = line.length - line.trimStart().length;
+      if (cls && indent <= cls.indent) cls = null;
+      const klass = PY_CLASS.exec(line);
+      if (klass) {
+        cls = { name: klass[2], indent: klass[1].length };
+        return;
+      }
+      const def = PY_DEF.exec(line);
+      if (!def) return;
+      if (def[1].length === 0) {
+        tests.push({ id: def[2], label: def[2], line: index });
+      } else if (cls) {
+        const id = `${cls.name}::${def[2]}`;
+        tests.push({ id, label: id, line: index });
+      }
+    });
+    return tests;
+  },
+
+  command(path, tests) {
+    return {
+      command: "pytest",
+      args: ["-v", "-rfE", "--tb=short", "--no-header", ...tests.map((t) => pytestNodeId(path, t))],
+      cwd: editor.getCwd(),
+    };
+  },
+
+  parse(path, result) {
+    const results = new Map<string, TestResult>();
+    // Parametrized cases (`test_a[1]`) fold into their function; any
+    // failing case fails it.
+    const setStatus = (id: string, status: TestStatus) => {
+      if (results.get(id)?.status !== "failed") results.set(id, { status });
+    };
+    const outcomes = /^\S+?::(\S+) (PASSED|FAILED|ERROR|SKIPPED|XFAIL|XPASS)\b/gm;
+    for (const m of result.stdout.matchAll(outcomes)) {
+      const id = m[1].replace(/\[.*\]$/, "");
+      const status = m[2] === "FAILED" || m[2] === "ERROR"
+        ? "failed"
+        : m[2] === "SKIPPED"
+        ? "skipped"
+        : "passed";
+      setStatus(id, status);
+    }
+    // `____ TestClass.test_b ____` headers open each failure's traceback.
+    const parts = result.stdout.split(/^_{3,} (\S+) _{3,}$/m);
+    for (let i = 1; i + 1 < parts.length; i += 2) {
+      const id = parts[i].replace(/\[.*\]$/, "").replace(".", "::");
+      const body = parts[i + 1];
+      const message = body
+        .split("\n")
+        .filter((l) => l.startsWith("E "))
+        .map((l) => l.slice(1).trim())
+        .join("\n");
+      let failureLine: number | undefined;
+      for (const m of body.matchAll(/^(\S+\.py):(\d+): /gm)) {
+        if (samePath(path, m[1])) failureLine = Number(m[2]) - 1;
+      }
+      results.set(id, { status: "failed", message: message || undefined, failureLine });
+    }
+    return results;
+  },
+
+  debugConfiguration(path, test) {
+    return Promise.resolve({
+      program: null,
+      module: "pytest",
+      args: [pytestNodeId(path, test)],
+      cwd: editor.getCwd(),
+    });
+  },
+};
+
+const GO_TEST = /^func\s+(Test\w*)\s*\(\s*\w+\s+\*testing\.T\s*\)/;
+
+interface GoTestEvent {
+  Action?: string;
+  Test?: string;
+  Output?: string;
+}
+
+const goAdapter: TestAdapter = {
+  matches: (path) => path.endsWith("_test.go"),
+
+  discover(_path, text) {
+    const tests: TestItem[] = [];
+    text.split("\n").forEach((line, index) => {
+      const m = GO_TEST.exec(line);
+      if (m) tests.push({ id: m[1], label: m[1], line: index });
+    });
+    return tests;
+  },
+
+  command(path, tests) {
+    return {
+      command: "go",
+      args: ["test", "-json", "-run", `^(${tests.map((t) => t.id).join("|")})$`, "."],
+      cwd: editor.pathDirname(path),
+    };
+  },
+
+  parse(path, result) {
+    const results = new Map<string, TestResult>();
+    const output = new Map<string, string[]>();
+    for (const line of result.stdout.split("\n")) {
+      let event: GoTestEvent;
+      try {
+        event = JSON.parse(line) as GoTestEvent;
+      } catch {
+        continue;
+      }
+      if (!event.Test) continue;
+      // Subtests report under their parent.
+      const id = event.Test.split("/")[0];
+      if (event.Action === "output" && event.Output) {
+        output.set(id, [...(output.get(id) ?? []), event.Output]);
+      } else if (event.Test === id && event.Action === "pass") {
+        results.set(id, { status: "passed" });
+      } else if (event.Test === id && event.Action === "skip") {
+        results.set(id, { status: "skipped" });
+      } else if (event.Test === id && event.Action === "fail") {
+        const lines = (output.get(id) ?? [])
+          .map((l) => l.trimEnd())
+          .filter((l) => l.trim() && !/^\s*(===|---) /.test(l));
+        let failureLine: number | undefined;
+        for (const l of lines) {
+          const m = /^\s*(\S+\.go):(\d+): /.exec(l);
+          if (m && editor.pathBasename(path) === m[1]) {
+            failureLine = Number(m[2]) - 1;
+            break;
+          }
+        }
+        results.set(id, {
+          status: "failed",
+          message: lines.map((l) => l.trim()).join("\n") || undefined,
+          failureLine,
+        });
+      }
+    }
+    return results;
+  },
+
+  debugConfiguration(path, test) {
+    return Promise.resolve({
+      mode: "test",
+      program: editor.pathDirname(path),
+      args: ["-test.run", `^${test.id}$`],
+    });
+  },
+};
+
+const JEST_CALL =
+  /^\s*(describe|test|it)(?:\.only|\.skip|\.concurrent)?\s*\(\s*(['"`])((?:\\.|(?!\2).)*)\2/;
+
+interface JestAssertion {
+  fullName: string;
+  status: string;
+  failureMessages?: string[];
+}
+
+interface JestReport {
+  testResults?: { assertionResults?: JestAssertion[] }[];
+}
+
+/** `-t` pattern selecting exactly `tests`. */
+function jestPattern(tests: TestItem[]): string {
+  return `^(${tests.map((t) => escapeRegex(t.id)).join("|")})$`;
+}
+
+const jestAdapter: TestAdapter = {
+  matches: (path) => /\.(test|spec)\.[cm]?[jt]sx?$/.test(path),
+
+  discover(_path, text) {
+    const tests: TestItem[] = [];
+    const describes: { name: string; depth: number }[] = [];
+    let depth = 0;
+    text.split("\n").forEach((line, index) => {
+      const m = JEST_CALL.exec(line);
+      if (m && m[1] === "describe") {
+        describes.push({ name: m[3], depth });
+      } else if (m) {
+        const id = [...describes.map((d) => d.name), m[3]].join(" ");
+        tests.push({ id, label: m[3], line: index });
+      }
+      depth = updateDepth(line, depth, describes);
+    });
+    return tests;
+  },
+
+  command(path, tests) {
+    return {
+      command: "npx",
+      args: [
+        "jest",
+        "--json",
+        normalize(getRelativePath(editor, path)),
+        "-t",
+        jestPattern(tests),
+      ],
+      cwd: editor.getCwd(),
+    };
+  },
+
+  parse(path, result) {
+    const results = new Map<string, TestResult>();
+    const start = result.stdout.indexOf("{");
+    if (start < 0) return results;
+    let report: JestReport;
+    try {
+      report = JSON.parse(result.stdout.slice(start)) as JestReport;
+    } catch {
+      return results;
+    }
+    const location = new RegExp(`${escapeRegex(editor.pathBasename(path))}:(\\d+):\\d+`);
+    for (const file of report.testResults ?? []) {
+      for (const assertion of file.assertionResults ?? []) {
+        if (assertion.status === "passed") {
+          results.set(assertion.fullName, { status: "passed" });
+        } else if (assertion.status === "failed") {
+          const text = (assertion.failureMessages ?? []).join("\n").replace(/\u001b\[[0-9;]*m/g, "");
+          const line = location.exec(text);
+          results.set(assertion.fullName, {
+            status: "failed",
+            message: text.split("\n    at ")[0].trim() || undefined,
+            failureLine: line ? Number(line[1]) - 1 : undefined,
+          });
+        } else {
+          results.set(assertion.fullName, { status: "skipped" });
+        }
+      }
+    }
+    return results;
+  },
+
+  debugConfiguration(path, test) {
+    return Promise.resolve({
+      program: "${workspaceFolder}/node_modules/jest/bin/jest.js",
+      args: ["--runInBand", normalize(getRelativePath(editor, path)), "-t", jestPattern([test])],
+      cwd: editor.getCwd(),
+    });
+  },
+};
+
+const ADAPTERS: TestAdapter[] = [cargoAdapter, pytestAdapter, goAdapter, jestAdapter];
+
+// =============================================================================
+// State
+// =============================================================================
+
+interface PanelState {
+  bufferId: number;
+  splitId: number;
+  sourceSplitId: number;
+  widget: WidgetPanel;
+  /** Tree key of the selected row. */
+  selectedKey: string | null;
+  /** File rows the user has expanded (all of them, unless collapsed). */
+  expanded: Set<string>;
+}
+
+/** Test files seen so far, by path. */
+const files = new Map<string, TestFile>();
+/** Latest result of each test, by `resultKey`. */
+const results = new Map<string, TestResult>();
+let lastRun: { path: string; ids: string[] } | null = null;
+let panel: PanelState | null = null;
+
+const TREE_KEY = "tests";
+const MARK_NAMESPACE = "test-results";
+const DIAGNOSTIC_SOURCE = "tests";
+
+const MARKS: Record<TestStatus, { symbol: string; color: [number, number, number] }> = {
+  passed: { symbol: "✓", color: [80, 200, 120] },
+  failed: { symbol: "✗", color: [229, 72, 77] },
+  skipped: { symbol: "○", color: [128, 128, 128] },
+  running: { symbol: "●", color: [229, 192, 123] },
+};
+/** Below breakpoints, so a breakpoint on a test line stays visible. */
+const MARK_PRIORITY = 15;
+
+const COLORS = {
+  title: "syntax.keyword" as OverlayColorSpec,
+  file: "syntax.type" as OverlayColorSpec,
+  muted: "editor.line_number_fg" as OverlayColorSpec,
+  passed: "diagnostic.info_fg" as OverlayColorSpec,
+  failed: "diagnostic.error_fg" as OverlayColorSpec,
+};
+
+function resultKey(path: string, id: string): string {
+  return `${path}#${id}`;
+}
+
+function fileKey(path: string): string {
+  return `f:${path}`;
+}
+
+function testKey(path: string, id: string): string {
+  return `t:${resultKey(path, id)}`;
+}
+
+function resultOf(file: TestFile, test: TestItem): TestResult | undefined {
+  return results.get(resultKey(file.path, test.id));
+}
+
+// =============================================================================
+// Discovery
+// =============================================================================
+
+function adapterFor(path: string): TestAdapter | undefined {
+  return ADAPTERS.find((adapter) => adapter.matches(path));
+}
+
+/** (Re)discover the tests of `path` from the text of its buffer. */
+async function discoverBuffer(bufferId: number): Promise<TestFile | undefined> {
+  const path = editor.getBufferPath(bufferId);
+  const adapter = path ? adapterFor(path) : undefined;
+  if (!path || !adapter) return undefined;
+  const text = await editor.getBufferText(bufferId, 0, editor.getBufferLength(bufferId));
+  const tests = adapter.discover(path, text);
+  if (tests.length === 0) {
+    files.delete(path);
+  } else {
+    files.set(path, { path, adapter, tests, lines: text.split("\n") });
+  }
+  render();
+  applyMarks(path);
+  return files.get(path);
+}
+
+// =============================================================================
+// Gutter marks and diagnostics
+// =============================================================================
+
+function applyMarks(path: string): void {
+  const bufferId = editor.findBufferByPath(path);
+  if (!bufferId) return;
+  editor.clearLineIndicators(bufferId, MARK_NAMESPACE);
+  const file = files.get(path);
+  const diagnostics: PluginDiagnostic[] = [];
+  if (file) {
+    const lines: Record<TestStatus, number[]> = { passed: [], failed: [], skipped: [], running: [] };
+    for (const test of file.tests) {
+      const result = resultOf(file, test);
+      if (!result) continue;
+      lines[result.status].push(test.line);
+      if (result.status !== "failed") continue;
+      const line = result.failureLine ?? test.line;
+      const text = file.lines[line] ?? "";
+      diagnostics.push({
+        message: result.message ?? editor.t("diag.failed", { name: test.label }),
+        severity: 1,
+        range: {
+          start: { line, character: text.length - text.trimStart().length },
+          end: { line, character: text.trimEnd().length },
+        },
+      });
+    }
+    for (const status of Object.keys(lines) as TestStatus[]) {
+      if (lines[status].length === 0) continue;
+      const { symbol, color } = MARKS[status];
+      editor.setLineIndicators(
+        bufferId,
+        lines[status],
+        MARK_NAMESPACE,
+        symbol,
+        color[0],
+        color[1],
+        color[2],
+        MARK_PRIORITY,
+      );
+    }
+  }
+  editor.setDiagnostics(bufferId, DIAGNOSTIC_SOURCE, diagnostics);
+}
+
+// =============================================================================
+// Running
+// =============================================================================
+
+async function runTests(file: TestFile, tests: TestItem[]): Promise<void> {
+  if (tests.length === 0) {
+    editor.setStatus(editor.t("status.no_tests"));
+    return;
+  }
+  lastRun = { path: file.path, ids: tests.map((t) => t.id) };
+  for (const test of tests) {
+    results.set(resultKey(file.path, test.id), { status: "running" });
+  }
+  render();
+  applyMarks(file.path);
+  editor.setStatus(editor.t("status.running", { count: String(tests.length) }));
+
+  const { command, args, cwd } = file.adapter.command(file.path, tests);
+  let result: SpawnResult;
+  try {
+    result = await editor.spawnProcess(command, args, cwd);
+  } catch (e) {
+    result = { stdout: "", stderr: String(e), exit_code: -1 };
+  }
+  const parsed = file.adapter.parse(file.path, result);
+  // Nothing parsed from a failed run: the tests never ran (build error,
+  // missing tool). Fail them all with the error so it is visible.
+  const buildError = parsed.size === 0 && result.exit_code !== 0
+    ? errorSummary(result) || editor.t("status.run_failed", { command })
+    : null;
+
+  let failed = 0;
+  for (const test of tests) {
+    const key = resultKey(file.path, test.id);
+    const outcome = parsed.get(test.id) ??
+      (buildError !== null ? { status: "failed" as const, message: buildError } : undefined);
+    if (outcome) {
+      results.set(key, outcome);
+      if (outcome.status === "failed") failed++;
+    } else {
+      results.delete(key);
+    }
+  }
+  render();
+  applyMarks(file.path);
+  editor.setStatus(
+    failed > 0
+      ? editor.t("status.failed", { failed: String(failed), total: String(tests.length) })
+      : editor.t("status.passed", { passed: String(tests.length) }),
+  );
+}
+
+async function debugTest(file: TestFile, test: TestItem): Promise<void> {
+  let configuration: Record<string, unknown>;
+  try {
+    editor.setStatus(editor.t("status.preparing_debug", { name: test.label }));
+    configuration = await file.adapter.debugConfiguration(file.path, test);
+  } catch (e) {
+    editor.setStatus(editor.t("status.debug_failed", { error: e instanceof Error ? e.message : String(e) }));
+    return;
+  }
+  // The session uses the debugger of the active buffer's language.
+  if (panel) editor.focusSplit(panel.sourceSplitId);
+  editor.openFile(file.path, test.line + 1, 1);
+  editor.debugStart(configuration);
+}
+
+/** The test file of the active buffer, freshly discovered. */
+async function activeTestFile(): Promise<TestFile | undefined> {
+  const bufferId = editor.getActiveBufferId();
+  const path = editor.getBufferPath(bufferId);
+  if (!path || !adapterFor(path)) {
+    editor.setStatus(editor.t("status.no_adapter"));
+    return undefined;
+  }
+  const file = await discoverBuffer(bufferId);
+  if (!file) editor.setStatus(editor.t("status.no_tests"));
+  return file;
+}
+
+/** The test declared closest above the cursor. */
+function testAtCursor(file: TestFile): TestItem | undefined {
+  const line = editor.getPrimaryCursor()?.line;
+  if (line === null || line === undefined) return undefined;
+  let found: TestItem | undefined;
+  for (const test of file.tests) {
+    if (test.line <= line && (!found || test.line > found.line)) found = test;
+  }
+  return found;
+}
+
+// =============================================================================
+// Tree panel
+// =============================================================================
+
+function statusGlyph(result: TestResult | undefined): string {
+  return result ? MARKS[result.status].symbol : "·";
+}
+
+function fileEntry(file: TestFile): TextPropertyEntry {
+  const statuses = file.tests.map((t) => resultOf(file, t)?.status);
+  const passed = statuses.filter((s) => s === "passed").length;
+  const failed = statuses.filter((s) => s === "failed").length;
+  const name = getRelativePath(editor, file.path);
+  const summary = passed + failed > 0
+    ? `  ${editor.t("panel.summary", { passed: String(passed), failed: String(failed) })}`
+    : "";
+  const text = name + summary;
+  return {
+    text,
+    inlineOverlays: [
+      { start: 0, end: name.length, style: { fg: COLORS.file, bold: true } },
+      { start: name.length, end: text.length, style: { fg: failed > 0 ? COLORS.failed : COLORS.muted } },
+    ],
+  };
+}
+
+function testEntry(file: TestFile, test: TestItem): TextPropertyEntry {
+  const result = resultOf(file, test);
+  const glyph = statusGlyph(result);
+  const text = `${glyph} ${test.label}`;
+  const color = result?.status === "failed"
+    ? COLORS.failed
+    : result?.status === "passed"
+    ? COLORS.passed
+    : COLORS.muted;
+  return {
+    text,
+    inlineOverlays: [{ start: 0, end: glyph.length, style: { fg: color } }],
+  };
+}
+
+function sortedFiles(): TestFile[] {
+  return [...files.values()].sort((a, b) => (a.path < b.path ? -1 : 1));
+}
+
+function render(): void {
+  if (!panel) return;
+  const nodes: TreeNode[] = [];
+  const keys: string[] = [];
+  const newlyExpanded: string[] = [];
+  for (const file of sortedFiles()) {
+    const key = fileKey(file.path);
+    nodes.push(treeNode(fileEntry(file), { depth: 0, hasChildren: true }));
+    keys.push(key);
+    if (!panel.expanded.has(key) && !panel.expanded.has(`-${key}`)) {
+      panel.expanded.add(key);
+      newlyExpanded.push(key);
+    }
+    for (const test of file.tests) {
+      nodes.push(treeNode(testEntry(file, test), { depth: 1 }));
+      keys.push(testKey(file.path, test.id));
+    }
+  }
+  if (panel.selectedKey === null || !keys.includes(panel.selectedKey)) {
+    panel.selectedKey = keys[0] ?? null;
+  }
+
+  const header: TextPropertyEntry[] = [
+    { text: `${editor.t("panel.title")}\n`, style: { fg: COLORS.title, bold: true } },
+    { text: `${editor.t("panel.hint")}\n`, style: { fg: COLORS.muted } },
+  ];
+  if (nodes.length === 0) {
+    header.push({ text: `${editor.t("panel.empty")}\n`, style: { fg: COLORS.muted, italic: true } });
+  }
+  const vp = editor.getViewport();
+  const visibleRows = Math.max(3, (vp && vp.height > 0 ? vp.height : 30) - 3);
+  const expandedKeys = [...panel.expanded].filter((k) => !k.startsWith("-"));
+  panel.widget.set(
+    col(
+      raw(header, "header"),
+      tree({
+        nodes,
+        itemKeys: keys,
+        selectedIndex: 0,
+        visibleRows,
+        expandedKeys,
+        key: TREE_KEY,
+      }),
+    ),
+  );
+  // Files discovered after the tree was mounted start expanded too.
+  if (newlyExpanded.length > 0) {
+    panel.widget.setExpandedKeys(TREE_KEY, expandedKeys);
+  }
+}
+
+/** File and test a tree key refers to. */
+function lookup(key: string | null): { file: TestFile; test?: TestItem } | undefined {
+  if (!key) return undefined;
+  for (const file of files.values()) {
+    if (key === fileKey(file.path)) return { file };
+    const test = file.tests.find((t) => key === testKey(file.path, t.id));
+    if (test) return { file, test };
+  }
+  return undefined;
+}
+
+async function test_explorer_open(): Promise<void> {
+  if (panel) {
+    editor.focusSplit(panel.splitId);
+    return;
+  }
+  // Pick up the file being edited right away.
+  const sourceBufferId = editor.getActiveBufferId();
+  const sourceSplitId = editor.getActiveSplitId();
+  try {
+    const result = await editor.createVirtualBufferInSplit({
+      name: "*Tests*",
+      mode: "test-explorer",
+      readOnly: true,
+      entries: [],
+      ratio: 0.6,
+      panelId: "test-explorer-panel",
+      role: "utility_dock",
+      showLineNumbers: false,
+      showCursors: false,
+      editingDisabled: true,
+    });
+    panel = {
+      bufferId: result.bufferId,
+      splitId: result.splitId ?? editor.getActiveSplitId(),
+      sourceSplitId,
+      widget: new WidgetPanel(result.bufferId),
+      selectedKey: null,
+      expanded: new Set(),
+    };
+  } catch (e) {
+    editor.setStatus(editor.t("status.failed_open_panel"));
+    editor.debug(`test_explorer: createVirtualBufferInSplit failed: ${e}`);
+    return;
+  }
+  render();
+  await discoverBuffer(sourceBufferId);
+}
+registerHandler("test_explorer_open", test_explorer_open);
+
+function test_explorer_close(): void {
+  if (!panel) return;
+  const { bufferId, splitId, sourceSplitId, widget } = panel;
+  panel = null;
+  widget.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+}
+registerHandler("test_explorer_close", test_explorer_close);
+
+function test_explorer_run_selected(): void {
+  const target = lookup(panel?.selectedKey ?? null);
+  if (!target) return;
+  void runTests(target.file, target.test ? [target.test] : target.file.tests);
+}
+registerHandler("test_explorer_run_selected", test_explorer_run_selected);
+
+function test_explorer_debug_selected(): void {
+  const target = lookup(panel?.selectedKey ?? null);
+  if (!target?.test) return;
+  void debugTest(target.file, target.test);
+}
+registerHandler("test_explorer_debug_selected", test_explorer_debug_selected);
+
+async function test_explorer_run_all(): Promise<void> {
+  await Promise.all(sortedFiles().map((file) => runTests(file, file.tests)));
+}
+registerHandler("test_explorer_run_all", test_explorer_run_all);
+
+function dispatch(name: string): void {
+  panel?.widget.command(widgetKey(name));
+}
+
+registerHandler("test_explorer_up", () => dispatch("Up"));
+registerHandler("test_explorer_down", () => dispatch("Down"));
+registerHandler("test_explorer_page_up", () => dispatch("PageUp"));
+registerHandler("test_explorer_page_down", () => dispatch("PageDown"));
+registerHandler("test_explorer_left", () => dispatch("Left"));
+registerHandler("test_explorer_right", () => dispatch("Right"));
+registerHandler("test_explorer_enter", () => dispatch("Enter"));
+
+editor.defineMode(
+  "test-explorer",
+  [
+    ["Up", "test_explorer_up"],
+    ["Down", "test_explorer_down"],
+    ["k", "test_explorer_up"],
+    ["j", "test_explorer_down"],
+    ["PageUp", "test_explorer_page_up"],
+    ["PageDown", "test_explorer_page_down"],
+    ["Left", "test_explorer_left"],
+    ["Right", "test_explorer_right"],
+    ["Return", "test_explorer_enter"],
+    ["r", "test_explorer_run_selected"],
+    ["d", "test_explorer_debug_selected"],
+    ["a", "test_explorer_run_all"],
+    ["q", "test_explorer_close"],
+    ["Escape", "test_explorer_close"],
+  ],
+  true, // read-only
+);
+
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widget.id()) return;
+  if (args.widget_key !== TREE_KEY) return;
+  const payload = args.payload as { key?: string; expanded?: boolean } | undefined;
+  const key = typeof payload?.key === "string" ? payload.key : null;
+  if (!key) return;
+
+  if (args.event_type === "select") {
+    panel.selectedKey = key;
+  } else if (args.event_type === "expand") {
+    // `-key` remembers a collapse, so re-renders don't expand it again.
+    panel.expanded.delete(payload?.expanded ? `-${key}` : key);
+    panel.expanded.add(payload?.expanded ? key : `-${key}`);
+  } else if (args.event_type === "activate") {
+    panel.selectedKey = key;
+    const target = lookup(key);
+    if (!target) return;
+    editor.openFileInSplit(
+      panel.sourceSplitId,
+      target.file.path,
+      (target.test?.line ?? 0) + 1,
+      1,
+    );
+  }
+});
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+async function test_run_at_cursor(): Promise<void> {
+  const file = await activeTestFile();
+  if (!file) return;
+  const test = testAtCursor(file);
+  if (!test) {
+    editor.setStatus(editor.t("status.no_test_at_cursor"));
+    return;
+  }
+  await runTests(file, [test]);
+}
+registerHandler("test_run_at_cursor", test_run_at_cursor);
+
+async function test_debug_at_cursor(): Promise<void> {
+  const file = await activeTestFile();
+  if (!file) return;
+  const test = testAtCursor(file);
+  if (!test) {
+    editor.setStatus(editor.t("status.no_test_at_cursor"));
+    return;
+  }
+  await debugTest(file, test);
+}
+registerHandler("test_debug_at_cursor", test_debug_at_cursor);
+
+async function test_run_file(): Promise<void> {
+  const file = await activeTestFile();
+  if (file) await runTests(file, file.tests);
+}
+registerHandler("test_run_file", test_run_file);
+
+async function test_rerun_last(): Promise<void> {
+  const run = lastRun;
+  const file = run ? files.get(run.path) : undefined;
+  if (!run || !file) {
+    editor.setStatus(editor.t("status.no_last_run"));
+    return;
+  }
+  await runTests(file, file.tests.filter((t) => run.ids.includes(t.id)));
+}
+registerHandler("test_rerun_last", test_rerun_last);
+
+editor.registerCommand("%cmd.show_test_explorer", "%cmd.show_test_explorer_desc", "test_explorer_open", null);
+editor.registerCommand("%cmd.run_test_at_cursor", "%cmd.run_test_at_cursor_desc", "test_run_at_cursor", null);
+editor.registerCommand("%cmd.debug_test_at_cursor", "%cmd.debug_test_at_cursor_desc", "test_debug_at_cursor", null);
+editor.registerCommand("%cmd.run_tests_in_file", "%cmd.run_tests_in_file_desc", "test_run_file", null);
+editor.registerCommand("%cmd.rerun_last_tests", "%cmd.rerun_last_tests_desc", "test_rerun_last", null);
+
+// =============================================================================
+// Hooks
+// =============================================================================
+
+editor.on("after_file_open", (data) => {
+  void discoverBuffer(data.buffer_id);
+});
+
+editor.on("after_file_save", (data) => {
+  void discoverBuffer(data.buffer_id);
+});
+
+editor.on("buffer_activated", (data) => {
+  // Only files not seen yet; saves keep known ones current.
+  const path = editor.getBufferPath(data.buffer_id);
+  if (path && !files.has(path)) void discoverBuffer(data.buffer_id);
+});
+
+editor.on("buffer_closed", (args) => {
+  if (panel && args.buffer_id === panel.bufferId) {
+    panel.widget.unmount();
+    panel = null;
+  }
+});
+
+editor.debug("Test Explorer plugin loaded");
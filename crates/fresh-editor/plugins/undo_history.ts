@@ -0,0 +1,487 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  key as widgetKey,
+  raw,
+  tree,
+  treeNode,
+  type TreeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+import { getRelativePath } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+/**
+ * Undo History Plugin
+ *
+ * The undo tree of the active file in a sidebar left of the editor, in
+ * the spirit of Vim's undotree and Emacs' vundo. Editing after an undo
+ * doesn't throw the undone edits away: they stay in the tree as a
+ * branch, nested under the state they were made from, while the line of
+ * history that undo/redo walks runs straight down the left edge.
+ *
+ * Each state shows when it was reached and the edit that reached it; the
+ * current state is marked ● and the one saved to disk is tagged. Moving
+ * the selection previews the diff from the buffer to that state, and
+ * Enter jumps there — undoing and redoing as needed, across branches.
+ */
+
+// =============================================================================
+// Types
+// =============================================================================
+
+type UndoTree = NonNullable<Awaited<ReturnType<typeof editor.getUndoTree>>>;
+type UndoNode = UndoTree["nodes"][number];
+
+/** One row of the panel's tree. */
+interface Row {
+  /** State id; 0 is the initial state. */
+  id: number;
+  /** Null for the initial state. */
+  node: UndoNode | null;
+  depth: number;
+  /** Whether branches fork from this state. */
+  hasBranches: boolean;
+}
+
+interface PanelState {
+  bufferId: number;
+  splitId: number;
+  /** Split the file is shown in; jumps happen there. */
+  sourceSplitId: number;
+  widget: WidgetPanel;
+  /** File whose history is shown, or `null` when there is none. */
+  source: { bufferId: number; path: string } | null;
+  /** The tree as last fetched, or `null` before it arrives. */
+  tree: UndoTree | null;
+  rows: Row[];
+  /** State whose diff is previewed. */
+  selected: number | null;
+  /** Diff from the buffer to `selected`; `null` while it loads. */
+  preview: string[] | null;
+  /** Bumped on every refresh so stale answers are dropped. */
+  generation: number;
+  /** Bumped on every preview request, likewise. */
+  previewGeneration: number;
+}
+
+let panel: PanelState | null = null;
+
+const TREE_KEY = "undo-history";
+
+/** Quiet time after an edit before the tree is fetched again. */
+const REFRESH_DELAY_MS = 300;
+
+const COLORS = {
+  title: "syntax.keyword" as OverlayColorSpec,
+  time: "editor.line_number_fg" as OverlayColorSpec,
+  current: "syntax.function" as OverlayColorSpec,
+  saved: "syntax.string" as OverlayColorSpec,
+  hunk: "syntax.keyword" as OverlayColorSpec,
+  added: "editor.diff_add_bg" as OverlayColorSpec,
+  removed: "editor.diff_remove_bg" as OverlayColorSpec,
+};
+
+// =============================================================================
+// Mode
+// =============================================================================
+
+editor.defineMode(
+  "undo-history",
+  [
+    ["Up", "undo_history_up"],
+    ["Down", "undo_history_down"],
+    ["PageUp", "undo_history_page_up"],
+    ["PageDown", "undo_history_page_down"],
+    ["Left", "undo_history_left"],
+    ["Right", "undo_history_right"],
+    ["Return", "undo_history_enter"],
+    ["Escape", "undo_history_escape"],
+  ],
+  true, // read-only
+);
+
+// =============================================================================
+// Model
+// =============================================================================
+
+/**
+ * Rows of the tree, oldest first. Each line of history runs down at one
+ * depth; a state's first child (on the active line, when the state is)
+ * continues it, and every other child starts a branch one level deeper,
+ * listed right under the state it forks from.
+ */
+function layout(history: UndoTree): Row[] {
+  const children = new Map<number, UndoNode[]>();
+  for (const node of history.nodes) {
+    const siblings = children.get(node.parent);
+    if (siblings) {
+      siblings.push(node);
+    } else {
+      children.set(node.parent, [node]);
+    }
+  }
+
+  const rows: Row[] = [];
+  const addLine = (first: UndoNode | null, depth: number) => {
+    let node = first;
+    for (;;) {
+      const id = node ? node.id : 0;
+      const [next, ...branches] = children.get(id) ?? [];
+      rows.push({ id, node, depth, hasBranches: branches.length > 0 });
+      for (const branch of branches) addLine(branch, depth + 1);
+      if (!next) return;
+      node = next;
+    }
+  };
+  addLine(null, 0);
+  return rows;
+}
+
+function formatTime(timestamp: number): string {
+  const date = new Date(timestamp);
+  const pad = (n: number) => String(n).padStart(2, "0");
+  return `${pad(date.getHours())}:${pad(date.getMinutes())}:${pad(date.getSeconds())}`;
+}
+
+async function refresh(): Promise<void> {
+  if (!panel) return;
+  const generation = ++panel.generation;
+  const source = panel.source;
+  if (!source) {
+    panel.tree = null;
+    panel.rows = [];
+    render();
+    return;
+  }
+  const history = await editor.getUndoTree(source.bufferId);
+  if (!panel || panel.generation !== generation) return;
+  panel.tree = history;
+  panel.rows = history ? layout(history) : [];
+  // Preview the state the selection is on, or the current one.
+  const selected = panel.rows.some((row) => row.id === panel!.selected)
+    ? panel.selected
+    : history?.current ?? null;
+  render();
+  void showPreview(selected);
+}
+
+async function scheduleRefresh(): Promise<void> {
+  if (!panel) return;
+  const generation = ++panel.generation;
+  await editor.delay(REFRESH_DELAY_MS);
+  if (panel && panel.generation === generation) void refresh();
+}
+
+async function showPreview(id: number | null): Promise<void> {
+  if (!panel) return;
+  const generation = ++panel.previewGeneration;
+  panel.selected = id;
+  panel.preview = null;
+  const source = panel.source;
+  if (id === null || !source) {
+    render();
+    return;
+  }
+  const diff = await editor.getUndoStateDiff(source.bufferId, id);
+  if (!panel || panel.previewGeneration !== generation) return;
+  panel.preview = diff ?? [editor.t("panel.preview_unavailable")];
+  render();
+}
+
+/** Show the history of `bufferId`, shown in `splitId`, if it is a file.
+ *  Returns whether the source changed. */
+function setSource(bufferId: number, splitId: number): boolean {
+  if (!panel || bufferId === panel.bufferId) return false;
+  const info = editor.getBufferInfo(bufferId);
+  if (!info || info.is_virtual || !info.path) return false;
+  if (panel.source?.bufferId === bufferId) return false;
+  panel.source = { bufferId, path: info.path };
+  panel.sourceSplitId = splitId;
+  panel.tree = null;
+  panel.rows = [];
+  panel.selected = null;
+  panel.preview = null;
+  return true;
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function rowEntry(row: Row, history: UndoTree): TextPropertyEntry {
+  const current = row.id === history.current;
+  const marker = current ? "●" : "○";
+  const time = row.node ? formatTime(row.node.timestamp) : "";
+  const description = row.node ? row.node.description : editor.t("panel.original");
+  const saved = row.id === history.saved ? `  ${editor.t("panel.saved")}` : "";
+
+  let text = `${marker} `;
+  const inlineOverlays: InlineOverlay[] = [];
+  if (time) {
+    inlineOverlays.push({
+      start: text.length,
+      end: text.length + time.length,
+      style: { fg: COLORS.time },
+    });
+    text += `${time}  `;
+  }
+  const descriptionStart = text.length;
+  text += description;
+  if (current) {
+    inlineOverlays.push(
+      { start: 0, end: marker.length, style: { fg: COLORS.current } },
+      {
+        start: descriptionStart,
+        end: text.length,
+        style: { fg: COLORS.current, bold: true },
+      },
+    );
+  }
+  if (saved) {
+    inlineOverlays.push({
+      start: text.length,
+      end: text.length + saved.length,
+      style: { fg: COLORS.saved },
+    });
+    text += saved;
+  }
+  return { text, inlineOverlays };
+}
+
+function previewEntries(lines: string[] | null, maxRows: number): TextPropertyEntry[] {
+  const entries: TextPropertyEntry[] = [
+    { text: `${editor.t("panel.preview")}\n`, style: { fg: COLORS.title, bold: true } },
+  ];
+  if (lines === null) {
+    entries.push({ text: `${editor.t("panel.loading")}\n` });
+    return entries;
+  }
+  if (lines.length === 0) {
+    entries.push({ text: `${editor.t("panel.no_changes")}\n` });
+    return entries;
+  }
+  for (const line of lines.slice(0, maxRows)) {
+    const style = line.startsWith("@@")
+      ? { fg: COLORS.hunk }
+      : line.startsWith("+")
+      ? { bg: COLORS.added, extendToLineEnd: true }
+      : line.startsWith("-")
+      ? { bg: COLORS.removed, extendToLineEnd: true }
+      : undefined;
+    entries.push({ text: `${line}\n`, style });
+  }
+  if (lines.length > maxRows) {
+    entries.push({
+      text: `${editor.t("panel.more_lines", { count: String(lines.length - maxRows) })}\n`,
+      style: { fg: COLORS.time },
+    });
+  }
+  return entries;
+}
+
+function render(): void {
+  if (!panel) return;
+  const { source, tree: history, rows } = panel;
+
+  const file = source ? getRelativePath(editor, source.path) : "";
+  const header: TextPropertyEntry[] = [
+    {
+      text: `${editor.t("panel.title", { file })}\n`,
+      style: { fg: COLORS.title, bold: true },
+    },
+  ];
+
+  let message: string | null = null;
+  if (!source) {
+    message = editor.t("panel.no_file");
+  } else if (!history) {
+    message = editor.t("panel.loading");
+  }
+  if (message !== null || !history) {
+    panel.widget.set(col(raw(header, "header"), raw([{ text: message ?? "" }], "empty")));
+    return;
+  }
+
+  // The tree takes the top half of the panel, the preview the rest.
+  const vp = editor.getViewport();
+  const height = vp && vp.height > 0 ? vp.height : 30;
+  const visibleRows = Math.max(3, Math.floor((height - 2) / 2));
+  const previewRows = Math.max(1, height - visibleRows - 3);
+  const treeNodes: TreeNode[] = rows.map((row) =>
+    treeNode(rowEntry(row, history), {
+      depth: row.depth,
+      hasChildren: row.hasBranches,
+    })
+  );
+  const forks = rows.filter((row) => row.hasBranches).map((row) => String(row.id));
+  const selectedIndex = rows.findIndex((row) => row.id === (panel!.selected ?? history.current));
+  panel.widget.set(
+    col(
+      raw(header, "header"),
+      tree({
+        nodes: treeNodes,
+        itemKeys: rows.map((row) => String(row.id)),
+        selectedIndex,
+        visibleRows,
+        expandedKeys: forks,
+        key: TREE_KEY,
+      }),
+      raw(previewEntries(panel.preview, previewRows), "preview"),
+    ),
+  );
+  // Host-owned after the first render, so push it.
+  if (selectedIndex >= 0) panel.widget.setSelectedIndex(TREE_KEY, selectedIndex);
+}
+
+// =============================================================================
+// Panel lifecycle
+// =============================================================================
+
+async function openUndoHistory(): Promise<void> {
+  if (panel) return;
+  const sourceBufferId = editor.getActiveBufferId();
+  const sourceSplitId = editor.getActiveSplitId();
+  try {
+    const result = await editor.createVirtualBufferInSplit({
+      name: "*Undo History*",
+      mode: "undo-history",
+      readOnly: true,
+      entries: [],
+      ratio: 0.3,
+      direction: "vertical",
+      before: true,
+      panelId: "undo-history-panel",
+      showLineNumbers: false,
+      showCursors: false,
+      editingDisabled: true,
+    });
+    panel = {
+      bufferId: result.bufferId,
+      splitId: result.splitId ?? editor.getActiveSplitId(),
+      sourceSplitId,
+      widget: new WidgetPanel(result.bufferId),
+      source: null,
+      tree: null,
+      rows: [],
+      selected: null,
+      preview: null,
+      generation: 0,
+      previewGeneration: 0,
+    };
+  } catch (e) {
+    editor.setStatus(editor.t("status.failed_open_panel"));
+    editor.debug(`undo_history: createVirtualBufferInSplit failed: ${e}`);
+    return;
+  }
+  setSource(sourceBufferId, sourceSplitId);
+  render();
+  void refresh();
+}
+
+function closeUndoHistory(): void {
+  if (!panel) return;
+  const { bufferId, splitId, sourceSplitId, widget } = panel;
+  panel = null;
+  widget.unmount();
+  editor.closeBuffer(bufferId);
+  if (splitId !== sourceSplitId) {
+    editor.closeSplit(splitId);
+  }
+  editor.focusSplit(sourceSplitId);
+}
+
+function toggle_undo_history(): void {
+  if (panel) {
+    closeUndoHistory();
+  } else {
+    void openUndoHistory();
+  }
+}
+registerHandler("toggle_undo_history", toggle_undo_history);
+
+editor.registerCommand(
+  "%cmd.toggle_undo_history",
+  "%cmd.toggle_undo_history_desc",
+  "toggle_undo_history",
+  null,
+);
+
+/** Move the file to state `id`, keeping the panel focused. */
+function jumpTo(id: number): void {
+  if (!panel?.source || !panel.tree || id === panel.tree.current) return;
+  // The jump applies to the active buffer, so hop over to the file.
+  editor.focusSplit(panel.sourceSplitId);
+  editor.jumpToUndoState(panel.source.bufferId, id);
+  editor.focusSplit(panel.splitId);
+  panel.selected = id;
+  void refresh();
+}
+
+editor.on("buffer_activated", (args) => {
+  if (!panel) return;
+  if (setSource(args.buffer_id, editor.getActiveSplitId())) {
+    render();
+    void refresh();
+  }
+});
+
+editor.on("buffer_closed", (args) => {
+  if (!panel) return;
+  if (args.buffer_id === panel.bufferId) {
+    panel.widget.unmount();
+    panel = null;
+  } else if (args.buffer_id === panel.source?.bufferId) {
+    panel.source = null;
+    void refresh();
+  }
+});
+
+editor.on("after_insert", (args) => {
+  if (panel && args.buffer_id === panel.source?.bufferId) void scheduleRefresh();
+});
+
+editor.on("after_delete", (args) => {
+  if (panel && args.buffer_id === panel.source?.bufferId) void scheduleRefresh();
+});
+
+editor.on("after_file_save", (args) => {
+  if (panel && args.buffer_id === panel.source?.bufferId) void refresh();
+});
+
+editor.on("widget_event", (args) => {
+  if (!panel || args.panel_id !== panel.widget.id()) return;
+  if (args.widget_key !== TREE_KEY) return;
+
+  const nodeKey = (args.payload as { key?: string } | undefined)?.key;
+  if (typeof nodeKey !== "string") return;
+  const id = Number(nodeKey);
+  if (args.event_type === "select") {
+    if (id !== panel.selected) void showPreview(id);
+  } else if (args.event_type === "activate") {
+    jumpTo(id);
+  }
+});
+
+// =============================================================================
+// Key handlers
+// =============================================================================
+
+function dispatch(name: string): void {
+  panel?.widget.command(widgetKey(name));
+}
+
+registerHandler("undo_history_up", () => dispatch("Up"));
+registerHandler("undo_history_down", () => dispatch("Down"));
+registerHandler("undo_history_page_up", () => dispatch("PageUp"));
+registerHandler("undo_history_page_down", () => dispatch("PageDown"));
+registerHandler("undo_history_left", () => dispatch("Left"));
+registerHandler("undo_history_right", () => dispatch("Right"));
+registerHandler("undo_history_enter", () => dispatch("Enter"));
+
+registerHandler("undo_history_escape", () => {
+  if (panel) editor.focusSplit(panel.sourceSplitId);
+});
+
+editor.debug("Undo History plugin loaded");
@@ -159,6 +159,8 @@ impl Editor {
                     self.resend_did_open_for_language(&language);
                     self.request_semantic_tokens_for_language(&language);
                     self.request_folding_ranges_for_language(&language);
+                    self.request_code_lens_for_language(&language);
+                    self.request_document_decorations_for_language(&language);
                     // Now that capabilities are known, kick off inlay hints
                     // and pull-diagnostics for buffers that opened before the
                     // `initialize` handshake completed. Both paths route
@@ -264,6 +266,9 @@ impl Editor {
                         tracing::error!("Error handling references response: {}", e);
                     }
                 }
+                AsyncMessage::LspHierarchyPrepared { request_id, items } => {
+                    self.handle_hierarchy_prepared(request_id, items);
+                }
                 AsyncMessage::LspSignatureHelp {
                     request_id,
                     signature_help,
@@ -278,18 +283,9 @@ impl Editor {
                 }
                 AsyncMessage::LspApplyEdit { edit, label } => {
                     tracing::info!("Applying workspace edit from server (label: {:?})", label);
-                    match self.apply_workspace_edit(edit) {
-                        Ok(n) => {
-                            if let Some(label) = label {
-                                self.set_status_message(
-                                    t!("lsp.code_action_applied", title = &label, count = n)
-                                        .to_string(),
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to apply workspace edit: {}", e);
-                        }
+                    let origin = super::workspace_edit_preview::EditOrigin::Server { label };
+                    if let Err(e) = self.apply_or_preview_workspace_edit(edit, origin) {
+                        tracing::error!("Failed to apply workspace edit: {}", e);
                     }
                 }
                 AsyncMessage::LspCodeActionResolved {
@@ -323,6 +319,12 @@ impl Editor {
                         }
                     }
                 }
+                AsyncMessage::LspOnTypeFormatting { request_id, edits } => {
+                    self.handle_on_type_formatting_response(request_id, edits);
+                }
+                AsyncMessage::LspLinkedEditingRanges { request_id, ranges } => {
+                    self.handle_linked_editing_ranges(request_id, ranges);
+                }
                 AsyncMessage::LspPrepareRename {
                     request_id: _,
                     result,
@@ -352,6 +354,45 @@ impl Editor {
                 } => {
                     self.handle_lsp_folding_ranges(request_id, uri, ranges);
                 }
+                AsyncMessage::LspCodeLenses {
+                    request_id,
+                    uri,
+                    lenses,
+                } => {
+                    self.handle_lsp_code_lenses(request_id, uri, lenses);
+                }
+                AsyncMessage::LspCodeLensResolved { request_id, lens } => {
+                    self.handle_lsp_code_lens_resolved(request_id, lens);
+                }
+                AsyncMessage::LspDocumentLinks {
+                    request_id,
+                    uri,
+                    links,
+                } => {
+                    self.handle_lsp_document_links(request_id, uri, links);
+                }
+                AsyncMessage::LspDocumentLinkResolved { request_id, link } => {
+                    self.handle_lsp_document_link_resolved(request_id, link);
+                }
+                AsyncMessage::LspDocumentColors {
+                    request_id,
+                    uri,
+                    colors,
+                } => {
+                    self.handle_lsp_document_colors(request_id, uri, colors);
+                }
+                AsyncMessage::LspColorPresentations {
+                    request_id,
+                    presentations,
+                } => {
+                    self.handle_lsp_color_presentations(request_id, presentations);
+                }
+                AsyncMessage::LspWillFileOperation { request_id, edit } => {
+                    self.handle_lsp_will_file_operation(request_id, edit);
+                }
+                AsyncMessage::LspSelectionRanges { request_id, ranges } => {
+                    self.handle_lsp_selection_ranges(request_id, ranges);
+                }
                 AsyncMessage::LspSemanticTokens {
                     request_id,
                     uri,
@@ -368,6 +409,9 @@ impl Editor {
                 AsyncMessage::LspInlayHintRefresh { language } => {
                     self.handle_lsp_inlay_hint_refresh(language);
                 }
+                AsyncMessage::LspCodeLensRefresh { language } => {
+                    self.request_code_lens_for_language(&language);
+                }
                 AsyncMessage::LspSemanticTokensRefresh { language } => {
                     self.handle_lsp_semantic_tokens_refresh(language);
                 }
@@ -385,7 +429,7 @@ impl Editor {
                     );
                 }
                 AsyncMessage::FileChanged { path } => {
-                    self.handle_async_file_changed(path);
+                    self.handle_watched_path_changed(path);
                 }
                 AsyncMessage::GitStatusChanged { status } => {
                     tracing::info!("Git status changed: {}", status);
@@ -714,6 +758,34 @@ impl Editor {
                 } => {
                     self.handle_plugin_lsp_response(request_id, result);
                 }
+                AsyncMessage::DapResponse {
+                    session_id,
+                    tag,
+                    command,
+                    result,
+                } => {
+                    self.handle_dap_response(session_id, tag, command, result);
+                }
+                AsyncMessage::DapEvent {
+                    session_id,
+                    event,
+                    body,
+                } => {
+                    self.handle_dap_event(session_id, event, body);
+                }
+                AsyncMessage::DapSessionEnded { session_id, error } => {
+                    self.handle_dap_session_ended(session_id, error);
+                }
+                AsyncMessage::TaskOutput { run_id, line } => {
+                    self.handle_task_output(run_id, line);
+                }
+                AsyncMessage::TaskFinished {
+                    run_id,
+                    exit_code,
+                    error,
+                } => {
+                    self.handle_task_finished(run_id, exit_code, error);
+                }
                 AsyncMessage::RemoteAttachReady(ready) => {
                     // The background connect succeeded. Install per `mode`:
                     // Restart rebuilds the whole editor around the backend
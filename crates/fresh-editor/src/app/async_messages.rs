@@ -115,7 +115,7 @@ impl Editor {
 
 impl Editor {
     /// Merge push + pull diagnostics for a URI and apply the combined set
-    fn merge_and_apply_diagnostics(&mut self, uri: &str) {
+    pub(super) fn merge_and_apply_diagnostics(&mut self, uri: &str) {
         // Merge diagnostics from all servers (push model) and pull model
         let mut merged = Vec::new();
         if let Some(server_map) = self.active_window_mut().stored_push_diagnostics.get(uri) {
@@ -817,8 +817,10 @@ impl Editor {
 
         // Skip inlay hints if disabled
         if !self.config.editor.enable_inlay_hints {
-            // Folding ranges may improve after project is fully loaded
+            // Folding ranges and code lenses may improve after project is fully loaded
             self.request_folding_ranges_for_language(&language);
+            self.request_code_lens_for_language(&language);
+            self.request_document_decorations_for_language(&language);
             return;
         }
 
@@ -885,8 +887,10 @@ impl Editor {
             }
         }
 
-        // Folding ranges may improve after project is fully loaded
+        // Folding ranges and code lenses may improve after project is fully loaded
         self.request_folding_ranges_for_language(&language);
+        self.request_code_lens_for_language(&language);
+        self.request_document_decorations_for_language(&language);
     }
 
     /// Handle workspace/diagnostic/refresh request from the LSP server.
@@ -952,6 +956,8 @@ impl Editor {
         if changed && register {
             self.request_semantic_tokens_for_language(&language);
             self.request_folding_ranges_for_language(&language);
+            self.request_code_lens_for_language(&language);
+            self.request_document_decorations_for_language(&language);
             self.request_inlay_hints_for_language(&language);
             self.pull_diagnostics_for_language(&language);
         }
@@ -1402,6 +1408,28 @@ impl Editor {
         self.handle_file_changed(&path);
         true
     }
+
+    /// Handle a change reported by a watch on one of the window's
+    /// directories (see `file_watcher::WatchedDirs`): revert the file if
+    /// it is open and queue its directory for the file explorer. A change
+    /// reported for a watched directory itself (a reconnect, an event
+    /// queue overflow) may hide changes to any file in it, so each open
+    /// one is checked.
+    pub(super) fn handle_watched_path_changed(&mut self, path: String) {
+        let path_buf = PathBuf::from(&path);
+        self.active_window_mut().watched_dirs.note_change(&path_buf);
+        let open_in_dir: Vec<String> = self
+            .buffers()
+            .paths()
+            .into_iter()
+            .filter(|open| open.parent() == Some(path_buf.as_path()))
+            .map(|open| open.to_string_lossy().into_owned())
+            .collect();
+        self.handle_async_file_changed(path);
+        for open in open_in_dir {
+            self.handle_async_file_changed(open);
+        }
+    }
 }
 
 // =============================================================================
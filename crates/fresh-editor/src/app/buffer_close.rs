@@ -62,6 +62,9 @@ impl Editor {
             self.active_window_mut().completed_waits.push(wait_id);
         }
 
+        // A closed macro edit buffer no longer writes back to its macro.
+        self.active_window_mut().macros.clear_edit_target(id);
+
         // Save file state before closing (for per-file session persistence)
         self.active_window().save_file_state_on_close(id);
 
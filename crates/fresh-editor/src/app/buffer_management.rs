@@ -266,17 +266,54 @@ impl Editor {
             );
             let display_name =
                 super::BufferMetadata::display_name_for_path(&new_path, self.working_dir());
+            let mut reopen = None;
             if let Some(metadata) = self.active_window_mut().buffer_metadata.get_mut(&id) {
+                // Servers that had the document open under its old URI.
+                let old_uri = metadata.file_uri().cloned();
+                let opened_with = std::mem::take(&mut metadata.lsp_opened_with);
                 metadata.kind = super::BufferKind::File {
                     path: new_path.clone(),
-                    uri: file_uri,
+                    uri: file_uri.clone(),
                 };
                 metadata.display_name = display_name;
+                if metadata.lsp_enabled {
+                    reopen = old_uri.map(|old_uri| (old_uri, opened_with));
+                }
+            }
+            if let (Some((old_uri, opened_with)), Some(new_uri)) = (reopen, file_uri) {
+                self.reopen_lsp_document(id, &old_uri, &new_uri, &opened_with);
             }
         }
         affected
     }
 
+    /// Move a document the servers had open to its new URI, keeping the
+    /// buffer (and so its undo history) as it is.
+    fn reopen_lsp_document(
+        &mut self,
+        buffer_id: BufferId,
+        old_uri: &super::types::LspUri,
+        new_uri: &super::types::LspUri,
+        opened_with: &HashSet<u64>,
+    ) {
+        let Some(language) = self.buffers().get(&buffer_id).map(|s| s.language.clone()) else {
+            return;
+        };
+        if let Some(lsp) = self.lsp_mut() {
+            for sh in lsp.get_handles_mut(&language) {
+                if !opened_with.contains(&sh.handle.id()) {
+                    continue;
+                }
+                if let Err(e) = sh.handle.did_close(old_uri.as_uri().clone()) {
+                    tracing::warn!("Failed to send didClose to '{}': {}", sh.name, e);
+                }
+            }
+        }
+        if !opened_with.is_empty() {
+            self.ensure_did_open_all(buffer_id, new_uri, &language);
+        }
+    }
+
     // `promote_current_preview`, `promote_preview_if_not_in_split`,
     // `is_buffer_preview`, `current_preview` moved to `impl Window`.
 
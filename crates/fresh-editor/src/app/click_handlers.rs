@@ -147,11 +147,11 @@ impl Editor {
             String::new()
         };
 
-        // Compute buffer-local row/col once. Both the widget hit-test
-        // and the mouse_click hook need them, and the cost (a single
-        // `screen_to_buffer_position` call) is non-trivial — share the
-        // result.
-        let (mc_buffer_row, mc_buffer_col) = {
+        // Compute the clicked byte and buffer-local row/col once. The
+        // widget hit-test, the mouse_click hook and link following need
+        // them, and the cost (a single `screen_to_buffer_position` call)
+        // is non-trivial — share the result.
+        let (clicked_byte, mc_buffer_row, mc_buffer_col) = {
             let cached_mappings = self
                 .active_layout()
                 .view_line_mappings
@@ -200,14 +200,15 @@ impl Editor {
                     if let Some(s) = state {
                         let (line, col_b) = s.buffer.position_to_line_col(byte_pos);
                         (
+                            Some(byte_pos),
                             Some(line.min(u32::MAX as usize) as u32),
                             Some(col_b.min(u32::MAX as usize) as u32),
                         )
                     } else {
-                        (None, None)
+                        (None, None, None)
                     }
                 }
-                None => (None, None),
+                None => (None, None, None),
             }
         };
 
@@ -268,6 +269,23 @@ impl Editor {
             return self.handle_composite_click(col, row, split_id, buffer_id, content_rect);
         }
 
+        // Sticky scroll headers are drawn over the top content rows.
+        if self.click_sticky_scroll(row, split_id, content_rect) {
+            return Ok(());
+        }
+
+        // Code lens titles are drawn on virtual rows above their symbols.
+        if self.click_code_lens(col, row, split_id, buffer_id, content_rect) {
+            return Ok(());
+        }
+
+        // Ctrl+click follows a document link instead of extending the selection.
+        if modifiers.contains(KeyModifiers::CONTROL)
+            && clicked_byte.is_some_and(|pos| self.follow_document_link_at(buffer_id, pos))
+        {
+            return Ok(());
+        }
+
         // Ensure key context is Normal for non-terminal buffers
         // This handles the edge case where split/buffer don't change but we clicked from FileExplorer
         if !self.active_window().is_terminal_buffer(buffer_id) {
@@ -0,0 +1,130 @@
+//! Per-client views for the session daemon.
+//!
+//! The clients attached to one daemon share its windows and buffers, but
+//! each keeps its own active window, split layout (tabs, cursors, scroll
+//! positions) and screen size. The server enters a client's view before
+//! handling that client's input, and the editor stays there for async work
+//! until another client types; every frame is rendered through each
+//! client's view in turn. Windows hold the layouts of the clients not
+//! currently in them (see `WindowBuffers::show_view`). Prompts, popups and
+//! the file explorer belong to the window, so clients on the same window
+//! share them.
+
+use std::collections::HashMap;
+
+use fresh_core::WindowId;
+use ratatui::Frame;
+
+use super::Editor;
+
+/// What one attached client sees. Held by the server, one per client.
+#[derive(Debug)]
+pub struct ClientView {
+    /// Unique among the server's clients; keys the client's layouts
+    id: u64,
+    width: u16,
+    height: u16,
+}
+
+impl ClientView {
+    /// View `id` of a client with a `width` x `height` terminal.
+    pub fn new(id: u64, width: u16, height: u16) -> Self {
+        Self { id, width, height }
+    }
+
+    /// The client's terminal was resized.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.width = width;
+        self.height = height;
+    }
+}
+
+/// The editor's side of the client views.
+#[derive(Debug, Default)]
+pub(crate) struct ClientViews {
+    /// View input last came from. The editor shows its window between
+    /// inputs, so windows switched by async work stay switched for it.
+    entered: Option<u64>,
+    /// Window each other view's input last left active
+    windows: HashMap<u64, WindowId>,
+}
+
+impl Editor {
+    /// Make `view` the one input acts on: switch to its window, its split
+    /// layout and its screen size.
+    pub fn enter_client_view(&mut self, view: &ClientView) {
+        if self.client_views.entered != Some(view.id) {
+            if let Some(previous) = self.client_views.entered.replace(view.id) {
+                self.client_views
+                    .windows
+                    .insert(previous, self.active_window);
+            }
+            if let Some(id) = self.client_views.windows.remove(&view.id) {
+                if self.windows.contains_key(&id) {
+                    self.set_active_window(id);
+                }
+            }
+        }
+        let (width, height) = (view.width, view.height);
+        self.active_window_mut()
+            .buffers
+            .show_view(view.id, width, height);
+        if (self.terminal_width, self.terminal_height) != (width, height) {
+            self.resize(width, height);
+        }
+    }
+
+    /// `view`'s terminal was resized: resize the editor if input acts on
+    /// that view.
+    pub fn client_view_resized(&mut self, view: &ClientView) {
+        if self.client_views.entered.is_none_or(|id| id == view.id) {
+            self.resize(view.width, view.height);
+        }
+    }
+
+    /// Render `frame` as `view` sees it, then put back the window, layout
+    /// and size of the view input acts on. Only the editor's own state is
+    /// switched: no hooks run, and terminal PTYs keep the size of the
+    /// client that last typed.
+    pub fn render_client_view(&mut self, view: &ClientView, frame: &mut Frame) {
+        let entered = self.active_window;
+        let is_entered = self.client_views.entered == Some(view.id);
+        let size = (self.terminal_width, self.terminal_height);
+        if !is_entered {
+            if let Some(&id) = self.client_views.windows.get(&view.id) {
+                if self.windows.contains_key(&id) {
+                    self.active_window = id;
+                }
+            }
+        }
+        let window = self.active_window_mut();
+        let window_size = (window.terminal_width, window.terminal_height);
+        let replaced = window.buffers.show_view(view.id, view.width, view.height);
+        (window.terminal_width, window.terminal_height) = (view.width, view.height);
+        (self.terminal_width, self.terminal_height) = (view.width, view.height);
+
+        self.render(frame);
+
+        (self.terminal_width, self.terminal_height) = size;
+        let window = self.active_window_mut();
+        (window.terminal_width, window.terminal_height) = window_size;
+        if !is_entered {
+            if let Some(previous) = replaced.filter(|previous| *previous != view.id) {
+                window.buffers.show_view(previous, size.0, size.1);
+            }
+        }
+        self.active_window = entered;
+    }
+
+    /// Forget `view` once its client is gone. A layout it left live stays
+    /// for the next client to adopt.
+    pub fn drop_client_view(&mut self, view: &ClientView) {
+        if self.client_views.entered == Some(view.id) {
+            self.client_views.entered = None;
+        }
+        self.client_views.windows.remove(&view.id);
+        for window in self.windows.values_mut() {
+            window.buffers.drop_view(view.id);
+        }
+    }
+}
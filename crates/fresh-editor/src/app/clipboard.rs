@@ -7,6 +7,7 @@
 
 use ratatui::style::{Modifier, Style};
 use rust_i18n::t;
+use std::ops::Range;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
@@ -21,9 +22,10 @@ use crate::primitives::word_navigation::{
     find_vi_word_end, find_word_start_left, find_word_start_right,
 };
 use crate::services::async_bridge::AsyncMessage;
+use crate::services::clipboard::ClipboardEntry;
 use crate::view::virtual_text::{VirtualTextId, VirtualTextPosition};
 
-use super::Editor;
+use super::{Editor, YankState};
 
 /// Per-paste timeout. The async-paste path renders a placeholder
 /// marker and lets the user keep editing; if the background arboard
@@ -150,51 +152,49 @@ impl Editor {
 
         if has_selection {
             // Original behavior: copy selected text
-            let ranges: Vec<_> = self
+            let mut ranges: Vec<_> = self
                 .active_cursors()
                 .iter()
                 .filter_map(|(_, cursor)| cursor.selection_range())
                 .collect();
+            // Top to bottom, the order in which a paste hands chunks out.
+            ranges.sort_by_key(|range| range.start);
 
-            let mut text = String::new();
             let state = self.active_state_mut();
-            for range in ranges {
-                if !text.is_empty() {
-                    text.push('\n');
-                }
-                let range_text = state.get_text_range(range.start, range.end);
-                text.push_str(&range_text);
-            }
+            let chunks: Vec<String> = ranges
+                .into_iter()
+                .map(|range| state.get_text_range(range.start, range.end))
+                .collect();
 
-            if !text.is_empty() {
-                self.clipboard.copy(text);
+            // One history entry with a chunk per cursor, so a paste with
+            // the same cursors puts each selection back where it belongs.
+            if chunks.iter().any(|chunk| !chunk.is_empty()) {
+                self.clipboard.copy_chunks(chunks);
                 self.active_window_mut().status_message = Some(t!("clipboard.copied").to_string());
             }
         } else {
             // No selection: copy entire line(s) for each cursor
             let estimated_line_length = 80;
-            let mut text = String::new();
+            let mut chunks = Vec::new();
 
-            // Collect cursor positions first
-            let positions: Vec<_> = self
+            // Collect cursor positions first, top to bottom
+            let mut positions: Vec<_> = self
                 .active_cursors()
                 .iter()
                 .map(|(_, c)| c.position)
                 .collect();
+            positions.sort_unstable();
             let state = self.active_state_mut();
 
             for pos in positions {
                 let mut iter = state.buffer.line_iterator(pos, estimated_line_length);
                 if let Some((_start, content)) = iter.next_line() {
-                    if !text.is_empty() {
-                        text.push('\n');
-                    }
-                    text.push_str(&content);
+                    chunks.push(content);
                 }
             }
 
-            if !text.is_empty() {
-                self.clipboard.copy(text);
+            if chunks.iter().any(|chunk| !chunk.is_empty()) {
+                self.clipboard.copy_chunks(chunks);
                 self.active_window_mut().status_message =
                     Some(t!("clipboard.copied_line").to_string());
             }
@@ -1223,9 +1223,30 @@ impl Editor {
             return;
         }
 
-        // Collect cursor info sorted in reverse order by position
-        let mut cursor_data: Vec<_> = self
-            .active_cursors()
+        // Text copied from several cursors is pasted back one chunk per
+        // cursor when the cursor count still matches.
+        let history_index = self.clipboard.record_paste(&paste_text);
+        let chunks = history_index
+            .and_then(|index| self.clipboard.history_entry(index))
+            .filter(|entry| entry.chunks.len() > 1)
+            .map(|entry| entry.chunks.clone());
+
+        let buffer_id = self.active_buffer();
+        let targets = self.paste_targets();
+        let ranges = self.insert_paste(targets, &normalized, chunks.as_deref());
+        self.active_window_mut().yank_state = history_index.map(|index| YankState {
+            buffer_id,
+            index,
+            ranges,
+        });
+
+        self.active_window_mut().status_message = Some(t!("clipboard.pasted").to_string());
+    }
+
+    /// Where each cursor pastes: its selection (replaced by the paste) and
+    /// the insert position.
+    fn paste_targets(&self) -> Vec<(CursorId, Option<Range<usize>>, usize)> {
+        self.active_cursors()
             .iter()
             .map(|(cursor_id, cursor)| {
                 let selection = cursor.selection_range();
@@ -1235,67 +1256,78 @@ impl Editor {
                     .unwrap_or(cursor.position);
                 (cursor_id, selection, insert_position)
             })
-            .collect();
-        cursor_data.sort_by_key(|(_, _, pos)| std::cmp::Reverse(*pos));
-
-        // Decide whether to distribute one clipboard line per cursor
-        // (column-mode paste). We split on LF (after normalization above) and
-        // ignore a single trailing empty entry from a trailing newline so that
-        // "a\nb\nc" and "a\nb\nc\n" both yield 3 lines.
-        let mut lines_for_distribution: Vec<&str> = normalized.split('\n').collect();
-        if lines_for_distribution.len() > 1 && lines_for_distribution.last() == Some(&"") {
-            lines_for_distribution.pop();
-        }
-        let use_column_paste = cursor_data.len() > 1
-            && lines_for_distribution.len() > 1
-            && lines_for_distribution.len() == cursor_data.len();
-
-        // Convert to buffer's line ending format (only used in non-column mode;
-        // a single column-paste line never contains an embedded newline).
-        let paste_text_full = match self.active_state().buffer.line_ending() {
-            crate::model::buffer::LineEnding::LF => normalized.clone(),
-            crate::model::buffer::LineEnding::CRLF => normalized.replace('\n', "\r\n"),
-            crate::model::buffer::LineEnding::CR => normalized.replace('\n', "\r"),
+            .collect()
+    }
+
+    /// Insert pasted text at each target as one undo step, replacing the
+    /// target's range first.
+    ///
+    /// `text` is LF-normalized. With `chunks` matching the number of
+    /// targets, each cursor receives its own chunk; otherwise column-mode
+    /// paste applies as described on [`Self::paste_text`]. Returns the
+    /// range each cursor inserted.
+    fn insert_paste(
+        &mut self,
+        mut targets: Vec<(CursorId, Option<Range<usize>>, usize)>,
+        text: &str,
+        chunks: Option<&[String]>,
+    ) -> Vec<(CursorId, Range<usize>)> {
+        let line_ending = self.active_state().buffer.line_ending();
+        let to_buffer_line_ending = |text: &str| {
+            let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+            match line_ending {
+                crate::model::buffer::LineEnding::LF => normalized,
+                crate::model::buffer::LineEnding::CRLF => normalized.replace('\n', "\r\n"),
+                crate::model::buffer::LineEnding::CR => normalized.replace('\n', "\r"),
+            }
         };
 
-        // Get deleted text for each selection
-        let cursor_data_with_text: Vec<_> = {
-            let state = self.active_state_mut();
-            cursor_data
-                .into_iter()
-                .map(|(cursor_id, selection, insert_position)| {
-                    let deleted_text = selection
-                        .as_ref()
-                        .map(|r| state.get_text_range(r.start, r.end));
-                    (cursor_id, selection, insert_position, deleted_text)
-                })
-                .collect()
+        // Sorted in reverse order by position, so events applied in vector
+        // order don't invalidate earlier offsets.
+        targets.sort_by_key(|(_, _, pos)| std::cmp::Reverse(*pos));
+        let total = targets.len();
+
+        // Decide what each cursor receives, topmost cursor first. Without
+        // per-cursor chunks, distribute one clipboard line per cursor
+        // (column-mode paste). We split on LF and ignore a single trailing
+        // empty entry from a trailing newline so that "a\nb\nc" and
+        // "a\nb\nc\n" both yield 3 lines.
+        let per_cursor: Option<Vec<String>> = match chunks {
+            Some(chunks) if total > 1 && chunks.len() == total => {
+                Some(chunks.iter().map(|c| to_buffer_line_ending(c)).collect())
+            }
+            _ => {
+                let mut lines: Vec<&str> = text.split('\n').collect();
+                if lines.len() > 1 && lines.last() == Some(&"") {
+                    lines.pop();
+                }
+                (total > 1 && lines.len() > 1 && lines.len() == total)
+                    .then(|| lines.into_iter().map(str::to_string).collect())
+            }
         };
+        let full_text = to_buffer_line_ending(text);
 
-        // Build events for each cursor.
-        //
-        // cursor_data_with_text is sorted by position DESCENDING (so events
-        // applied in vector order don't invalidate earlier offsets). For column
-        // paste we want the topmost cursor (smallest position) to receive the
-        // first clipboard line, so we index into `lines_for_distribution` from
-        // the back when iterating.
-        let total = cursor_data_with_text.len();
+        // Build events for each cursor. `targets` is sorted DESCENDING, so
+        // the topmost cursor (smallest position) — which receives the first
+        // chunk or line — is indexed from the back.
+        let mut inserted = Vec::with_capacity(total);
         let mut events = Vec::new();
-        for (i, (cursor_id, selection, insert_position, deleted_text)) in
-            cursor_data_with_text.into_iter().enumerate()
-        {
-            if let (Some(range), Some(text)) = (selection, deleted_text) {
+        for (i, (cursor_id, range, insert_position)) in targets.into_iter().enumerate() {
+            if let Some(range) = range {
+                let deleted_text = self
+                    .active_state_mut()
+                    .get_text_range(range.start, range.end);
                 events.push(Event::Delete {
                     range,
-                    deleted_text: text,
+                    deleted_text,
                     cursor_id,
                 });
             }
-            let text = if use_column_paste {
-                lines_for_distribution[total - 1 - i].to_string()
-            } else {
-                paste_text_full.clone()
+            let text = match &per_cursor {
+                Some(texts) => texts[total - 1 - i].clone(),
+                None => full_text.clone(),
             };
+            inserted.push((cursor_id, text.len()));
             events.push(Event::Insert {
                 position: insert_position,
                 text,
@@ -1313,7 +1345,126 @@ impl Editor {
             self.log_and_apply_event(&event);
         }
 
-        self.active_window_mut().status_message = Some(t!("clipboard.pasted").to_string());
+        // Each cursor now sits at the end of the text it inserted.
+        let cursors = self.active_cursors();
+        inserted
+            .into_iter()
+            .filter_map(|(cursor_id, len)| {
+                let end = cursors.get(cursor_id)?.position;
+                Some((cursor_id, end.saturating_sub(len)..end))
+            })
+            .collect()
+    }
+
+    /// Open the Paste from History picker, listing the clipboard history
+    /// newest first with a one-line preview of each entry.
+    pub fn start_paste_from_history_prompt(&mut self) {
+        if self.clipboard.history_len() == 0 {
+            self.set_status_message(t!("clipboard.history_empty").to_string());
+            return;
+        }
+
+        let suggestions: Vec<crate::input::commands::Suggestion> = self
+            .clipboard
+            .history()
+            .enumerate()
+            .map(|(index, entry)| crate::input::commands::Suggestion {
+                description_spans: None,
+                text: history_preview(&entry.text()),
+                description: Some(history_entry_summary(&entry.chunks)),
+                value: Some(index.to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+
+        self.active_window_mut().prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            t!("clipboard.history_prompt").to_string(),
+            crate::view::prompt::PromptType::PasteFromHistory,
+            suggestions,
+        ));
+        if let Some(prompt) = self.active_window_mut().prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Paste entry `index` of the clipboard history, replacing the
+    /// selections. Chosen from the Paste from History picker.
+    pub(crate) fn paste_from_history(&mut self, index: usize) {
+        let Some(entry) = self.clipboard.history_entry(index).cloned() else {
+            return;
+        };
+        if self.active_window().terminal_mode {
+            self.paste_text(entry.text());
+            return;
+        }
+        let targets = self.paste_targets();
+        self.paste_history_entry(index, &entry, targets);
+    }
+
+    /// Replace the text of the last paste with the next older entry of the
+    /// clipboard history (Emacs `yank-pop`).
+    ///
+    /// Without a paste to replace — the previous action wasn't a paste, or
+    /// the cursors moved since — this opens the Paste from History picker,
+    /// like `M-y` does in recent Emacs versions.
+    pub fn yank_pop(&mut self) {
+        let state = self.active_window_mut().yank_state.take();
+        let Some(state) = state.filter(|state| {
+            state.buffer_id == self.active_buffer()
+                && state.ranges.iter().all(|(cursor_id, range)| {
+                    self.active_cursors()
+                        .get(*cursor_id)
+                        .is_some_and(|c| c.position == range.end)
+                })
+        }) else {
+            self.start_paste_from_history_prompt();
+            return;
+        };
+
+        let len = self.clipboard.history_len();
+        if len == 0 {
+            return;
+        }
+        let index = (state.index + 1) % len;
+        let Some(entry) = self.clipboard.history_entry(index).cloned() else {
+            return;
+        };
+        let targets = state
+            .ranges
+            .into_iter()
+            .map(|(cursor_id, range)| {
+                let start = range.start;
+                (cursor_id, Some(range), start)
+            })
+            .collect();
+        self.paste_history_entry(index, &entry, targets);
+    }
+
+    fn paste_history_entry(
+        &mut self,
+        index: usize,
+        entry: &ClipboardEntry,
+        targets: Vec<(CursorId, Option<Range<usize>>, usize)>,
+    ) {
+        let text = entry.text().replace("\r\n", "\n").replace('\r', "\n");
+        let chunks = (entry.chunks.len() > 1).then_some(entry.chunks.as_slice());
+        let buffer_id = self.active_buffer();
+        let ranges = self.insert_paste(targets, &text, chunks);
+        self.active_window_mut().yank_state = Some(YankState {
+            buffer_id,
+            index,
+            ranges,
+        });
+        self.set_status_message(
+            t!(
+                "clipboard.pasted_history_entry",
+                index = index + 1,
+                count = self.clipboard.history_len()
+            )
+            .to_string(),
+        );
     }
 
     /// Set clipboard content for testing purposes
@@ -1848,3 +1999,35 @@ impl Editor {
         }
     }
 }
+
+/// Longest preview shown for an entry in the Paste from History picker.
+const HISTORY_PREVIEW_CHARS: usize = 80;
+
+/// One-line preview of a clipboard history entry: line breaks shown as
+/// `↵`, runs of whitespace collapsed, cut to [`HISTORY_PREVIEW_CHARS`].
+fn history_preview(text: &str) -> String {
+    let flattened = text
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join(" ↵ ");
+    if flattened.chars().count() > HISTORY_PREVIEW_CHARS {
+        let cut: String = flattened.chars().take(HISTORY_PREVIEW_CHARS - 1).collect();
+        format!("{cut}…")
+    } else {
+        flattened
+    }
+}
+
+/// Size of a clipboard history entry, shown next to its preview.
+fn history_entry_summary(chunks: &[String]) -> String {
+    if chunks.len() > 1 {
+        return t!("clipboard.history_cursors", count = chunks.len()).to_string();
+    }
+    let text = chunks.first().map(String::as_str).unwrap_or_default();
+    t!(
+        "clipboard.history_lines",
+        count = text.lines().count().max(1)
+    )
+    .to_string()
+}
@@ -0,0 +1,372 @@
+//! Code lens orchestrators.
+//!
+//! Lenses are pulled per buffer (`textDocument/codeLens`) on the same
+//! debounced schedule as folding ranges, stored on the buffer's
+//! [`CodeLenses`](crate::view::code_lens::CodeLenses) and drawn as virtual
+//! lines. Lenses that arrive without a command are resolved one by one
+//! (`codeLens/resolve`) when the server supports it.
+//!
+//! A lens is activated by clicking its title or with the `lsp_code_lens`
+//! action on the symbol's line; either way its command goes out as
+//! `workspace/executeCommand`, exactly like a command-only code action.
+
+use std::time::Instant;
+
+use ratatui::layout::Rect;
+use rust_i18n::t;
+
+use crate::model::event::{BufferId, LeafId};
+use crate::types::LspFeature;
+
+use super::{CodeLensRequest, Editor};
+
+impl Editor {
+    /// Issue a debounced code lens request if the timer has elapsed.
+    pub(crate) fn maybe_request_code_lens_debounced(&mut self, buffer_id: BufferId) {
+        let Some(ready_at) = self
+            .active_window()
+            .code_lens_debounce
+            .get(&buffer_id)
+            .copied()
+        else {
+            return;
+        };
+        if Instant::now() < ready_at {
+            return;
+        }
+
+        self.active_window_mut()
+            .code_lens_debounce
+            .remove(&buffer_id);
+        self.request_code_lens_for_buffer(buffer_id);
+    }
+
+    /// Request code lenses for a buffer from the server that provides them.
+    pub(crate) fn request_code_lens_for_buffer(&mut self, buffer_id: BufferId) {
+        let Some(version) = self.buffers().get(&buffer_id).map(|s| s.buffer.version()) else {
+            return;
+        };
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, LspFeature::CodeLens, |handle, uri, _language| {
+                match handle.code_lens(request_id, uri.as_uri().clone()) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request code lenses: {}", e);
+                        false
+                    }
+                }
+            })
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.pending_code_lens_requests.insert(
+                request_id,
+                CodeLensRequest {
+                    buffer_id,
+                    version,
+                    resolve_index: None,
+                },
+            );
+        }
+    }
+
+    /// Schedule a code lens refresh for all open buffers of a language.
+    pub(super) fn request_code_lens_for_language(&mut self, language: &str) {
+        let buffer_ids: Vec<_> = self
+            .buffers_for_language(language)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            self.active_window_mut()
+                .schedule_code_lens_refresh(buffer_id);
+        }
+    }
+
+    /// Handle a `textDocument/codeLens` response: replace the buffer's
+    /// lenses, draw them, and resolve the ones that came without a command.
+    pub(super) fn handle_lsp_code_lenses(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        lenses: Vec<lsp_types::CodeLens>,
+    ) {
+        let Some(request) = self
+            .active_window_mut()
+            .pending_code_lens_requests
+            .remove(&request_id)
+        else {
+            tracing::debug!(
+                "Ignoring code lens response without pending request (request_id={})",
+                request_id
+            );
+            return;
+        };
+        let buffer_id = request.buffer_id;
+
+        let win = self.active_window_mut();
+        let Some(state) = win.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version {
+            tracing::debug!(
+                "Ignoring stale code lenses for {} (request_id={})",
+                uri,
+                request_id
+            );
+            win.schedule_code_lens_refresh(buffer_id);
+            return;
+        }
+
+        tracing::debug!("Received {} code lenses for {}", lenses.len(), uri);
+        let tab_size = state.buffer_settings.tab_size;
+        state.code_lenses.set(lenses);
+        state.code_lenses.render(
+            &state.buffer,
+            &mut state.virtual_texts,
+            &mut state.marker_list,
+            tab_size,
+        );
+        let unresolved: Vec<_> = state
+            .code_lenses
+            .unresolved()
+            .into_iter()
+            .filter_map(|i| Some((i, state.code_lenses.get(i)?.clone())))
+            .collect();
+        // Resolves of the previous set point at indices that no longer exist.
+        win.pending_code_lens_requests
+            .retain(|_, r| r.buffer_id != buffer_id || r.resolve_index.is_none());
+
+        if !unresolved.is_empty() && self.code_lens_resolve_supported(buffer_id) {
+            self.resolve_code_lenses(buffer_id, request.version, unresolved);
+        }
+    }
+
+    /// Handle a `codeLens/resolve` response: store the lens and redraw.
+    pub(super) fn handle_lsp_code_lens_resolved(
+        &mut self,
+        request_id: u64,
+        lens: Result<lsp_types::CodeLens, String>,
+    ) {
+        let Some(request) = self
+            .active_window_mut()
+            .pending_code_lens_requests
+            .remove(&request_id)
+        else {
+            return;
+        };
+        let (Some(index), Ok(lens)) = (request.resolve_index, lens) else {
+            return;
+        };
+        let Some(state) = self.active_window_mut().buffers.get_mut(&request.buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version || !state.code_lenses.resolve(index, lens) {
+            return;
+        }
+        let tab_size = state.buffer_settings.tab_size;
+        state.code_lenses.render(
+            &state.buffer,
+            &mut state.virtual_texts,
+            &mut state.marker_list,
+            tab_size,
+        );
+    }
+
+    /// Run the code lens on the cursor's line. Several lenses on the line
+    /// are offered in a popup.
+    pub(crate) fn run_code_lens_at_cursor(&mut self) {
+        let cursor = self.active_cursors().primary().position;
+        let commands = {
+            let state = self.active_state();
+            let line = state.buffer.get_line_number(cursor);
+            state.code_lenses.commands_on_line(
+                &state.buffer,
+                &state.virtual_texts,
+                &state.marker_list,
+                line,
+            )
+        };
+
+        match commands.len() {
+            0 => self.set_status_message(t!("lsp.no_code_lens").to_string()),
+            1 => {
+                let command = commands.into_iter().next().expect("one command");
+                self.send_execute_command(command);
+            }
+            _ => self.show_code_lens_popup(commands),
+        }
+    }
+
+    /// Run the code lens whose title was clicked. Returns `true` when the
+    /// click landed on a lens title and was consumed.
+    pub(super) fn click_code_lens(
+        &mut self,
+        col: u16,
+        row: u16,
+        split_id: LeafId,
+        buffer_id: BufferId,
+        content_rect: Rect,
+    ) -> bool {
+        let Some(mappings) = self.active_layout().view_line_mappings.get(&split_id) else {
+            return false;
+        };
+        let Some((_, view_states)) = self
+            .windows
+            .get(&self.active_window)
+            .and_then(|w| w.buffers.splits())
+        else {
+            return false;
+        };
+        let Some(view_state) = view_states.get(&split_id) else {
+            return false;
+        };
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return false;
+        };
+        if state.code_lenses.is_empty() {
+            return false;
+        }
+
+        let rect = super::click_geometry::adjust_content_rect_for_compose(
+            content_rect,
+            view_state.compose_width,
+        );
+        let gutter_width = state.margins.left_total_width() as u16;
+        let Some(text_col) = col
+            .checked_sub(rect.x)
+            .and_then(|c| c.checked_sub(gutter_width))
+        else {
+            return false;
+        };
+        let visual_row = row.saturating_sub(rect.y) as usize;
+        let Some(lens_row) = mappings.get(visual_row).filter(|m| m.is_plugin_virtual) else {
+            return false;
+        };
+        let column = lens_row
+            .visual_to_char
+            .get(text_col as usize)
+            .copied()
+            .unwrap_or(text_col as usize);
+        // The lens row sits above its symbol: the first real row below it.
+        let Some(symbol_byte) = mappings[visual_row + 1..]
+            .iter()
+            .filter(|m| !m.is_plugin_virtual)
+            .find_map(|m| m.char_source_bytes.iter().find_map(|b| *b))
+        else {
+            return false;
+        };
+        let line = state.buffer.get_line_number(symbol_byte);
+
+        let Some(command) = state.code_lenses.command_at(
+            &state.buffer,
+            &state.virtual_texts,
+            &state.marker_list,
+            line,
+            column,
+        ) else {
+            return false;
+        };
+        self.send_execute_command(command);
+        true
+    }
+
+    /// Whether the server providing code lenses for `buffer_id` resolves them.
+    fn code_lens_resolve_supported(&self, buffer_id: BufferId) -> bool {
+        let Some(language) = self.buffers().get(&buffer_id).map(|s| s.language.clone()) else {
+            return false;
+        };
+        self.lsp()
+            .and_then(|lsp| lsp.handle_for_feature(&language, LspFeature::CodeLens))
+            .is_some_and(|sh| sh.capabilities.code_lens_resolve)
+    }
+
+    fn resolve_code_lenses(
+        &mut self,
+        buffer_id: BufferId,
+        version: u64,
+        lenses: Vec<(usize, lsp_types::CodeLens)>,
+    ) {
+        let first_id = self.active_window().next_lsp_request_id;
+        let count = lenses.len() as u64;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::CodeLens,
+                |handle, _uri, _language| {
+                    lenses
+                        .into_iter()
+                        .zip(first_id..)
+                        .filter_map(|((index, lens), request_id)| {
+                            match handle.code_lens_resolve(request_id, lens) {
+                                Ok(()) => Some((request_id, index)),
+                                Err(e) => {
+                                    tracing::debug!("Failed to resolve code lens: {}", e);
+                                    None
+                                }
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                },
+            )
+            .unwrap_or_default();
+
+        let win = self.active_window_mut();
+        win.next_lsp_request_id += count;
+        for (request_id, index) in sent {
+            win.pending_code_lens_requests.insert(
+                request_id,
+                CodeLensRequest {
+                    buffer_id,
+                    version,
+                    resolve_index: Some(index),
+                },
+            );
+        }
+    }
+
+    /// Offer the lenses of one line in the code action popup; picking one
+    /// runs it through the same path as a command-only code action.
+    fn show_code_lens_popup(&mut self, commands: Vec<lsp_types::Command>) {
+        use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+        use ratatui::style::Style;
+
+        let items = commands
+            .iter()
+            .enumerate()
+            .map(|(i, cmd)| PopupListItem {
+                text: format!("{}. {}", i + 1, cmd.title),
+                detail: None,
+                icon: None,
+                data: Some(i.to_string()),
+                disabled: false,
+            })
+            .collect();
+        self.active_window_mut().pending_code_actions = Some(
+            commands
+                .into_iter()
+                .map(|cmd| (String::new(), lsp_types::CodeActionOrCommand::Command(cmd)))
+                .collect(),
+        );
+
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.title = Some(t!("lsp.popup_code_lenses").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 60;
+        popup.max_height = 15;
+        popup.border_style = Style::default().fg(theme.popup_border_fg);
+        popup.background_style = Style::default().bg(theme.popup_bg);
+        popup.resolver = PopupResolver::CodeAction;
+        popup.focused = true;
+        drop(theme);
+
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+}
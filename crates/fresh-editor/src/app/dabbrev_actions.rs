@@ -212,8 +212,8 @@ impl Editor {
 
     /// Run the `CompletionService` (buffer-words + dabbrev providers) and
     /// return results as `PopupListItemData` items suitable for the
-    /// completion popup. The items use icon `"w"` to visually distinguish
-    /// them from LSP results.
+    /// completion popup. Items without a provider icon use `"w"` to
+    /// visually distinguish them from LSP results.
     ///
     /// Returns an empty vec if the prefix is empty or no candidates match.
     pub(crate) fn get_buffer_completion_popup_items(
@@ -276,7 +276,7 @@ impl Editor {
             scan_range,
             viewport_top_byte,
             viewport_bottom_byte,
-            language_id: None,
+            language_id: Some(self.active_state().language.clone()),
             word_chars_extra,
             prefix_has_uppercase: prefix_has_upper,
             other_buffers,
@@ -292,7 +292,7 @@ impl Editor {
             .map(|c| PopupListItemData {
                 text: c.label.clone(),
                 detail: c.detail.clone(),
-                icon: Some("w".to_string()),
+                icon: c.icon.or_else(|| Some("w".to_string())),
                 data: c.insert_text.or(Some(c.label)),
             })
             .collect()
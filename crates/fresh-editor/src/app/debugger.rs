@@ -0,0 +1,306 @@
+//! Self-contained debugger state.
+//!
+//! Holds a window's breakpoints and its (at most one) live debug adapter
+//! session. Breakpoints are stored per file as 0-based line numbers; while a
+//! file is open its margin indicators are authoritative (they follow edits)
+//! and are folded back in here whenever the set is sent to the adapter.
+//! Talking to the adapter, moving the cursor and firing hooks lives in
+//! `debugger_actions`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::model::event::BufferId;
+use crate::services::dap::DapHandle;
+use crate::types::DebugRequestKind;
+
+/// Margin-indicator namespace for breakpoints.
+pub(crate) const BREAKPOINT_NAMESPACE: &str = "debug-breakpoint";
+
+/// Margin-indicator and overlay namespace for the current stack frame.
+pub(crate) const FRAME_NAMESPACE: &str = "debug-frame";
+
+/// Why a DAP request was sent; decides what to do with its response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DapRequestPurpose {
+    Initialize,
+    /// `launch` or `attach`
+    Launch,
+    SetBreakpoints,
+    ConfigurationDone,
+    StackTrace {
+        thread_id: i64,
+        reason: String,
+    },
+    /// `continue`, `next`, `stepIn`, `stepOut`, `pause`
+    Control,
+    Disconnect,
+    /// Issued by a plugin through `editor.debugRequest`
+    Plugin {
+        request_id: u64,
+    },
+}
+
+/// Where the debuggee is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SessionPhase {
+    /// `initialize`/`launch` sent, waiting for the `initialized` event
+    Initializing,
+    Running,
+    Stopped {
+        thread_id: i64,
+    },
+    /// `disconnect` sent, waiting for the adapter to exit
+    Terminating,
+}
+
+/// A live debug adapter session.
+#[derive(Debug)]
+pub(crate) struct DebugSession {
+    pub handle: DapHandle,
+    pub phase: SessionPhase,
+    /// `configurationDone` has been sent, so breakpoint edits must be
+    /// forwarded to the adapter as they happen.
+    pub configured: bool,
+    /// Last thread reported by a `stopped` event (target of `pause`)
+    pub last_thread_id: Option<i64>,
+    /// Exit code from the `exited` event, reported when the session ends
+    pub exit_code: Option<i64>,
+    /// Buffer currently carrying the stack-frame highlight
+    pub frame_buffer: Option<BufferId>,
+    /// `launch`/`attach` request to send once `initialize` succeeds
+    pub launch: Option<(DebugRequestKind, Value)>,
+    pending: HashMap<u64, DapRequestPurpose>,
+    next_tag: u64,
+}
+
+impl DebugSession {
+    pub(crate) fn new(handle: DapHandle, request: DebugRequestKind, configuration: Value) -> Self {
+        Self {
+            handle,
+            phase: SessionPhase::Initializing,
+            configured: false,
+            last_thread_id: None,
+            exit_code: None,
+            frame_buffer: None,
+            launch: Some((request, configuration)),
+            pending: HashMap::new(),
+            next_tag: 1,
+        }
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.handle.session_id()
+    }
+
+    /// Record `purpose` for a request about to be sent and return its tag.
+    pub(crate) fn track(&mut self, purpose: DapRequestPurpose) -> u64 {
+        let tag = self.next_tag;
+        self.next_tag += 1;
+        self.pending.insert(tag, purpose);
+        tag
+    }
+
+    /// Forget and return the purpose of the request tagged `tag`.
+    pub(crate) fn take_pending(&mut self, tag: u64) -> Option<DapRequestPurpose> {
+        self.pending.remove(&tag)
+    }
+
+    /// Forget every outstanding request (the adapter is gone).
+    pub(crate) fn drain_pending(&mut self) -> Vec<DapRequestPurpose> {
+        self.pending.drain().map(|(_, purpose)| purpose).collect()
+    }
+
+    /// Thread to resume or step, when the debuggee is stopped.
+    pub(crate) fn stopped_thread(&self) -> Option<i64> {
+        match self.phase {
+            SessionPhase::Stopped { thread_id } => Some(thread_id),
+            _ => None,
+        }
+    }
+}
+
+/// Owner of a window's breakpoints and debug session.
+#[derive(Debug, Default)]
+pub(crate) struct DebuggerState {
+    breakpoints: BTreeMap<PathBuf, BTreeSet<usize>>,
+    pub session: Option<DebugSession>,
+    next_session_id: u64,
+}
+
+impl DebuggerState {
+    /// Allocate an id for a new session. Ids are never reused so messages
+    /// from a previous adapter can't be mistaken for the current one.
+    pub(crate) fn alloc_session_id(&mut self) -> u64 {
+        self.next_session_id += 1;
+        self.next_session_id
+    }
+
+    /// The session with `session_id`, if it is still the current one.
+    pub(crate) fn session_mut(&mut self, session_id: u64) -> Option<&mut DebugSession> {
+        self.session.as_mut().filter(|s| s.id() == session_id)
+    }
+
+    /// Breakpoint lines (0-based) in `path`.
+    pub(crate) fn breakpoints_in(&self, path: &Path) -> Vec<usize> {
+        self.breakpoints
+            .get(path)
+            .map(|lines| lines.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Replace the breakpoints of `path`. An empty set forgets the file.
+    pub(crate) fn set_breakpoints(&mut self, path: PathBuf, lines: BTreeSet<usize>) {
+        if lines.is_empty() {
+            self.breakpoints.remove(&path);
+        } else {
+            self.breakpoints.insert(path, lines);
+        }
+    }
+
+    /// Toggle the breakpoint at `line` (0-based). Returns `true` when the
+    /// breakpoint is now set.
+    pub(crate) fn toggle(&mut self, path: &Path, line: usize) -> bool {
+        let mut lines = self.breakpoints.remove(path).unwrap_or_default();
+        let now_set = if lines.remove(&line) {
+            false
+        } else {
+            lines.insert(line);
+            true
+        };
+        self.set_breakpoints(path.to_path_buf(), lines);
+        now_set
+    }
+
+    /// Files that have at least one breakpoint.
+    pub(crate) fn files(&self) -> Vec<PathBuf> {
+        self.breakpoints.keys().cloned().collect()
+    }
+}
+
+/// Replace top-level keys of a launch configuration with those of
+/// `overrides`; a `null` override removes the key.
+pub(crate) fn apply_overrides(configuration: &mut Value, overrides: Value) {
+    let (Value::Object(base), Value::Object(overrides)) = (configuration, overrides) else {
+        return;
+    };
+    for (key, value) in overrides {
+        if value.is_null() {
+            base.remove(&key);
+        } else {
+            base.insert(key, value);
+        }
+    }
+}
+
+/// Expand `${file}`, `${fileDirname}`, `${fileBasenameNoExtension}` and
+/// `${workspaceFolder}` in every string of a launch configuration.
+pub(crate) fn substitute_variables(value: Value, file: Option<&Path>, workspace: &Path) -> Value {
+    match value {
+        Value::String(s) => {
+            let mut out = s.replace("${workspaceFolder}", &workspace.to_string_lossy());
+            if let Some(file) = file {
+                let dirname = file.parent().unwrap_or(workspace);
+                let stem = file.file_stem().unwrap_or_default();
+                out = out
+                    .replace("${fileDirname}", &dirname.to_string_lossy())
+                    .replace("${fileBasenameNoExtension}", &stem.to_string_lossy())
+                    .replace("${file}", &file.to_string_lossy());
+            }
+            Value::String(out)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|v| substitute_variables(v, file, workspace))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, substitute_variables(v, file, workspace)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn toggle_adds_then_removes() {
+        let mut s = DebuggerState::default();
+        let path = Path::new("/p/main.rs");
+        assert!(s.toggle(path, 4));
+        assert!(s.toggle(path, 9));
+        assert_eq!(s.breakpoints_in(path), vec![4, 9]);
+        assert!(!s.toggle(path, 4));
+        assert_eq!(s.breakpoints_in(path), vec![9]);
+    }
+
+    #[test]
+    fn removing_last_breakpoint_forgets_file() {
+        let mut s = DebuggerState::default();
+        let path = Path::new("/p/main.rs");
+        s.toggle(path, 1);
+        assert_eq!(s.files(), vec![PathBuf::from("/p/main.rs")]);
+        s.toggle(path, 1);
+        assert!(s.files().is_empty());
+    }
+
+    #[test]
+    fn session_ids_are_not_reused() {
+        let mut s = DebuggerState::default();
+        let a = s.alloc_session_id();
+        let b = s.alloc_session_id();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn substitutes_file_and_workspace_variables() {
+        let config = json!({
+            "program": "${fileDirname}/target/debug/${fileBasenameNoExtension}",
+            "args": ["${file}"],
+            "cwd": "${workspaceFolder}",
+            "stopOnEntry": false
+        });
+        let out = substitute_variables(config, Some(Path::new("/w/src/main.rs")), Path::new("/w"));
+        assert_eq!(
+            out,
+            json!({
+                "program": "/w/src/target/debug/main",
+                "args": ["/w/src/main.rs"],
+                "cwd": "/w",
+                "stopOnEntry": false
+            })
+        );
+    }
+
+    #[test]
+    fn overrides_replace_and_remove_keys() {
+        let mut config = json!({ "program": "${file}", "cwd": "/w", "stopOnEntry": false });
+        apply_overrides(
+            &mut config,
+            json!({ "program": null, "module": "pytest", "args": ["t.py::test_a"] }),
+        );
+        assert_eq!(
+            config,
+            json!({
+                "cwd": "/w",
+                "stopOnEntry": false,
+                "module": "pytest",
+                "args": ["t.py::test_a"]
+            })
+        );
+    }
+
+    #[test]
+    fn file_variables_are_left_alone_without_a_file() {
+        let out = substitute_variables(json!("${file}:${workspaceFolder}"), None, Path::new("/w"));
+        assert_eq!(out, json!("${file}:/w"));
+    }
+}
@@ -0,0 +1,834 @@
+//! Debugger orchestrators.
+//!
+//! Breakpoint bookkeeping (toggling, keeping margin indicators and the
+//! stored per-file sets in step) is pure window state and lives on
+//! `impl Window`. Driving the debug adapter — starting a session, reacting
+//! to its responses and events, stepping — stays on `impl Editor` because it
+//! needs the tokio runtime, file-open orchestration and plugin hooks.
+//!
+//! Session flow: `initialize` → `launch`/`attach` → (adapter sends
+//! `initialized`) → one `setBreakpoints` per file → `configurationDone`.
+//! Every `stopped` event is followed by a `stackTrace` request whose top
+//! frame is revealed in the editor.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use ratatui::style::{Color, Style};
+use rust_i18n::t;
+use serde_json::{json, Value};
+
+use crate::app::debugger::{
+    apply_overrides, substitute_variables, DapRequestPurpose, DebugSession, SessionPhase,
+    BREAKPOINT_NAMESPACE, FRAME_NAMESPACE,
+};
+use crate::model::event::BufferId;
+use crate::services::dap::protocol::{
+    ExitedEventBody, OutputEventBody, StackFrame, StackTraceResponseBody, StoppedEventBody,
+};
+use crate::services::dap::{DapHandle, DapLaunch};
+use crate::services::plugins::hooks::{DebugStackFrame, HookArgs};
+use crate::view::margin::LineIndicator;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+use fresh_core::WindowId;
+
+use super::Editor;
+
+const BREAKPOINT_SYMBOL: &str = "●";
+const BREAKPOINT_COLOR: Color = Color::Rgb(229, 72, 77);
+const BREAKPOINT_PRIORITY: i32 = 20;
+
+const FRAME_SYMBOL: &str = "▶";
+const FRAME_COLOR: Color = Color::Rgb(229, 192, 123);
+/// Above breakpoints so the current line shows the arrow, not the dot.
+const FRAME_PRIORITY: i32 = 30;
+
+/// Frames requested per `stackTrace`; deep recursion is truncated.
+const MAX_STACK_FRAMES: i64 = 20;
+
+impl crate::app::window::Window {
+    /// Toggle a breakpoint on the primary cursor's line. Returns the file
+    /// whose breakpoint set changed, or `None` if the buffer has no file.
+    pub(crate) fn toggle_breakpoint_at_cursor(&mut self) -> Option<PathBuf> {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        let Some(path) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|s| s.buffer.file_path())
+            .map(|p| p.to_path_buf())
+        else {
+            self.set_status_message(t!("debug.breakpoint_needs_file").to_string());
+            return None;
+        };
+
+        self.sync_breakpoints_from_buffer(buffer_id, &path);
+        let line = self.active_state().buffer.get_line_number(position);
+        let now_set = self.debugger.toggle(&path, line);
+        self.refresh_breakpoint_indicators(buffer_id, &path);
+
+        let message = if now_set {
+            t!("debug.breakpoint_set", line = line + 1)
+        } else {
+            t!("debug.breakpoint_removed", line = line + 1)
+        };
+        self.set_status_message(message.to_string());
+        Some(path)
+    }
+
+    /// Redraw the breakpoint indicators of `buffer_id` from the stored set.
+    /// Called when a file is opened so breakpoints survive close/reopen.
+    pub(crate) fn refresh_breakpoint_indicators(&mut self, buffer_id: BufferId, path: &Path) {
+        let lines = self.debugger.breakpoints_in(path);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        state
+            .margins
+            .clear_line_indicators_for_namespace(BREAKPOINT_NAMESPACE);
+        for line in lines {
+            if let Some(byte_offset) = state.buffer.line_start_offset(line) {
+                state.margins.set_line_indicator(
+                    byte_offset,
+                    BREAKPOINT_NAMESPACE.to_string(),
+                    LineIndicator::new(BREAKPOINT_SYMBOL, BREAKPOINT_COLOR, BREAKPOINT_PRIORITY),
+                );
+            }
+        }
+    }
+
+    /// Fold the indicator positions of an open buffer back into the stored
+    /// set, so breakpoints follow lines inserted or deleted above them.
+    fn sync_breakpoints_from_buffer(&mut self, buffer_id: BufferId, path: &Path) {
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let end = state.buffer.len().saturating_add(1);
+        let lines: BTreeSet<usize> = state
+            .margins
+            .query_indicator_range(0, end)
+            .into_iter()
+            .filter(|(marker_id, _, _)| {
+                state
+                    .margins
+                    .namespaces_for_marker(*marker_id)
+                    .iter()
+                    .any(|ns| ns == BREAKPOINT_NAMESPACE)
+            })
+            .map(|(_, start, _)| state.buffer.get_line_number(start))
+            .collect();
+        self.debugger.set_breakpoints(path.to_path_buf(), lines);
+    }
+
+    /// Every file with breakpoints and its 0-based lines, with open buffers
+    /// synced from their indicators first.
+    fn collect_breakpoints(&mut self) -> Vec<(PathBuf, Vec<usize>)> {
+        let stored = self.debugger.files();
+        for buffer_id in self.buffers.ids() {
+            let path = self
+                .buffers
+                .get(&buffer_id)
+                .and_then(|s| s.buffer.file_path())
+                .map(|p| p.to_path_buf());
+            if let Some(path) = path.filter(|p| stored.contains(p)) {
+                self.sync_breakpoints_from_buffer(buffer_id, &path);
+            }
+        }
+        self.debugger
+            .files()
+            .into_iter()
+            .map(|path| {
+                let lines = self.debugger.breakpoints_in(&path);
+                (path, lines)
+            })
+            .collect()
+    }
+
+    /// Remove the current-frame arrow and line highlight.
+    fn clear_debug_frame(&mut self) {
+        let Some(buffer_id) = self
+            .debugger
+            .session
+            .as_mut()
+            .and_then(|s| s.frame_buffer.take())
+        else {
+            return;
+        };
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state
+                .margins
+                .clear_line_indicators_for_namespace(FRAME_NAMESPACE);
+            let ns = OverlayNamespace::from_string(FRAME_NAMESPACE.to_string());
+            state.overlays.clear_namespace(&ns, &mut state.marker_list);
+        }
+    }
+
+    /// Mark `line` (1-based) of `buffer_id` as the current stack frame.
+    fn mark_debug_frame(&mut self, buffer_id: BufferId, line: usize) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(start) = state.buffer.line_start_offset(line.saturating_sub(1)) else {
+            return;
+        };
+        let end = state
+            .buffer
+            .line_start_offset(line)
+            .unwrap_or_else(|| state.buffer.len());
+        state.margins.set_line_indicator(
+            start,
+            FRAME_NAMESPACE.to_string(),
+            LineIndicator::new(FRAME_SYMBOL, FRAME_COLOR, FRAME_PRIORITY),
+        );
+        let overlay = Overlay::with_namespace(
+            &mut state.marker_list,
+            start..end,
+            OverlayFace::ThemedStyle {
+                fallback_style: Style::default().bg(Color::Rgb(70, 62, 30)),
+                fg_theme: None,
+                bg_theme: Some("editor.diff_modify_bg".to_string()),
+                fg_on_collision_only: false,
+            },
+            OverlayNamespace::from_string(FRAME_NAMESPACE.to_string()),
+        )
+        .with_priority_value(5)
+        .with_extend_to_line_end(true);
+        state.overlays.add(overlay);
+        if let Some(session) = self.debugger.session.as_mut() {
+            session.frame_buffer = Some(buffer_id);
+        }
+    }
+}
+
+impl Editor {
+    // ==================== Actions ====================
+
+    /// Start a debug session for the active buffer's language.
+    pub(super) fn debug_start(&mut self) {
+        self.debug_start_with(None);
+    }
+
+    /// Start a debug session for the active buffer's language, with
+    /// `overrides` applied to the configured launch/attach arguments (see
+    /// [`apply_overrides`]). Plugins use this to debug a single test.
+    pub(super) fn debug_start_with(&mut self, overrides: Option<Value>) {
+        if self.active_window().debugger.session.is_some() {
+            self.set_status_message(t!("debug.already_running").to_string());
+            return;
+        }
+
+        let language = self.active_state().language.clone();
+        let Some(debugger) = self.config().debuggers.get(&language).cloned() else {
+            self.set_status_message(t!("debug.no_config", language = language).to_string());
+            return;
+        };
+        if debugger.command.is_empty() {
+            self.set_status_message(t!("debug.no_config", language = language).to_string());
+            return;
+        }
+        let Some(runtime) = self.tokio_runtime.clone() else {
+            self.set_status_message(t!("debug.no_runtime").to_string());
+            return;
+        };
+
+        let file = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf());
+        let window = self.active_window_mut();
+        let root = window.root.clone();
+        let mut configuration = debugger.configuration.clone().unwrap_or_else(|| json!({}));
+        if let Some(overrides) = overrides {
+            apply_overrides(&mut configuration, overrides);
+        }
+        let configuration = substitute_variables(configuration, file.as_deref(), &root);
+
+        // Sorted so the spawned environment is deterministic (matters for
+        // docker `-e` argument order, same as LSP spawning).
+        let mut env: Vec<(String, String)> = debugger
+            .env
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        env.sort();
+
+        let session_id = window.debugger.alloc_session_id();
+        let handle = DapHandle::spawn(
+            runtime.handle(),
+            session_id,
+            DapLaunch {
+                command: debugger.command.clone(),
+                args: debugger.args.clone(),
+                env,
+                cwd: Some(root),
+            },
+            window.bridge.sender(),
+            window.authority.long_running_spawner.clone(),
+        );
+        window.debugger.session = Some(DebugSession::new(handle, debugger.request, configuration));
+
+        let window_id = self.active_window;
+        let initialize = json!({
+            "clientID": "fresh",
+            "clientName": "Fresh",
+            "adapterID": debugger.adapter_id(),
+            "locale": crate::i18n::current_locale(),
+            "pathFormat": "path",
+            "linesStartAt1": true,
+            "columnsStartAt1": true,
+            "supportsVariableType": true,
+            "supportsRunInTerminalRequest": false,
+        });
+        if let Err(e) = self.dap_send(
+            window_id,
+            "initialize",
+            Some(initialize),
+            DapRequestPurpose::Initialize,
+        ) {
+            self.set_status_message(t!("debug.failed", error = e).to_string());
+            return;
+        }
+        self.set_status_message(t!("debug.starting", command = debugger.command).to_string());
+    }
+
+    /// End the debug session, terminating the debuggee.
+    pub(super) fn debug_stop(&mut self) {
+        let window_id = self.active_window;
+        let Some(session) = self.active_window_mut().debugger.session.as_mut() else {
+            self.set_status_message(t!("debug.no_session").to_string());
+            return;
+        };
+        if session.phase == SessionPhase::Terminating {
+            // Second stop while waiting for the adapter: stop waiting.
+            session.handle.kill();
+            return;
+        }
+        session.phase = SessionPhase::Terminating;
+        if self
+            .dap_send(
+                window_id,
+                "disconnect",
+                Some(json!({ "terminateDebuggee": true })),
+                DapRequestPurpose::Disconnect,
+            )
+            .is_err()
+        {
+            if let Some(session) = self.active_window().debugger.session.as_ref() {
+                session.handle.kill();
+            }
+        }
+        self.set_status_message(t!("debug.stopping").to_string());
+    }
+
+    /// Resume the stopped thread, or start a session if none is running.
+    pub(super) fn debug_continue(&mut self) {
+        if self.active_window().debugger.session.is_none() {
+            self.debug_start();
+            return;
+        }
+        self.debug_resume("continue");
+    }
+
+    pub(super) fn debug_step_over(&mut self) {
+        self.debug_resume("next");
+    }
+
+    pub(super) fn debug_step_into(&mut self) {
+        self.debug_resume("stepIn");
+    }
+
+    pub(super) fn debug_step_out(&mut self) {
+        self.debug_resume("stepOut");
+    }
+
+    /// Interrupt the running debuggee.
+    pub(super) fn debug_pause(&mut self) {
+        let window_id = self.active_window;
+        let Some(session) = self.active_window().debugger.session.as_ref() else {
+            self.set_status_message(t!("debug.no_session").to_string());
+            return;
+        };
+        if session.phase != SessionPhase::Running {
+            self.set_status_message(t!("debug.not_running").to_string());
+            return;
+        }
+        // Adapters accept any known thread; before the first stop we have
+        // none, and 1 is the main thread for every adapter we know of.
+        let thread_id = session.last_thread_id.unwrap_or(1);
+        if let Err(e) = self.dap_send(
+            window_id,
+            "pause",
+            Some(json!({ "threadId": thread_id })),
+            DapRequestPurpose::Control,
+        ) {
+            self.set_status_message(t!("debug.failed", error = e).to_string());
+        }
+    }
+
+    /// Toggle a breakpoint on the cursor line and, during a session, send
+    /// the file's new breakpoint set to the adapter.
+    pub(super) fn debug_toggle_breakpoint(&mut self) {
+        let window_id = self.active_window;
+        let Some(path) = self.active_window_mut().toggle_breakpoint_at_cursor() else {
+            return;
+        };
+        let configured = self
+            .active_window()
+            .debugger
+            .session
+            .as_ref()
+            .is_some_and(|s| s.configured);
+        if configured {
+            let lines = self.active_window().debugger.breakpoints_in(&path);
+            self.dap_set_breakpoints(window_id, &path, &lines);
+        }
+    }
+
+    /// Send `continue` / `next` / `stepIn` / `stepOut` for the stopped thread.
+    fn debug_resume(&mut self, command: &str) {
+        let window_id = self.active_window;
+        let Some(session) = self.active_window().debugger.session.as_ref() else {
+            self.set_status_message(t!("debug.no_session").to_string());
+            return;
+        };
+        let Some(thread_id) = session.stopped_thread() else {
+            self.set_status_message(t!("debug.not_stopped").to_string());
+            return;
+        };
+        if let Err(e) = self.dap_send(
+            window_id,
+            command,
+            Some(json!({ "threadId": thread_id })),
+            DapRequestPurpose::Control,
+        ) {
+            self.set_status_message(t!("debug.failed", error = e).to_string());
+            return;
+        }
+        self.debug_mark_running(window_id, Some(thread_id));
+    }
+
+    // ==================== Plugin API ====================
+
+    /// Forward a plugin's `editor.debugRequest` to the active session. The
+    /// plugin callback is settled when the response arrives.
+    pub(super) fn debug_plugin_request(
+        &mut self,
+        command: &str,
+        arguments: Option<Value>,
+        request_id: u64,
+    ) -> Result<(), String> {
+        let window_id = self.active_window;
+        if self.active_window().debugger.session.is_none() {
+            return Err(t!("debug.no_session").to_string());
+        }
+        self.dap_send(
+            window_id,
+            command,
+            arguments,
+            DapRequestPurpose::Plugin { request_id },
+        )
+    }
+
+    // ==================== Adapter messages ====================
+
+    /// Handle a response from the debug adapter.
+    pub(super) fn handle_dap_response(
+        &mut self,
+        session_id: u64,
+        tag: u64,
+        command: String,
+        result: Result<Value, String>,
+    ) {
+        let Some(window_id) = self.debug_session_window(session_id) else {
+            return;
+        };
+        let Some(purpose) = self.windows.get_mut(&window_id).and_then(|w| {
+            w.debugger
+                .session_mut(session_id)
+                .and_then(|s| s.take_pending(tag))
+        }) else {
+            return;
+        };
+
+        match (purpose, result) {
+            (DapRequestPurpose::Initialize, Ok(_)) => {
+                let launch = self
+                    .windows
+                    .get_mut(&window_id)
+                    .and_then(|w| w.debugger.session_mut(session_id))
+                    .and_then(|s| s.launch.take());
+                if let Some((request, configuration)) = launch {
+                    if let Err(e) = self.dap_send(
+                        window_id,
+                        request.as_str(),
+                        Some(configuration),
+                        DapRequestPurpose::Launch,
+                    ) {
+                        self.set_status_message(t!("debug.failed", error = e).to_string());
+                    }
+                }
+            }
+            (DapRequestPurpose::Initialize | DapRequestPurpose::Launch, Err(e)) => {
+                self.set_status_message(t!("debug.failed", error = e).to_string());
+                if let Some(session) = self
+                    .windows
+                    .get(&window_id)
+                    .and_then(|w| w.debugger.session.as_ref())
+                {
+                    session.handle.kill();
+                }
+            }
+            (DapRequestPurpose::ConfigurationDone, Ok(_)) => {
+                if let Some(session) = self
+                    .windows
+                    .get_mut(&window_id)
+                    .and_then(|w| w.debugger.session_mut(session_id))
+                {
+                    if session.phase == SessionPhase::Initializing {
+                        session.phase = SessionPhase::Running;
+                    }
+                }
+                self.set_status_message(t!("debug.running").to_string());
+            }
+            (DapRequestPurpose::StackTrace { thread_id, reason }, Ok(body)) => {
+                match serde_json::from_value::<StackTraceResponseBody>(body) {
+                    Ok(trace) => self.debug_show_stop(window_id, thread_id, reason, trace),
+                    Err(e) => tracing::warn!("Malformed stackTrace response: {}", e),
+                }
+            }
+            (DapRequestPurpose::Control | DapRequestPurpose::StackTrace { .. }, Err(e)) => {
+                self.set_status_message(
+                    t!("debug.request_failed", command = command, error = e).to_string(),
+                );
+            }
+            (DapRequestPurpose::SetBreakpoints | DapRequestPurpose::ConfigurationDone, Err(e)) => {
+                tracing::warn!("Debug adapter rejected {}: {}", command, e);
+            }
+            (DapRequestPurpose::Disconnect, _) => {
+                if let Some(session) = self
+                    .windows
+                    .get(&window_id)
+                    .and_then(|w| w.debugger.session.as_ref())
+                {
+                    session.handle.kill();
+                }
+            }
+            (DapRequestPurpose::Plugin { request_id }, result) => {
+                let callback_id = fresh_core::api::JsCallbackId::from(request_id);
+                let plugin_manager = self.plugin_manager.read().unwrap();
+                match result {
+                    Ok(value) => plugin_manager.resolve_callback(callback_id, value.to_string()),
+                    Err(err) => plugin_manager.reject_callback(callback_id, err),
+                }
+            }
+            (
+                DapRequestPurpose::Launch
+                | DapRequestPurpose::SetBreakpoints
+                | DapRequestPurpose::Control,
+                Ok(_),
+            ) => {}
+        }
+    }
+
+    /// Handle an event from the debug adapter.
+    pub(super) fn handle_dap_event(&mut self, session_id: u64, event: String, body: Value) {
+        let Some(window_id) = self.debug_session_window(session_id) else {
+            return;
+        };
+        match event.as_str() {
+            "initialized" => {
+                let breakpoints = self
+                    .windows
+                    .get_mut(&window_id)
+                    .map(|w| w.collect_breakpoints())
+                    .unwrap_or_default();
+                for (path, lines) in breakpoints {
+                    self.dap_set_breakpoints(window_id, &path, &lines);
+                }
+                if let Err(e) = self.dap_send(
+                    window_id,
+                    "configurationDone",
+                    None,
+                    DapRequestPurpose::ConfigurationDone,
+                ) {
+                    tracing::warn!("Failed to send configurationDone: {}", e);
+                }
+                if let Some(session) = self
+                    .windows
+                    .get_mut(&window_id)
+                    .and_then(|w| w.debugger.session_mut(session_id))
+                {
+                    session.configured = true;
+                }
+            }
+            "stopped" => {
+                let stopped: StoppedEventBody = match serde_json::from_value(body) {
+                    Ok(stopped) => stopped,
+                    Err(e) => {
+                        tracing::warn!("Malformed stopped event: {}", e);
+                        return;
+                    }
+                };
+                let Some(session) = self
+                    .windows
+                    .get_mut(&window_id)
+                    .and_then(|w| w.debugger.session_mut(session_id))
+                else {
+                    return;
+                };
+                let thread_id = stopped.thread_id.or(session.last_thread_id).unwrap_or(1);
+                session.phase = SessionPhase::Stopped { thread_id };
+                session.last_thread_id = Some(thread_id);
+                if let Err(e) = self.dap_send(
+                    window_id,
+                    "stackTrace",
+                    Some(json!({
+                        "threadId": thread_id,
+                        "startFrame": 0,
+                        "levels": MAX_STACK_FRAMES,
+                    })),
+                    DapRequestPurpose::StackTrace {
+                        thread_id,
+                        reason: stopped.reason,
+                    },
+                ) {
+                    tracing::warn!("Failed to request stack trace: {}", e);
+                }
+            }
+            "continued" => {
+                let thread_id = body.get("threadId").and_then(Value::as_i64);
+                self.debug_mark_running(window_id, thread_id);
+            }
+            "output" => {
+                let Ok(output) = serde_json::from_value::<OutputEventBody>(body) else {
+                    return;
+                };
+                if output.category.as_deref() == Some("telemetry") {
+                    return;
+                }
+                tracing::info!("debuggee: {}", output.output.trim_end());
+                if window_id == self.active_window {
+                    if let Some(line) = output.output.lines().rev().find(|l| !l.trim().is_empty()) {
+                        self.set_status_message(line.to_string());
+                    }
+                }
+            }
+            "exited" => {
+                if let Ok(exited) = serde_json::from_value::<ExitedEventBody>(body) {
+                    if let Some(session) = self
+                        .windows
+                        .get_mut(&window_id)
+                        .and_then(|w| w.debugger.session_mut(session_id))
+                    {
+                        session.exit_code = Some(exited.exit_code);
+                    }
+                }
+            }
+            "terminated" => {
+                let Some(session) = self
+                    .windows
+                    .get_mut(&window_id)
+                    .and_then(|w| w.debugger.session_mut(session_id))
+                else {
+                    return;
+                };
+                if session.phase != SessionPhase::Terminating {
+                    session.phase = SessionPhase::Terminating;
+                    if self
+                        .dap_send(window_id, "disconnect", None, DapRequestPurpose::Disconnect)
+                        .is_err()
+                    {
+                        self.handle_dap_session_ended(session_id, None);
+                    }
+                }
+            }
+            _ => {
+                tracing::trace!("Ignoring DAP event '{}'", event);
+            }
+        }
+    }
+
+    /// The adapter process exited (or never started). Tears the session
+    /// down and settles anything still waiting on it.
+    pub(super) fn handle_dap_session_ended(&mut self, session_id: u64, error: Option<String>) {
+        let Some(window_id) = self.debug_session_window(session_id) else {
+            return;
+        };
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        window.clear_debug_frame();
+        let Some(mut session) = window.debugger.session.take() else {
+            return;
+        };
+
+        for purpose in session.drain_pending() {
+            if let DapRequestPurpose::Plugin { request_id } = purpose {
+                self.plugin_manager.read().unwrap().reject_callback(
+                    fresh_core::api::JsCallbackId::from(request_id),
+                    t!("debug.ended").to_string(),
+                );
+            }
+        }
+
+        let message = match error {
+            Some(error) => t!("debug.failed", error = error),
+            None => match session.exit_code {
+                Some(code) => t!("debug.ended_with_code", code = code),
+                None => t!("debug.ended"),
+            },
+        };
+        self.set_status_message(message.to_string());
+        self.plugin_manager.read().unwrap().run_hook(
+            "debug_terminated",
+            HookArgs::DebugTerminated {
+                exit_code: session.exit_code,
+            },
+        );
+    }
+
+    // ==================== Helpers ====================
+
+    /// Window owning the session `session_id`, if it is still live.
+    fn debug_session_window(&self, session_id: u64) -> Option<WindowId> {
+        self.windows.iter().find_map(|(id, w)| {
+            w.debugger
+                .session
+                .as_ref()
+                .filter(|s| s.id() == session_id)
+                .map(|_| *id)
+        })
+    }
+
+    /// Send a request on `window_id`'s session, remembering why.
+    fn dap_send(
+        &mut self,
+        window_id: WindowId,
+        command: &str,
+        arguments: Option<Value>,
+        purpose: DapRequestPurpose,
+    ) -> Result<(), String> {
+        let session = self
+            .windows
+            .get_mut(&window_id)
+            .and_then(|w| w.debugger.session.as_mut())
+            .ok_or_else(|| t!("debug.no_session").to_string())?;
+        let tag = session.track(purpose);
+        let result = session.handle.request(tag, command, arguments);
+        if result.is_err() {
+            session.take_pending(tag);
+        }
+        result
+    }
+
+    /// Send the full breakpoint set of one file (DAP replaces per file).
+    fn dap_set_breakpoints(&mut self, window_id: WindowId, path: &Path, lines: &[usize]) {
+        let breakpoints: Vec<Value> = lines
+            .iter()
+            .map(|line| json!({ "line": line + 1 }))
+            .collect();
+        let arguments = json!({
+            "source": {
+                "name": path.file_name().map(|n| n.to_string_lossy().into_owned()),
+                "path": path.to_string_lossy(),
+            },
+            "breakpoints": breakpoints,
+        });
+        if let Err(e) = self.dap_send(
+            window_id,
+            "setBreakpoints",
+            Some(arguments),
+            DapRequestPurpose::SetBreakpoints,
+        ) {
+            tracing::warn!("Failed to send breakpoints for {:?}: {}", path, e);
+        }
+    }
+
+    /// Reveal the top frame of a stopped thread and notify plugins.
+    fn debug_show_stop(
+        &mut self,
+        window_id: WindowId,
+        thread_id: i64,
+        reason: String,
+        trace: StackTraceResponseBody,
+    ) {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.clear_debug_frame();
+        }
+
+        let top = trace.stack_frames.first().cloned();
+        if let Some(frame) = top.as_ref() {
+            self.debug_reveal_frame(window_id, frame);
+            if window_id == self.active_window {
+                self.set_status_message(
+                    t!("debug.stopped", reason = reason, name = frame.name).to_string(),
+                );
+            }
+        }
+
+        let frames = trace
+            .stack_frames
+            .into_iter()
+            .map(|frame| DebugStackFrame {
+                id: frame.id,
+                name: frame.name,
+                path: frame.source.and_then(|s| s.path).map(PathBuf::from),
+                line: frame.line,
+                column: frame.column,
+            })
+            .collect();
+        self.plugin_manager.read().unwrap().run_hook(
+            "debug_stopped",
+            HookArgs::DebugStopped {
+                reason,
+                thread_id,
+                frames,
+            },
+        );
+    }
+
+    /// Open a frame's source at its line and mark it as the current frame.
+    /// Only done for the active window; a background window's session keeps
+    /// running without stealing focus.
+    fn debug_reveal_frame(&mut self, window_id: WindowId, frame: &StackFrame) {
+        if window_id != self.active_window {
+            return;
+        }
+        let Some(path) = frame.source.as_ref().and_then(|s| s.path.as_ref()) else {
+            return;
+        };
+        let line = frame.line.max(1) as usize;
+        let column = frame.column.max(1) as usize;
+        // Open failures are logged by handle_open_file_at_location
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.handle_open_file_at_location(PathBuf::from(path), Some(line), Some(column));
+
+        let buffer_id = self.active_buffer();
+        let opened = self
+            .active_state()
+            .buffer
+            .file_path()
+            .is_some_and(|p| p == Path::new(path));
+        if opened {
+            self.active_window_mut().mark_debug_frame(buffer_id, line);
+        }
+    }
+
+    /// The debuggee resumed: drop the frame highlight and notify plugins.
+    fn debug_mark_running(&mut self, window_id: WindowId, thread_id: Option<i64>) {
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        window.clear_debug_frame();
+        if let Some(session) = window.debugger.session.as_mut() {
+            if session.phase != SessionPhase::Terminating {
+                session.phase = SessionPhase::Running;
+            }
+        }
+        self.plugin_manager
+            .read()
+            .unwrap()
+            .run_hook("debug_continued", HookArgs::DebugContinued { thread_id });
+    }
+}
@@ -0,0 +1,540 @@
+//! Document link and document color orchestrators.
+//!
+//! Links (`textDocument/documentLink`) and color literals
+//! (`textDocument/documentColor`) are pulled per buffer on one debounced
+//! schedule, like code lenses, and stored on the buffer's
+//! [`DocumentLinks`](crate::view::document_link::DocumentLinks) and
+//! [`DocumentColors`](crate::view::document_color::DocumentColors).
+//!
+//! A link is followed with Ctrl+click or the `lsp_open_link` action at the
+//! cursor: `file:` targets open in the editor, jumping to a `#L12,5`
+//! fragment when there is one, and anything else goes to the system's URL
+//! handler. Links that arrive without a target are resolved
+//! (`documentLink/resolve`) when followed.
+//!
+//! The `lsp_color_presentation` action asks the server how the color under
+//! the cursor can be written (`textDocument/colorPresentation`) and offers
+//! the answers in a popup; picking one rewrites the literal as one undo step.
+
+use std::time::Instant;
+
+use rust_i18n::t;
+
+use crate::model::event::BufferId;
+use crate::types::LspFeature;
+use crate::view::document_link::split_target_location;
+
+use super::{DocumentColorRequest, DocumentLinkRequest, Editor};
+
+/// A `textDocument/colorPresentation` request, then the presentations the
+/// picker offers for it.
+#[derive(Debug)]
+pub(crate) struct ColorPresentationPicker {
+    request_id: u64,
+    buffer_id: BufferId,
+    version: u64,
+    /// The literal being rewritten
+    range: lsp_types::Range,
+    /// Empty until the response arrives
+    presentations: Vec<lsp_types::ColorPresentation>,
+}
+
+impl Editor {
+    /// Issue debounced document link and color requests if the timer has
+    /// elapsed.
+    pub(crate) fn maybe_request_document_decorations_debounced(&mut self, buffer_id: BufferId) {
+        let Some(ready_at) = self
+            .active_window()
+            .document_decorations_debounce
+            .get(&buffer_id)
+            .copied()
+        else {
+            return;
+        };
+        if Instant::now() < ready_at {
+            return;
+        }
+
+        self.active_window_mut()
+            .document_decorations_debounce
+            .remove(&buffer_id);
+        self.request_document_links_for_buffer(buffer_id);
+        self.request_document_colors_for_buffer(buffer_id);
+    }
+
+    /// Schedule a document link and color refresh for all open buffers of a
+    /// language.
+    pub(super) fn request_document_decorations_for_language(&mut self, language: &str) {
+        let buffer_ids: Vec<_> = self
+            .buffers_for_language(language)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for buffer_id in buffer_ids {
+            self.active_window_mut()
+                .schedule_document_decorations_refresh(buffer_id);
+        }
+    }
+
+    /// Request document links for a buffer from the server that provides
+    /// them.
+    fn request_document_links_for_buffer(&mut self, buffer_id: BufferId) {
+        let Some(version) = self.buffers().get(&buffer_id).map(|s| s.buffer.version()) else {
+            return;
+        };
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::DocumentLink,
+                |handle, uri, _language| match handle
+                    .document_link(request_id, uri.as_uri().clone())
+                {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request document links: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.pending_document_link_requests.insert(
+                request_id,
+                DocumentLinkRequest {
+                    buffer_id,
+                    version,
+                    resolve_index: None,
+                },
+            );
+        }
+    }
+
+    /// Request color literals for a buffer from the server that provides
+    /// them.
+    fn request_document_colors_for_buffer(&mut self, buffer_id: BufferId) {
+        let Some(version) = self.buffers().get(&buffer_id).map(|s| s.buffer.version()) else {
+            return;
+        };
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::DocumentColor,
+                |handle, uri, _language| match handle
+                    .document_color(request_id, uri.as_uri().clone())
+                {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request document colors: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.pending_document_color_requests
+                .insert(request_id, DocumentColorRequest { buffer_id, version });
+        }
+    }
+
+    /// Handle a `textDocument/documentLink` response: replace the buffer's
+    /// links.
+    pub(super) fn handle_lsp_document_links(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        links: Vec<lsp_types::DocumentLink>,
+    ) {
+        let Some(request) = self
+            .active_window_mut()
+            .pending_document_link_requests
+            .remove(&request_id)
+        else {
+            tracing::debug!(
+                "Ignoring document link response without pending request (request_id={})",
+                request_id
+            );
+            return;
+        };
+        let buffer_id = request.buffer_id;
+
+        let win = self.active_window_mut();
+        let Some(state) = win.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version {
+            tracing::debug!(
+                "Ignoring stale document links for {} (request_id={})",
+                uri,
+                request_id
+            );
+            win.schedule_document_decorations_refresh(buffer_id);
+            return;
+        }
+
+        tracing::debug!("Received {} document links for {}", links.len(), uri);
+        state.document_links.set(
+            links,
+            &state.buffer,
+            &mut state.overlays,
+            &mut state.marker_list,
+        );
+        // Resolves of the previous set point at indices that no longer exist.
+        win.pending_document_link_requests
+            .retain(|_, r| r.buffer_id != buffer_id || r.resolve_index.is_none());
+    }
+
+    /// Handle a `documentLink/resolve` response: store the link and follow
+    /// it, since links are only resolved when followed.
+    pub(super) fn handle_lsp_document_link_resolved(
+        &mut self,
+        request_id: u64,
+        link: Result<lsp_types::DocumentLink, String>,
+    ) {
+        let Some(request) = self
+            .active_window_mut()
+            .pending_document_link_requests
+            .remove(&request_id)
+        else {
+            return;
+        };
+        let Some(index) = request.resolve_index else {
+            return;
+        };
+        let target = link.ok().and_then(|link| {
+            let target = link.target.as_ref()?.as_str().to_string();
+            if let Some(state) = self.active_window_mut().buffers.get_mut(&request.buffer_id) {
+                if state.buffer.version() == request.version {
+                    state
+                        .document_links
+                        .resolve(index, link, &mut state.overlays);
+                }
+            }
+            Some(target)
+        });
+        match target {
+            Some(target) => self.open_link_target(&target),
+            None => self.set_status_message(t!("lsp.document_link_no_target").to_string()),
+        }
+    }
+
+    /// Handle a `textDocument/documentColor` response: replace the buffer's
+    /// color swatches.
+    pub(super) fn handle_lsp_document_colors(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        colors: Vec<lsp_types::ColorInformation>,
+    ) {
+        let Some(request) = self
+            .active_window_mut()
+            .pending_document_color_requests
+            .remove(&request_id)
+        else {
+            tracing::debug!(
+                "Ignoring document color response without pending request (request_id={})",
+                request_id
+            );
+            return;
+        };
+        let buffer_id = request.buffer_id;
+
+        let win = self.active_window_mut();
+        let Some(state) = win.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != request.version {
+            tracing::debug!(
+                "Ignoring stale document colors for {} (request_id={})",
+                uri,
+                request_id
+            );
+            win.schedule_document_decorations_refresh(buffer_id);
+            return;
+        }
+
+        tracing::debug!("Received {} document colors for {}", colors.len(), uri);
+        state.document_colors.set(
+            colors,
+            &state.buffer,
+            &mut state.virtual_texts,
+            &mut state.marker_list,
+        );
+    }
+
+    /// Follow the document link at the cursor.
+    pub(crate) fn open_document_link_at_cursor(&mut self) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        if !self.follow_document_link_at(buffer_id, position) {
+            self.set_status_message(t!("lsp.no_document_link").to_string());
+        }
+    }
+
+    /// Follow the document link covering byte `position` of `buffer_id`.
+    /// Returns `false` when there is none.
+    pub(super) fn follow_document_link_at(&mut self, buffer_id: BufferId, position: usize) -> bool {
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return false;
+        };
+        let Some(index) =
+            state
+                .document_links
+                .index_at(position, &state.overlays, &state.marker_list)
+        else {
+            return false;
+        };
+        let Some(link) = state.document_links.get(index).cloned() else {
+            return false;
+        };
+        let version = state.buffer.version();
+
+        if let Some(target) = &link.target {
+            self.open_link_target(target.as_str());
+        } else if self.document_link_resolve_supported(buffer_id) {
+            self.resolve_document_link(buffer_id, version, index, link);
+        } else {
+            self.set_status_message(t!("lsp.document_link_no_target").to_string());
+        }
+        true
+    }
+
+    /// Whether the server providing document links for `buffer_id`
+    /// resolves them.
+    fn document_link_resolve_supported(&self, buffer_id: BufferId) -> bool {
+        let Some(language) = self.buffers().get(&buffer_id).map(|s| s.language.clone()) else {
+            return false;
+        };
+        self.lsp()
+            .and_then(|lsp| lsp.handle_for_feature(&language, LspFeature::DocumentLink))
+            .is_some_and(|sh| sh.capabilities.document_link_resolve)
+    }
+
+    fn resolve_document_link(
+        &mut self,
+        buffer_id: BufferId,
+        version: u64,
+        index: usize,
+        link: lsp_types::DocumentLink,
+    ) {
+        let request_id = self.active_window().next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::DocumentLink,
+                |handle, _uri, _language| match handle.document_link_resolve(request_id, link) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to resolve document link: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.pending_document_link_requests.insert(
+                request_id,
+                DocumentLinkRequest {
+                    buffer_id,
+                    version,
+                    resolve_index: Some(index),
+                },
+            );
+        }
+    }
+
+    /// Open a link target: files in the editor, other URIs with the
+    /// system's handler.
+    fn open_link_target(&mut self, target: &str) {
+        let (uri, line, column) = split_target_location(target);
+        if !uri.starts_with("file:") {
+            #[cfg(feature = "runtime")]
+            if let Err(e) = open::that(target) {
+                self.set_status_message(
+                    t!("lsp.open_link_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+            self.set_status_message(t!("lsp.opening_link", url = target).to_string());
+            return;
+        }
+
+        let Ok(uri) = uri.parse::<lsp_types::Uri>() else {
+            self.set_status_message(t!("lsp.document_link_no_target").to_string());
+            return;
+        };
+        let wire = crate::app::types::LspUri::from_wire(uri);
+        match self.open_lsp_uri_target(&wire) {
+            Ok(_) => {
+                if line.is_some() {
+                    self.jump_to_line_column(line, column);
+                }
+            }
+            Err(e) => {
+                self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string())
+            }
+        }
+    }
+
+    /// Ask the server how the color under the cursor can be written, to
+    /// offer the answers in a picker.
+    pub(crate) fn request_color_presentations(&mut self) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        let found = {
+            let state = self.active_state();
+            state
+                .document_colors
+                .at(position, &state.virtual_texts, &state.marker_list)
+                .map(|(range, color)| {
+                    let (start_line, start_char) =
+                        state.buffer.position_to_lsp_position(range.start);
+                    let (end_line, end_char) = state.buffer.position_to_lsp_position(range.end);
+                    let range = lsp_types::Range {
+                        start: lsp_types::Position::new(start_line as u32, start_char as u32),
+                        end: lsp_types::Position::new(end_line as u32, end_char as u32),
+                    };
+                    (range, color, state.buffer.version())
+                })
+        };
+        let Some((range, color, version)) = found else {
+            self.set_status_message(t!("lsp.no_color_at_cursor").to_string());
+            return;
+        };
+
+        let request_id = self.active_window().next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::DocumentColor,
+                |handle, uri, _language| match handle.color_presentation(
+                    request_id,
+                    uri.as_uri().clone(),
+                    color,
+                    range,
+                ) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request color presentations: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.color_presentation_picker = Some(ColorPresentationPicker {
+                request_id,
+                buffer_id,
+                version,
+                range,
+                presentations: Vec::new(),
+            });
+        }
+    }
+
+    /// Handle a `textDocument/colorPresentation` response: offer the
+    /// presentations in a popup.
+    pub(super) fn handle_lsp_color_presentations(
+        &mut self,
+        request_id: u64,
+        presentations: Vec<lsp_types::ColorPresentation>,
+    ) {
+        use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+        use ratatui::style::Style;
+
+        let awaited = self
+            .active_window()
+            .color_presentation_picker
+            .as_ref()
+            .is_some_and(|p| p.request_id == request_id && p.presentations.is_empty());
+        if !awaited {
+            tracing::debug!("Ignoring stale color presentations: {}", request_id);
+            return;
+        }
+        if presentations.is_empty() {
+            self.active_window_mut().color_presentation_picker = None;
+            self.set_status_message(t!("lsp.no_color_presentations").to_string());
+            return;
+        }
+        let items = presentations
+            .iter()
+            .enumerate()
+            .map(|(i, presentation)| PopupListItem {
+                text: presentation.label.clone(),
+                detail: None,
+                icon: None,
+                data: Some(i.to_string()),
+                disabled: false,
+            })
+            .collect();
+        if let Some(picker) = self.active_window_mut().color_presentation_picker.as_mut() {
+            picker.presentations = presentations;
+        }
+
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.title = Some(t!("lsp.popup_color_presentations").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 40;
+        popup.max_height = 15;
+        popup.border_style = Style::default().fg(theme.popup_border_fg);
+        popup.background_style = Style::default().bg(theme.popup_bg);
+        popup.resolver = PopupResolver::ColorPresentation;
+        popup.focused = true;
+        drop(theme);
+
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+
+    /// Rewrite the literal with the picked presentation, together with its
+    /// additional edits (an added import, say), as one undo step.
+    pub(super) fn apply_color_presentation(&mut self, index: usize) {
+        let Some(picker) = self.active_window_mut().color_presentation_picker.take() else {
+            return;
+        };
+        let Some(presentation) = picker.presentations.into_iter().nth(index) else {
+            return;
+        };
+        let version = self
+            .buffers()
+            .get(&picker.buffer_id)
+            .map(|state| state.buffer.version());
+        if version != Some(picker.version) {
+            tracing::debug!("Dropping color presentation for an edited buffer");
+            return;
+        }
+
+        let edit = presentation
+            .text_edit
+            .unwrap_or_else(|| lsp_types::TextEdit {
+                range: picker.range,
+                new_text: presentation.label,
+            });
+        let mut edits = vec![edit];
+        edits.extend(presentation.additional_text_edits.into_iter().flatten());
+        if let Err(e) = self.apply_lsp_text_edits(picker.buffer_id, edits) {
+            tracing::warn!("Failed to apply color presentation: {}", e);
+        }
+    }
+
+    /// Forget the picker once its popup is dismissed.
+    pub(super) fn cancel_color_presentation(&mut self) {
+        self.active_window_mut().color_presentation_picker = None;
+    }
+}
@@ -265,6 +265,7 @@ impl Editor {
             full_redraw_requested: false,
             suppress_chrome_cells: false,
             suspend_requested: false,
+            client_views: crate::app::client_views::ClientViews::default(),
             plugin_global_state: parts.plugin_global_state,
             warning_log: None,
             status_log_path: None,
@@ -1318,6 +1319,8 @@ impl Editor {
         t.phase("editor_struct_assembly");
         // Apply clipboard configuration
         editor.clipboard.apply_config(&editor.config.clipboard);
+        // List the config's named macros in the command palette
+        editor.sync_named_macro_commands();
 
         // Seed splits/buffers for every persisted inactive window so they
         // render in preview surfaces (Orchestrator's WindowEmbed) before the
@@ -30,8 +30,15 @@ impl Editor {
     /// so undo can restore them to their exact original positions.
     pub fn log_and_apply_event(&mut self, event: &Event) {
         // Capture displaced markers before the event is applied
-        if let Event::Delete { range, .. } = event {
-            let displaced = self.active_state().capture_displaced_markers(range);
+        let deleted = match event {
+            Event::Delete { range, .. } => Some(range.clone()),
+            Event::ReplaceBytes {
+                position, deleted, ..
+            } => Some(*position..position + deleted.len()),
+            _ => None,
+        };
+        if let Some(range) = deleted {
+            let displaced = self.active_state().capture_displaced_markers(&range);
             self.active_event_log_mut().append(event.clone());
             if !displaced.is_empty() {
                 self.active_event_log_mut()
@@ -122,12 +129,17 @@ impl Editor {
         // 1c. Invalidate layouts for all views of this buffer after content changes
         // Note: recovery_pending is set automatically by the buffer on edits
         match event {
-            Event::Insert { .. } | Event::Delete { .. } | Event::BulkEdit { .. } => {
+            Event::Insert { .. }
+            | Event::Delete { .. }
+            | Event::ReplaceBytes { .. }
+            | Event::BulkEdit { .. } => {
                 let buf = self.active_buffer();
                 let win = self.active_window_mut();
                 win.invalidate_layouts_for_buffer(buf);
                 win.schedule_semantic_tokens_full_refresh(buf);
                 win.schedule_folding_ranges_refresh(buf);
+                win.schedule_code_lens_refresh(buf);
+                win.schedule_document_decorations_refresh(buf);
             }
             Event::Batch { events, .. } => {
                 let has_edits = events
@@ -139,6 +151,8 @@ impl Editor {
                     win.invalidate_layouts_for_buffer(buf);
                     win.schedule_semantic_tokens_full_refresh(buf);
                     win.schedule_folding_ranges_refresh(buf);
+                    win.schedule_code_lens_refresh(buf);
+                    win.schedule_document_decorations_refresh(buf);
                 }
             }
             _ => {}
@@ -177,6 +191,16 @@ impl Editor {
                         search_bg,
                     );
                 }
+                Event::ReplaceBytes {
+                    position, inserted, ..
+                } => {
+                    self.active_window_mut().reevaluate_search_overlays_around(
+                        *position,
+                        inserted.len(),
+                        search_fg,
+                        search_bg,
+                    );
+                }
                 Event::Batch { events, .. } => {
                     for e in events {
                         match e {
@@ -1,8 +1,10 @@
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 
+use super::lsp_file_operations::DeferredFileAction;
 use super::*;
 use crate::services::async_bridge::AsyncMessage;
+use crate::services::lsp::file_operations::{FileOperation, FileOperationKind};
 use crate::view::file_tree::TreeNode;
 use std::path::{Path, PathBuf};
 
@@ -497,6 +499,7 @@ impl Editor {
                                     t!("explorer.created_file", name = &filename).to_string(),
                                 );
                                 self.notify_file_explorer_change(&path_clone);
+                                self.notify_lsp_files_created([path_clone.as_path()]);
 
                                 // Open the file in the buffer
                                 if let Err(e) = self.open_file(&path_clone) {
@@ -563,6 +566,7 @@ impl Editor {
                                     t!("explorer.created_dir", name = &dirname_clone).to_string(),
                                 );
                                 self.notify_file_explorer_change(&path_clone);
+                                self.notify_lsp_files_created([path_clone.as_path()]);
 
                                 let prompt = crate::view::prompt::Prompt::with_initial_text(
                                     t!("explorer.new_directory_prompt").to_string(),
@@ -642,10 +646,22 @@ impl Editor {
         }
     }
 
+    /// Delete confirmed paths, once language servers interested in the
+    /// deletion have had their say.
+    pub(super) fn delete_explorer_paths(&mut self, paths: Vec<(PathBuf, bool)>) {
+        let operation = self.file_operation(
+            FileOperationKind::Delete,
+            paths
+                .iter()
+                .map(|(path, is_dir)| (path.clone(), None, *is_dir)),
+        );
+        self.run_file_operation(operation, DeferredFileAction::Delete { paths });
+    }
+
     /// Perform the actual file explorer delete operation (called after prompt confirmation)
     /// For local files: moves to system trash/recycle bin
     /// For remote files: moves to ~/.local/share/fresh/trash/ on remote
-    pub fn perform_file_explorer_delete(&mut self, path: std::path::PathBuf, _is_dir: bool) {
+    pub fn perform_file_explorer_delete(&mut self, path: std::path::PathBuf, is_dir: bool) {
         let name = path
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
@@ -736,6 +752,9 @@ impl Editor {
                 }
                 self.set_status_message(t!("explorer.moved_to_trash", name = &name).to_string());
                 self.notify_file_explorer_change(&path);
+                let operation =
+                    self.file_operation(FileOperationKind::Delete, [(path, None, is_dir)]);
+                self.notify_lsp_file_operation(operation);
 
                 // Ensure focus remains on file explorer
                 self.active_window_mut().key_context = KeyContext::FileExplorer;
@@ -838,6 +857,37 @@ impl Editor {
             .map(|p| p.join(&new_name))
             .unwrap_or_else(|| original_path.clone());
 
+        let is_dir = self
+            .authority()
+            .filesystem
+            .is_dir(&original_path)
+            .unwrap_or(false);
+        let operation = self.file_operation(
+            FileOperationKind::Rename,
+            [(original_path.clone(), Some(new_path.clone()), is_dir)],
+        );
+        self.run_file_operation(
+            operation,
+            DeferredFileAction::Rename {
+                original_path,
+                original_name,
+                new_path,
+                new_name,
+                is_new_file,
+            },
+        );
+    }
+
+    /// Rename on disk and carry the explorer selection and open buffers
+    /// over to the new path.
+    pub(super) fn rename_explorer_path(
+        &mut self,
+        original_path: PathBuf,
+        original_name: String,
+        new_path: PathBuf,
+        new_name: String,
+        is_new_file: bool,
+    ) {
         if self.tokio_runtime.is_some() {
             let result = self
                 .authority()
@@ -891,6 +941,16 @@ impl Editor {
                         t!("explorer.renamed", old = &original_name, new = &new_name).to_string(),
                     );
                     self.notify_file_explorer_change(&new_path);
+                    let is_dir = self
+                        .authority()
+                        .filesystem
+                        .is_dir(&new_path)
+                        .unwrap_or(false);
+                    let operation = self.file_operation(
+                        FileOperationKind::Rename,
+                        [(original_path, Some(new_path), is_dir)],
+                    );
+                    self.notify_lsp_file_operation(operation);
                 }
                 Err(e) => {
                     self.set_status_message(
@@ -1115,6 +1175,26 @@ impl Editor {
         safe: Vec<(PathBuf, PathBuf)>,
         to_overwrite: Vec<(PathBuf, PathBuf)>,
         is_cut: bool,
+    ) {
+        if !is_cut {
+            self.paste_resolved_explorer_paths(safe, to_overwrite, false);
+            return;
+        }
+        // A move is a rename as far as language servers are concerned.
+        let operation = self.move_operation(safe.iter().chain(&to_overwrite));
+        self.run_file_operation(
+            operation,
+            DeferredFileAction::MultiMove { safe, to_overwrite },
+        );
+    }
+
+    /// Paste resolved items once any language servers have had their say
+    /// about the moves.
+    pub(super) fn paste_resolved_explorer_paths(
+        &mut self,
+        safe: Vec<(PathBuf, PathBuf)>,
+        to_overwrite: Vec<(PathBuf, PathBuf)>,
+        is_cut: bool,
     ) {
         let total = safe.len() + to_overwrite.len();
         if total == 0 {
@@ -1164,6 +1244,11 @@ impl Editor {
             for (src, dst) in &clean_moves {
                 self.relocate_buffers_for_rename(src, dst);
             }
+            let operation = self.move_operation(&clean_moves);
+            self.notify_lsp_file_operation(operation);
+            self.notify_lsp_files_created(partial_moves.iter().map(|(dst, _)| dst.as_path()));
+        } else {
+            self.notify_lsp_files_created(succeeded.iter().map(|(_, dst)| dst.as_path()));
         }
 
         if !succeeded.is_empty() {
@@ -1401,6 +1486,35 @@ impl Editor {
     }
 
     pub fn perform_file_explorer_paste(&mut self, src: PathBuf, dst: PathBuf, is_cut: bool) {
+        if !is_cut {
+            self.paste_explorer_path(src, dst, false);
+            return;
+        }
+        let moved = (src, dst);
+        let operation = self.move_operation([&moved]);
+        let (src, dst) = moved;
+        self.run_file_operation(operation, DeferredFileAction::Move { src, dst });
+    }
+
+    /// Describe moves from the explorer as renames, for language servers.
+    fn move_operation<'a>(
+        &self,
+        moves: impl IntoIterator<Item = &'a (PathBuf, PathBuf)>,
+    ) -> FileOperation {
+        let fs = &self.authority().filesystem;
+        self.file_operation(
+            FileOperationKind::Rename,
+            moves.into_iter().map(|(src, dst)| {
+                // After a move only the destination exists.
+                let is_dir = fs.is_dir(src).or_else(|_| fs.is_dir(dst)).unwrap_or(false);
+                (src.clone(), Some(dst.clone()), is_dir)
+            }),
+        )
+    }
+
+    /// Move or copy one item once any language servers have had their say
+    /// about the move.
+    pub(super) fn paste_explorer_path(&mut self, src: PathBuf, dst: PathBuf, is_cut: bool) {
         let name = dst
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
@@ -1419,9 +1533,12 @@ impl Editor {
                 }
                 self.refresh_tree_after_paste(&src, &dst, is_cut);
                 if is_cut {
+                    let operation = self.move_operation([&(src, dst)]);
+                    self.notify_lsp_file_operation(operation);
                     self.active_window_mut().file_explorer_clipboard = None;
                     self.set_status_message(t!("explorer.pasted_moved", name = &name).to_string());
                 } else {
+                    self.notify_lsp_files_created([dst.as_path()]);
                     self.set_status_message(t!("explorer.pasted", name = &name).to_string());
                 }
                 self.active_window_mut().key_context = KeyContext::FileExplorer;
@@ -1435,6 +1552,7 @@ impl Editor {
                 // populated so the user can retry, and spell out both
                 // sides of the partial state in the status line.
                 self.refresh_tree_after_paste(&src, &landed_dst, is_cut);
+                self.notify_lsp_files_created([landed_dst.as_path()]);
                 self.set_status_message(
                     t!(
                         "explorer.move_source_removal_failed",
@@ -1524,6 +1642,7 @@ impl Editor {
             let (first_src, first_dst) = succeeded[0].clone();
             self.refresh_tree_after_paste(&first_src, &first_dst, false);
         }
+        self.notify_lsp_files_created(succeeded.iter().map(|(_, dst)| dst.as_path()));
 
         let msg = if let Some(e) = &first_error {
             t!("explorer.error_copying", error = e.to_string()).to_string()
@@ -379,6 +379,7 @@ impl Editor {
         self.active_window_mut()
             .event_logs
             .insert(buffer_id, crate::model::event::EventLog::new());
+        self.active_window_mut().restore_undo_history(buffer_id);
 
         let metadata = super::types::BufferMetadata::with_file(
             path.to_path_buf(),
@@ -569,6 +570,7 @@ impl Editor {
         self.active_window_mut()
             .event_logs
             .insert(buffer_id, crate::model::event::EventLog::new());
+        self.active_window_mut().restore_undo_history(buffer_id);
 
         let metadata = super::types::BufferMetadata::with_file(
             path.to_path_buf(),
@@ -1150,6 +1152,7 @@ impl crate::app::window::Window {
         self.buffers.insert(buffer_id, state);
         self.event_logs
             .insert(buffer_id, crate::model::event::EventLog::new());
+        self.restore_undo_history(buffer_id);
 
         // Create metadata for this buffer
         let mut metadata = crate::app::types::BufferMetadata::with_file(
@@ -1240,6 +1243,35 @@ impl crate::app::window::Window {
         // Track file for auto-revert and conflict detection
         self.watch_file(path);
 
+        // Re-draw breakpoints set in an earlier visit to this file
+        if let Some(file_path) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|s| s.buffer.file_path())
+            .map(|p| p.to_path_buf())
+        {
+            self.refresh_breakpoint_indicators(buffer_id, &file_path);
+        }
+
+        // Show problems already reported for this file (e.g. by a task
+        // that ran before it was opened). Language servers re-publish on
+        // didOpen; tasks don't.
+        if let Some(diagnostics) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_uri())
+            .and_then(|uri| self.stored_diagnostics.get(uri.as_str()))
+            .cloned()
+        {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                crate::services::lsp::diagnostics::apply_diagnostics_to_state(
+                    state,
+                    &diagnostics,
+                    &self.resources.theme.read().unwrap(),
+                );
+            }
+        }
+
         // Fire AfterFileOpen hook for plugins — but not for preview opens
         // (file-explorer browse, live-grep overlay). A preview is "just
         // looking": firing this hook lets plugins raise intrusive UI (e.g.
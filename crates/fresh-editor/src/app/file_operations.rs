@@ -12,6 +12,7 @@ use crate::model::buffer::SudoSaveRequired;
 use crate::model::filesystem::FileSystem;
 use crate::view::file_tree::FileTreeView;
 use crate::view::prompt::PromptType;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use lsp_types::TextDocumentContentChangeEvent;
@@ -208,6 +209,11 @@ impl Editor {
             }
         }
 
+        // Persist undo history and record local history last, so edits made
+        // by on-save formatters are part of them.
+        self.active_window().persist_undo_history(buffer_id);
+        self.active_window().record_local_history(buffer_id);
+
         Ok(())
     }
 
@@ -588,8 +594,15 @@ impl Editor {
         }
         self.active_window_mut().last_auto_revert_poll = self.time_source.now();
 
-        // Collect paths of open files that need checking
-        let files_to_check: Vec<PathBuf> = self.buffers().paths();
+        // Collect paths of open files that need checking. Files in a
+        // watched directory report their changes as events.
+        let watched = &self.active_window().watched_dirs;
+        let files_to_check: Vec<PathBuf> = self
+            .buffers()
+            .paths()
+            .into_iter()
+            .filter(|path| !path.parent().is_some_and(|dir| watched.covers(dir)))
+            .collect();
 
         if files_to_check.is_empty() {
             return any_changed;
@@ -644,6 +657,31 @@ impl Editor {
         any_changed
     }
 
+    /// Point the active window's directory watches at the directories
+    /// of its open files and its expanded explorer directories.
+    fn sync_watched_dirs(&mut self) {
+        let mut wanted: HashSet<PathBuf> = self
+            .buffers()
+            .paths()
+            .iter()
+            .filter_map(|path| path.parent().map(Path::to_path_buf))
+            .collect();
+        if let Some(explorer) = self.file_explorer() {
+            wanted.extend(
+                explorer
+                    .tree()
+                    .all_nodes()
+                    .filter(|node| node.is_dir() && node.is_expanded())
+                    .map(|node| node.entry.path.clone()),
+            );
+        }
+        let fs = self.authority().filesystem.clone();
+        let window = self.active_window_mut();
+        window
+            .watched_dirs
+            .sync(fs.as_ref(), &window.bridge, wanted);
+    }
+
     /// Poll for file tree changes (called from main loop)
     ///
     /// Checks modification times of expanded directories to detect new/deleted files.
@@ -690,6 +728,26 @@ impl Editor {
             any_refreshed = true;
         }
 
+        // Watched directories report changes as events; re-read those
+        // that did instead of polling them.
+        self.sync_watched_dirs();
+        let changed: Vec<PathBuf> = self
+            .active_window_mut()
+            .watched_dirs
+            .changed
+            .drain()
+            .collect();
+        if !changed.is_empty() {
+            self.refresh_file_tree_dirs(&changed);
+            let fs = self.authority().filesystem.clone();
+            if let Some(explorer) = self.file_explorer_mut().as_mut() {
+                for dir in &changed {
+                    load_gitignore_via_fs(fs.as_ref(), explorer, dir);
+                }
+            }
+            any_refreshed = true;
+        }
+
         // If a previous dir-poll is still in flight, don't stack another.
         if dir_poll_pending {
             return any_refreshed;
@@ -714,11 +772,13 @@ impl Editor {
             return any_refreshed;
         };
 
-        // Collect expanded directories (node_id, path)
+        // Collect expanded directories (node_id, path) not watched
+        let watched = &self.active_window().watched_dirs;
         let expanded_dirs: Vec<(NodeId, PathBuf)> = explorer
             .tree()
             .all_nodes()
             .filter(|node| node.is_dir() && node.is_expanded())
+            .filter(|node| !watched.covers(&node.entry.path))
             .map(|node| (node.id, node.entry.path.clone()))
             .collect();
 
@@ -1538,8 +1598,10 @@ impl crate::app::window::Window {
                     }
                 }
 
-                // Schedule folding range refresh
+                // Schedule folding range, code lens, link and color refresh
                 self.schedule_folding_ranges_refresh(buffer_id);
+                self.schedule_code_lens_refresh(buffer_id);
+                self.schedule_document_decorations_refresh(buffer_id);
             }
             LspSpawnResult::NotAutoStart => {
                 tracing::debug!(
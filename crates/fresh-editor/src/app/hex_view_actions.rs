@@ -0,0 +1,357 @@
+//! Hex view actions on `Editor`.
+//!
+//! A split toggled into hex view keeps the buffer's primary cursor as a
+//! byte offset. Keys are routed here before keybinding resolution so arrows
+//! move by nibble and byte, and typing overwrites bytes in place. Edits are
+//! recorded as `ReplaceBytes` events, because `Insert` and `Delete` events
+//! only hold UTF-8 text and binary files rarely are.
+
+use std::ops::Range;
+
+use anyhow::Result as AnyhowResult;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rust_i18n::t;
+
+use crate::input::keybindings::Action;
+use crate::model::event::Event;
+use crate::view::hex_view::{
+    find_pattern, parse_byte_pattern, parse_offset, pattern_regex, to_hex_string, HexViewState,
+};
+use crate::view::prompt::PromptType;
+
+use super::Editor;
+
+impl Editor {
+    /// Hex view state of the active buffer in the active split, if that
+    /// view is in hex mode.
+    fn active_hex_view(&self) -> Option<&HexViewState> {
+        let split_id = self.effective_active_split();
+        let buffer_id = self.active_buffer();
+        self.active_window()
+            .buffers
+            .split_view_states()?
+            .get(&split_id)?
+            .keyed_states
+            .get(&buffer_id)?
+            .hex_view
+            .as_ref()
+    }
+
+    fn active_hex_view_mut(&mut self) -> Option<&mut HexViewState> {
+        let split_id = self.effective_active_split();
+        let buffer_id = self.active_buffer();
+        self.split_view_states_mut()
+            .get_mut(&split_id)?
+            .keyed_states
+            .get_mut(&buffer_id)?
+            .hex_view
+            .as_mut()
+    }
+
+    /// Whether the active split shows its buffer as a hex view.
+    pub fn is_hex_view_active(&self) -> bool {
+        self.active_hex_view().is_some()
+    }
+
+    /// Switch the active split between the text and hex views of its buffer.
+    pub fn toggle_hex_view(&mut self) {
+        if self
+            .active_window()
+            .is_composite_buffer(self.active_buffer())
+        {
+            return;
+        }
+        let split_id = self.effective_active_split();
+        let buffer_id = self.active_buffer();
+        let Some(view) = self
+            .split_view_states_mut()
+            .get_mut(&split_id)
+            .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+        else {
+            return;
+        };
+        let enabled = if view.hex_view.take().is_some() {
+            false
+        } else {
+            view.hex_view = Some(HexViewState::default());
+            // Byte-wise moves and selections make no sense across cursors.
+            view.cursors.remove_secondary();
+            true
+        };
+        self.active_window_mut()
+            .invalidate_layouts_for_buffer(buffer_id);
+        let message = if enabled {
+            t!("hex.enabled")
+        } else {
+            t!("hex.disabled")
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Handle a key in a hex view. Returns `None` for keys that should
+    /// fall through to normal keybinding resolution.
+    pub(super) fn try_route_hex_key(&mut self, key_event: &KeyEvent) -> Option<AnyhowResult<()>> {
+        let hex = self.active_hex_view()?;
+        let (per_row, page) = (hex.bytes_per_row.max(1), hex.visible_rows.max(1));
+        let (ascii_focus, low_nibble) = (hex.ascii_focus, hex.low_nibble);
+        let total = self.active_state().buffer.len();
+        let cursor = self.active_cursors().primary();
+        let position = cursor.position.min(total);
+        let has_selection = cursor.selection_range().is_some();
+
+        let modifiers = key_event.modifiers;
+        let shift = modifiers.contains(KeyModifiers::SHIFT);
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        if modifiers.intersects(KeyModifiers::ALT | KeyModifiers::SUPER)
+            || (ctrl && !matches!(key_event.code, KeyCode::Home | KeyCode::End))
+        {
+            return None;
+        }
+
+        let row_start = position - position % per_row;
+        match key_event.code {
+            KeyCode::Left if !shift && !ascii_focus && low_nibble => {
+                self.hex_move(position, false, false);
+            }
+            KeyCode::Left => {
+                let low = !shift && !ascii_focus && position > 0;
+                self.hex_move(position.saturating_sub(1), shift, low);
+            }
+            KeyCode::Right if !shift && !ascii_focus && !low_nibble => {
+                self.hex_move(position, false, position < total);
+            }
+            KeyCode::Right => self.hex_move((position + 1).min(total), shift, false),
+            KeyCode::Up => self.hex_move(position.saturating_sub(per_row), shift, false),
+            KeyCode::Down => self.hex_move((position + per_row).min(total), shift, false),
+            KeyCode::PageUp => self.hex_move(position.saturating_sub(per_row * page), shift, false),
+            KeyCode::PageDown => {
+                self.hex_move((position + per_row * page).min(total), shift, false)
+            }
+            KeyCode::Home if ctrl => self.hex_move(0, shift, false),
+            KeyCode::End if ctrl => self.hex_move(total, shift, false),
+            KeyCode::Home => self.hex_move(row_start, shift, false),
+            KeyCode::End => self.hex_move((row_start + per_row - 1).min(total), shift, false),
+            KeyCode::Tab | KeyCode::BackTab => {
+                if let Some(hex) = self.active_hex_view_mut() {
+                    hex.ascii_focus = !hex.ascii_focus;
+                    hex.low_nibble = false;
+                }
+            }
+            KeyCode::Esc if has_selection => self.hex_move(position, false, false),
+            KeyCode::Backspace => self.hex_move(position.saturating_sub(1), false, false),
+            KeyCode::Delete => self.hex_delete(),
+            // Overwrite mode has no use for line breaks.
+            KeyCode::Enter => {}
+            KeyCode::Char(c) if ascii_focus => {
+                if c.is_ascii() && !c.is_ascii_control() {
+                    self.hex_overwrite(position, c as u8, true);
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(digit) = c.to_digit(16) {
+                    let old = self.hex_byte_at(position).unwrap_or(0);
+                    let byte = if low_nibble {
+                        (old & 0xf0) | digit as u8
+                    } else {
+                        (old & 0x0f) | ((digit as u8) << 4)
+                    };
+                    self.hex_overwrite(position, byte, low_nibble);
+                }
+            }
+            _ => return None,
+        }
+        Some(Ok(()))
+    }
+
+    /// Run the hex view's byte-oriented counterpart of a text action.
+    /// Returns `false` when the action should run as usual.
+    pub(super) fn try_hex_view_action(&mut self, action: &Action) -> bool {
+        if !self.is_hex_view_active() {
+            return false;
+        }
+        match action {
+            Action::Copy => self.copy_as_hex(),
+            Action::Search => self.start_search_bytes_prompt(),
+            Action::FindNext => self.search_bytes_next(),
+            Action::GotoLine => self.start_goto_offset_prompt(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Move the primary cursor to byte `position`, keeping it on screen.
+    fn hex_move(&mut self, position: usize, extend: bool, low_nibble: bool) {
+        self.active_cursors_mut()
+            .primary_mut()
+            .move_to(position, extend);
+        if let Some(hex) = self.active_hex_view_mut() {
+            hex.low_nibble = low_nibble;
+            hex.reveal_cursor = true;
+        }
+    }
+
+    fn hex_byte_at(&mut self, offset: usize) -> Option<u8> {
+        self.active_state_mut()
+            .buffer
+            .get_text_range_mut(offset, 1)
+            .ok()?
+            .first()
+            .copied()
+    }
+
+    /// Replace the byte at `position` (or append it at the end of the
+    /// buffer), then either step to the next byte or to the low nibble.
+    fn hex_overwrite(&mut self, position: usize, byte: u8, advance: bool) {
+        let total = self.active_state().buffer.len();
+        let replaced = usize::from(position < total);
+        if !self.hex_replace_bytes(position..position + replaced, vec![byte]) {
+            return;
+        }
+        if advance {
+            self.hex_move(position + 1, false, false);
+        } else {
+            self.hex_move(position, false, true);
+        }
+    }
+
+    /// Delete the selected bytes, or the byte under the cursor.
+    fn hex_delete(&mut self) {
+        let total = self.active_state().buffer.len();
+        let cursor = self.active_cursors().primary();
+        let range = cursor.selection_range().unwrap_or_else(|| {
+            let position = cursor.position.min(total);
+            position..(position + 1).min(total)
+        });
+        if range.is_empty() {
+            return;
+        }
+        if self.hex_replace_bytes(range.clone(), Vec::new()) {
+            self.hex_move(range.start, false, false);
+        }
+    }
+
+    /// Replace `range` of the active buffer with `bytes` as one undoable
+    /// edit. Returns `false` if the buffer can't be edited.
+    fn hex_replace_bytes(&mut self, range: Range<usize>, bytes: Vec<u8>) -> bool {
+        if self.active_window().is_editing_disabled() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return false;
+        }
+        let Ok(deleted) = self
+            .active_state_mut()
+            .buffer
+            .get_text_range_mut(range.start, range.len())
+        else {
+            return false;
+        };
+        let cursor_id = self.active_cursors().primary_id();
+        self.log_and_apply_event(&Event::ReplaceBytes {
+            position: range.start,
+            deleted,
+            inserted: bytes,
+            cursor_id,
+        });
+        true
+    }
+
+    /// Prompt for a byte offset to jump to.
+    pub fn start_goto_offset_prompt(&mut self) {
+        self.start_prompt(
+            t!("hex.goto_offset_prompt").to_string(),
+            PromptType::GotoOffset,
+        );
+    }
+
+    /// Jump to the byte offset typed in the Go to Offset prompt.
+    pub fn goto_offset(&mut self, input: &str) {
+        let Some(offset) = parse_offset(input) else {
+            self.set_status_message(t!("hex.invalid_offset", input = input.trim()).to_string());
+            return;
+        };
+        let total = self.active_state().buffer.len();
+        if offset > total {
+            self.set_status_message(
+                t!("hex.offset_past_end", offset = offset, len = total).to_string(),
+            );
+            return;
+        }
+        self.hex_move(offset, false, false);
+    }
+
+    /// Prompt for a byte pattern to search for.
+    pub fn start_search_bytes_prompt(&mut self) {
+        self.start_prompt(t!("hex.search_prompt").to_string(), PromptType::SearchBytes);
+    }
+
+    /// Search for the byte pattern typed in the Search Bytes prompt.
+    pub fn search_bytes(&mut self, input: &str) {
+        if let Some(hex) = self.active_hex_view_mut() {
+            hex.last_search = Some(input.to_string());
+        }
+        self.search_bytes_next();
+    }
+
+    /// Select the next match of the last byte pattern after the cursor,
+    /// wrapping around at the end of the buffer.
+    pub fn search_bytes_next(&mut self) {
+        let Some(input) = self.active_hex_view().and_then(|h| h.last_search.clone()) else {
+            self.start_search_bytes_prompt();
+            return;
+        };
+        let pattern = match parse_byte_pattern(&input) {
+            Ok(pattern) => pattern,
+            Err(error) => {
+                self.set_status_message(t!("hex.invalid_pattern", error = error).to_string());
+                return;
+            }
+        };
+        let regex = pattern_regex(&pattern);
+        let cursor = self.active_cursors().primary();
+        // Start past a match the cursor is on so repeating finds the next one.
+        let from = cursor
+            .selection_range()
+            .map_or(cursor.position, |r| r.start + 1);
+        let state = self.active_state_mut();
+        let total = state.buffer.len();
+        let found = find_pattern(
+            |offset, len| state.buffer.get_text_range_mut(offset, len).ok(),
+            total,
+            from,
+            &regex,
+            pattern.len(),
+        );
+        match found {
+            Some(range) => {
+                self.hex_move(range.start, false, false);
+                self.hex_move(range.end, true, false);
+                self.set_status_message(
+                    t!("hex.found", offset = format!("{:x}", range.start)).to_string(),
+                );
+            }
+            None => self.set_status_message(t!("hex.not_found").to_string()),
+        }
+    }
+
+    /// Copy the selected bytes, or the byte under the cursor, as
+    /// space-separated hex pairs.
+    pub fn copy_as_hex(&mut self) {
+        let total = self.active_state().buffer.len();
+        let cursor = self.active_cursors().primary();
+        let range = cursor.selection_range().unwrap_or_else(|| {
+            let position = cursor.position.min(total);
+            position..(position + 1).min(total)
+        });
+        let Ok(bytes) = self
+            .active_state_mut()
+            .buffer
+            .get_text_range_mut(range.start, range.len())
+        else {
+            return;
+        };
+        if bytes.is_empty() {
+            return;
+        }
+        self.clipboard.copy(to_hex_string(&bytes));
+        self.set_status_message(t!("hex.copied", count = bytes.len()).to_string());
+    }
+}
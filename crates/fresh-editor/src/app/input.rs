@@ -1,3 +1,4 @@
+use super::types::HierarchyDirection;
 use super::*;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -716,7 +717,9 @@ impl Editor {
                             c
                         };
                         if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
-                            let action_name = format!("mode_text_input:{}", ch);
+                            // Tagged with the mode so it reaches the plugin
+                            // that defined it.
+                            let action_name = format!("mode_text_input@{}:{}", mode_name, ch);
                             return self.handle_action(Action::PluginAction(action_name));
                         }
                     }
@@ -833,6 +836,17 @@ impl Editor {
             }
         }
 
+        // --- Hex view input routing ---
+        // A split showing its buffer as hex moves by nibble and byte and
+        // overwrites bytes on typing; other keys resolve as usual.
+        if context == crate::input::keybindings::KeyContext::Normal
+            && self.active_window().chord_state.is_empty()
+        {
+            if let Some(handled) = self.try_route_hex_key(&key_event) {
+                return handled;
+            }
+        }
+
         // Check for chord sequence matches first
         let key_event = crossterm::event::KeyEvent::new(code, modifiers);
         let (chord_result, action) = {
@@ -960,6 +974,13 @@ impl Editor {
         if !matches!(action, Action::DabbrevExpand) {
             self.reset_dabbrev_state();
         }
+        // Only a yank-pop right after a paste may replace the pasted text.
+        if !matches!(action, Action::YankPop) {
+            self.active_window_mut().yank_state = None;
+        }
+        if self.try_hex_view_action(&action) {
+            return Ok(());
+        }
 
         match action {
             Action::Quit => self.quit(),
@@ -989,6 +1010,10 @@ impl Editor {
                 self.show_workspace_trust_popup(true);
             }
             Action::Save => {
+                // Macro edit buffers save back to their named macro
+                if self.save_macro_edit_buffer() {
+                    return Ok(());
+                }
                 // Check if buffer has a file path - if not, redirect to SaveAs
                 if self.active_state().buffer.file_path().is_none() {
                     self.start_prompt_with_initial_text(
@@ -1059,6 +1084,10 @@ impl Editor {
                     );
                 }
             }
+            Action::ToggleHexView => self.toggle_hex_view(),
+            Action::GotoOffset => self.start_goto_offset_prompt(),
+            Action::SearchBytes => self.start_search_bytes_prompt(),
+            Action::CopyAsHex => self.copy_as_hex(),
             Action::ScanLineIndex => {
                 self.start_incremental_line_scan(false);
             }
@@ -1225,6 +1254,17 @@ impl Editor {
                 }
                 self.paste()
             }
+            Action::PasteFromHistory | Action::YankPop => {
+                if self.active_window().is_editing_disabled() {
+                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    return Ok(());
+                }
+                if matches!(action, Action::YankPop) {
+                    self.yank_pop();
+                } else {
+                    self.start_paste_from_history_prompt();
+                }
+            }
             Action::SelectAll => {
                 // Focused widget Text wins over the buffer's
                 // select-all. SelectAll on the buffer is then
@@ -1482,6 +1522,17 @@ impl Editor {
                     t!("view.occurrence_highlight_state", state = state).to_string(),
                 );
             }
+            Action::ToggleStickyScroll => {
+                let new_value = !self.config.editor.sticky_scroll;
+                self.config_mut().editor.sticky_scroll = new_value;
+
+                let state = if new_value {
+                    t!("view.state_enabled").to_string()
+                } else {
+                    t!("view.state_disabled").to_string()
+                };
+                self.set_status_message(t!("view.sticky_scroll_state", state = state).to_string());
+            }
             Action::ToggleReadOnly => {
                 let buffer_id = self.active_buffer();
                 let is_now_read_only = self
@@ -1569,12 +1620,39 @@ impl Editor {
             Action::LspReferences => {
                 self.request_references()?;
             }
+            Action::LspIncomingCalls => {
+                self.request_hierarchy(HierarchyDirection::IncomingCalls);
+            }
+            Action::LspOutgoingCalls => {
+                self.request_hierarchy(HierarchyDirection::OutgoingCalls);
+            }
+            Action::LspSupertypes => {
+                self.request_hierarchy(HierarchyDirection::Supertypes);
+            }
+            Action::LspSubtypes => {
+                self.request_hierarchy(HierarchyDirection::Subtypes);
+            }
             Action::LspSignatureHelp => {
                 self.request_signature_help();
             }
             Action::LspCodeActions => {
                 self.request_code_actions()?;
             }
+            Action::LspCodeLens => {
+                self.run_code_lens_at_cursor();
+            }
+            Action::LspOpenLink => {
+                self.open_document_link_at_cursor();
+            }
+            Action::LspColorPresentation => {
+                self.request_color_presentations();
+            }
+            Action::SmartExpandSelection => {
+                self.expand_structural_selection();
+            }
+            Action::SmartShrinkSelection => {
+                self.shrink_structural_selection();
+            }
             Action::LspRestart => {
                 self.handle_lsp_restart();
             }
@@ -1736,6 +1814,7 @@ impl Editor {
             Action::TogglePromptLine => self.active_window_mut().toggle_prompt_line(),
             Action::ToggleVerticalScrollbar => self.toggle_vertical_scrollbar(),
             Action::ToggleHorizontalScrollbar => self.toggle_horizontal_scrollbar(),
+            Action::ToggleMinimap => self.toggle_minimap(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleLineNumbersCurrentBuffer => self.toggle_line_numbers_current_buffer(),
             Action::ToggleLineWrapCurrentBuffer => self.toggle_line_wrap_current_buffer(),
@@ -1870,6 +1949,7 @@ impl Editor {
             }
             Action::FileExplorerSelectAll => self.active_window_mut().file_explorer_select_all(),
             Action::RemoveSecondaryCursors => {
+                self.end_snippet_session();
                 // Convert action to events and apply them
                 if let Some(events) = self
                     .active_window_mut()
@@ -1963,6 +2043,39 @@ impl Editor {
             Action::JumpToPreviousError => {
                 self.jump_to_previous_error();
             }
+            Action::DebugStart => {
+                self.debug_start();
+            }
+            Action::DebugStop => {
+                self.debug_stop();
+            }
+            Action::DebugContinue => {
+                self.debug_continue();
+            }
+            Action::DebugPause => {
+                self.debug_pause();
+            }
+            Action::DebugStepOver => {
+                self.debug_step_over();
+            }
+            Action::DebugStepInto => {
+                self.debug_step_into();
+            }
+            Action::DebugStepOut => {
+                self.debug_step_out();
+            }
+            Action::DebugToggleBreakpoint => {
+                self.debug_toggle_breakpoint();
+            }
+            Action::RunTask => {
+                self.start_run_task_prompt();
+            }
+            Action::RerunLastTask => {
+                self.rerun_last_task();
+            }
+            Action::StopTask => {
+                self.stop_tasks();
+            }
             Action::SetBookmark(key) => {
                 self.active_window_mut().set_bookmark(key);
             }
@@ -2060,6 +2173,24 @@ impl Editor {
                     self.set_status_message(t!("status.no_macro_recorded").to_string());
                 }
             }
+            Action::SaveMacro => {
+                self.start_save_macro_prompt(super::macros::MacroScope::Config);
+            }
+            Action::SaveMacroToWorkspace => {
+                self.start_save_macro_prompt(super::macros::MacroScope::Workspace);
+            }
+            Action::PlayNamedMacro(name) => {
+                self.play_named_macro(&name);
+            }
+            Action::EditNamedMacro => {
+                self.start_edit_named_macro_prompt();
+            }
+            Action::ApplyMacroToLines => {
+                self.apply_macro_to_lines();
+            }
+            Action::ApplyMacroToMatches => {
+                self.apply_macro_to_matches();
+            }
             Action::PromptSetBookmark => {
                 self.start_prompt("Set bookmark (0-9): ".to_string(), PromptType::SetBookmark);
             }
@@ -2095,7 +2226,7 @@ impl Editor {
                         {
                             self.active_event_log_mut().append(bulk_edit);
                         }
-                    } else {
+                    } else if !self.apply_linked_edit(&events) {
                         for event in events {
                             self.active_event_log_mut().append(event.clone());
                             self.apply_event_to_active_buffer(&event);
@@ -2444,6 +2575,12 @@ impl Editor {
             Action::CompletionDismiss => {
                 self.handle_popup_cancel();
             }
+            Action::InsertTab if self.snippet_session_active() => {
+                self.snippet_next_tabstop();
+            }
+            Action::DedentSelection if self.snippet_session_active() => {
+                self.snippet_prev_tabstop();
+            }
             Action::InsertChar(c) => {
                 if self.is_prompting() {
                     return self.handle_insert_char_prompt(c);
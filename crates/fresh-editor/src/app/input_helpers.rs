@@ -251,7 +251,7 @@ impl Editor {
                 {
                     self.active_event_log_mut().append(bulk_edit);
                 }
-            } else {
+            } else if !self.apply_linked_edit(&events) {
                 // Single cursor - apply normally
                 for event in events {
                     self.active_event_log_mut().append(event.clone());
@@ -260,6 +260,8 @@ impl Editor {
             }
         }
 
+        self.maybe_request_on_type_formatting(c);
+
         // Auto-trigger signature help on '(' and ','
         if c == '(' || c == ',' {
             self.request_signature_help();
@@ -287,6 +289,8 @@ impl Editor {
 
         // Get description before moving action
         let action_description = format!("{:?}", action);
+        let is_newline = matches!(action, Action::InsertNewline);
+        let is_linkable = matches!(action, Action::DeleteForward);
 
         // Check if this is an editing action and editing is disabled
         let is_editing_action = matches!(
@@ -337,7 +341,7 @@ impl Editor {
                 for event in &events {
                     self.track_cursor_movement(event);
                 }
-            } else {
+            } else if !(is_linkable && self.apply_linked_edit(&events)) {
                 // Single cursor - apply normally
                 for event in events {
                     self.log_and_apply_event(&event);
@@ -346,6 +350,10 @@ impl Editor {
             }
         }
 
+        if is_newline {
+            self.maybe_request_on_type_formatting('\n');
+        }
+
         Ok(())
     }
 
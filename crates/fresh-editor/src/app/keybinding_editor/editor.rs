@@ -92,6 +92,8 @@ impl KeybindingEditor {
     /// (File, Edit, …, plus any plugin menus). They're used to enumerate
     /// concrete `menu_open:<name>` entries in the action dropdown, so each
     /// menu gets its own selectable row instead of one generic `menu_open`.
+    /// `workspace_macros` are the names of the named macros saved to the
+    /// workspace; those saved to the config are read from `config`.
     pub fn new(
         config: &Config,
         resolver: &KeybindingResolver,
@@ -99,6 +101,7 @@ impl KeybindingEditor {
         command_registry: &CommandRegistry,
         config_file_path: String,
         menu_names: &[String],
+        workspace_macros: &[String],
     ) -> Self {
         let bindings =
             Self::resolve_all_bindings(config, resolver, mode_registry, command_registry);
@@ -131,7 +134,7 @@ impl KeybindingEditor {
         // Expand parameterised actions (menu_open, switch_keybinding_map) from a
         // single bare entry — which is unparseable without args and silently
         // becomes a no-op PluginAction — into one entry per concrete variant.
-        Self::expand_variant_actions(&mut available_actions, menu_names, config);
+        Self::expand_variant_actions(&mut available_actions, menu_names, workspace_macros, config);
 
         available_actions.sort();
         available_actions.dedup();
@@ -433,7 +436,12 @@ impl KeybindingEditor {
     /// `menu_open` from the dropdown would produce an un-parseable binding
     /// because `Action::from_str` requires the args map to carry the menu
     /// name.
-    fn expand_variant_actions(actions: &mut Vec<String>, menu_names: &[String], config: &Config) {
+    fn expand_variant_actions(
+        actions: &mut Vec<String>,
+        menu_names: &[String],
+        workspace_macros: &[String],
+        config: &Config,
+    ) {
         // Menu names: built-in + plugin, deduplicated case-insensitively.
         let mut menus: Vec<String> = menu_names.to_vec();
         menus.sort();
@@ -454,6 +462,16 @@ impl KeybindingEditor {
         for map in &keymaps {
             actions.push(format!("switch_keybinding_map:{}", map));
         }
+
+        // Named macros saved to the config or the workspace. The bare
+        // `play_named_macro` stays: bound without a name, it opens the
+        // macro picker.
+        let mut macros: Vec<&String> = config.macros.keys().chain(workspace_macros).collect();
+        macros.sort();
+        macros.dedup();
+        for name in macros {
+            actions.push(format!("play_named_macro:{}", name));
+        }
     }
 
     /// Update autocomplete suggestions based on current action text
@@ -1211,6 +1229,7 @@ mod tests {
             &cmd_registry,
             String::from("/tmp/fresh-config.toml"),
             &menu_names,
+            &[],
         )
     }
 
@@ -1270,6 +1289,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dropdown_lists_named_macros_from_config_and_workspace() {
+        let mut config = Config::default();
+        config
+            .macros
+            .insert("wrap".to_string(), vec![serde_json::json!("MoveLineEnd")]);
+        let editor = KeybindingEditor::new(
+            &config,
+            &KeybindingResolver::new(&config),
+            &ModeRegistry::new(),
+            &CommandRegistry::new(),
+            String::from("/tmp/fresh-config.toml"),
+            &[],
+            &["tidy".to_string()],
+        );
+        assert!(editor
+            .available_actions
+            .contains(&"play_named_macro:wrap".to_string()));
+        assert!(editor
+            .available_actions
+            .contains(&"play_named_macro:tidy".to_string()));
+        // The bare form opens the macro picker, so it stays listed too.
+        assert!(editor
+            .available_actions
+            .contains(&"play_named_macro".to_string()));
+    }
+
     #[test]
     fn qualified_action_roundtrips_through_resolved_to_config() {
         // A binding selected from the dropdown as `menu_open:File` must be
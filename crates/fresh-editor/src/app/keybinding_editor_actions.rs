@@ -26,6 +26,15 @@ impl Editor {
             .chain(self.menu_state.plugin_menus.iter())
             .map(|m| m.match_id().to_string())
             .collect();
+        // Macros saved to the workspace can be bound too; those saved to
+        // the config come from `self.config`.
+        let workspace_macros: Vec<String> = self
+            .active_window()
+            .macros
+            .named_macros()
+            .keys()
+            .cloned()
+            .collect();
         self.keybinding_editor = Some(KeybindingEditor::new(
             &self.config,
             &keybindings,
@@ -33,6 +42,7 @@ impl Editor {
             &cmd_registry,
             config_path,
             &menu_names,
+            &workspace_macros,
         ));
     }
 
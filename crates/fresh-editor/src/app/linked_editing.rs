@@ -0,0 +1,361 @@
+//! Linked editing: an edit inside one of the ranges reported by
+//! `textDocument/linkedEditingRange` — an HTML or JSX opening tag name, say
+//! — is mirrored into the others, such as the matching closing tag.
+//!
+//! Ranges are requested whenever the primary cursor settles somewhere new
+//! (checked once per frame) and kept as byte ranges for the buffer version
+//! they were computed against. A typed character, backspace or delete inside
+//! one of them is applied to all of them as a single undo group. Mirroring
+//! moves the ranges along with the edit, so typing a whole new tag name
+//! needs no further round trips; any other edit leaves them stale until the
+//! server answers again.
+
+use std::ops::Range;
+
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::types::LspFeature;
+
+use super::Editor;
+
+/// Linked editing ranges of a window and the request that refreshes them.
+#[derive(Debug, Default)]
+pub(crate) struct LinkedEditingState {
+    /// Ranges reported for the primary cursor's last position
+    ranges: Option<LinkedRanges>,
+    pending: Option<PendingLinkedRanges>,
+    /// `(buffer, version, position)` last asked about, so a cursor that
+    /// stays put is asked about once
+    last_requested: Option<(BufferId, u64, usize)>,
+}
+
+#[derive(Debug)]
+struct LinkedRanges {
+    buffer_id: BufferId,
+    /// Buffer version the ranges are valid for
+    version: u64,
+    /// Byte ranges in document order, all holding the same text
+    ranges: Vec<Range<usize>>,
+    /// The server's `wordPattern`, anchored to match a whole range
+    word_pattern: Option<regex::Regex>,
+}
+
+impl LinkedRanges {
+    /// Index of the range containing `start..end` if the ranges are
+    /// current for `buffer_id` at `version`.
+    fn containing(
+        &self,
+        buffer_id: BufferId,
+        version: u64,
+        start: usize,
+        end: usize,
+    ) -> Option<usize> {
+        if self.buffer_id != buffer_id || self.version != version {
+            return None;
+        }
+        self.ranges
+            .iter()
+            .position(|r| r.start <= start && end <= r.end)
+    }
+}
+
+#[derive(Debug)]
+struct PendingLinkedRanges {
+    request_id: u64,
+    buffer_id: BufferId,
+    version: u64,
+}
+
+/// The one buffer modification in `events` as `(position, deleted length,
+/// inserted text, cursor)`, or `None` when there are none or several.
+fn single_edit(events: &[Event]) -> Option<(usize, usize, String, CursorId)> {
+    let mut edits = events
+        .iter()
+        .filter(|e| matches!(e, Event::Insert { .. } | Event::Delete { .. }));
+    match (edits.next(), edits.next()) {
+        (
+            Some(Event::Insert {
+                position,
+                text,
+                cursor_id,
+            }),
+            None,
+        ) => Some((*position, 0, text.clone(), *cursor_id)),
+        (
+            Some(Event::Delete {
+                range, cursor_id, ..
+            }),
+            None,
+        ) => Some((range.start, range.len(), String::new(), *cursor_id)),
+        _ => None,
+    }
+}
+
+impl Editor {
+    /// Test-only accessor for the linked editing ranges currently known
+    /// for the active buffer.
+    #[doc(hidden)]
+    pub fn linked_editing_ranges_for_tests(&self) -> Vec<Range<usize>> {
+        let buffer_id = self.active_buffer();
+        let version = self.active_state().buffer.version();
+        self.active_window()
+            .linked_editing
+            .ranges
+            .as_ref()
+            .filter(|r| r.buffer_id == buffer_id && r.version == version)
+            .map(|r| r.ranges.clone())
+            .unwrap_or_default()
+    }
+
+    /// Whether linked editing is on for `language` and one of its servers
+    /// provides it.
+    fn linked_editing_available(&self, language: &str) -> bool {
+        self.config
+            .languages
+            .get(language)
+            .is_none_or(|l| l.linked_editing)
+            && self.lsp().is_some_and(|lsp| {
+                lsp.handle_for_feature(language, LspFeature::LinkedEditingRange)
+                    .is_some()
+            })
+    }
+
+    /// Ask the server for the ranges linked to the primary cursor's
+    /// position, once per position. Called every frame.
+    pub(super) fn maybe_request_linked_editing_ranges(&mut self) {
+        if self.active_cursors().count() != 1 {
+            return;
+        }
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        let (version, language) = {
+            let state = self.active_state();
+            (state.buffer.version(), state.language.clone())
+        };
+        let key = (buffer_id, version, position);
+
+        let linked = &mut self.active_window_mut().linked_editing;
+        if linked.last_requested == Some(key) {
+            return;
+        }
+        linked.last_requested = Some(key);
+        // Typing inside known ranges keeps them current without asking again.
+        if linked
+            .ranges
+            .as_ref()
+            .and_then(|r| r.containing(buffer_id, version, position, position))
+            .is_some()
+        {
+            return;
+        }
+
+        if !self.linked_editing_available(&language) {
+            self.active_window_mut().linked_editing.ranges = None;
+            return;
+        }
+        let (line, character) = self
+            .active_state()
+            .buffer
+            .position_to_lsp_position(position);
+        let request_id = self.active_window().next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::LinkedEditingRange,
+                |handle, uri, _language| match handle.linked_editing_range(
+                    request_id,
+                    uri.as_uri().clone(),
+                    line as u32,
+                    character as u32,
+                ) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request linked editing ranges: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.linked_editing.pending = Some(PendingLinkedRanges {
+                request_id,
+                buffer_id,
+                version,
+            });
+        }
+    }
+
+    /// Handle a `textDocument/linkedEditingRange` response: replace the
+    /// known ranges, unless the buffer changed since the request.
+    pub(super) fn handle_linked_editing_ranges(
+        &mut self,
+        request_id: u64,
+        ranges: Option<lsp_types::LinkedEditingRanges>,
+    ) {
+        let linked = &mut self.active_window_mut().linked_editing;
+        let Some(pending) = linked
+            .pending
+            .take_if(|pending| pending.request_id == request_id)
+        else {
+            tracing::debug!("Ignoring stale linked editing response: {}", request_id);
+            return;
+        };
+        let ranges = ranges.and_then(|ranges| self.linked_ranges_from_lsp(&pending, ranges));
+        self.active_window_mut().linked_editing.ranges = ranges;
+    }
+
+    /// Convert a server response to byte ranges. `None` if the buffer
+    /// changed since the request, or there is nothing to mirror into.
+    fn linked_ranges_from_lsp(
+        &mut self,
+        pending: &PendingLinkedRanges,
+        response: lsp_types::LinkedEditingRanges,
+    ) -> Option<LinkedRanges> {
+        let state = self.buffers_mut().get_mut(&pending.buffer_id)?;
+        if state.buffer.version() != pending.version {
+            return None;
+        }
+        let to_byte = |pos: lsp_types::Position| {
+            state
+                .buffer
+                .lsp_position_to_byte(pos.line as usize, pos.character as usize)
+        };
+        let mut ranges: Vec<Range<usize>> = response
+            .ranges
+            .iter()
+            .map(|r| to_byte(r.start)..to_byte(r.end))
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+        let well_formed = ranges.len() >= 2
+            && ranges.iter().all(|r| r.start <= r.end)
+            && ranges.windows(2).all(|w| w[0].end <= w[1].start);
+        if !well_formed {
+            return None;
+        }
+        // Mirroring copies edits by offset, which only makes sense while
+        // every range holds the same text.
+        let first = state.get_text_range(ranges[0].start, ranges[0].end);
+        if ranges[1..]
+            .iter()
+            .any(|r| state.get_text_range(r.start, r.end) != first)
+        {
+            return None;
+        }
+        let word_pattern = response.word_pattern.and_then(|pattern| {
+            regex::Regex::new(&format!("^(?:{})$", pattern))
+                .inspect_err(|e| tracing::debug!("Unusable linked editing wordPattern: {}", e))
+                .ok()
+        });
+        Some(LinkedRanges {
+            buffer_id: pending.buffer_id,
+            version: pending.version,
+            ranges,
+            word_pattern,
+        })
+    }
+
+    /// Apply the single-cursor edit `events` (a typed character, backspace
+    /// or delete) together with the same edit in every other linked range,
+    /// as one undo group. Returns `false` without applying anything unless
+    /// the edit lies inside a linked range.
+    ///
+    /// An edit that would leave the range no longer a word — a space typed
+    /// after a tag name to start an attribute, say — ends linked editing
+    /// instead of being mirrored.
+    pub(super) fn apply_linked_edit(&mut self, events: &[Event]) -> bool {
+        let Some((position, deleted_len, text, cursor_id)) = single_edit(events) else {
+            return false;
+        };
+        let buffer_id = self.active_buffer();
+        let version = self.active_state().buffer.version();
+        let Some(linked) = self.active_window().linked_editing.ranges.as_ref() else {
+            return false;
+        };
+        let Some(index) = linked.containing(buffer_id, version, position, position + deleted_len)
+        else {
+            return false;
+        };
+        let ranges = linked.ranges.clone();
+        let word_pattern = linked.word_pattern.clone();
+
+        let edited = ranges[index].clone();
+        let offset = position - edited.start;
+        let old_text = self
+            .active_state_mut()
+            .get_text_range(edited.start, edited.end);
+        if !old_text.is_char_boundary(offset) || !old_text.is_char_boundary(offset + deleted_len) {
+            return false;
+        }
+        let mut new_text = old_text.clone();
+        new_text.replace_range(offset..offset + deleted_len, &text);
+        let still_word = match &word_pattern {
+            Some(pattern) => new_text.is_empty() || pattern.is_match(&new_text),
+            None => !new_text.chars().any(char::is_whitespace),
+        };
+        if !still_word {
+            self.active_window_mut().linked_editing.ranges = None;
+            return false;
+        }
+
+        // Mirror positions are in the buffer after the original edit, which
+        // shifts the ranges behind it by `delta`.
+        let delta = text.len() as isize - deleted_len as isize;
+        let deleted_text = &old_text[offset..offset + deleted_len];
+        let mut mirrored = Vec::new();
+        for (i, range) in ranges.iter().enumerate() {
+            if i == index {
+                continue;
+            }
+            let start = if i > index {
+                range.start.saturating_add_signed(delta)
+            } else {
+                range.start
+            };
+            let at = start + offset;
+            if deleted_len > 0 {
+                mirrored.push(Event::Delete {
+                    range: at..at + deleted_len,
+                    deleted_text: deleted_text.to_string(),
+                    cursor_id,
+                });
+            }
+            if !text.is_empty() {
+                mirrored.push(Event::Insert {
+                    position: at,
+                    text: text.clone(),
+                    cursor_id,
+                });
+            }
+        }
+
+        if let Some(log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
+            log.begin_undo_group();
+        }
+        for event in events {
+            self.log_and_apply_event(event);
+        }
+        if let Err(e) =
+            self.apply_events_to_buffer_as_bulk_edit(buffer_id, mirrored, "Linked edit".to_string())
+        {
+            tracing::warn!("Failed to mirror linked edit: {}", e);
+        }
+        if let Some(log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
+            log.end_undo_group();
+        }
+
+        // Every range took the same edit, so the k-th one moves by `delta`
+        // for each range before it and grows by `delta` itself.
+        let version = self.active_state().buffer.version();
+        if let Some(linked) = self.active_window_mut().linked_editing.ranges.as_mut() {
+            linked.version = version;
+            for (k, range) in linked.ranges.iter_mut().enumerate() {
+                let start = range.start.saturating_add_signed(delta * k as isize);
+                let end = range.end.saturating_add_signed(delta * (k as isize + 1));
+                *range = start..end;
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,94 @@
+//! Local file history (`editor.local_history_enabled`).
+//!
+//! Every save records the file as written to disk in the local history
+//! store, from which the "File History" plugin lists, diffs and restores
+//! earlier versions.
+
+use crate::model::event::BufferId;
+use crate::services::local_history::{LocalHistoryStore, Retention};
+use std::path::Path;
+
+impl crate::app::window::Window {
+    pub(crate) fn local_history_store(&self) -> LocalHistoryStore {
+        LocalHistoryStore::new(&self.resources.dir_context.local_history_dir())
+    }
+
+    /// Record the saved content of a buffer as a new local history version.
+    pub(crate) fn record_local_history(&self, buffer_id: BufferId) {
+        let config = &self.resources.config.editor;
+        if !config.local_history_enabled {
+            return;
+        }
+        let Some(path) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .map(Path::to_path_buf)
+        else {
+            return;
+        };
+        let filesystem = self.authority().filesystem.as_ref();
+        match filesystem.metadata(&path) {
+            Ok(metadata) if metadata.size <= config.local_history_max_size_bytes => {}
+            _ => return,
+        }
+        let content = match filesystem.read_file(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::debug!("Can't read {} for local history: {}", path.display(), e);
+                return;
+            }
+        };
+        let retention = Retention::from_config(config);
+        if let Err(e) = self.local_history_store().record(
+            &path,
+            &content,
+            std::time::SystemTime::now(),
+            &retention,
+        ) {
+            tracing::warn!(
+                "Failed to record local history for {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+impl crate::app::Editor {
+    /// Replace the content of the active buffer with version `id` of its
+    /// file's local history, as a single undoable edit. Returns whether the
+    /// version was found and applied.
+    pub fn restore_local_history_version(&mut self, id: u64) -> bool {
+        if self.active_window().is_editing_disabled() {
+            return false;
+        }
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf)
+        else {
+            return false;
+        };
+        let content = match self.active_window().local_history_store().read(&path, id) {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(content) => content,
+                Err(_) => {
+                    tracing::warn!(
+                        "Local history version {} of {} isn't UTF-8",
+                        id,
+                        path.display()
+                    );
+                    return false;
+                }
+            },
+            Err(e) => {
+                tracing::warn!("Can't read local history of {}: {}", path.display(), e);
+                return false;
+            }
+        };
+        self.replace_active_buffer_content(&content, "Restore from local history");
+        true
+    }
+}
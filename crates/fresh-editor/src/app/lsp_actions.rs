@@ -1098,6 +1098,21 @@ impl Editor {
         self.active_window_mut()
             .pending_inlay_hints_requests
             .retain(|_, req| req.buffer_id != buffer_id);
+        self.active_window_mut()
+            .code_lens_debounce
+            .remove(&buffer_id);
+        self.active_window_mut()
+            .pending_code_lens_requests
+            .retain(|_, req| req.buffer_id != buffer_id);
+        self.active_window_mut()
+            .document_decorations_debounce
+            .remove(&buffer_id);
+        self.active_window_mut()
+            .pending_document_link_requests
+            .retain(|_, req| req.buffer_id != buffer_id);
+        self.active_window_mut()
+            .pending_document_color_requests
+            .retain(|_, req| req.buffer_id != buffer_id);
 
         // Clear all LSP-related overlays for this buffer (diagnostics + inlay hints)
         let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
@@ -1236,12 +1251,16 @@ impl Editor {
             }
         }
 
-        // Schedule folding range refresh
+        // Schedule folding range, code lens, link and color refresh
         let _ = __next_id;
         let _ = lsp;
         let _ = handle;
         self.active_window_mut()
             .schedule_folding_ranges_refresh(buffer_id);
+        self.active_window_mut()
+            .schedule_code_lens_refresh(buffer_id);
+        self.active_window_mut()
+            .schedule_document_decorations_refresh(buffer_id);
     }
 
     /// Set up a plugin development workspace for LSP support on a buffer.
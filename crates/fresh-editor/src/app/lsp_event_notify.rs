@@ -54,6 +54,28 @@ impl Window {
                     text: String::new(),
                 }]
             }
+            Event::ReplaceBytes {
+                position,
+                deleted,
+                inserted,
+                ..
+            } => {
+                // A Delete and an Insert in one; bytes that aren't UTF-8 go
+                // over as replacement characters.
+                let buffer = &self.active_state().buffer;
+                let (start_line, start_char) = buffer.position_to_lsp_position(*position);
+                let (end_line, end_char) =
+                    buffer.position_to_lsp_position(position + deleted.len());
+                let lsp_range = LspRange::new(
+                    Position::new(start_line as u32, start_char as u32),
+                    Position::new(end_line as u32, end_char as u32),
+                );
+                vec![TextDocumentContentChangeEvent {
+                    range: Some(lsp_range),
+                    range_length: None,
+                    text: String::from_utf8_lossy(inserted).into_owned(),
+                }]
+            }
             Event::Batch { events, .. } => {
                 // Collect all changes from sub-events into a single vector
                 // This allows sending all changes in one didChange notification
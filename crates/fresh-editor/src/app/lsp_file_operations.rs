@@ -0,0 +1,339 @@
+//! Language servers taking part in file operations from the file explorer.
+//!
+//! Renames, moves and deletions are first put to the servers that asked for
+//! `workspace/willRenameFiles` or `workspace/willDeleteFiles`. Their answers
+//! may carry edits — imports of a moved module, say — which are shown in
+//! the workspace edit preview (or summarized in a confirmation prompt when
+//! there is none) and applied before the files change. Once the
+//! operation has landed, servers that asked for the `workspace/did*Files`
+//! notifications are told, creations included.
+//!
+//! Open buffers follow renamed files (`relocate_buffers_for_rename`), so the
+//! edits and the undo history made at the old path carry over.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+
+use crate::services::lsp::file_operations::{
+    FileOperation, FileOperationKind, FileOperationTarget,
+};
+use crate::view::prompt::PromptType;
+
+use super::Editor;
+
+/// An explorer operation held back until the servers have answered.
+#[derive(Debug)]
+pub(crate) enum DeferredFileAction {
+    Rename {
+        original_path: PathBuf,
+        original_name: String,
+        new_path: PathBuf,
+        new_name: String,
+        is_new_file: bool,
+    },
+    Delete {
+        paths: Vec<(PathBuf, bool)>,
+    },
+    /// A single cut-and-paste
+    Move {
+        src: PathBuf,
+        dst: PathBuf,
+    },
+    /// A multi-item cut-and-paste, conflicts already resolved
+    MultiMove {
+        safe: Vec<(PathBuf, PathBuf)>,
+        to_overwrite: Vec<(PathBuf, PathBuf)>,
+    },
+}
+
+/// `workspace/will*Files` requests in flight for one explorer operation.
+#[derive(Debug)]
+pub(crate) struct PendingFileOperation {
+    action: DeferredFileAction,
+    /// Requests not answered yet
+    awaiting: HashSet<u64>,
+    /// Edits returned so far
+    edits: Vec<lsp_types::WorkspaceEdit>,
+}
+
+impl Editor {
+    /// Describe `paths` — `(old, new, is_dir)` — to the servers, with `new`
+    /// set for renames only.
+    pub(super) fn file_operation(
+        &self,
+        kind: FileOperationKind,
+        paths: impl IntoIterator<Item = (PathBuf, Option<PathBuf>, bool)>,
+    ) -> FileOperation {
+        let translation = self.authority().path_translation.as_ref();
+        let uri = |path: &Path| {
+            super::types::LspUri::from_host_path(path, translation)
+                .map(|uri| uri.as_str().to_string())
+        };
+        let targets = paths
+            .into_iter()
+            .filter_map(|(path, new_path, is_dir)| {
+                Some(FileOperationTarget {
+                    uri: uri(&path)?,
+                    new_uri: match new_path {
+                        Some(new_path) => Some(uri(&new_path)?),
+                        None => None,
+                    },
+                    is_dir,
+                })
+            })
+            .collect();
+        FileOperation { kind, targets }
+    }
+
+    /// Run `action` once every server interested in `operation` has had
+    /// the chance to answer with edits. Without interested servers it runs
+    /// straight away.
+    pub(super) fn run_file_operation(
+        &mut self,
+        operation: FileOperation,
+        action: DeferredFileAction,
+    ) {
+        let mut awaiting = HashSet::new();
+        // Field-level borrows: the participants borrow `lsp` while request
+        // ids are taken from the same window.
+        let win = self.active_window_mut();
+        for (sh, filtered) in win.lsp.file_operation_participants(&operation, true) {
+            let request_id = win.next_lsp_request_id;
+            win.next_lsp_request_id += 1;
+            match sh.handle.will_file_operation(request_id, filtered) {
+                Ok(()) => {
+                    awaiting.insert(request_id);
+                }
+                Err(e) => tracing::warn!("Failed to ask '{}' about file operation: {}", sh.name, e),
+            }
+        }
+
+        if awaiting.is_empty() {
+            win.pending_file_operation = None;
+            self.run_deferred_file_action(action);
+            return;
+        }
+        win.pending_file_operation = Some(PendingFileOperation {
+            action,
+            awaiting,
+            edits: Vec::new(),
+        });
+        self.set_status_message(t!("explorer.waiting_for_lsp").to_string());
+    }
+
+    /// Handle an answer to a `workspace/will*Files` request. Once all are
+    /// in, either run the operation or ask whether to apply the edits.
+    pub(super) fn handle_lsp_will_file_operation(
+        &mut self,
+        request_id: u64,
+        edit: Result<Option<lsp_types::WorkspaceEdit>, String>,
+    ) {
+        let Some(pending) = self.active_window_mut().pending_file_operation.as_mut() else {
+            return;
+        };
+        if !pending.awaiting.remove(&request_id) {
+            tracing::debug!("Ignoring stale file operation answer: {}", request_id);
+            return;
+        }
+        // A server that failed or timed out simply has no edits to add.
+        if let Ok(Some(edit)) = edit {
+            pending.edits.push(edit);
+        }
+        if !pending.awaiting.is_empty() {
+            return;
+        }
+
+        let (count, paths) = self.summarize_file_operation_edits();
+        if count == 0 {
+            self.finish_file_operation(false);
+            return;
+        }
+        if self.can_preview_workspace_edits() {
+            let edits = self
+                .active_window_mut()
+                .pending_file_operation
+                .as_mut()
+                .map(|pending| std::mem::take(&mut pending.edits))
+                .unwrap_or_default();
+            let origin = super::workspace_edit_preview::EditOrigin::FileOperation;
+            if let Err(e) = self.apply_or_preview_workspace_edit(merge_edits(edits), origin) {
+                tracing::warn!("Failed to preview file operation edits: {}", e);
+                self.cancel_file_operation();
+            }
+            return;
+        }
+        let names = super::file_explorer::format_path_preview_for_prompt(&paths, 3);
+        self.start_prompt(
+            t!("explorer.lsp_edits_confirm", count = count, names = &names).to_string(),
+            PromptType::ConfirmFileOperationEdits,
+        );
+    }
+
+    /// Count the edits gathered for the pending operation and list the
+    /// files they touch, for the confirmation prompt.
+    fn summarize_file_operation_edits(&self) -> (usize, Vec<PathBuf>) {
+        use lsp_types::{DocumentChangeOperation, DocumentChanges, ResourceOp};
+
+        let Some(pending) = self.active_window().pending_file_operation.as_ref() else {
+            return (0, Vec::new());
+        };
+        let mut count = 0;
+        let mut uris = Vec::new();
+        for edit in &pending.edits {
+            for (uri, edits) in edit.changes.iter().flatten() {
+                count += edits.len();
+                uris.push(uri.clone());
+            }
+            match &edit.document_changes {
+                Some(DocumentChanges::Edits(edits)) => {
+                    for edit in edits {
+                        count += edit.edits.len();
+                        uris.push(edit.text_document.uri.clone());
+                    }
+                }
+                Some(DocumentChanges::Operations(ops)) => {
+                    for op in ops {
+                        match op {
+                            DocumentChangeOperation::Edit(edit) => {
+                                count += edit.edits.len();
+                                uris.push(edit.text_document.uri.clone());
+                            }
+                            DocumentChangeOperation::Op(op) => {
+                                count += 1;
+                                uris.push(match op {
+                                    ResourceOp::Create(c) => c.uri.clone(),
+                                    ResourceOp::Rename(r) => r.old_uri.clone(),
+                                    ResourceOp::Delete(d) => d.uri.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+
+        let translation = self.authority().path_translation.as_ref();
+        let mut seen = HashSet::new();
+        let paths = uris
+            .into_iter()
+            .filter_map(|uri| super::types::LspUri::from_wire(uri).to_host_path(translation))
+            .filter(|path| seen.insert(path.clone()))
+            .collect();
+        (count, paths)
+    }
+
+    /// Run the pending operation, applying the servers' edits first when
+    /// `apply_edits` is set.
+    pub(super) fn finish_file_operation(&mut self, apply_edits: bool) {
+        let Some(pending) = self.active_window_mut().pending_file_operation.take() else {
+            return;
+        };
+        if apply_edits {
+            for edit in pending.edits {
+                if let Err(e) = self.apply_workspace_edit(edit) {
+                    tracing::warn!("Failed to apply file operation edits: {}", e);
+                }
+            }
+        }
+        self.run_deferred_file_action(pending.action);
+    }
+
+    /// Drop the pending operation without touching any file.
+    pub(super) fn cancel_file_operation(&mut self) {
+        if self
+            .active_window_mut()
+            .pending_file_operation
+            .take()
+            .is_some()
+        {
+            self.set_status_message(t!("explorer.file_operation_cancelled").to_string());
+        }
+    }
+
+    fn run_deferred_file_action(&mut self, action: DeferredFileAction) {
+        match action {
+            DeferredFileAction::Rename {
+                original_path,
+                original_name,
+                new_path,
+                new_name,
+                is_new_file,
+            } => self.rename_explorer_path(
+                original_path,
+                original_name,
+                new_path,
+                new_name,
+                is_new_file,
+            ),
+            DeferredFileAction::Delete { paths } => {
+                for (path, is_dir) in paths {
+                    self.perform_file_explorer_delete(path, is_dir);
+                }
+            }
+            DeferredFileAction::Move { src, dst } => self.paste_explorer_path(src, dst, true),
+            DeferredFileAction::MultiMove { safe, to_overwrite } => {
+                self.paste_resolved_explorer_paths(safe, to_overwrite, true)
+            }
+        }
+    }
+
+    /// Tell the interested servers about files and folders the explorer
+    /// created.
+    pub(super) fn notify_lsp_files_created<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) {
+        let fs = &self.authority().filesystem;
+        let operation = self.file_operation(
+            FileOperationKind::Create,
+            paths
+                .into_iter()
+                .map(|path| (path.to_path_buf(), None, fs.is_dir(path).unwrap_or(false))),
+        );
+        self.notify_lsp_file_operation(operation);
+    }
+
+    /// Tell the interested servers that `operation` has happened.
+    pub(super) fn notify_lsp_file_operation(&self, operation: FileOperation) {
+        if operation.targets.is_empty() {
+            return;
+        }
+        let Some(lsp) = self.lsp() else {
+            return;
+        };
+        for (sh, filtered) in lsp.file_operation_participants(&operation, false) {
+            if let Err(e) = sh.handle.did_file_operation(filtered) {
+                tracing::warn!("Failed to notify '{}' of file operation: {}", sh.name, e);
+            }
+        }
+    }
+}
+
+/// Fold the servers' answers into one edit, in the order they came in.
+fn merge_edits(edits: Vec<lsp_types::WorkspaceEdit>) -> lsp_types::WorkspaceEdit {
+    use lsp_types::{
+        DocumentChangeOperation, DocumentChanges, OneOf, OptionalVersionedTextDocumentIdentifier,
+        TextDocumentEdit,
+    };
+
+    let mut operations = Vec::new();
+    for edit in edits {
+        for (uri, edits) in edit.changes.into_iter().flatten() {
+            operations.push(DocumentChangeOperation::Edit(TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+                edits: edits.into_iter().map(OneOf::Left).collect(),
+            }));
+        }
+        match edit.document_changes {
+            Some(DocumentChanges::Edits(edits)) => {
+                operations.extend(edits.into_iter().map(DocumentChangeOperation::Edit));
+            }
+            Some(DocumentChanges::Operations(ops)) => operations.extend(ops),
+            None => {}
+        }
+    }
+    lsp_types::WorkspaceEdit {
+        document_changes: Some(DocumentChanges::Operations(operations)),
+        ..Default::default()
+    }
+}
@@ -529,7 +529,7 @@ impl Editor {
 
     /// Ensure didOpen has been sent to all handles for the given buffer's language.
     /// Returns Some(()) on success, None if we can't access required state.
-    fn ensure_did_open_all(
+    pub(super) fn ensure_did_open_all(
         &mut self,
         buffer_id: BufferId,
         uri: &crate::app::types::LspUri,
@@ -1284,8 +1284,8 @@ impl Editor {
         use crate::view::virtual_text::VirtualTextPosition;
         use ratatui::style::{Color, Style};
 
-        // Clear existing inlay hints
-        state.virtual_texts.clear(&mut state.marker_list);
+        // Clear existing inlay hints (virtual lines such as code lenses stay)
+        state.virtual_texts.clear_inline(&mut state.marker_list);
 
         if hints.is_empty() {
             return;
@@ -1427,6 +1427,52 @@ impl Editor {
         Ok(())
     }
 
+    /// Resolve the call- or type-hierarchy item under the cursor.
+    ///
+    /// Only the `prepare*Hierarchy` step runs here; the result is handed to
+    /// plugins through the `lsp_hierarchy` hook, and the view that renders it
+    /// expands each node on demand via `editor.sendLspRequest`.
+    pub(crate) fn request_hierarchy(&mut self, direction: crate::app::types::HierarchyDirection) {
+        let cursor_pos = self.active_cursors().primary().position;
+        let (line, character) = self
+            .active_state()
+            .buffer
+            .position_to_lsp_position(cursor_pos);
+        let buffer_id = self.active_buffer();
+        let request_id = self.active_window_mut().next_lsp_request_id;
+
+        let sent =
+            self.with_lsp_for_buffer(buffer_id, direction.feature(), |handle, uri, language| {
+                handle
+                    .prepare_hierarchy(
+                        request_id,
+                        direction.kind(),
+                        uri.as_uri().clone(),
+                        line as u32,
+                        character as u32,
+                    )
+                    .is_ok()
+                    .then(|| language.to_string())
+            });
+
+        match sent.flatten() {
+            Some(language) => {
+                tracing::info!(
+                    "Requested {} hierarchy at {}:{}",
+                    direction.as_str(),
+                    line,
+                    character
+                );
+                let window = self.active_window_mut();
+                window.next_lsp_request_id += 1;
+                window.pending_hierarchy_request = Some((request_id, direction, language));
+            }
+            None => {
+                self.set_status_message(t!("lsp.hierarchy_unsupported").to_string());
+            }
+        }
+    }
+
     /// Request LSP signature help at current cursor position
     pub(crate) fn request_signature_help(&mut self) {
         // Get the current buffer and cursor position
@@ -1911,31 +1957,26 @@ impl Editor {
 
     /// Execute a code action that has been fully resolved (has edit and/or command).
     pub(crate) fn execute_resolved_code_action(&mut self, ca: lsp_types::CodeAction) {
-        let title = ca.title.clone();
-
-        // Apply workspace edit if present
+        // Apply workspace edit if present. The command, if any, runs once
+        // the edit is in (it may trigger workspace/applyEdit from server).
         if let Some(edit) = ca.edit {
-            match self.apply_workspace_edit(edit) {
-                Ok(n) => {
-                    self.set_status_message(
-                        t!("lsp.code_action_applied", title = &title, count = n).to_string(),
-                    );
-                }
-                Err(e) => {
-                    self.set_status_message(format!("Code action failed: {e}"));
-                    return;
-                }
+            let origin = super::workspace_edit_preview::EditOrigin::CodeAction {
+                title: ca.title,
+                command: ca.command,
+            };
+            if let Err(e) = self.apply_or_preview_workspace_edit(edit, origin) {
+                self.set_status_message(format!("Code action failed: {e}"));
             }
+            return;
         }
 
-        // Execute command if present (may trigger workspace/applyEdit from server)
         if let Some(cmd) = ca.command {
             self.send_execute_command(cmd);
         }
     }
 
     /// Send workspace/executeCommand to the LSP server
-    fn send_execute_command(&mut self, cmd: lsp_types::Command) {
+    pub(super) fn send_execute_command(&mut self, cmd: lsp_types::Command) {
         tracing::info!("Executing LSP command: {} ({})", cmd.title, cmd.command);
         self.set_status_message(
             t!(
@@ -2166,6 +2207,37 @@ impl Editor {
         Ok(())
     }
 
+    /// Handle the items of a `prepare*Hierarchy` response.
+    pub(crate) fn handle_hierarchy_prepared(
+        &mut self,
+        request_id: u64,
+        items: Vec<serde_json::Value>,
+    ) {
+        let Some((direction, language)) = self
+            .active_window_mut()
+            .pending_hierarchy_request
+            .take_if(|(id, _, _)| *id == request_id)
+            .map(|(_, direction, language)| (direction, language))
+        else {
+            tracing::debug!("Ignoring stale hierarchy response: {}", request_id);
+            return;
+        };
+
+        if items.is_empty() {
+            self.set_status_message(t!("lsp.no_hierarchy").to_string());
+            return;
+        }
+
+        self.plugin_manager.read().unwrap().run_hook(
+            "lsp_hierarchy",
+            crate::services::plugins::hooks::HookArgs::LspHierarchy {
+                direction: direction.as_str().to_string(),
+                language,
+                items,
+            },
+        );
+    }
+
     /// Apply LSP text edits to a buffer and return the number of changes made.
     /// Edits are sorted in reverse order and applied as a batch.
     pub(crate) fn apply_lsp_text_edits(
@@ -2283,6 +2355,70 @@ impl Editor {
         Ok(changes)
     }
 
+    /// Whether `text_doc_edit` was computed against another version of
+    /// the document than the one the servers have from us. Edits without a
+    /// version are never stale.
+    pub(super) fn is_stale_text_document_edit(
+        &self,
+        text_doc_edit: &lsp_types::TextDocumentEdit,
+    ) -> bool {
+        let Some(expected_version) = text_doc_edit.text_document.version else {
+            return false;
+        };
+        let uri = crate::app::types::LspUri::from_wire(text_doc_edit.text_document.uri.clone());
+        let Ok(path) =
+            super::lsp_uri_to_host_path(&uri, self.authority().path_translation.as_ref())
+        else {
+            return false;
+        };
+        let Some(lsp) = self.lsp() else {
+            return false;
+        };
+        let language = self
+            .buffers()
+            .get(&self.active_buffer())
+            .map(|s| s.language.clone())
+            .unwrap_or_default();
+        for sh in lsp.get_handles(&language) {
+            if let Some(current_version) = sh.handle.document_version(&path) {
+                if (expected_version as i64) != current_version {
+                    tracing::warn!(
+                        "Rejecting stale TextDocumentEdit for {:?}: \
+                         server version {} != our version {}",
+                        path,
+                        expected_version,
+                        current_version,
+                    );
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Open the file a workspace edit targets, reporting failure in the
+    /// status bar (or asking about the encoding of a large file).
+    pub(super) fn open_file_for_workspace_edit(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Option<BufferId> {
+        match self.open_file(path) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                if let Some(confirmation) =
+                    e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+                {
+                    self.start_large_file_encoding_confirmation(confirmation);
+                } else {
+                    self.set_status_message(
+                        t!("file.error_opening", error = e.to_string()).to_string(),
+                    );
+                }
+                None
+            }
+        }
+    }
+
     /// Apply a single TextDocumentEdit from a workspace edit.
     ///
     /// Per LSP spec: if `text_document.version` is non-null, it must match the
@@ -2292,57 +2428,16 @@ impl Editor {
         &mut self,
         text_doc_edit: lsp_types::TextDocumentEdit,
     ) -> AnyhowResult<usize> {
-        // Wrap the incoming wire URI once; both the version-check
-        // lookup and the file-open below need the host-path form.
-        let uri = crate::app::types::LspUri::from_wire(text_doc_edit.text_document.uri);
-
-        // Version check: if the server specifies a version, verify it matches
-        // what we sent. A mismatch means the edit was computed against stale content.
-        if let Some(expected_version) = text_doc_edit.text_document.version {
-            if let Ok(path) =
-                super::lsp_uri_to_host_path(&uri, self.authority().path_translation.as_ref())
-            {
-                if let Some(lsp) = self.lsp() {
-                    let language = self
-                        .buffers()
-                        .get(&self.active_buffer())
-                        .map(|s| s.language.clone())
-                        .unwrap_or_default();
-                    for sh in lsp.get_handles(&language) {
-                        if let Some(current_version) = sh.handle.document_version(&path) {
-                            if (expected_version as i64) != current_version {
-                                tracing::warn!(
-                                    "Rejecting stale TextDocumentEdit for {:?}: \
-                                     server version {} != our version {}",
-                                    path,
-                                    expected_version,
-                                    current_version,
-                                );
-                                return Ok(0);
-                            }
-                        }
-                    }
-                }
-            }
+        if self.is_stale_text_document_edit(&text_doc_edit) {
+            return Ok(0);
         }
+        let uri = crate::app::types::LspUri::from_wire(text_doc_edit.text_document.uri);
 
         if let Ok(path) =
             super::lsp_uri_to_host_path(&uri, self.authority().path_translation.as_ref())
         {
-            let buffer_id = match self.open_file(&path) {
-                Ok(id) => id,
-                Err(e) => {
-                    if let Some(confirmation) =
-                        e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
-                    {
-                        self.start_large_file_encoding_confirmation(confirmation);
-                    } else {
-                        self.set_status_message(
-                            t!("file.error_opening", error = e.to_string()).to_string(),
-                        );
-                    }
-                    return Ok(0);
-                }
+            let Some(buffer_id) = self.open_file_for_workspace_edit(&path) else {
+                return Ok(0);
             };
 
             let edits: Vec<lsp_types::TextEdit> = text_doc_edit
@@ -2374,7 +2469,10 @@ impl Editor {
     }
 
     /// Apply a resource operation (CreateFile, RenameFile, DeleteFile) from a workspace edit.
-    fn apply_resource_operation(&mut self, op: lsp_types::ResourceOp) -> AnyhowResult<()> {
+    pub(super) fn apply_resource_operation(
+        &mut self,
+        op: lsp_types::ResourceOp,
+    ) -> AnyhowResult<()> {
         // Each URI in a resource operation is wire-side and must be
         // translated back to the host before we touch the host
         // filesystem. Wrapping in [`LspUri`] and calling
@@ -2515,21 +2613,8 @@ impl Editor {
                 if let Ok(path) =
                     super::lsp_uri_to_host_path(&uri, self.authority().path_translation.as_ref())
                 {
-                    let buffer_id = match self.open_file(&path) {
-                        Ok(id) => id,
-                        Err(e) => {
-                            if let Some(confirmation) = e.downcast_ref::<
-                                crate::model::buffer::LargeFileEncodingConfirmation,
-                            >() {
-                                self.start_large_file_encoding_confirmation(confirmation);
-                            } else {
-                                self.set_status_message(
-                                    t!("file.error_opening", error = e.to_string())
-                                        .to_string(),
-                                );
-                            }
-                            return Ok(0);
-                        }
+                    let Some(buffer_id) = self.open_file_for_workspace_edit(&path) else {
+                        return Ok(0);
                     };
                     total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
                 }
@@ -2575,9 +2660,10 @@ impl Editor {
     ) -> AnyhowResult<()> {
         match result {
             Ok(workspace_edit) => {
-                let total_changes = self.apply_workspace_edit(workspace_edit)?;
-                self.active_window_mut().status_message =
-                    Some(t!("lsp.renamed", count = total_changes).to_string());
+                self.apply_or_preview_workspace_edit(
+                    workspace_edit,
+                    super::workspace_edit_preview::EditOrigin::Rename,
+                )?;
             }
             Err(error) => {
                 // Per LSP spec: ContentModified errors (-32801) should NOT be shown to user
@@ -2,18 +2,27 @@
 //!
 //! Cross-cutting effects — status messages, action replay through
 //! `handle_action`, virtual buffer creation for `show_macro_in_buffer` /
-//! `list_macros_in_buffer` — for the macro subsystem. Plain data state
-//! lives in `super::macros::MacroState`; these methods drive it.
+//! `list_macros_in_buffer` / `edit_named_macro`, saving named macros to the
+//! config — for the macro subsystem. Plain data state lives in
+//! `super::macros::MacroState`; these methods drive it.
 
 use rust_i18n::t;
 
-use crate::input::keybindings::Action;
-use crate::model::event::EventLog;
+use crate::config_io::{ConfigLayer, ConfigResolver};
+use crate::input::commands::{Command, CommandSource, Suggestion};
+use crate::input::keybindings::{Action, KeyContext};
+use crate::model::event::{BufferId, EventLog};
 use crate::state::EditorState;
+use crate::view::prompt::{Prompt, PromptType};
 
+use super::macros::{MacroScope, NamedMacroTarget};
+use super::navigation::JumpOptions;
 use super::types::{BufferKind, BufferMetadata};
 use super::Editor;
 
+/// Buffer mode of the buffers "Edit Macro" opens.
+const MACRO_EDIT_MODE: &str = "macro-edit";
+
 impl Editor {
     /// Toggle macro recording for the given register
     pub(super) fn toggle_macro_recording(&mut self, key: char) {
@@ -120,30 +129,141 @@ impl Editor {
             return;
         }
 
+        self.replay_macro(&actions, None);
+        self.set_status_message(t!("macro.played", key = key, count = actions.len()).to_string());
+    }
+
+    /// Replay `actions` as a single undo unit: one Undo reverts the entire
+    /// playback (and one Redo re-applies it) rather than one event per
+    /// replayed write action.
+    ///
+    /// With `starts`, the primary cursor is moved to each position in turn
+    /// and the macro replayed there. Callers pass the positions bottom-up so
+    /// edits made by one run don't shift the positions still to be visited.
+    fn replay_macro(&mut self, actions: &[Action], starts: Option<&[usize]>) {
         self.active_window_mut().macros.begin_play();
-        // Bracket the replay so the whole macro is a single undo unit: one Undo
-        // reverts the entire playback (and one Redo re-applies it) rather than
-        // one event per replayed write action. The group is opened and closed
-        // on the same buffer's log even if the macro switches buffers mid-replay.
+        // The group is opened and closed on the same buffer's log even if the
+        // macro switches buffers mid-replay.
         let group_buffer = self.active_buffer();
         if let Some(log) = self.active_window_mut().event_logs.get_mut(&group_buffer) {
             log.begin_undo_group();
         }
-        let action_count = actions.len();
         let width = self.active_chrome().last_frame_width;
         let height = self.active_chrome().last_frame_height;
-        for action in actions {
-            if let Err(e) = self.handle_action(action) {
-                tracing::warn!("Macro action failed: {}", e);
+        let runs: Vec<Option<usize>> = match starts {
+            Some(starts) => starts.iter().copied().map(Some).collect(),
+            None => vec![None],
+        };
+        for start in runs {
+            if let Some(position) = start {
+                // A macro that leaves the buffer ends the batch rather than
+                // running the remaining positions somewhere else.
+                if self.active_buffer() != group_buffer {
+                    break;
+                }
+                self.active_window_mut()
+                    .jump_active_cursor_to(position, JumpOptions::navigation());
+            }
+            for action in actions {
+                if let Err(e) = self.handle_action(action.clone()) {
+                    tracing::warn!("Macro action failed: {}", e);
+                }
+                self.recompute_layout(width, height);
             }
-            self.recompute_layout(width, height);
         }
         if let Some(log) = self.active_window_mut().event_logs.get_mut(&group_buffer) {
             log.end_undo_group();
         }
         self.active_window_mut().macros.end_play();
+    }
 
-        self.set_status_message(t!("macro.played", key = key, count = action_count).to_string());
+    /// Actions of the last recorded macro, for "apply macro to ..." commands.
+    fn last_macro_actions(&mut self) -> Option<(char, Vec<Action>)> {
+        let macros = &self.active_window().macros;
+        let Some((key, actions)) = macros
+            .last_register()
+            .and_then(|key| Some((key, macros.get(key)?.to_vec())))
+        else {
+            self.set_status_message(t!("status.no_macro_recorded").to_string());
+            return None;
+        };
+        if actions.is_empty() {
+            self.set_status_message(t!("macro.empty", key = key).to_string());
+            return None;
+        }
+        Some((key, actions))
+    }
+
+    /// Play the last recorded macro once at the start of every line the
+    /// primary selection touches (Emacs `apply-macro-to-region-lines`).
+    pub(super) fn apply_macro_to_lines(&mut self) {
+        if self.active_window().macros.is_playing() {
+            return;
+        }
+        let Some(range) = self.active_cursors().primary().selection_range() else {
+            self.set_status_message(t!("macro.no_selection").to_string());
+            return;
+        };
+        let Some((key, actions)) = self.last_macro_actions() else {
+            return;
+        };
+
+        let buffer = &self.active_state().buffer;
+        let first_line = buffer.get_line_number(range.start);
+        let mut last_line = buffer.get_line_number(range.end);
+        // A selection ending at the start of a line doesn't include that line.
+        if last_line > first_line && buffer.line_start_offset(last_line) == Some(range.end) {
+            last_line -= 1;
+        }
+        let starts: Vec<usize> = (first_line..=last_line)
+            .rev()
+            .filter_map(|line| buffer.line_start_offset(line))
+            .collect();
+
+        self.collapse_to_primary_cursor();
+        self.replay_macro(&actions, Some(&starts));
+        self.set_status_message(
+            t!("macro.applied_lines", key = key, count = starts.len()).to_string(),
+        );
+    }
+
+    /// Play the last recorded macro once at the start of every match of the
+    /// current search.
+    pub(super) fn apply_macro_to_matches(&mut self) {
+        if self.active_window().macros.is_playing() {
+            return;
+        }
+        let mut starts = self
+            .active_window()
+            .search_state
+            .as_ref()
+            .map(|search| search.matches.clone())
+            .unwrap_or_default();
+        if starts.is_empty() {
+            self.set_status_message(t!("macro.no_search_matches").to_string());
+            return;
+        }
+        let Some((key, actions)) = self.last_macro_actions() else {
+            return;
+        };
+
+        starts.sort_unstable_by(|a, b| b.cmp(a));
+        starts.dedup();
+        self.collapse_to_primary_cursor();
+        self.replay_macro(&actions, Some(&starts));
+        self.set_status_message(
+            t!("macro.applied_matches", key = key, count = starts.len()).to_string(),
+        );
+    }
+
+    /// Drop secondary cursors so a macro replayed at a list of positions runs
+    /// once per position rather than once per cursor.
+    fn collapse_to_primary_cursor(&mut self) {
+        if self.active_cursors().count() > 1 {
+            if let Err(e) = self.handle_action(Action::RemoveSecondaryCursors) {
+                tracing::warn!("Failed to remove secondary cursors: {}", e);
+            }
+        }
     }
 
     /// Record an action to the current macro (if recording).
@@ -196,63 +316,7 @@ impl Editor {
             json
         );
 
-        // Create a new buffer for the macro
-        let buffer_id = self.alloc_buffer_id();
-
-        let mut state = EditorState::new(
-            self.terminal_width,
-            self.terminal_height,
-            self.config.editor.large_file_threshold_bytes as usize,
-            std::sync::Arc::clone(&self.authority().filesystem),
-        );
-        state
-            .margins
-            .configure_for_line_numbers(self.config.editor.line_numbers);
-
-        self.windows
-            .get_mut(&self.active_window)
-            .map(|w| &mut w.buffers)
-            .expect("active window present")
-            .insert(buffer_id, state);
-        self.active_window_mut()
-            .event_logs
-            .insert(buffer_id, EventLog::new());
-
-        // Set buffer content
-        let fs = std::sync::Arc::clone(&self.authority().filesystem);
-        let threshold = self.config.editor.large_file_threshold_bytes as usize;
-        if let Some(state) = self
-            .windows
-            .get_mut(&self.active_window)
-            .map(|w| &mut w.buffers)
-            .expect("active window present")
-            .get_mut(&buffer_id)
-        {
-            state.buffer = crate::model::buffer::Buffer::from_str(&content, threshold, fs);
-        }
-
-        // Set metadata
-        let metadata = BufferMetadata {
-            kind: BufferKind::Virtual {
-                mode: "macro-view".to_string(),
-            },
-            display_name: format!("*Macro {}*", key),
-            lsp_enabled: false,
-            lsp_disabled_reason: Some("Virtual macro buffer".to_string()),
-            read_only: false, // Allow editing for saving
-            binary: false,
-            lsp_opened_with: std::collections::HashSet::new(),
-            hidden_from_tabs: false,
-            auto_revert_enabled: true,
-            synthetic_placeholder: false,
-            recovery_id: None,
-        };
-        self.active_window_mut()
-            .buffer_metadata
-            .insert(buffer_id, metadata);
-
-        // Switch to the new buffer
-        self.set_active_buffer(buffer_id);
+        self.open_macro_buffer(format!("*Macro {}*", key), "macro-view", &content, false);
         self.set_status_message(
             t!("macro.shown_buffer", key = key, count = actions_len).to_string(),
         );
@@ -281,7 +345,300 @@ impl Editor {
             }
         }
 
-        // Create a new buffer for the macro list
+        self.open_macro_buffer("*Macros*".to_string(), "macro-list", &content, true);
+        let count = self.active_window().macros.count();
+        self.set_status_message(t!("macro.showing", count = count).to_string());
+    }
+
+    // ---- Named macros ------------------------------------------------------
+
+    /// All named macros, sorted by name, with where each is saved and its
+    /// action count. A workspace macro hides a config macro of the same name.
+    fn named_macro_list(&self) -> Vec<(String, MacroScope, usize)> {
+        let workspace = self.active_window().macros.named_macros();
+        let mut list: Vec<(String, MacroScope, usize)> = workspace
+            .iter()
+            .map(|(name, actions)| (name.clone(), MacroScope::Workspace, actions.len()))
+            .collect();
+        list.extend(
+            self.config
+                .macros
+                .iter()
+                .filter(|(name, _)| !workspace.contains_key(*name))
+                .map(|(name, actions)| (name.clone(), MacroScope::Config, actions.len())),
+        );
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+        list
+    }
+
+    /// The named macro `name` and where it is saved, as JSON values so that
+    /// an invalid config entry can still be opened for editing.
+    fn named_macro_json(&self, name: &str) -> Option<(MacroScope, Vec<serde_json::Value>)> {
+        if let Some(actions) = self.active_window().macros.named(name) {
+            let values = actions
+                .iter()
+                .filter_map(|action| serde_json::to_value(action).ok())
+                .collect();
+            return Some((MacroScope::Workspace, values));
+        }
+        self.config
+            .macros
+            .get(name)
+            .map(|values| (MacroScope::Config, values.clone()))
+    }
+
+    /// Mirror the named macros into the command palette, one "Macro: name"
+    /// command each. Called whenever the set of named macros may change.
+    pub(crate) fn sync_named_macro_commands(&mut self) {
+        let registry = self.command_registry.read().unwrap();
+        for command in registry.get_all() {
+            if matches!(&command.action, Action::PlayNamedMacro(name) if !name.is_empty()) {
+                registry.unregister(&command.name);
+            }
+        }
+        for (name, _, count) in self.named_macro_list() {
+            registry.register(Command {
+                name: t!("cmd.named_macro", name = &name).to_string(),
+                description: t!("cmd.named_macro_desc", count = count).to_string(),
+                action: Action::PlayNamedMacro(name),
+                contexts: vec![KeyContext::Normal],
+                custom_contexts: Vec::new(),
+                source: CommandSource::Builtin,
+                terminal_bypass: false,
+            });
+        }
+    }
+
+    /// Prompt for a name to save the last recorded macro under.
+    pub(super) fn start_save_macro_prompt(&mut self, scope: MacroScope) {
+        if self.active_window().macros.last_register().is_none() {
+            self.set_status_message(t!("status.no_macro_recorded").to_string());
+            return;
+        }
+        let (label, workspace) = match scope {
+            MacroScope::Config => (t!("macro.save_prompt"), false),
+            MacroScope::Workspace => (t!("macro.save_to_workspace_prompt"), true),
+        };
+        self.start_prompt(label.to_string(), PromptType::SaveMacro { workspace });
+    }
+
+    /// Save the last recorded macro as `name`.
+    pub(crate) fn save_last_macro_as(&mut self, name: &str, scope: MacroScope) {
+        let name = name.trim();
+        if name.is_empty() {
+            self.set_status_message(t!("macro.name_empty").to_string());
+            return;
+        }
+        let macros = &self.active_window().macros;
+        let Some(actions) = macros
+            .last_register()
+            .and_then(|key| macros.get(key))
+            .map(<[_]>::to_vec)
+        else {
+            self.set_status_message(t!("status.no_macro_recorded").to_string());
+            return;
+        };
+        let count = actions.len();
+        let target = NamedMacroTarget {
+            name: name.to_string(),
+            scope,
+        };
+        match self.store_named_macro(&target, actions) {
+            Ok(()) => self.set_status_message(
+                t!("macro.saved_named", name = name, count = count).to_string(),
+            ),
+            Err(e) => self.set_status_message(t!("macro.save_failed", error = e).to_string()),
+        }
+    }
+
+    /// Save `actions` as the named macro `target`. Config macros are written
+    /// to the user config file straight away; workspace macros are written
+    /// with the workspace.
+    fn store_named_macro(
+        &mut self,
+        target: &NamedMacroTarget,
+        actions: Vec<Action>,
+    ) -> Result<(), String> {
+        match target.scope {
+            MacroScope::Workspace => {
+                self.active_window_mut()
+                    .macros
+                    .save_named(target.name.clone(), actions);
+            }
+            MacroScope::Config => {
+                let values = actions
+                    .iter()
+                    .map(serde_json::to_value)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| e.to_string())?;
+                self.config_mut().macros.insert(target.name.clone(), values);
+                self.sync_windows_config();
+
+                let value = serde_json::to_value(&self.config.macros).map_err(|e| e.to_string())?;
+                let changes = std::collections::HashMap::from([("/macros".to_string(), value)]);
+                ConfigResolver::new(self.dir_context.clone(), self.working_dir().to_path_buf())
+                    .save_changes_to_layer(
+                        &changes,
+                        &std::collections::HashSet::new(),
+                        ConfigLayer::User,
+                    )
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        self.sync_named_macro_commands();
+        Ok(())
+    }
+
+    /// Open a picker over the named macros; confirming runs `prompt_type`'s
+    /// handler with the chosen name.
+    fn start_named_macro_prompt(&mut self, label: String, prompt_type: PromptType) {
+        let list = self.named_macro_list();
+        if list.is_empty() {
+            self.set_status_message(t!("macro.no_named_macros").to_string());
+            return;
+        }
+        let suggestions: Vec<Suggestion> = list
+            .into_iter()
+            .map(|(name, scope, count)| {
+                let scope = match scope {
+                    MacroScope::Config => t!("macro.scope_config"),
+                    MacroScope::Workspace => t!("macro.scope_workspace"),
+                };
+                Suggestion {
+                    description_spans: None,
+                    text: name.clone(),
+                    description: Some(
+                        t!("macro.named_summary", count = count, scope = scope).to_string(),
+                    ),
+                    value: Some(name),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        self.active_window_mut().prompt =
+            Some(Prompt::with_suggestions(label, prompt_type, suggestions));
+        if let Some(prompt) = self.active_window_mut().prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Play the named macro `name`, or pick one when `name` is empty.
+    pub(super) fn play_named_macro(&mut self, name: &str) {
+        if name.is_empty() {
+            self.start_named_macro_prompt(
+                t!("macro.play_named_prompt").to_string(),
+                PromptType::PlayNamedMacro,
+            );
+            return;
+        }
+        if self.active_window().macros.is_playing() {
+            return;
+        }
+        let Some((_, values)) = self.named_macro_json(name) else {
+            self.set_status_message(t!("macro.named_not_found", name = name).to_string());
+            return;
+        };
+        let actions = match parse_macro_actions(values) {
+            Ok(actions) => actions,
+            Err(e) => {
+                self.set_status_message(
+                    t!("macro.named_invalid", name = name, error = e).to_string(),
+                );
+                return;
+            }
+        };
+        if actions.is_empty() {
+            self.set_status_message(t!("macro.empty", key = name).to_string());
+            return;
+        }
+        self.replay_macro(&actions, None);
+        self.set_status_message(
+            t!("macro.played_named", name = name, count = actions.len()).to_string(),
+        );
+    }
+
+    /// Pick a named macro to edit.
+    pub(super) fn start_edit_named_macro_prompt(&mut self) {
+        self.start_named_macro_prompt(
+            t!("macro.edit_prompt").to_string(),
+            PromptType::EditNamedMacro,
+        );
+    }
+
+    /// Open the named macro `name` as a JSON action list in a buffer. Saving
+    /// the buffer writes the list back to the macro.
+    pub(crate) fn edit_named_macro(&mut self, name: &str) {
+        let Some((scope, values)) = self.named_macro_json(name) else {
+            self.set_status_message(t!("macro.named_not_found", name = name).to_string());
+            return;
+        };
+        let json = match serde_json::to_string_pretty(&values) {
+            Ok(json) => json,
+            Err(e) => {
+                self.set_status_message(
+                    t!("macro.serialize_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        let buffer_id = self.open_macro_buffer(
+            format!("*Macro: {}*", name),
+            MACRO_EDIT_MODE,
+            &format!("{}\n", json),
+            false,
+        );
+        self.active_window_mut().macros.set_edit_target(
+            buffer_id,
+            NamedMacroTarget {
+                name: name.to_string(),
+                scope,
+            },
+        );
+        self.set_status_message(t!("macro.editing", name = name).to_string());
+    }
+
+    /// Save a buffer opened by [`Self::edit_named_macro`] back to its macro.
+    /// Returns `false` if the active buffer isn't a macro edit buffer.
+    pub(super) fn save_macro_edit_buffer(&mut self) -> bool {
+        let buffer_id = self.active_buffer();
+        let Some(target) = self.active_window().macros.edit_target(buffer_id).cloned() else {
+            return false;
+        };
+        let text = self.active_state().buffer.to_string().unwrap_or_default();
+        let actions = match serde_json::from_str(&text)
+            .map_err(|e| e.to_string())
+            .and_then(parse_macro_actions)
+        {
+            Ok(actions) => actions,
+            Err(e) => {
+                self.set_status_message(t!("macro.edit_invalid", error = e).to_string());
+                return true;
+            }
+        };
+        let count = actions.len();
+        match self.store_named_macro(&target, actions) {
+            Ok(()) => {
+                self.active_state_mut().buffer.clear_modified();
+                self.active_event_log_mut().mark_saved();
+                self.set_status_message(
+                    t!("macro.saved_named", name = &target.name, count = count).to_string(),
+                );
+            }
+            Err(e) => self.set_status_message(t!("macro.save_failed", error = e).to_string()),
+        }
+        true
+    }
+
+    /// Open `content` in a new virtual buffer and switch to it.
+    fn open_macro_buffer(
+        &mut self,
+        display_name: String,
+        mode: &str,
+        content: &str,
+        read_only: bool,
+    ) -> BufferId {
         let buffer_id = self.alloc_buffer_id();
 
         let mut state = EditorState::new(
@@ -293,6 +650,9 @@ impl Editor {
         state
             .margins
             .configure_for_line_numbers(self.config.editor.line_numbers);
+        if mode == MACRO_EDIT_MODE {
+            state.set_language_from_name("macro.json", &self.grammar_registry);
+        }
 
         self.windows
             .get_mut(&self.active_window)
@@ -313,18 +673,18 @@ impl Editor {
             .expect("active window present")
             .get_mut(&buffer_id)
         {
-            state.buffer = crate::model::buffer::Buffer::from_str(&content, threshold, fs);
+            state.buffer = crate::model::buffer::Buffer::from_str(content, threshold, fs);
         }
 
         // Set metadata
         let metadata = BufferMetadata {
             kind: BufferKind::Virtual {
-                mode: "macro-list".to_string(),
+                mode: mode.to_string(),
             },
-            display_name: "*Macros*".to_string(),
+            display_name,
             lsp_enabled: false,
-            lsp_disabled_reason: Some("Virtual macro list buffer".to_string()),
-            read_only: true,
+            lsp_disabled_reason: Some("Virtual macro buffer".to_string()),
+            read_only,
             binary: false,
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
@@ -338,7 +698,14 @@ impl Editor {
 
         // Switch to the new buffer
         self.set_active_buffer(buffer_id);
-        let count = self.active_window().macros.count();
-        self.set_status_message(t!("macro.showing", count = count).to_string());
+        buffer_id
     }
 }
+
+/// Parse a macro's JSON action list.
+fn parse_macro_actions(values: Vec<serde_json::Value>) -> Result<Vec<Action>, String> {
+    values
+        .into_iter()
+        .map(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+        .collect()
+}
@@ -3,16 +3,35 @@
 //! `MacroState` owns the four fields that used to live directly on `Editor`:
 //! the map of register-key to recorded actions, the in-flight recording
 //! buffer, the last register played (for "play last macro"), and the
-//! playing flag that suppresses recursive playback.
+//! playing flag that suppresses recursive playback. It also holds the
+//! named macros saved to the workspace and the buffers open for editing a
+//! named macro. Named macros saved to the config live in `Config::macros`.
 //!
 //! Nothing else in the codebase reaches into `MacroState`'s internals —
 //! every cross-subsystem effect (status messages, handle_action replay,
 //! buffer creation for `show_macro_in_buffer`) is handled by the
 //! orchestrator on `Editor` using the narrow public API below.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::input::keybindings::Action;
+use crate::model::event::BufferId;
+
+/// Where a named macro is saved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MacroScope {
+    /// `macros` in the user config — available in every project.
+    Config,
+    /// The workspace file — available in this project only.
+    Workspace,
+}
+
+/// The named macro a macro edit buffer writes back to on save.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NamedMacroTarget {
+    pub name: String,
+    pub scope: MacroScope,
+}
 
 /// State for a macro recording in progress.
 #[derive(Debug, Clone)]
@@ -34,6 +53,10 @@ pub(crate) struct MacroState {
     last_register: Option<char>,
     /// True while a macro is being replayed — suppresses recursive recording.
     playing: bool,
+    /// Named macros saved to the workspace, by name.
+    named: BTreeMap<String, Vec<Action>>,
+    /// Buffers open for editing a named macro as JSON.
+    edit_buffers: HashMap<BufferId, NamedMacroTarget>,
 }
 
 impl MacroState {
@@ -80,6 +103,44 @@ impl MacroState {
         keys
     }
 
+    /// Workspace macro saved as `name`, or `None` if there is none.
+    pub(crate) fn named(&self, name: &str) -> Option<&[Action]> {
+        self.named.get(name).map(Vec::as_slice)
+    }
+
+    /// All workspace macros, sorted by name.
+    pub(crate) fn named_macros(&self) -> &BTreeMap<String, Vec<Action>> {
+        &self.named
+    }
+
+    /// The named macro `buffer_id` is editing, if it is a macro edit buffer.
+    pub(crate) fn edit_target(&self, buffer_id: BufferId) -> Option<&NamedMacroTarget> {
+        self.edit_buffers.get(&buffer_id)
+    }
+
+    // ---- Named macros ------------------------------------------------------
+
+    /// Save `actions` to the workspace as `name`, replacing any macro of
+    /// that name.
+    pub(crate) fn save_named(&mut self, name: String, actions: Vec<Action>) {
+        self.named.insert(name, actions);
+    }
+
+    /// Replace the workspace macros (used when restoring a workspace).
+    pub(crate) fn set_named(&mut self, named: BTreeMap<String, Vec<Action>>) {
+        self.named = named;
+    }
+
+    /// Remember that `buffer_id` edits the named macro `target`.
+    pub(crate) fn set_edit_target(&mut self, buffer_id: BufferId, target: NamedMacroTarget) {
+        self.edit_buffers.insert(buffer_id, target);
+    }
+
+    /// Forget the macro edit buffer `buffer_id` once it is closed.
+    pub(crate) fn clear_edit_target(&mut self, buffer_id: BufferId) {
+        self.edit_buffers.remove(&buffer_id);
+    }
+
     // ---- Lifecycle ---------------------------------------------------------
 
     /// Begin recording into `key`. Any existing recording is discarded —
@@ -167,6 +228,12 @@ fn is_macro_control_action(action: &Action) -> bool {
             | Action::PromptRecordMacro
             | Action::PromptPlayMacro
             | Action::PlayLastMacro
+            | Action::SaveMacro
+            | Action::SaveMacroToWorkspace
+            | Action::PlayNamedMacro(_)
+            | Action::EditNamedMacro
+            | Action::ApplyMacroToLines
+            | Action::ApplyMacroToMatches
     )
 }
 
@@ -273,6 +340,31 @@ mod tests {
         assert_eq!(m.keys_sorted(), vec!['a', 'b', 'c']);
     }
 
+    #[test]
+    fn named_macros_are_kept_apart_from_registers() {
+        let mut m = MacroState::default();
+        m.save_named("wrap".to_string(), vec![Action::MoveLineStart]);
+        m.save_named("wrap".to_string(), vec![Action::MoveLineEnd]);
+        assert_eq!(m.named("wrap"), Some(&[Action::MoveLineEnd][..]));
+        assert_eq!(m.named("missing"), None);
+        assert_eq!(m.named_macros().len(), 1);
+        // Registers are untouched.
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn closed_edit_buffer_has_no_target() {
+        let mut m = MacroState::default();
+        let target = NamedMacroTarget {
+            name: "wrap".to_string(),
+            scope: MacroScope::Workspace,
+        };
+        m.set_edit_target(BufferId(3), target.clone());
+        assert_eq!(m.edit_target(BufferId(3)), Some(&target));
+        m.clear_edit_target(BufferId(3));
+        assert_eq!(m.edit_target(BufferId(3)), None);
+    }
+
     #[test]
     fn last_register_tracks_most_recent_recording() {
         let mut m = MacroState::default();
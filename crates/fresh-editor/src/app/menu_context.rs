@@ -102,6 +102,7 @@ impl Editor {
         let menu_bar = self.active_window_mut().menu_bar_visible;
         let vertical_scrollbar = self.config.editor.show_vertical_scrollbar;
         let horizontal_scrollbar = self.config.editor.show_horizontal_scrollbar;
+        let minimap = self.config.editor.show_minimap;
 
         // File explorer state
         let show_hidden = self.active_window().is_file_explorer_showing_hidden();
@@ -150,6 +151,7 @@ impl Editor {
             .set(context_keys::SESSION_MODE, session_mode)
             .set(context_keys::VERTICAL_SCROLLBAR, vertical_scrollbar)
             .set(context_keys::HORIZONTAL_SCROLLBAR, horizontal_scrollbar)
+            .set(context_keys::MINIMAP, minimap)
             .set(context_keys::SCROLL_SYNC, scroll_sync)
             .set(context_keys::HAS_SAME_BUFFER_SPLITS, has_same_buffer_splits);
     }
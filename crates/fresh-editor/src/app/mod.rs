@@ -12,10 +12,15 @@ mod calibration_actions;
 pub mod calibration_wizard;
 mod click_geometry;
 mod click_handlers;
+mod client_views;
 mod clipboard;
+mod code_lens;
 mod composite_buffer_actions;
 mod dabbrev_actions;
+mod debugger;
+mod debugger_actions;
 mod diagnostic_jumps;
+mod document_decorations;
 mod editor_accessors;
 mod editor_init;
 mod event_apply;
@@ -29,6 +34,7 @@ mod file_open_queue;
 mod file_operations;
 mod help;
 mod help_actions;
+mod hex_view_actions;
 mod hover;
 mod input;
 mod input_dispatch;
@@ -37,8 +43,11 @@ pub mod keybinding_editor;
 mod keybinding_editor_actions;
 mod lifecycle;
 mod line_scan;
+mod linked_editing;
+mod local_history;
 mod lsp_actions;
 mod lsp_event_notify;
+mod lsp_file_operations;
 mod lsp_requests;
 mod lsp_status;
 mod macro_actions;
@@ -48,9 +57,11 @@ mod menu_context;
 mod mouse_input;
 mod navigation;
 mod on_save_actions;
+mod on_type_formatting;
 mod orchestrator_persistence;
 mod overlay;
 mod path_utils;
+mod persistent_undo;
 #[cfg(feature = "plugins")]
 mod plugin_commands;
 #[cfg(feature = "plugins")]
@@ -62,6 +73,7 @@ mod prompt_actions;
 mod prompt_lifecycle;
 mod recovery_actions;
 mod regex_replace;
+mod remote_control;
 mod render;
 mod scan_orchestrators;
 mod scroll_sync;
@@ -73,9 +85,14 @@ mod settings_actions;
 mod settings_prompts;
 mod shell_command;
 mod smart_home;
+mod snippet_actions;
 mod split_actions;
 mod stdin_stream;
+mod sticky_scroll;
+mod structural_selection;
 mod tab_drag;
+mod task_actions;
+mod tasks;
 mod terminal;
 mod terminal_input;
 mod terminal_link;
@@ -93,6 +110,7 @@ pub mod window;
 mod window_actions;
 pub mod window_resources;
 pub mod workspace;
+mod workspace_edit_preview;
 
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -221,6 +239,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
 
+pub use self::client_views::ClientView;
 // Re-export BufferId from event module for backward compatibility
 pub use self::types::{BufferKind, BufferMetadata, HoverTarget};
 pub use self::warning_domains::{
@@ -291,6 +310,30 @@ pub(crate) struct InlayHintsRequest {
     pub(crate) version: u64,
 }
 
+#[derive(Clone, Debug)]
+pub(crate) struct CodeLensRequest {
+    pub(crate) buffer_id: BufferId,
+    pub(crate) version: u64,
+    /// Index of the lens being resolved (`codeLens/resolve`); `None` for
+    /// `textDocument/codeLens`.
+    pub(crate) resolve_index: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct DocumentLinkRequest {
+    pub(crate) buffer_id: BufferId,
+    pub(crate) version: u64,
+    /// Index of the link being resolved (`documentLink/resolve`); `None`
+    /// for `textDocument/documentLink`.
+    pub(crate) resolve_index: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct DocumentColorRequest {
+    pub(crate) buffer_id: BufferId,
+    pub(crate) version: u64,
+}
+
 /// State for the dabbrev cycling session (Alt+/ style).
 ///
 /// When the user presses Alt+/ repeatedly, we cycle through candidates
@@ -308,6 +351,20 @@ pub struct DabbrevCycleState {
     pub index: usize,
 }
 
+/// The most recent paste, which yank-pop can replace with another entry
+/// of the clipboard history (Emacs `M-y`).
+///
+/// Reset when any other action is taken, like [`DabbrevCycleState`].
+#[derive(Debug, Clone)]
+pub struct YankState {
+    /// Buffer the text was pasted into.
+    pub buffer_id: BufferId,
+    /// Index of the pasted entry in the clipboard history.
+    pub index: usize,
+    /// The pasted range of each cursor; the cursor sits at its end.
+    pub ranges: Vec<(crate::model::event::CursorId, std::ops::Range<usize>)>,
+}
+
 /// Snapshot of cursor and viewport state used to restore the original position
 /// when a goto-line preview is abandoned (cancel, or the user edits the input
 /// so it no longer targets a line).
@@ -870,6 +927,10 @@ pub struct Editor {
     /// Consumed by the outer event loop after the current action returns.
     suspend_requested: bool,
 
+    /// Which session daemon client input acts on, and the window each
+    /// other client was on (see `client_views`).
+    client_views: client_views::ClientViews,
+
     /// Time source for testable time operations
     time_source: SharedTimeSource,
 
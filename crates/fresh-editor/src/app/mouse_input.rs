@@ -1790,6 +1790,9 @@ impl Editor {
         if let Some(r) = self.handle_click_scrollbar(col, row) {
             return r;
         }
+        if let Some(r) = self.handle_click_minimap(col, row) {
+            return r;
+        }
         if let Some(r) = self.handle_click_horizontal_scrollbar(col, row) {
             return r;
         }
@@ -2334,6 +2337,25 @@ impl Editor {
         Some(Ok(()))
     }
 
+    fn handle_click_minimap(&mut self, col: u16, row: u16) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, minimap_rect, scale) = self
+            .active_layout()
+            .minimap_areas
+            .iter()
+            .find(|(_, _, minimap_rect, _)| in_rect(col, row, *minimap_rect))
+            .copied()?;
+
+        self.focus_split(split_id, buffer_id);
+        self.active_window_mut().mouse_state.dragging_minimap = Some(split_id);
+        Some(self.active_window_mut().handle_minimap_jump(
+            row,
+            split_id,
+            buffer_id,
+            minimap_rect,
+            scale,
+        ))
+    }
+
     fn handle_click_horizontal_scrollbar(
         &mut self,
         col: u16,
@@ -2852,6 +2874,25 @@ impl Editor {
             }
         }
 
+        // If dragging across the minimap, scroll to the row under the pointer
+        if let Some(dragging_split_id) = self.active_window().mouse_state.dragging_minimap {
+            let area = self
+                .active_layout()
+                .minimap_areas
+                .iter()
+                .find(|(split_id, ..)| *split_id == dragging_split_id)
+                .copied();
+            if let Some((split_id, buffer_id, minimap_rect, scale)) = area {
+                return self.active_window_mut().handle_minimap_jump(
+                    row,
+                    split_id,
+                    buffer_id,
+                    minimap_rect,
+                    scale,
+                );
+            }
+        }
+
         // If dragging horizontal scrollbar, update horizontal scroll position
         if let Some(dragging_split_id) = self
             .active_window_mut()
@@ -4294,6 +4335,7 @@ impl Editor {
         ms.dragging_horizontal_scrollbar = None;
         ms.drag_start_hcol = None;
         ms.drag_start_left_column = None;
+        ms.dragging_minimap = None;
         ms.dragging_separator = None;
         ms.drag_start_position = None;
         ms.drag_start_ratio = None;
@@ -463,6 +463,14 @@ impl Editor {
 
     /// Replace the active buffer's content with new output.
     fn replace_buffer_with_output(&mut self, output: &str) -> Result<(), String> {
+        self.replace_active_buffer_content(output, "On-save format");
+        Ok(())
+    }
+
+    /// Replace the whole content of the active buffer as a single undo
+    /// step named `description`, keeping the cursor where it was (clamped
+    /// to the new length). Does nothing if the content is unchanged.
+    pub(crate) fn replace_active_buffer_content(&mut self, output: &str, description: &str) {
         let cursor_id = self.active_cursors().primary_id();
 
         // Get current buffer content
@@ -470,7 +478,7 @@ impl Editor {
 
         // Only replace if content is different
         if buffer_content == output {
-            return Ok(());
+            return;
         }
 
         let buffer_len = buffer_content.len();
@@ -533,12 +541,10 @@ impl Editor {
         // Apply as a batch for atomic undo
         let batch = Event::Batch {
             events,
-            description: "On-save format".to_string(),
+            description: description.to_string(),
         };
         self.active_event_log_mut().append(batch.clone());
         self.apply_event_to_active_buffer(&batch);
-
-        Ok(())
     }
 
     /// Trim trailing whitespace from all lines in the active buffer.
@@ -0,0 +1,113 @@
+//! On-type formatting: after a character the language server lists as a
+//! trigger for `textDocument/onTypeFormatting` (gopls and clangd use `;`,
+//! `}` and newline, for instance) the server's edits are applied as one
+//! undo step.
+
+use crate::model::event::BufferId;
+use crate::types::LspFeature;
+
+use super::Editor;
+
+/// An in-flight `textDocument/onTypeFormatting` request.
+#[derive(Debug)]
+pub(crate) struct PendingOnTypeFormatting {
+    request_id: u64,
+    buffer_id: BufferId,
+    /// Buffer version the server formats against
+    version: u64,
+}
+
+impl Editor {
+    /// Ask the server to format after `ch` was typed at the primary cursor,
+    /// if `ch` is one of its trigger characters.
+    pub(super) fn maybe_request_on_type_formatting(&mut self, ch: char) {
+        if self.active_cursors().count() != 1 {
+            return;
+        }
+        let buffer_id = self.active_buffer();
+        let language = self.active_state().language.clone();
+        let enabled = self
+            .config
+            .languages
+            .get(&language)
+            .is_none_or(|l| l.on_type_formatting);
+        if !enabled
+            || !self
+                .lsp()
+                .is_some_and(|lsp| lsp.is_on_type_formatting_trigger_char(ch, &language))
+        {
+            return;
+        }
+
+        let position = self.active_cursors().primary().position;
+        let state = self.active_state();
+        let (line, character) = state.buffer.position_to_lsp_position(position);
+        let version = state.buffer.version();
+        let tab_size = state.buffer_settings.tab_size as u32;
+        let insert_spaces = !state.buffer_settings.use_tabs;
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::OnTypeFormatting,
+                |handle, uri, _language| match handle.on_type_formatting(
+                    request_id,
+                    uri.as_uri().clone(),
+                    line as u32,
+                    character as u32,
+                    ch.to_string(),
+                    tab_size,
+                    insert_spaces,
+                ) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request on-type formatting: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            win.pending_on_type_formatting = Some(PendingOnTypeFormatting {
+                request_id,
+                buffer_id,
+                version,
+            });
+        }
+    }
+
+    /// Handle a `textDocument/onTypeFormatting` response.
+    ///
+    /// The edits are positions in the text the server saw, so they are
+    /// dropped if anything was typed in the meantime rather than applied
+    /// to the wrong place; the next trigger character asks again.
+    pub(super) fn handle_on_type_formatting_response(
+        &mut self,
+        request_id: u64,
+        edits: Vec<lsp_types::TextEdit>,
+    ) {
+        let Some(pending) = self
+            .active_window_mut()
+            .pending_on_type_formatting
+            .take_if(|pending| pending.request_id == request_id)
+        else {
+            tracing::debug!("Ignoring stale on-type formatting response: {}", request_id);
+            return;
+        };
+        let version = self
+            .buffers()
+            .get(&pending.buffer_id)
+            .map(|state| state.buffer.version());
+        if version != Some(pending.version) {
+            tracing::debug!("Dropping on-type formatting for an edited buffer");
+            return;
+        }
+        if let Err(e) = self.apply_lsp_text_edits(pending.buffer_id, edits) {
+            tracing::warn!("Failed to apply on-type formatting: {}", e);
+        }
+    }
+}
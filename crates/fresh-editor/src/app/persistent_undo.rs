@@ -0,0 +1,76 @@
+//! Persistent undo (`editor.persistent_undo`).
+//!
+//! On save the buffer's undo history is written to the recovery directory;
+//! when the file is opened again and is unchanged on disk, the history is
+//! seeded into the new event log so undo reaches past the restart.
+
+use crate::model::event::BufferId;
+use crate::services::recovery::{content_hash, UndoHistoryStore};
+
+impl crate::app::window::Window {
+    fn undo_history_store(&self) -> UndoHistoryStore {
+        UndoHistoryStore::new(
+            &self.resources.dir_context.recovery_dir(),
+            self.authority().filesystem.remote_connection_info(),
+        )
+    }
+
+    /// Fingerprint of the buffer's file on disk, if it has one.
+    fn saved_content_hash(&self, buffer_id: BufferId) -> Option<(std::path::PathBuf, String)> {
+        let path = self
+            .buffers
+            .get(&buffer_id)?
+            .buffer
+            .file_path()?
+            .to_path_buf();
+        match content_hash(
+            self.authority().filesystem.as_ref(),
+            &path,
+            self.resources.config.editor.large_file_threshold_bytes,
+        ) {
+            Ok(hash) => Some((path, hash)),
+            Err(e) => {
+                tracing::debug!("Can't fingerprint {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Write the undo history of a buffer that was just saved.
+    pub(crate) fn persist_undo_history(&self, buffer_id: BufferId) {
+        if !self.resources.config.editor.persistent_undo {
+            return;
+        }
+        let Some(steps) = self
+            .event_logs
+            .get(&buffer_id)
+            .map(|log| log.persistable_history())
+        else {
+            return;
+        };
+        let Some((path, hash)) = self.saved_content_hash(buffer_id) else {
+            return;
+        };
+        if let Err(e) = self.undo_history_store().save(&path, &hash, steps) {
+            tracing::warn!("Failed to save undo history for {}: {}", path.display(), e);
+        }
+    }
+
+    /// Seed the event log of a freshly opened buffer with the history saved
+    /// for its file, if the file hasn't changed since.
+    pub(crate) fn restore_undo_history(&mut self, buffer_id: BufferId) {
+        if !self.resources.config.editor.persistent_undo {
+            return;
+        }
+        let Some((path, hash)) = self.saved_content_hash(buffer_id) else {
+            return;
+        };
+        let Some(steps) = self.undo_history_store().load(&path, &hash) else {
+            return;
+        };
+        tracing::debug!("Restored {} undo steps for {}", steps.len(), path.display());
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.seed_history(steps);
+        }
+    }
+}
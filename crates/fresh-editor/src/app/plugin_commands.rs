@@ -1635,6 +1635,95 @@ impl Editor {
         }
     }
 
+    /// Handle SetRulers command
+    ///
+    /// Like line numbers, rulers live on the buffer's per-split view state in
+    /// the active split.
+    pub(super) fn handle_set_rulers(&mut self, buffer_id: BufferId, columns: Vec<usize>) {
+        let active_split = self
+            .windows
+            .get(&self.active_window)
+            .and_then(|w| w.buffers.splits())
+            .map(|(mgr, _)| mgr)
+            .expect("active window must have a populated split layout")
+            .active_split();
+        if let Some(view_state) = self
+            .windows
+            .get_mut(&self.active_window)
+            .and_then(|w| w.split_view_states_mut())
+            .expect("active window must have a populated split layout")
+            .get_mut(&active_split)
+        {
+            if let Some(buf_state) = view_state.buffer_state_mut(buffer_id) {
+                buf_state.rulers = columns;
+            } else {
+                view_state.rulers = columns;
+            }
+        }
+    }
+
+    /// Handle SetDiagnostics command
+    ///
+    /// Plugin diagnostics are stored next to the push diagnostics of LSP
+    /// servers, keyed `plugin:<source>`, so they are merged, rendered and
+    /// listed exactly like LSP diagnostics.
+    pub(super) fn handle_set_diagnostics(
+        &mut self,
+        buffer_id: BufferId,
+        source: String,
+        diagnostics: Vec<fresh_core::api::PluginDiagnostic>,
+    ) {
+        let Some(uri) = self
+            .active_window()
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_uri())
+            .map(|u| u.as_str().to_string())
+        else {
+            tracing::warn!(
+                "setDiagnostics: buffer {:?} is not backed by a file",
+                buffer_id
+            );
+            return;
+        };
+
+        let position =
+            |p: &fresh_core::api::JsPosition| lsp_types::Position::new(p.line, p.character);
+        let diagnostics: Vec<lsp_types::Diagnostic> = diagnostics
+            .into_iter()
+            .map(|d| lsp_types::Diagnostic {
+                range: lsp_types::Range::new(position(&d.range.start), position(&d.range.end)),
+                severity: Some(match d.severity {
+                    Some(2) => lsp_types::DiagnosticSeverity::WARNING,
+                    Some(3) => lsp_types::DiagnosticSeverity::INFORMATION,
+                    Some(4) => lsp_types::DiagnosticSeverity::HINT,
+                    _ => lsp_types::DiagnosticSeverity::ERROR,
+                }),
+                source: Some(source.clone()),
+                message: d.message,
+                ..Default::default()
+            })
+            .collect();
+
+        let key = format!("plugin:{source}");
+        let window = self.active_window_mut();
+        if diagnostics.is_empty() {
+            if let Some(server_map) = window.stored_push_diagnostics.get_mut(&uri) {
+                server_map.remove(&key);
+                if server_map.is_empty() {
+                    window.stored_push_diagnostics.remove(&uri);
+                }
+            }
+        } else {
+            window
+                .stored_push_diagnostics
+                .entry(uri.clone())
+                .or_default()
+                .insert(key, diagnostics);
+        }
+        self.merge_and_apply_diagnostics(&uri);
+    }
+
     /// Handle SetLineWrap command
     pub(super) fn handle_set_line_wrap(
         &mut self,
@@ -2169,6 +2258,24 @@ impl Editor {
         }
     }
 
+    // ==================== Debugger Commands ====================
+
+    /// Handle DebugRequest command
+    pub(super) fn handle_plugin_debug_request(
+        &mut self,
+        command: String,
+        arguments: Option<serde_json::Value>,
+        request_id: u64,
+    ) {
+        tracing::debug!("Plugin debug request {}: {}", request_id, command);
+        if let Err(err_msg) = self.debug_plugin_request(&command, arguments, request_id) {
+            self.plugin_manager
+                .read()
+                .unwrap()
+                .reject_callback(fresh_core::api::JsCallbackId::from(request_id), err_msg);
+        }
+    }
+
     // ==================== Clipboard Commands ====================
 
     /// Handle SetClipboard command
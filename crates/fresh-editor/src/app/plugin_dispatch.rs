@@ -535,6 +535,16 @@ impl Editor {
             PluginCommand::SetLineNumbers { buffer_id, enabled } => {
                 self.handle_set_line_numbers(buffer_id, enabled);
             }
+            PluginCommand::SetRulers { buffer_id, columns } => {
+                self.handle_set_rulers(buffer_id, columns);
+            }
+            PluginCommand::SetDiagnostics {
+                buffer_id,
+                source,
+                diagnostics,
+            } => {
+                self.handle_set_diagnostics(buffer_id, source, diagnostics);
+            }
             PluginCommand::SetViewMode { buffer_id, mode } => {
                 self.handle_set_view_mode(buffer_id, &mode);
             }
@@ -906,6 +916,18 @@ impl Editor {
                 self.handle_send_lsp_request(language, method, params, request_id);
             }
 
+            // ==================== Debugger Commands ====================
+            PluginCommand::DebugRequest {
+                command,
+                arguments,
+                request_id,
+            } => {
+                self.handle_plugin_debug_request(command, arguments, request_id);
+            }
+            PluginCommand::DebugStart { configuration } => {
+                self.debug_start_with(configuration);
+            }
+
             // ==================== Clipboard Commands ====================
             PluginCommand::SetClipboard { text } => {
                 self.handle_set_clipboard(text);
@@ -1147,6 +1169,87 @@ impl Editor {
             } => {
                 self.handle_get_buffer_line_count(buffer_id, request_id);
             }
+            PluginCommand::GetSyntaxSymbols {
+                buffer_id,
+                request_id,
+            } => {
+                self.handle_get_syntax_symbols(buffer_id, request_id);
+            }
+            PluginCommand::GetUndoTree {
+                buffer_id,
+                request_id,
+            } => {
+                self.handle_get_undo_tree(buffer_id, request_id);
+            }
+            PluginCommand::GetUndoStateDiff {
+                buffer_id,
+                node_id,
+                request_id,
+            } => {
+                self.handle_get_undo_state_diff(buffer_id, node_id, request_id);
+            }
+            PluginCommand::DiffLines {
+                old_text,
+                new_text,
+                request_id,
+            } => {
+                self.handle_diff_lines(&old_text, &new_text, request_id);
+            }
+            PluginCommand::JumpToUndoState { buffer_id, node_id } => {
+                if self.resolve_buffer_id(buffer_id) == self.active_buffer() {
+                    self.jump_to_undo_state(node_id);
+                } else {
+                    tracing::warn!("JumpToUndoState: buffer {:?} is not active", buffer_id);
+                }
+            }
+            PluginCommand::GetFileHistory { path, request_id } => {
+                let versions: Vec<_> = self
+                    .active_window()
+                    .local_history_store()
+                    .list(&path)
+                    .into_iter()
+                    .map(|entry| {
+                        serde_json::json!({
+                            "id": entry.id,
+                            "timestamp": entry.id,
+                            "size": entry.size,
+                        })
+                    })
+                    .collect();
+                self.resolve_json_callback(request_id, versions);
+            }
+            PluginCommand::ReadFileHistoryVersion {
+                path,
+                version_id,
+                request_id,
+            } => {
+                let text = self
+                    .active_window()
+                    .local_history_store()
+                    .read(&path, version_id)
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok());
+                self.resolve_json_callback(request_id, text);
+            }
+            PluginCommand::RestoreFileHistoryVersion {
+                buffer_id,
+                version_id,
+            } => {
+                if self.resolve_buffer_id(buffer_id) == self.active_buffer() {
+                    self.restore_local_history_version(version_id);
+                } else {
+                    tracing::warn!(
+                        "RestoreFileHistoryVersion: buffer {:?} is not active",
+                        buffer_id
+                    );
+                }
+            }
+            PluginCommand::ApplyWorkspaceEditPreview { id, excluded } => {
+                self.apply_workspace_edit_preview(id, &excluded);
+            }
+            PluginCommand::CancelWorkspaceEditPreview { id } => {
+                self.cancel_workspace_edit_preview(id);
+            }
             PluginCommand::GetCompositeCursorInfo { request_id } => {
                 self.handle_get_composite_cursor_info(request_id);
             }
@@ -1497,7 +1600,9 @@ impl Editor {
 
     fn handle_watch_path(&mut self, path: std::path::PathBuf, recursive: bool, request_id: u64) {
         let result = if let Some(ref bridge) = self.async_bridge {
-            self.file_watcher_manager.watch(bridge, &path, recursive)
+            let fs = self.authority().filesystem.clone();
+            self.file_watcher_manager
+                .watch(bridge, fs.as_ref(), &path, recursive)
         } else {
             Err(
                 "watchPath: no async bridge — file watching is unavailable in this build"
@@ -2204,6 +2309,87 @@ impl Editor {
         self.resolve_json_callback(request_id, result);
     }
 
+    /// Resolve a buffer's symbols from its syntax (see
+    /// [`document_symbols`](crate::primitives::document_symbols)). An unknown
+    /// buffer resolves with an empty list.
+    fn handle_get_syntax_symbols(&mut self, buffer_id: BufferId, request_id: u64) {
+        let buffer_id = self.resolve_buffer_id(buffer_id);
+        let symbols = self
+            .buffers()
+            .get(&buffer_id)
+            .map(|state| {
+                crate::primitives::document_symbols::document_symbols(
+                    &state.buffer,
+                    state.highlighter.language(),
+                    &state.language,
+                )
+            })
+            .unwrap_or_default();
+        self.resolve_json_callback(request_id, symbols);
+    }
+
+    /// Resolve a buffer's undo tree, or `null` for an unknown buffer.
+    fn handle_get_undo_tree(&mut self, buffer_id: BufferId, request_id: u64) {
+        let buffer_id = self.resolve_buffer_id(buffer_id);
+        let tree = self
+            .active_window()
+            .event_logs
+            .get(&buffer_id)
+            .map(|log| log.undo_tree());
+        self.resolve_json_callback(request_id, tree);
+    }
+
+    /// Resolve the diff from a buffer's content to a state of its undo
+    /// tree, or `null` (see [`Self::undo_state_diff`]).
+    fn handle_get_undo_state_diff(&mut self, buffer_id: BufferId, node_id: u64, request_id: u64) {
+        let buffer_id = self.resolve_buffer_id(buffer_id);
+        let diff = self.undo_state_diff(buffer_id, node_id);
+        self.resolve_json_callback(request_id, diff);
+    }
+
+    /// Resolve the hunks of a line diff between two plugin-supplied texts.
+    fn handle_diff_lines(&mut self, old_text: &str, new_text: &str, request_id: u64) {
+        let hunks: Vec<fresh_core::api::CompositeHunk> =
+            crate::model::line_diff::diff_hunks(old_text, new_text)
+                .into_iter()
+                .map(|hunk| fresh_core::api::CompositeHunk {
+                    old_start: hunk.old_start,
+                    old_count: hunk.old_count,
+                    new_start: hunk.new_start,
+                    new_count: hunk.new_count,
+                    ops: Some(hunk.ops),
+                })
+                .collect();
+        self.resolve_json_callback(request_id, hunks);
+    }
+
+    /// Diff from a buffer's content to state `node_id` of its undo tree,
+    /// computed on a copy of the text without touching the buffer. `None`
+    /// for an unknown state or a buffer that isn't fully loaded.
+    fn undo_state_diff(&self, buffer_id: BufferId, node_id: u64) -> Option<Vec<String>> {
+        /// Unchanged lines shown around each change.
+        const DIFF_CONTEXT_LINES: usize = 3;
+
+        let jump = self
+            .active_window()
+            .event_logs
+            .get(&buffer_id)?
+            .preview_jump(node_id)?;
+        let current = self.buffers().get(&buffer_id)?.buffer.to_string()?;
+        let mut target = current.clone();
+        let events = jump.undo.iter().map(|(event, _)| event).chain(&jump.redo);
+        for event in events {
+            if !event.apply_to_text(&mut target) {
+                return None;
+            }
+        }
+        Some(crate::model::line_diff::unified_diff(
+            &current,
+            &target,
+            DIFF_CONTEXT_LINES,
+        ))
+    }
+
     /// Resolve cursor info for the active composite (side-by-side diff)
     /// buffer. Returns `null` to the plugin when the active buffer isn't a
     /// composite buffer; otherwise an object with the focused pane index,
@@ -2856,7 +3042,7 @@ impl Editor {
     }
 
     #[allow(clippy::too_many_arguments)]
-    fn handle_create_virtual_buffer_in_split(
+    pub(super) fn handle_create_virtual_buffer_in_split(
         &mut self,
         name: String,
         mode: String,
@@ -4180,6 +4366,25 @@ impl Editor {
                     );
                 }
             }
+            WidgetMutation::SetSelectedIndex { widget_key, index }
+                if self.widget_registry.get(panel_key).is_some_and(|panel| {
+                    matches!(
+                        panel.instance_states.get(&widget_key),
+                        Some(crate::widgets::WidgetInstanceState::Tree { .. })
+                    )
+                }) =>
+            {
+                // Tree selected_index is the absolute node index; the
+                // renderer clamps it to a visible node and scrolls it into
+                // view.
+                if let Some(crate::widgets::WidgetInstanceState::Tree { selected_index, .. }) = self
+                    .widget_registry
+                    .get_mut(panel_key)
+                    .and_then(|panel| panel.instance_states.get_mut(&widget_key))
+                {
+                    *selected_index = index;
+                }
+            }
             WidgetMutation::SetSelectedIndex { widget_key, index } => {
                 // List selected_index lives in instance state.
                 if let Some(panel) = self.widget_registry.get_mut(panel_key) {
@@ -4,7 +4,7 @@
 
 use super::Editor;
 use crate::model::event::Event;
-use crate::primitives::snippet::{expand_snippet, is_snippet};
+use crate::primitives::snippet::is_snippet;
 use crate::primitives::word_navigation::find_completion_word_start;
 use rust_i18n::t;
 
@@ -149,16 +149,48 @@ impl Editor {
                     .top()
                     .and_then(|p| p.selected_item())
                     .map(|item| (item.text.clone(), item.data.clone()));
+                // Hide first: an inserted snippet may open its own choice
+                // popup, which must stay up.
+                self.hide_popup();
                 if let Some((label, insert_text)) = completion_info {
                     if let Some(text) = insert_text {
                         self.insert_completion_text(text);
                     }
                     self.apply_completion_additional_edits(&label);
                 }
-                self.hide_popup();
                 PopupConfirmResult::Done
             }
 
+            Some(PopupResolver::ColorPresentation) => {
+                let selected_index = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.as_ref())
+                    .and_then(|data| data.parse::<usize>().ok());
+                self.hide_popup();
+                match selected_index {
+                    Some(index) => self.apply_color_presentation(index),
+                    None => self.cancel_color_presentation(),
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::SnippetChoice) => {
+                let choice = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                self.hide_popup();
+                if let Some(choice) = choice {
+                    self.choose_snippet_option(choice);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
             Some(PopupResolver::None) | None => {
                 self.hide_popup();
                 PopupConfirmResult::Done
@@ -167,7 +199,8 @@ impl Editor {
     }
 
     /// Insert completion text, replacing the word prefix at *every* cursor.
-    /// If the text contains LSP snippet syntax, it will be expanded.
+    /// If the text contains LSP snippet syntax, it will be expanded; with a
+    /// single cursor, a snippet with tabstops starts a snippet session.
     ///
     /// Multi-cursor: each cursor's own word prefix is replaced, so cursors
     /// stay in lock-step after the accept (issue #1901, accept path). All
@@ -176,12 +209,13 @@ impl Editor {
     fn insert_completion_text(&mut self, text: String) {
         use crate::model::event::CursorId;
 
+        self.end_snippet_session();
+
         // Check if this is a snippet and expand it
-        let (insert_text, cursor_offset) = if is_snippet(&text) {
-            let expanded = expand_snippet(&text);
-            (expanded.text, Some(expanded.cursor_offset))
-        } else {
-            (text, None)
+        let snippet = is_snippet(&text).then(|| self.expand_snippet_at_cursor(&text));
+        let (insert_text, cursor_offset) = match &snippet {
+            Some(expanded) => (expanded.text.clone(), Some(expanded.cursor_offset)),
+            None => (text, None),
         };
 
         // Collect per-cursor data: id, current position, word_start, prefix text.
@@ -242,6 +276,12 @@ impl Editor {
             }
         }
 
+        if let (Some(expanded), [(_, _, word_start, _)]) = (&snippet, cursor_data.as_slice()) {
+            if self.start_snippet_session(expanded, *word_start) {
+                return;
+            }
+        }
+
         // Snippet placement: after the bulk edit, each cursor sits at the end
         // of its own inserted text; the snippet's $0 sits `cursor_offset` bytes
         // into that text. Walk each cursor back to its $0 placeholder.
@@ -363,6 +403,15 @@ impl Editor {
                 self.hide_popup();
             }
 
+            Some(PopupResolver::SnippetChoice) => {
+                self.hide_popup();
+            }
+
+            Some(PopupResolver::ColorPresentation) => {
+                self.cancel_color_presentation();
+                self.hide_popup();
+            }
+
             Some(PopupResolver::WorkspaceTrust) => {
                 // The trust prompt is a forced choice: there is no "undecided"
                 // outcome, so Escape does nothing. The user must pick Trust /
@@ -4,6 +4,7 @@
 
 use rust_i18n::t;
 
+use super::macros::MacroScope;
 use super::normalize_path;
 use super::BufferId;
 use super::BufferMetadata;
@@ -262,6 +263,37 @@ impl Editor {
             PromptType::PlayMacro => {
                 self.handle_register_input(&input, |editor, c| editor.play_macro(c), "Macro");
             }
+            PromptType::SaveMacro { workspace } => {
+                let scope = if workspace {
+                    MacroScope::Workspace
+                } else {
+                    MacroScope::Config
+                };
+                self.save_last_macro_as(&input, scope);
+            }
+            PromptType::GotoOffset => {
+                self.goto_offset(&input);
+            }
+            PromptType::SearchBytes => {
+                if !input.trim().is_empty() {
+                    self.search_bytes(&input);
+                }
+            }
+            PromptType::PlayNamedMacro => {
+                let name = input.trim();
+                if !name.is_empty() {
+                    self.play_named_macro(name);
+                }
+            }
+            PromptType::EditNamedMacro => {
+                self.edit_named_macro(input.trim());
+            }
+            PromptType::RunTask => {
+                let name = input.trim();
+                if !name.is_empty() {
+                    self.run_task(name);
+                }
+            }
             PromptType::SetBookmark => {
                 self.handle_register_input(
                     &input,
@@ -433,7 +465,7 @@ impl Editor {
             PromptType::ConfirmDeleteFile { path, is_dir } => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "y" || input_lower == "yes" {
-                    self.perform_file_explorer_delete(path, is_dir);
+                    self.delete_explorer_paths(vec![(path, is_dir)]);
                 } else {
                     self.set_status_message(t!("explorer.delete_cancelled").to_string());
                 }
@@ -507,14 +539,25 @@ impl Editor {
             PromptType::ConfirmMultiDelete { paths } => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "y" || input_lower == "yes" {
-                    for path in paths {
-                        let is_dir = self.authority().filesystem.is_dir(&path).unwrap_or(false);
-                        self.perform_file_explorer_delete(path, is_dir);
-                    }
+                    let paths = paths
+                        .into_iter()
+                        .map(|path| {
+                            let is_dir = self.authority().filesystem.is_dir(&path).unwrap_or(false);
+                            (path, is_dir)
+                        })
+                        .collect();
+                    self.delete_explorer_paths(paths);
                 } else {
                     self.set_status_message(t!("explorer.delete_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmFileOperationEdits => {
+                // Enter alone applies the edits; declining still performs
+                // the operation, just without touching other files.
+                let input_lower = input.trim().to_lowercase();
+                let apply = !(input_lower == "n" || input_lower == "no");
+                self.finish_file_operation(apply);
+            }
             PromptType::ConfirmMultiPasteConflict {
                 safe,
                 confirmed,
@@ -628,6 +671,11 @@ impl Editor {
                     self.switch_to_tab(BufferId(id));
                 }
             }
+            PromptType::PasteFromHistory => {
+                if let Ok(index) = input.trim().parse::<usize>() {
+                    self.paste_from_history(index);
+                }
+            }
             PromptType::QueryReplaceConfirm => {
                 // This is handled by InsertChar, not PromptConfirm
                 // But if somehow Enter is pressed, treat it as skip (n)
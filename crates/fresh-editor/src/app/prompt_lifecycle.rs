@@ -734,6 +734,9 @@ impl Editor {
                             });
                     }
                 }
+                PromptType::ConfirmFileOperationEdits => {
+                    self.cancel_file_operation();
+                }
                 PromptType::LspRename { overlay_handle, .. } => {
                     // Remove the rename overlay when cancelling
                     let remove_overlay_event = crate::model::event::Event::RemoveOverlay {
@@ -911,6 +914,10 @@ impl Editor {
                     | PromptType::SelectTheme { .. }
                     | PromptType::SelectLocale
                     | PromptType::SwitchToTab
+                    | PromptType::PasteFromHistory
+                    | PromptType::PlayNamedMacro
+                    | PromptType::EditNamedMacro
+                    | PromptType::RunTask
                     | PromptType::SetLanguage
                     | PromptType::SetEncoding
                     | PromptType::SetLineEnding
@@ -1058,6 +1065,8 @@ impl Editor {
                 Some("replace".to_string())
             }
             PromptType::GotoLine => Some("goto_line".to_string()),
+            PromptType::GotoOffset => Some("goto_offset".to_string()),
+            PromptType::SearchBytes => Some("search_bytes".to_string()),
             PromptType::Plugin { custom_type } => Some(format!("plugin:{}", custom_type)),
             _ => None,
         }
@@ -1213,6 +1222,10 @@ impl Editor {
                 }
             }
             PromptType::SwitchToTab
+            | PromptType::PasteFromHistory
+            | PromptType::PlayNamedMacro
+            | PromptType::EditNamedMacro
+            | PromptType::RunTask
             | PromptType::SelectTheme { .. }
             | PromptType::StopLspServer
             | PromptType::RestartLspServer
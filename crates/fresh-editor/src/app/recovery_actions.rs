@@ -155,9 +155,10 @@ impl Editor {
                                     // Mark as modified since it differs from disk
                                     state.buffer.set_modified(true);
                                 }
-                                // Invalidate the event log's saved position so undo
-                                // can't incorrectly clear the modified flag
-                                self.active_event_log_mut().clear_saved_position();
+                                // The recorded history doesn't apply to the replaced
+                                // content, and dropping the saved position stops undo
+                                // from incorrectly clearing the modified flag
+                                self.active_event_log_mut().discard_history();
                                 // Recovery replay mutates the buffer directly —
                                 // push the new content to LSP so tokens and
                                 // positions don't drift against an on-disk base.
@@ -216,9 +217,10 @@ impl Editor {
                             // Mark as modified since it differs from disk
                             state.buffer.set_modified(true);
                         }
-                        // Invalidate the event log's saved position so undo
-                        // can't incorrectly clear the modified flag
-                        self.active_event_log_mut().clear_saved_position();
+                        // The recorded history doesn't apply to the patched
+                        // content, and dropping the saved position stops undo
+                        // from incorrectly clearing the modified flag
+                        self.active_event_log_mut().discard_history();
                         self.sync_lsp_after_recovery_replay(buffer_id);
                         recovered_count += 1;
                         tracing::info!("Recovered buffer with chunks: {}", original_path.display());
@@ -312,7 +314,7 @@ impl Editor {
                                     state.buffer.set_modified(true);
                                     state.buffer.set_recovery_pending(false);
                                 }
-                                self.active_event_log_mut().clear_saved_position();
+                                self.active_event_log_mut().discard_history();
                                 self.sync_lsp_after_recovery_replay(buffer_id);
                                 restored += 1;
                                 tracing::info!(
@@ -378,7 +380,7 @@ impl Editor {
                             state.buffer.set_modified(true);
                             state.buffer.set_recovery_pending(false);
                         }
-                        self.active_event_log_mut().clear_saved_position();
+                        self.active_event_log_mut().discard_history();
                         self.sync_lsp_after_recovery_replay(buffer_id);
                         restored += 1;
                         tracing::info!(
@@ -0,0 +1,273 @@
+//! Editor side of the remote-control API (`fresh --remote`).
+//!
+//! Scripts and external tools send JSON-RPC requests over the control
+//! socket; the server (see `server::remote`) keeps their hook
+//! subscriptions and hands every other request to
+//! [`Editor::handle_remote_request`]. Method names and result shapes follow
+//! the plugin API. Requests act on the active window and, in the session
+//! daemon, on the view of the client that last typed.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::Editor;
+use crate::input::keybindings::Action;
+use crate::model::event::BufferId;
+use crate::server::protocol::{RemoteError, RemoteRequest, RemoteResponse};
+
+/// Params of `executeAction`
+#[derive(Deserialize)]
+struct ActionParams {
+    /// Action name as used in keybindings, e.g. `"save"`
+    name: String,
+    #[serde(default)]
+    args: HashMap<String, Value>,
+}
+
+/// Params of `executeCommand`
+#[derive(Deserialize)]
+struct CommandParams {
+    /// Command palette name
+    name: String,
+}
+
+/// Params of the methods that act on a buffer: the active buffer unless
+/// one is named
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BufferParams {
+    buffer_id: Option<usize>,
+    /// Open file path, relative to the working directory
+    path: Option<PathBuf>,
+}
+
+/// Params of `insertAtCursor`
+#[derive(Deserialize)]
+struct InsertParams {
+    text: String,
+}
+
+fn internal_error(e: anyhow::Error) -> RemoteError {
+    RemoteError::new(RemoteError::INTERNAL_ERROR, e.to_string())
+}
+
+impl Editor {
+    /// Answer a remote-control request.
+    pub fn handle_remote_request(&mut self, request: &RemoteRequest) -> RemoteResponse {
+        let result = match request.method.as_str() {
+            "executeAction" => request
+                .parse_params()
+                .and_then(|p| self.remote_execute_action(p)),
+            "executeCommand" => request
+                .parse_params()
+                .and_then(|p| self.remote_execute_command(p)),
+            "listBuffers" => Ok(self.remote_list_buffers()),
+            "getBufferText" => request
+                .parse_params()
+                .and_then(|p| self.remote_buffer_text(p)),
+            "getAllCursors" => Ok(self.remote_cursors()),
+            "getAllDiagnostics" => request
+                .parse_params()
+                .and_then(|p| self.remote_diagnostics(p)),
+            "insertAtCursor" => request.parse_params().map(|p| self.remote_insert(p)),
+            method => Err(RemoteError::new(
+                RemoteError::METHOD_NOT_FOUND,
+                format!("unknown method: {}", method),
+            )),
+        };
+        RemoteResponse::new(request.id.clone(), result)
+    }
+
+    /// Record the hooks remote clients subscribed to as they fire.
+    pub fn set_remote_hook_subscriptions(&self, hooks: HashSet<String>) {
+        self.plugin_manager().set_tapped_hooks(hooks);
+    }
+
+    /// Subscribed hooks fired since the last call, as `(name, args)`.
+    pub fn take_remote_hook_events(&self) -> Vec<(String, Value)> {
+        self.plugin_manager().take_tapped_hooks()
+    }
+
+    fn remote_execute_action(&mut self, params: ActionParams) -> Result<Value, RemoteError> {
+        // Unknown names are plugin actions, as in keybindings
+        let action = Action::from_str(&params.name, &params.args).ok_or_else(|| {
+            RemoteError::new(
+                RemoteError::INVALID_PARAMS,
+                format!("invalid arguments for action {}", params.name),
+            )
+        })?;
+        self.handle_action(action).map_err(internal_error)?;
+        Ok(json!(true))
+    }
+
+    fn remote_execute_command(&mut self, params: CommandParams) -> Result<Value, RemoteError> {
+        let command = self
+            .command_registry()
+            .read()
+            .unwrap()
+            .get_all()
+            .into_iter()
+            .find(|c| c.name == params.name || c.get_localized_name() == params.name)
+            .ok_or_else(|| {
+                RemoteError::new(
+                    RemoteError::INVALID_PARAMS,
+                    format!("unknown command: {}", params.name),
+                )
+            })?;
+        self.handle_action(command.action).map_err(internal_error)?;
+        Ok(json!(true))
+    }
+
+    fn remote_list_buffers(&self) -> Value {
+        let active = self.active_buffer();
+        let window = self.active_window();
+        let mut buffers: Vec<_> = window.buffers.iter().collect();
+        buffers.sort_by_key(|(id, _)| id.0);
+        buffers
+            .into_iter()
+            .map(|(id, state)| {
+                json!({
+                    "id": id.0,
+                    "path": state.buffer.file_path(),
+                    "modified": state.buffer.is_modified(),
+                    "length": state.buffer.len(),
+                    "language": state.language,
+                    "active": *id == active,
+                })
+            })
+            .collect()
+    }
+
+    /// The buffer `params` names, or the active one.
+    fn remote_buffer(&self, params: &BufferParams) -> Result<BufferId, RemoteError> {
+        let buffers = &self.active_window().buffers;
+        if let Some(id) = params.buffer_id {
+            let id = BufferId(id);
+            return if buffers.contains_key(&id) {
+                Ok(id)
+            } else {
+                Err(RemoteError::new(
+                    RemoteError::INVALID_PARAMS,
+                    format!("no buffer {}", id.0),
+                ))
+            };
+        }
+        let Some(path) = &params.path else {
+            return Ok(self.active_buffer());
+        };
+        let path = self.working_dir().join(path);
+        buffers
+            .iter()
+            .find(|(_, state)| state.buffer.file_path() == Some(path.as_path()))
+            .map(|(id, _)| *id)
+            .ok_or_else(|| {
+                RemoteError::new(
+                    RemoteError::INVALID_PARAMS,
+                    format!("{} is not open", path.display()),
+                )
+            })
+    }
+
+    fn remote_buffer_text(&self, params: BufferParams) -> Result<Value, RemoteError> {
+        let id = self.remote_buffer(&params)?;
+        let state = self
+            .active_window()
+            .buffers
+            .get(&id)
+            .expect("remote_buffer returns an open buffer");
+        let text = state.buffer.to_string().ok_or_else(|| {
+            RemoteError::new(
+                RemoteError::INTERNAL_ERROR,
+                "buffer is not fully loaded (large file mode)",
+            )
+        })?;
+        Ok(Value::String(text))
+    }
+
+    /// Cursors of the active buffer, primary first. Lines and columns are
+    /// 0-based; columns count bytes.
+    fn remote_cursors(&self) -> Value {
+        let buffer = &self.active_state().buffer;
+        let cursors = self.active_cursors();
+        let primary = cursors.primary_id();
+        let mut all: Vec<_> = cursors.iter().collect();
+        all.sort_by_key(|(id, cursor)| (*id != primary, cursor.position));
+        all.into_iter()
+            .map(|(_, cursor)| {
+                let (line, column) = buffer.position_to_line_col(cursor.position);
+                json!({
+                    "position": cursor.position,
+                    "line": line,
+                    "column": column,
+                    "selection": cursor
+                        .selection_range()
+                        .map(|range| json!({ "start": range.start, "end": range.end })),
+                })
+            })
+            .collect()
+    }
+
+    /// LSP diagnostics of every file, or of the buffer `params` names, in
+    /// the plugin API's `JsDiagnostic` shape.
+    fn remote_diagnostics(&self, params: BufferParams) -> Result<Value, RemoteError> {
+        use fresh_core::api::{JsDiagnostic, JsPosition, JsRange};
+
+        let uri = if params.buffer_id.is_some() || params.path.is_some() {
+            let id = self.remote_buffer(&params)?;
+            let uri = self
+                .active_window()
+                .buffer_metadata
+                .get(&id)
+                .and_then(|m| m.file_uri())
+                .map(|uri| uri.as_str().to_string());
+            // A buffer without a file has no diagnostics
+            let Some(uri) = uri else {
+                return Ok(json!([]));
+            };
+            Some(uri)
+        } else {
+            None
+        };
+
+        let mut diagnostics = Vec::new();
+        for (diag_uri, diags) in self.active_window().stored_diagnostics.iter() {
+            if uri.as_ref().is_some_and(|uri| uri != diag_uri) {
+                continue;
+            }
+            for diag in diags {
+                diagnostics.push(JsDiagnostic {
+                    uri: diag_uri.clone(),
+                    message: diag.message.clone(),
+                    severity: diag.severity.map(|s| match s {
+                        lsp_types::DiagnosticSeverity::ERROR => 1,
+                        lsp_types::DiagnosticSeverity::WARNING => 2,
+                        lsp_types::DiagnosticSeverity::INFORMATION => 3,
+                        lsp_types::DiagnosticSeverity::HINT => 4,
+                        _ => 0,
+                    }),
+                    range: JsRange {
+                        start: JsPosition {
+                            line: diag.range.start.line,
+                            character: diag.range.start.character,
+                        },
+                        end: JsPosition {
+                            line: diag.range.end.line,
+                            character: diag.range.end.character,
+                        },
+                    },
+                    source: diag.source.clone(),
+                });
+            }
+        }
+        serde_json::to_value(diagnostics)
+            .map_err(|e| RemoteError::new(RemoteError::INTERNAL_ERROR, e.to_string()))
+    }
+
+    fn remote_insert(&mut self, params: InsertParams) -> Value {
+        self.handle_insert_at_cursor(params.text);
+        json!(true)
+    }
+}
@@ -93,6 +93,7 @@ impl Editor {
         // correct content area dimensions. Don't sync here with incorrect EditorState viewport size.
 
         self.request_semantic_ranges_for_visible_splits();
+        self.maybe_request_linked_editing_ranges();
 
         self.prepare_visible_buffers_for_render();
 
@@ -671,6 +672,7 @@ impl Editor {
         let __composite_view_states_mut = &mut __win.composite_view_states;
         let __cell_theme_map_mut = &mut __win.chrome_layout.cell_theme_map;
         let __tab_bar_visible = __win.tab_bar_visible;
+        let __search_ns_ref = &__win.search_namespace;
         let (
             split_areas,
             tab_layouts,
@@ -678,6 +680,7 @@ impl Editor {
             maximize_split_areas,
             view_line_mappings,
             horizontal_scrollbar_areas,
+            minimap_areas,
             grouped_separator_areas,
         ) = __win
             .buffers
@@ -714,9 +717,12 @@ impl Editor {
                     self.software_cursor_only,
                     self.config.editor.show_vertical_scrollbar,
                     self.config.editor.show_horizontal_scrollbar,
+                    self.config.editor.show_minimap,
+                    __search_ns_ref,
                     self.config.editor.diagnostics_inline_text,
                     self.config.editor.show_tilde,
                     self.config.editor.highlight_current_column,
+                    self.config.editor.sticky_scroll,
                     self.config.editor.hide_current_line_on_selection,
                     __cell_theme_map_mut,
                     size.width,
@@ -857,6 +863,7 @@ impl Editor {
 
         self.active_layout_mut().split_areas = split_areas;
         self.active_layout_mut().horizontal_scrollbar_areas = horizontal_scrollbar_areas;
+        self.active_layout_mut().minimap_areas = minimap_areas;
         self.active_layout_mut().tab_layouts = tab_layouts;
         self.active_layout_mut().close_split_areas = close_split_areas;
         self.active_layout_mut().maximize_split_areas = maximize_split_areas;
@@ -1884,6 +1891,8 @@ impl Editor {
             self.maybe_request_semantic_tokens_range(buffer_id, start_line, end_line);
             self.maybe_request_semantic_tokens_full_debounced(buffer_id);
             self.maybe_request_folding_ranges_debounced(buffer_id);
+            self.maybe_request_code_lens_debounced(buffer_id);
+            self.maybe_request_document_decorations_debounced(buffer_id);
         }
     }
 
@@ -2309,6 +2318,7 @@ impl Editor {
         // rendering the split's underlying pre-group buffer.
         let __preview_grouped_subtrees = &__win_for_preview.grouped_subtrees;
         let preview_tab_bar_visible = __win_for_preview.tab_bar_visible;
+        let __preview_search_ns = &__win_for_preview.search_namespace;
 
         // Per-call scratch — keeps the preview pass from
         // clobbering the active editor area's hit-testing /
@@ -2362,9 +2372,12 @@ impl Editor {
                     // active session's chrome is the source of truth.
                     false,
                     false,
+                    false,
+                    __preview_search_ns,
                     self.config.editor.diagnostics_inline_text,
                     false, // hide tilde markers in the preview
                     self.config.editor.highlight_current_column,
+                    self.config.editor.sticky_scroll,
                     self.config.editor.hide_current_line_on_selection,
                     &mut scratch_cell_theme_map,
                     inner.width,
@@ -3987,6 +4000,7 @@ impl Editor {
                     tab_bar_visible,
                     self.config.editor.show_vertical_scrollbar,
                     self.config.editor.show_horizontal_scrollbar,
+                    self.config.editor.show_minimap,
                     self.config.editor.diagnostics_inline_text,
                     self.config.editor.show_tilde,
                 )
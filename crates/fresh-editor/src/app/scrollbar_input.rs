@@ -1,14 +1,15 @@
 //! Mouse-driven scrollbar input on `Editor`.
 //!
 //! Mouse-wheel scrolling, horizontal panning, and the click/drag handlers
-//! for both the regular vertical scrollbar and the composite-buffer
-//! scrollbar (used in unified diff views). Pure scrollbar math lives in
+//! for the regular vertical scrollbar, the composite-buffer scrollbar (used
+//! in unified diff views) and the minimap. Pure scrollbar math lives in
 //! `super::scrollbar_math`; these methods do the side-effecting work of
 //! mutating viewports and split state.
 
 use anyhow::Result as AnyhowResult;
 
 use crate::model::event::{BufferId, LeafId};
+use crate::view::minimap::{MinimapScale, MinimapTarget};
 
 impl crate::app::window::Window {
     /// Handle mouse wheel scroll event
@@ -101,6 +102,19 @@ impl crate::app::window::Window {
             return Ok(());
         }
 
+        // Hex views scroll by rows of bytes, leaving the cursor where it is
+        let total_bytes = self.buffers.get(&buffer_id).map(|s| s.buffer.len());
+        if let Some(hex) = self
+            .buffers
+            .split_view_states_mut()
+            .and_then(|vs| vs.get_mut(&target_split))
+            .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+            .and_then(|bs| bs.hex_view.as_mut())
+        {
+            hex.scroll(delta as isize, total_bytes.unwrap_or(0));
+            return Ok(());
+        }
+
         // Get view_transform tokens from SplitViewState (if any)
         let view_transform_tokens = self
             .buffers
@@ -577,6 +591,63 @@ impl crate::app::window::Window {
         Ok(())
     }
 
+    /// Scroll a split so the part of the document under minimap row `row`
+    /// is centered on screen. Used for both clicks and drags; rows outside
+    /// the minimap clamp to its first or last row.
+    pub(super) fn handle_minimap_jump(
+        &mut self,
+        row: u16,
+        split_id: LeafId,
+        buffer_id: BufferId,
+        minimap_rect: ratatui::layout::Rect,
+        scale: MinimapScale,
+    ) -> AnyhowResult<()> {
+        let relative_row = row
+            .saturating_sub(minimap_rect.y)
+            .min(minimap_rect.height.saturating_sub(1)) as usize;
+
+        let viewport_height = self
+            .buffers
+            .splits()
+            .map(|(_, vs)| vs)
+            .expect("active window must have a populated split layout")
+            .get(&split_id)
+            .map(|vs| vs.viewport.height as usize)
+            .unwrap_or(10);
+
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return Ok(());
+        };
+        let top_byte = match scale.target(relative_row) {
+            MinimapTarget::Line(line) => {
+                let top_line = line.saturating_sub(viewport_height / 2);
+                let line_start = state.buffer.line_start_offset(top_line).unwrap_or(0);
+                let max_top_byte =
+                    Self::calculate_max_scroll_position(&mut state.buffer, viewport_height);
+                line_start.min(max_top_byte)
+            }
+            MinimapTarget::Byte(byte) => {
+                // Large files: no line index, so land on the line holding
+                // the byte rather than centering on it.
+                let byte = byte.min(state.buffer.len().saturating_sub(1));
+                state.buffer.line_iterator(byte, 80).current_position()
+            }
+        };
+
+        if let Some(view_state) = self
+            .split_view_states_mut()
+            .expect("active window must have a populated split layout")
+            .get_mut(&split_id)
+        {
+            view_state.viewport.top_byte = top_byte;
+            view_state.viewport.top_view_line_offset = 0;
+            view_state.viewport.set_skip_ensure_visible();
+        }
+        self.move_cursor_to_visible_area(split_id, buffer_id);
+
+        Ok(())
+    }
+
     /// Handle scrollbar jump (click on track) for composite buffers.
     /// Maps the click ratio to a row-based scroll position.
     fn handle_composite_scrollbar_jump(
@@ -0,0 +1,442 @@
+//! Snippet sessions.
+//!
+//! Accepting a completion whose text is a snippet (an LSP item or one of the
+//! user's snippet files) expands it at the cursor and, when it has tabstops,
+//! starts a [`SnippetSession`] on the buffer. Tab and Shift+Tab then select
+//! the next and previous tabstop, with a cursor on every mirror so they are
+//! edited together. Leaving a tabstop rewrites its transformed mirrors, and
+//! choice tabstops offer their options in a popup.
+//!
+//! The session ends on reaching `$0`, on Escape, when another snippet is
+//! accepted, or as soon as the cursor moves outside the snippet.
+
+use std::ops::Range;
+use std::path::PathBuf;
+
+use rust_i18n::t;
+
+use crate::model::event::{CursorId, Event};
+use crate::model::snippet_session::SnippetSession;
+use crate::primitives::snippet::{expand_snippet_with, ExpandedSnippet};
+use crate::primitives::word_navigation::{find_word_end, find_word_start};
+use crate::view::folding::indent_folding::find_line_start_byte;
+
+use super::Editor;
+
+/// Bytes of the cursor line read for `TM_CURRENT_LINE` and the indent.
+const MAX_LINE_SCAN: usize = 4096;
+
+/// Values of the snippet variables at the cursor.
+struct SnippetVariables {
+    file_path: Option<PathBuf>,
+    working_dir: PathBuf,
+    line_index: usize,
+    current_line: String,
+    current_word: String,
+    selected_text: String,
+    clipboard: Option<String>,
+}
+
+impl SnippetVariables {
+    fn resolve(&self, name: &str) -> Option<String> {
+        let file_name = |path: &PathBuf| path.file_name().map(|n| n.to_string_lossy().into_owned());
+        let now = chrono::Local::now();
+        let value = match name {
+            "TM_SELECTED_TEXT" => self.selected_text.clone(),
+            "TM_CURRENT_LINE" => self.current_line.clone(),
+            "TM_CURRENT_WORD" => self.current_word.clone(),
+            "TM_LINE_INDEX" => self.line_index.to_string(),
+            "TM_LINE_NUMBER" => (self.line_index + 1).to_string(),
+            "TM_FILENAME" => file_name(self.file_path.as_ref()?)?,
+            "TM_FILENAME_BASE" => self
+                .file_path
+                .as_ref()?
+                .file_stem()?
+                .to_string_lossy()
+                .into_owned(),
+            "TM_DIRECTORY" => self
+                .file_path
+                .as_ref()?
+                .parent()?
+                .to_string_lossy()
+                .into_owned(),
+            "TM_FILEPATH" => self.file_path.as_ref()?.to_string_lossy().into_owned(),
+            "RELATIVE_FILEPATH" => {
+                let path = self.file_path.as_ref()?;
+                path.strip_prefix(&self.working_dir)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .into_owned()
+            }
+            "WORKSPACE_NAME" => file_name(&self.working_dir)?,
+            "WORKSPACE_FOLDER" => self.working_dir.to_string_lossy().into_owned(),
+            "CLIPBOARD" => self.clipboard.clone()?,
+            "CURRENT_YEAR" => now.format("%Y").to_string(),
+            "CURRENT_YEAR_SHORT" => now.format("%y").to_string(),
+            "CURRENT_MONTH" => now.format("%m").to_string(),
+            "CURRENT_MONTH_NAME" => now.format("%B").to_string(),
+            "CURRENT_MONTH_NAME_SHORT" => now.format("%b").to_string(),
+            "CURRENT_DATE" => now.format("%d").to_string(),
+            "CURRENT_DAY_NAME" => now.format("%A").to_string(),
+            "CURRENT_DAY_NAME_SHORT" => now.format("%a").to_string(),
+            "CURRENT_HOUR" => now.format("%H").to_string(),
+            "CURRENT_MINUTE" => now.format("%M").to_string(),
+            "CURRENT_SECOND" => now.format("%S").to_string(),
+            "CURRENT_SECONDS_UNIX" => now.timestamp().to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl Editor {
+    /// Expand `text` for insertion at the primary cursor: variables take
+    /// their values from the buffer and cursor, and every continuation line
+    /// gets the indentation of the cursor line.
+    pub(super) fn expand_snippet_at_cursor(&mut self, text: &str) -> ExpandedSnippet {
+        let cursor = *self.active_cursors().primary();
+        // Only touch the system clipboard when the snippet asks for it.
+        let clipboard = if text.contains("CLIPBOARD") {
+            self.clipboard.paste()
+        } else {
+            None
+        };
+        let working_dir = self.working_dir().to_path_buf();
+
+        let state = self.active_state_mut();
+        let pos = cursor.position;
+        let line_start = find_line_start_byte(&state.buffer, pos);
+        let scan_end = state.buffer.len().min(line_start + MAX_LINE_SCAN);
+        let line_bytes = state.buffer.slice_bytes(line_start..scan_end);
+        let line_len = line_bytes
+            .iter()
+            .position(|&b| b == b'\n' || b == b'\r')
+            .unwrap_or(line_bytes.len());
+        let current_line = String::from_utf8_lossy(&line_bytes[..line_len]).into_owned();
+        let indent_len = current_line
+            .bytes()
+            .take_while(|&b| b == b' ' || b == b'\t')
+            .count()
+            .min(pos - line_start);
+        let indent = current_line[..indent_len].to_string();
+
+        let word = find_word_start(&state.buffer, pos)..find_word_end(&state.buffer, pos);
+        let current_word = if word.start < word.end {
+            state.get_text_range(word.start, word.end)
+        } else {
+            String::new()
+        };
+        let selected_text = match cursor.selection_range() {
+            Some(range) => state.get_text_range(range.start, range.end),
+            None => String::new(),
+        };
+
+        let variables = SnippetVariables {
+            file_path: state.buffer.file_path().map(|p| p.to_path_buf()),
+            working_dir,
+            line_index: state.buffer.get_line_number(pos),
+            current_line,
+            current_word,
+            selected_text,
+            clipboard,
+        };
+        expand_snippet_with(text, &|name| variables.resolve(name), &indent)
+    }
+
+    /// Whether Tab and Shift+Tab should step through a snippet. Ends the
+    /// session when the cursor has moved outside of it.
+    pub(super) fn snippet_session_active(&mut self) -> bool {
+        let pos = self.active_cursors().primary().position;
+        let state = self.active_state();
+        let Some(session) = &state.snippet_session else {
+            return false;
+        };
+        if session.contains(pos, &state.marker_list) {
+            return true;
+        }
+        self.end_snippet_session();
+        false
+    }
+
+    /// Drop the active buffer's snippet session, if any.
+    pub(super) fn end_snippet_session(&mut self) {
+        let state = self.active_state_mut();
+        if let Some(session) = state.snippet_session.take() {
+            session.release(&mut state.marker_list);
+        }
+    }
+
+    /// Start a session for `snippet`, just inserted at byte `base`, and
+    /// select its first tabstop. Returns `false` when the snippet has no
+    /// tabstops to step through.
+    pub(super) fn start_snippet_session(&mut self, snippet: &ExpandedSnippet, base: usize) -> bool {
+        let state = self.active_state_mut();
+        let Some(session) = SnippetSession::start(snippet, base, &mut state.marker_list) else {
+            return false;
+        };
+        state.snippet_session = Some(session);
+        self.select_snippet_tabstop(0);
+        self.show_snippet_choices(0);
+        true
+    }
+
+    /// Move to the next tabstop, ending the session once `$0` is reached.
+    pub(super) fn snippet_next_tabstop(&mut self) {
+        let Some((current, len)) = self
+            .active_state()
+            .snippet_session
+            .as_ref()
+            .map(|s| (s.current(), s.len()))
+        else {
+            return;
+        };
+        self.apply_snippet_transforms(current);
+        let next = current + 1;
+        self.select_snippet_tabstop(next);
+        if next + 1 >= len {
+            self.end_snippet_session();
+        } else {
+            self.show_snippet_choices(next);
+        }
+    }
+
+    /// Move back to the previous tabstop. Stays on the first one.
+    pub(super) fn snippet_prev_tabstop(&mut self) {
+        let Some(current) = self
+            .active_state()
+            .snippet_session
+            .as_ref()
+            .map(|s| s.current())
+        else {
+            return;
+        };
+        if current == 0 {
+            return;
+        }
+        self.apply_snippet_transforms(current);
+        self.select_snippet_tabstop(current - 1);
+        self.show_snippet_choices(current - 1);
+    }
+
+    /// Make tabstop `index` current and select its placeholder, with one
+    /// more cursor on each mirror.
+    fn select_snippet_tabstop(&mut self, index: usize) {
+        let ranges = {
+            let state = self.active_state_mut();
+            let Some(session) = state.snippet_session.as_mut() else {
+                return;
+            };
+            session.set_current(index, &mut state.marker_list);
+            session.ranges(index, &state.marker_list)
+        };
+        let Some(first) = ranges.first() else {
+            return;
+        };
+
+        let cursors = self.active_cursors();
+        let primary_id = cursors.primary_id();
+        let primary = *cursors.primary();
+        let anchor = |r: &Range<usize>| (r.start < r.end).then_some(r.start);
+        let mut events = vec![Event::MoveCursor {
+            cursor_id: primary_id,
+            old_position: primary.position,
+            new_position: first.end,
+            old_anchor: primary.anchor,
+            new_anchor: anchor(first),
+            old_sticky_column: primary.sticky_column,
+            new_sticky_column: 0,
+        }];
+        for (cursor_id, cursor) in cursors.iter() {
+            if cursor_id != primary_id {
+                events.push(Event::RemoveCursor {
+                    cursor_id,
+                    position: cursor.position,
+                    anchor: cursor.anchor,
+                });
+            }
+        }
+        let next_free_id = cursors.iter().map(|(id, _)| id.0).max().unwrap_or(0) + 1;
+        for (i, mirror) in ranges.iter().skip(1).enumerate() {
+            events.push(Event::AddCursor {
+                cursor_id: CursorId(next_free_id + i),
+                position: mirror.end,
+                anchor: anchor(mirror),
+            });
+        }
+
+        let batch = Event::Batch {
+            events,
+            description: "Snippet tabstop".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+    }
+
+    /// Rewrite the transformed mirrors of tabstop `index` from the text now
+    /// in its placeholder.
+    fn apply_snippet_transforms(&mut self, index: usize) {
+        let (source, transformed) = {
+            let state = self.active_state();
+            let Some(session) = &state.snippet_session else {
+                return;
+            };
+            let transformed = session.transformed(index, &state.marker_list);
+            let Some(source) = session.ranges(index, &state.marker_list).first().cloned() else {
+                return;
+            };
+            (source, transformed)
+        };
+        if transformed.is_empty() {
+            return;
+        }
+
+        let text = self
+            .active_state_mut()
+            .get_text_range(source.start, source.end);
+        let cursor_id = self.active_cursors().primary_id();
+        let mut replacements: Vec<(Range<usize>, String, String)> = transformed
+            .iter()
+            .map(|(range, transform)| {
+                let old = self
+                    .active_state_mut()
+                    .get_text_range(range.start, range.end);
+                (range.clone(), old, transform.apply(&text))
+            })
+            .collect();
+
+        let mut events = Vec::new();
+        for (range, old, new) in &replacements {
+            if old == new {
+                continue;
+            }
+            if !range.is_empty() {
+                events.push(Event::Delete {
+                    range: range.clone(),
+                    deleted_text: old.clone(),
+                    cursor_id,
+                });
+            }
+            if !new.is_empty() {
+                events.push(Event::Insert {
+                    position: range.start,
+                    text: new.clone(),
+                    cursor_id,
+                });
+            }
+        }
+        if events.is_empty() {
+            return;
+        }
+        if let Some(bulk_edit) =
+            self.apply_events_as_bulk_edit(events, "Snippet transform".to_string())
+        {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+
+        // Markers can't tell where a replaced range ends, so work out the
+        // new ranges from the lengths: each one shifts by the growth of the
+        // replacements before it.
+        let mut order: Vec<usize> = (0..replacements.len()).collect();
+        order.sort_by_key(|&i| replacements[i].0.start);
+        let mut shift: isize = 0;
+        for i in order {
+            let (range, old, new) = &mut replacements[i];
+            let start = (range.start as isize + shift) as usize;
+            shift += new.len() as isize - old.len() as isize;
+            *range = start..start + new.len();
+        }
+        let state = self.active_state_mut();
+        if let Some(session) = state.snippet_session.as_mut() {
+            session.set_transformed_ranges(
+                index,
+                replacements
+                    .into_iter()
+                    .map(|(range, _, _)| range)
+                    .collect(),
+                &mut state.marker_list,
+            );
+        }
+    }
+
+    /// Offer the options of a choice tabstop in a popup.
+    fn show_snippet_choices(&mut self, index: usize) {
+        use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+        use ratatui::style::Style;
+
+        let choices = match &self.active_state().snippet_session {
+            Some(session) => session.choices(index).to_vec(),
+            None => return,
+        };
+        if choices.is_empty() {
+            return;
+        }
+        let items = choices
+            .into_iter()
+            .map(|choice| PopupListItem {
+                text: choice.clone(),
+                detail: None,
+                icon: None,
+                data: Some(choice),
+                disabled: false,
+            })
+            .collect();
+
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.title = Some(t!("snippet.popup_choices").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 40;
+        popup.max_height = 10;
+        popup.border_style = Style::default().fg(theme.popup_border_fg);
+        popup.background_style = Style::default().bg(theme.popup_bg);
+        popup.resolver = PopupResolver::SnippetChoice;
+        popup.focused = true;
+        drop(theme);
+
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+
+    /// Replace the current choice tabstop, and its mirrors, with `choice`.
+    pub(super) fn choose_snippet_option(&mut self, choice: String) {
+        let (index, ranges) = {
+            let state = self.active_state();
+            let Some(session) = &state.snippet_session else {
+                return;
+            };
+            let index = session.current();
+            (index, session.ranges(index, &state.marker_list))
+        };
+        let cursor_id = self.active_cursors().primary_id();
+        let mut events = Vec::new();
+        for range in ranges {
+            let old = self
+                .active_state_mut()
+                .get_text_range(range.start, range.end);
+            if old == choice {
+                continue;
+            }
+            if !range.is_empty() {
+                events.push(Event::Delete {
+                    range: range.clone(),
+                    deleted_text: old,
+                    cursor_id,
+                });
+            }
+            events.push(Event::Insert {
+                position: range.start,
+                text: choice.clone(),
+                cursor_id,
+            });
+        }
+        if events.is_empty() {
+            return;
+        }
+        if let Some(bulk_edit) =
+            self.apply_events_as_bulk_edit(events, "Snippet choice".to_string())
+        {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+        self.select_snippet_tabstop(index);
+    }
+}
@@ -0,0 +1,65 @@
+//! Sticky scroll interaction.
+//!
+//! The renderer pins the headers of the scopes enclosing the top of a split
+//! over its first rows and records them on the viewport
+//! ([`Viewport::sticky_lines`](crate::view::viewport::Viewport::sticky_lines)).
+//! Clicking one of those rows jumps to that header.
+
+use ratatui::layout::Rect;
+
+use crate::model::event::LeafId;
+
+use super::navigation::JumpOptions;
+use super::Editor;
+
+/// Bytes scanned for the first non-blank character of a header line.
+const MAX_INDENT_SCAN: usize = 1024;
+
+impl Editor {
+    /// Jump to the scope header pinned on the clicked row. Returns `true`
+    /// when the click landed on a sticky row and was consumed.
+    pub(super) fn click_sticky_scroll(
+        &mut self,
+        row: u16,
+        split_id: LeafId,
+        content_rect: Rect,
+    ) -> bool {
+        let Some((_, view_states)) = self
+            .windows
+            .get(&self.active_window)
+            .and_then(|w| w.buffers.splits())
+        else {
+            return false;
+        };
+        let Some(view_state) = view_states.get(&split_id) else {
+            return false;
+        };
+        let rect = super::click_geometry::adjust_content_rect_for_compose(
+            content_rect,
+            view_state.compose_width,
+        );
+        let Some(visual_row) = row.checked_sub(rect.y) else {
+            return false;
+        };
+        let Some(&header) = view_state.viewport.sticky_lines.get(visual_row as usize) else {
+            return false;
+        };
+
+        let buffer = &self.active_state().buffer;
+        let end = buffer.len().min(header.saturating_add(MAX_INDENT_SCAN));
+        let indent = buffer
+            .slice_bytes(header..end)
+            .iter()
+            .take_while(|&&b| b == b' ' || b == b'\t')
+            .count();
+
+        self.active_window_mut().jump_active_cursor_to(
+            header + indent,
+            JumpOptions {
+                clear_anchor: true,
+                recenter_on_scroll: false,
+            },
+        );
+        true
+    }
+}
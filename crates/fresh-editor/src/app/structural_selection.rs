@@ -0,0 +1,362 @@
+//! Structural expand/shrink selection.
+//!
+//! Each expand step grows every cursor's selection to the next range that
+//! strictly encloses it. Ranges come from `textDocument/selectionRange` when
+//! the buffer's server provides it (one request per expansion sequence, for
+//! all cursors at once), and from
+//! [`syntax_selection`](crate::primitives::syntax_selection) otherwise —
+//! tree-sitter nodes, then brackets and indentation.
+//!
+//! Every step is remembered per cursor so shrink can walk back to the exact
+//! selection it started from. The sequence is dropped as soon as the buffer
+//! is edited, another buffer becomes active, or a cursor's selection no
+//! longer matches the one the last step left behind.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::model::buffer::Buffer;
+use crate::model::cursor::Cursor;
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::primitives::syntax_selection;
+use crate::types::LspFeature;
+
+use super::Editor;
+
+/// The running expand/shrink sequence of the active buffer.
+#[derive(Debug)]
+pub(crate) struct SelectionExpansion {
+    buffer_id: BufferId,
+    version: u64,
+    cursors: HashMap<CursorId, CursorExpansion>,
+    pending: Option<PendingSelectionRanges>,
+}
+
+/// An in-flight `textDocument/selectionRange` request.
+#[derive(Debug)]
+struct PendingSelectionRanges {
+    request_id: u64,
+    /// Cursors in the order their positions were sent
+    cursors: Vec<CursorId>,
+    /// Whether the response should perform the expand step that sent it.
+    /// Cleared when the user moves on before the server answers.
+    apply: bool,
+}
+
+#[derive(Debug)]
+struct CursorExpansion {
+    /// `(anchor, position)` before each expand step, innermost last
+    history: Vec<(Option<usize>, usize)>,
+    /// The selection the last step left behind
+    current: Range<usize>,
+    /// Chain reported by the server; `None` until it answered
+    lsp_ranges: Option<Vec<Range<usize>>>,
+}
+
+impl CursorExpansion {
+    fn new(current: Range<usize>) -> Self {
+        Self {
+            history: Vec::new(),
+            current,
+            lsp_ranges: None,
+        }
+    }
+}
+
+/// The selected range of `cursor`, or an empty range at its position.
+fn selection_of(cursor: &Cursor) -> Range<usize> {
+    cursor
+        .selection_range()
+        .unwrap_or(cursor.position..cursor.position)
+}
+
+/// Byte ranges of a server-reported chain, innermost first.
+fn lsp_chain(buffer: &Buffer, range: &lsp_types::SelectionRange) -> Vec<Range<usize>> {
+    let to_byte = |pos: lsp_types::Position| {
+        buffer.lsp_position_to_byte(pos.line as usize, pos.character as usize)
+    };
+    let mut chain = Vec::new();
+    let mut next = Some(range);
+    while let Some(r) = next {
+        let start = to_byte(r.range.start);
+        let end = to_byte(r.range.end);
+        if start <= end {
+            chain.push(start..end);
+        }
+        next = r.parent.as_deref();
+    }
+    chain
+}
+
+impl Editor {
+    /// Grow every cursor's selection to the next enclosing syntax range.
+    pub(crate) fn expand_structural_selection(&mut self) {
+        self.sync_selection_expansion();
+
+        let Some(expansion) = self.active_window_mut().selection_expansion.as_mut() else {
+            return;
+        };
+        if let Some(pending) = expansion.pending.as_mut() {
+            // Don't queue behind a slow server: step with the local ranges
+            // now and keep its answer for the steps after this one.
+            pending.apply = false;
+        } else if expansion.cursors.values().any(|c| c.lsp_ranges.is_none())
+            && self.request_selection_ranges()
+        {
+            return;
+        }
+        self.step_structural_selection();
+    }
+
+    /// Undo the last expand step of every cursor.
+    pub(crate) fn shrink_structural_selection(&mut self) {
+        self.sync_selection_expansion();
+
+        let cursors: Vec<(CursorId, Cursor)> = self
+            .active_cursors()
+            .iter()
+            .map(|(id, c)| (id, *c))
+            .collect();
+        let Some(expansion) = self.active_window_mut().selection_expansion.as_mut() else {
+            return;
+        };
+        if let Some(pending) = expansion.pending.as_mut() {
+            pending.apply = false;
+        }
+
+        let mut events = Vec::new();
+        for (cursor_id, cursor) in cursors {
+            let Some(entry) = expansion.cursors.get_mut(&cursor_id) else {
+                continue;
+            };
+            let Some((anchor, position)) = entry.history.pop() else {
+                continue;
+            };
+            entry.current = match anchor {
+                Some(anchor) => anchor.min(position)..anchor.max(position),
+                None => position..position,
+            };
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: cursor.position,
+                new_position: position,
+                old_anchor: cursor.anchor,
+                new_anchor: anchor,
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: cursor.sticky_column,
+            });
+        }
+        self.apply_selection_moves(events, "Shrink selection");
+    }
+
+    /// Handle a `textDocument/selectionRange` response: store each cursor's
+    /// chain and, unless the user already moved on, take the expand step
+    /// that was waiting for it.
+    pub(super) fn handle_lsp_selection_ranges(
+        &mut self,
+        request_id: u64,
+        ranges: Vec<lsp_types::SelectionRange>,
+    ) {
+        let Some(expansion) = self.active_window_mut().selection_expansion.as_mut() else {
+            return;
+        };
+        if expansion.pending.as_ref().map(|p| p.request_id) != Some(request_id) {
+            tracing::debug!(
+                "Ignoring selection ranges without pending request (request_id={})",
+                request_id
+            );
+            return;
+        }
+        let pending = expansion.pending.take().expect("pending request checked");
+        let (buffer_id, version) = (expansion.buffer_id, expansion.version);
+
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return;
+        };
+        if state.buffer.version() != version {
+            return;
+        }
+        let chains: Vec<_> = ranges.iter().map(|r| lsp_chain(&state.buffer, r)).collect();
+
+        let Some(expansion) = self.active_window_mut().selection_expansion.as_mut() else {
+            return;
+        };
+        // A missing chain is stored as empty so the step falls back to the
+        // local ranges instead of asking again.
+        let mut chains = chains.into_iter();
+        for cursor_id in pending.cursors {
+            let chain = chains.next().unwrap_or_default();
+            if let Some(entry) = expansion.cursors.get_mut(&cursor_id) {
+                entry.lsp_ranges = Some(chain);
+            }
+        }
+
+        if pending.apply && self.active_buffer() == buffer_id {
+            self.sync_selection_expansion();
+            self.step_structural_selection();
+        }
+    }
+
+    /// Start a new sequence when the buffer, its version or a cursor's
+    /// selection no longer match the last step.
+    fn sync_selection_expansion(&mut self) {
+        let buffer_id = self.active_buffer();
+        let version = self.active_state().buffer.version();
+        let selections: HashMap<CursorId, Range<usize>> = self
+            .active_cursors()
+            .iter()
+            .map(|(id, c)| (id, selection_of(c)))
+            .collect();
+
+        let expansion = &mut self.active_window_mut().selection_expansion;
+        if expansion
+            .as_ref()
+            .is_none_or(|e| e.buffer_id != buffer_id || e.version != version)
+        {
+            *expansion = Some(SelectionExpansion {
+                buffer_id,
+                version,
+                cursors: HashMap::new(),
+                pending: None,
+            });
+        }
+        let expansion = expansion.as_mut().expect("expansion just ensured");
+
+        expansion
+            .cursors
+            .retain(|id, entry| selections.get(id) == Some(&entry.current));
+        for (id, selection) in selections {
+            expansion
+                .cursors
+                .entry(id)
+                .or_insert_with(|| CursorExpansion::new(selection));
+        }
+    }
+
+    /// Ask the server for the selection ranges at every cursor. Returns
+    /// `true` when the request went out.
+    fn request_selection_ranges(&mut self) -> bool {
+        let buffer_id = self.active_buffer();
+        let (cursors, positions): (Vec<CursorId>, Vec<lsp_types::Position>) = {
+            let buffer = &self.active_state().buffer;
+            self.active_cursors()
+                .iter()
+                .map(|(id, c)| {
+                    let (line, character) = buffer.position_to_lsp_position(selection_of(c).start);
+                    (id, lsp_types::Position::new(line as u32, character as u32))
+                })
+                .unzip()
+        };
+        let request_id = self.active_window().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::SelectionRange,
+                |handle, uri, _language| match handle.selection_range(
+                    request_id,
+                    uri.as_uri().clone(),
+                    positions,
+                ) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::debug!("Failed to request selection ranges: {}", e);
+                        false
+                    }
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let win = self.active_window_mut();
+            win.next_lsp_request_id += 1;
+            if let Some(expansion) = win.selection_expansion.as_mut() {
+                expansion.pending = Some(PendingSelectionRanges {
+                    request_id,
+                    cursors,
+                    apply: true,
+                });
+            }
+        }
+        sent
+    }
+
+    /// Move every cursor one range outwards: the server's chain first, the
+    /// local syntax ranges once it is exhausted or missing.
+    fn step_structural_selection(&mut self) {
+        let cursors: Vec<(CursorId, Cursor)> = self
+            .active_cursors()
+            .iter()
+            .map(|(id, c)| (id, *c))
+            .collect();
+        let selections: Vec<Range<usize>> = cursors.iter().map(|(_, c)| selection_of(c)).collect();
+
+        let targets: Vec<Option<Range<usize>>> = {
+            let Some(expansion) = self.active_window().selection_expansion.as_ref() else {
+                return;
+            };
+            let state = self.active_state();
+            let language = state.highlighter.language().copied();
+            // Parsed at most once per step, and only if some cursor needs it.
+            let mut local: Option<Vec<Vec<Range<usize>>>> = None;
+            cursors
+                .iter()
+                .zip(&selections)
+                .enumerate()
+                .map(|(i, ((cursor_id, _), selection))| {
+                    expansion
+                        .cursors
+                        .get(cursor_id)
+                        .and_then(|e| e.lsp_ranges.as_deref())
+                        .and_then(|chain| syntax_selection::next_enclosing(chain, selection))
+                        .or_else(|| {
+                            let local = local.get_or_insert_with(|| {
+                                syntax_selection::enclosing_ranges(
+                                    &state.buffer,
+                                    language.as_ref(),
+                                    &selections,
+                                )
+                            });
+                            syntax_selection::next_enclosing(&local[i], selection)
+                        })
+                })
+                .collect()
+        };
+
+        let Some(expansion) = self.active_window_mut().selection_expansion.as_mut() else {
+            return;
+        };
+        let mut events = Vec::new();
+        for ((cursor_id, cursor), target) in cursors.into_iter().zip(targets) {
+            let (Some(target), Some(entry)) = (target, expansion.cursors.get_mut(&cursor_id))
+            else {
+                continue;
+            };
+            entry.history.push((cursor.anchor, cursor.position));
+            entry.current = target.clone();
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: cursor.position,
+                new_position: target.end,
+                old_anchor: cursor.anchor,
+                new_anchor: Some(target.start),
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: cursor.sticky_column,
+            });
+        }
+        self.apply_selection_moves(events, "Expand selection");
+    }
+
+    fn apply_selection_moves(&mut self, mut events: Vec<Event>, description: &str) {
+        let event = match events.len() {
+            0 => return,
+            1 => events.pop().expect("one event"),
+            _ => Event::Batch {
+                events,
+                description: description.to_string(),
+            },
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+    }
+}
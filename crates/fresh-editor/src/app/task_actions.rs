@@ -0,0 +1,354 @@
+//! Task runner orchestrators.
+//!
+//! "Run Task" lists the configured and detected tasks and runs the chosen
+//! one through the authority's long-running spawner. Output streams into a
+//! read-only `*Task: name*` buffer in the Utility Dock (reused by the next
+//! run of the same task). Problems found by the task's matchers are stored
+//! as push diagnostics under the task's key, so they show in the
+//! Diagnostics panel and are reached with Next/Previous Error like those
+//! of a language server. Each run replaces the problems of the previous
+//! one.
+
+use std::collections::BTreeMap;
+
+use rust_i18n::t;
+
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::services::tasks::{detect_tasks, MatcherState, ProblemMatcher, TaskHandle, TaskLaunch};
+use crate::types::TaskConfig;
+use crate::view::prompt::{Prompt, PromptType};
+use fresh_core::WindowId;
+
+use super::tasks::{diagnostics_key, TaskRun};
+use super::Editor;
+
+/// Buffer mode of task output buffers.
+const TASK_OUTPUT_MODE: &str = "task-output";
+
+impl crate::app::window::Window {
+    /// Append `text` to a task output buffer. Cursors sitting at the end
+    /// move along, so a view left at the bottom follows the output.
+    fn append_task_output(&mut self, buffer_id: BufferId, text: &str) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let old_len = state.buffer.len();
+        state.buffer.insert(old_len, text);
+        state.buffer.clear_modified();
+        self.buffers
+            .with_buffer_and_view_states(buffer_id, |state, vs_map| {
+                let new_len = state.buffer.len();
+                for view_state in vs_map.values_mut() {
+                    let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) else {
+                        continue;
+                    };
+                    buf_state.cursors.map(|cursor| {
+                        if cursor.position == old_len && cursor.anchor.is_none() {
+                            cursor.position = new_len;
+                        }
+                    });
+                }
+            });
+    }
+}
+
+impl Editor {
+    // ==================== Actions ====================
+
+    /// Tasks of the workspace by name: detected ones, overridden by the
+    /// `tasks` config.
+    pub(super) fn available_tasks(&self) -> BTreeMap<String, TaskConfig> {
+        let root = &self.active_window().root;
+        let mut tasks: BTreeMap<String, TaskConfig> =
+            detect_tasks(&*self.authority().filesystem, root)
+                .into_iter()
+                .collect();
+        tasks.extend(
+            self.config()
+                .tasks
+                .iter()
+                .map(|(name, task)| (name.clone(), task.clone())),
+        );
+        tasks.retain(|_, task| !task.command.trim().is_empty());
+        tasks
+    }
+
+    /// Pick a task to run.
+    pub(super) fn start_run_task_prompt(&mut self) {
+        let tasks = self.available_tasks();
+        if tasks.is_empty() {
+            self.set_status_message(t!("task.none").to_string());
+            return;
+        }
+        let last = self.active_window().tasks.last_task.clone();
+        let mut suggestions: Vec<Suggestion> = tasks
+            .into_iter()
+            .map(|(name, task)| Suggestion {
+                description_spans: None,
+                // A detected task is named after its command line.
+                description: (task.command != name).then_some(task.command),
+                text: name.clone(),
+                value: Some(name),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        // The last task first, so Enter repeats it.
+        if let Some(index) = last.and_then(|last| {
+            suggestions
+                .iter()
+                .position(|s| s.value.as_deref() == Some(&last))
+        }) {
+            let suggestion = suggestions.remove(index);
+            suggestions.insert(0, suggestion);
+        }
+        self.active_window_mut().prompt = Some(Prompt::with_suggestions(
+            t!("task.prompt").to_string(),
+            PromptType::RunTask,
+            suggestions,
+        ));
+        if let Some(prompt) = self.active_window_mut().prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Run task `name`. A run of the same task still going is stopped
+    /// first.
+    pub(super) fn run_task(&mut self, name: &str) {
+        let Some(task) = self.available_tasks().remove(name) else {
+            self.set_status_message(t!("task.not_found", name = name).to_string());
+            return;
+        };
+        let matchers = match task
+            .problem_matchers
+            .iter()
+            .map(|m| ProblemMatcher::resolve(m, &self.config().problem_matchers))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(matchers) => matchers.into_iter().map(MatcherState::new).collect(),
+            Err(e) => {
+                self.set_status_message(
+                    t!("task.failed_to_start", name = name, error = e).to_string(),
+                );
+                return;
+            }
+        };
+        let Some(runtime) = self.tokio_runtime.clone() else {
+            self.set_status_message(t!("task.no_runtime").to_string());
+            return;
+        };
+
+        if let Some(previous) = self.active_window().tasks.running(name) {
+            previous.handle.kill();
+            let run_id = previous.id();
+            self.active_window_mut().tasks.take(run_id);
+        }
+        self.clear_diagnostics_for_server(&diagnostics_key(name));
+
+        let output_buffer = self.show_task_output(name);
+        let window = self.active_window_mut();
+        window.append_task_output(output_buffer, &format!("$ {}\n", task.command));
+
+        let cwd = match &task.cwd {
+            Some(dir) => window.root.join(dir),
+            None => window.root.clone(),
+        };
+        // Sorted so the spawned environment is deterministic (matters for
+        // docker `-e` argument order, same as LSP spawning).
+        let mut env: Vec<(String, String)> = task
+            .env
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        env.sort();
+
+        let run_id = window.tasks.alloc_run_id();
+        let handle = TaskHandle::spawn(
+            runtime.handle(),
+            run_id,
+            TaskLaunch {
+                command: task.command.clone(),
+                env,
+                cwd: Some(cwd.clone()),
+            },
+            window.bridge.sender(),
+            window.authority.long_running_spawner.clone(),
+        );
+        window.tasks.insert(TaskRun::new(
+            handle,
+            name.to_string(),
+            cwd,
+            output_buffer,
+            matchers,
+        ));
+        window.tasks.last_task = Some(name.to_string());
+        self.set_status_message(t!("task.started", name = name).to_string());
+    }
+
+    /// Run the last task again, or pick one if none has run yet.
+    pub(super) fn rerun_last_task(&mut self) {
+        match self.active_window().tasks.last_task.clone() {
+            Some(name) => self.run_task(&name),
+            None => self.start_run_task_prompt(),
+        }
+    }
+
+    /// Stop every task running in the active window.
+    pub(super) fn stop_tasks(&mut self) {
+        let tasks = &self.active_window().tasks;
+        if tasks.runs().next().is_none() {
+            self.set_status_message(t!("task.none_running").to_string());
+            return;
+        }
+        // The runs end (and report) through `handle_task_finished`.
+        for run in tasks.runs() {
+            run.handle.kill();
+        }
+    }
+
+    // ==================== Output ====================
+
+    /// Clear the output buffer of task `name`, creating it in the Utility
+    /// Dock when the last one's tab was closed. Focus stays where it was.
+    fn show_task_output(&mut self, name: &str) -> BufferId {
+        let window = self.active_window();
+        let open = window.tasks.output_buffer(name).filter(|id| {
+            window
+                .buffers
+                .split_view_states()
+                .is_some_and(|states| states.values().any(|vs| vs.has_buffer(*id)))
+        });
+        if let Some(buffer_id) = open {
+            if let Err(e) = self.set_virtual_buffer_content(buffer_id, Vec::new()) {
+                tracing::warn!("Failed to clear task output: {}", e);
+            }
+            return buffer_id;
+        }
+
+        let source_split = self.split_manager().active_split();
+        self.handle_create_virtual_buffer_in_split(
+            format!("*Task: {}*", name),
+            TASK_OUTPUT_MODE.to_string(),
+            true,
+            Vec::new(),
+            0.7,
+            None,
+            None,
+            false,
+            true,
+            true,
+            Some(false),
+            false,
+            Some("utility_dock".to_string()),
+            None,
+        );
+        let buffer_id = self.active_buffer();
+        self.handle_focus_split(source_split.0);
+        buffer_id
+    }
+
+    // ==================== Async messages ====================
+
+    /// Append a line of task output and report the problems it completes.
+    pub(super) fn handle_task_output(&mut self, run_id: u64, line: String) {
+        let Some(window_id) = self.task_run_window(run_id) else {
+            return;
+        };
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let Some(run) = window.tasks.run_mut(run_id) else {
+            return;
+        };
+        let key = diagnostics_key(&run.name);
+        let output_buffer = run.output_buffer;
+        let problems: Vec<_> = run
+            .feed(&line)
+            .into_iter()
+            .map(|problem| (run.resolve(&problem.file), problem.diagnostic))
+            .collect();
+        window.append_task_output(output_buffer, &format!("{}\n", line));
+
+        let translation = window.authority.path_translation.as_ref();
+        let mut uris = Vec::new();
+        for (path, diagnostic) in problems {
+            // Paths are as the task saw them: remote-side on a container
+            // authority, which is also what the wire URIs carry.
+            let host_path = translation
+                .and_then(|t| t.remote_to_host(&path))
+                .unwrap_or(path);
+            let Some(uri) = super::types::LspUri::from_host_path(&host_path, translation) else {
+                continue;
+            };
+            let uri = uri.as_str().to_string();
+            window
+                .stored_push_diagnostics
+                .entry(uri.clone())
+                .or_default()
+                .entry(key.clone())
+                .or_default()
+                .push(diagnostic);
+            uris.push(uri);
+        }
+        // Overlays and the panel follow the active window; another
+        // window's problems are merged when it next receives any.
+        if window_id == self.active_window {
+            for uri in uris {
+                self.merge_and_apply_diagnostics(&uri);
+            }
+        }
+    }
+
+    /// A task process is gone: close its output and say how it went.
+    pub(super) fn handle_task_finished(
+        &mut self,
+        run_id: u64,
+        exit_code: Option<i32>,
+        error: Option<String>,
+    ) {
+        let Some(window_id) = self.task_run_window(run_id) else {
+            return;
+        };
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let Some(run) = window.tasks.take(run_id) else {
+            return;
+        };
+        let name = run.name.as_str();
+        let count = run.problem_count;
+
+        let (footer, message) = match (error, exit_code) {
+            (Some(error), _) => (
+                t!("task.output_failed", error = &error),
+                t!("task.failed_to_start", name = name, error = &error),
+            ),
+            (None, None) => (t!("task.output_stopped"), t!("task.stopped", name = name)),
+            (None, Some(0)) if count == 0 => (
+                t!("task.output_exit", code = 0),
+                t!("task.succeeded", name = name),
+            ),
+            (None, Some(code)) => (
+                t!("task.output_exit", code = code),
+                if code == 0 {
+                    t!("task.succeeded_with_problems", name = name, count = count)
+                } else {
+                    t!("task.failed", name = name, code = code, count = count)
+                },
+            ),
+        };
+        window.append_task_output(run.output_buffer, &format!("\n[{}]\n", footer));
+        if window_id == self.active_window {
+            self.set_status_message(message.to_string());
+        }
+    }
+
+    /// Window owning the run `run_id`, if it is still live.
+    fn task_run_window(&self, run_id: u64) -> Option<WindowId> {
+        self.windows
+            .iter()
+            .find_map(|(id, w)| w.tasks.has_run(run_id).then_some(*id))
+    }
+}
@@ -0,0 +1,166 @@
+//! Self-contained task runner state.
+//!
+//! Holds a window's running tasks, the output buffer of every task that
+//! has run, and the task to repeat with "Rerun Last Task". Each run feeds
+//! its output through the task's problem matchers; the problems become
+//! push diagnostics under [`diagnostics_key`], next to those of the
+//! language servers. Spawning, output buffers and diagnostics plumbing
+//! live in `task_actions`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::model::event::BufferId;
+use crate::services::tasks::{MatcherState, Problem, TaskHandle};
+
+/// Key a task's problems are stored under in `stored_push_diagnostics`,
+/// in place of a server name.
+pub(crate) fn diagnostics_key(task: &str) -> String {
+    format!("task:{task}")
+}
+
+/// A task process and what has come out of it so far.
+#[derive(Debug)]
+pub(crate) struct TaskRun {
+    pub handle: TaskHandle,
+    pub name: String,
+    /// Directory relative paths in the output are resolved against
+    pub cwd: PathBuf,
+    pub output_buffer: BufferId,
+    /// Problems found so far
+    pub problem_count: usize,
+    matchers: Vec<MatcherState>,
+}
+
+impl TaskRun {
+    pub(crate) fn new(
+        handle: TaskHandle,
+        name: String,
+        cwd: PathBuf,
+        output_buffer: BufferId,
+        matchers: Vec<MatcherState>,
+    ) -> Self {
+        Self {
+            handle,
+            name,
+            cwd,
+            output_buffer,
+            problem_count: 0,
+            matchers,
+        }
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.handle.run_id()
+    }
+
+    /// Feed one output line to every matcher, returning the problems it
+    /// completes.
+    pub(crate) fn feed(&mut self, line: &str) -> Vec<Problem> {
+        let problems: Vec<Problem> = self
+            .matchers
+            .iter_mut()
+            .filter_map(|matcher| matcher.feed(line))
+            .collect();
+        self.problem_count += problems.len();
+        problems
+    }
+
+    /// The file a problem names, relative paths taken from the task's
+    /// working directory.
+    pub(crate) fn resolve(&self, file: &str) -> PathBuf {
+        resolve_path(&self.cwd, file)
+    }
+}
+
+fn resolve_path(cwd: &Path, file: &str) -> PathBuf {
+    let path = Path::new(file);
+    let path = path.strip_prefix("./").unwrap_or(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    }
+}
+
+/// Owner of a window's task runs.
+#[derive(Debug, Default)]
+pub(crate) struct TaskState {
+    runs: HashMap<u64, TaskRun>,
+    /// Output buffer of each task that has run, by task name. Reused by
+    /// the next run of the same task.
+    output_buffers: HashMap<String, BufferId>,
+    /// Task "Rerun Last Task" starts
+    pub last_task: Option<String>,
+    next_run_id: u64,
+}
+
+impl TaskState {
+    /// Allocate an id for a new run. Ids are never reused so output from
+    /// a killed run can't be mistaken for the current one.
+    pub(crate) fn alloc_run_id(&mut self) -> u64 {
+        self.next_run_id += 1;
+        self.next_run_id
+    }
+
+    pub(crate) fn insert(&mut self, run: TaskRun) {
+        self.output_buffers
+            .insert(run.name.clone(), run.output_buffer);
+        self.runs.insert(run.id(), run);
+    }
+
+    pub(crate) fn run_mut(&mut self, run_id: u64) -> Option<&mut TaskRun> {
+        self.runs.get_mut(&run_id)
+    }
+
+    pub(crate) fn has_run(&self, run_id: u64) -> bool {
+        self.runs.contains_key(&run_id)
+    }
+
+    /// Forget the run `run_id` and return it.
+    pub(crate) fn take(&mut self, run_id: u64) -> Option<TaskRun> {
+        self.runs.remove(&run_id)
+    }
+
+    /// The running instance of task `name`, if any.
+    pub(crate) fn running(&self, name: &str) -> Option<&TaskRun> {
+        self.runs.values().find(|run| run.name == name)
+    }
+
+    /// Every running task.
+    pub(crate) fn runs(&self) -> impl Iterator<Item = &TaskRun> {
+        self.runs.values()
+    }
+
+    /// Output buffer the last run of `name` wrote to.
+    pub(crate) fn output_buffer(&self, name: &str) -> Option<BufferId> {
+        self.output_buffers.get(name).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_ids_are_not_reused() {
+        let mut s = TaskState::default();
+        let a = s.alloc_run_id();
+        let b = s.alloc_run_id();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_cwd() {
+        let cwd = Path::new("/w/crate");
+        assert_eq!(
+            resolve_path(cwd, "src/main.rs"),
+            PathBuf::from("/w/crate/src/main.rs")
+        );
+        assert_eq!(
+            resolve_path(cwd, "./main.go"),
+            PathBuf::from("/w/crate/main.go")
+        );
+        assert_eq!(resolve_path(cwd, "/abs/x.c"), PathBuf::from("/abs/x.c"));
+    }
+}
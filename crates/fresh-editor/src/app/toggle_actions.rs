@@ -317,6 +317,19 @@ impl Editor {
         self.set_status_message(status.to_string());
     }
 
+    /// Toggle minimap visibility
+    pub fn toggle_minimap(&mut self) {
+        let new_value = !self.config.editor.show_minimap;
+        self.config_mut().editor.show_minimap = new_value;
+        self.persist_config_change("/editor/show_minimap", serde_json::Value::Bool(new_value));
+        let status = if new_value {
+            t!("toggle.minimap_shown")
+        } else {
+            t!("toggle.minimap_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
     /// Reset buffer settings (tab_size, use_tabs, auto_close, whitespace visibility) to config defaults
     pub fn reset_buffer_settings(&mut self) {
         use crate::config::WhitespaceVisibility;
@@ -465,7 +478,7 @@ impl Editor {
                 .map(|w| &mut w.buffers)
                 .expect("active window present")
             {
-                state.virtual_texts.clear(&mut state.marker_list);
+                state.virtual_texts.clear_inline(&mut state.marker_list);
             }
             self.set_status_message(t!("toggle.inlay_hints_disabled").to_string());
         }
@@ -571,6 +584,9 @@ impl Editor {
         // Update clipboard configuration
         self.clipboard.apply_config(&self.config.clipboard);
 
+        // Named macros may have been added or removed
+        self.sync_named_macro_commands();
+
         // Apply bar visibility changes immediately
         self.active_window_mut().menu_bar_visible = self.config.editor.show_menu_bar;
         self.active_window_mut().tab_bar_visible = self.config.editor.show_tab_bar;
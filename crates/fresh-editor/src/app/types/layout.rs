@@ -208,6 +208,9 @@ pub(crate) struct WindowLayoutCache {
     /// Horizontal scrollbar areas per split
     /// (split_id, buffer_id, horizontal_scrollbar_rect, max_content_width, thumb_start_col, thumb_end_col)
     pub horizontal_scrollbar_areas: Vec<(LeafId, BufferId, Rect, usize, usize, usize)>,
+    /// Minimap areas per split, with the scale used to draw them
+    /// (split_id, buffer_id, minimap_rect, scale)
+    pub minimap_areas: Vec<(LeafId, BufferId, Rect, crate::view::minimap::MinimapScale)>,
     /// Split separator positions for drag resize
     /// (container_id, direction, x, y, length)
     pub separator_areas: Vec<(ContainerId, SplitDirection, u16, u16, u16)>,
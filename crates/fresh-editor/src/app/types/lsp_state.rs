@@ -1,4 +1,6 @@
 use crate::services::async_bridge::LspMessageType;
+use crate::services::lsp::async_handler::HierarchyKind;
+use crate::types::LspFeature;
 
 /// LSP progress information
 #[derive(Debug, Clone)]
@@ -15,6 +17,41 @@ pub struct LspProgressInfo {
 // and the internal storage type.
 pub use fresh_core::api::LspMenuItem;
 
+/// Which call- or type-hierarchy view a pending request was made for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyDirection {
+    IncomingCalls,
+    OutgoingCalls,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyDirection {
+    /// Name passed to plugins in the `lsp_hierarchy` hook.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::IncomingCalls => "incoming",
+            Self::OutgoingCalls => "outgoing",
+            Self::Supertypes => "supertypes",
+            Self::Subtypes => "subtypes",
+        }
+    }
+
+    pub fn kind(self) -> HierarchyKind {
+        match self {
+            Self::IncomingCalls | Self::OutgoingCalls => HierarchyKind::Call,
+            Self::Supertypes | Self::Subtypes => HierarchyKind::Type,
+        }
+    }
+
+    pub fn feature(self) -> LspFeature {
+        match self.kind() {
+            HierarchyKind::Call => LspFeature::CallHierarchy,
+            HierarchyKind::Type => LspFeature::TypeHierarchy,
+        }
+    }
+}
+
 /// LSP message entry (for window messages and logs)
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -38,7 +38,7 @@ pub use layout::{OverlayPreviewState, ViewLineMapping};
 
 // lsp_state re-exports
 pub use lsp_state::LspMenuItem;
-pub(crate) use lsp_state::{LspMessageEntry, LspProgressInfo};
+pub(crate) use lsp_state::{HierarchyDirection, LspMessageEntry, LspProgressInfo};
 
 // lsp_uri re-exports
 pub use lsp_uri::{file_path_to_lsp_uri, file_path_to_lsp_uri_with_translation, LspUri};
@@ -14,6 +14,8 @@ pub struct MouseState {
     pub drag_start_hcol: Option<u16>,
     /// Initial left_column when starting horizontal scrollbar drag
     pub drag_start_left_column: Option<usize>,
+    /// Whether we're currently dragging across a split's minimap
+    pub dragging_minimap: Option<LeafId>,
     /// Last mouse position
     pub last_position: Option<(u16, u16)>,
     /// Mouse hover for LSP: byte position being hovered, timer start, and screen position
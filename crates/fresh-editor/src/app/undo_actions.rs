@@ -1,8 +1,31 @@
 //! Undo and redo action handlers.
 
 use super::Editor;
+use crate::model::event::{BufferId, Event, LeafId};
 use rust_i18n::t;
 
+/// How many linked edits are remembered; older ones undo per buffer again.
+const MAX_LINKED_UNDO: usize = 50;
+
+/// Edits to several buffers that undo and redo as one, such as a rename
+/// applied across files. Each member is `(buffer, state before, state
+/// after)` in its undo tree (see
+/// [`crate::model::event::EventLog::current_state`]). Undoing a member from
+/// `after` to `before` undoes every other member still at its own `after`;
+/// redo mirrors that. A member edited since is left alone.
+#[derive(Debug, Default)]
+pub(crate) struct LinkedUndo {
+    members: Vec<(BufferId, u64, u64)>,
+}
+
+impl LinkedUndo {
+    pub(crate) fn push(&mut self, buffer_id: BufferId, before: u64, after: u64) {
+        if before != after {
+            self.members.push((buffer_id, before, after));
+        }
+    }
+}
+
 impl Editor {
     /// Handle Undo action - revert the last edit operation.
     pub fn handle_undo(&mut self) {
@@ -11,6 +34,8 @@ impl Editor {
             return;
         }
 
+        let buffer_id = self.active_buffer();
+        let from = self.active_event_log().current_state();
         let event_log = self.active_event_log_mut();
         let before_idx = event_log.current_index();
         let can_undo = event_log.can_undo();
@@ -25,25 +50,30 @@ impl Editor {
             events.len()
         );
 
-        // Apply all inverse events collected during undo.
-        // Each event may carry displaced markers that need restoration after apply.
-        for (event, displaced_markers) in &events {
+        self.apply_undo_events(&events);
+
+        // Update modified status based on event log position
+        self.active_window_mut().update_modified_from_event_log();
+
+        self.follow_linked_undo(buffer_id, from, false);
+    }
+
+    /// Apply inverse events collected from the event log to the active
+    /// buffer. Each event may carry displaced markers that need restoration
+    /// after apply.
+    fn apply_undo_events(&mut self, events: &[(Event, Vec<(u64, usize)>)]) {
+        for (event, displaced_markers) in events {
             tracing::debug!("Undo applying event: {:?}", event);
             self.apply_event_to_active_buffer(event);
 
             // Restore displaced markers from LogEntry (for single Delete events).
             // Skip for BulkEdit — they handle displaced markers internally
             // in state.apply(BulkEdit) via the Event's own displaced_markers field.
-            if !displaced_markers.is_empty()
-                && !matches!(event, crate::model::event::Event::BulkEdit { .. })
-            {
+            if !displaced_markers.is_empty() && !matches!(event, Event::BulkEdit { .. }) {
                 self.active_state_mut()
                     .restore_displaced_markers(displaced_markers);
             }
         }
-
-        // Update modified status based on event log position
-        self.active_window_mut().update_modified_from_event_log();
     }
 
     /// Handle Redo action - reapply an undone edit operation.
@@ -53,6 +83,8 @@ impl Editor {
             return;
         }
 
+        let buffer_id = self.active_buffer();
+        let from = self.active_event_log().current_state();
         let events = self.active_event_log_mut().redo();
 
         // Apply all events collected during redo
@@ -62,5 +94,175 @@ impl Editor {
 
         // Update modified status based on event log position
         self.active_window_mut().update_modified_from_event_log();
+
+        self.follow_linked_undo(buffer_id, from, true);
+    }
+
+    /// Remember edits made together to several buffers so that they undo
+    /// and redo as one.
+    pub(super) fn link_undo(&mut self, linked: LinkedUndo) {
+        if linked.members.len() < 2 {
+            return;
+        }
+        let history = &mut self.active_window_mut().linked_undo;
+        if history.len() == MAX_LINKED_UNDO {
+            history.remove(0);
+        }
+        history.push(linked);
+    }
+
+    /// `buffer_id` just went from undo state `from` to its current one by an
+    /// undo (or a redo). If that crossed a linked edit, take the other
+    /// buffers of the edit along.
+    fn follow_linked_undo(&mut self, buffer_id: BufferId, from: u64, redo: bool) {
+        let to = self.active_event_log().current_state();
+        let crossed = |&(id, before, after): &(BufferId, u64, u64)| {
+            id == buffer_id
+                && (from, to)
+                    == if redo {
+                        (before, after)
+                    } else {
+                        (after, before)
+                    }
+        };
+        let Some(linked) = self
+            .active_window()
+            .linked_undo
+            .iter()
+            .find(|linked| linked.members.iter().any(crossed))
+        else {
+            return;
+        };
+        let others: Vec<(BufferId, u64)> = linked
+            .members
+            .iter()
+            .filter(|(id, _, _)| *id != buffer_id)
+            .map(|&(id, before, after)| (id, if redo { before } else { after }))
+            .collect();
+
+        for (other, expected) in others {
+            let in_step = self
+                .active_window()
+                .event_logs
+                .get(&other)
+                .is_some_and(|log| log.current_state() == expected);
+            if !in_step {
+                continue;
+            }
+            let stepped = self.with_buffer_in_focus(other, |editor| {
+                if redo {
+                    for event in editor.active_event_log_mut().redo() {
+                        editor.apply_event_to_active_buffer(&event);
+                    }
+                } else {
+                    let events = editor.active_event_log_mut().undo();
+                    editor.apply_undo_events(&events);
+                }
+                editor.active_window_mut().update_modified_from_event_log();
+            });
+            if stepped.is_none() {
+                tracing::warn!("Linked undo: no split can show buffer {:?}", other);
+                self.set_status_message(t!("buffer.linked_undo_unavailable").to_string());
+            }
+        }
+    }
+
+    /// Run `f` with `buffer_id` standing in as the active buffer of a split
+    /// that has it open, so that event application, LSP sync and hooks all
+    /// target it; the split layout is put back afterwards. Same idea as the
+    /// swap in `apply_events_to_buffer_as_bulk_edit`. A buffer no split has
+    /// open is first opened as a background tab where files go. `None` when
+    /// that split shows a buffer group instead.
+    fn with_buffer_in_focus<R>(
+        &mut self,
+        buffer_id: BufferId,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> Option<R> {
+        let (mgr, view_states) = self.active_window().buffers.splits()?;
+        let original_split = mgr.active_split();
+        let holds = |split: &LeafId| {
+            view_states.get(split).is_some_and(|vs| {
+                vs.active_group_tab.is_none() && vs.keyed_states.contains_key(&buffer_id)
+            })
+        };
+        let found = if holds(&original_split) {
+            Some(original_split)
+        } else {
+            view_states
+                .keys()
+                .copied()
+                .find(|split| holds(split) && mgr.get_buffer_id((*split).into()).is_some())
+        };
+        let split = match found {
+            Some(split) => split,
+            None => self.open_in_background_tab(buffer_id)?,
+        };
+        let split_buffer = self.split_manager().get_buffer_id(split.into())?;
+
+        let mgr = self.split_manager_mut();
+        if split != original_split {
+            mgr.set_active_split(split);
+        }
+        mgr.set_active_buffer_id(buffer_id);
+        let result = f(self);
+        let mgr = self.split_manager_mut();
+        mgr.set_active_buffer_id(split_buffer);
+        if split != original_split {
+            mgr.set_active_split(original_split);
+        }
+        Some(result)
+    }
+
+    /// Add `buffer_id` to the tabs of the split files open in, without
+    /// switching to it. Returns that split, unless it shows a buffer group.
+    fn open_in_background_tab(&mut self, buffer_id: BufferId) -> Option<LeafId> {
+        let split = self.active_window().preferred_split_for_file();
+        let line_wrap = self.active_window().resolve_line_wrap_for_buffer(buffer_id);
+        let wrap_column = self
+            .active_window()
+            .resolve_wrap_column_for_buffer(buffer_id);
+        let editor = &self.config.editor;
+        let view_state = self
+            .windows
+            .get_mut(&self.active_window)
+            .and_then(|w| w.split_view_states_mut())?
+            .get_mut(&split)?;
+        if view_state.active_group_tab.is_some() {
+            return None;
+        }
+        view_state.add_buffer(buffer_id);
+        view_state
+            .ensure_buffer_state(buffer_id)
+            .apply_config_defaults(
+                editor.line_numbers,
+                editor.highlight_current_line,
+                line_wrap,
+                editor.wrap_indent,
+                wrap_column,
+                editor.rulers.clone(),
+                editor.scroll_offset,
+            );
+        Some(split)
+    }
+
+    /// Move the active buffer to state `node_id` of its undo tree (see
+    /// [`crate::model::event::EventLog::undo_tree`]), undoing back to where
+    /// the two lines of history meet and redoing forward from there.
+    pub fn jump_to_undo_state(&mut self, node_id: u64) {
+        if self.active_window().is_editing_disabled() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return;
+        }
+
+        let Some(jump) = self.active_event_log_mut().jump_to(node_id) else {
+            tracing::debug!("Undo tree has no state {}", node_id);
+            return;
+        };
+        self.apply_undo_events(&jump.undo);
+        for event in &jump.redo {
+            self.apply_event_to_active_buffer(event);
+        }
+
+        self.active_window_mut().update_modified_from_event_log();
     }
 }
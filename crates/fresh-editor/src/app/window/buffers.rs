@@ -10,13 +10,19 @@ use std::path::PathBuf;
 
 use crate::model::event::LeafId;
 use crate::state::EditorState;
-use crate::view::split::{SplitManager, SplitViewState};
+use crate::view::split::{SplitManager, SplitRole, SplitViewState, TabTarget};
 
 type Splits = (SplitManager, HashMap<LeafId, SplitViewState>);
 
 pub struct WindowBuffers {
     map: HashMap<BufferId, EditorState>,
     splits: Option<Splits>,
+    /// Split layouts of the session daemon's clients other than the one
+    /// whose layout is in `splits`, by client view id (see
+    /// `app::client_views`).
+    parked: HashMap<u64, Splits>,
+    /// Client view `splits` belongs to; `None` until a client adopts it.
+    live_view: Option<u64>,
 }
 
 impl WindowBuffers {
@@ -24,6 +30,8 @@ impl WindowBuffers {
         Self {
             map: HashMap::new(),
             splits: None,
+            parked: HashMap::new(),
+            live_view: None,
         }
     }
 
@@ -170,6 +178,108 @@ impl WindowBuffers {
         self.splits = None;
     }
 
+    // -- client views (session daemon) -----------------------------------
+
+    /// Make the layout of client view `view` the live one, parking the
+    /// layout it replaces under that layout's view. A view without a
+    /// layout here adopts an unowned one, or else starts with a single
+    /// split showing the live layout's focused buffer at the same cursor.
+    /// Returns the view whose layout was replaced, so a temporary switch
+    /// can be undone.
+    pub fn show_view(&mut self, view: u64, width: u16, height: u16) -> Option<u64> {
+        if self.live_view == Some(view) {
+            return Some(view);
+        }
+        if self.live_view.is_none() && !self.parked.contains_key(&view) {
+            self.live_view = Some(view);
+            return None;
+        }
+        let Some(live) = self.splits.take() else {
+            return self.live_view;
+        };
+        let next = match self.parked.remove(&view) {
+            Some(parked) => Some(self.revalidate(parked, &live)),
+            None => fresh_view(&live, width, height),
+        };
+        let Some(next) = next else {
+            self.splits = Some(live);
+            return self.live_view;
+        };
+        let previous = self.live_view.replace(view);
+        if let Some(owner) = previous {
+            self.parked.insert(owner, live);
+        }
+        self.splits = Some(next);
+        previous
+    }
+
+    /// Forget the layout of a client view that went away. Its live
+    /// layout stays, for the next client to adopt.
+    pub fn drop_view(&mut self, view: u64) {
+        self.parked.remove(&view);
+        if self.live_view == Some(view) {
+            self.live_view = None;
+        }
+    }
+
+    /// Every view state of the parked layouts. Edits shift their cursors
+    /// like those of the live splits.
+    pub fn parked_view_states_mut(&mut self) -> impl Iterator<Item = &mut SplitViewState> {
+        self.parked
+            .values_mut()
+            .flat_map(|(_, view_states)| view_states.values_mut())
+    }
+
+    /// Bring a parked layout up to date with what happened while it was
+    /// parked: splits showing a closed buffer switch to the live focused
+    /// buffer, closed buffers leave the tabs, and cursors are clamped to
+    /// the buffers' current lengths.
+    fn revalidate(&self, parked: Splits, live: &Splits) -> Splits {
+        let (mut mgr, mut view_states) = parked;
+        mgr.reserve_split_ids(&live.0);
+        let fallback = live
+            .0
+            .active_buffer_id()
+            .filter(|id| self.map.contains_key(id))
+            .or_else(|| self.map.keys().next().copied());
+        let Some(fallback) = fallback else {
+            return (mgr, view_states);
+        };
+        for leaf in mgr.root().leaf_split_ids() {
+            if mgr
+                .get_buffer_id(leaf.into())
+                .is_some_and(|id| !self.map.contains_key(&id))
+            {
+                mgr.set_split_buffer(leaf, fallback);
+                if let Some(view_state) = view_states.get_mut(&leaf) {
+                    view_state.add_buffer(fallback);
+                    view_state.switch_buffer(fallback);
+                }
+            }
+        }
+        let exists = |target: &TabTarget| match target {
+            TabTarget::Buffer(id) => self.map.contains_key(id),
+            TabTarget::Group(_) => true,
+        };
+        for view_state in view_states.values_mut() {
+            view_state.open_buffers.retain(|target| exists(target));
+            view_state.focus_history.retain(|target| exists(target));
+            let active = view_state.active_buffer;
+            view_state
+                .keyed_states
+                .retain(|id, _| *id == active || self.map.contains_key(id));
+            for (id, buffer_view) in view_state.keyed_states.iter_mut() {
+                let len = self.map.get(id).map_or(0, |state| state.buffer.len());
+                buffer_view.cursors.map(|cursor| {
+                    cursor.position = cursor.position.min(len);
+                    cursor.anchor = cursor.anchor.map(|anchor| anchor.min(len));
+                });
+            }
+            view_state.invalidate_layout();
+        }
+        (mgr, view_states)
+    }
+
     // -- combined mutation (closure-based) -------------------------------
     //
     // These methods own the disjoint sub-borrow internally so callers
@@ -219,6 +329,24 @@ impl WindowBuffers {
     }
 }
 
+/// A single-split layout showing the buffer focused in `live` (outside
+/// the Utility Dock), with the same cursor and scroll position.
+fn fresh_view(live: &Splits, width: u16, height: u16) -> Option<Splits> {
+    let (mgr, view_states) = live;
+    let leaf = mgr
+        .last_focused_where(|leaf| mgr.leaf_role(leaf) != Some(SplitRole::UtilityDock))
+        .unwrap_or_else(|| mgr.active_split());
+    let source = view_states.get(&leaf)?;
+    let buffer_id = source.active_buffer;
+    let new_mgr = SplitManager::new_after(buffer_id, mgr);
+    let mut view_state = SplitViewState::with_buffer(width, height, buffer_id);
+    let mut buffer_view = source.active_state().clone();
+    buffer_view.viewport.resize(width, height);
+    view_state.keyed_states.insert(buffer_id, buffer_view);
+    let view_states = HashMap::from([(new_mgr.active_split(), view_state)]);
+    Some((new_mgr, view_states))
+}
+
 impl Default for WindowBuffers {
     fn default() -> Self {
         Self::new()
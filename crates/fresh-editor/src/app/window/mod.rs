@@ -114,6 +114,12 @@ pub struct Window {
     /// paused".
     pub file_mod_times: HashMap<PathBuf, std::time::SystemTime>,
 
+    /// Change watches on the directories of this window's open files
+    /// and expanded explorer directories, when the authority's
+    /// filesystem can report changes itself. Covered paths are left
+    /// out of the mtime polls.
+    pub(crate) watched_dirs: crate::services::file_watcher::WatchedDirs,
+
     /// LSP manager (running language servers, configs, per-language
     /// root URIs). Each window owns its own LSP set, rooted at its
     /// project root; inactive windows' servers remain running in the
@@ -199,6 +205,9 @@ pub struct Window {
     /// Dabbrev cycling state (Alt+/ session).
     pub dabbrev_state: Option<crate::app::DabbrevCycleState>,
 
+    /// Last paste that yank-pop may replace (Emacs `M-y`).
+    pub yank_state: Option<crate::app::YankState>,
+
     /// Pending LSP go-to-definition request id.
     pub pending_goto_definition_request: Option<u64>,
 
@@ -206,6 +215,10 @@ pub struct Window {
     pub pending_references_request: Option<u64>,
     pub pending_references_symbol: String,
 
+    /// Pending LSP prepare-hierarchy request: id, requested view and the
+    /// language of the requesting buffer.
+    pub pending_hierarchy_request: Option<(u64, crate::app::types::HierarchyDirection, String)>,
+
     /// Pending LSP signature-help request id.
     pub pending_signature_help_request: Option<u64>,
 
@@ -225,6 +238,49 @@ pub struct Window {
     pub folding_ranges_in_flight: std::collections::HashMap<BufferId, (u64, u64)>,
     pub folding_ranges_debounce: std::collections::HashMap<BufferId, std::time::Instant>,
 
+    /// Pending code-lens and code-lens-resolve requests + debounce.
+    pub(crate) pending_code_lens_requests:
+        std::collections::HashMap<u64, crate::app::CodeLensRequest>,
+    pub code_lens_debounce: std::collections::HashMap<BufferId, std::time::Instant>,
+
+    /// Pending document-link, link-resolve and document-color requests,
+    /// plus the debounce refreshing links and colors together.
+    pub(crate) pending_document_link_requests:
+        std::collections::HashMap<u64, crate::app::DocumentLinkRequest>,
+    pub(crate) pending_document_color_requests:
+        std::collections::HashMap<u64, crate::app::DocumentColorRequest>,
+    pub document_decorations_debounce: std::collections::HashMap<BufferId, std::time::Instant>,
+
+    /// Color presentation request for the literal at the cursor, and the
+    /// picker offering its answers.
+    pub(crate) color_presentation_picker:
+        Option<crate::app::document_decorations::ColorPresentationPicker>,
+
+    /// Explorer rename, move or delete waiting on the servers'
+    /// `workspace/will*Files` answers, or on confirmation of their edits.
+    pub(crate) pending_file_operation:
+        Option<crate::app::lsp_file_operations::PendingFileOperation>,
+
+    /// Multi-file LSP edit shown in the preview panel, waiting to be
+    /// applied or dropped.
+    pub(crate) pending_edit_preview:
+        Option<crate::app::workspace_edit_preview::WorkspaceEditPreview>,
+
+    /// Recent edits spanning several buffers that undo as one.
+    pub(crate) linked_undo: Vec<crate::app::undo_actions::LinkedUndo>,
+
+    /// Per-cursor state of the running expand/shrink selection sequence,
+    /// including any in-flight selection-range request.
+    pub(crate) selection_expansion: Option<crate::app::structural_selection::SelectionExpansion>,
+
+    /// Linked editing ranges around the primary cursor (e.g. an HTML tag
+    /// pair) and the in-flight request refreshing them.
+    pub(crate) linked_editing: crate::app::linked_editing::LinkedEditingState,
+
+    /// In-flight on-type formatting request, if any.
+    pub(crate) pending_on_type_formatting:
+        Option<crate::app::on_type_formatting::PendingOnTypeFormatting>,
+
     /// Pending semantic-tokens-full requests + per-buffer in-flight tracking +
     /// the next-allowed-refresh debounce.
     pub(crate) pending_semantic_token_requests:
@@ -267,6 +323,15 @@ pub struct Window {
     /// window has its own register set.
     pub(crate) bookmarks: crate::app::bookmarks::BookmarkState,
 
+    /// Breakpoints and the live debug adapter session for this window.
+    /// Breakpoints are keyed by file path so they outlive the buffers
+    /// they were set in.
+    pub(crate) debugger: crate::app::debugger::DebuggerState,
+
+    /// Running tasks and the output buffer of each task that has run in
+    /// this window.
+    pub(crate) tasks: crate::app::tasks::TaskState,
+
     /// Composite buffers in this window (separate from regular
     /// buffers). These display multiple source buffers in a single
     /// tab — Live Grep results, References, Diagnostics list,
@@ -1663,6 +1728,15 @@ impl Window {
                 state
                     .overlays
                     .clear_namespace(diagnostic_namespace, &mut state.marker_list);
+                state
+                    .code_lenses
+                    .clear(&mut state.virtual_texts, &mut state.marker_list);
+                state
+                    .document_links
+                    .clear(&mut state.overlays, &mut state.marker_list);
+                state
+                    .document_colors
+                    .clear(&mut state.virtual_texts, &mut state.marker_list);
                 state.virtual_texts.clear(&mut state.marker_list);
                 state.folding_ranges.clear(&mut state.marker_list);
                 for view_state in vs_map.values_mut() {
@@ -1760,9 +1834,11 @@ impl Window {
             completion_items: None,
             scheduled_completion_trigger: None,
             dabbrev_state: None,
+            yank_state: None,
             pending_goto_definition_request: None,
             pending_references_request: None,
             pending_references_symbol: String::new(),
+            pending_hierarchy_request: None,
             pending_signature_help_request: None,
             pending_code_actions_requests: std::collections::HashSet::new(),
             pending_code_actions_server_names: std::collections::HashMap::new(),
@@ -1771,6 +1847,18 @@ impl Window {
             pending_folding_range_requests: std::collections::HashMap::new(),
             folding_ranges_in_flight: std::collections::HashMap::new(),
             folding_ranges_debounce: std::collections::HashMap::new(),
+            pending_code_lens_requests: std::collections::HashMap::new(),
+            code_lens_debounce: std::collections::HashMap::new(),
+            pending_document_link_requests: std::collections::HashMap::new(),
+            pending_document_color_requests: std::collections::HashMap::new(),
+            document_decorations_debounce: std::collections::HashMap::new(),
+            color_presentation_picker: None,
+            pending_file_operation: None,
+            pending_edit_preview: None,
+            linked_undo: Vec::new(),
+            selection_expansion: None,
+            linked_editing: Default::default(),
+            pending_on_type_formatting: None,
             pending_semantic_token_requests: std::collections::HashMap::new(),
             semantic_tokens_in_flight: std::collections::HashMap::new(),
             semantic_tokens_full_debounce: std::collections::HashMap::new(),
@@ -1782,6 +1870,9 @@ impl Window {
             in_navigation: false,
             suppress_position_history_once: false,
             bookmarks: crate::app::bookmarks::BookmarkState::default(),
+            debugger: crate::app::debugger::DebuggerState::default(),
+            tasks: crate::app::tasks::TaskState::default(),
+            watched_dirs: crate::services::file_watcher::WatchedDirs::default(),
             grouped_subtrees: HashMap::new(),
             composite_buffers: HashMap::new(),
             composite_view_states: HashMap::new(),
@@ -1834,7 +1925,15 @@ impl Window {
             editor_mode: None,
             prompt_histories: HashMap::new(),
             pending_close_buffer: None,
-            completion_service: crate::services::completion::CompletionService::new(),
+            completion_service: {
+                let mut service = crate::services::completion::CompletionService::new();
+                service.register(Box::new(
+                    crate::services::completion::SnippetProvider::load(
+                        &resources.dir_context.snippets_dir(),
+                    ),
+                ));
+                service
+            },
             lsp_diagnostic_namespace: crate::view::overlay::OverlayNamespace::from_string(
                 "lsp-diagnostic".to_string(),
             ),
@@ -3050,6 +3149,26 @@ impl Window {
         self.folding_ranges_debounce.insert(buffer_id, next_time);
     }
 
+    /// Schedule a code-lens refresh for a buffer (debounced). Lenses
+    /// carry positions and reference counts, so they are re-pulled
+    /// after edits the same way folding ranges are.
+    pub fn schedule_code_lens_refresh(&mut self, buffer_id: BufferId) {
+        const CODE_LENS_DEBOUNCE_MS: u64 = 500;
+        let next_time =
+            std::time::Instant::now() + std::time::Duration::from_millis(CODE_LENS_DEBOUNCE_MS);
+        self.code_lens_debounce.insert(buffer_id, next_time);
+    }
+
+    /// Schedule a document link and color refresh for a buffer
+    /// (debounced), on the same terms as code lenses.
+    pub fn schedule_document_decorations_refresh(&mut self, buffer_id: BufferId) {
+        const DOCUMENT_DECORATIONS_DEBOUNCE_MS: u64 = 500;
+        let next_time = std::time::Instant::now()
+            + std::time::Duration::from_millis(DOCUMENT_DECORATIONS_DEBOUNCE_MS);
+        self.document_decorations_debounce
+            .insert(buffer_id, next_time);
+    }
+
     /// Schedule a full semantic-tokens refresh for a buffer (debounced).
     /// No-op when `enable_semantic_tokens_full` is off in the active
     /// config.
@@ -3259,7 +3378,8 @@ impl Window {
     /// cursors moved by `BufferState::apply`; this method walks every
     /// other split displaying the same buffer and shifts (or, for a
     /// `BulkEdit`, resets) their cursors so they don't dangle past
-    /// freshly-deleted text.
+    /// freshly-deleted text. The layouts of the session daemon's other
+    /// clients are adjusted the same way.
     pub fn adjust_other_split_cursors_for_event(&mut self, event: &Event) {
         let current_buffer_id = self.active_buffer();
         let buffer_len = self
@@ -3286,6 +3406,15 @@ impl Window {
                     }
                 }
             }
+            if let Some((_, pos, _)) = new_cursors.first() {
+                let new_pos = (*pos).min(buffer_len);
+                for view_state in self.buffers.parked_view_states_mut() {
+                    if let Some(state) = view_state.buffer_state_mut(current_buffer_id) {
+                        state.cursors.primary_mut().position = new_pos;
+                        state.cursors.primary_mut().anchor = None;
+                    }
+                }
+            }
             return;
         }
 
@@ -3296,6 +3425,12 @@ impl Window {
             Event::Delete { range, .. } => {
                 vec![(range.start, range.len(), 0)]
             }
+            Event::ReplaceBytes {
+                position,
+                deleted,
+                inserted,
+                ..
+            } => vec![(*position, deleted.len(), inserted.len())],
             Event::Batch { events, .. } => events
                 .iter()
                 .filter_map(|e| match e {
@@ -3323,6 +3458,13 @@ impl Window {
                 }
             }
         }
+        for view_state in self.buffers.parked_view_states_mut() {
+            if let Some(state) = view_state.buffer_state_mut(current_buffer_id) {
+                for (edit_pos, old_len, new_len) in &adjustments {
+                    state.cursors.adjust_for_edit(*edit_pos, *old_len, *new_len);
+                }
+            }
+        }
     }
 
     /// Handle scroll events using the active split's viewport.
@@ -245,12 +245,13 @@ impl Editor {
                                 state.buffer.insert(0, &text);
                                 state.buffer.set_modified(true);
                                 state.buffer.set_recovery_pending(false);
-                                // Invalidate saved position so undo can't
-                                // incorrectly clear the modified flag
+                                // Drop the history of the replaced content, and the
+                                // saved position so undo can't incorrectly clear
+                                // the modified flag
                                 if let Some(log) =
                                     self.active_window_mut().event_logs.get_mut(&buffer_id)
                                 {
-                                    log.clear_saved_position();
+                                    log.discard_history();
                                 }
                                 mutated = true;
                                 recovered += 1;
@@ -287,12 +288,13 @@ impl Editor {
                             }
                             state.buffer.set_modified(true);
                             state.buffer.set_recovery_pending(false);
-                            // Invalidate saved position so undo can't
-                            // incorrectly clear the modified flag
+                            // Drop the history of the patched content, and the
+                            // saved position so undo can't incorrectly clear
+                            // the modified flag
                             if let Some(log) =
                                 self.active_window_mut().event_logs.get_mut(&buffer_id)
                             {
-                                log.clear_saved_position();
+                                log.discard_history();
                             }
                             mutated = true;
                             recovered += 1;
@@ -374,7 +376,14 @@ impl Editor {
         win.sync_terminal_backing_files();
         win.save_all_global_file_states();
 
-        let workspace = win.capture_workspace();
+        let mut workspace = win.capture_workspace();
+        if self.config.clipboard.persist_history {
+            workspace.clipboard_history = self
+                .clipboard
+                .history()
+                .map(|entry| entry.chunks.clone())
+                .collect();
+        }
 
         // Refuse to overwrite a non-empty on-disk workspace with an
         // all-virtual snapshot (issue #2027). The protection is for
@@ -438,6 +447,12 @@ impl Editor {
 
         // Editor-global config overrides (the shared `Config`).
         self.restore_config_overrides(&workspace.config_overrides);
+        // The clipboard history is editor-global too: the first restored
+        // workspace that carries one seeds it.
+        if self.config.clipboard.persist_history && self.clipboard.history_len() == 0 {
+            self.clipboard
+                .set_history(workspace.clipboard_history.clone());
+        }
         // Editor-global plugin state is NOT taken from per-window
         // workspace files: it has a single canonical home in the
         // global `orchestrator/state/` store, loaded once at boot.
@@ -496,6 +511,8 @@ impl Editor {
         // buffer_activated for the restored active buffer. Background
         // (inactive) window restores must NOT fire these focus effects.
         if id == self.active_window {
+            // The workspace's named macros join the command palette.
+            self.sync_named_macro_commands();
             #[cfg(feature = "plugins")]
             {
                 let buffer_id = self.active_buffer();
@@ -1915,7 +1932,11 @@ impl crate::app::window::Window {
                         }
                     }
                     if let Some(log) = self.event_logs.get_mut(&buffer_id) {
-                        log.clear_saved_position();
+                        if mutated {
+                            log.discard_history();
+                        } else {
+                            log.clear_saved_position();
+                        }
                     }
                     if mutated {
                         self.sync_lsp_after_recovery_replay(buffer_id);
@@ -1944,7 +1965,11 @@ impl crate::app::window::Window {
                         );
                     }
                     if let Some(log) = self.event_logs.get_mut(&buffer_id) {
-                        log.clear_saved_position();
+                        if mutated {
+                            log.discard_history();
+                        } else {
+                            log.clear_saved_position();
+                        }
                     }
                     if mutated {
                         self.sync_lsp_after_recovery_replay(buffer_id);
@@ -2040,6 +2065,7 @@ impl crate::app::window::Window {
         }
 
         self.restore_bookmarks_from_workspace(&workspace.bookmarks, &path_to_buffer);
+        self.macros.set_named(workspace.macros.clone());
         self.clean_orphaned_buffers();
         self.log_restore_summary(session_name);
 
@@ -2295,6 +2321,10 @@ impl crate::app::window::Window {
             histories,
             search_options,
             bookmarks,
+            // The clipboard is editor-global; `Editor::save_workspace_for`
+            // fills this in when the history is persisted.
+            clipboard_history: Vec::new(),
+            macros: self.macros.named_macros().clone(),
             terminals,
             external_files,
             read_only_files,
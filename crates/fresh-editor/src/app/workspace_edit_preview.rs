@@ -0,0 +1,474 @@
+//! Review of multi-file LSP edits before they are applied.
+//!
+//! A rename, code action or server-applied edit that reaches beyond one
+//! file — or creates, renames or deletes files — is flattened into a list
+//! of changes and handed to plugins through the `workspace_edit_preview`
+//! hook. The bundled `refactor_preview.ts` shows them as a diff with a
+//! checkbox per change, then answers with the changes to leave out
+//! (`applyWorkspaceEditPreview`) or drops the edit
+//! (`cancelWorkspaceEditPreview`). Without a plugin listening, or with
+//! `preview_workspace_edits` off, edits are applied straight away.
+//!
+//! Edits are positions in the text as it was when the preview opened, so
+//! the preview is refused if any file it edits has changed since.
+//!
+//! The kept text changes land as one bulk edit per file, linked so that
+//! undo or redo in any of the files takes all of them along (see
+//! [`super::undo_actions::LinkedUndo`]).
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
+
+use crate::model::event::BufferId;
+use crate::services::plugins::hooks::{HookArgs, WorkspaceEditChange};
+
+use super::undo_actions::LinkedUndo;
+use super::Editor;
+
+/// Where a workspace edit came from, which decides the title of its preview
+/// and what follows once it is applied or dropped.
+#[derive(Debug)]
+pub(crate) enum EditOrigin {
+    Rename,
+    /// A code action, with the command to run after its edit
+    CodeAction {
+        title: String,
+        command: Option<lsp_types::Command>,
+    },
+    /// `workspace/applyEdit` from a server
+    Server {
+        label: Option<String>,
+    },
+    /// Edits for an explorer operation waiting in `pending_file_operation`
+    FileOperation,
+}
+
+#[derive(Debug)]
+enum PreviewChange {
+    Text {
+        path: PathBuf,
+        edit: lsp_types::TextEdit,
+    },
+    Resource(lsp_types::ResourceOp),
+}
+
+/// What a file a preview edits was like when the preview opened.
+#[derive(Debug, PartialEq)]
+enum FileVersion {
+    /// Open in this buffer, at this version
+    Buffer(BufferId, u64),
+    /// Not open; fingerprint of the file on disk, `None` if unreadable
+    Disk(Option<String>),
+}
+
+/// A workspace edit waiting for the preview plugin's verdict.
+#[derive(Debug)]
+pub(crate) struct WorkspaceEditPreview {
+    id: u64,
+    origin: EditOrigin,
+    changes: Vec<PreviewChange>,
+    /// Each edited file as it was when the preview opened
+    versions: HashMap<PathBuf, FileVersion>,
+}
+
+impl Editor {
+    /// Whether multi-file edits go to the preview plugin.
+    pub(super) fn can_preview_workspace_edits(&self) -> bool {
+        self.config.editor.preview_workspace_edits
+            && self
+                .plugin_manager
+                .read()
+                .unwrap()
+                .has_subscribers("workspace_edit_preview")
+    }
+
+    /// Apply `edit` and follow up on `origin`, or hold both back for review
+    /// when the edit spans several files.
+    pub(crate) fn apply_or_preview_workspace_edit(
+        &mut self,
+        edit: lsp_types::WorkspaceEdit,
+        origin: EditOrigin,
+    ) -> AnyhowResult<()> {
+        if !self.can_preview_workspace_edits() {
+            let count = self.apply_workspace_edit(edit)?;
+            self.follow_up_workspace_edit(origin, count);
+            return Ok(());
+        }
+
+        let changes = self.flatten_workspace_edit(edit);
+        let mut paths = HashSet::new();
+        let spans_files = changes.iter().any(|change| match change {
+            PreviewChange::Text { path, .. } => {
+                paths.insert(path.clone());
+                paths.len() > 1
+            }
+            PreviewChange::Resource(_) => true,
+        });
+        // The explorer asked about these edits anyway, so even one file's
+        // worth is shown rather than applied unseen.
+        if !spans_files && !matches!(origin, EditOrigin::FileOperation) {
+            return self.apply_preview_changes(changes, origin);
+        }
+
+        let id = self.active_window_mut().alloc_lsp_request_id();
+        let title = match &origin {
+            EditOrigin::Rename => t!("lsp.edit_preview_rename").to_string(),
+            EditOrigin::CodeAction { title, .. } => title.clone(),
+            EditOrigin::Server { label } => label
+                .clone()
+                .unwrap_or_else(|| t!("lsp.edit_preview_server").to_string()),
+            EditOrigin::FileOperation => t!("lsp.edit_preview_file_operation").to_string(),
+        };
+        let (hook_changes, texts) = self.describe_preview_changes(&changes);
+        let mut versions = HashMap::new();
+        for change in &changes {
+            if let PreviewChange::Text { path, .. } = change {
+                if !versions.contains_key(path) {
+                    versions.insert(path.clone(), self.file_version(path));
+                }
+            }
+        }
+        self.active_window_mut().pending_edit_preview = Some(WorkspaceEditPreview {
+            id,
+            origin,
+            changes,
+            versions,
+        });
+        self.plugin_manager.read().unwrap().run_hook(
+            "workspace_edit_preview",
+            HookArgs::WorkspaceEditPreview {
+                id,
+                title,
+                changes: hook_changes,
+                texts,
+            },
+        );
+        Ok(())
+    }
+
+    /// Apply preview `id` without the changes listed in `excluded`.
+    pub(crate) fn apply_workspace_edit_preview(&mut self, id: u64, excluded: &[usize]) {
+        let Some(preview) = self.take_edit_preview(id) else {
+            return;
+        };
+        if self.is_stale_edit_preview(&preview) {
+            if let EditOrigin::FileOperation = preview.origin {
+                self.cancel_file_operation();
+            }
+            self.set_status_message(t!("lsp.edit_preview_stale").to_string());
+            return;
+        }
+        let excluded: HashSet<usize> = excluded.iter().copied().collect();
+        let changes = preview
+            .changes
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !excluded.contains(index))
+            .map(|(_, change)| change)
+            .collect();
+        if let Err(e) = self.apply_preview_changes(changes, preview.origin) {
+            tracing::error!("Failed to apply workspace edit: {}", e);
+            self.set_status_message(
+                t!("lsp.edit_preview_failed", error = e.to_string()).to_string(),
+            );
+        }
+    }
+
+    /// Drop preview `id`, along with whatever was waiting on it.
+    pub(crate) fn cancel_workspace_edit_preview(&mut self, id: u64) {
+        let Some(preview) = self.take_edit_preview(id) else {
+            return;
+        };
+        match preview.origin {
+            EditOrigin::FileOperation => self.cancel_file_operation(),
+            _ => self.set_status_message(t!("lsp.edit_preview_cancelled").to_string()),
+        }
+    }
+
+    fn take_edit_preview(&mut self, id: u64) -> Option<WorkspaceEditPreview> {
+        let preview = self
+            .active_window_mut()
+            .pending_edit_preview
+            .take_if(|preview| preview.id == id);
+        if preview.is_none() {
+            tracing::debug!("Ignoring stale workspace edit preview: {}", id);
+        }
+        preview
+    }
+
+    /// Whether a file `preview` edits changed after it opened: its buffer
+    /// was edited, closed or reopened, or a file that wasn't open now has
+    /// unsaved changes or different content on disk.
+    fn is_stale_edit_preview(&self, preview: &WorkspaceEditPreview) -> bool {
+        preview.versions.iter().any(|(path, then)| match then {
+            FileVersion::Buffer(..) => self.file_version(path) != *then,
+            FileVersion::Disk(hash) => {
+                self.open_buffer_version(path)
+                    .is_some_and(|(_, _, modified)| modified)
+                    || self.disk_fingerprint(path) != *hash
+            }
+        })
+    }
+
+    /// `path` as it is now: its buffer's version if open, else its
+    /// fingerprint on disk.
+    fn file_version(&self, path: &Path) -> FileVersion {
+        match self.open_buffer_version(path) {
+            Some((id, version, _)) => FileVersion::Buffer(id, version),
+            None => FileVersion::Disk(self.disk_fingerprint(path)),
+        }
+    }
+
+    /// Fingerprint of `path` on disk, as persistent undo takes it.
+    fn disk_fingerprint(&self, path: &Path) -> Option<String> {
+        crate::services::recovery::content_hash(
+            self.authority().filesystem.as_ref(),
+            path,
+            self.config.editor.large_file_threshold_bytes,
+        )
+        .ok()
+    }
+
+    /// The buffer `path` is open in, with its version and whether it has
+    /// unsaved changes.
+    fn open_buffer_version(&self, path: &Path) -> Option<(BufferId, u64, bool)> {
+        self.buffers()
+            .iter()
+            .find(|(_, state)| state.buffer.file_path() == Some(path))
+            .map(|(id, state)| (*id, state.buffer.version(), state.buffer.is_modified()))
+    }
+
+    /// Split `edit` into single text edits and resource operations, in the
+    /// order `apply_workspace_edit` would apply them.
+    fn flatten_workspace_edit(&self, edit: lsp_types::WorkspaceEdit) -> Vec<PreviewChange> {
+        use lsp_types::{DocumentChangeOperation, DocumentChanges, OneOf};
+
+        let translation = self.authority().path_translation.as_ref();
+        let to_path = |uri: lsp_types::Uri| {
+            super::lsp_uri_to_host_path(&super::types::LspUri::from_wire(uri), translation).ok()
+        };
+        let mut changes = Vec::new();
+
+        // `changes` is a map; list its files in a stable order.
+        let mut by_file: Vec<_> = edit
+            .changes
+            .into_iter()
+            .flatten()
+            .filter_map(|(uri, edits)| Some((to_path(uri)?, edits)))
+            .collect();
+        by_file.sort_by(|a, b| a.0.cmp(&b.0));
+        for (path, edits) in by_file {
+            changes.extend(edits.into_iter().map(|edit| PreviewChange::Text {
+                path: path.clone(),
+                edit,
+            }));
+        }
+
+        let document_edit = |changes: &mut Vec<PreviewChange>, doc: lsp_types::TextDocumentEdit| {
+            if self.is_stale_text_document_edit(&doc) {
+                return;
+            }
+            let Some(path) = to_path(doc.text_document.uri) else {
+                return;
+            };
+            changes.extend(doc.edits.into_iter().map(|edit| PreviewChange::Text {
+                path: path.clone(),
+                edit: match edit {
+                    OneOf::Left(edit) => edit,
+                    OneOf::Right(annotated) => annotated.text_edit,
+                },
+            }));
+        };
+        match edit.document_changes {
+            Some(DocumentChanges::Edits(docs)) => {
+                for doc in docs {
+                    document_edit(&mut changes, doc);
+                }
+            }
+            Some(DocumentChanges::Operations(ops)) => {
+                for op in ops {
+                    match op {
+                        DocumentChangeOperation::Edit(doc) => document_edit(&mut changes, doc),
+                        DocumentChangeOperation::Op(op) => {
+                            changes.push(PreviewChange::Resource(op));
+                        }
+                    }
+                }
+            }
+            None => {}
+        }
+        changes
+    }
+
+    /// The changes as the preview plugin sees them, with the content of
+    /// each file their text edits apply to.
+    fn describe_preview_changes(
+        &self,
+        changes: &[PreviewChange],
+    ) -> (Vec<WorkspaceEditChange>, HashMap<String, String>) {
+        let translation = self.authority().path_translation.as_ref();
+        let to_path = |uri: &lsp_types::Uri| {
+            super::types::LspUri::from_wire(uri.clone())
+                .to_host_path(translation)
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_else(|| uri.as_str().to_string())
+        };
+        let mut texts: HashMap<String, String> = HashMap::new();
+
+        let changes = changes
+            .iter()
+            .enumerate()
+            .map(|(id, change)| match change {
+                PreviewChange::Text { path, edit } => {
+                    let path_str = path.to_string_lossy().into_owned();
+                    if !texts.contains_key(&path_str) {
+                        texts.insert(path_str.clone(), self.text_for_preview(path));
+                    }
+                    WorkspaceEditChange {
+                        id,
+                        kind: "edit".to_string(),
+                        path: path_str,
+                        new_path: None,
+                        range: Some(edit.range),
+                        new_text: edit.new_text.clone(),
+                    }
+                }
+                PreviewChange::Resource(op) => {
+                    let (kind, path, new_path) = match op {
+                        lsp_types::ResourceOp::Create(c) => ("create", to_path(&c.uri), None),
+                        lsp_types::ResourceOp::Rename(r) => {
+                            ("rename", to_path(&r.old_uri), Some(to_path(&r.new_uri)))
+                        }
+                        lsp_types::ResourceOp::Delete(d) => ("delete", to_path(&d.uri), None),
+                    };
+                    WorkspaceEditChange {
+                        id,
+                        kind: kind.to_string(),
+                        path,
+                        new_path,
+                        range: None,
+                        new_text: String::new(),
+                    }
+                }
+            })
+            .collect();
+        (changes, texts)
+    }
+
+    /// Content of `path` as edits will see it: the open buffer if there is
+    /// one, the file on disk otherwise.
+    fn text_for_preview(&self, path: &Path) -> String {
+        self.buffers()
+            .iter()
+            .find(|(_, state)| state.buffer.file_path() == Some(path))
+            .and_then(|(_, state)| state.buffer.to_string())
+            .or_else(|| {
+                self.authority()
+                    .filesystem
+                    .read_file(path)
+                    .ok()
+                    .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Apply `changes` in order, each file's text edits as one bulk edit,
+    /// then follow up on `origin`.
+    fn apply_preview_changes(
+        &mut self,
+        changes: Vec<PreviewChange>,
+        origin: EditOrigin,
+    ) -> AnyhowResult<()> {
+        // Buffers touched so far, with the undo state each started from.
+        // Their logs stay in an undo group until the end, so a buffer that
+        // gets edits on both sides of a resource operation still undoes
+        // them in one step.
+        let mut touched: Vec<(BufferId, u64)> = Vec::new();
+        let result = self.apply_preview_changes_grouped(changes, &mut touched);
+
+        let mut linked = LinkedUndo::default();
+        for (buffer_id, before) in touched {
+            if let Some(log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
+                log.end_undo_group();
+                linked.push(buffer_id, before, log.current_state());
+            }
+        }
+        self.link_undo(linked);
+
+        let count = result?;
+        self.follow_up_workspace_edit(origin, count);
+        Ok(())
+    }
+
+    /// Report `count` applied changes and carry on with what was waiting
+    /// for them.
+    fn follow_up_workspace_edit(&mut self, origin: EditOrigin, count: usize) {
+        match origin {
+            EditOrigin::Rename => {
+                self.set_status_message(t!("lsp.renamed", count = count).to_string());
+            }
+            EditOrigin::CodeAction { title, command } => {
+                self.set_status_message(
+                    t!("lsp.code_action_applied", title = &title, count = count).to_string(),
+                );
+                if let Some(command) = command {
+                    self.send_execute_command(command);
+                }
+            }
+            EditOrigin::Server { label } => {
+                if let Some(label) = label {
+                    self.set_status_message(
+                        t!("lsp.code_action_applied", title = &label, count = count).to_string(),
+                    );
+                }
+            }
+            EditOrigin::FileOperation => self.finish_file_operation(false),
+        }
+    }
+
+    fn apply_preview_changes_grouped(
+        &mut self,
+        changes: Vec<PreviewChange>,
+        touched: &mut Vec<(BufferId, u64)>,
+    ) -> AnyhowResult<usize> {
+        let mut total = 0;
+        // Text edits collected since the last resource operation, by file
+        let mut pending: Vec<(PathBuf, Vec<lsp_types::TextEdit>)> = Vec::new();
+        let mut changes = changes.into_iter().peekable();
+
+        while let Some(change) = changes.next() {
+            match change {
+                PreviewChange::Text { path, edit } => {
+                    match pending.iter_mut().find(|(p, _)| *p == path) {
+                        Some((_, edits)) => edits.push(edit),
+                        None => pending.push((path, vec![edit])),
+                    }
+                }
+                PreviewChange::Resource(op) => {
+                    self.apply_resource_operation(op)?;
+                    total += 1;
+                }
+            }
+            let flush = !matches!(changes.peek(), Some(PreviewChange::Text { .. }));
+            if !flush {
+                continue;
+            }
+            for (path, edits) in pending.drain(..) {
+                let Some(buffer_id) = self.open_file_for_workspace_edit(&path) else {
+                    continue;
+                };
+                if !touched.iter().any(|(id, _)| *id == buffer_id) {
+                    if let Some(log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
+                        touched.push((buffer_id, log.current_state()));
+                        log.begin_undo_group();
+                    }
+                }
+                total += self.apply_lsp_text_edits(buffer_id, edits)?;
+            }
+        }
+        Ok(total)
+    }
+}
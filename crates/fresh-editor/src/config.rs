@@ -1,4 +1,7 @@
-use crate::types::{context_keys, LspFeature, LspLanguageConfig, LspServerConfig, ProcessLimits};
+use crate::types::{
+    context_keys, DebuggerConfig, LspFeature, LspLanguageConfig, LspServerConfig,
+    ProblemMatcherConfig, ProcessLimits, TaskConfig,
+};
 
 use rust_i18n::t;
 use schemars::JsonSchema;
@@ -376,6 +379,13 @@ pub struct Config {
     #[schemars(default = "default_keybinding_map_schema")]
     pub active_keybinding_map: KeybindingMapName,
 
+    /// Named keyboard macros, by name. Each macro is the list of actions it
+    /// replays, in the JSON form "Edit Macro" shows. Saved macros appear in
+    /// the command palette and can be bound to keys with the
+    /// `play_named_macro` action and a `name` argument.
+    #[serde(default)]
+    pub macros: HashMap<String, Vec<serde_json::Value>>,
+
     /// Per-language configuration overrides (tab size, formatters, etc.)
     #[serde(default)]
     pub languages: HashMap<String, LanguageConfig>,
@@ -409,6 +419,24 @@ pub struct Config {
     #[serde(default)]
     pub universal_lsp: HashMap<String, LspLanguageConfig>,
 
+    /// Debug adapter configurations by language.
+    /// Used by "Debug: Start" to launch or attach to a program via the
+    /// Debug Adapter Protocol.
+    #[serde(default)]
+    pub debuggers: HashMap<String, DebuggerConfig>,
+
+    /// Project tasks by name (build, test, lint, ...), run with
+    /// "Run Task". Cargo, npm and Makefile tasks are detected
+    /// automatically; an entry here with the same name replaces the
+    /// detected one.
+    #[serde(default)]
+    pub tasks: HashMap<String, TaskConfig>,
+
+    /// Problem matchers by name, for tasks to refer to in addition to
+    /// the built-in ones.
+    #[serde(default)]
+    pub problem_matchers: HashMap<String, ProblemMatcherConfig>,
+
     /// Warning notification settings
     #[serde(default)]
     pub warnings: WarningsConfig,
@@ -971,6 +999,12 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub highlight_current_column: bool,
 
+    /// Pin the header lines of the scopes enclosing the top of the view
+    /// (functions, classes, blocks) above the content while scrolling
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub sticky_scroll: bool,
+
     /// Wrap long lines to fit the window width (default for new views)
     #[serde(default = "default_true")]
     #[schemars(extend("x-section" = "Display"))]
@@ -1081,6 +1115,16 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_horizontal_scrollbar: bool,
 
+    /// Whether a minimap is shown beside the vertical scrollbar in each split pane.
+    /// The minimap draws a compressed view of the document with marks for diagnostics,
+    /// search matches, git changes and cursors; click or drag it to scroll.
+    /// Files above the large-file threshold show the marks only.
+    /// Can be toggled at runtime via command palette or the View menu.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_minimap: bool,
+
     /// Show tilde (~) markers on lines after the end of the file.
     /// These vim-style markers indicate lines that are not part of the file content.
     /// Default: true
@@ -1317,6 +1361,14 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "LSP"))]
     pub enable_semantic_tokens_full: bool,
 
+    /// Review multi-file LSP edits (renames, code actions, server-applied
+    /// edits) in a preview panel before they are applied.
+    /// Edits confined to one file are applied straight away.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "LSP"))]
+    pub preview_workspace_edits: bool,
+
     /// Whether to show inline diagnostic text at the end of lines with errors/warnings.
     /// When enabled, the highest-severity diagnostic message is rendered after the
     /// source code on each affected line.
@@ -1444,6 +1496,44 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_recovery_save_interval_secs: u32,
 
+    /// Keep undo history across editor restarts.
+    /// On save, the undo history is written next to the recovery data and
+    /// restored when the file is opened again, as long as the file wasn't
+    /// changed outside the editor in between.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub persistent_undo: bool,
+
+    /// Keep a local history of saved versions for every file.
+    /// Each save stores a snapshot of the file, which the "File History"
+    /// command can diff against or restore. Useful outside version control.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub local_history_enabled: bool,
+
+    /// Maximum number of saved versions kept per file in local history.
+    /// Default: 50
+    #[serde(default = "default_local_history_max_entries")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub local_history_max_entries: usize,
+
+    /// Saved versions older than this many days are dropped from local history.
+    /// 0 keeps versions regardless of age.
+    /// Default: 30
+    #[serde(default = "default_local_history_max_age_days")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub local_history_max_age_days: u32,
+
+    /// Maximum total size in bytes of the saved versions kept for one file.
+    /// The oldest versions are dropped first; files larger than this are
+    /// not recorded at all.
+    /// Default: 10485760 (10 MB)
+    #[serde(default = "default_local_history_max_size_bytes")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub local_history_max_size_bytes: u64,
+
     /// Poll interval in milliseconds for auto-reverting open buffers.
     /// When auto-revert is enabled, file modification times are checked at this interval.
     /// Lower values detect external changes faster but use more CPU.
@@ -1608,6 +1698,18 @@ fn default_auto_recovery_save_interval() -> u32 {
     2 // 2 seconds between recovery saves
 }
 
+fn default_local_history_max_entries() -> usize {
+    50
+}
+
+fn default_local_history_max_age_days() -> u32 {
+    30
+}
+
+fn default_local_history_max_size_bytes() -> u64 {
+    10 * 1024 * 1024 // 10 MB per file
+}
+
 fn default_highlight_context_bytes() -> usize {
     10_000 // 10KB context for accurate syntax highlighting
 }
@@ -1650,6 +1752,7 @@ impl Default for EditorConfig {
             highlight_occurrences: true,
             hide_current_line_on_selection: false,
             highlight_current_column: false,
+            sticky_scroll: false,
             line_wrap: true,
             wrap_indent: true,
             wrap_column: None,
@@ -1660,6 +1763,7 @@ impl Default for EditorConfig {
             estimated_line_length: default_estimated_line_length(),
             enable_inlay_hints: true,
             enable_semantic_tokens_full: false,
+            preview_workspace_edits: true,
             diagnostics_inline_text: false,
             auto_save_enabled: false,
             auto_save_interval_secs: default_auto_save_interval(),
@@ -1670,6 +1774,11 @@ impl Default for EditorConfig {
             auto_create_empty_buffer_on_last_buffer_close: true,
             recovery_enabled: true,
             auto_recovery_save_interval_secs: default_auto_recovery_save_interval(),
+            persistent_undo: false,
+            local_history_enabled: true,
+            local_history_max_entries: default_local_history_max_entries(),
+            local_history_max_age_days: default_local_history_max_age_days(),
+            local_history_max_size_bytes: default_local_history_max_size_bytes(),
             highlight_context_bytes: default_highlight_context_bytes(),
             mouse_hover_enabled: default_mouse_hover_enabled(),
             mouse_hover_delay_ms: default_mouse_hover_delay(),
@@ -1702,6 +1811,7 @@ impl Default for EditorConfig {
             show_prompt_line: false,
             show_vertical_scrollbar: true,
             show_horizontal_scrollbar: false,
+            show_minimap: false,
             show_tilde: true,
             use_terminal_bg: false,
             set_window_title: true,
@@ -2065,6 +2175,20 @@ pub struct ClipboardConfig {
     /// Disable this if you don't have a display server or it causes issues
     #[serde(default = "default_true")]
     pub use_system_clipboard: bool,
+
+    /// Number of copied texts kept for Paste from History and yank-pop
+    /// (default: 30). 0 disables the clipboard history.
+    #[serde(default = "default_clipboard_history_size")]
+    pub history_size: usize,
+
+    /// Save the clipboard history in the workspace file so it survives
+    /// restarts (default: false)
+    #[serde(default)]
+    pub persist_history: bool,
+}
+
+fn default_clipboard_history_size() -> usize {
+    30
 }
 
 impl Default for ClipboardConfig {
@@ -2072,6 +2196,8 @@ impl Default for ClipboardConfig {
         Self {
             use_osc52: true,
             use_system_clipboard: true,
+            history_size: default_clipboard_history_size(),
+            persist_history: false,
         }
     }
 }
@@ -2443,6 +2569,20 @@ pub struct LanguageConfig {
     /// grammar. See `IndentRulesConfig`.
     #[serde(default)]
     pub indent: Option<IndentRulesConfig>,
+
+    /// Whether to mirror edits into the language server's linked editing
+    /// ranges (`textDocument/linkedEditingRange`), so renaming an HTML/JSX
+    /// opening tag updates its closing tag as you type.
+    /// Only takes effect when the server supports it.
+    #[serde(default = "default_true")]
+    pub linked_editing: bool,
+
+    /// Whether to let the language server reformat after typing one of its
+    /// trigger characters, such as `;`, `}` or newline
+    /// (`textDocument/onTypeFormatting`).
+    /// Only takes effect when the server supports it.
+    #[serde(default = "default_true")]
+    pub on_type_formatting: bool,
 }
 
 /// User-overridable auto-indentation rules for a language.
@@ -2805,11 +2945,15 @@ impl Default for Config {
             keybindings: vec![], // User customizations only; defaults come from active_keybinding_map
             keybinding_maps: HashMap::new(), // User-defined maps go here
             active_keybinding_map: default_keybinding_map_name(),
+            macros: HashMap::new(),
             languages: Self::default_languages(),
             default_language: None,
             lsp_enabled: true,
             lsp: Self::default_lsp_config(),
             universal_lsp: Self::default_universal_lsp_config(),
+            debuggers: HashMap::new(),
+            tasks: HashMap::new(),
+            problem_matchers: HashMap::new(),
             warnings: WarningsConfig::default(),
             plugins: HashMap::new(),
             packages: PackagesConfig::default(),
@@ -3087,6 +3231,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: Some(context_keys::HORIZONTAL_SCROLLBAR.to_string()),
                     },
+                    MenuItem::Action {
+                        label: t!("menu.view.minimap").to_string(),
+                        action: "toggle_minimap".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::MINIMAP.to_string()),
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.view.set_background").to_string(),
@@ -3309,6 +3460,20 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.selection.smart_expand_selection").to_string(),
+                        action: "smart_expand_selection".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.selection.smart_shrink_selection").to_string(),
+                        action: "smart_shrink_selection".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.selection.add_cursor_above").to_string(),
@@ -3801,6 +3966,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -3832,6 +3999,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -3863,6 +4032,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -3898,6 +4069,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -3924,6 +4097,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -3955,6 +4130,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -3993,6 +4170,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4019,6 +4198,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4060,6 +4241,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4086,6 +4269,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4116,6 +4301,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4142,6 +4329,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4173,6 +4362,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4229,6 +4420,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4255,6 +4448,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4286,6 +4481,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4312,6 +4509,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4344,6 +4543,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4370,6 +4571,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4396,6 +4599,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4422,6 +4627,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4448,6 +4655,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4481,6 +4690,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4507,6 +4718,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4533,6 +4746,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4560,6 +4775,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4591,6 +4808,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4622,6 +4841,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4648,6 +4869,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4674,6 +4897,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4700,6 +4925,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4730,6 +4957,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4756,6 +4985,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4782,6 +5013,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4808,6 +5041,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4834,6 +5069,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4860,6 +5097,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4886,6 +5125,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4912,6 +5153,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4943,6 +5186,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4969,6 +5214,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -4995,6 +5242,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5021,6 +5270,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5047,6 +5298,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5073,6 +5326,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5104,6 +5359,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5130,6 +5387,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5156,6 +5415,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5182,6 +5443,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5208,6 +5471,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5234,6 +5499,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5265,6 +5532,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5291,6 +5560,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5320,6 +5591,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5348,6 +5621,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5378,6 +5653,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5404,6 +5681,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5430,6 +5709,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5456,6 +5737,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5482,6 +5765,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5508,6 +5793,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5534,6 +5821,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5560,6 +5849,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5586,6 +5877,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5612,6 +5905,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5638,6 +5933,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5664,6 +5961,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5690,6 +5989,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5718,6 +6019,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5744,6 +6047,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5770,6 +6075,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5796,6 +6103,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5822,6 +6131,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5852,6 +6163,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5878,6 +6191,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5904,6 +6219,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5930,6 +6247,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5956,6 +6275,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -5982,6 +6303,8 @@ impl Config {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -7821,6 +8144,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
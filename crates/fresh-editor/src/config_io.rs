@@ -929,6 +929,11 @@ impl DirectoryContext {
         self.data_dir.join("recovery")
     }
 
+    /// Get the local file history directory path (saved versions of files)
+    pub fn local_history_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("local-history")
+    }
+
     /// Get the workspaces directory path
     pub fn workspaces_dir(&self) -> std::path::PathBuf {
         self.data_dir.join("workspaces")
@@ -1022,6 +1027,11 @@ impl DirectoryContext {
         self.config_dir.join("plugins")
     }
 
+    /// Get the user snippets directory path
+    pub fn snippets_dir(&self) -> std::path::PathBuf {
+        self.config_dir.join("snippets")
+    }
+
     /// Get the default config directory path (static/internal version).
     ///
     /// This is used internally by `from_system()` to determine the config directory.
@@ -1104,6 +1114,24 @@ mod tests {
         drop(temp);
     }
 
+    #[test]
+    fn resolver_project_keeps_user_sticky_scroll() {
+        let (temp, resolver) = create_test_resolver();
+
+        let user_config_path = resolver.user_config_path();
+        std::fs::create_dir_all(user_config_path.parent().unwrap()).unwrap();
+        std::fs::write(&user_config_path, r#"{"editor": {"sticky_scroll": true}}"#).unwrap();
+
+        let project_config_path = resolver.project_config_path();
+        std::fs::create_dir_all(project_config_path.parent().unwrap()).unwrap();
+        std::fs::write(&project_config_path, r#"{"editor": {"tab_size": 8}}"#).unwrap();
+
+        let config = resolver.resolve().unwrap();
+        assert_eq!(config.editor.tab_size, 8);
+        assert!(config.editor.sticky_scroll); // User value preserved
+        drop(temp);
+    }
+
     #[test]
     fn resolver_session_overrides_all() {
         let (temp, resolver) = create_test_resolver();
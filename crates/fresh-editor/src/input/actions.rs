@@ -2621,6 +2621,8 @@ pub fn action_to_events(
         | Action::CopyRelativeFilePath
         | Action::Cut
         | Action::Paste
+        | Action::PasteFromHistory
+        | Action::YankPop
         | Action::YankWordForward
         | Action::YankWordBackward
         | Action::YankToLineEnd
@@ -2643,6 +2645,11 @@ pub fn action_to_events(
         | Action::ToggleLineWrap
         | Action::ToggleCurrentLineHighlight
         | Action::ToggleOccurrenceHighlight
+        | Action::ToggleStickyScroll
+        | Action::ToggleHexView
+        | Action::GotoOffset
+        | Action::SearchBytes
+        | Action::CopyAsHex
         | Action::ToggleReadOnly
         | Action::TogglePageView
         | Action::SetPageWidth
@@ -2654,6 +2661,17 @@ pub fn action_to_events(
         | Action::GoToMatchingBracket
         | Action::JumpToNextError
         | Action::JumpToPreviousError
+        | Action::DebugStart
+        | Action::DebugStop
+        | Action::DebugContinue
+        | Action::DebugPause
+        | Action::DebugStepOver
+        | Action::DebugStepInto
+        | Action::DebugStepOut
+        | Action::DebugToggleBreakpoint
+        | Action::RunTask
+        | Action::RerunLastTask
+        | Action::StopTask
         | Action::ShowKeyboardShortcuts
         | Action::ShowWarnings
         | Action::ShowStatusLog
@@ -2681,6 +2699,12 @@ pub fn action_to_events(
         | Action::PromptRecordMacro
         | Action::PromptPlayMacro
         | Action::PlayLastMacro
+        | Action::SaveMacro
+        | Action::SaveMacroToWorkspace
+        | Action::PlayNamedMacro(_)
+        | Action::EditNamedMacro
+        | Action::ApplyMacroToLines
+        | Action::ApplyMacroToMatches
         | Action::PromptSetBookmark
         | Action::PromptJumpToBookmark
         | Action::PromptConfirm
@@ -2731,6 +2755,7 @@ pub fn action_to_events(
         | Action::TogglePromptLine
         | Action::ToggleVerticalScrollbar
         | Action::ToggleHorizontalScrollbar
+        | Action::ToggleMinimap
         | Action::FocusFileExplorer
         | Action::FocusEditor
         | Action::ToggleDockFocus
@@ -2765,10 +2790,19 @@ pub fn action_to_events(
         | Action::LspCompletion
         | Action::LspGotoDefinition
         | Action::LspReferences
+        | Action::LspIncomingCalls
+        | Action::LspOutgoingCalls
+        | Action::LspSupertypes
+        | Action::LspSubtypes
         | Action::LspRename
         | Action::LspHover
         | Action::LspSignatureHelp
         | Action::LspCodeActions
+        | Action::LspCodeLens
+        | Action::LspOpenLink
+        | Action::LspColorPresentation
+        | Action::SmartExpandSelection
+        | Action::SmartShrinkSelection
         | Action::LspRestart
         | Action::LspStop
         | Action::LspToggleForBuffer
@@ -15,7 +15,7 @@ pub struct BufferMode {
     pub read_only: bool,
 
     /// When true, unbound character keys in a read-only mode are dispatched as
-    /// `PluginAction("mode_text_input:<char>")` instead of being silently dropped.
+    /// `PluginAction("mode_text_input@<mode>:<char>")` instead of being silently dropped.
     /// This allows plugins to handle inline text editing (e.g. search fields)
     /// without registering individual bindings for every character.
     pub allow_text_input: bool,
@@ -344,6 +344,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.paste_from_history",
+        desc_key: "cmd.paste_from_history_desc",
+        action: || Action::PasteFromHistory,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.delete_line",
         desc_key: "cmd.delete_line_desc",
@@ -471,6 +478,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.smart_expand_selection",
+        desc_key: "cmd.smart_expand_selection_desc",
+        action: || Action::SmartExpandSelection,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.smart_shrink_selection",
+        desc_key: "cmd.smart_shrink_selection_desc",
+        action: || Action::SmartShrinkSelection,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Multi-cursor
     CommandDef {
         name_key: "cmd.add_cursor_above",
@@ -828,6 +849,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal, FileExplorer, Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_minimap",
+        desc_key: "cmd.toggle_minimap_desc",
+        action: || Action::ToggleMinimap,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.focus_file_explorer",
         desc_key: "cmd.focus_file_explorer_desc",
@@ -920,6 +948,41 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_sticky_scroll",
+        desc_key: "cmd.toggle_sticky_scroll_desc",
+        action: || Action::ToggleStickyScroll,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_hex_view",
+        desc_key: "cmd.toggle_hex_view_desc",
+        action: || Action::ToggleHexView,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.goto_offset",
+        desc_key: "cmd.goto_offset_desc",
+        action: || Action::GotoOffset,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.search_bytes",
+        desc_key: "cmd.search_bytes_desc",
+        action: || Action::SearchBytes,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.copy_as_hex",
+        desc_key: "cmd.copy_as_hex_desc",
+        action: || Action::CopyAsHex,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_page_view",
         desc_key: "cmd.toggle_page_view_desc",
@@ -1069,6 +1132,34 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_incoming_calls",
+        desc_key: "cmd.show_incoming_calls_desc",
+        action: || Action::LspIncomingCalls,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_outgoing_calls",
+        desc_key: "cmd.show_outgoing_calls_desc",
+        action: || Action::LspOutgoingCalls,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_supertypes",
+        desc_key: "cmd.show_supertypes_desc",
+        action: || Action::LspSupertypes,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_subtypes",
+        desc_key: "cmd.show_subtypes_desc",
+        action: || Action::LspSubtypes,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_signature_help",
         desc_key: "cmd.show_signature_help_desc",
@@ -1083,6 +1174,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.run_code_lens",
+        desc_key: "cmd.run_code_lens_desc",
+        action: || Action::LspCodeLens,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.open_document_link",
+        desc_key: "cmd.open_document_link_desc",
+        action: || Action::LspOpenLink,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.pick_color_presentation",
+        desc_key: "cmd.pick_color_presentation_desc",
+        action: || Action::LspColorPresentation,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.start_restart_lsp",
         desc_key: "cmd.start_restart_lsp_desc",
@@ -1162,6 +1274,85 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    // Debugger
+    CommandDef {
+        name_key: "cmd.debug_start",
+        desc_key: "cmd.debug_start_desc",
+        action: || Action::DebugStart,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_stop",
+        desc_key: "cmd.debug_stop_desc",
+        action: || Action::DebugStop,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_continue",
+        desc_key: "cmd.debug_continue_desc",
+        action: || Action::DebugContinue,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_pause",
+        desc_key: "cmd.debug_pause_desc",
+        action: || Action::DebugPause,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_over",
+        desc_key: "cmd.debug_step_over_desc",
+        action: || Action::DebugStepOver,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_into",
+        desc_key: "cmd.debug_step_into_desc",
+        action: || Action::DebugStepInto,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_out",
+        desc_key: "cmd.debug_step_out_desc",
+        action: || Action::DebugStepOut,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_toggle_breakpoint",
+        desc_key: "cmd.debug_toggle_breakpoint_desc",
+        action: || Action::DebugToggleBreakpoint,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    // Tasks
+    CommandDef {
+        name_key: "cmd.run_task",
+        desc_key: "cmd.run_task_desc",
+        action: || Action::RunTask,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.rerun_last_task",
+        desc_key: "cmd.rerun_last_task_desc",
+        action: || Action::RerunLastTask,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.stop_task",
+        desc_key: "cmd.stop_task_desc",
+        action: || Action::StopTask,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // LSP
     CommandDef {
         name_key: "cmd.rename_symbol",
@@ -1213,6 +1404,48 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.save_macro",
+        desc_key: "cmd.save_macro_desc",
+        action: || Action::SaveMacro,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.save_macro_to_workspace",
+        desc_key: "cmd.save_macro_to_workspace_desc",
+        action: || Action::SaveMacroToWorkspace,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.play_named_macro",
+        desc_key: "cmd.play_named_macro_desc",
+        action: || Action::PlayNamedMacro(String::new()),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.edit_named_macro",
+        desc_key: "cmd.edit_named_macro_desc",
+        action: || Action::EditNamedMacro,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.apply_macro_to_lines",
+        desc_key: "cmd.apply_macro_to_lines_desc",
+        action: || Action::ApplyMacroToLines,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.apply_macro_to_matches",
+        desc_key: "cmd.apply_macro_to_matches_desc",
+        action: || Action::ApplyMacroToMatches,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.set_bookmark",
         desc_key: "cmd.set_bookmark_desc",
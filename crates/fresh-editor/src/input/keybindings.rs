@@ -400,6 +400,8 @@ pub enum Action {
     SelectWord,
     SelectLine,
     ExpandSelection,
+    SmartExpandSelection,
+    SmartShrinkSelection,
 
     // Block/rectangular selection (column-wise)
     BlockSelectLeft,
@@ -433,6 +435,11 @@ pub enum Action {
     CopyWithTheme(String),
     Cut,
     Paste,
+    /// Pick an entry of the clipboard history to paste.
+    PasteFromHistory,
+    /// Replace the text just pasted with the next older clipboard history
+    /// entry (Emacs `yank-pop`).
+    YankPop,
     /// Copy the absolute filesystem path of the active buffer's file to the clipboard.
     CopyFilePath,
     /// Copy the active buffer's file path relative to the workspace root, falling
@@ -484,6 +491,21 @@ pub enum Action {
     DabbrevExpand,
     ToggleFold,
 
+    // Debugger (Debug Adapter Protocol)
+    DebugStart,
+    DebugStop,
+    DebugContinue,
+    DebugPause,
+    DebugStepOver,
+    DebugStepInto,
+    DebugStepOut,
+    DebugToggleBreakpoint,
+
+    // Tasks
+    RunTask,
+    RerunLastTask,
+    StopTask,
+
     // Bookmarks
     SetBookmark(char),
     JumpToBookmark(char),
@@ -506,6 +528,12 @@ pub enum Action {
     PromptRecordMacro,
     PromptPlayMacro,
     PlayLastMacro,
+    SaveMacro,
+    SaveMacroToWorkspace,
+    PlayNamedMacro(String),
+    EditNamedMacro,
+    ApplyMacroToLines,
+    ApplyMacroToMatches,
 
     // Bookmarks (prompt-based)
     PromptSetBookmark,
@@ -551,6 +579,11 @@ pub enum Action {
     ToggleLineWrap,
     ToggleCurrentLineHighlight,
     ToggleOccurrenceHighlight,
+    ToggleStickyScroll,
+    ToggleHexView,
+    GotoOffset,
+    SearchBytes,
+    CopyAsHex,
     ToggleReadOnly,
     TogglePageView,
     SetPageWidth,
@@ -656,6 +689,7 @@ pub enum Action {
     // Scrollbar visibility
     ToggleVerticalScrollbar,
     ToggleHorizontalScrollbar,
+    ToggleMinimap,
     FocusFileExplorer,
     FocusEditor,
     /// Toggle keyboard focus between the editor/explorer area and the
@@ -693,10 +727,17 @@ pub enum Action {
     LspCompletion,
     LspGotoDefinition,
     LspReferences,
+    LspIncomingCalls,
+    LspOutgoingCalls,
+    LspSupertypes,
+    LspSubtypes,
     LspRename,
     LspHover,
     LspSignatureHelp,
     LspCodeActions,
+    LspCodeLens,
+    LspOpenLink,
+    LspColorPresentation,
     LspRestart,
     LspStop,
     LspToggleForBuffer,
@@ -951,6 +992,8 @@ impl Action {
             "select_word" => SelectWord,
             "select_line" => SelectLine,
             "expand_selection" => ExpandSelection,
+            "smart_expand_selection" => SmartExpandSelection,
+            "smart_shrink_selection" => SmartShrinkSelection,
 
             "block_select_left" => BlockSelectLeft,
             "block_select_right" => BlockSelectRight,
@@ -976,6 +1019,8 @@ impl Action {
             "copy" => Copy,
             "cut" => Cut,
             "paste" => Paste,
+            "paste_from_history" => PasteFromHistory,
+            "yank_pop" => YankPop,
             "copy_file_path" => CopyFilePath,
             "copy_relative_file_path" => CopyRelativeFilePath,
 
@@ -1012,6 +1057,18 @@ impl Action {
             "jump_to_next_error" => JumpToNextError,
             "jump_to_previous_error" => JumpToPreviousError,
 
+            "debug_start" => DebugStart,
+            "debug_stop" => DebugStop,
+            "debug_continue" => DebugContinue,
+            "debug_pause" => DebugPause,
+            "debug_step_over" => DebugStepOver,
+            "debug_step_into" => DebugStepInto,
+            "debug_step_out" => DebugStepOut,
+            "debug_toggle_breakpoint" => DebugToggleBreakpoint,
+            "run_task" => RunTask,
+            "rerun_last_task" => RerunLastTask,
+            "stop_task" => StopTask,
+
             "smart_home" => SmartHome,
             "dedent_selection" => DedentSelection,
             "toggle_comment" => ToggleComment,
@@ -1032,6 +1089,11 @@ impl Action {
             "prompt_record_macro" => PromptRecordMacro,
             "prompt_play_macro" => PromptPlayMacro,
             "play_last_macro" => PlayLastMacro,
+            "save_macro" => SaveMacro,
+            "save_macro_to_workspace" => SaveMacroToWorkspace,
+            "edit_named_macro" => EditNamedMacro,
+            "apply_macro_to_lines" => ApplyMacroToLines,
+            "apply_macro_to_matches" => ApplyMacroToMatches,
             "prompt_set_bookmark" => PromptSetBookmark,
             "prompt_jump_to_bookmark" => PromptJumpToBookmark,
 
@@ -1059,6 +1121,11 @@ impl Action {
             "toggle_line_wrap" => ToggleLineWrap,
             "toggle_current_line_highlight" => ToggleCurrentLineHighlight,
             "toggle_occurrence_highlight" => ToggleOccurrenceHighlight,
+            "toggle_sticky_scroll" => ToggleStickyScroll,
+            "toggle_hex_view" => ToggleHexView,
+            "goto_offset" => GotoOffset,
+            "search_bytes" => SearchBytes,
+            "copy_as_hex" => CopyAsHex,
             "toggle_read_only" => ToggleReadOnly,
             "toggle_page_view" => TogglePageView,
             "set_page_width" => SetPageWidth,
@@ -1134,6 +1201,7 @@ impl Action {
             "toggle_prompt_line" => TogglePromptLine,
             "toggle_vertical_scrollbar" => ToggleVerticalScrollbar,
             "toggle_horizontal_scrollbar" => ToggleHorizontalScrollbar,
+            "toggle_minimap" => ToggleMinimap,
             "focus_file_explorer" => FocusFileExplorer,
             "focus_editor" => FocusEditor,
             "toggle_dock_focus" => ToggleDockFocus,
@@ -1167,10 +1235,17 @@ impl Action {
             "lsp_completion" => LspCompletion,
             "lsp_goto_definition" => LspGotoDefinition,
             "lsp_references" => LspReferences,
+            "lsp_incoming_calls" => LspIncomingCalls,
+            "lsp_outgoing_calls" => LspOutgoingCalls,
+            "lsp_supertypes" => LspSupertypes,
+            "lsp_subtypes" => LspSubtypes,
             "lsp_rename" => LspRename,
             "lsp_hover" => LspHover,
             "lsp_signature_help" => LspSignatureHelp,
             "lsp_code_actions" => LspCodeActions,
+            "lsp_code_lens" => LspCodeLens,
+            "lsp_open_link" => LspOpenLink,
+            "lsp_color_presentation" => LspColorPresentation,
             "lsp_restart" => LspRestart,
             "lsp_stop" => LspStop,
             "lsp_toggle_for_buffer" => LspToggleForBuffer,
@@ -1308,6 +1383,11 @@ impl Action {
                 let text = args.get("text")?.as_str()?;
                 Self::PromptConfirmWithText(text.to_string())
             },
+            "play_named_macro" => PlayNamedMacro : {
+                // Empty name = open the named macro picker
+                let name = args.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                Self::PlayNamedMacro(name.to_string())
+            },
         }
     }
 
@@ -1321,6 +1401,7 @@ impl Action {
         match bare_action {
             "menu_open" => Some("name"),
             "switch_keybinding_map" => Some("map"),
+            "play_named_macro" => Some("name"),
             _ => None,
         }
     }
@@ -1345,6 +1426,9 @@ impl Action {
         match self {
             Self::MenuOpen(name) => format!("menu_open:{}", name),
             Self::SwitchKeybindingMap(map) => format!("switch_keybinding_map:{}", map),
+            Self::PlayNamedMacro(name) if !name.is_empty() => {
+                format!("play_named_macro:{}", name)
+            }
             other => other.to_action_str(),
         }
     }
@@ -1412,6 +1496,8 @@ impl Action {
                 | Action::SelectWord
                 | Action::SelectLine
                 | Action::ExpandSelection
+                | Action::SmartExpandSelection
+                | Action::SmartShrinkSelection
                 // Block selection
                 | Action::BlockSelectLeft
                 | Action::BlockSelectRight
@@ -1436,6 +1522,8 @@ impl Action {
                 // Clipboard editing (but not Copy)
                 | Action::Cut
                 | Action::Paste
+                | Action::PasteFromHistory
+                | Action::YankPop
                 // Undo/Redo
                 | Action::Undo
                 | Action::Redo
@@ -1465,6 +1553,8 @@ impl Action {
                 | Action::MoveLineDown
                 | Action::Cut
                 | Action::Paste
+                | Action::PasteFromHistory
+                | Action::YankPop
         )
     }
 }
@@ -2390,6 +2480,8 @@ impl KeybindingResolver {
             Action::SelectWord => t!("action.select_word"),
             Action::SelectLine => t!("action.select_line"),
             Action::ExpandSelection => t!("action.expand_selection"),
+            Action::SmartExpandSelection => t!("action.smart_expand_selection"),
+            Action::SmartShrinkSelection => t!("action.smart_shrink_selection"),
             Action::BlockSelectLeft => t!("action.block_select_left"),
             Action::BlockSelectRight => t!("action.block_select_right"),
             Action::BlockSelectUp => t!("action.block_select_up"),
@@ -2414,6 +2506,8 @@ impl KeybindingResolver {
             Action::CopyWithTheme(theme) => t!("action.copy_with_theme", theme = theme),
             Action::Cut => t!("action.cut"),
             Action::Paste => t!("action.paste"),
+            Action::PasteFromHistory => t!("action.paste_from_history"),
+            Action::YankPop => t!("action.yank_pop"),
             Action::CopyFilePath => t!("action.copy_file_path"),
             Action::CopyRelativeFilePath => t!("action.copy_relative_file_path"),
             Action::YankWordForward => t!("action.yank_word_forward"),
@@ -2446,6 +2540,17 @@ impl KeybindingResolver {
             Action::GoToMatchingBracket => t!("action.goto_matching_bracket"),
             Action::JumpToNextError => t!("action.jump_to_next_error"),
             Action::JumpToPreviousError => t!("action.jump_to_previous_error"),
+            Action::DebugStart => t!("action.debug_start"),
+            Action::DebugStop => t!("action.debug_stop"),
+            Action::DebugContinue => t!("action.debug_continue"),
+            Action::DebugPause => t!("action.debug_pause"),
+            Action::DebugStepOver => t!("action.debug_step_over"),
+            Action::DebugStepInto => t!("action.debug_step_into"),
+            Action::DebugStepOut => t!("action.debug_step_out"),
+            Action::DebugToggleBreakpoint => t!("action.debug_toggle_breakpoint"),
+            Action::RunTask => t!("action.run_task"),
+            Action::RerunLastTask => t!("action.rerun_last_task"),
+            Action::StopTask => t!("action.stop_task"),
             Action::SmartHome => t!("action.smart_home"),
             Action::DedentSelection => t!("action.dedent_selection"),
             Action::ToggleComment => t!("action.toggle_comment"),
@@ -2468,6 +2573,13 @@ impl KeybindingResolver {
             Action::PromptRecordMacro => t!("action.prompt_record_macro"),
             Action::PromptPlayMacro => t!("action.prompt_play_macro"),
             Action::PlayLastMacro => t!("action.play_last_macro"),
+            Action::SaveMacro => t!("action.save_macro"),
+            Action::SaveMacroToWorkspace => t!("action.save_macro_to_workspace"),
+            Action::PlayNamedMacro(name) if name.is_empty() => t!("action.prompt_play_named_macro"),
+            Action::PlayNamedMacro(name) => t!("action.play_named_macro", name = name),
+            Action::EditNamedMacro => t!("action.edit_named_macro"),
+            Action::ApplyMacroToLines => t!("action.apply_macro_to_lines"),
+            Action::ApplyMacroToMatches => t!("action.apply_macro_to_matches"),
             Action::PromptSetBookmark => t!("action.prompt_set_bookmark"),
             Action::PromptJumpToBookmark => t!("action.prompt_jump_to_bookmark"),
             Action::Undo => t!("action.undo"),
@@ -2494,6 +2606,11 @@ impl KeybindingResolver {
             Action::ToggleLineWrap => t!("action.toggle_line_wrap"),
             Action::ToggleCurrentLineHighlight => t!("action.toggle_current_line_highlight"),
             Action::ToggleOccurrenceHighlight => t!("action.toggle_occurrence_highlight"),
+            Action::ToggleStickyScroll => t!("action.toggle_sticky_scroll"),
+            Action::ToggleHexView => t!("action.toggle_hex_view"),
+            Action::GotoOffset => t!("action.goto_offset"),
+            Action::SearchBytes => t!("action.search_bytes"),
+            Action::CopyAsHex => t!("action.copy_as_hex"),
             Action::ToggleReadOnly => t!("action.toggle_read_only"),
             Action::TogglePageView => t!("action.toggle_page_view"),
             Action::SetPageWidth => t!("action.set_page_width"),
@@ -2563,6 +2680,7 @@ impl KeybindingResolver {
             Action::TogglePromptLine => t!("action.toggle_prompt_line"),
             Action::ToggleVerticalScrollbar => t!("action.toggle_vertical_scrollbar"),
             Action::ToggleHorizontalScrollbar => t!("action.toggle_horizontal_scrollbar"),
+            Action::ToggleMinimap => t!("action.toggle_minimap"),
             Action::FocusFileExplorer => t!("action.focus_file_explorer"),
             Action::FocusEditor => t!("action.focus_editor"),
             Action::ToggleDockFocus => t!("action.toggle_dock_focus"),
@@ -2597,10 +2715,17 @@ impl KeybindingResolver {
             Action::LspCompletion => t!("action.lsp_completion"),
             Action::LspGotoDefinition => t!("action.lsp_goto_definition"),
             Action::LspReferences => t!("action.lsp_references"),
+            Action::LspIncomingCalls => t!("action.lsp_incoming_calls"),
+            Action::LspOutgoingCalls => t!("action.lsp_outgoing_calls"),
+            Action::LspSupertypes => t!("action.lsp_supertypes"),
+            Action::LspSubtypes => t!("action.lsp_subtypes"),
             Action::LspRename => t!("action.lsp_rename"),
             Action::LspHover => t!("action.lsp_hover"),
             Action::LspSignatureHelp => t!("action.lsp_signature_help"),
             Action::LspCodeActions => t!("action.lsp_code_actions"),
+            Action::LspCodeLens => t!("action.lsp_code_lens"),
+            Action::LspOpenLink => t!("action.lsp_open_link"),
+            Action::LspColorPresentation => t!("action.lsp_color_presentation"),
             Action::LspRestart => t!("action.lsp_restart"),
             Action::LspStop => t!("action.lsp_stop"),
             Action::LspToggleForBuffer => t!("action.lsp_toggle_for_buffer"),
@@ -3035,6 +3160,24 @@ mod tests {
         assert_eq!(action.to_qualified_action_str(), "menu_open:Edit");
     }
 
+    #[test]
+    fn test_play_named_macro_qualified_roundtrip() {
+        let (bare, args) = Action::unqualify_action("play_named_macro:wrap lines");
+        assert_eq!(
+            Action::from_str(&bare, &args),
+            Some(Action::PlayNamedMacro("wrap lines".to_string()))
+        );
+        assert_eq!(
+            Action::PlayNamedMacro("wrap lines".to_string()).to_qualified_action_str(),
+            "play_named_macro:wrap lines"
+        );
+        // Without a name the action opens the macro picker.
+        assert_eq!(
+            Action::from_str("play_named_macro", &HashMap::new()),
+            Some(Action::PlayNamedMacro(String::new()))
+        );
+    }
+
     #[test]
     fn test_resolve_basic() {
         let config = Config::default();
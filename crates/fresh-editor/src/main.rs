@@ -107,6 +107,16 @@ struct Cli {
     #[arg(long, value_name = "LOCALE")]
     locale: Option<String>,
 
+    /// Send a JSON-RPC request to a running editor (`-` reads one per line
+    /// from stdin)
+    #[arg(long, value_name = "JSON")]
+    remote: Option<String>,
+
+    /// Daemon to send --remote requests to (defaults to the editor this
+    /// terminal runs in, then the current-directory daemon)
+    #[arg(long, value_name = "NAME", requires = "remote")]
+    remote_session: Option<String>,
+
     // === Hidden internal flags ===
     /// Start as a daemon server (internal)
     #[arg(long, hide = true)]
@@ -181,6 +191,8 @@ struct Args {
     kill: Option<Option<String>>,
     /// Open files in a daemon without attaching (session_name, files, wait)
     open_files_in_session: Option<(Option<String>, Vec<String>, bool)>,
+    /// Remote-control request (`--remote`) and target daemon
+    remote: Option<(String, Option<String>)>,
     /// Launch in GUI mode
     #[cfg(feature = "gui")]
     gui: bool,
@@ -464,6 +476,7 @@ impl From<Cli> for Args {
             session_name,
             kill,
             open_files_in_session,
+            remote: cli.remote.map(|request| (request, cli.remote_session)),
             #[cfg(feature = "gui")]
             gui: cli.gui,
         }
@@ -3003,6 +3016,106 @@ fn run_open_files_command(
     Ok(())
 }
 
+/// Send remote-control requests (`--remote`) to a running editor and print
+/// every response and notification as one JSON object per line.
+///
+/// Targets `--remote-session`, else the editor whose terminal we run in
+/// (`FRESH_SESSION`), else the current-directory daemon; an editor is never
+/// started. After a successful `subscribe`, hook notifications keep printing
+/// until the editor goes away. Exits with status 1 if any request failed.
+fn run_remote_command(request: &str, session_name: Option<&str>) -> AnyhowResult<()> {
+    use fresh::server::protocol::{ClientControl, RemoteRequest, ServerControl};
+    use std::io::{BufRead, BufReader, Write};
+
+    let nested = std::env::var("FRESH_SESSION")
+        .ok()
+        .filter(|s| !s.trim().is_empty());
+    let socket_paths = resolve_session(session_name.or(nested.as_deref()))?;
+    socket_paths.cleanup_if_stale();
+    if !socket_paths.is_server_alive() {
+        anyhow::bail!("No running editor to send the request to");
+    }
+
+    let conn = fresh::server::ipc::ClientConnection::connect(&socket_paths)?;
+    if !client_handshake(&conn)? {
+        std::process::exit(1);
+    }
+
+    let requests: Box<dyn Iterator<Item = io::Result<String>>> = if request == "-" {
+        Box::new(io::stdin().lock().lines())
+    } else {
+        Box::new(std::iter::once(Ok(request.to_string())))
+    };
+
+    // One reader for the whole session: `read_control` makes a fresh
+    // `BufReader` per call and would drop lines buffered past a response.
+    let mut reader = BufReader::new(&conn.control);
+    let mut read_message = || -> AnyhowResult<Option<ServerControl>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&line)?))
+    };
+    let mut out = stdout().lock();
+
+    let mut failed = false;
+    let mut subscribed = false;
+    let mut next_id = 1u64;
+    for line in requests {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut request: RemoteRequest =
+            serde_json::from_str(&line).with_context(|| format!("Invalid request: {}", line))?;
+        if request.id.is_null() {
+            request.id = next_id.into();
+            next_id += 1;
+        }
+        let method = request.method.clone();
+        conn.write_control(&serde_json::to_string(&ClientControl::Remote(request))?)?;
+
+        loop {
+            match read_message()? {
+                Some(ServerControl::RemoteResponse(response)) => {
+                    if response.error.is_some() {
+                        failed = true;
+                    } else if method == "subscribe" {
+                        subscribed = true;
+                    }
+                    writeln!(out, "{}", serde_json::to_string(&response)?)?;
+                    break;
+                }
+                Some(ServerControl::RemoteNotification(notification)) => {
+                    writeln!(out, "{}", serde_json::to_string(&notification)?)?;
+                }
+                Some(ServerControl::Quit { .. }) | None => {
+                    anyhow::bail!("The editor closed the connection")
+                }
+                Some(_) => {}
+            }
+        }
+        out.flush()?;
+    }
+
+    while subscribed {
+        match read_message()? {
+            Some(ServerControl::RemoteNotification(notification)) => {
+                writeln!(out, "{}", serde_json::to_string(&notification)?)?;
+                out.flush()?;
+            }
+            Some(ServerControl::Quit { .. }) | None => break,
+            Some(_) => {}
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 /// Perform the client side of the handshake on an established connection.
 ///
 /// Sends `Hello` and reads the server's reply. Returns `Ok(true)` when the
@@ -3392,6 +3505,7 @@ fn is_interactive_launch(args: &Args) -> bool {
         && !args.list_sessions
         && args.kill.is_none()
         && args.open_files_in_session.is_none()
+        && args.remote.is_none()
         && args.init.is_none()
         && !args.list_grammars
         && !args.dump_config
@@ -3463,6 +3577,9 @@ fn run_if_subcommand(
             *wait,
         ));
     }
+    if let Some((request, session)) = &args.remote {
+        return Some(run_remote_command(request, session.as_deref()));
+    }
     if args.attach {
         return Some(run_attach_command(args));
     }
@@ -3649,6 +3766,10 @@ fn build_localized_after_help() -> String {
         "  fresh --cmd daemon open-file proj a.rs      {}\n",
         t("cli.example.open_in_named")
     ));
+    out.push_str(&format!(
+        "  fresh --remote '{{\"method\":\"listBuffers\"}}'   {}\n",
+        t("cli.example.remote_control")
+    ));
     out.push('\n');
 
     out.push_str(&format!("{}\n", t("cli.section.remote")));
@@ -3714,7 +3835,9 @@ fn build_localized_cli_command() -> clap::Command {
         .mut_arg("no_upgrade_check", |a| {
             a.help(t("cli.arg.no_upgrade_check"))
         })
-        .mut_arg("locale", |a| a.help(t("cli.arg.locale")));
+        .mut_arg("locale", |a| a.help(t("cli.arg.locale")))
+        .mut_arg("remote", |a| a.help(t("cli.arg.remote")))
+        .mut_arg("remote_session", |a| a.help(t("cli.arg.remote_session")));
 
     #[cfg(feature = "gui")]
     let cmd = cmd.mut_arg("gui", |a| a.help(t("cli.arg.gui")));
@@ -1,12 +1,13 @@
-//! Encoding and line-ending state for a `TextBuffer`.
+//! Encoding, line-ending and compression state for a `TextBuffer`.
 //!
-//! Owns the four format-related fields (current line-ending,
-//! original line-ending at load, current encoding, original encoding
-//! at load) as a `BufferFormat` sub-struct composed inside
-//! `TextBuffer`. Exposes pure free functions for detection and
+//! Owns the format-related fields (current line-ending, original
+//! line-ending at load, current encoding, original encoding at load,
+//! compression of the file on disk) as a `BufferFormat` sub-struct
+//! composed inside `TextBuffer`. Exposes pure free functions for detection and
 //! conversion so they can be used without constructing any buffer
 //! state.
 
+use crate::model::compression::Compression;
 use crate::model::encoding::{self, Encoding};
 
 /// Line-ending format detected in (or chosen for) a text buffer.
@@ -52,6 +53,9 @@ pub struct BufferFormat {
     original_line_ending: LineEnding,
     encoding: Encoding,
     original_encoding: Encoding,
+    /// Compression of the file on disk: the buffer holds the decompressed
+    /// text and saving recompresses it.
+    compression: Option<Compression>,
 }
 
 impl BufferFormat {
@@ -61,6 +65,7 @@ impl BufferFormat {
             original_line_ending: line_ending,
             encoding,
             original_encoding: encoding,
+            compression: None,
         }
     }
 
@@ -72,6 +77,10 @@ impl BufferFormat {
         self.encoding
     }
 
+    pub fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
     pub fn original_line_ending(&self) -> LineEnding {
         self.original_line_ending
     }
@@ -88,6 +97,10 @@ impl BufferFormat {
         self.encoding = e;
     }
 
+    pub fn set_compression(&mut self, c: Option<Compression>) {
+        self.compression = c;
+    }
+
     pub fn set_default_line_ending(&mut self, le: LineEnding) {
         self.line_ending = le;
         self.original_line_ending = le;
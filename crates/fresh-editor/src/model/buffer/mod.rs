@@ -1,5 +1,6 @@
 /// Text buffer that uses PieceTree with integrated line tracking
 /// Architecture where the tree is the single source of truth for text and line information
+use crate::model::compression::Compression;
 use crate::model::encoding;
 use crate::model::filesystem::{FileSearchOptions, FileSystem};
 use crate::model::piece_tree::{
@@ -10,7 +11,7 @@ use crate::model::piece_tree_diff::PieceTreeDiff;
 use crate::primitives::grapheme;
 use anyhow::{Context, Result};
 use regex::bytes::Regex;
-use std::io;
+use std::io::{self, Read, Write};
 
 use std::ops::Range;
 use std::path::{Path, PathBuf};
@@ -194,6 +195,76 @@ pub struct BufferSnapshot {
     pub next_buffer_id: usize,
 }
 
+impl BufferSnapshot {
+    /// Bytes in the snapshot.
+    pub fn len(&self) -> usize {
+        self.piece_tree.total_bytes()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Text in `offset..offset + bytes`, or `None` if part of it lives in a
+    /// chunk of a large file that was never loaded.
+    pub fn text_range(&self, offset: usize, bytes: usize) -> Option<Vec<u8>> {
+        read_text_range(&self.piece_tree, &self.buffers, offset, bytes)
+    }
+}
+
+/// Read `offset..offset + bytes` from a piece tree and its string buffers.
+/// Returns `None` if any buffer in the range is unloaded.
+fn read_text_range(
+    piece_tree: &PieceTree,
+    buffers: &[StringBuffer],
+    offset: usize,
+    bytes: usize,
+) -> Option<Vec<u8>> {
+    if bytes == 0 {
+        return Some(Vec::new());
+    }
+
+    let mut result = Vec::with_capacity(bytes);
+    let end_offset = offset + bytes;
+    let mut collected = 0;
+
+    // Use the efficient piece iterator (single O(log n) traversal + O(N) iteration)
+    for piece_view in piece_tree.iter_pieces_in_range(offset, end_offset) {
+        let buffer_id = piece_view.location.buffer_id();
+        if let Some(buffer) = buffers.get(buffer_id) {
+            // Calculate the range to read from this piece
+            let piece_start_in_doc = piece_view.doc_offset;
+            let piece_end_in_doc = piece_view.doc_offset + piece_view.bytes;
+
+            // Clip to the requested range
+            let read_start = offset.max(piece_start_in_doc);
+            let read_end = end_offset.min(piece_end_in_doc);
+
+            if read_end > read_start {
+                let offset_in_piece = read_start - piece_start_in_doc;
+                let bytes_to_read = read_end - read_start;
+
+                let buffer_start = piece_view.buffer_offset + offset_in_piece;
+                let buffer_end = buffer_start + bytes_to_read;
+
+                // Return None if buffer is unloaded (type-safe)
+                let data = buffer.get_data()?;
+
+                if buffer_end <= data.len() {
+                    result.extend_from_slice(&data[buffer_start..buffer_end]);
+                    collected += bytes_to_read;
+
+                    if collected >= bytes {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    Some(result)
+}
+
 impl TextBuffer {
     /// Create a new text buffer with the given filesystem implementation.
     /// Note: large_file_threshold is ignored in the new implementation
@@ -222,6 +293,9 @@ impl TextBuffer {
         path: PathBuf,
     ) -> Self {
         let mut buffer = Self::new(large_file_threshold, fs);
+        buffer
+            .format
+            .set_compression(Compression::from_extension(&path));
         buffer.persistence.set_file_path(path);
         buffer
     }
@@ -402,6 +476,12 @@ impl TextBuffer {
             DEFAULT_LARGE_FILE_THRESHOLD
         };
 
+        // Compressed files can't be read at arbitrary offsets, so they are
+        // decompressed first; the threshold applies to the decompressed size
+        if let Some(compression) = fs.detect_compression(path) {
+            return Self::load_compressed_file(path, file_size, threshold, compression, fs);
+        }
+
         // Choose loading strategy based on file size
         if file_size >= threshold {
             Self::load_large_file(path, file_size, fs)
@@ -418,11 +498,16 @@ impl TextBuffer {
         config: BufferConfig,
     ) -> anyhow::Result<Self> {
         let path = path.as_ref();
-        let contents = fs.read_file(path)?;
+        let compression = fs.detect_compression(path);
+        let contents = match compression {
+            Some(compression) => Self::read_decompressed(path, compression, &fs)?,
+            None => fs.read_file(path)?,
+        };
 
         let mut buffer = Self::from_bytes_with_encoding(contents, encoding, fs);
         buffer.persistence.set_file_path(path.to_path_buf());
         buffer.persistence.clear_modified();
+        buffer.format.set_compression(compression);
         buffer.config = config;
         Ok(buffer)
     }
@@ -430,7 +515,116 @@ impl TextBuffer {
     /// Load a small file with full eager loading and line indexing
     fn load_small_file(path: &Path, fs: Arc<dyn FileSystem + Send + Sync>) -> anyhow::Result<Self> {
         let contents = fs.read_file(path)?;
+        Self::from_file_contents(path, contents, fs)
+    }
+
+    /// Load a compressed file.
+    ///
+    /// The decoder streams from the file. Text below `threshold` is loaded
+    /// eagerly like a small file (with line indexing). Larger text is
+    /// decompressed into a temp file, which is then opened in lazy
+    /// large-file mode; the temp file lives as long as the buffer.
+    fn load_compressed_file(
+        path: &Path,
+        file_size: usize,
+        threshold: usize,
+        compression: Compression,
+        fs: Arc<dyn FileSystem + Send + Sync>,
+    ) -> anyhow::Result<Self> {
+        tracing::info!(
+            "Loading {} compressed file {:?} ({} bytes on disk)",
+            compression.display_name(),
+            path,
+            file_size
+        );
+        let decompress_context = || {
+            format!(
+                "Failed to decompress {} file {}",
+                compression.display_name(),
+                path.display()
+            )
+        };
+
+        let mut contents = Vec::new();
+        let mut decoder = None;
+        if file_size > 0 {
+            let mut reader = compression
+                .decoder(fs.open_file(path)?)
+                .with_context(decompress_context)?;
+            (&mut reader)
+                .take(threshold as u64)
+                .read_to_end(&mut contents)
+                .with_context(decompress_context)?;
+            decoder = Some(reader);
+        }
+        let Some(mut decoder) = decoder.filter(|_| contents.len() >= threshold) else {
+            let mut buffer = Self::from_file_contents(path, contents, fs)?;
+            buffer.format.set_compression(Some(compression));
+            return Ok(buffer);
+        };
+
+        let temp_path = fs.unique_temp_path(path);
+        tracing::info!(
+            "Decompressing large {} file {:?} to {:?}",
+            compression.display_name(),
+            path,
+            temp_path
+        );
+        let written = fs.create_file(&temp_path).and_then(|mut temp_file| {
+            temp_file.write_all(&contents)?;
+            drop(contents);
+            io::copy(&mut decoder, &mut temp_file)?;
+            temp_file.sync_all()
+        });
+        let loaded = written.with_context(decompress_context).and_then(|()| {
+            let temp_size = fs.metadata(&temp_path)?.size as usize;
+            // The encoding can't be confirmed against a temp file the
+            // user never opened, so non-resynchronizable text loads in full
+            Self::load_large_file_internal(&temp_path, temp_size, fs.clone(), true)
+        });
+        let mut buffer = match loaded {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                // Best-effort cleanup of the partial temp file
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = fs.remove_file(&temp_path);
+                return Err(e);
+            }
+        };
+        buffer.persistence.set_file_path(path.to_path_buf());
+        buffer.persistence.set_decompressed_file(temp_path);
+        buffer.format.set_compression(Some(compression));
+        Ok(buffer)
+    }
+
+    /// Decompress `path`, naming the format in the error when the data is
+    /// corrupt or truncated. Empty files (e.g. just created with a
+    /// compression extension) hold no stream and decompress to nothing.
+    fn read_decompressed(
+        path: &Path,
+        compression: Compression,
+        fs: &Arc<dyn FileSystem + Send + Sync>,
+    ) -> anyhow::Result<Vec<u8>> {
+        if fs.metadata(path)?.size == 0 {
+            return Ok(Vec::new());
+        }
+        fs.read_file_decompressed(path, compression)
+            .with_context(|| {
+                format!(
+                    "Failed to decompress {} file {}",
+                    compression.display_name(),
+                    path.display()
+                )
+            })
+    }
 
+    /// Build a buffer for `path` from its fully read contents, detecting
+    /// encoding, binary content and line endings.
+    fn from_file_contents(
+        path: &Path,
+        contents: Vec<u8>,
+        fs: Arc<dyn FileSystem + Send + Sync>,
+    ) -> anyhow::Result<Self> {
         // Use unified encoding/binary detection
         let (encoding, is_binary) = format::detect_encoding_or_binary(&contents, false);
 
@@ -469,8 +663,9 @@ impl TextBuffer {
         let metadata = fs.metadata(path)?;
         let file_size = metadata.size as usize;
 
-        // Only check for large files
-        if file_size < DEFAULT_LARGE_FILE_THRESHOLD {
+        // Only check for large files; the on-disk bytes of compressed files
+        // say nothing about the text encoding
+        if file_size < DEFAULT_LARGE_FILE_THRESHOLD || fs.detect_compression(path).is_some() {
             return Ok(None);
         }
 
@@ -671,10 +866,18 @@ impl TextBuffer {
         let dest_path = path.as_ref();
         let total = self.total_bytes();
 
-        // Handle empty files
-        if total == 0 {
+        // Saving back to the file keeps its compression; saving elsewhere
+        // compresses when the new name has a compression extension
+        let compression = if self.persistence.file_path() == Some(dest_path) {
+            self.format.compression()
+        } else {
+            Compression::from_extension(dest_path)
+        };
+
+        // Handle empty files (a compressed file still needs a valid stream)
+        if total == 0 && compression.is_none() {
             self.persistence.fs().write_file(dest_path, &[])?;
-            self.finalize_save(dest_path)?;
+            self.finalize_save(dest_path, None)?;
             return Ok(());
         }
 
@@ -694,7 +897,10 @@ impl TextBuffer {
         let is_local = fs.remote_connection_info().is_none();
         let use_inplace = is_local && save::should_use_inplace_write(fs, dest_path);
 
-        if use_inplace {
+        if let Some(compression) = compression {
+            // Compressed output streams through the encoder into a temp file
+            save::save_compressed(fs, dest_path, &recipe, compression, use_inplace)?;
+        } else if use_inplace {
             // In-place write: write directly to preserve ownership
             save::save_with_inplace_write(fs, dest_path, &recipe)?;
         } else if !recipe.has_copy_ops() && !is_local {
@@ -733,12 +939,16 @@ impl TextBuffer {
             fs.write_patched(src_for_patch, dest_path, &ops)?;
         }
 
-        self.finalize_save(dest_path)?;
+        self.finalize_save(dest_path, compression)?;
         Ok(())
     }
 
     /// Finalize save state after successful write.
-    fn finalize_save(&mut self, dest_path: &Path) -> anyhow::Result<()> {
+    fn finalize_save(
+        &mut self,
+        dest_path: &Path,
+        compression: Option<Compression>,
+    ) -> anyhow::Result<()> {
         let new_size = self.persistence.fs().metadata(dest_path)?.size as usize;
         tracing::debug!(
             "Buffer::save: updating saved_file_size from {:?} to {}",
@@ -747,6 +957,7 @@ impl TextBuffer {
         );
         self.persistence.set_saved_file_size(Some(new_size));
         self.persistence.set_file_path(dest_path.to_path_buf());
+        self.format.set_compression(compression);
 
         // Consolidate the piece tree to synchronize with disk (for large files)
         // or to simplify structure (for small files).
@@ -776,9 +987,16 @@ impl TextBuffer {
     /// Consolidate the piece tree into a single piece.
     /// For large files, this creates a reference to the disk file to save memory and sync offsets.
     /// For small files, this flattens all edits into a single in-memory buffer.
+    ///
+    /// A large file saved compressed can't be referenced by offset, so its
+    /// pieces keep pointing at the uncompressed file they were loaded from
+    /// (the original plain file, or the temp file an archive was
+    /// decompressed to).
     fn consolidate_after_save(&mut self, path: &Path, file_size: usize) {
         if self.file_kind.is_large_file() {
-            self.consolidate_large_file(path, file_size);
+            if self.format.compression().is_none() {
+                self.consolidate_large_file(path, file_size);
+            }
         } else {
             self.consolidate_small_file();
         }
@@ -1169,49 +1387,7 @@ impl TextBuffer {
     /// Returns None if any buffer in the range is unloaded
     /// PRIVATE: External code should use get_text_range_mut() which handles lazy loading
     fn get_text_range(&self, offset: usize, bytes: usize) -> Option<Vec<u8>> {
-        if bytes == 0 {
-            return Some(Vec::new());
-        }
-
-        let mut result = Vec::with_capacity(bytes);
-        let end_offset = offset + bytes;
-        let mut collected = 0;
-
-        // Use the efficient piece iterator (single O(log n) traversal + O(N) iteration)
-        for piece_view in self.piece_tree.iter_pieces_in_range(offset, end_offset) {
-            let buffer_id = piece_view.location.buffer_id();
-            if let Some(buffer) = self.buffers.get(buffer_id) {
-                // Calculate the range to read from this piece
-                let piece_start_in_doc = piece_view.doc_offset;
-                let piece_end_in_doc = piece_view.doc_offset + piece_view.bytes;
-
-                // Clip to the requested range
-                let read_start = offset.max(piece_start_in_doc);
-                let read_end = end_offset.min(piece_end_in_doc);
-
-                if read_end > read_start {
-                    let offset_in_piece = read_start - piece_start_in_doc;
-                    let bytes_to_read = read_end - read_start;
-
-                    let buffer_start = piece_view.buffer_offset + offset_in_piece;
-                    let buffer_end = buffer_start + bytes_to_read;
-
-                    // Return None if buffer is unloaded (type-safe)
-                    let data = buffer.get_data()?;
-
-                    if buffer_end <= data.len() {
-                        result.extend_from_slice(&data[buffer_start..buffer_end]);
-                        collected += bytes_to_read;
-
-                        if collected >= bytes {
-                            break;
-                        }
-                    }
-                }
-            }
-        }
-
-        Some(result)
+        read_text_range(&self.piece_tree, &self.buffers, offset, bytes)
     }
 
     /// Get text from a byte offset range with lazy loading
@@ -2384,6 +2560,14 @@ impl TextBuffer {
         self.format.set_default_encoding(encoding);
     }
 
+    /// Get the compression of the file on disk, if it is compressed
+    ///
+    /// The buffer always holds the decompressed text; saving to the same
+    /// path recompresses it with this format.
+    pub fn compression(&self) -> Option<Compression> {
+        self.format.compression()
+    }
+
     /// Get the first line of the buffer as a lossy UTF-8 string, suitable
     /// for shebang / first-line grammar detection. Returns `None` for an
     /// empty buffer. Non-UTF-8 bytes are replaced with U+FFFD.
@@ -43,6 +43,13 @@ pub struct Persistence {
     /// reconstruction. Updated when loading from file or after
     /// saving.
     saved_file_size: Option<usize>,
+
+    /// Temp file holding the decompressed text of a large compressed file.
+    ///
+    /// Its stored pieces are read from (and unchanged regions copied from
+    /// on save) this file, since the archive itself can't be read at an
+    /// offset. Removed when the buffer is dropped.
+    decompressed_file: Option<PathBuf>,
 }
 
 impl Persistence {
@@ -59,6 +66,7 @@ impl Persistence {
             recovery_pending: false,
             saved_root,
             saved_file_size,
+            decompressed_file: None,
         }
     }
 
@@ -133,6 +141,14 @@ impl Persistence {
         self.saved_file_size = size;
     }
 
+    pub fn decompressed_file(&self) -> Option<&Path> {
+        self.decompressed_file.as_deref()
+    }
+
+    pub fn set_decompressed_file(&mut self, path: PathBuf) {
+        self.decompressed_file = Some(path);
+    }
+
     // ---------- snapshot / diff operations ----------
 
     /// Replace the saved snapshot with the current piece tree and clear
@@ -350,6 +366,16 @@ impl Persistence {
     }
 }
 
+impl Drop for Persistence {
+    fn drop(&mut self) {
+        if let Some(path) = self.decompressed_file.take() {
+            // Best-effort: a leftover temp file only costs disk space
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = self.fs.remove_file(&path);
+        }
+    }
+}
+
 // ---------- private free-fn helpers over borrowed storage ----------
 
 /// Total bytes in a tree rooted at `root`.
@@ -7,6 +7,7 @@
 use super::file_kind::BufferFileKind;
 use super::format::{self, BufferFormat};
 use super::persistence::Persistence;
+use crate::model::compression::Compression;
 use crate::model::encoding::Encoding;
 use crate::model::filesystem::{FileMetadata, FileSystem, FileWriter, WriteOp};
 use crate::model::piece_tree::{BufferData, BufferLocation, PieceTree, StringBuffer};
@@ -159,12 +160,16 @@ pub(super) fn build_write_recipe(
             || !matches!(format.encoding(), Encoding::Utf8 | Encoding::Ascii));
     let needs_conversion = needs_line_ending_conversion || needs_encoding_conversion;
 
+    // A compressed file can't be copied from by offset; a large one keeps
+    // its text in a decompressed temp file that its stored pieces read from
+    let copy_source = persistence
+        .decompressed_file()
+        .filter(|_| format.compression().is_some())
+        .or(persistence.file_path());
     let src_path_for_copy: Option<&Path> = if needs_conversion {
         None
     } else {
-        persistence
-            .file_path()
-            .filter(|p| persistence.fs().exists(p))
+        copy_source.filter(|p| persistence.fs().exists(p))
     };
     let target_ending = format.line_ending();
     let target_encoding = format.encoding();
@@ -506,10 +511,13 @@ pub(super) fn stream_file_to_writer(
     Ok(())
 }
 
-/// Write the recipe content to a file writer.
+/// Chunk size for reading Copy ops in `write_recipe_to_file`.
+const COPY_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// Write the recipe content to a writer.
 pub(super) fn write_recipe_to_file(
     fs: &Arc<dyn FileSystem + Send + Sync>,
-    out_file: &mut Box<dyn FileWriter>,
+    out_file: &mut dyn Write,
     recipe: &WriteRecipe,
 ) -> io::Result<()> {
     for action in &recipe.actions {
@@ -519,8 +527,15 @@ pub(super) fn write_recipe_to_file(
                 let src_path = recipe.src_path.as_ref().ok_or_else(|| {
                     io::Error::new(io::ErrorKind::InvalidData, "Copy action without source")
                 })?;
-                let data = fs.read_range(src_path, *offset, *len as usize)?;
-                out_file.write_all(&data)?;
+                // Read in chunks so a large unchanged region is never held whole
+                let end = offset + len;
+                let mut pos = *offset;
+                while pos < end {
+                    let chunk_len = (end - pos).min(COPY_CHUNK_SIZE);
+                    let data = fs.read_range(src_path, pos, chunk_len as usize)?;
+                    out_file.write_all(&data)?;
+                    pos += chunk_len;
+                }
             }
             RecipeAction::Insert { index } => {
                 out_file.write_all(&recipe.insert_data[*index])?;
@@ -530,6 +545,71 @@ pub(super) fn write_recipe_to_file(
     Ok(())
 }
 
+/// Save the recipe content compressed with `compression`.
+///
+/// The encoder writes straight into a temp file next to `dest_path`, and
+/// Copy ops (unchanged regions of a large source file) are read through it
+/// in chunks, so neither the plain text nor the compressed output is
+/// gathered in memory. The temp file then replaces the destination like an
+/// atomic save, or is streamed into it when `inplace` is set to preserve
+/// the file's ownership.
+pub(super) fn save_compressed(
+    fs: &Arc<dyn FileSystem + Send + Sync>,
+    dest_path: &Path,
+    recipe: &WriteRecipe,
+    compression: Compression,
+    inplace: bool,
+) -> anyhow::Result<()> {
+    let original_metadata = fs.metadata_if_exists(dest_path);
+
+    let (temp_path, temp_file) = create_temp_file(fs, dest_path)?;
+    let written = compression
+        .compress_into(temp_file, |out| write_recipe_to_file(fs, out, recipe))
+        .and_then(|temp_file| temp_file.sync_all());
+    if let Err(e) = written {
+        // Best-effort cleanup of temp file on write failure
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = fs.remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    let result = if inplace {
+        fs.open_file_for_write(dest_path).and_then(|mut out_file| {
+            stream_file_to_writer(fs, &temp_path, &mut out_file)?;
+            out_file.sync_all()
+        })
+    } else {
+        if let Some(perms) = original_metadata
+            .as_ref()
+            .and_then(|m| m.permissions.as_ref())
+        {
+            // Best-effort permission restore; rename will proceed regardless
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = fs.set_permissions(&temp_path, perms);
+        }
+        fs.rename(&temp_path, dest_path)
+    };
+
+    match result {
+        Ok(()) => {
+            if inplace {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = fs.remove_file(&temp_path);
+            }
+            Ok(())
+        }
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            // Keep the temp file for sudo to move into place
+            Err(make_sudo_error(temp_path, dest_path, original_metadata))
+        }
+        Err(e) => {
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = fs.remove_file(&temp_path);
+            Err(e.into())
+        }
+    }
+}
+
 /// Internal helper to create a SudoSaveRequired error.
 pub(super) fn make_sudo_error(
     temp_path: PathBuf,
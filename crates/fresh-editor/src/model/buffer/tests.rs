@@ -1561,6 +1561,152 @@ mod large_file_encoding_tests {
     }
 }
 
+#[cfg(feature = "runtime")]
+mod compressed_files {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn decompress(path: &Path, compression: Compression) -> String {
+        let mut text = String::new();
+        compression
+            .decoder(std::fs::File::open(path).unwrap())
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        text
+    }
+
+    #[test]
+    fn test_compressed_file_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        for compression in Compression::ALL {
+            let path = temp_dir
+                .path()
+                .join(format!("app.log.1.{}", compression.extension()));
+            std::fs::write(&path, compression.compress(b"one\ntwo\n").unwrap()).unwrap();
+
+            let mut buffer = TextBuffer::load_from_file(&path, 0, test_fs()).unwrap();
+            assert_eq!(buffer.compression(), Some(compression));
+            assert!(!buffer.is_large_file());
+            assert_eq!(buffer.line_count(), Some(3));
+            assert_eq!(buffer.get_all_text().unwrap(), b"one\ntwo\n");
+
+            buffer.insert(0, "zero\n");
+            buffer.save().unwrap();
+            assert_eq!(decompress(&path, compression), "zero\none\ntwo\n");
+            assert_eq!(buffer.compression(), Some(compression));
+            assert!(!buffer.is_modified());
+        }
+    }
+
+    #[test]
+    fn test_large_compressed_file_loads_lazily_from_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("app.log.gz");
+        let text = "a log line\n".repeat(100);
+        std::fs::write(&path, Compression::Gzip.compress(text.as_bytes()).unwrap()).unwrap();
+
+        // The threshold applies to the decompressed text, not the archive
+        let mut buffer = TextBuffer::load_from_file(&path, 64, test_fs()).unwrap();
+        assert!(buffer.is_large_file());
+        assert_eq!(buffer.compression(), Some(Compression::Gzip));
+        assert_eq!(buffer.file_path(), Some(path.as_path()));
+        let temp_path = buffer
+            .persistence
+            .decompressed_file()
+            .unwrap()
+            .to_path_buf();
+        assert_eq!(std::fs::read(&temp_path).unwrap(), text.as_bytes());
+
+        buffer.insert(0, "first\n");
+        buffer.save().unwrap();
+        assert_eq!(
+            decompress(&path, Compression::Gzip),
+            format!("first\n{text}")
+        );
+        let total = buffer.total_bytes();
+        assert_eq!(
+            buffer.get_text_range_mut(0, total).unwrap(),
+            format!("first\n{text}").as_bytes()
+        );
+
+        drop(buffer);
+        assert!(!temp_path.exists());
+    }
+
+    #[test]
+    fn test_plain_file_with_compression_extension_stays_plain() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("notes.gz");
+        std::fs::write(&path, b"not actually gzip\n").unwrap();
+
+        let mut buffer = TextBuffer::load_from_file(&path, 0, test_fs()).unwrap();
+        assert_eq!(buffer.compression(), None);
+        buffer.insert(0, "still ");
+        buffer.save().unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"still not actually gzip\n");
+    }
+
+    #[test]
+    fn test_save_as_compresses_by_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let plain = temp_dir.path().join("big.log");
+        std::fs::write(&plain, b"hello\nworld\ntest\n").unwrap();
+
+        // Large-file mode: unchanged regions are copied from the source
+        let mut buffer = TextBuffer::load_from_file(&plain, 10, test_fs()).unwrap();
+        assert!(buffer.is_large_file());
+        buffer.insert(0, "> ");
+
+        let compressed = temp_dir.path().join("big.log.zst");
+        buffer.save_to_file(&compressed).unwrap();
+        assert_eq!(buffer.compression(), Some(Compression::Zstd));
+        assert_eq!(
+            decompress(&compressed, Compression::Zstd),
+            "> hello\nworld\ntest\n"
+        );
+        let total = buffer.total_bytes();
+        assert_eq!(
+            buffer.get_text_range_mut(0, total).unwrap(),
+            b"> hello\nworld\ntest\n"
+        );
+
+        // Saving back to a plain name writes plain text
+        let back = temp_dir.path().join("copy.log");
+        buffer.save_to_file(&back).unwrap();
+        assert_eq!(buffer.compression(), None);
+        assert_eq!(std::fs::read(&back).unwrap(), b"> hello\nworld\ntest\n");
+    }
+
+    #[test]
+    fn test_new_empty_compressed_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("new.xz");
+        let mut buffer = TextBuffer::new_with_path(0, test_fs(), path.clone());
+        assert_eq!(buffer.compression(), Some(Compression::Xz));
+
+        buffer.save().unwrap();
+        assert!(Compression::Xz.matches_magic(&std::fs::read(&path).unwrap()));
+        let reloaded = TextBuffer::load_from_file(&path, 0, test_fs()).unwrap();
+        assert_eq!(reloaded.compression(), Some(Compression::Xz));
+        assert!(reloaded.is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_compressed_file_fails_to_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.gz");
+        // Valid gzip header followed by a deflate block of reserved type
+        let mut data = vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0x03];
+        data.extend([0xff; 8]);
+        std::fs::write(&path, data).unwrap();
+
+        let err = TextBuffer::load_from_file(&path, 0, test_fs()).unwrap_err();
+        assert!(err.to_string().contains("gzip"), "{err}");
+    }
+}
+
 mod rebuild_pristine_saved_root_tests {
     use super::*;
     use crate::model::piece_tree::BufferLocation;
@@ -0,0 +1,265 @@
+//! Compressed file formats edited transparently
+//!
+//! Files such as `foo.log.gz` (as left behind by logrotate) or
+//! `dump.sql.zst` are decompressed when opened and recompressed with the
+//! same codec when saved, so the buffer only ever holds the plain text.
+//!
+//! A file counts as compressed when its extension names a format *and* its
+//! leading bytes carry that format's magic number, so a plain-text file that
+//! merely ends in `.gz` is opened as-is. Detection itself lives on the
+//! `FileSystem` trait (`detect_compression`), which can read the header
+//! from any backend; this module holds the formats and codecs.
+//!
+//! All three codecs stream: decoding reads the file through the decoder in
+//! chunks and encoding consumes the content piece by piece, so neither side
+//! needs the compressed bytes in memory next to the plain text. None of them
+//! supports reading at arbitrary offsets, so text past the large-file
+//! threshold is decompressed into a temp file that is opened in lazy
+//! large-file mode instead.
+
+use std::borrow::Cow;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// Number of leading bytes needed to recognise every supported format.
+pub const MAGIC_LEN: usize = 6;
+
+/// A compression format opened and saved transparently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Xz,
+}
+
+impl Compression {
+    pub const ALL: [Compression; 3] = [Self::Gzip, Self::Zstd, Self::Xz];
+
+    /// Get the display name for the status bar
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+            Self::Xz => "xz",
+        }
+    }
+
+    /// File extension naming this format, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            Self::Zstd => "zst",
+            Self::Xz => "xz",
+        }
+    }
+
+    /// Magic number at the start of every stream in this format
+    fn magic(&self) -> &'static [u8] {
+        match self {
+            Self::Gzip => &[0x1f, 0x8b],
+            Self::Zstd => &[0x28, 0xb5, 0x2f, 0xfd],
+            Self::Xz => &[0xfd, b'7', b'z', b'X', b'Z', 0x00],
+        }
+    }
+
+    /// The format named by `path`'s extension, if any
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|c| ext.eq_ignore_ascii_case(c.extension()))
+    }
+
+    /// Whether `head` (the first [`MAGIC_LEN`] bytes of a file, or fewer
+    /// for shorter files) starts with this format's magic number
+    pub fn matches_magic(&self, head: &[u8]) -> bool {
+        head.starts_with(self.magic())
+    }
+
+    /// Wrap `reader` in a streaming decoder for this format.
+    ///
+    /// Concatenated gzip members and multi-stream xz files are decoded in
+    /// full, as produced by `cat a.gz b.gz > c.gz`; zstd does this by
+    /// default.
+    pub fn decoder<'a>(&self, reader: impl Read + 'a) -> io::Result<Box<dyn Read + 'a>> {
+        codec::decoder(*self, reader)
+    }
+
+    /// Compress everything `write` produces into `out`, finishing the stream
+    /// and returning `out` once `write` is done.
+    pub fn compress_into<W: Write>(
+        &self,
+        out: W,
+        write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+    ) -> io::Result<W> {
+        codec::compress_into(*self, out, write)
+    }
+
+    /// Compress `data` into a new buffer
+    pub fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        self.compress_into(Vec::new(), |w| w.write_all(data))
+    }
+}
+
+/// `path` with any compression extension removed, for detecting the
+/// language of the text inside: `dump.sql.zst` becomes `dump.sql`.
+///
+/// Rotation suffixes left by logrotate before the compression extension are
+/// dropped too, so `app.log.1.gz` and `app.log-20240101.gz` both become
+/// `app.log`. Paths without a compression extension are returned unchanged.
+pub fn uncompressed_path(path: &Path) -> Cow<'_, Path> {
+    if Compression::from_extension(path).is_none() {
+        return Cow::Borrowed(path);
+    }
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return Cow::Borrowed(path);
+    };
+    let stem = strip_rotation_suffix(stem);
+    Cow::Owned(path.with_file_name(stem))
+}
+
+/// Strip a trailing `.N` or `-YYYYMMDD`-style rotation suffix from `name`.
+fn strip_rotation_suffix(name: &str) -> &str {
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    match name.rfind(['.', '-']) {
+        Some(idx) if idx > 0 && all_digits(&name[idx + 1..]) => &name[..idx],
+        _ => name,
+    }
+}
+
+#[cfg(feature = "runtime")]
+mod codec {
+    use super::Compression;
+    use std::io::{self, Read, Write};
+
+    /// 0 selects zstd's default level (currently 3), as the `zstd` CLI uses.
+    const ZSTD_LEVEL: i32 = 0;
+
+    /// xz preset, matching the `xz` command line tool's default.
+    const XZ_PRESET: u32 = 6;
+
+    pub(super) fn decoder<'a>(
+        compression: Compression,
+        reader: impl Read + 'a,
+    ) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match compression {
+            Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+            Compression::Zstd => Box::new(zstd::stream::read::Decoder::new(reader)?),
+            Compression::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(reader)),
+        })
+    }
+
+    pub(super) fn compress_into<W: Write>(
+        compression: Compression,
+        out: W,
+        write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+    ) -> io::Result<W> {
+        match compression {
+            Compression::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(out, flate2::Compression::default());
+                write(&mut encoder)?;
+                encoder.finish()
+            }
+            Compression::Zstd => {
+                let mut encoder = zstd::stream::write::Encoder::new(out, ZSTD_LEVEL)?;
+                write(&mut encoder)?;
+                encoder.finish()
+            }
+            Compression::Xz => {
+                let mut encoder = xz2::write::XzEncoder::new(out, XZ_PRESET);
+                write(&mut encoder)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+/// Builds without the native codecs (e.g. WASM) recognise compressed files
+/// but refuse to decode or encode them.
+#[cfg(not(feature = "runtime"))]
+mod codec {
+    use super::Compression;
+    use std::io::{self, Read, Write};
+
+    fn unsupported(compression: Compression) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "{} files are not supported in this build",
+                compression.display_name()
+            ),
+        )
+    }
+
+    pub(super) fn decoder<'a>(
+        compression: Compression,
+        _reader: impl Read + 'a,
+    ) -> io::Result<Box<dyn Read + 'a>> {
+        Err(unsupported(compression))
+    }
+
+    pub(super) fn compress_into<W: Write>(
+        compression: Compression,
+        _out: W,
+        _write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+    ) -> io::Result<W> {
+        Err(unsupported(compression))
+    }
+}
+
+#[cfg(all(test, feature = "runtime"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_every_format() {
+        let text = "first line\nsecond line\n".repeat(100);
+        for compression in Compression::ALL {
+            let compressed = compression.compress(text.as_bytes()).unwrap();
+            assert!(compression.matches_magic(&compressed[..MAGIC_LEN]));
+            assert!(compressed.len() < text.len());
+
+            let mut decoded = String::new();
+            compression
+                .decoder(compressed.as_slice())
+                .unwrap()
+                .read_to_string(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, text, "{}", compression.display_name());
+        }
+    }
+
+    #[test]
+    fn decodes_concatenated_gzip_members() {
+        let mut data = Compression::Gzip.compress(b"one\n").unwrap();
+        data.extend(Compression::Gzip.compress(b"two\n").unwrap());
+        let mut decoded = String::new();
+        Compression::Gzip
+            .decoder(data.as_slice())
+            .unwrap()
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "one\ntwo\n");
+    }
+
+    #[test]
+    fn detects_extension_and_strips_rotation_suffixes() {
+        let from_ext = |p: &str| Compression::from_extension(Path::new(p));
+        assert_eq!(from_ext("a/syslog.2.gz"), Some(Compression::Gzip));
+        assert_eq!(from_ext("dump.sql.ZST"), Some(Compression::Zstd));
+        assert_eq!(from_ext("backup.tar.xz"), Some(Compression::Xz));
+        assert_eq!(from_ext("notes.txt"), None);
+        assert_eq!(from_ext("gz"), None);
+
+        let stripped = |p: &str| uncompressed_path(Path::new(p)).into_owned();
+        assert_eq!(
+            stripped("/var/log/app.log.1.gz"),
+            Path::new("/var/log/app.log")
+        );
+        assert_eq!(stripped("app.log-20240101.gz"), Path::new("app.log"));
+        assert_eq!(stripped("dump.sql.zst"), Path::new("dump.sql"));
+        assert_eq!(stripped("v1.2.tar.xz"), Path::new("v1.2.tar"));
+        assert_eq!(stripped("notes.1.txt"), Path::new("notes.1.txt"));
+    }
+}
@@ -23,6 +23,15 @@ pub enum Event {
         cursor_id: CursorId,
     },
 
+    /// Replace the bytes at `position` with others, for edits that needn't
+    /// be UTF-8, such as overwriting bytes in the hex view
+    ReplaceBytes {
+        position: usize,
+        deleted: Vec<u8>,
+        inserted: Vec<u8>,
+        cursor_id: CursorId,
+    },
+
     /// Move a cursor to a new position
     MoveCursor {
         cursor_id: CursorId,
@@ -391,6 +400,17 @@ impl Event {
                 text: deleted_text.clone(),
                 cursor_id: CursorId::UNDO_SENTINEL,
             }),
+            Self::ReplaceBytes {
+                position,
+                deleted,
+                inserted,
+                ..
+            } => Some(Self::ReplaceBytes {
+                position: *position,
+                deleted: inserted.clone(),
+                inserted: deleted.clone(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            }),
             Self::Batch {
                 events,
                 description,
@@ -508,7 +528,10 @@ impl Event {
     /// Returns true if this event modifies the buffer content
     pub fn modifies_buffer(&self) -> bool {
         match self {
-            Self::Insert { .. } | Self::Delete { .. } | Self::BulkEdit { .. } => true,
+            Self::Insert { .. }
+            | Self::Delete { .. }
+            | Self::ReplaceBytes { .. }
+            | Self::BulkEdit { .. } => true,
             Self::Batch { events, .. } => events.iter().any(|e| e.modifies_buffer()),
             _ => false,
         }
@@ -529,7 +552,10 @@ impl Event {
     pub fn is_write_action(&self) -> bool {
         match self {
             // Buffer modifications are write actions
-            Self::Insert { .. } | Self::Delete { .. } | Self::BulkEdit { .. } => true,
+            Self::Insert { .. }
+            | Self::Delete { .. }
+            | Self::ReplaceBytes { .. }
+            | Self::BulkEdit { .. } => true,
 
             // Adding/removing cursors are write actions (structural changes)
             Self::AddCursor { .. } | Self::RemoveCursor { .. } => true,
@@ -542,17 +568,202 @@ impl Event {
         }
     }
 
+    /// The buffer edits of this event as `Insert`/`Delete`/`ReplaceBytes`
+    /// events that replay without snapshots and leave cursors alone. `BulkEdit` text is read from
+    /// its snapshots; returns `None` when that isn't possible.
+    fn to_plain_edits(&self) -> Option<Vec<Self>> {
+        match self {
+            Self::Insert { position, text, .. } => Some(vec![Self::Insert {
+                position: *position,
+                text: text.clone(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            }]),
+            Self::Delete {
+                range,
+                deleted_text,
+                ..
+            } => Some(vec![Self::Delete {
+                range: range.clone(),
+                deleted_text: deleted_text.clone(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            }]),
+            Self::ReplaceBytes {
+                position,
+                deleted,
+                inserted,
+                ..
+            } => Some(vec![Self::ReplaceBytes {
+                position: *position,
+                deleted: deleted.clone(),
+                inserted: inserted.clone(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            }]),
+            Self::Batch { events, .. } => {
+                let mut edits = Vec::new();
+                for event in events.iter().filter(|e| e.modifies_buffer()) {
+                    edits.extend(event.to_plain_edits()?);
+                }
+                Some(edits)
+            }
+            Self::BulkEdit {
+                old_snapshot,
+                new_snapshot,
+                edits,
+                ..
+            } => {
+                let (old, new) = (old_snapshot.as_deref()?, new_snapshot.as_deref()?);
+                let net: isize = edits
+                    .iter()
+                    .map(|&(_, del_len, ins_len)| ins_len as isize - del_len as isize)
+                    .sum();
+                if old.len() as isize + net != new.len() as isize {
+                    return None;
+                }
+
+                // Edits are sorted descending, so applying them in order never
+                // shifts the ones still to come. In the new snapshot an edit's
+                // text sits after the net growth of all edits below it.
+                let read = |snapshot: &BufferSnapshot, offset: usize, len: usize| {
+                    let bytes = snapshot.text_range(offset, len)?;
+                    if bytes.len() != len {
+                        return None;
+                    }
+                    String::from_utf8(bytes).ok()
+                };
+                let mut plain = Vec::new();
+                let mut shift_below = net;
+                for &(pos, del_len, ins_len) in edits {
+                    shift_below -= ins_len as isize - del_len as isize;
+                    if del_len > 0 {
+                        let deleted_text = read(old, pos, del_len)?;
+                        plain.push(Self::Delete {
+                            range: pos..pos + del_len,
+                            deleted_text,
+                            cursor_id: CursorId::UNDO_SENTINEL,
+                        });
+                    }
+                    if ins_len > 0 {
+                        let new_pos = usize::try_from(pos as isize + shift_below).ok()?;
+                        let text = read(new, new_pos, ins_len)?;
+                        plain.push(Self::Insert {
+                            position: pos,
+                            text,
+                            cursor_id: CursorId::UNDO_SENTINEL,
+                        });
+                    }
+                }
+                Some(plain)
+            }
+            _ => None,
+        }
+    }
+
+    /// Start of the text touched by an `Insert`/`Delete`/`ReplaceBytes`.
+    fn edit_start(&self) -> Option<usize> {
+        match self {
+            Self::Insert { position, .. } | Self::ReplaceBytes { position, .. } => Some(*position),
+            Self::Delete { range, .. } => Some(range.start),
+            _ => None,
+        }
+    }
+
     /// Returns the cursor ID associated with this event, if any
     pub fn cursor_id(&self) -> Option<CursorId> {
         match self {
             Self::Insert { cursor_id, .. }
             | Self::Delete { cursor_id, .. }
+            | Self::ReplaceBytes { cursor_id, .. }
             | Self::MoveCursor { cursor_id, .. }
             | Self::AddCursor { cursor_id, .. }
             | Self::RemoveCursor { cursor_id, .. } => Some(*cursor_id),
             _ => None,
         }
     }
+
+    /// Short human-readable summary of a write action, for the undo tree.
+    pub fn summary(&self) -> String {
+        fn quoted(text: &str) -> String {
+            const MAX_CHARS: usize = 24;
+            let first_line = text.lines().next().unwrap_or("");
+            let mut shown: String = first_line.chars().take(MAX_CHARS).collect();
+            if shown.len() < text.len() {
+                shown.push('…');
+            }
+            format!("{shown:?}")
+        }
+        match self {
+            Self::Insert { text, .. } => format!("Insert {}", quoted(text)),
+            Self::Delete { deleted_text, .. } => format!("Delete {}", quoted(deleted_text)),
+            Self::ReplaceBytes { .. } => "Hex edit".to_string(),
+            Self::Batch { description, .. } | Self::BulkEdit { description, .. } => {
+                description.clone()
+            }
+            Self::AddCursor { .. } => "Add cursor".to_string(),
+            Self::RemoveCursor { .. } => "Remove cursor".to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Apply the buffer edits of this event to `text`, a plain copy of the
+    /// buffer content. Returns `false` if an edit doesn't fit the text or a
+    /// `BulkEdit` snapshot can't be read back as UTF-8.
+    pub fn apply_to_text(&self, text: &mut String) -> bool {
+        match self {
+            Self::Insert {
+                position,
+                text: inserted,
+                ..
+            } => {
+                if !text.is_char_boundary(*position) {
+                    return false;
+                }
+                text.insert_str(*position, inserted);
+                true
+            }
+            Self::Delete { range, .. } => {
+                if range.start > range.end
+                    || !text.is_char_boundary(range.start)
+                    || !text.is_char_boundary(range.end)
+                {
+                    return false;
+                }
+                text.replace_range(range.clone(), "");
+                true
+            }
+            Self::ReplaceBytes {
+                position,
+                deleted,
+                inserted,
+                ..
+            } => {
+                let end = position + deleted.len();
+                let Ok(inserted) = std::str::from_utf8(inserted) else {
+                    return false;
+                };
+                if end > text.len()
+                    || !text.is_char_boundary(*position)
+                    || !text.is_char_boundary(end)
+                {
+                    return false;
+                }
+                text.replace_range(*position..end, inserted);
+                true
+            }
+            Self::Batch { events, .. } => events.iter().all(|e| e.apply_to_text(text)),
+            Self::BulkEdit { new_snapshot, .. } => {
+                let Some(content) = new_snapshot
+                    .as_deref()
+                    .and_then(|snapshot| snapshot.text_range(0, snapshot.len()))
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                else {
+                    return false;
+                };
+                *text = content;
+                true
+            }
+            _ => true,
+        }
+    }
 }
 
 /// A log entry containing an event and metadata
@@ -580,6 +791,11 @@ pub struct LogEntry {
     /// back to per-entry undo.
     #[serde(skip)]
     pub group_id: Option<u64>,
+
+    /// Identity of the entry in the undo tree, unique within its log.
+    /// Runtime-only, assigned when the entry is added to an [`EventLog`].
+    #[serde(skip)]
+    pub seq: u64,
 }
 
 impl LogEntry {
@@ -593,6 +809,7 @@ impl LogEntry {
             description: None,
             displaced_markers: Vec::new(),
             group_id: None,
+            seq: 0,
         }
     }
 
@@ -616,7 +833,59 @@ pub struct Snapshot {
     pub cursor_positions: Vec<(CursorId, usize, Option<usize>)>,
 }
 
+/// Maximum number of abandoned redo branches kept per buffer; the oldest
+/// are dropped first.
+const MAX_UNDO_BRANCHES: usize = 100;
+
+/// A redo tail that was replaced by a new edit, kept so the undo tree can
+/// still reach it.
+#[derive(Debug, Clone)]
+struct UndoBranch {
+    /// `seq` of the entry the branch continues from (0 = the initial state)
+    fork_seq: u64,
+    entries: Vec<LogEntry>,
+}
+
+/// One state of the undo tree, reached by an undo step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UndoTreeNode {
+    /// Id of the state. 0 is the initial state, which has no node.
+    pub id: u64,
+    /// State the step was made from
+    pub parent: u64,
+    /// When the step was made (milliseconds since epoch)
+    pub timestamp: u64,
+    /// Short summary of the step
+    pub description: String,
+}
+
+/// Every state of a buffer reachable by undo, redo or an abandoned redo
+/// branch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UndoTree {
+    /// Nodes of the active line of history first, then the other branches
+    pub nodes: Vec<UndoTreeNode>,
+    /// State the buffer is in
+    pub current: u64,
+    /// State last saved to disk, if it is still in the tree
+    pub saved: Option<u64>,
+}
+
+/// Events that move a buffer to another state of the undo tree
+#[derive(Debug, Default)]
+pub struct UndoJump {
+    /// Inverse events to apply first, each with the displaced markers to
+    /// restore after it (as returned by [`EventLog::undo`])
+    pub undo: Vec<(Event, Vec<(u64, usize)>)>,
+    /// Events to apply after `undo`
+    pub redo: Vec<Event>,
+}
+
 /// The event log - append-only log of all events
+///
+/// `entries` is the active line of history that undo and redo walk. A redo
+/// tail replaced by a new edit is kept in `branches`, which together with
+/// `entries` form the undo tree (see [`EventLog::undo_tree`]).
 pub struct EventLog {
     /// All logged events
     entries: Vec<LogEntry>,
@@ -647,6 +916,17 @@ pub struct EventLog {
     /// Nesting depth of open undo groups. The group is closed (and a fresh id
     /// allocated for the next group) only when this returns to zero.
     group_depth: u32,
+
+    /// Abandoned redo tails, the other branches of the undo tree.
+    branches: Vec<UndoBranch>,
+
+    /// Last `seq` handed out to an entry.
+    next_seq: u64,
+
+    /// `seq` of the saved entry while it sits on one of `branches` rather
+    /// than the active line, so `saved_at_index` can be restored when a jump
+    /// brings it back.
+    saved_in_branch: Option<u64>,
 }
 
 impl EventLog {
@@ -663,6 +943,9 @@ impl EventLog {
             next_group_id: 0,
             current_group: None,
             group_depth: 0,
+            branches: Vec::new(),
+            next_seq: 0,
+            saved_in_branch: None,
         }
     }
 
@@ -691,6 +974,7 @@ impl EventLog {
     /// Call this when the buffer is saved to disk
     pub fn mark_saved(&mut self) {
         self.saved_at_index = Some(self.current_index);
+        self.saved_in_branch = None;
     }
 
     /// Invalidate the saved position so the buffer is always considered modified.
@@ -698,6 +982,15 @@ impl EventLog {
     /// disk but the event log has no record of the changes.
     pub fn clear_saved_position(&mut self) {
         self.saved_at_index = None;
+        self.saved_in_branch = None;
+    }
+
+    /// Drop all history along with the saved position. Call this when the
+    /// buffer content was replaced without going through the log (e.g. by
+    /// recovery), so the recorded edits no longer apply to it.
+    pub fn discard_history(&mut self) {
+        self.clear();
+        self.clear_saved_position();
     }
 
     /// Check if the buffer is at the saved position (not modified)
@@ -811,15 +1104,17 @@ impl EventLog {
         // navigation after undo does not destroy the redo chain.
         if self.current_index < self.entries.len() {
             if event.is_write_action() {
-                // Write action: truncate redo history and log normally
-                self.entries.truncate(self.current_index);
-
-                // Invalidate saved_at_index if it pointed to a truncated entry
+                // Write action: the redo history moves to a branch of the
+                // undo tree and a new line of history starts here
                 if let Some(saved_idx) = self.saved_at_index {
                     if saved_idx > self.current_index {
                         self.saved_at_index = None;
+                        self.saved_in_branch = Some(self.entries[saved_idx - 1].seq);
                     }
                 }
+                let tail = self.entries.split_off(self.current_index);
+                self.archive_branch(tail);
+                self.prune_branches();
             } else {
                 // Non-write event while redo exists: skip logging to preserve redo
                 return self.current_index;
@@ -848,7 +1143,7 @@ impl EventLog {
 
         let mut entry = LogEntry::new(event);
         entry.group_id = self.current_group;
-        self.entries.push(entry);
+        self.push_entry(entry);
         self.current_index = self.entries.len();
 
         // Check if we should create a snapshot
@@ -860,6 +1155,12 @@ impl EventLog {
         self.current_index - 1
     }
 
+    fn push_entry(&mut self, mut entry: LogEntry) {
+        self.next_seq += 1;
+        entry.seq = self.next_seq;
+        self.entries.push(entry);
+    }
+
     /// Set displaced markers on the last appended entry.
     /// Call this right after `append()` to record markers that were inside
     /// the deleted range, so undo can restore them to exact positions.
@@ -999,6 +1300,7 @@ impl EventLog {
     /// Clear all events (for testing or reset)
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.branches.clear();
         self.current_index = 0;
         self.snapshots.clear();
     }
@@ -1031,7 +1333,7 @@ impl EventLog {
                 continue;
             }
             let entry: LogEntry = serde_json::from_str(&line)?;
-            log.entries.push(entry);
+            log.push_entry(entry);
         }
 
         log.current_index = log.entries.len();
@@ -1043,6 +1345,314 @@ impl EventLog {
     pub fn set_snapshot_interval(&mut self, interval: usize) {
         self.snapshot_interval = interval;
     }
+
+    /// Undo steps leading up to the saved position, for persistent undo.
+    ///
+    /// Each step is a `Batch` of plain edit events that replays without
+    /// buffer snapshots, so only the edited text is stored, never the whole
+    /// buffer. Cursor-only entries are dropped and an undo group becomes a
+    /// single step. Each step starts with a `MoveCursor` of the primary
+    /// cursor to the edit, so undoing it brings the cursor there.
+    ///
+    /// A `BulkEdit` whose text can't be recovered (unloaded chunks of a large
+    /// file, non-UTF-8 text) can't be replayed; history before it is dropped.
+    pub fn persistable_history(&self) -> Vec<Event> {
+        let end = match self.saved_at_index {
+            Some(saved_idx) if saved_idx <= self.entries.len() => saved_idx,
+            _ => return Vec::new(),
+        };
+
+        let mut steps: Vec<(Option<u64>, Vec<Event>)> = Vec::new();
+        for entry in &self.entries[..end] {
+            if !entry.event.modifies_buffer() {
+                continue;
+            }
+            let Some(edits) = entry.event.to_plain_edits() else {
+                steps.clear();
+                continue;
+            };
+            match steps.last_mut() {
+                Some((Some(group), events)) if entry.group_id == Some(*group) => {
+                    events.extend(edits)
+                }
+                _ => steps.push((entry.group_id, edits)),
+            }
+        }
+
+        steps
+            .into_iter()
+            .filter_map(|(_, events)| {
+                let start = events.iter().filter_map(Event::edit_start).min()?;
+                let mut step = vec![Event::MoveCursor {
+                    cursor_id: CursorId(0),
+                    old_position: start,
+                    new_position: start,
+                    old_anchor: None,
+                    new_anchor: None,
+                    old_sticky_column: 0,
+                    new_sticky_column: 0,
+                }];
+                step.extend(events);
+                Some(Event::Batch {
+                    events: step,
+                    description: "Restored edit".to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Seed an empty log with steps from [`Self::persistable_history`], taken
+    /// when the buffer had its current content. The log ends up at the saved
+    /// position with everything undoable and nothing to redo. Ignored if the
+    /// log already has entries.
+    pub fn seed_history(&mut self, steps: Vec<Event>) {
+        if !self.entries.is_empty() {
+            return;
+        }
+        for step in steps {
+            self.push_entry(LogEntry::new(step));
+        }
+        self.current_index = self.entries.len();
+        self.saved_at_index = Some(self.current_index);
+    }
+}
+
+// Undo tree
+//
+// Every entry, on the active line or on a branch, lives in exactly one place
+// and is identified by its `seq`. A state of the tree is identified by the
+// `seq` of the last write action of the undo step that reaches it, so
+// states stay valid while jumps rearrange which branch is active.
+impl EventLog {
+    /// The undo tree, for browsing history.
+    pub fn undo_tree(&self) -> UndoTree {
+        let mut nodes = Vec::new();
+        for branch in std::iter::once(None).chain((0..self.branches.len()).map(Some)) {
+            let (fork_seq, entries) = self.line(branch);
+            let mut parent = self.state_after(fork_seq);
+            let mut edits = 0;
+            for (entry, end) in entries.iter().zip(Self::step_ends(entries)) {
+                if entry.event.is_write_action() {
+                    edits += 1;
+                }
+                let Some(id) = end else { continue };
+                let mut description = entry.event.summary();
+                if edits > 1 {
+                    description.push_str(&format!(" (+{} edits)", edits - 1));
+                }
+                nodes.push(UndoTreeNode {
+                    id,
+                    parent,
+                    timestamp: entry.timestamp,
+                    description,
+                });
+                parent = id;
+                edits = 0;
+            }
+        }
+
+        let saved = match self.saved_at_index {
+            Some(saved_idx) if saved_idx <= self.entries.len() => {
+                Some(self.state_at_index(saved_idx))
+            }
+            _ => self
+                .saved_in_branch
+                .filter(|&seq| self.locate(seq).is_some())
+                .map(|seq| self.state_after(seq)),
+        };
+        UndoTree {
+            nodes,
+            current: self.state_at_index(self.current_index),
+            saved,
+        }
+    }
+
+    /// The state the buffer is in, as [`UndoTree::current`] would report it,
+    /// without building the whole tree.
+    pub fn current_state(&self) -> u64 {
+        self.state_at_index(self.current_index)
+    }
+
+    /// Move to state `id` of the undo tree, making the line of history that
+    /// leads to it the active one. Returns the events that take the buffer
+    /// there: inverses back to the last state shared with the current line,
+    /// then the edits forward to `id`. `None` if there is no such state.
+    pub fn jump_to(&mut self, id: u64) -> Option<UndoJump> {
+        if id != 0 && self.locate(id).is_none() {
+            return None;
+        }
+        let old_line: Vec<u64> = self.entries.iter().map(|e| e.seq).collect();
+        let old_index = self.current_index;
+        let saved_seq = match self.saved_at_index {
+            Some(0) => Some(0),
+            Some(saved_idx) if saved_idx <= old_line.len() => Some(old_line[saved_idx - 1]),
+            _ => self.saved_in_branch,
+        };
+
+        self.promote(id);
+        let target = self.line_index(id)?;
+        let shared = old_line
+            .iter()
+            .zip(&self.entries)
+            .take_while(|(seq, entry)| **seq == entry.seq)
+            .count();
+        let turn = old_index.min(shared).min(target);
+
+        let undo = old_line[turn..old_index]
+            .iter()
+            .rev()
+            .filter_map(|&seq| {
+                let entry = self.entry(seq)?;
+                let inverse = entry.event.inverse()?;
+                Some((inverse, entry.displaced_markers.clone()))
+            })
+            .collect();
+        let redo = self.entries[turn..target]
+            .iter()
+            .map(|entry| entry.event.clone())
+            .collect();
+
+        self.current_index = target;
+        self.saved_at_index = saved_seq.and_then(|seq| self.line_index(seq));
+        self.saved_in_branch = saved_seq.filter(|_| self.saved_at_index.is_none());
+        Some(UndoJump { undo, redo })
+    }
+
+    /// The events [`Self::jump_to`] would return, without moving.
+    pub fn preview_jump(&self, id: u64) -> Option<UndoJump> {
+        let mut copy = Self {
+            entries: self.entries.clone(),
+            current_index: self.current_index,
+            branches: self.branches.clone(),
+            ..Self::new()
+        };
+        copy.jump_to(id)
+    }
+
+    /// Keep a redo tail that is being replaced as a branch forking from the
+    /// last entry of the active line.
+    fn archive_branch(&mut self, entries: Vec<LogEntry>) {
+        if !entries.iter().any(|e| e.event.is_write_action()) {
+            return;
+        }
+        let fork_seq = self.entries.last().map_or(0, |e| e.seq);
+        self.branches.push(UndoBranch { fork_seq, entries });
+    }
+
+    /// Drop the oldest branches beyond [`MAX_UNDO_BRANCHES`], along with
+    /// branches that forked from them.
+    fn prune_branches(&mut self) {
+        if self.branches.len() <= MAX_UNDO_BRANCHES {
+            return;
+        }
+        self.branches
+            .drain(..self.branches.len() - MAX_UNDO_BRANCHES);
+        while let Some(orphan) = self
+            .branches
+            .iter()
+            .position(|b| b.fork_seq != 0 && self.locate(b.fork_seq).is_none())
+        {
+            self.branches.remove(orphan);
+        }
+    }
+
+    /// Make the entry `seq` part of the active line by swapping in the
+    /// branches leading to it, outermost first.
+    fn promote(&mut self, seq: u64) {
+        while let Some((Some(mut branch), _)) = self.locate(seq) {
+            while let Some((Some(outer), _)) = self.locate(self.branches[branch].fork_seq) {
+                branch = outer;
+            }
+            let branch = self.branches.remove(branch);
+            let Some(split) = self.line_index(branch.fork_seq) else {
+                return;
+            };
+            let tail = self.entries.split_off(split);
+            self.archive_branch(tail);
+            self.entries.extend(branch.entries);
+        }
+    }
+
+    /// Where `seq` is: `None` for the active line or the index of a branch,
+    /// and the position within it.
+    fn locate(&self, seq: u64) -> Option<(Option<usize>, usize)> {
+        if let Some(pos) = self.entries.iter().position(|e| e.seq == seq) {
+            return Some((None, pos));
+        }
+        self.branches.iter().enumerate().find_map(|(i, branch)| {
+            let pos = branch.entries.iter().position(|e| e.seq == seq)?;
+            Some((Some(i), pos))
+        })
+    }
+
+    fn entry(&self, seq: u64) -> Option<&LogEntry> {
+        let (branch, pos) = self.locate(seq)?;
+        Some(&self.line(branch).1[pos])
+    }
+
+    /// Index on the active line just after the entry `seq` (0 for the
+    /// initial state).
+    fn line_index(&self, seq: u64) -> Option<usize> {
+        if seq == 0 {
+            return Some(0);
+        }
+        self.entries
+            .iter()
+            .position(|e| e.seq == seq)
+            .map(|p| p + 1)
+    }
+
+    /// The fork and entries of a branch, or of the active line for `None`.
+    fn line(&self, branch: Option<usize>) -> (u64, &[LogEntry]) {
+        match branch {
+            None => (0, &self.entries),
+            Some(i) => (self.branches[i].fork_seq, &self.branches[i].entries),
+        }
+    }
+
+    /// For each of `entries`, its `seq` if it is the last write action of an
+    /// undo step (grouped as in [`Self::undo`]), i.e. the state it reaches.
+    fn step_ends(entries: &[LogEntry]) -> Vec<Option<u64>> {
+        let mut ends = vec![None; entries.len()];
+        // Group of the next write action, once one has been seen.
+        let mut next_group: Option<Option<u64>> = None;
+        for (i, entry) in entries.iter().enumerate().rev() {
+            if !entry.event.is_write_action() {
+                continue;
+            }
+            let same_step = matches!(
+                (entry.group_id, next_group),
+                (Some(group), Some(Some(next))) if group == next
+            );
+            if !same_step {
+                ends[i] = Some(entry.seq);
+            }
+            next_group = Some(entry.group_id);
+        }
+        ends
+    }
+
+    /// The state of the tree once entry `seq` has been applied.
+    fn state_after(&self, seq: u64) -> u64 {
+        let Some((branch, pos)) = self.locate(seq) else {
+            return 0;
+        };
+        let (fork_seq, entries) = self.line(branch);
+        Self::step_ends(entries)[..=pos]
+            .iter()
+            .rev()
+            .find_map(|end| *end)
+            .unwrap_or_else(|| self.state_after(fork_seq))
+    }
+
+    /// The state of the tree after the first `index` entries of the active
+    /// line.
+    fn state_at_index(&self, index: usize) -> u64 {
+        match index.checked_sub(1) {
+            Some(last) => self.state_after(self.entries[last].seq),
+            None => 0,
+        }
+    }
 }
 
 impl Default for EventLog {
@@ -1234,6 +1844,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_replace_bytes_inverse_swaps_bytes() {
+        let replace = Event::ReplaceBytes {
+            position: 1,
+            deleted: vec![b'b'],
+            inserted: vec![0xff, 0x00],
+            cursor_id: CursorId(0),
+        };
+
+        let Some(Event::ReplaceBytes {
+            position,
+            deleted,
+            inserted,
+            cursor_id,
+        }) = replace.inverse()
+        else {
+            panic!("Expected ReplaceBytes event");
+        };
+        assert_eq!(position, 1);
+        assert_eq!(deleted, vec![0xff, 0x00]);
+        assert_eq!(inserted, vec![b'b']);
+        assert_eq!(cursor_id, CursorId::UNDO_SENTINEL);
+
+        // Bytes that aren't UTF-8 can't be applied to plain text.
+        let mut text = "abc".to_string();
+        assert!(!replace.apply_to_text(&mut text));
+        let utf8 = Event::ReplaceBytes {
+            position: 1,
+            deleted: vec![b'b'],
+            inserted: b"xy".to_vec(),
+            cursor_id: CursorId(0),
+        };
+        assert!(utf8.apply_to_text(&mut text));
+        assert_eq!(text, "axyc");
+    }
+
     #[test]
     fn test_truncate_on_new_event_after_undo() {
         let mut log = EventLog::new();
@@ -1418,6 +2064,168 @@ mod tests {
         assert_eq!(log.current_index(), 0);
     }
 
+    fn insert(position: usize, text: &str) -> Event {
+        Event::Insert {
+            position,
+            text: text.to_string(),
+            cursor_id: CursorId(0),
+        }
+    }
+
+    fn step_edits(step: &Event) -> Vec<(usize, String, bool)> {
+        let Event::Batch { events, .. } = step else {
+            panic!("expected Batch, got {step:?}");
+        };
+        assert!(matches!(events[0], Event::MoveCursor { .. }));
+        events[1..]
+            .iter()
+            .map(|e| match e {
+                Event::Insert { position, text, .. } => (*position, text.clone(), true),
+                Event::Delete {
+                    range,
+                    deleted_text,
+                    ..
+                } => (range.start, deleted_text.clone(), false),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_persistable_history_stops_at_saved_position() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(Event::Scroll { line_offset: 1 });
+        log.begin_undo_group();
+        log.append(insert(1, "b"));
+        log.append(insert(2, "c"));
+        log.end_undo_group();
+        log.mark_saved();
+        log.append(insert(3, "unsaved"));
+
+        let steps = log.persistable_history();
+        assert_eq!(steps.len(), 2, "cursor-only entries dropped, group merged");
+        assert_eq!(step_edits(&steps[0]), vec![(0, "a".to_string(), true)]);
+        assert_eq!(
+            step_edits(&steps[1]),
+            vec![(1, "b".to_string(), true), (2, "c".to_string(), true)]
+        );
+
+        log.clear_saved_position();
+        assert!(log.persistable_history().is_empty());
+    }
+
+    #[test]
+    fn test_clear_saved_position_keeps_history() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.mark_saved();
+
+        log.clear_saved_position();
+        assert!(!log.is_at_saved_position());
+        assert!(log.can_undo());
+
+        log.discard_history();
+        assert!(!log.is_at_saved_position());
+        assert!(!log.can_undo());
+        assert!(!log.can_redo());
+    }
+
+    #[test]
+    fn test_seed_history_undoes_past_restart() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "hello"));
+        log.append(insert(5, " world"));
+        log.mark_saved();
+        let steps = log.persistable_history();
+
+        let mut reopened = EventLog::new();
+        reopened.seed_history(steps.clone());
+        assert!(reopened.is_at_saved_position());
+        assert!(!reopened.can_redo());
+
+        let undone = reopened.undo();
+        assert_eq!(undone.len(), 1);
+        let Event::Batch { events, .. } = &undone[0].0 else {
+            panic!("expected Batch");
+        };
+        assert!(matches!(
+            &events[0],
+            Event::Delete { range, .. } if *range == (5..11)
+        ));
+        assert!(matches!(
+            events[1],
+            Event::MoveCursor {
+                new_position: 5,
+                ..
+            }
+        ));
+        assert!(!reopened.is_at_saved_position());
+
+        // A log that already has history is left alone.
+        reopened.seed_history(steps);
+        assert_eq!(reopened.len(), 2);
+    }
+
+    #[test]
+    fn test_bulk_edit_persists_as_plain_edits() {
+        use crate::model::buffer::TextBuffer;
+
+        let mut buffer = TextBuffer::from_str_test("one two three");
+        let old_snapshot = buffer.snapshot_buffer_state();
+        buffer.apply_bulk_edits(&[(8, 5, "3"), (4, 0, "and "), (0, 3, "ONE")]);
+        let new_snapshot = buffer.snapshot_buffer_state();
+        assert_eq!(buffer.to_string().unwrap(), "ONE and two 3");
+
+        let mut log = EventLog::new();
+        log.append(Event::BulkEdit {
+            old_snapshot: Some(old_snapshot),
+            new_snapshot: Some(new_snapshot),
+            old_cursors: Vec::new(),
+            new_cursors: Vec::new(),
+            description: "bulk".to_string(),
+            edits: vec![(8, 5, 1), (4, 0, 4), (0, 3, 3)],
+            displaced_markers: Vec::new(),
+        });
+        log.mark_saved();
+        let steps = log.persistable_history();
+        assert_eq!(steps.len(), 1);
+
+        // Replaying the plain edits on the old text yields the new text.
+        let mut replay = TextBuffer::from_str_test("one two three");
+        let Event::Batch { events, .. } = &steps[0] else {
+            panic!("expected Batch");
+        };
+        for event in events {
+            match event {
+                Event::Insert { position, text, .. } => {
+                    replay.insert(*position, text);
+                }
+                Event::Delete { range, .. } => replay.delete(range.clone()),
+                _ => {}
+            }
+        }
+        assert_eq!(replay.to_string().unwrap(), "ONE and two 3");
+
+        // Without snapshots the edit can't be replayed.
+        let mut log = EventLog::new();
+        log.append(insert(0, "x"));
+        log.append(Event::BulkEdit {
+            old_snapshot: None,
+            new_snapshot: None,
+            old_cursors: Vec::new(),
+            new_cursors: Vec::new(),
+            description: "bulk".to_string(),
+            edits: vec![(0, 1, 1)],
+            displaced_markers: Vec::new(),
+        });
+        log.append(insert(0, "y"));
+        log.mark_saved();
+        let steps = log.persistable_history();
+        assert_eq!(steps.len(), 1, "history before the bulk edit is dropped");
+        assert_eq!(step_edits(&steps[0]), vec![(0, "y".to_string(), true)]);
+    }
+
     #[test]
     fn test_write_action_after_undo_clears_redo() {
         // Write actions after undo SHOULD still clear redo history
@@ -1500,4 +2308,162 @@ mod tests {
             "Should not be at saved position after undo + new edit"
         );
     }
+
+    /// Apply a jump to `text`, as the editor applies it to the buffer.
+    fn apply_jump(text: &mut String, jump: &UndoJump) {
+        for (event, _) in &jump.undo {
+            assert!(event.apply_to_text(text));
+        }
+        for event in &jump.redo {
+            assert!(event.apply_to_text(text));
+        }
+    }
+
+    #[test]
+    fn test_edit_after_undo_keeps_branch_in_tree() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.undo();
+        log.append(insert(1, "c"));
+
+        let tree = log.undo_tree();
+        let summary: Vec<_> = tree
+            .nodes
+            .iter()
+            .map(|n| (n.id, n.parent, n.description.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, 0, "Insert \"a\""),
+                (3, 1, "Insert \"c\""),
+                (2, 1, "Insert \"b\"")
+            ]
+        );
+        assert_eq!(tree.current, 3);
+        assert_eq!(tree.saved, Some(0));
+    }
+
+    #[test]
+    fn test_jump_to_abandoned_branch_and_back() {
+        let mut log = EventLog::new();
+        let mut text = String::new();
+        for (position, chunk) in [(0, "a"), (1, "b")] {
+            log.append(insert(position, chunk));
+            assert!(insert(position, chunk).apply_to_text(&mut text));
+        }
+        for (event, _) in log.undo() {
+            assert!(event.apply_to_text(&mut text));
+        }
+        log.append(insert(1, "c"));
+        text.push('c');
+        log.mark_saved();
+
+        // "ac" -> "ab": undo c, then redo b from the old branch.
+        let preview = log.preview_jump(2).unwrap();
+        let mut previewed = text.clone();
+        apply_jump(&mut previewed, &preview);
+        assert_eq!(previewed, "ab");
+        assert_eq!(log.undo_tree().current, 3, "preview doesn't move");
+
+        let jump = log.jump_to(2).unwrap();
+        apply_jump(&mut text, &jump);
+        assert_eq!(text, "ab");
+        assert_eq!(log.undo_tree().current, 2);
+        assert!(!log.is_at_saved_position());
+
+        // Plain undo/redo now walk the line through b.
+        for (event, _) in log.undo() {
+            assert!(event.apply_to_text(&mut text));
+        }
+        assert_eq!(text, "a");
+        for event in log.redo() {
+            assert!(event.apply_to_text(&mut text));
+        }
+        assert_eq!(text, "ab");
+
+        // Back to the saved state on the other branch.
+        let jump = log.jump_to(3).unwrap();
+        apply_jump(&mut text, &jump);
+        assert_eq!(text, "ac");
+        assert!(log.is_at_saved_position());
+        assert_eq!(log.undo_tree().saved, Some(3));
+
+        let jump = log.jump_to(0).unwrap();
+        apply_jump(&mut text, &jump);
+        assert_eq!(text, "");
+        assert!(log.jump_to(99).is_none());
+    }
+
+    #[test]
+    fn test_nested_branches_stay_reachable() {
+        let mut log = EventLog::new();
+        let mut text = String::new();
+        let edit = |log: &mut EventLog, text: &mut String, position: usize, chunk: &str| {
+            log.append(insert(position, chunk));
+            text.insert_str(position, chunk);
+        };
+        edit(&mut log, &mut text, 0, "1"); // seq 1
+        edit(&mut log, &mut text, 1, "2"); // seq 2
+        edit(&mut log, &mut text, 2, "3"); // seq 3
+        log.undo();
+        log.undo();
+        text.truncate(1);
+        edit(&mut log, &mut text, 1, "x"); // seq 4, branch [2, 3]
+        log.undo();
+        text.truncate(1);
+        edit(&mut log, &mut text, 1, "y"); // seq 5, branch [4]
+
+        let jump = log.jump_to(3).unwrap();
+        apply_jump(&mut text, &jump);
+        assert_eq!(text, "123");
+        let jump = log.jump_to(4).unwrap();
+        apply_jump(&mut text, &jump);
+        assert_eq!(text, "1x");
+        let jump = log.jump_to(5).unwrap();
+        apply_jump(&mut text, &jump);
+        assert_eq!(text, "1y");
+
+        let tree = log.undo_tree();
+        let mut parents: Vec<_> = tree.nodes.iter().map(|n| (n.id, n.parent)).collect();
+        parents.sort();
+        assert_eq!(parents, vec![(1, 0), (2, 1), (3, 2), (4, 1), (5, 1)]);
+    }
+
+    #[test]
+    fn test_grouped_edits_are_one_tree_node() {
+        let mut log = EventLog::new();
+        log.begin_undo_group();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.end_undo_group();
+
+        let tree = log.undo_tree();
+        assert_eq!(tree.nodes.len(), 1);
+        assert_eq!(tree.nodes[0].id, 2);
+        assert_eq!(tree.nodes[0].description, "Insert \"b\" (+1 edits)");
+    }
+
+    #[test]
+    fn test_current_state_matches_tree() {
+        let mut log = EventLog::new();
+        assert_eq!(log.current_state(), 0);
+        log.append(insert(0, "a"));
+        log.append(Event::MoveCursor {
+            cursor_id: CursorId(0),
+            old_position: 1,
+            new_position: 0,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        });
+        assert_eq!(log.current_state(), 1, "cursor moves don't change state");
+        log.append(insert(1, "b"));
+        log.undo();
+        assert_eq!(log.current_state(), log.undo_tree().current);
+        log.redo();
+        assert_eq!(log.current_state(), log.undo_tree().current);
+    }
 }
@@ -9,8 +9,11 @@
 //! The trait is synchronous. For async UI operations (like the file explorer),
 //! callers should use `spawn_blocking` or similar patterns.
 
+use crate::model::compression::{Compression, MAGIC_LEN};
 use std::io::{self, Read, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
 use std::time::SystemTime;
 
 // ============================================================================
@@ -408,6 +411,93 @@ pub struct SearchMatch {
     pub context: String,
 }
 
+// ============================================================================
+// Change Notifications
+// ============================================================================
+
+/// Receives each change reported by [`FileSystem::watch`], on a
+/// background thread.
+pub type FsChangeCallback =
+    Box<dyn Fn(PathBuf, crate::services::async_bridge::PathChangeKind) + Send + Sync>;
+
+/// A live [`FileSystem::watch`]. Dropping it stops the watch.
+pub struct FsWatch {
+    stop: Option<Box<dyn FnOnce() + Send>>,
+    state: Arc<AtomicU8>,
+}
+
+/// How far a [`FsWatch`] got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsWatchState {
+    /// Not delivering events yet
+    Starting,
+    /// Delivering events
+    Running,
+    /// Never started; no events will come
+    Failed,
+}
+
+impl FsWatchState {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Starting,
+            1 => Self::Running,
+            _ => Self::Failed,
+        }
+    }
+}
+
+impl FsWatch {
+    /// A running watch that runs `stop` when dropped.
+    pub fn new(stop: impl FnOnce() + Send + 'static) -> Self {
+        let (watch, starter) = Self::starting(stop);
+        starter.started();
+        watch
+    }
+
+    /// A watch still starting in the background, with the handle the
+    /// background side reports its outcome through.
+    pub fn starting(stop: impl FnOnce() + Send + 'static) -> (Self, FsWatchStarter) {
+        let state = Arc::new(AtomicU8::new(0));
+        let watch = Self {
+            stop: Some(Box::new(stop)),
+            state: state.clone(),
+        };
+        (watch, FsWatchStarter(state))
+    }
+
+    pub fn state(&self) -> FsWatchState {
+        FsWatchState::from_u8(self.state.load(Ordering::SeqCst))
+    }
+}
+
+/// Reports whether a [`FsWatch::starting`] watch came up
+pub struct FsWatchStarter(Arc<AtomicU8>);
+
+impl FsWatchStarter {
+    pub fn started(&self) {
+        self.0.store(1, Ordering::SeqCst);
+    }
+
+    pub fn failed(&self) {
+        self.0.store(2, Ordering::SeqCst);
+    }
+}
+
+impl Drop for FsWatch {
+    fn drop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            stop();
+        }
+    }
+}
+
+impl std::fmt::Debug for FsWatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FsWatch").finish_non_exhaustive()
+    }
+}
+
 // ============================================================================
 // FileSystem Trait
 // ============================================================================
@@ -493,6 +583,37 @@ pub trait FileSystem: Send + Sync {
         self.write_file(dst_path, &buffer)
     }
 
+    /// Compression format of a file that is edited transparently (see
+    /// [`crate::model::compression`]).
+    ///
+    /// The extension must name the format and, unless the file is missing or
+    /// empty, its first bytes must carry the format's magic number. Missing
+    /// files follow the extension alone, so a new `notes.gz` is compressed
+    /// on its first save.
+    fn detect_compression(&self, path: &Path) -> Option<Compression> {
+        let compression = Compression::from_extension(path)?;
+        let size = self.metadata_if_exists(path).map_or(0, |m| m.size);
+        if size == 0 {
+            return Some(compression);
+        }
+        let head = self
+            .read_range(path, 0, (size as usize).min(MAGIC_LEN))
+            .ok()?;
+        compression.matches_magic(&head).then_some(compression)
+    }
+
+    /// Read a compressed file and return its decompressed contents.
+    ///
+    /// The default implementation streams `open_file` through the decoder,
+    /// so the compressed bytes are never held in memory as a whole.
+    fn read_file_decompressed(&self, path: &Path, compression: Compression) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        compression
+            .decoder(self.open_file(path)?)?
+            .read_to_end(&mut data)?;
+        Ok(data)
+    }
+
     // ========================================================================
     // File Operations
     // ========================================================================
@@ -743,6 +864,29 @@ pub trait FileSystem: Send + Sync {
         cancel: &std::sync::atomic::AtomicBool,
         on_file: &mut dyn FnMut(&Path, &str) -> bool,
     ) -> io::Result<()>;
+
+    // ========================================================================
+    // Change Notifications
+    // ========================================================================
+
+    /// Watch `path` (and everything below it when `recursive`) for changes
+    /// made on the host this filesystem lives on, calling `on_change` for
+    /// each one until the returned [`FsWatch`] is dropped.
+    ///
+    /// Returns `Ok(None)` when the filesystem has no notifications of its
+    /// own; callers then poll metadata. Must not block on a round trip:
+    /// a watch that needs one comes back [`FsWatchState::Starting`] and
+    /// callers keep polling until it reports `Running`. Local files are watched with
+    /// `notify` in `services::file_watcher`, so only remote
+    /// implementations override this.
+    fn watch(
+        &self,
+        _path: &Path,
+        _recursive: bool,
+        _on_change: FsChangeCallback,
+    ) -> io::Result<Option<FsWatch>> {
+        Ok(None)
+    }
 }
 
 // ============================================================================
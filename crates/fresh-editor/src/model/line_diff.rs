@@ -82,6 +82,179 @@ pub fn diff_lines(saved: &[u8], current: &[u8]) -> LineDiff {
     }
 }
 
+/// Largest middle section (old lines × new lines, after trimming the common
+/// prefix and suffix) that [`unified_diff`] and [`diff_hunks`] diff line by
+/// line. Bigger sections are shown as replaced wholesale.
+const MAX_UNIFIED_DIFF_CELLS: usize = 4_000_000;
+
+/// A run of changed lines, as shown by a side-by-side diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    /// First old line of the hunk (0-indexed)
+    pub old_start: usize,
+    /// Number of old lines removed
+    pub old_count: usize,
+    /// First new line of the hunk (0-indexed)
+    pub new_start: usize,
+    /// Number of new lines added
+    pub new_count: usize,
+    /// One char per line in old/new order: `'-'` removed, `'+'` added
+    pub ops: String,
+}
+
+/// Each line of the diff from `old_lines` to `new_lines` as (old line
+/// index, new line index); a removed line has no new index and an added
+/// line no old one. Removed lines come before the added lines they sit
+/// next to.
+fn align_lines(old_lines: &[&str], new_lines: &[&str]) -> Vec<(Option<usize>, Option<usize>)> {
+    let prefix = old_lines
+        .iter()
+        .zip(new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid: Vec<&[u8]> = old_lines[prefix..old_lines.len() - suffix]
+        .iter()
+        .map(|line| line.as_bytes())
+        .collect();
+    let new_mid: Vec<&[u8]> = new_lines[prefix..new_lines.len() - suffix]
+        .iter()
+        .map(|line| line.as_bytes())
+        .collect();
+
+    let mut lines: Vec<(Option<usize>, Option<usize>)> =
+        (0..prefix).map(|i| (Some(i), Some(i))).collect();
+    let matches = if old_mid.len().saturating_mul(new_mid.len()) <= MAX_UNIFIED_DIFF_CELLS {
+        longest_common_subsequence(&old_mid, &new_mid)
+    } else {
+        Vec::new()
+    };
+    let end = LineMatch {
+        saved_idx: old_mid.len(),
+        current_idx: new_mid.len(),
+    };
+    let (mut i, mut j) = (0, 0);
+    for m in matches.into_iter().chain(std::iter::once(end)) {
+        lines.extend((i..m.saved_idx).map(|i| (Some(prefix + i), None)));
+        lines.extend((j..m.current_idx).map(|j| (None, Some(prefix + j))));
+        lines.push((Some(prefix + m.saved_idx), Some(prefix + m.current_idx)));
+        i = m.saved_idx + 1;
+        j = m.current_idx + 1;
+    }
+    // The end marker pushed the first suffix line (or one past the last
+    // line); replace it with the actual suffix.
+    lines.pop();
+    lines.extend((0..suffix).map(|k| {
+        (
+            Some(old_lines.len() - suffix + k),
+            Some(new_lines.len() - suffix + k),
+        )
+    }));
+    lines
+}
+
+/// The runs of changed lines from `old` to `new`, for a side-by-side view
+/// that shows both texts in full. A final newline doesn't start an extra
+/// line. Returns no hunks when the texts are equal.
+pub fn diff_hunks(old: &str, new: &str) -> Vec<DiffHunk> {
+    fn split(text: &str) -> Vec<&str> {
+        text.strip_suffix('\n')
+            .unwrap_or(text)
+            .split('\n')
+            .collect()
+    }
+    let (old_lines, new_lines) = (split(old), split(new));
+
+    let mut hunks: Vec<DiffHunk> = Vec::new();
+    let mut in_hunk = false;
+    let (mut old_line, mut new_line) = (0, 0);
+    for (o, n) in align_lines(&old_lines, &new_lines) {
+        if o.is_some() && n.is_some() {
+            in_hunk = false;
+            old_line += 1;
+            new_line += 1;
+            continue;
+        }
+        if !in_hunk {
+            hunks.push(DiffHunk {
+                old_start: old_line,
+                old_count: 0,
+                new_start: new_line,
+                new_count: 0,
+                ops: String::new(),
+            });
+            in_hunk = true;
+        }
+        let hunk = hunks.last_mut().expect("a hunk was just started");
+        if o.is_some() {
+            hunk.ops.push('-');
+            hunk.old_count += 1;
+            old_line += 1;
+        } else {
+            hunk.ops.push('+');
+            hunk.new_count += 1;
+            new_line += 1;
+        }
+    }
+    hunks
+}
+
+/// Render the change from `old` to `new` as unified diff hunks: `@@` headers
+/// followed by lines prefixed with ` `, `-` or `+`, with `context` unchanged
+/// lines around each change. Returns no lines when the texts are equal.
+pub fn unified_diff(old: &str, new: &str, context: usize) -> Vec<String> {
+    let old_lines: Vec<&str> = old.split('\n').collect();
+    let new_lines: Vec<&str> = new.split('\n').collect();
+    let lines = align_lines(&old_lines, &new_lines);
+
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (o, n))| o.is_none() || n.is_none())
+        .map(|(k, _)| k)
+        .collect();
+
+    let mut out = Vec::new();
+    let mut k = 0;
+    while k < changed.len() {
+        let start = changed[k].saturating_sub(context);
+        let mut last = changed[k];
+        while k + 1 < changed.len() && changed[k + 1] <= last + 2 * context + 1 {
+            k += 1;
+            last = changed[k];
+        }
+        let end = (last + context + 1).min(lines.len());
+        k += 1;
+
+        let count = |range: &[(Option<usize>, Option<usize>)], old: bool| {
+            range
+                .iter()
+                .filter(|(o, n)| if old { o.is_some() } else { n.is_some() })
+                .count()
+        };
+        let hunk = &lines[start..end];
+        let (old_count, new_count) = (count(hunk, true), count(hunk, false));
+        let old_start = count(&lines[..start], true) + usize::from(old_count > 0);
+        let new_start = count(&lines[..start], false) + usize::from(new_count > 0);
+        out.push(format!(
+            "@@ -{},{} +{},{} @@",
+            old_start, old_count, new_start, new_count
+        ));
+        out.extend(hunk.iter().map(|line| match *line {
+            (Some(o), Some(_)) => format!(" {}", old_lines[o]),
+            (Some(o), None) => format!("-{}", old_lines[o]),
+            (None, Some(n)) => format!("+{}", new_lines[n]),
+            (None, None) => unreachable!("every diff line has a side"),
+        }));
+    }
+    out
+}
+
 /// Represents a match between saved and current line indices
 #[derive(Debug, Clone, Copy)]
 struct LineMatch {
@@ -303,6 +476,69 @@ pub fn merge_ranges(ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_unified_diff_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\n";
+        assert_eq!(
+            unified_diff(old, new, 1),
+            vec![
+                "@@ -1,3 +1,3 @@",
+                " a",
+                "-b",
+                "+B",
+                " c",
+                "@@ -8,2 +8,3 @@",
+                " h",
+                "+i",
+                " ",
+            ]
+        );
+        // Nearby changes share a hunk.
+        assert_eq!(
+            unified_diff(old, new, 3)
+                .iter()
+                .filter(|l| l.starts_with("@@"))
+                .count(),
+            1
+        );
+        assert!(unified_diff(old, old, 3).is_empty());
+    }
+
+    #[test]
+    fn test_unified_diff_pure_insertion_and_removal() {
+        assert_eq!(unified_diff("", "x", 3), vec!["@@ -1,1 +1,1 @@", "-", "+x"]);
+        assert_eq!(unified_diff("a\nb", "a", 0), vec!["@@ -2,1 +1,0 @@", "-b"]);
+    }
+
+    #[test]
+    fn test_diff_hunks() {
+        let old = "a\nb\nc\nd\n";
+        let new = "a\nB\nc\nd\ne\n";
+        assert_eq!(
+            diff_hunks(old, new),
+            vec![
+                DiffHunk {
+                    old_start: 1,
+                    old_count: 1,
+                    new_start: 1,
+                    new_count: 1,
+                    ops: "-+".to_string(),
+                },
+                DiffHunk {
+                    old_start: 4,
+                    old_count: 0,
+                    new_start: 4,
+                    new_count: 1,
+                    ops: "+".to_string(),
+                },
+            ]
+        );
+        assert!(diff_hunks(old, old).is_empty());
+        // Only the final newline differs: no extra line either side.
+        assert!(diff_hunks("a\nb", "a\nb\n").is_empty());
+    }
+
     #[test]
     fn test_identical_content() {
         let content = b"line 1\nline 2\nline 3\n";
@@ -5,6 +5,7 @@
 pub mod buffer;
 pub mod buffer_position;
 pub mod composite_buffer;
+pub mod compression;
 pub mod control_event;
 pub mod cursor;
 pub mod document_model;
@@ -18,3 +19,4 @@ pub mod marker;
 pub mod marker_tree;
 pub mod piece_tree;
 pub mod piece_tree_diff;
+pub mod snippet_session;
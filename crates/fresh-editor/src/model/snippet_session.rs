@@ -0,0 +1,262 @@
+//! Snippet session state for one buffer.
+//!
+//! After a snippet with tabstops is inserted, Tab and Shift+Tab step through
+//! its placeholders until the final `$0`. Every tabstop range is held by a
+//! pair of markers so it follows edits, both typing inside the placeholder
+//! and changes elsewhere in the buffer.
+//!
+//! Marker gravity is set per visit: the ranges of the current tabstop grow
+//! when text is typed at either edge, the others don't, so typing at the end
+//! of one placeholder never leaks into the next.
+
+use std::ops::Range;
+
+use crate::model::marker::{MarkerId, MarkerList};
+use crate::primitives::snippet::{ExpandedSnippet, Transform};
+
+/// A byte range held by a start and an end marker.
+#[derive(Debug, Clone, Copy)]
+struct MarkerRange {
+    start: MarkerId,
+    end: MarkerId,
+}
+
+impl MarkerRange {
+    /// Anchor `range`. A growing range takes text typed at its edges; a
+    /// fixed one leaves it outside.
+    fn new(range: Range<usize>, grows: bool, markers: &mut MarkerList) -> Self {
+        if grows {
+            Self {
+                start: markers.create_left_gravity(range.start),
+                end: markers.create(range.end, false),
+            }
+        } else {
+            Self {
+                start: markers.create(range.start, false),
+                end: markers.create_left_gravity(range.end),
+            }
+        }
+    }
+
+    fn get(&self, markers: &MarkerList) -> Option<Range<usize>> {
+        let start = markers.get_position(self.start)?;
+        // A fixed empty range pushed by text typed at its position ends up
+        // inverted; it now sits after that text.
+        let end = markers.get_position(self.end)?.max(start);
+        Some(start..end)
+    }
+
+    fn release(&self, markers: &mut MarkerList) {
+        markers.delete(self.start);
+        markers.delete(self.end);
+    }
+}
+
+#[derive(Debug)]
+struct SessionTabstop {
+    ranges: Vec<MarkerRange>,
+    transformed: Vec<(MarkerRange, Transform)>,
+    choices: Vec<String>,
+}
+
+/// Tabstops of the snippet being filled in, in visiting order (`$0` last).
+#[derive(Debug)]
+pub struct SnippetSession {
+    tabstops: Vec<SessionTabstop>,
+    current: usize,
+}
+
+impl SnippetSession {
+    /// Anchor the tabstops of `snippet`, inserted at byte `base`, with the
+    /// first one current. Returns `None` when there is nothing to step
+    /// through, i.e. the snippet only has its final `$0`.
+    pub fn start(snippet: &ExpandedSnippet, base: usize, markers: &mut MarkerList) -> Option<Self> {
+        if snippet.tabstops.len() < 2 {
+            return None;
+        }
+        let at_base = |r: &Range<usize>| base + r.start..base + r.end;
+        let tabstops = snippet
+            .tabstops
+            .iter()
+            .enumerate()
+            .map(|(i, tabstop)| SessionTabstop {
+                ranges: tabstop
+                    .ranges
+                    .iter()
+                    .map(|r| MarkerRange::new(at_base(r), i == 0, markers))
+                    .collect(),
+                transformed: tabstop
+                    .transformed
+                    .iter()
+                    .map(|(r, transform)| {
+                        (
+                            MarkerRange::new(at_base(r), false, markers),
+                            transform.clone(),
+                        )
+                    })
+                    .collect(),
+                choices: tabstop.choices.clone(),
+            })
+            .collect();
+        Some(Self {
+            tabstops,
+            current: 0,
+        })
+    }
+
+    /// Number of tabstops, including the final `$0`.
+    pub fn len(&self) -> usize {
+        self.tabstops.len()
+    }
+
+    /// Always false: a session has at least two tabstops.
+    pub fn is_empty(&self) -> bool {
+        self.tabstops.is_empty()
+    }
+
+    /// Index of the current tabstop in visiting order.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Make tabstop `index` current, re-anchoring markers so that only its
+    /// ranges grow as the user types.
+    pub fn set_current(&mut self, index: usize, markers: &mut MarkerList) {
+        let previous = self.current;
+        self.current = index;
+        for (i, grows) in [(previous, false), (index, true)] {
+            for range in &mut self.tabstops[i].ranges {
+                if let Some(r) = range.get(markers) {
+                    range.release(markers);
+                    *range = MarkerRange::new(r, grows, markers);
+                }
+            }
+        }
+    }
+
+    /// Current byte ranges of tabstop `index`; the placeholder comes first,
+    /// then its mirrors.
+    pub fn ranges(&self, index: usize, markers: &MarkerList) -> Vec<Range<usize>> {
+        self.tabstops[index]
+            .ranges
+            .iter()
+            .filter_map(|r| r.get(markers))
+            .collect()
+    }
+
+    /// Transformed mirrors of tabstop `index` with their current ranges.
+    pub fn transformed(
+        &self,
+        index: usize,
+        markers: &MarkerList,
+    ) -> Vec<(Range<usize>, Transform)> {
+        self.tabstops[index]
+            .transformed
+            .iter()
+            .filter_map(|(r, transform)| Some((r.get(markers)?, transform.clone())))
+            .collect()
+    }
+
+    /// Re-anchor the transformed mirrors of tabstop `index` after they were
+    /// rewritten. `ranges` pairs up with [`Self::transformed`].
+    pub fn set_transformed_ranges(
+        &mut self,
+        index: usize,
+        ranges: Vec<Range<usize>>,
+        markers: &mut MarkerList,
+    ) {
+        for ((marker_range, _), range) in self.tabstops[index].transformed.iter_mut().zip(ranges) {
+            marker_range.release(markers);
+            *marker_range = MarkerRange::new(range, false, markers);
+        }
+    }
+
+    /// Options of tabstop `index` if it is a choice.
+    pub fn choices(&self, index: usize) -> &[String] {
+        &self.tabstops[index].choices
+    }
+
+    /// Whether `pos` lies within the snippet, from its first tabstop byte
+    /// to its last. A cursor outside means the user moved on.
+    pub fn contains(&self, pos: usize, markers: &MarkerList) -> bool {
+        let ranges = self.tabstops.iter().flat_map(|t| {
+            t.ranges
+                .iter()
+                .chain(t.transformed.iter().map(|(r, _)| r))
+                .filter_map(|r| r.get(markers))
+        });
+        let (start, end) = ranges.fold((usize::MAX, 0), |(start, end), r| {
+            (start.min(r.start), end.max(r.end))
+        });
+        start <= pos && pos <= end
+    }
+
+    /// Delete the session's markers.
+    pub fn release(self, markers: &mut MarkerList) {
+        for tabstop in &self.tabstops {
+            for range in tabstop
+                .ranges
+                .iter()
+                .chain(tabstop.transformed.iter().map(|(r, _)| r))
+            {
+                range.release(markers);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::snippet::expand_snippet;
+
+    fn session(snippet: &str, base: usize) -> (SnippetSession, MarkerList) {
+        let mut markers = MarkerList::new();
+        let session = SnippetSession::start(&expand_snippet(snippet), base, &mut markers).unwrap();
+        (session, markers)
+    }
+
+    #[test]
+    fn test_no_session_without_tabstops() {
+        let mut markers = MarkerList::new();
+        assert!(SnippetSession::start(&expand_snippet("foo($0)"), 0, &mut markers).is_none());
+    }
+
+    #[test]
+    fn test_ranges_offset_by_base() {
+        let (session, markers) = session("f(${1:a}, ${2:b})$0", 10);
+        assert_eq!(session.len(), 3);
+        assert_eq!(session.ranges(0, &markers), vec![12..13]);
+        assert_eq!(session.ranges(1, &markers), vec![15..16]);
+        assert_eq!(session.ranges(2, &markers), vec![17..17]);
+    }
+
+    #[test]
+    fn test_current_tabstop_grows_neighbours_do_not() {
+        // `${1:a}${2:b}`: typing at the end of $1 must not extend $2.
+        let (session, mut markers) = session("${1:a}${2:b}", 0);
+        markers.adjust_for_insert(1, 2);
+        assert_eq!(session.ranges(0, &markers), vec![0..3]);
+        assert_eq!(session.ranges(1, &markers), vec![3..4]);
+    }
+
+    #[test]
+    fn test_set_current_moves_growth() {
+        let (mut session, mut markers) = session("${1:a}${2:b}", 0);
+        session.set_current(1, &mut markers);
+        // Typing at the start of $2 now extends $2, not $1.
+        markers.adjust_for_insert(1, 2);
+        assert_eq!(session.ranges(0, &markers), vec![0..1]);
+        assert_eq!(session.ranges(1, &markers), vec![1..4]);
+    }
+
+    #[test]
+    fn test_contains_and_release() {
+        let (session, mut markers) = session("x(${1:a})$0", 5);
+        assert!(session.contains(7, &markers));
+        assert!(session.contains(9, &markers));
+        assert!(!session.contains(2, &markers));
+        session.release(&mut markers);
+        assert_eq!(markers.marker_count(), 0);
+    }
+}
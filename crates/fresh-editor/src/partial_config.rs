@@ -8,7 +8,7 @@ use crate::config::{
     Keybinding, KeybindingMapName, KeymapConfig, LanguageConfig, LineEndingOption, OnSaveAction,
     PluginConfig, TerminalConfig, ThemeName, WarningsConfig,
 };
-use crate::types::LspLanguageConfig;
+use crate::types::{DebuggerConfig, LspLanguageConfig, ProblemMatcherConfig, TaskConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -85,11 +85,15 @@ pub struct PartialConfig {
     pub keybindings: Option<Vec<Keybinding>>,
     pub keybinding_maps: Option<HashMap<String, KeymapConfig>>,
     pub active_keybinding_map: Option<KeybindingMapName>,
+    pub macros: Option<HashMap<String, Vec<serde_json::Value>>>,
     pub languages: Option<HashMap<String, PartialLanguageConfig>>,
     pub default_language: Option<String>,
     pub lsp_enabled: Option<bool>,
     pub lsp: Option<HashMap<String, LspLanguageConfig>>,
     pub universal_lsp: Option<HashMap<String, LspLanguageConfig>>,
+    pub debuggers: Option<HashMap<String, DebuggerConfig>>,
+    pub tasks: Option<HashMap<String, TaskConfig>>,
+    pub problem_matchers: Option<HashMap<String, ProblemMatcherConfig>>,
     pub warnings: Option<PartialWarningsConfig>,
     pub plugins: Option<HashMap<String, PartialPluginConfig>>,
     pub packages: Option<PartialPackagesConfig>,
@@ -121,11 +125,15 @@ impl Merge for PartialConfig {
 
         // HashMaps: merge entries, higher precedence wins on key collision
         merge_hashmap(&mut self.keybinding_maps, &other.keybinding_maps);
+        merge_hashmap(&mut self.macros, &other.macros);
         merge_hashmap_recursive(&mut self.languages, &other.languages);
         self.default_language.merge_from(&other.default_language);
         self.lsp_enabled.merge_from(&other.lsp_enabled);
         merge_hashmap(&mut self.lsp, &other.lsp);
         merge_hashmap(&mut self.universal_lsp, &other.universal_lsp);
+        merge_hashmap(&mut self.debuggers, &other.debuggers);
+        merge_hashmap(&mut self.tasks, &other.tasks);
+        merge_hashmap(&mut self.problem_matchers, &other.problem_matchers);
         merge_hashmap_recursive(&mut self.plugins, &other.plugins);
 
         self.active_keybinding_map
@@ -161,6 +169,7 @@ pub struct PartialEditorConfig {
     pub highlight_occurrences: Option<bool>,
     pub hide_current_line_on_selection: Option<bool>,
     pub highlight_current_column: Option<bool>,
+    pub sticky_scroll: Option<bool>,
     pub line_wrap: Option<bool>,
     pub wrap_indent: Option<bool>,
     pub wrap_column: Option<Option<usize>>,
@@ -171,9 +180,15 @@ pub struct PartialEditorConfig {
     pub estimated_line_length: Option<usize>,
     pub enable_inlay_hints: Option<bool>,
     pub enable_semantic_tokens_full: Option<bool>,
+    pub preview_workspace_edits: Option<bool>,
     pub diagnostics_inline_text: Option<bool>,
     pub recovery_enabled: Option<bool>,
     pub auto_recovery_save_interval_secs: Option<u32>,
+    pub persistent_undo: Option<bool>,
+    pub local_history_enabled: Option<bool>,
+    pub local_history_max_entries: Option<usize>,
+    pub local_history_max_age_days: Option<u32>,
+    pub local_history_max_size_bytes: Option<u64>,
     pub auto_save_enabled: Option<bool>,
     pub auto_save_interval_secs: Option<u32>,
     pub hot_exit: Option<bool>,
@@ -213,6 +228,7 @@ pub struct PartialEditorConfig {
     pub show_prompt_line: Option<bool>,
     pub show_vertical_scrollbar: Option<bool>,
     pub show_horizontal_scrollbar: Option<bool>,
+    pub show_minimap: Option<bool>,
     pub show_tilde: Option<bool>,
     pub use_terminal_bg: Option<bool>,
     pub set_window_title: Option<bool>,
@@ -243,6 +259,7 @@ impl Merge for PartialEditorConfig {
         self.scroll_offset.merge_from(&other.scroll_offset);
         self.syntax_highlighting
             .merge_from(&other.syntax_highlighting);
+        self.sticky_scroll.merge_from(&other.sticky_scroll);
         self.line_wrap.merge_from(&other.line_wrap);
         self.wrap_indent.merge_from(&other.wrap_indent);
         self.wrap_column.merge_from(&other.wrap_column);
@@ -258,11 +275,22 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.enable_inlay_hints);
         self.enable_semantic_tokens_full
             .merge_from(&other.enable_semantic_tokens_full);
+        self.preview_workspace_edits
+            .merge_from(&other.preview_workspace_edits);
         self.diagnostics_inline_text
             .merge_from(&other.diagnostics_inline_text);
         self.recovery_enabled.merge_from(&other.recovery_enabled);
         self.auto_recovery_save_interval_secs
             .merge_from(&other.auto_recovery_save_interval_secs);
+        self.persistent_undo.merge_from(&other.persistent_undo);
+        self.local_history_enabled
+            .merge_from(&other.local_history_enabled);
+        self.local_history_max_entries
+            .merge_from(&other.local_history_max_entries);
+        self.local_history_max_age_days
+            .merge_from(&other.local_history_max_age_days);
+        self.local_history_max_size_bytes
+            .merge_from(&other.local_history_max_size_bytes);
         self.auto_save_enabled.merge_from(&other.auto_save_enabled);
         self.auto_save_interval_secs
             .merge_from(&other.auto_save_interval_secs);
@@ -330,6 +358,7 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.show_vertical_scrollbar);
         self.show_horizontal_scrollbar
             .merge_from(&other.show_horizontal_scrollbar);
+        self.show_minimap.merge_from(&other.show_minimap);
         self.show_tilde.merge_from(&other.show_tilde);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
         self.set_window_title.merge_from(&other.set_window_title);
@@ -416,6 +445,8 @@ impl Merge for PartialFileBrowserConfig {
 pub struct PartialClipboardConfig {
     pub use_osc52: Option<bool>,
     pub use_system_clipboard: Option<bool>,
+    pub history_size: Option<usize>,
+    pub persist_history: Option<bool>,
 }
 
 impl Merge for PartialClipboardConfig {
@@ -423,6 +454,8 @@ impl Merge for PartialClipboardConfig {
         self.use_osc52.merge_from(&other.use_osc52);
         self.use_system_clipboard
             .merge_from(&other.use_system_clipboard);
+        self.history_size.merge_from(&other.history_size);
+        self.persist_history.merge_from(&other.persist_history);
     }
 }
 
@@ -539,6 +572,8 @@ pub struct PartialLanguageConfig {
     pub on_save: Option<Vec<OnSaveAction>>,
     pub word_characters: Option<Option<String>>,
     pub indent: Option<crate::config::IndentRulesConfig>,
+    pub linked_editing: Option<bool>,
+    pub on_type_formatting: Option<bool>,
 }
 
 impl Merge for PartialLanguageConfig {
@@ -564,6 +599,9 @@ impl Merge for PartialLanguageConfig {
         self.on_save.merge_from(&other.on_save);
         self.word_characters.merge_from(&other.word_characters);
         self.indent.merge_from(&other.indent);
+        self.linked_editing.merge_from(&other.linked_editing);
+        self.on_type_formatting
+            .merge_from(&other.on_type_formatting);
     }
 }
 
@@ -587,6 +625,7 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             highlight_occurrences: Some(cfg.highlight_occurrences),
             hide_current_line_on_selection: Some(cfg.hide_current_line_on_selection),
             highlight_current_column: Some(cfg.highlight_current_column),
+            sticky_scroll: Some(cfg.sticky_scroll),
             line_wrap: Some(cfg.line_wrap),
             wrap_indent: Some(cfg.wrap_indent),
             wrap_column: Some(cfg.wrap_column),
@@ -597,9 +636,15 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             estimated_line_length: Some(cfg.estimated_line_length),
             enable_inlay_hints: Some(cfg.enable_inlay_hints),
             enable_semantic_tokens_full: Some(cfg.enable_semantic_tokens_full),
+            preview_workspace_edits: Some(cfg.preview_workspace_edits),
             diagnostics_inline_text: Some(cfg.diagnostics_inline_text),
             recovery_enabled: Some(cfg.recovery_enabled),
             auto_recovery_save_interval_secs: Some(cfg.auto_recovery_save_interval_secs),
+            persistent_undo: Some(cfg.persistent_undo),
+            local_history_enabled: Some(cfg.local_history_enabled),
+            local_history_max_entries: Some(cfg.local_history_max_entries),
+            local_history_max_age_days: Some(cfg.local_history_max_age_days),
+            local_history_max_size_bytes: Some(cfg.local_history_max_size_bytes),
             auto_save_enabled: Some(cfg.auto_save_enabled),
             auto_save_interval_secs: Some(cfg.auto_save_interval_secs),
             hot_exit: Some(cfg.hot_exit),
@@ -645,6 +690,7 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             show_prompt_line: Some(cfg.show_prompt_line),
             show_vertical_scrollbar: Some(cfg.show_vertical_scrollbar),
             show_horizontal_scrollbar: Some(cfg.show_horizontal_scrollbar),
+            show_minimap: Some(cfg.show_minimap),
             show_tilde: Some(cfg.show_tilde),
             use_terminal_bg: Some(cfg.use_terminal_bg),
             set_window_title: Some(cfg.set_window_title),
@@ -694,6 +740,7 @@ impl PartialEditorConfig {
             highlight_current_column: self
                 .highlight_current_column
                 .unwrap_or(defaults.highlight_current_column),
+            sticky_scroll: self.sticky_scroll.unwrap_or(defaults.sticky_scroll),
             line_wrap: self.line_wrap.unwrap_or(defaults.line_wrap),
             wrap_indent: self.wrap_indent.unwrap_or(defaults.wrap_indent),
             wrap_column: self.wrap_column.unwrap_or(defaults.wrap_column),
@@ -714,6 +761,9 @@ impl PartialEditorConfig {
             enable_semantic_tokens_full: self
                 .enable_semantic_tokens_full
                 .unwrap_or(defaults.enable_semantic_tokens_full),
+            preview_workspace_edits: self
+                .preview_workspace_edits
+                .unwrap_or(defaults.preview_workspace_edits),
             diagnostics_inline_text: self
                 .diagnostics_inline_text
                 .unwrap_or(defaults.diagnostics_inline_text),
@@ -721,6 +771,19 @@ impl PartialEditorConfig {
             auto_recovery_save_interval_secs: self
                 .auto_recovery_save_interval_secs
                 .unwrap_or(defaults.auto_recovery_save_interval_secs),
+            persistent_undo: self.persistent_undo.unwrap_or(defaults.persistent_undo),
+            local_history_enabled: self
+                .local_history_enabled
+                .unwrap_or(defaults.local_history_enabled),
+            local_history_max_entries: self
+                .local_history_max_entries
+                .unwrap_or(defaults.local_history_max_entries),
+            local_history_max_age_days: self
+                .local_history_max_age_days
+                .unwrap_or(defaults.local_history_max_age_days),
+            local_history_max_size_bytes: self
+                .local_history_max_size_bytes
+                .unwrap_or(defaults.local_history_max_size_bytes),
             auto_save_enabled: self.auto_save_enabled.unwrap_or(defaults.auto_save_enabled),
             auto_save_interval_secs: self
                 .auto_save_interval_secs
@@ -814,6 +877,7 @@ impl PartialEditorConfig {
             show_horizontal_scrollbar: self
                 .show_horizontal_scrollbar
                 .unwrap_or(defaults.show_horizontal_scrollbar),
+            show_minimap: self.show_minimap.unwrap_or(defaults.show_minimap),
             show_tilde: self.show_tilde.unwrap_or(defaults.show_tilde),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
             set_window_title: self.set_window_title.unwrap_or(defaults.set_window_title),
@@ -915,6 +979,8 @@ impl From<&ClipboardConfig> for PartialClipboardConfig {
         Self {
             use_osc52: Some(cfg.use_osc52),
             use_system_clipboard: Some(cfg.use_system_clipboard),
+            history_size: Some(cfg.history_size),
+            persist_history: Some(cfg.persist_history),
         }
     }
 }
@@ -926,6 +992,8 @@ impl PartialClipboardConfig {
             use_system_clipboard: self
                 .use_system_clipboard
                 .unwrap_or(defaults.use_system_clipboard),
+            history_size: self.history_size.unwrap_or(defaults.history_size),
+            persist_history: self.persist_history.unwrap_or(defaults.persist_history),
         }
     }
 }
@@ -1040,6 +1108,8 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             on_save: Some(cfg.on_save.clone()),
             word_characters: Some(cfg.word_characters.clone()),
             indent: cfg.indent.clone(),
+            linked_editing: Some(cfg.linked_editing),
+            on_type_formatting: Some(cfg.on_type_formatting),
         }
     }
 }
@@ -1077,6 +1147,10 @@ impl PartialLanguageConfig {
                 .word_characters
                 .unwrap_or_else(|| defaults.word_characters.clone()),
             indent: self.indent.or_else(|| defaults.indent.clone()),
+            linked_editing: self.linked_editing.unwrap_or(defaults.linked_editing),
+            on_type_formatting: self
+                .on_type_formatting
+                .unwrap_or(defaults.on_type_formatting),
         }
     }
 }
@@ -1096,6 +1170,7 @@ impl From<&crate::config::Config> for PartialConfig {
             keybindings: Some(cfg.keybindings.clone()),
             keybinding_maps: Some(cfg.keybinding_maps.clone()),
             active_keybinding_map: Some(cfg.active_keybinding_map.clone()),
+            macros: Some(cfg.macros.clone()),
             languages: Some(
                 cfg.languages
                     .iter()
@@ -1135,6 +1210,9 @@ impl From<&crate::config::Config> for PartialConfig {
                     })
                     .collect(),
             ),
+            debuggers: Some(cfg.debuggers.clone()),
+            tasks: Some(cfg.tasks.clone()),
+            problem_matchers: Some(cfg.problem_matchers.clone()),
             warnings: Some(PartialWarningsConfig::from(&cfg.warnings)),
             // Only include plugins that differ from defaults
             // Path is auto-discovered at runtime and should never be saved
@@ -1310,6 +1388,13 @@ impl PartialConfig {
             active_keybinding_map: self
                 .active_keybinding_map
                 .unwrap_or_else(|| defaults.active_keybinding_map.clone()),
+            macros: {
+                let mut result = defaults.macros.clone();
+                if let Some(partial_macros) = self.macros {
+                    result.extend(partial_macros);
+                }
+                result
+            },
             languages,
             default_language: self
                 .default_language
@@ -1317,6 +1402,28 @@ impl PartialConfig {
             lsp_enabled: self.lsp_enabled.unwrap_or(defaults.lsp_enabled),
             lsp,
             universal_lsp,
+            debuggers: {
+                // Same strategy as `lsp`: user entries replace defaults per language.
+                let mut result = defaults.debuggers.clone();
+                if let Some(partial_debuggers) = self.debuggers {
+                    result.extend(partial_debuggers);
+                }
+                result
+            },
+            tasks: {
+                let mut result = defaults.tasks.clone();
+                if let Some(partial_tasks) = self.tasks {
+                    result.extend(partial_tasks);
+                }
+                result
+            },
+            problem_matchers: {
+                let mut result = defaults.problem_matchers.clone();
+                if let Some(partial_matchers) = self.problem_matchers {
+                    result.extend(partial_matchers);
+                }
+                result
+            },
             warnings: self
                 .warnings
                 .map(|e| e.resolve(&defaults.warnings))
@@ -1359,6 +1466,8 @@ impl Default for LanguageConfig {
             on_save: Vec::new(),
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         }
     }
 }
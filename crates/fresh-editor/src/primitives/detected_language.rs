@@ -78,6 +78,11 @@ impl DetectedLanguage {
         languages: &HashMap<String, LanguageConfig>,
         default_language: Option<&str>,
     ) -> Self {
+        // Compressed files (`dump.sql.zst`, `app.log.1.gz`) are detected by
+        // the name of the text inside.
+        let inner_path = crate::model::compression::uncompressed_path(path);
+        let path = inner_path.as_ref();
+
         // Resolve the config/LSP language id *independently* of the grammar
         // catalog. A file matching a `[languages.foo]` rule must end up with
         // `name = "foo"` so comment prefix / tab config / LSP routing all
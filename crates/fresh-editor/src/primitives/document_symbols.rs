@@ -0,0 +1,723 @@
+//! Document symbols without a language server.
+//!
+//! The outline panel asks the buffer's server for `textDocument/documentSymbol`
+//! and falls back to the symbols produced here when there is none:
+//!
+//! - **Tree-sitter**: declarations in the parse tree — for languages whose
+//!   grammar is bundled (JavaScript, TypeScript, Go, Templ, JSON).
+//! - **Line patterns**: one regex per declaration form, with the extent taken
+//!   from indentation and closing brackets — for the other common languages.
+//!   Markdown gets its headings, nested by level.
+//!
+//! Either way the result has the shape of an LSP `DocumentSymbol[]` answer,
+//! nested by range containment, so consumers treat both sources alike.
+
+use std::ops::Range;
+
+use lsp_types::{DocumentSymbol, Position, SymbolKind};
+use regex::bytes::Regex;
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{Node, Parser};
+
+/// Largest buffer that gets symbols at all.
+const MAX_SOURCE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Names a loose line pattern can capture that are never declarations.
+const KEYWORDS: &[&str] = &[
+    "if", "else", "for", "while", "do", "switch", "case", "return", "sizeof", "catch",
+];
+
+/// A symbol before nesting.
+#[derive(Debug, Clone)]
+struct FlatSymbol {
+    name: String,
+    kind: SymbolKind,
+    /// Bytes of the whole declaration
+    range: Range<usize>,
+    /// Bytes of the name within it
+    selection: Range<usize>,
+}
+
+/// Symbols of `buffer`, outermost first. `language_id` selects the line
+/// patterns when there is no grammar for `language`.
+pub fn document_symbols(
+    buffer: &Buffer,
+    language: Option<&Language>,
+    language_id: &str,
+) -> Vec<DocumentSymbol> {
+    let len = buffer.len();
+    if len > MAX_SOURCE_BYTES {
+        return Vec::new();
+    }
+    let source = buffer.slice_bytes(0..len);
+    let position = |byte: usize| {
+        let (line, character) = buffer.position_to_lsp_position(byte);
+        Position::new(line as u32, character as u32)
+    };
+    nest(flat_symbols(&source, language, language_id), &position)
+}
+
+fn flat_symbols(source: &[u8], language: Option<&Language>, language_id: &str) -> Vec<FlatSymbol> {
+    match language.and_then(|l| syntax_symbols(source, l)) {
+        Some(symbols) if !symbols.is_empty() => symbols,
+        _ if language_id == "markdown" => heading_symbols(source),
+        _ => pattern_symbols(source, language_id),
+    }
+}
+
+/// Nest symbols by range containment.
+fn nest(mut flat: Vec<FlatSymbol>, position: &dyn Fn(usize) -> Position) -> Vec<DocumentSymbol> {
+    flat.sort_by(|a, b| {
+        a.range
+            .start
+            .cmp(&b.range.start)
+            .then(b.range.end.cmp(&a.range.end))
+    });
+
+    let mut roots = Vec::new();
+    // Symbols still open at the current offset, with their end (clamped to
+    // the parent's), outermost first.
+    let mut open: Vec<(DocumentSymbol, usize)> = Vec::new();
+    for symbol in flat {
+        close_until(&mut open, &mut roots, symbol.range.start);
+        let end = open.last().map_or(symbol.range.end, |(_, parent_end)| {
+            symbol.range.end.min(*parent_end)
+        });
+        open.push((to_lsp(symbol, position), end));
+    }
+    close_until(&mut open, &mut roots, usize::MAX);
+    roots
+}
+
+/// Close every open symbol ending at or before `offset`, attaching it to
+/// its parent.
+fn close_until(
+    open: &mut Vec<(DocumentSymbol, usize)>,
+    roots: &mut Vec<DocumentSymbol>,
+    offset: usize,
+) {
+    while open.last().is_some_and(|(_, end)| *end <= offset) {
+        let (symbol, _) = open.pop().expect("open symbol checked");
+        match open.last_mut() {
+            Some((parent, _)) => parent.children.get_or_insert_with(Vec::new).push(symbol),
+            None => roots.push(symbol),
+        }
+    }
+}
+
+#[allow(deprecated)] // `DocumentSymbol::deprecated` must still be set
+fn to_lsp(symbol: FlatSymbol, position: &dyn Fn(usize) -> Position) -> DocumentSymbol {
+    DocumentSymbol {
+        name: symbol.name,
+        detail: None,
+        kind: symbol.kind,
+        tags: None,
+        deprecated: None,
+        range: lsp_types::Range::new(position(symbol.range.start), position(symbol.range.end)),
+        selection_range: lsp_types::Range::new(
+            position(symbol.selection.start),
+            position(symbol.selection.end),
+        ),
+        children: None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tree-sitter
+// ---------------------------------------------------------------------------
+
+/// Declarations in the parse tree, or `None` without a grammar or kind
+/// table for `language`.
+fn syntax_symbols(source: &[u8], language: &Language) -> Option<Vec<FlatSymbol>> {
+    let classify: fn(&Node, &[u8]) -> Option<SymbolKind> = match language {
+        Language::JavaScript | Language::TypeScript => script_kind,
+        Language::Go | Language::Templ => go_kind,
+        Language::Json | Language::Jsonc => json_kind,
+        _ => return None,
+    };
+    let mut parser = Parser::new();
+    parser.set_language(&language.ts_language()?).ok()?;
+    let tree = parser.parse(source, None)?;
+
+    let mut symbols = Vec::new();
+    let mut cursor = tree.walk();
+    loop {
+        let node = cursor.node();
+        let kind = classify(&node, source);
+        let name = kind.and_then(|_| {
+            ["name", "key", "property"]
+                .iter()
+                .find_map(|field| node.child_by_field_name(field))
+        });
+        if let (Some(kind), Some(name)) = (kind, name) {
+            symbols.push(FlatSymbol {
+                name: symbol_name(&source[name.byte_range()]),
+                kind,
+                range: node.byte_range(),
+                selection: name.byte_range(),
+            });
+        }
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return Some(symbols);
+            }
+        }
+    }
+}
+
+/// JavaScript and TypeScript declarations.
+fn script_kind(node: &Node, source: &[u8]) -> Option<SymbolKind> {
+    Some(match node.kind() {
+        "function_declaration" | "generator_function_declaration" | "function_signature" => {
+            SymbolKind::FUNCTION
+        }
+        "class_declaration" | "abstract_class_declaration" => SymbolKind::CLASS,
+        "method_definition" | "method_signature" | "abstract_method_signature" => {
+            let name = node.child_by_field_name("name")?;
+            if &source[name.byte_range()] == b"constructor" {
+                SymbolKind::CONSTRUCTOR
+            } else {
+                SymbolKind::METHOD
+            }
+        }
+        "interface_declaration" => SymbolKind::INTERFACE,
+        "enum_declaration" => SymbolKind::ENUM,
+        "type_alias_declaration" => SymbolKind::TYPE_PARAMETER,
+        "internal_module" | "module" => SymbolKind::MODULE,
+        "public_field_definition" | "field_definition" | "property_signature" => {
+            SymbolKind::PROPERTY
+        }
+        // `const f = () => {}` and friends
+        "variable_declarator" => match node.child_by_field_name("value")?.kind() {
+            "arrow_function" | "function_expression" | "function" | "generator_function" => {
+                SymbolKind::FUNCTION
+            }
+            "class" => SymbolKind::CLASS,
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Go declarations; Templ components parse on top of the Go grammar.
+fn go_kind(node: &Node, _source: &[u8]) -> Option<SymbolKind> {
+    Some(match node.kind() {
+        "function_declaration" | "component_declaration" => SymbolKind::FUNCTION,
+        "method_declaration" | "method_elem" | "method_spec" => SymbolKind::METHOD,
+        "type_spec" | "type_alias" => match node.child_by_field_name("type").map(|t| t.kind()) {
+            Some("struct_type") => SymbolKind::STRUCT,
+            Some("interface_type") => SymbolKind::INTERFACE,
+            _ => SymbolKind::CLASS,
+        },
+        "field_declaration" => SymbolKind::FIELD,
+        // Package-level only: `source_file > const_declaration > const_spec`
+        "const_spec" | "var_spec"
+            if node
+                .parent()
+                .and_then(|p| p.parent())
+                .is_some_and(|g| g.kind() == "source_file") =>
+        {
+            if node.kind() == "const_spec" {
+                SymbolKind::CONSTANT
+            } else {
+                SymbolKind::VARIABLE
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// JSON object members, by the kind of their value.
+fn json_kind(node: &Node, _source: &[u8]) -> Option<SymbolKind> {
+    if node.kind() != "pair" {
+        return None;
+    }
+    Some(match node.child_by_field_name("value")?.kind() {
+        "object" => SymbolKind::OBJECT,
+        "array" => SymbolKind::ARRAY,
+        "string" => SymbolKind::STRING,
+        "number" => SymbolKind::NUMBER,
+        "true" | "false" => SymbolKind::BOOLEAN,
+        "null" => SymbolKind::NULL,
+        _ => SymbolKind::FIELD,
+    })
+}
+
+fn symbol_name(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Line patterns
+// ---------------------------------------------------------------------------
+
+/// Declaration patterns for `language_id`. Group 1 captures the name; the
+/// first matching pattern on a line wins.
+fn line_patterns(language_id: &str) -> Vec<(Regex, SymbolKind)> {
+    const VIS: &str = r"^\s*(?:pub(?:\([^)]*\))?\s+)?";
+    const JAVA_MODS: &str = r"^\s*(?:(?:public|private|protected|internal|static|final|abstract|sealed|partial|readonly|override|virtual|async|synchronized|native|extern|unsafe|new)\s+)";
+
+    let table: Vec<(String, SymbolKind)> = match language_id {
+        "rust" => vec![
+            (
+                format!(r#"{VIS}(?:(?:const|async|unsafe|extern(?:\s+"[^"]*")?)\s+)*fn\s+(\w+)"#),
+                SymbolKind::FUNCTION,
+            ),
+            (format!(r"{VIS}(?:struct|union)\s+(\w+)"), SymbolKind::STRUCT),
+            (format!(r"{VIS}enum\s+(\w+)"), SymbolKind::ENUM),
+            (
+                format!(r"{VIS}(?:unsafe\s+)?trait\s+(\w+)"),
+                SymbolKind::INTERFACE,
+            ),
+            (
+                r"^\s*(?:unsafe\s+)?impl\b(?:\s*<[^>]*>)?\s+([^{]+?)\s*(?:\{|\bwhere\b|$)".into(),
+                SymbolKind::OBJECT,
+            ),
+            (format!(r"{VIS}mod\s+(\w+)"), SymbolKind::MODULE),
+            (
+                format!(r"{VIS}(?:const|static(?:\s+mut)?)\s+(\w+)\s*:"),
+                SymbolKind::CONSTANT,
+            ),
+            (format!(r"{VIS}type\s+(\w+)"), SymbolKind::TYPE_PARAMETER),
+            (r"^\s*macro_rules!\s*(\w+)".into(), SymbolKind::FUNCTION),
+        ],
+        "python" => vec![
+            (r"^\s*(?:async\s+)?def\s+(\w+)".into(), SymbolKind::FUNCTION),
+            (r"^\s*class\s+(\w+)".into(), SymbolKind::CLASS),
+        ],
+        "go" | "templ" => vec![
+            (r"^templ\s+(\w+)".into(), SymbolKind::FUNCTION),
+            (
+                r"^func\s+\([^)]*\)\s*(\w+)".into(),
+                SymbolKind::METHOD,
+            ),
+            (r"^func\s+(\w+)".into(), SymbolKind::FUNCTION),
+            (r"^type\s+(\w+)\s+struct\b".into(), SymbolKind::STRUCT),
+            (r"^type\s+(\w+)\s+interface\b".into(), SymbolKind::INTERFACE),
+            (r"^type\s+(\w+)".into(), SymbolKind::CLASS),
+        ],
+        "javascript" | "typescript" => vec![
+            (
+                r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)"
+                    .into(),
+                SymbolKind::FUNCTION,
+            ),
+            (
+                r"^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+([A-Za-z_$][\w$]*)"
+                    .into(),
+                SymbolKind::CLASS,
+            ),
+            (
+                r"^\s*(?:export\s+)?interface\s+(\w+)".into(),
+                SymbolKind::INTERFACE,
+            ),
+            (
+                r"^\s*(?:export\s+)?(?:const\s+)?enum\s+(\w+)".into(),
+                SymbolKind::ENUM,
+            ),
+            (
+                r"^\s*(?:export\s+)?type\s+(\w+)\s*(?:<[^>]*>)?\s*=".into(),
+                SymbolKind::TYPE_PARAMETER,
+            ),
+            (
+                r"^\s*(?:export\s+)?(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*=>|[A-Za-z_$][\w$]*\s*=>)"
+                    .into(),
+                SymbolKind::FUNCTION,
+            ),
+        ],
+        "c" | "cpp" => vec![
+            (
+                r"^\s*(?:template\s*<.*>\s*)?(?:class|struct|union)\s+(\w+)\s*(?:final\s*)?(?::[^;(]*)?\{?\s*$"
+                    .into(),
+                SymbolKind::STRUCT,
+            ),
+            (r"^\s*(?:typedef\s+)?enum\s+(?:class\s+)?(\w+)[^;]*$".into(), SymbolKind::ENUM),
+            (r"^\s*namespace\s+(\w+)".into(), SymbolKind::NAMESPACE),
+            // Function definitions start in column 0
+            (
+                r"^(?:[A-Za-z_][\w:<>,*&\s]*?[\s*&])?([A-Za-z_~][\w:~]*)\s*\([^;]*$".into(),
+                SymbolKind::FUNCTION,
+            ),
+        ],
+        "java" | "csharp" => vec![
+            (
+                format!(r"{JAVA_MODS}*(?:class|record)\s+(\w+)"),
+                SymbolKind::CLASS,
+            ),
+            (format!(r"{JAVA_MODS}*interface\s+(\w+)"), SymbolKind::INTERFACE),
+            (format!(r"{JAVA_MODS}*enum\s+(\w+)"), SymbolKind::ENUM),
+            (format!(r"{JAVA_MODS}*struct\s+(\w+)"), SymbolKind::STRUCT),
+            (r"^\s*namespace\s+([\w.]+)".into(), SymbolKind::NAMESPACE),
+            (
+                format!(r"{JAVA_MODS}+(?:<[^>]*>\s*)?[\w<>\[\],.?]+\s+(\w+)\s*\("),
+                SymbolKind::METHOD,
+            ),
+            (
+                r"^\s*(?:public|private|protected|internal)\s+([A-Z]\w*)\s*\(".into(),
+                SymbolKind::CONSTRUCTOR,
+            ),
+        ],
+        "ruby" => vec![
+            (r"^\s*def\s+((?:self\.)?[\w.?!=]+)".into(), SymbolKind::METHOD),
+            (r"^\s*class\s+([\w:]+)".into(), SymbolKind::CLASS),
+            (r"^\s*module\s+([\w:]+)".into(), SymbolKind::MODULE),
+        ],
+        "lua" => vec![
+            (
+                r"^\s*(?:local\s+)?function\s+([\w.:]+)".into(),
+                SymbolKind::FUNCTION,
+            ),
+            (
+                r"^\s*(?:local\s+)?([\w.]+)\s*=\s*function\b".into(),
+                SymbolKind::FUNCTION,
+            ),
+        ],
+        "php" => vec![
+            (
+                r"^\s*(?:(?:public|private|protected|static|abstract|final)\s+)*function\s+&?(\w+)"
+                    .into(),
+                SymbolKind::FUNCTION,
+            ),
+            (
+                r"^\s*(?:(?:abstract|final|readonly)\s+)*class\s+(\w+)".into(),
+                SymbolKind::CLASS,
+            ),
+            (
+                r"^\s*(?:interface|trait)\s+(\w+)".into(),
+                SymbolKind::INTERFACE,
+            ),
+            (r"^\s*enum\s+(\w+)".into(), SymbolKind::ENUM),
+            (r"^\s*namespace\s+([\w\\]+)".into(), SymbolKind::NAMESPACE),
+        ],
+        "bash" => vec![
+            (r"^\s*function\s+([\w:.-]+)".into(), SymbolKind::FUNCTION),
+            (r"^\s*([\w:.-]+)\s*\(\)".into(), SymbolKind::FUNCTION),
+        ],
+        "odin" => vec![
+            (r"^\s*(\w+)\s*::\s*proc\b".into(), SymbolKind::FUNCTION),
+            (r"^\s*(\w+)\s*::\s*struct\b".into(), SymbolKind::STRUCT),
+            (r"^\s*(\w+)\s*::\s*enum\b".into(), SymbolKind::ENUM),
+        ],
+        "pascal" => vec![(
+            r"(?i)^\s*(?:procedure|function|constructor|destructor)\s+([\w.]+)".into(),
+            SymbolKind::FUNCTION,
+        )],
+        _ => Vec::new(),
+    };
+
+    table
+        .into_iter()
+        .filter_map(|(pattern, kind)| Some((Regex::new(&pattern).ok()?, kind)))
+        .collect()
+}
+
+/// Declarations found by [`line_patterns`], each extending over its
+/// indented body.
+fn pattern_symbols(source: &[u8], language_id: &str) -> Vec<FlatSymbol> {
+    let patterns = line_patterns(language_id);
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+    let lines = line_ranges(source);
+
+    let mut symbols = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let text = &source[line.clone()];
+        let found = patterns.iter().find_map(|(regex, kind)| {
+            let name = regex.captures(text)?.get(1)?;
+            let keyword = KEYWORDS.iter().any(|k| k.as_bytes() == name.as_bytes());
+            (!keyword).then_some((name, *kind))
+        });
+        let Some((name, kind)) = found else {
+            continue;
+        };
+        symbols.push(FlatSymbol {
+            name: String::from_utf8_lossy(name.as_bytes()).into_owned(),
+            kind,
+            range: line.start..block_end(source, &lines, i),
+            selection: line.start + name.start()..line.start + name.end(),
+        });
+    }
+    symbols
+}
+
+/// End of the block headed by line `header`: the last line indented deeper
+/// than it, plus a closing `}`/`end` line at its own level.
+fn block_end(source: &[u8], lines: &[Range<usize>], header: usize) -> usize {
+    let text = &source[lines[header].clone()];
+    let mut end = lines[header].end;
+    // A declaration without a body
+    if text.trim_ascii_end().ends_with(b";") {
+        return end;
+    }
+    let indent = indent_of(text);
+    for line in &lines[header + 1..] {
+        let text = &source[line.clone()];
+        let trimmed = text.trim_ascii();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if indent_of(text) <= indent {
+            // A brace on its own line, or the `) -> T {` / `):` closing a
+            // multi-line signature, still belongs to the header.
+            let opens = trimmed.ends_with(b"{") || trimmed.ends_with(b":");
+            if trimmed.starts_with(b"{") || (is_closer(trimmed) && opens) {
+                end = line.end;
+                continue;
+            }
+            if is_closer(trimmed) {
+                end = line.end;
+            }
+            break;
+        }
+        end = line.end;
+    }
+    end
+}
+
+fn is_closer(trimmed: &[u8]) -> bool {
+    matches!(trimmed.first(), Some(b'}' | b')' | b']'))
+        || (trimmed.starts_with(b"end")
+            && !trimmed
+                .get(3)
+                .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_'))
+}
+
+fn indent_of(line: &[u8]) -> usize {
+    line.iter()
+        .take_while(|c| **c == b' ' || **c == b'\t')
+        .map(|c| if *c == b'\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Byte ranges of every line, without the line break.
+fn line_ranges(source: &[u8]) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for (i, byte) in source.iter().enumerate() {
+        if *byte == b'\n' {
+            let end = if i > start && source[i - 1] == b'\r' {
+                i - 1
+            } else {
+                i
+            };
+            lines.push(start..end);
+            start = i + 1;
+        }
+    }
+    if start < source.len() {
+        lines.push(start..source.len());
+    }
+    lines
+}
+
+// ---------------------------------------------------------------------------
+// Markdown
+// ---------------------------------------------------------------------------
+
+/// ATX headings, each extending to the next heading of the same or a higher
+/// level. Lines inside fenced code blocks are skipped.
+fn heading_symbols(source: &[u8]) -> Vec<FlatSymbol> {
+    let heading = Regex::new(r"^(#{1,6})[ \t]+(.+?)[ \t#]*$").expect("valid heading regex");
+    let mut headings: Vec<(usize, Range<usize>, Range<usize>)> = Vec::new();
+    let mut in_fence = false;
+    for line in line_ranges(source) {
+        let text = &source[line.clone()];
+        let trimmed = text.trim_ascii_start();
+        if trimmed.starts_with(b"```") || trimmed.starts_with(b"~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some(caps) = heading.captures(text) {
+            let (Some(level), Some(name)) = (caps.get(1), caps.get(2)) else {
+                continue;
+            };
+            headings.push((
+                level.len(),
+                line.clone(),
+                line.start + name.start()..line.start + name.end(),
+            ));
+        }
+    }
+
+    headings
+        .iter()
+        .enumerate()
+        .map(|(i, (level, line, name))| {
+            let next = headings[i + 1..]
+                .iter()
+                .find(|(other, _, _)| other <= level)
+                .map_or(source.len(), |(_, next_line, _)| next_line.start);
+            let end = line.end.max(source[..next].trim_ascii_end().len());
+            FlatSymbol {
+                name: String::from_utf8_lossy(&source[name.clone()]).into_owned(),
+                kind: SymbolKind::STRING,
+                range: line.start..end,
+                selection: name.clone(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `name` of every symbol, indented two spaces per nesting level.
+    fn outline(source: &str, language: Option<Language>, language_id: &str) -> Vec<String> {
+        fn walk(symbols: &[DocumentSymbol], depth: usize, out: &mut Vec<String>) {
+            for symbol in symbols {
+                out.push(format!("{}{}", "  ".repeat(depth), symbol.name));
+                walk(
+                    symbol.children.as_deref().unwrap_or_default(),
+                    depth + 1,
+                    out,
+                );
+            }
+        }
+        let buffer = Buffer::from_str_test(source);
+        let mut out = Vec::new();
+        walk(
+            &document_symbols(&buffer, language.as_ref(), language_id),
+            0,
+            &mut out,
+        );
+        out
+    }
+
+    #[test]
+    fn rust_items_nest_inside_impl_blocks() {
+        let source = "\
+pub struct Point {
+    x: i32,
+}
+
+impl Point {
+    pub fn new() -> Self {
+        Point { x: 0 }
+    }
+
+    fn len(
+        &self,
+    ) -> usize {
+        0
+    }
+}
+
+fn main() {}
+";
+        assert_eq!(
+            outline(source, None, "rust"),
+            ["Point", "Point", "  new", "  len", "main"]
+        );
+    }
+
+    #[test]
+    fn python_blocks_end_at_dedent() {
+        let source = "\
+class Shape:
+    def area(self):
+        return 0
+
+    async def draw(self):
+        pass
+
+def helper():
+    if True:
+        return 1
+";
+        assert_eq!(
+            outline(source, None, "python"),
+            ["Shape", "  area", "  draw", "helper"]
+        );
+    }
+
+    #[test]
+    fn ranges_cover_the_body_and_selection_the_name() {
+        let source = "fn a() {\n    1\n}\n\nfn b() {}\n";
+        let buffer = Buffer::from_str_test(source);
+        let symbols = document_symbols(&buffer, None, "rust");
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].range.start, Position::new(0, 0));
+        assert_eq!(symbols[0].range.end, Position::new(2, 1));
+        assert_eq!(symbols[0].selection_range.start, Position::new(0, 3));
+        assert_eq!(symbols[0].selection_range.end, Position::new(0, 4));
+        assert_eq!(symbols[1].range.start, Position::new(4, 0));
+    }
+
+    #[test]
+    fn markdown_headings_nest_by_level_outside_code_fences() {
+        let source = "\
+# Title
+
+## Install
+
+```sh
+# not a heading
+```
+
+### From source ##
+
+## Usage
+
+# Appendix
+";
+        assert_eq!(
+            outline(source, None, "markdown"),
+            [
+                "Title",
+                "  Install",
+                "    From source",
+                "  Usage",
+                "Appendix"
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_language_has_no_symbols() {
+        assert!(outline("fn main() {}\n", None, "plaintext").is_empty());
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn tree_sitter_declarations() {
+        let source = "\
+export class Greeter {
+  constructor() {}
+  greet(name: string) {}
+}
+
+const shout = (s: string) => s.toUpperCase();
+
+interface Options { loud: boolean }
+";
+        assert_eq!(
+            outline(source, Some(Language::TypeScript), "typescript"),
+            [
+                "Greeter",
+                "  constructor",
+                "  greet",
+                "shout",
+                "Options",
+                "  loud"
+            ]
+        );
+
+        let json = r#"{"name": "fresh", "scripts": {"build": "tsc"}}"#;
+        assert_eq!(
+            outline(json, Some(Language::Json), "json"),
+            ["name", "scripts", "  build"]
+        );
+    }
+}
@@ -913,6 +913,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
         registry.apply_language_config(&languages);
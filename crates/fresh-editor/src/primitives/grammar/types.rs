@@ -1766,6 +1766,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
         registry.apply_language_config(&languages);
@@ -1811,6 +1813,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
         registry.apply_language_config(&languages);
@@ -1859,6 +1863,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -1886,6 +1892,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -2166,6 +2174,8 @@ mod tests {
             on_save: vec![],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         }
     }
 
@@ -68,6 +68,8 @@ pub mod textmate_engine;
 #[cfg(feature = "runtime")]
 pub mod detected_language;
 #[cfg(feature = "runtime")]
+pub mod document_symbols;
+#[cfg(feature = "runtime")]
 pub mod highlight_engine;
 #[cfg(feature = "runtime")]
 pub mod highlighter;
@@ -75,3 +77,5 @@ pub mod highlighter;
 pub mod indent;
 #[cfg(feature = "runtime")]
 pub mod reference_highlighter;
+#[cfg(feature = "runtime")]
+pub mod syntax_selection;
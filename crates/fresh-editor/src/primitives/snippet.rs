@@ -1,13 +1,50 @@
 //! LSP snippet parser and expander.
 //!
-//! Parses LSP snippet syntax and expands it to plain text with cursor positioning.
+//! Parses the snippet syntax shared by LSP completions and VS Code snippet
+//! files, expands it to plain text, and records where each tabstop landed so
+//! an editing session can step through them.
 //! Supports:
 //! - `$0` - final cursor position
-//! - `$n` - tabstops (expanded as empty, cursor goes to $0 or end)
-//! - `${n:text}` - tabstops with default text (uses the default)
-//! - `${n|choice1,choice2|}` - choices (uses first choice)
+//! - `$n` / `${n}` - tabstops; a repeated number mirrors the first one's text
+//! - `${n:text}` - tabstops with default text, which may nest other tabstops
+//! - `${n|choice1,choice2|}` - choices (inserts the first, offers all)
+//! - `$NAME` / `${NAME:default}` - variables listed in [`VARIABLES`]
+//! - `${n/regex/format/flags}` / `${NAME/regex/format/flags}` - transforms
 //! - `\\$` - escaped dollar sign
 
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+/// Variables a snippet can reference. Any other `$NAME` expands to its
+/// default if it has one, otherwise to the bare name.
+pub const VARIABLES: &[&str] = &[
+    "TM_SELECTED_TEXT",
+    "TM_CURRENT_LINE",
+    "TM_CURRENT_WORD",
+    "TM_LINE_INDEX",
+    "TM_LINE_NUMBER",
+    "TM_FILENAME",
+    "TM_FILENAME_BASE",
+    "TM_DIRECTORY",
+    "TM_FILEPATH",
+    "RELATIVE_FILEPATH",
+    "WORKSPACE_NAME",
+    "WORKSPACE_FOLDER",
+    "CLIPBOARD",
+    "CURRENT_YEAR",
+    "CURRENT_YEAR_SHORT",
+    "CURRENT_MONTH",
+    "CURRENT_MONTH_NAME",
+    "CURRENT_MONTH_NAME_SHORT",
+    "CURRENT_DATE",
+    "CURRENT_DAY_NAME",
+    "CURRENT_DAY_NAME_SHORT",
+    "CURRENT_HOUR",
+    "CURRENT_MINUTE",
+    "CURRENT_SECOND",
+    "CURRENT_SECONDS_UNIX",
+];
+
 /// Result of expanding a snippet
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExpandedSnippet {
@@ -15,10 +52,168 @@ pub struct ExpandedSnippet {
     pub text: String,
     /// Cursor offset from start of inserted text (where $0 was, or end if no $0)
     pub cursor_offset: usize,
+    /// Tabstops in visiting order: ascending by number, with `$0` last. A
+    /// snippet without `$0` gets one at the end of its text.
+    pub tabstops: Vec<Tabstop>,
+}
+
+/// Where one tabstop number landed in [`ExpandedSnippet::text`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tabstop {
+    /// The tabstop number (`1` for `$1`)
+    pub index: u32,
+    /// Byte ranges edited together. The first is the placeholder that
+    /// supplied the text; the rest are its mirrors.
+    pub ranges: Vec<Range<usize>>,
+    /// Mirrors with a transform, rewritten from the first range's text when
+    /// the tabstop is left.
+    pub transformed: Vec<(Range<usize>, Transform)>,
+    /// Options of a `${n|a,b|}` choice; empty for other tabstops.
+    pub choices: Vec<String>,
+}
+
+/// A `/regex/format/flags` transform.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transform {
+    pattern: String,
+    format: Vec<FormatItem>,
+    flags: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FormatItem {
+    Text(String),
+    /// `$n` / `${n}`
+    Group(usize),
+    /// `${n:/upcase}` and friends
+    Case(usize, CaseChange),
+    /// `${n:+if}`, `${n:-else}`, `${n:else}` and `${n:?if:else}`.
+    /// `if_set: None` inserts the group itself.
+    Conditional {
+        group: usize,
+        if_set: Option<String>,
+        if_unset: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CaseChange {
+    Upcase,
+    Downcase,
+    Capitalize,
+    Camelcase,
+    Pascalcase,
+}
+
+impl Transform {
+    /// Rewrite `input`: each match of the regex (only the first, unless the
+    /// `g` flag is set) is replaced by the format. An invalid regex leaves
+    /// the input unchanged.
+    pub fn apply(&self, input: &str) -> String {
+        let inline_flags: String = self
+            .flags
+            .chars()
+            .filter(|c| matches!(c, 'i' | 'm' | 's'))
+            .collect();
+        let pattern = if inline_flags.is_empty() {
+            self.pattern.clone()
+        } else {
+            format!("(?{}){}", inline_flags, self.pattern)
+        };
+        let Ok(regex) = regex::Regex::new(&pattern) else {
+            return input.to_string();
+        };
+        let global = self.flags.contains('g');
+
+        let mut out = String::new();
+        let mut last = 0;
+        for caps in regex.captures_iter(input) {
+            let whole = caps.get(0).expect("group 0 always participates");
+            out.push_str(&input[last..whole.start()]);
+            for item in &self.format {
+                item.append(&caps, &mut out);
+            }
+            last = whole.end();
+            if !global {
+                break;
+            }
+        }
+        out.push_str(&input[last..]);
+        out
+    }
+}
+
+impl FormatItem {
+    fn append(&self, caps: &regex::Captures, out: &mut String) {
+        let group = |n: usize| caps.get(n).map_or("", |m| m.as_str());
+        match self {
+            FormatItem::Text(text) => out.push_str(text),
+            FormatItem::Group(n) => out.push_str(group(*n)),
+            FormatItem::Case(n, change) => out.push_str(&change.apply(group(*n))),
+            FormatItem::Conditional {
+                group: n,
+                if_set,
+                if_unset,
+            } => {
+                let value = group(*n);
+                if value.is_empty() {
+                    out.push_str(if_unset);
+                } else {
+                    out.push_str(if_set.as_deref().unwrap_or(value));
+                }
+            }
+        }
+    }
+}
+
+impl CaseChange {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "upcase" => Self::Upcase,
+            "downcase" => Self::Downcase,
+            "capitalize" => Self::Capitalize,
+            "camelcase" => Self::Camelcase,
+            "pascalcase" => Self::Pascalcase,
+            _ => return None,
+        })
+    }
+
+    fn apply(self, value: &str) -> String {
+        fn capitalize(word: &str) -> String {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        let words = || {
+            value
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+        };
+        match self {
+            Self::Upcase => value.to_uppercase(),
+            Self::Downcase => value.to_lowercase(),
+            Self::Capitalize => capitalize(value),
+            Self::Pascalcase => words().map(capitalize).collect(),
+            Self::Camelcase => words()
+                .enumerate()
+                .map(|(i, w)| {
+                    if i == 0 {
+                        w.to_lowercase()
+                    } else {
+                        capitalize(w)
+                    }
+                })
+                .collect(),
+        }
+    }
 }
 
 /// Expand an LSP snippet to plain text
 ///
+/// Variables are left unresolved (see [`expand_snippet_with`]).
+///
 /// # Examples
 /// ```
 /// use fresh::primitives::snippet::expand_snippet;
@@ -34,141 +229,529 @@ pub struct ExpandedSnippet {
 /// assert_eq!(result.cursor_offset, 8); // cursor at end (no $0)
 /// ```
 pub fn expand_snippet(snippet: &str) -> ExpandedSnippet {
-    let mut result = String::new();
-    let mut cursor_offset: Option<usize> = None;
-    let mut chars = snippet.chars().peekable();
+    expand_snippet_with(snippet, &|_| None, "")
+}
 
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            // Escape sequence
-            if let Some(&next) = chars.peek() {
-                if next == '$' || next == '\\' || next == '}' {
-                    result.push(chars.next().unwrap());
-                    continue;
-                }
+/// Expand a snippet, resolving [`VARIABLES`] through `variables` and
+/// following every newline in the snippet with `indent`.
+///
+/// `variables` returning `None` (or an empty string) makes the variable
+/// fall back to its default.
+///
+/// # Examples
+/// ```
+/// use fresh::primitives::snippet::expand_snippet_with;
+///
+/// let vars = |name: &str| (name == "TM_FILENAME_BASE").then(|| "main".to_string());
+/// let result = expand_snippet_with("mod ${TM_FILENAME_BASE} {\n\t$0\n}", &vars, "    ");
+/// assert_eq!(result.text, "mod main {\n    \t\n    }");
+/// assert_eq!(result.cursor_offset, 16);
+/// ```
+pub fn expand_snippet_with(
+    snippet: &str,
+    variables: &dyn Fn(&str) -> Option<String>,
+    indent: &str,
+) -> ExpandedSnippet {
+    let nodes = Parser::new(snippet).parse_nodes(false);
+    // Mirrors repeat their placeholder's text, which may come later in the
+    // snippet: a first pass learns each tabstop's text.
+    let definitions = Renderer::run(&nodes, variables, indent, &HashMap::new()).definitions();
+    Renderer::run(&nodes, variables, indent, &definitions).finish()
+}
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Tabstop {
+        index: u32,
+        body: TabstopBody,
+    },
+    Variable {
+        name: String,
+        default: Option<Vec<Node>>,
+        transform: Option<Transform>,
+    },
+}
+
+#[derive(Debug)]
+enum TabstopBody {
+    /// `$n` / `${n}`
+    Bare,
+    /// `${n:...}`
+    Placeholder(Vec<Node>),
+    /// `${n|...|}`
+    Choice(Vec<String>),
+    /// `${n/.../.../...}`
+    Transform(Transform),
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> Option<()> {
+        (self.peek()? == expected).then(|| self.pos += 1)
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.src[start..self.pos].parse().ok()
+    }
+
+    fn name(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c == '_' || c.is_ascii_alphanumeric())
+        {
+            self.pos += 1;
+        }
+        self.src[start..self.pos].to_string()
+    }
+
+    /// Parse text and placeholders up to the end of input, or up to the
+    /// closing `}` of the enclosing placeholder (left unconsumed).
+    fn parse_nodes(&mut self, in_placeholder: bool) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        let mut text = String::new();
+
+        while let Some(c) = self.peek() {
+            if c == '}' && in_placeholder {
+                break;
             }
-            result.push(c);
-        } else if c == '$' {
-            // Snippet placeholder
-            if let Some(&next) = chars.peek() {
-                if next == '{' {
-                    // ${...} syntax
-                    chars.next(); // consume '{'
-                    let (expanded, is_final) = parse_brace_placeholder(&mut chars);
-                    if is_final {
-                        cursor_offset = Some(result.len());
+            self.bump();
+            match c {
+                '\\' => match self.peek() {
+                    Some(next @ ('$' | '\\' | '}')) => {
+                        self.bump();
+                        text.push(next);
                     }
-                    result.push_str(&expanded);
-                } else if next.is_ascii_digit() {
-                    // $n syntax
-                    let mut num = String::new();
-                    while let Some(&d) = chars.peek() {
-                        if d.is_ascii_digit() {
-                            num.push(chars.next().unwrap());
-                        } else {
-                            break;
+                    _ => text.push(c),
+                },
+                '$' => {
+                    let start = self.pos;
+                    match self.parse_dollar() {
+                        Some(node) => {
+                            if !text.is_empty() {
+                                nodes.push(Node::Text(std::mem::take(&mut text)));
+                            }
+                            nodes.push(node);
+                        }
+                        // Not a valid placeholder, keep the $
+                        None => {
+                            self.pos = start;
+                            text.push(c);
                         }
                     }
-                    if num == "0" {
-                        cursor_offset = Some(result.len());
-                    }
-                    // Other tabstops expand to nothing
-                } else {
-                    // Not a valid placeholder, keep the $
-                    result.push(c);
                 }
-            } else {
-                result.push(c);
+                _ => text.push(c),
             }
+        }
+
+        if !text.is_empty() {
+            nodes.push(Node::Text(text));
+        }
+        nodes
+    }
+
+    /// Parse what follows a `$`.
+    fn parse_dollar(&mut self) -> Option<Node> {
+        let c = self.peek()?;
+        if c.is_ascii_digit() {
+            return Some(Node::Tabstop {
+                index: self.number()?,
+                body: TabstopBody::Bare,
+            });
+        }
+        if c == '_' || c.is_ascii_alphabetic() {
+            return Some(Node::Variable {
+                name: self.name(),
+                default: None,
+                transform: None,
+            });
+        }
+        self.eat('{')?;
+
+        let c = self.peek()?;
+        if c.is_ascii_digit() {
+            let index = self.number()?;
+            let body = match self.bump()? {
+                '}' => TabstopBody::Bare,
+                ':' => {
+                    let nodes = self.parse_nodes(true);
+                    self.eat('}')?;
+                    TabstopBody::Placeholder(nodes)
+                }
+                '|' => TabstopBody::Choice(self.parse_choices()?),
+                '/' => TabstopBody::Transform(self.parse_transform()?),
+                _ => return None,
+            };
+            Some(Node::Tabstop { index, body })
+        } else if c == '_' || c.is_ascii_alphabetic() {
+            let name = self.name();
+            let (default, transform) = match self.bump()? {
+                '}' => (None, None),
+                ':' => {
+                    let nodes = self.parse_nodes(true);
+                    self.eat('}')?;
+                    (Some(nodes), None)
+                }
+                '/' => (None, Some(self.parse_transform()?)),
+                _ => return None,
+            };
+            Some(Node::Variable {
+                name,
+                default,
+                transform,
+            })
         } else {
-            result.push(c);
+            None
         }
     }
 
-    ExpandedSnippet {
-        cursor_offset: cursor_offset.unwrap_or(result.len()),
-        text: result,
+    /// Parse `a,b|}` after the opening `|` of a choice.
+    fn parse_choices(&mut self) -> Option<Vec<String>> {
+        let mut choices = Vec::new();
+        let mut current = String::new();
+        loop {
+            match self.bump()? {
+                '\\' => current.push(self.bump()?),
+                ',' => choices.push(std::mem::take(&mut current)),
+                '|' => {
+                    self.eat('}')?;
+                    choices.push(current);
+                    return Some(choices);
+                }
+                c => current.push(c),
+            }
+        }
+    }
+
+    /// Parse `regex/format/flags}` after the first `/` of a transform.
+    fn parse_transform(&mut self) -> Option<Transform> {
+        let mut pattern = String::new();
+        loop {
+            match self.bump()? {
+                '/' => break,
+                '\\' => {
+                    let c = self.bump()?;
+                    if c != '/' {
+                        pattern.push('\\');
+                    }
+                    pattern.push(c);
+                }
+                c => pattern.push(c),
+            }
+        }
+        let format = self.parse_format()?;
+        let flags = self.format_text(&['}'])?;
+        self.eat('}')?;
+        Some(Transform {
+            pattern,
+            format,
+            flags,
+        })
     }
-}
 
-/// Parse a ${...} placeholder
-/// Returns (expanded_text, is_final_cursor)
-fn parse_brace_placeholder(chars: &mut std::iter::Peekable<std::str::Chars>) -> (String, bool) {
-    let mut content = String::new();
-    let mut depth = 1;
+    /// Parse a transform's format string up to and including its closing `/`.
+    fn parse_format(&mut self) -> Option<Vec<FormatItem>> {
+        let mut items = Vec::new();
+        let mut text = String::new();
+        loop {
+            match self.bump()? {
+                '/' => break,
+                '\\' => text.push(match self.bump()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    c => c,
+                }),
+                '$' => {
+                    let start = self.pos;
+                    match self.parse_format_group() {
+                        Some(item) => {
+                            if !text.is_empty() {
+                                items.push(FormatItem::Text(std::mem::take(&mut text)));
+                            }
+                            items.push(item);
+                        }
+                        None => {
+                            self.pos = start;
+                            text.push('$');
+                        }
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            items.push(FormatItem::Text(text));
+        }
+        Some(items)
+    }
 
-    // Collect everything until matching '}'
-    while let Some(c) = chars.next() {
-        if c == '{' {
-            depth += 1;
-            content.push(c);
-        } else if c == '}' {
-            depth -= 1;
-            if depth == 0 {
-                break;
+    /// Parse what follows a `$` in a format string.
+    fn parse_format_group(&mut self) -> Option<FormatItem> {
+        if self.peek()?.is_ascii_digit() {
+            return Some(FormatItem::Group(self.number()? as usize));
+        }
+        self.eat('{')?;
+        let group = self.number()? as usize;
+        match self.bump()? {
+            '}' => return Some(FormatItem::Group(group)),
+            ':' => {}
+            _ => return None,
+        }
+
+        let item = match self.peek()? {
+            '/' => {
+                self.bump();
+                let change = CaseChange::from_name(&self.name())?;
+                FormatItem::Case(group, change)
             }
-            content.push(c);
-        } else if c == '\\' {
-            // Handle escapes inside braces
-            if let Some(&next) = chars.peek() {
-                if next == '$' || next == '\\' || next == '}' || next == '|' {
-                    content.push(chars.next().unwrap());
-                    continue;
+            '+' => {
+                self.bump();
+                FormatItem::Conditional {
+                    group,
+                    if_set: Some(self.format_text(&['}'])?),
+                    if_unset: String::new(),
                 }
             }
-            content.push(c);
-        } else {
-            content.push(c);
+            '?' => {
+                self.bump();
+                let if_set = self.format_text(&[':'])?;
+                self.eat(':')?;
+                FormatItem::Conditional {
+                    group,
+                    if_set: Some(if_set),
+                    if_unset: self.format_text(&['}'])?,
+                }
+            }
+            c => {
+                if c == '-' {
+                    self.bump();
+                }
+                FormatItem::Conditional {
+                    group,
+                    if_set: None,
+                    if_unset: self.format_text(&['}'])?,
+                }
+            }
+        };
+        self.eat('}')?;
+        Some(item)
+    }
+
+    /// Text up to (not including) the first unescaped character in `stops`.
+    fn format_text(&mut self, stops: &[char]) -> Option<String> {
+        let mut text = String::new();
+        loop {
+            let c = self.peek()?;
+            if stops.contains(&c) {
+                return Some(text);
+            }
+            self.bump();
+            if c == '\\' {
+                text.push(self.bump()?);
+            } else {
+                text.push(c);
+            }
         }
     }
+}
 
-    // Parse the content: n or n:default or n|choices|
-    let (tabstop, default) = parse_placeholder_content(&content);
+/// One place a tabstop number was expanded to.
+struct Occurrence {
+    index: u32,
+    range: Range<usize>,
+    kind: OccurrenceKind,
+}
 
-    let is_final = tabstop == Some(0);
-    (default, is_final)
+enum OccurrenceKind {
+    Placeholder,
+    Choice(Vec<String>),
+    Mirror,
+    Transformed(Transform),
 }
 
-/// Parse placeholder content like "1", "1:default", or "1|a,b,c|"
-/// Returns (tabstop_number, default_text)
-fn parse_placeholder_content(content: &str) -> (Option<u32>, String) {
-    // Find the tabstop number
-    let mut chars = content.chars().peekable();
-    let mut num_str = String::new();
+struct Renderer<'a> {
+    variables: &'a dyn Fn(&str) -> Option<String>,
+    indent: &'a str,
+    /// Text of each tabstop's placeholder, repeated by its mirrors.
+    definitions: &'a HashMap<u32, String>,
+    text: String,
+    occurrences: Vec<Occurrence>,
+}
 
-    while let Some(&c) = chars.peek() {
-        if c.is_ascii_digit() {
-            num_str.push(chars.next().unwrap());
-        } else {
-            break;
+impl<'a> Renderer<'a> {
+    fn run(
+        nodes: &[Node],
+        variables: &'a dyn Fn(&str) -> Option<String>,
+        indent: &'a str,
+        definitions: &'a HashMap<u32, String>,
+    ) -> Self {
+        let mut renderer = Self {
+            variables,
+            indent,
+            definitions,
+            text: String::new(),
+            occurrences: Vec::new(),
+        };
+        renderer.render(nodes);
+        renderer
+    }
+
+    fn push_text(&mut self, text: &str) {
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.text.push('\n');
+                self.text.push_str(self.indent);
+            }
+            self.text.push_str(line);
         }
     }
 
-    let tabstop = num_str.parse::<u32>().ok();
+    fn render(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            match node {
+                Node::Text(text) => self.push_text(text),
+                Node::Tabstop { index, body } => {
+                    let start = self.text.len();
+                    let definitions = self.definitions;
+                    let definition = definitions.get(index).map(String::as_str);
+                    let kind = match body {
+                        TabstopBody::Bare => {
+                            self.text.push_str(definition.unwrap_or_default());
+                            OccurrenceKind::Mirror
+                        }
+                        TabstopBody::Placeholder(children) => {
+                            self.render(children);
+                            OccurrenceKind::Placeholder
+                        }
+                        TabstopBody::Choice(choices) => {
+                            self.push_text(choices.first().map_or("", String::as_str));
+                            OccurrenceKind::Choice(choices.clone())
+                        }
+                        TabstopBody::Transform(transform) => {
+                            let text = transform.apply(definition.unwrap_or_default());
+                            self.text.push_str(&text);
+                            OccurrenceKind::Transformed(transform.clone())
+                        }
+                    };
+                    self.occurrences.push(Occurrence {
+                        index: *index,
+                        range: start..self.text.len(),
+                        kind,
+                    });
+                }
+                Node::Variable {
+                    name,
+                    default,
+                    transform,
+                } => {
+                    let value = VARIABLES
+                        .contains(&name.as_str())
+                        .then(|| (self.variables)(name.as_str()).unwrap_or_default());
+                    match (value, default) {
+                        (Some(value), _) if !value.is_empty() => {
+                            let value = match transform {
+                                Some(transform) => transform.apply(&value),
+                                None => value,
+                            };
+                            self.push_text(&value);
+                        }
+                        (_, Some(default)) => self.render(default),
+                        (Some(_), None) => {}
+                        (None, None) => self.push_text(name),
+                    }
+                }
+            }
+        }
+    }
 
-    // Check what follows
-    match chars.peek() {
-        Some(':') => {
-            // Default text: ${n:default}
-            chars.next(); // consume ':'
-            let default: String = chars.collect();
-            // Recursively expand nested snippets in default
-            let expanded = expand_snippet(&default);
-            (tabstop, expanded.text)
+    /// Text of the first placeholder (or choice) of each tabstop.
+    fn definitions(&self) -> HashMap<u32, String> {
+        let mut definitions = HashMap::new();
+        for occurrence in &self.occurrences {
+            if matches!(
+                occurrence.kind,
+                OccurrenceKind::Placeholder | OccurrenceKind::Choice(_)
+            ) {
+                definitions
+                    .entry(occurrence.index)
+                    .or_insert_with(|| self.text[occurrence.range.clone()].to_string());
+            }
         }
-        Some('|') => {
-            // Choices: ${n|choice1,choice2|}
-            chars.next(); // consume '|'
-            let choices: String = chars.collect();
-            // Remove trailing '|' if present
-            let choices = choices.trim_end_matches('|');
-            // Use first choice
-            let first_choice = choices.split(',').next().unwrap_or("");
-            (tabstop, first_choice.to_string())
+        definitions
+    }
+
+    fn finish(self) -> ExpandedSnippet {
+        let mut tabstops: BTreeMap<u32, Tabstop> = BTreeMap::new();
+        let mut defined = std::collections::HashSet::new();
+        for Occurrence { index, range, kind } in self.occurrences {
+            let tabstop = tabstops.entry(index).or_insert_with(|| Tabstop {
+                index,
+                ranges: Vec::new(),
+                transformed: Vec::new(),
+                choices: Vec::new(),
+            });
+            match kind {
+                OccurrenceKind::Transformed(transform) => {
+                    tabstop.transformed.push((range, transform));
+                }
+                OccurrenceKind::Mirror => tabstop.ranges.push(range),
+                OccurrenceKind::Placeholder | OccurrenceKind::Choice(_) => {
+                    if !defined.insert(index) {
+                        tabstop.ranges.push(range);
+                        continue;
+                    }
+                    tabstop.ranges.insert(0, range);
+                    if let OccurrenceKind::Choice(choices) = kind {
+                        tabstop.choices = choices;
+                    }
+                }
+            }
         }
-        _ => {
-            // Just a tabstop number, no default
-            (tabstop, String::new())
+
+        let end = self.text.len()..self.text.len();
+        let last = tabstops
+            .remove(&0)
+            .filter(|t| !t.ranges.is_empty())
+            .unwrap_or_else(|| Tabstop {
+                index: 0,
+                ranges: vec![end],
+                transformed: Vec::new(),
+                choices: Vec::new(),
+            });
+        let mut tabstops: Vec<Tabstop> = tabstops
+            .into_values()
+            .filter(|t| !t.ranges.is_empty())
+            .collect();
+        let cursor_offset = last.ranges[0].start;
+        tabstops.push(last);
+
+        ExpandedSnippet {
+            text: self.text,
+            cursor_offset,
+            tabstops,
         }
     }
 }
@@ -182,11 +765,19 @@ pub fn is_snippet(text: &str) -> bool {
             // Skip escaped character
             chars.next();
         } else if c == '$' {
-            // Check if it's a valid placeholder
-            if let Some(&next) = chars.peek() {
-                if next == '{' || next.is_ascii_digit() {
-                    return true;
+            // Check if it's a valid placeholder or a known variable
+            match chars.peek() {
+                Some(&next) if next == '{' || next.is_ascii_digit() => return true,
+                Some(&next) if next == '_' || next.is_ascii_alphabetic() => {
+                    let name: String = chars
+                        .clone()
+                        .take_while(|c| *c == '_' || c.is_ascii_alphanumeric())
+                        .collect();
+                    if VARIABLES.contains(&name.as_str()) {
+                        return true;
+                    }
                 }
+                _ => {}
             }
         }
     }
@@ -277,4 +868,123 @@ mod tests {
         assert_eq!(result.text, "foo()");
         assert_eq!(result.cursor_offset, 5);
     }
+
+    fn ranges(result: &ExpandedSnippet) -> Vec<(u32, Vec<&str>)> {
+        result
+            .tabstops
+            .iter()
+            .map(|t| {
+                (
+                    t.index,
+                    t.ranges.iter().map(|r| &result.text[r.clone()]).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tabstops_in_visiting_order() {
+        let result = expand_snippet("$0 ${2:b} ${1:a}");
+        assert_eq!(result.text, " b a");
+        assert_eq!(
+            ranges(&result),
+            vec![(1, vec!["a"]), (2, vec!["b"]), (0, vec![""])]
+        );
+        assert_eq!(result.cursor_offset, 0);
+    }
+
+    #[test]
+    fn test_implicit_final_tabstop() {
+        let result = expand_snippet("${1:x}");
+        let last = result.tabstops.last().unwrap();
+        assert_eq!(last.index, 0);
+        assert_eq!(last.ranges, vec![1..1]);
+    }
+
+    #[test]
+    fn test_mirrors_repeat_placeholder_text() {
+        let result = expand_snippet("$1 = ${1:name}; use($1)");
+        assert_eq!(result.text, "name = name; use(name)");
+        let first = &result.tabstops[0];
+        // The placeholder comes first even though a mirror precedes it.
+        assert_eq!(first.ranges, vec![7..11, 0..4, 17..21]);
+    }
+
+    #[test]
+    fn test_nested_placeholder_ranges() {
+        let result = expand_snippet("${1:foo${2:bar}}");
+        assert_eq!(
+            ranges(&result),
+            vec![(1, vec!["foobar"]), (2, vec!["bar"]), (0, vec![""])]
+        );
+    }
+
+    #[test]
+    fn test_choices_are_recorded() {
+        let result = expand_snippet("${1|a\\,b,c|} $1");
+        assert_eq!(result.text, "a,b a,b");
+        assert_eq!(result.tabstops[0].choices, vec!["a,b", "c"]);
+        assert_eq!(result.tabstops[0].ranges, vec![0..3, 4..7]);
+    }
+
+    #[test]
+    fn test_variables() {
+        let vars = |name: &str| match name {
+            "TM_FILENAME" => Some("main.rs".to_string()),
+            _ => None,
+        };
+        let result = expand_snippet_with(
+            "$TM_FILENAME ${TM_SELECTED_TEXT:none} ${UNKNOWN:dflt} $UNKNOWN",
+            &vars,
+            "",
+        );
+        assert_eq!(result.text, "main.rs none dflt UNKNOWN");
+    }
+
+    #[test]
+    fn test_variable_transform() {
+        let vars = |_: &str| Some("my_module.rs".to_string());
+        let result = expand_snippet_with("${TM_FILENAME/(.*)\\.rs$/${1:/pascalcase}/}", &vars, "");
+        assert_eq!(result.text, "MyModule");
+    }
+
+    #[test]
+    fn test_transformed_mirror() {
+        let result = expand_snippet("${1:hello} ${1/(h)(.*)/${1:/upcase}$2/}");
+        assert_eq!(result.text, "hello Hello");
+        let first = &result.tabstops[0];
+        assert_eq!(first.ranges, vec![0..5]);
+        assert_eq!(first.transformed.len(), 1);
+        let (range, transform) = &first.transformed[0];
+        assert_eq!(*range, 6..11);
+        assert_eq!(transform.apply("world"), "world");
+        assert_eq!(transform.apply("hi"), "Hi");
+    }
+
+    #[test]
+    fn test_transform_conditionals_and_flags() {
+        let result = expand_snippet("${1:a-b-c} ${1/-/_/g} ${1/(x)?.*/${1:?yes:no}/}");
+        assert_eq!(result.text, "a-b-c a_b_c no");
+    }
+
+    #[test]
+    fn test_indent_follows_newlines() {
+        let result = expand_snippet_with("if ${1:x} {\n\t$0\n}", &|_| None, "  ");
+        assert_eq!(result.text, "if x {\n  \t\n  }");
+        assert_eq!(result.cursor_offset, 10);
+    }
+
+    #[test]
+    fn test_malformed_placeholder_is_literal() {
+        let result = expand_snippet("${1:open");
+        assert_eq!(result.text, "${1:open");
+        let result = expand_snippet("cost $ and ${}");
+        assert_eq!(result.text, "cost $ and ${}");
+    }
+
+    #[test]
+    fn test_is_snippet_variables() {
+        assert!(is_snippet("$TM_FILENAME"));
+        assert!(!is_snippet("$foo"));
+    }
 }
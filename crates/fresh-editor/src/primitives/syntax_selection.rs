@@ -0,0 +1,426 @@
+//! Structural selection candidates.
+//!
+//! Expand/shrink selection walks a chain of byte ranges that enclose the
+//! current selection, each one strictly larger than the last. When a
+//! language server answers `textDocument/selectionRange` the editor uses its
+//! chain; otherwise the ranges come from here:
+//!
+//! - **Tree-sitter**: the syntax node under the selection and its ancestors,
+//!   plus the inside of every bracketed node (`bar, baz` between the parens
+//!   of an argument list) — for languages whose grammar is bundled.
+//! - **Text heuristics**: word, quoted string, bracket pairs (inside, then
+//!   including the brackets), the trimmed line, and indentation blocks with
+//!   their header line — for everything else.
+//!
+//! Candidates are not ordered; [`next_enclosing`] picks the next step.
+
+use std::ops::Range;
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{Node, Parser, Tree};
+
+/// Largest buffer that is parsed in full. Bigger buffers only get the text
+/// heuristics over a window around the selection.
+const MAX_PARSE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Bytes scanned on each side of the selection in buffers too big to parse.
+const WINDOW_BYTES: usize = 64 * 1024;
+
+/// Ranges in `buffer` that enclose each of `selections`, including the
+/// whole buffer. The buffer is parsed once for all of them.
+pub fn enclosing_ranges(
+    buffer: &Buffer,
+    language: Option<&Language>,
+    selections: &[Range<usize>],
+) -> Vec<Vec<Range<usize>>> {
+    let len = buffer.len();
+    if len > MAX_PARSE_BYTES {
+        return selections
+            .iter()
+            .map(|selection| {
+                let offset = selection.start.saturating_sub(WINDOW_BYTES);
+                let end = len.min(selection.end + WINDOW_BYTES);
+                let source = buffer.slice_bytes(offset..end);
+                let mut ranges: Vec<_> =
+                    text_ranges(&source, selection.start - offset..selection.end - offset)
+                        .into_iter()
+                        .map(|r| r.start + offset..r.end + offset)
+                        .collect();
+                ranges.push(0..len);
+                ranges
+            })
+            .collect();
+    }
+
+    let source = buffer.slice_bytes(0..len);
+    let tree = language
+        .and_then(|l| l.ts_language())
+        .and_then(|l| parse(&source, &l));
+    selections
+        .iter()
+        .map(|selection| {
+            let syntax = tree
+                .as_ref()
+                .and_then(|tree| syntax_ranges(&source, tree, selection.clone()));
+            let mut ranges = match syntax {
+                Some(mut ranges) => {
+                    ranges.extend(word_range(&source, selection));
+                    ranges
+                }
+                None => text_ranges(&source, selection.clone()),
+            };
+            ranges.push(0..len);
+            ranges
+        })
+        .collect()
+}
+
+/// The smallest candidate that strictly contains `selection`.
+pub fn next_enclosing(
+    candidates: &[Range<usize>],
+    selection: &Range<usize>,
+) -> Option<Range<usize>> {
+    candidates
+        .iter()
+        .filter(|r| r.start <= selection.start && r.end >= selection.end && *r != selection)
+        .min_by_key(|r| r.len())
+        .cloned()
+}
+
+fn parse(source: &[u8], language: &fresh_languages::tree_sitter::Language) -> Option<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(language).ok()?;
+    parser.parse(source, None)
+}
+
+/// The syntax node covering `selection` and all of its ancestors, plus the
+/// inside of each bracketed one.
+fn syntax_ranges(source: &[u8], tree: &Tree, selection: Range<usize>) -> Option<Vec<Range<usize>>> {
+    let mut node = tree
+        .root_node()
+        .descendant_for_byte_range(selection.start, selection.end)?;
+
+    let mut ranges = Vec::new();
+    loop {
+        if let Some(inner) = bracketed_inside(source, &node) {
+            ranges.push(inner);
+        }
+        ranges.push(node.byte_range());
+        match node.parent() {
+            Some(parent) => node = parent,
+            None => break,
+        }
+    }
+    Some(ranges)
+}
+
+/// Candidates from plain text: word, quoted string, bracket pairs, line and
+/// indentation blocks.
+fn text_ranges(source: &[u8], selection: Range<usize>) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    ranges.extend(word_range(source, &selection));
+    quote_ranges(source, &selection, &mut ranges);
+    bracket_ranges(source, &selection, &mut ranges);
+    ranges.push(trim(
+        source,
+        line_start(source, selection.start)..line_end(source, selection.end),
+    ));
+    indent_ranges(source, selection.start, &mut ranges);
+    ranges
+}
+
+/// Between a node's first and last child when those are a bracket pair,
+/// trimmed of surrounding whitespace.
+fn bracketed_inside(source: &[u8], node: &Node) -> Option<Range<usize>> {
+    let mut cursor = node.walk();
+    let mut children = node.children(&mut cursor);
+    let first = children.next()?;
+    let last = children.last()?;
+    let open = *source.get(first.start_byte())?;
+    let close = *source.get(last.start_byte())?;
+    if first.byte_range().len() != 1 || closer_for(open) != Some(close) {
+        return None;
+    }
+    Some(trim(source, first.end_byte()..last.start_byte()))
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+/// The word under (or ending at) an empty or in-word selection.
+fn word_range(source: &[u8], selection: &Range<usize>) -> Option<Range<usize>> {
+    let mut start = selection.start;
+    let mut end = selection.end;
+    if source[start..end].iter().any(|b| !is_word_byte(*b)) {
+        return None;
+    }
+    while start > 0 && is_word_byte(source[start - 1]) {
+        start -= 1;
+    }
+    while end < source.len() && is_word_byte(source[end]) {
+        end += 1;
+    }
+    (start < end).then_some(start..end)
+}
+
+/// Quoted strings on the selection's line: inside, then with the quotes.
+fn quote_ranges(source: &[u8], selection: &Range<usize>, out: &mut Vec<Range<usize>>) {
+    let start = line_start(source, selection.start);
+    let end = line_end(source, selection.start);
+    let mut open: Option<usize> = None;
+    let mut i = start;
+    while i < end {
+        let b = source[i];
+        match open {
+            Some(_) if b == b'\\' => i += 1,
+            Some(o) if b == source[o] => {
+                if o < selection.start && i >= selection.end {
+                    out.push(o + 1..i);
+                    out.push(o..i + 1);
+                }
+                open = None;
+            }
+            None if matches!(b, b'"' | b'\'' | b'`') => open = Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+fn closer_for(open: u8) -> Option<u8> {
+    match open {
+        b'(' => Some(b')'),
+        b'[' => Some(b']'),
+        b'{' => Some(b'}'),
+        _ => None,
+    }
+}
+
+/// Every bracket pair around the selection: inside trimmed, inside, and
+/// with the brackets.
+fn bracket_ranges(source: &[u8], selection: &Range<usize>, out: &mut Vec<Range<usize>>) {
+    let mut left = selection.start;
+    let mut right = selection.end;
+    loop {
+        let Some(open) = unmatched_opener(source, left) else {
+            return;
+        };
+        let Some(close) = matching_closer(source, right, open) else {
+            return;
+        };
+        out.push(trim(source, open + 1..close));
+        out.push(open + 1..close);
+        out.push(open..close + 1);
+        left = open;
+        right = close + 1;
+    }
+}
+
+fn unmatched_opener(source: &[u8], before: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for i in (0..before).rev() {
+        match source[i] {
+            b')' | b']' | b'}' => depth += 1,
+            b'(' | b'[' | b'{' if depth == 0 => return Some(i),
+            b'(' | b'[' | b'{' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn matching_closer(source: &[u8], from: usize, open: usize) -> Option<usize> {
+    let want = closer_for(source[open])?;
+    let mut depth = 0usize;
+    for (i, &b) in source.iter().enumerate().skip(from) {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return (b == want).then_some(i),
+            b')' | b']' | b'}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Indentation blocks around `pos`: the run of lines indented at least as
+/// deep as its line, then that run together with its less-indented header
+/// line (and a closing bracket line at the header's depth), repeated
+/// outwards.
+fn indent_ranges(source: &[u8], pos: usize, out: &mut Vec<Range<usize>>) {
+    let lines = line_starts(source);
+    let mut line = lines.partition_point(|&s| s <= pos) - 1;
+    let Some(mut depth) = indent_of(source, &lines, line) else {
+        return;
+    };
+
+    while depth > 0 {
+        let mut first = line;
+        while first > 0 && indent_of(source, &lines, first - 1).is_none_or(|d| d >= depth) {
+            first -= 1;
+        }
+        let mut last = line;
+        while last + 1 < lines.len()
+            && indent_of(source, &lines, last + 1).is_none_or(|d| d >= depth)
+        {
+            last += 1;
+        }
+        let block_end = line_end(source, lines[last]);
+        out.push(trim(source, lines[first]..block_end));
+
+        if first == 0 {
+            return;
+        }
+        let header = first - 1;
+        let header_depth = indent_of(source, &lines, header).unwrap_or(0);
+        let mut end = block_end;
+        if let Some(&next) = lines.get(last + 1) {
+            let next_end = line_end(source, next);
+            let closes = trim(source, next..next_end);
+            if indent_of(source, &lines, last + 1) == Some(header_depth)
+                && matches!(source.get(closes.start), Some(b')' | b']' | b'}'))
+            {
+                end = next_end;
+            }
+        }
+        out.push(trim(source, lines[header]..end));
+        line = header;
+        depth = header_depth;
+    }
+}
+
+fn line_starts(source: &[u8]) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(
+            source
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| **b == b'\n')
+                .map(|(i, _)| i + 1),
+        )
+        .collect()
+}
+
+/// Leading whitespace width of a line, `None` for blank lines.
+fn indent_of(source: &[u8], lines: &[usize], line: usize) -> Option<usize> {
+    let start = lines[line];
+    let end = line_end(source, start);
+    let text = &source[start..end];
+    let width = text
+        .iter()
+        .take_while(|b| matches!(b, b' ' | b'\t'))
+        .count();
+    (width < text.len()).then_some(width)
+}
+
+fn line_start(source: &[u8], pos: usize) -> usize {
+    source[..pos]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |i| i + 1)
+}
+
+/// End of the line containing `pos`, before its newline.
+fn line_end(source: &[u8], pos: usize) -> usize {
+    source[pos..]
+        .iter()
+        .position(|b| *b == b'\n' || *b == b'\r')
+        .map_or(source.len(), |i| pos + i)
+}
+
+fn trim(source: &[u8], range: Range<usize>) -> Range<usize> {
+    let text = &source[range.clone()];
+    let Some(first) = text.iter().position(|b| !b.is_ascii_whitespace()) else {
+        return range;
+    };
+    let last = text
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .unwrap_or(first);
+    range.start + first..range.start + last + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Selected text after each expansion from `cursor`.
+    fn steps(source: &str, candidates: &[Range<usize>], cursor: usize) -> Vec<String> {
+        let mut selection = cursor..cursor;
+        let mut out = Vec::new();
+        while let Some(next) = next_enclosing(candidates, &selection) {
+            out.push(source[next.clone()].to_string());
+            selection = next;
+        }
+        out
+    }
+
+    #[test]
+    fn text_heuristics_walk_word_brackets_line_and_block() {
+        let source = "fn main() {\n    foo(bar, baz);\n}\n";
+        let cursor = source.find("ar,").unwrap();
+        let mut candidates = text_ranges(source.as_bytes(), cursor..cursor);
+        candidates.push(0..source.len());
+
+        assert_eq!(
+            steps(source, &candidates, cursor),
+            vec![
+                "bar",
+                "bar, baz",
+                "(bar, baz)",
+                "foo(bar, baz);",
+                "\n    foo(bar, baz);\n",
+                "{\n    foo(bar, baz);\n}",
+                "fn main() {\n    foo(bar, baz);\n}",
+                source,
+            ]
+        );
+    }
+
+    #[test]
+    fn quotes_are_selected_inside_then_whole() {
+        let source = r#"x = call("a \"b\" c", 'd')"#;
+        let cursor = source.find("c\"").unwrap();
+        let candidates = text_ranges(source.as_bytes(), cursor..cursor);
+        let walked = steps(source, &candidates, cursor);
+        assert_eq!(&walked[..3], &["c", r#"a \"b\" c"#, r#""a \"b\" c""#]);
+    }
+
+    #[test]
+    fn indentation_blocks_without_brackets() {
+        let source = "def f():\n    if x:\n        a = 1\n        b = 2\n    return\n";
+        let cursor = source.find("b = 2").unwrap();
+        let candidates = text_ranges(source.as_bytes(), cursor..cursor);
+        let walked = steps(source, &candidates, cursor);
+        assert!(walked.contains(&"a = 1\n        b = 2".to_string()));
+        assert!(walked.contains(&"if x:\n        a = 1\n        b = 2".to_string()));
+        assert!(walked.contains(&source.trim_end().to_string()));
+    }
+
+    #[test]
+    fn selection_that_is_already_a_candidate_moves_outwards() {
+        let candidates = vec![2..5, 0..10, 2..5];
+        assert_eq!(next_enclosing(&candidates, &(2..5)), Some(0..10));
+        assert_eq!(next_enclosing(&candidates, &(0..10)), None);
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn tree_sitter_walks_syntax_nodes() {
+        let source = "package main\n\nfunc main() {\n\tfoo(bar, baz)\n}\n";
+        let cursor = source.find("ar,").unwrap();
+        let language = Language::Go.ts_language().expect("Go grammar is bundled");
+        let tree = parse(source.as_bytes(), &language).expect("parses");
+        let candidates =
+            syntax_ranges(source.as_bytes(), &tree, cursor..cursor).expect("node at cursor");
+        let walked = steps(source, &candidates, cursor);
+
+        assert_eq!(
+            &walked[..4],
+            &["bar", "bar, baz", "(bar, baz)", "foo(bar, baz)"]
+        );
+        assert!(walked.contains(&"func main() {\n\tfoo(bar, baz)\n}".to_string()));
+    }
+}
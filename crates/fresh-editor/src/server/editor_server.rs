@@ -2,8 +2,9 @@
 //!
 //! This module bridges the Editor with the server infrastructure:
 //! - Creates Editor with CaptureBackend for rendering
-//! - Processes input events from clients
-//! - Broadcasts rendered output to all clients
+//! - Processes input events from clients, each in the client's own view
+//!   (window, split layout and size — see `app::client_views`)
+//! - Renders each client's view and sends it to that client
 
 use std::io;
 use std::path::PathBuf;
@@ -15,7 +16,7 @@ use std::time::{Duration, Instant};
 use crossterm::event::{Event, KeyEventKind};
 use ratatui::Terminal;
 
-use crate::app::Editor;
+use crate::app::{ClientView, Editor};
 use crate::config::Config;
 use crate::config_io::DirectoryContext;
 // Filesystem is now owned by `self.current_authority`; the server no
@@ -28,6 +29,7 @@ use crate::server::ipc::{ServerConnection, ServerListener, SocketPaths, StreamWr
 use crate::server::protocol::{
     ClientControl, ServerControl, ServerHello, TermSize, VersionMismatch, PROTOCOL_VERSION,
 };
+use crate::server::remote::RemoteClients;
 use crate::view::color_support::ColorCapability;
 
 /// Configuration for the editor server
@@ -77,10 +79,10 @@ pub struct EditorServer {
     listener: ServerListener,
     clients: Vec<ConnectedClient>,
     editor: Option<Editor>,
-    terminal: Option<Terminal<CaptureBackend>>,
     last_client_activity: Instant,
     shutdown: Arc<AtomicBool>,
-    /// Effective terminal size (from the primary/first client)
+    /// Size the editor is built at (from the primary/first client).
+    /// Each client renders at its own size.
     term_size: TermSize,
     /// Index of the client that most recently provided input (for per-client detach)
     last_input_client: Option<usize>,
@@ -88,6 +90,8 @@ pub struct EditorServer {
     next_wait_id: u64,
     /// Maps wait_id → client_id for clients waiting on file events
     waiting_clients: std::collections::HashMap<u64, u64>,
+    /// Hook subscriptions of remote-control clients (`fresh --remote`)
+    remote: RemoteClients,
     /// Current authority. Carried across editor rebuilds so plugin-
     /// installed authorities (e.g. a devcontainer attach) survive the
     /// restart-based transition: the old editor is dropped, a new one
@@ -168,11 +172,15 @@ impl ClientDataWriter {
     }
 }
 
-/// A connected client with its own input parser
+/// A connected client with its own input parser and view
 struct ConnectedClient {
     conn: ServerConnection,
     /// Background writer for non-blocking data output
     data_writer: ClientDataWriter,
+    /// Renders this client's view; diffs against its previous frame
+    terminal: Terminal<CaptureBackend>,
+    /// The client's window, split layout and size in the editor
+    view: ClientView,
     term_size: TermSize,
     env: std::collections::HashMap<String, Option<String>>,
     id: u64,
@@ -181,6 +189,9 @@ struct ConnectedClient {
     needs_full_render: bool,
     /// If set, this client is waiting for a --wait completion signal
     wait_id: Option<u64>,
+    /// Set once the client sends a remote-control request; such clients
+    /// script the editor and are never rendered to
+    remote_control: bool,
 }
 
 impl EditorServer {
@@ -225,13 +236,13 @@ impl EditorServer {
             listener,
             clients: Vec::new(),
             editor: None,
-            terminal: None,
             last_client_activity: Instant::now(),
             shutdown: Arc::new(AtomicBool::new(false)),
             term_size: TermSize::new(80, 24), // Default until first client connects
             last_input_client: None,
             next_wait_id: 1,
             waiting_clients: std::collections::HashMap::new(),
+            remote: RemoteClients::default(),
             current_authority,
             workspace_trust,
             env_provider,
@@ -306,7 +317,7 @@ impl EditorServer {
                                 // Reconnecting after all clients disconnected - update terminal size
                                 if self.term_size != client.term_size {
                                     self.term_size = client.term_size;
-                                    self.update_terminal_size()?;
+                                    self.update_terminal_size();
                                 }
                             }
                             // Note: full redraw is handled via client.needs_full_render flag
@@ -443,15 +454,14 @@ impl EditorServer {
 
             // Handle resize
             if resize_occurred {
-                self.update_terminal_size()?;
                 needs_render = true;
             }
 
             // Process input events
             if !input_events.is_empty() {
                 self.last_client_activity = Instant::now();
-                for event in input_events {
-                    if self.handle_event(event)? {
+                for (client_id, event) in input_events {
+                    if self.handle_event(client_id, event)? {
                         needs_render = true;
                     }
                 }
@@ -480,6 +490,18 @@ impl EditorServer {
                     }
                 }
 
+                // Push fired hooks to the remote-control clients subscribed
+                // to them
+                for (client_id, notification) in self.remote.notifications(editor) {
+                    if let Some(client) = self.clients.iter_mut().find(|c| c.id == client_id) {
+                        let msg =
+                            serde_json::to_string(&ServerControl::RemoteNotification(notification))
+                                .unwrap_or_default();
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = client.conn.write_control(&msg);
+                    }
+                }
+
                 // Send pending clipboard data to clients via control message
                 if let Some(cb) = editor.take_pending_clipboard() {
                     let msg = serde_json::to_string(&ServerControl::SetClipboard {
@@ -558,11 +580,7 @@ impl EditorServer {
     /// Build a fresh `Editor` instance using the current configuration
     /// and stored authority.  Shared between first-boot initialization
     /// and post-restart rebuild.
-    fn build_editor_instance(&mut self) -> io::Result<(Editor, Terminal<CaptureBackend>)> {
-        let backend = CaptureBackend::new(self.term_size.cols, self.term_size.rows);
-        let terminal = Terminal::new(backend)
-            .map_err(|e| io::Error::other(format!("Failed to create terminal: {}", e)))?;
-
+    fn build_editor_instance(&mut self) -> io::Result<Editor> {
         // The editor is constructed with the real authority it runs under, so
         // plugins and init.ts load against the correct backend from the first
         // tick — no post-construction swap. The authority already carries the
@@ -611,7 +629,7 @@ impl EditorServer {
         });
         editor.set_session_name(Some(session_display_name));
 
-        Ok((editor, terminal))
+        Ok(editor)
     }
 
     /// Initialize the editor on first client connection.
@@ -621,7 +639,7 @@ impl EditorServer {
     /// session.  Subsequent rebuilds (on authority/working-dir change)
     /// go through [`rebuild_editor`].
     pub fn initialize_editor(&mut self) -> io::Result<()> {
-        let (mut editor, terminal) = self.build_editor_instance()?;
+        let mut editor = self.build_editor_instance()?;
 
         // Restore workspace and recovery data (mirrors the standalone startup
         // path in handle_first_run_setup in main.rs).
@@ -658,7 +676,6 @@ impl EditorServer {
             tracing::warn!("Failed to start recovery session: {}", e);
         }
 
-        self.terminal = Some(terminal);
         self.editor = Some(editor);
 
         self.maybe_prompt_workspace_trust();
@@ -726,10 +743,9 @@ impl EditorServer {
             }
         }
 
-        // Drop old editor + terminal.  Drop impls shut down PTYs, LSP
-        // servers, and plugin threads.
+        // Drop old editor.  Drop impls shut down PTYs, LSP servers, and
+        // plugin threads.
         self.editor = None;
-        self.terminal = None;
 
         // Apply the pending changes before building the next editor.
         if let Some(dir) = new_working_dir {
@@ -768,7 +784,9 @@ impl EditorServer {
             self.session_keepalive = Some(keepalive);
         }
 
-        let (mut editor, terminal) = self.build_editor_instance()?;
+        let mut editor = self.build_editor_instance()?;
+        // The new editor's plugin manager starts with no hooks tapped
+        self.remote.sync(&editor);
 
         // Bring buffers back under the new backend.  `try_restore_workspace`
         // reads the workspace file we wrote above and re-opens the
@@ -783,7 +801,6 @@ impl EditorServer {
             tracing::warn!("Rebuild: failed to start recovery session: {}", e);
         }
 
-        self.terminal = Some(terminal);
         self.editor = Some(editor);
 
         // A working-dir change lands us in a possibly-undecided project;
@@ -792,7 +809,9 @@ impl EditorServer {
         self.maybe_prompt_workspace_trust();
 
         // Force every attached client to repaint from scratch — the
-        // previous frame described the old editor's screen.
+        // previous frame described the old editor's screen. Their views
+        // start over in the new editor, whose layouts they adopt or
+        // derive on their next frame.
         for client in &mut self.clients {
             client.needs_full_render = true;
         }
@@ -888,21 +907,30 @@ impl EditorServer {
         // Create background writer for non-blocking render output
         let data_writer = ClientDataWriter::new(conn.data.clone(), client_id);
 
+        let backend = CaptureBackend::new(hello.term_size.cols, hello.term_size.rows);
+        let terminal = Terminal::new(backend)
+            .map_err(|e| io::Error::other(format!("Failed to create terminal: {}", e)))?;
+
         Ok(ConnectedClient {
             conn,
             data_writer,
+            terminal,
+            view: ClientView::new(client_id, hello.term_size.cols, hello.term_size.rows),
             term_size: hello.term_size,
             env: hello.env,
             id: client_id,
             input_parser: InputParser::new(),
             needs_full_render: true,
             wait_id: None,
+            remote_control: false,
         })
     }
 
     /// Process messages from connected clients
-    /// Returns (input_events, resize_occurred, index of client that provided input)
-    fn process_clients(&mut self) -> io::Result<(Vec<Event>, bool, Option<usize>)> {
+    /// Returns (input events with the id of the client they came from,
+    /// resize_occurred, index of client that provided input)
+    #[allow(clippy::type_complexity)]
+    fn process_clients(&mut self) -> io::Result<(Vec<(u64, Event)>, bool, Option<usize>)> {
         let mut disconnected = Vec::new();
         let mut input_source_client: Option<usize> = None;
         let mut input_events = Vec::new();
@@ -939,7 +967,7 @@ impl EditorServer {
                     if !events.is_empty() {
                         input_source_client = Some(idx);
                     }
-                    input_events.extend(events);
+                    input_events.extend(events.into_iter().map(|event| (client.id, event)));
                 }
                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
                     // No data available
@@ -1027,9 +1055,12 @@ impl EditorServer {
                 continue;
             }
 
-            // Always process OpenFiles / OpenWindow - they're one-shot
-            // commands from clients that disconnect immediately
-            if let ClientControl::OpenFiles { .. } | ClientControl::OpenWindow { .. } = msg {
+            // Always process OpenFiles / OpenWindow / Remote - they're
+            // one-shot commands from clients that may disconnect immediately
+            if let ClientControl::OpenFiles { .. }
+            | ClientControl::OpenWindow { .. }
+            | ClientControl::Remote(_) = msg
+            {
                 // Fall through to process it
             } else if disconnected.contains(&idx) {
                 // Skip other messages from disconnected clients
@@ -1043,11 +1074,16 @@ impl EditorServer {
                 ClientControl::Resize { cols, rows } => {
                     if let Some(client) = self.clients.get_mut(idx) {
                         client.term_size = TermSize::new(cols, rows);
+                        client.terminal.backend_mut().resize(cols, rows);
+                        client.view.resize(cols, rows);
+                        if let Some(ref mut editor) = self.editor {
+                            editor.client_view_resized(&client.view);
+                        }
                         // Update server size to match first client
                         if idx == 0 {
                             self.term_size = TermSize::new(cols, rows);
-                            resize_occurred = true;
                         }
+                        resize_occurred = true;
                     }
                 }
                 ClientControl::Ping => {
@@ -1128,6 +1164,20 @@ impl EditorServer {
                         }
                     }
                 }
+                ClientControl::Remote(request) => {
+                    if let (Some(editor), Some(client)) =
+                        (self.editor.as_mut(), self.clients.get_mut(idx))
+                    {
+                        client.remote_control = true;
+                        let response = self.remote.handle(editor, client.id, &request);
+                        let msg = serde_json::to_string(&ServerControl::RemoteResponse(response))
+                            .unwrap_or_default();
+                        // Best-effort: the client may already be gone
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = client.conn.write_control(&msg);
+                        resize_occurred = true; // Force re-render
+                    }
+                }
                 ClientControl::Quit => unreachable!(), // Handled above
             }
         }
@@ -1147,6 +1197,10 @@ impl EditorServer {
         // Remove disconnected clients
         for idx in disconnected.into_iter().rev() {
             let client = self.clients.remove(idx);
+            if let Some(ref mut editor) = self.editor {
+                editor.drop_client_view(&client.view);
+                self.remote.remove(editor, client.id);
+            }
             // Clean up --wait tracking if this client was waiting
             if let Some(wait_id) = client.wait_id {
                 self.waiting_clients.remove(&wait_id);
@@ -1168,25 +1222,22 @@ impl EditorServer {
         Ok((input_events, resize_occurred, input_source_client))
     }
 
-    /// Update terminal size after resize
-    fn update_terminal_size(&mut self) -> io::Result<()> {
-        if let Some(ref mut terminal) = self.terminal {
-            let backend = terminal.backend_mut();
-            backend.resize(self.term_size.cols, self.term_size.rows);
-        }
-
+    /// Update the editor size after the server size changed
+    fn update_terminal_size(&mut self) {
         if let Some(ref mut editor) = self.editor {
             editor.resize(self.term_size.cols, self.term_size.rows);
         }
-
-        Ok(())
     }
 
-    /// Handle an input event
-    fn handle_event(&mut self, event: Event) -> io::Result<bool> {
+    /// Handle an input event from client `client_id`, in that client's view
+    fn handle_event(&mut self, client_id: u64, event: Event) -> io::Result<bool> {
         let Some(ref mut editor) = self.editor else {
             return Ok(false);
         };
+        let Some(client) = self.clients.iter().find(|c| c.id == client_id) else {
+            return Ok(false);
+        };
+        editor.enter_client_view(&client.view);
 
         match event {
             Event::Key(key_event) => {
@@ -1214,50 +1265,42 @@ impl EditorServer {
         }
     }
 
-    /// Render the editor and broadcast output to all clients
+    /// Render each client's view of the editor and send it to that client
     fn render_and_broadcast(&mut self) -> io::Result<()> {
         let Some(ref mut editor) = self.editor else {
             return Ok(());
         };
 
-        let Some(ref mut terminal) = self.terminal else {
-            return Ok(());
-        };
-
-        // Check if any client needs a full render (e.g., newly connected)
-        let any_needs_full = self.clients.iter().any(|c| c.needs_full_render);
-        if any_needs_full {
-            tracing::info!(
-                "Full render requested for {} client(s)",
-                self.clients.iter().filter(|c| c.needs_full_render).count()
-            );
-            // Force full redraw by invalidating terminal state
-            terminal.backend_mut().reset_style_state();
-            // Best-effort terminal clear for full redraw
-            #[allow(clippy::let_underscore_must_use)]
-            let _ = terminal.clear();
-        }
-
-        // Take any pending escape sequences (e.g., cursor style changes)
+        // Take any pending escape sequences (e.g., cursor style changes);
+        // they go to every client.
         let pending_sequences = editor.take_pending_escape_sequences();
 
-        // Render to capture backend
-        terminal
-            .draw(|frame| editor.render(frame))
-            .map_err(|e| io::Error::other(e.to_string()))?;
-
-        // Get the captured output
-        let output = terminal.backend_mut().take_buffer();
-
-        if output.is_empty() && pending_sequences.is_empty() {
-            return Ok(());
-        }
-
-        // Broadcast to all clients via non-blocking writer threads (skip waiting clients)
+        // Render each client's view via non-blocking writer threads (skip
+        // waiting and remote-control clients)
         for client in &mut self.clients {
-            if client.wait_id.is_some() {
+            if client.wait_id.is_some() || client.remote_control {
                 continue;
             }
+            // A newly connected client (or one resuming) needs a full render
+            if client.needs_full_render {
+                tracing::info!("Full render requested for client {}", client.id);
+                // Force full redraw by invalidating terminal state
+                client.terminal.backend_mut().reset_style_state();
+                // Best-effort terminal clear for full redraw
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = client.terminal.clear();
+            }
+
+            // Render to the client's capture backend
+            let view = &client.view;
+            client
+                .terminal
+                .draw(|frame| editor.render_client_view(view, frame))
+                .map_err(|e| io::Error::other(e.to_string()))?;
+
+            // Get the captured output
+            let output = client.terminal.backend_mut().take_buffer();
+
             // Combine pending sequences and output into a single frame
             let frame = if !pending_sequences.is_empty() && !output.is_empty() {
                 let mut combined = Vec::with_capacity(pending_sequences.len() + output.len());
@@ -1267,7 +1310,7 @@ impl EditorServer {
             } else if !pending_sequences.is_empty() {
                 pending_sequences.clone()
             } else {
-                output.clone()
+                output
             };
 
             if !frame.is_empty() && !client.data_writer.try_write(&frame) {
@@ -21,6 +21,10 @@
 //!   requests through the editor's existing `queue_file_open` /
 //!   `create_window_at` machinery and waking parked connections when a
 //!   wait completes.
+//! - A connection that sends a [`ClientControl::Remote`] request (`fresh
+//!   --remote`, see `server::remote`) switches to polling its socket, so
+//!   replies and subscribed hook notifications can be written while it
+//!   waits for the next request.
 //!
 //! This deliberately reuses the same IPC primitives ([`ServerListener`],
 //! [`ServerConnection`]) and wire protocol ([`ClientControl`] /
@@ -38,8 +42,10 @@ use std::time::Duration;
 use crate::app::Editor;
 use crate::server::ipc::{ServerConnection, ServerListener, SocketPaths, StreamWrapper};
 use crate::server::protocol::{
-    ClientControl, FileRequest, ServerControl, ServerHello, VersionMismatch, PROTOCOL_VERSION,
+    ClientControl, FileRequest, RemoteRequest, ServerControl, ServerHello, VersionMismatch,
+    PROTOCOL_VERSION,
 };
+use crate::server::remote::RemoteClients;
 
 /// Time the accept thread sleeps between non-blocking accept polls. Only
 /// affects how quickly a freshly-launched nested `fresh` is picked up;
@@ -57,6 +63,15 @@ enum LocalControlRequest {
     },
     /// Open a directory as a new, focused orchestrator window.
     OpenWindow { path: PathBuf },
+    /// A remote-control request from connection `conn_id`. Its reply, and
+    /// later the hooks it subscribes to, go out through `outbox`.
+    Remote {
+        conn_id: u64,
+        request: RemoteRequest,
+        outbox: Sender<ServerControl>,
+    },
+    /// Remote-control connection `conn_id` closed.
+    RemoteClosed { conn_id: u64 },
 }
 
 /// Shared state between the connection-handler threads and the editor
@@ -67,6 +82,16 @@ struct Shared {
     /// `wait_id` -> notifier that wakes the parked handler thread once the
     /// editor reports the matching buffer closed.
     waiters: Arc<Mutex<HashMap<u64, Sender<()>>>>,
+    /// Remote-control connections; only `pump` touches these.
+    remote: Mutex<LocalRemote>,
+}
+
+/// Remote-control connections as seen from the editor thread
+#[derive(Default)]
+struct LocalRemote {
+    clients: RemoteClients,
+    /// Connection id → where its replies and notifications go
+    outboxes: HashMap<u64, Sender<ServerControl>>,
 }
 
 /// Process-global handle. `None` until [`start`] succeeds; the control
@@ -106,6 +131,7 @@ pub fn start() -> std::io::Result<&'static str> {
     let _ = GLOBAL.set(Shared {
         req_rx: Mutex::new(bound.req_rx),
         waiters: bound.waiters,
+        remote: Mutex::default(),
     });
     let id = SESSION_ID.get_or_init(|| session_id);
     tracing::info!("Local control socket listening as session {}", id);
@@ -215,10 +241,42 @@ pub fn pump(editor: &mut Editor) -> bool {
                     tracing::warn!("OpenWindow ignored: path must be absolute: {:?}", path);
                 }
             }
+            Ok(LocalControlRequest::Remote {
+                conn_id,
+                request,
+                outbox,
+            }) => {
+                let mut remote = shared.remote.lock().unwrap();
+                let response = remote.clients.handle(editor, conn_id, &request);
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = outbox.send(ServerControl::RemoteResponse(response));
+                remote.outboxes.insert(conn_id, outbox);
+                changed = true;
+            }
+            Ok(LocalControlRequest::RemoteClosed { conn_id }) => {
+                let mut remote = shared.remote.lock().unwrap();
+                remote.clients.remove(editor, conn_id);
+                remote.outboxes.remove(&conn_id);
+            }
             Err(_) => break,
         }
     }
 
+    // Push subscribed hooks to their connections. Re-tap them first: the
+    // editor is rebuilt when the working directory changes.
+    {
+        let remote = shared.remote.lock().unwrap();
+        if remote.clients.has_subscriptions() {
+            remote.clients.sync(editor);
+            for (conn_id, notification) in remote.clients.notifications(editor) {
+                if let Some(outbox) = remote.outboxes.get(&conn_id) {
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = outbox.send(ServerControl::RemoteNotification(notification));
+                }
+            }
+        }
+    }
+
     // Route completed waits back to the parked handler threads so they can
     // send WaitComplete and let the blocked nested `fresh` (e.g. the editor
     // git launched) exit.
@@ -246,6 +304,7 @@ fn accept_loop(
     shutdown: Arc<AtomicBool>,
 ) {
     let next_wait_id = Arc::new(AtomicU64::new(1));
+    let mut next_conn_id = 1;
 
     while !shutdown.load(Ordering::SeqCst) {
         match listener.accept() {
@@ -253,13 +312,15 @@ fn accept_loop(
                 let req_tx = req_tx.clone();
                 let waiters = waiters.clone();
                 let next_wait_id = next_wait_id.clone();
+                let conn_id = next_conn_id;
+                next_conn_id += 1;
                 // Best-effort: a failed handler spawn just drops the
                 // connection; the nested client falls back to inline.
                 #[allow(clippy::let_underscore_must_use)]
                 let _ = std::thread::Builder::new()
                     .name("fresh-local-control-conn".to_string())
                     .spawn(move || {
-                        handle_connection(conn, req_tx, waiters, next_wait_id);
+                        handle_connection(conn, conn_id, req_tx, waiters, next_wait_id);
                     });
             }
             Ok(None) => std::thread::sleep(ACCEPT_POLL_INTERVAL),
@@ -275,6 +336,7 @@ fn accept_loop(
 /// the editor thread, and (for waited opens) park until the buffer closes.
 fn handle_connection(
     conn: ServerConnection,
+    conn_id: u64,
     req_tx: Sender<LocalControlRequest>,
     waiters: Arc<Mutex<HashMap<u64, Sender<()>>>>,
     next_wait_id: Arc<AtomicU64>,
@@ -348,6 +410,13 @@ fn handle_connection(
                     waiters.lock().unwrap().remove(&id);
                 }
             }
+            ClientControl::Remote(request) => {
+                // Whatever the reader buffered past this request is the
+                // start of the next one
+                let pending = reader.buffer().to_vec();
+                serve_remote(&conn, conn_id, &req_tx, request, pending);
+                return;
+            }
             other => {
                 tracing::debug!("Local control ignoring unexpected message: {:?}", other);
             }
@@ -355,6 +424,81 @@ fn handle_connection(
     }
 }
 
+/// Serve a remote-control connection from its first request on. Polls the
+/// socket rather than blocking on it, so replies and hook notifications
+/// from the editor thread go out while no request is pending. `pending`
+/// holds bytes already read past the first request.
+fn serve_remote(
+    conn: &ServerConnection,
+    conn_id: u64,
+    req_tx: &Sender<LocalControlRequest>,
+    first: RemoteRequest,
+    mut pending: Vec<u8>,
+) {
+    let (outbox, outbox_rx) = mpsc::channel::<ServerControl>();
+    let forward = |request| {
+        req_tx
+            .send(LocalControlRequest::Remote {
+                conn_id,
+                request,
+                outbox: outbox.clone(),
+            })
+            .is_ok()
+    };
+
+    let mut open = forward(first);
+    let mut buf = [0u8; 4096];
+    while open {
+        let mut idle = true;
+
+        while let Ok(msg) = outbox_rx.try_recv() {
+            idle = false;
+            let line = serde_json::to_string(&msg).unwrap_or_default();
+            if conn.write_control(&line).is_err() {
+                open = false;
+                break;
+            }
+        }
+
+        match conn.control.try_read(&mut buf) {
+            Ok(0) => open = false, // client disconnected
+            Ok(n) => {
+                idle = false;
+                pending.extend_from_slice(&buf[..n]);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(e) => {
+                tracing::debug!("Local control read error: {}", e);
+                open = false;
+            }
+        }
+        while let Some(end) = pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ClientControl>(line.trim()) {
+                Ok(ClientControl::Remote(request)) => open &= forward(request),
+                Ok(other) => {
+                    tracing::debug!(
+                        "Local control ignoring message on remote connection: {:?}",
+                        other
+                    );
+                }
+                Err(e) => tracing::debug!("Local control invalid remote message: {}", e),
+            }
+        }
+
+        if idle {
+            std::thread::sleep(ACCEPT_POLL_INTERVAL);
+        }
+    }
+
+    #[allow(clippy::let_underscore_must_use)]
+    let _ = req_tx.send(LocalControlRequest::RemoteClosed { conn_id });
+}
+
 /// Read the `Hello`, version-check, and reply with `ServerHello` — the
 /// same shape as the daemon server's handshake, minus the data-channel
 /// terminal setup (nested clients never render).
@@ -673,10 +817,90 @@ mod tests {
         bound.shutdown.store(true, Ordering::SeqCst);
     }
 
+    /// A remote-control request is forwarded with an outbox; whatever the
+    /// editor thread posts there — the reply, then hook notifications while
+    /// the client sends nothing — reaches the client, and closing the
+    /// connection is reported so the editor drops its subscriptions.
+    #[test]
+    fn remote_request_reply_and_notifications_reach_client() {
+        use crate::server::protocol::{RemoteNotification, RemoteResponse};
+
+        let dir = TempDir::new().unwrap();
+        let paths = SocketPaths::for_session_name_in_dir("remote-test", dir.path());
+        let bound = bind_and_spawn(paths.clone()).expect("bind");
+
+        let conn = connect_client(&paths);
+        conn.write_control(
+            r#"{"type":"remote","jsonrpc":"2.0","id":3,"method":"subscribe","params":{"hooks":["after_file_save"]}}"#,
+        )
+        .unwrap();
+
+        let (conn_id, outbox) = match bound
+            .req_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("request forwarded")
+        {
+            LocalControlRequest::Remote {
+                conn_id,
+                request,
+                outbox,
+            } => {
+                assert_eq!(request.method, "subscribe");
+                assert_eq!(request.params["hooks"][0], "after_file_save");
+                outbox
+                    .send(ServerControl::RemoteResponse(RemoteResponse::new(
+                        request.id,
+                        Ok(serde_json::json!(true)),
+                    )))
+                    .unwrap();
+                (conn_id, outbox)
+            }
+            other => panic!("expected Remote, got {}", req_kind(&other)),
+        };
+
+        let line = conn.read_control().unwrap().expect("response present");
+        match serde_json::from_str::<ServerControl>(&line).unwrap() {
+            ServerControl::RemoteResponse(response) => {
+                assert_eq!(response.id, serde_json::json!(3));
+                assert_eq!(response.result, Some(serde_json::json!(true)));
+            }
+            other => panic!("expected RemoteResponse, got {:?}", other),
+        }
+
+        outbox
+            .send(ServerControl::RemoteNotification(RemoteNotification::new(
+                "hook",
+                serde_json::json!({ "name": "after_file_save" }),
+            )))
+            .unwrap();
+        let line = conn.read_control().unwrap().expect("notification present");
+        match serde_json::from_str::<ServerControl>(&line).unwrap() {
+            ServerControl::RemoteNotification(notification) => {
+                assert_eq!(notification.method, "hook");
+                assert_eq!(notification.params["name"], "after_file_save");
+            }
+            other => panic!("expected RemoteNotification, got {:?}", other),
+        }
+
+        drop(conn);
+        match bound
+            .req_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("close reported")
+        {
+            LocalControlRequest::RemoteClosed { conn_id: closed } => assert_eq!(closed, conn_id),
+            other => panic!("expected RemoteClosed, got {}", req_kind(&other)),
+        }
+
+        bound.shutdown.store(true, Ordering::SeqCst);
+    }
+
     fn req_kind(req: &LocalControlRequest) -> &'static str {
         match req {
             LocalControlRequest::OpenFiles { .. } => "OpenFiles",
             LocalControlRequest::OpenWindow { .. } => "OpenWindow",
+            LocalControlRequest::Remote { .. } => "Remote",
+            LocalControlRequest::RemoteClosed { .. } => "RemoteClosed",
         }
     }
 }
@@ -18,6 +18,7 @@ pub mod input_parser;
 pub mod ipc;
 pub mod local_control;
 pub mod protocol;
+pub mod remote;
 
 #[cfg(test)]
 mod runner;
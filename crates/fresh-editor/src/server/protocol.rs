@@ -11,7 +11,9 @@ use std::collections::HashMap;
 ///
 /// v2: added `ClientControl::OpenWindow` (open a directory as a new
 /// orchestrator window), used by the nested-terminal forwarding path.
-pub const PROTOCOL_VERSION: u32 = 2;
+/// v3: added `ClientControl::Remote` and the `ServerControl::Remote*`
+/// replies (the JSON-RPC remote-control API behind `fresh --remote`).
+pub const PROTOCOL_VERSION: u32 = 3;
 
 /// Terminal size in columns and rows
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -132,6 +134,9 @@ pub enum ClientControl {
     /// embedded terminal: the directory becomes a new workspace (a `Window`)
     /// instead of launching a second editor in the terminal.
     OpenWindow { path: String },
+    /// A remote-control request (`fresh --remote`), answered with a
+    /// `ServerControl::RemoteResponse`
+    Remote(RemoteRequest),
 }
 
 /// A file to open with optional line/column position, range, and hover message
@@ -184,6 +189,106 @@ pub enum ServerControl {
     /// keeps running so the editor state is preserved and picked up cleanly
     /// when the client resumes.
     SuspendClient,
+    /// Reply to a `ClientControl::Remote` request
+    RemoteResponse(RemoteResponse),
+    /// A hook the client subscribed to over remote control fired
+    RemoteNotification(RemoteNotification),
+}
+
+fn jsonrpc_version() -> String {
+    "2.0".to_string()
+}
+
+/// A JSON-RPC 2.0 request to drive a running editor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteRequest {
+    #[serde(default = "jsonrpc_version")]
+    pub jsonrpc: String,
+    /// Echoed back in the response
+    #[serde(default)]
+    pub id: serde_json::Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+impl RemoteRequest {
+    /// Decode the params; missing params decode as `{}`.
+    pub fn parse_params<T: serde::de::DeserializeOwned>(&self) -> Result<T, RemoteError> {
+        let params = if self.params.is_null() {
+            serde_json::json!({})
+        } else {
+            self.params.clone()
+        };
+        serde_json::from_value(params)
+            .map_err(|e| RemoteError::new(RemoteError::INVALID_PARAMS, e.to_string()))
+    }
+}
+
+/// A JSON-RPC 2.0 response to a [`RemoteRequest`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteResponse {
+    pub jsonrpc: String,
+    pub id: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RemoteError>,
+}
+
+impl RemoteResponse {
+    /// The response to request `id` with the given outcome
+    pub fn new(id: serde_json::Value, result: Result<serde_json::Value, RemoteError>) -> Self {
+        let (result, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: jsonrpc_version(),
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+/// A JSON-RPC 2.0 error object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RemoteError {
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// A JSON-RPC 2.0 notification pushed to a remote-control client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+impl RemoteNotification {
+    pub fn new(method: impl Into<String>, params: serde_json::Value) -> Self {
+        Self {
+            jsonrpc: jsonrpc_version(),
+            method: method.into(),
+            params,
+        }
+    }
 }
 
 /// Wrapper for control channel messages (used for JSON serialization)
@@ -244,6 +349,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_remote_request_roundtrip() {
+        let json = r#"{"type":"remote","jsonrpc":"2.0","id":7,"method":"executeAction","params":{"name":"save"}}"#;
+        let request = match serde_json::from_str::<ClientControl>(json).unwrap() {
+            ClientControl::Remote(request) => request,
+            other => panic!("expected Remote, got {:?}", other),
+        };
+        assert_eq!(request.id, serde_json::json!(7));
+        assert_eq!(request.method, "executeAction");
+        assert_eq!(request.params["name"], "save");
+
+        // `jsonrpc`, `id` and `params` may be left out
+        let json = r#"{"type":"remote","method":"listBuffers"}"#;
+        match serde_json::from_str::<ClientControl>(json).unwrap() {
+            ClientControl::Remote(request) => {
+                assert_eq!(request.jsonrpc, "2.0");
+                assert!(request.id.is_null());
+                assert!(request.params.is_null());
+            }
+            other => panic!("expected Remote, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_remote_response_has_result_or_error() {
+        let ok = RemoteResponse::new(serde_json::json!(1), Ok(serde_json::json!(true)));
+        let json = serde_json::to_value(&ok).unwrap();
+        assert_eq!(json["result"], true);
+        assert!(json.get("error").is_none());
+
+        let err = RemoteResponse::new(
+            serde_json::json!(2),
+            Err(RemoteError::new(RemoteError::METHOD_NOT_FOUND, "nope")),
+        );
+        let json = serde_json::to_value(&err).unwrap();
+        assert!(json.get("result").is_none());
+        assert_eq!(json["error"]["code"], RemoteError::METHOD_NOT_FOUND);
+    }
+
     #[test]
     fn test_control_messages_use_snake_case_tags() {
         let resize = ClientControl::Resize {
@@ -326,6 +470,12 @@ mod tests {
                 }],
                 wait: false,
             },
+            ClientControl::Remote(RemoteRequest {
+                jsonrpc: "2.0".to_string(),
+                id: serde_json::json!(1),
+                method: "listBuffers".to_string(),
+                params: serde_json::Value::Null,
+            }),
         ];
 
         for variant in variants {
@@ -356,6 +506,14 @@ mod tests {
                 use_system_clipboard: true,
             },
             ServerControl::SuspendClient,
+            ServerControl::RemoteResponse(RemoteResponse::new(
+                serde_json::json!(1),
+                Ok(serde_json::json!([])),
+            )),
+            ServerControl::RemoteNotification(RemoteNotification::new(
+                "hook",
+                serde_json::json!({ "name": "after_file_save" }),
+            )),
         ];
 
         for variant in variants {
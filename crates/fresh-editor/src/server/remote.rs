@@ -0,0 +1,101 @@
+//! Remote-control connections (`fresh --remote`).
+//!
+//! The session daemon and the direct-mode control listener both route
+//! `ClientControl::Remote` requests through [`RemoteClients`]. It answers
+//! `subscribe` / `unsubscribe` itself, keeping each connection's hook
+//! subscriptions, and hands every other method to
+//! [`Editor::handle_remote_request`]. Subscribed hooks come back out of
+//! [`RemoteClients::notifications`] as `hook` notifications, one per
+//! subscribed connection.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::app::Editor;
+use crate::server::protocol::{RemoteNotification, RemoteRequest, RemoteResponse};
+
+/// Params of `subscribe` / `unsubscribe`
+#[derive(Deserialize)]
+struct SubscribeParams {
+    /// Hook names, as passed to `editor.on` in plugins
+    hooks: Vec<String>,
+}
+
+/// Hook subscriptions of the remote-control connections
+#[derive(Debug, Default)]
+pub struct RemoteClients {
+    /// Connection id → hooks it subscribed to
+    subscriptions: HashMap<u64, HashSet<String>>,
+}
+
+impl RemoteClients {
+    /// Answer `request` from connection `conn`.
+    pub fn handle(
+        &mut self,
+        editor: &mut Editor,
+        conn: u64,
+        request: &RemoteRequest,
+    ) -> RemoteResponse {
+        let subscribe = match request.method.as_str() {
+            "subscribe" => true,
+            "unsubscribe" => false,
+            _ => return editor.handle_remote_request(request),
+        };
+        let result = request.parse_params().map(|params: SubscribeParams| {
+            let hooks = self.subscriptions.entry(conn).or_default();
+            if subscribe {
+                hooks.extend(params.hooks);
+            } else {
+                for hook in &params.hooks {
+                    hooks.remove(hook);
+                }
+                if hooks.is_empty() {
+                    self.subscriptions.remove(&conn);
+                }
+            }
+            self.sync(editor);
+            json!(true)
+        });
+        RemoteResponse::new(request.id.clone(), result)
+    }
+
+    /// Drop the subscriptions of connection `conn` once it's gone.
+    pub fn remove(&mut self, editor: &Editor, conn: u64) {
+        if self.subscriptions.remove(&conn).is_some() {
+            self.sync(editor);
+        }
+    }
+
+    /// Whether any connection subscribed to a hook
+    pub fn has_subscriptions(&self) -> bool {
+        !self.subscriptions.is_empty()
+    }
+
+    /// Notifications for the subscribed hooks fired since the last call,
+    /// with the connection to send each to.
+    pub fn notifications(&self, editor: &Editor) -> Vec<(u64, RemoteNotification)> {
+        if self.subscriptions.is_empty() {
+            return Vec::new();
+        }
+        let mut out = Vec::new();
+        for (name, args) in editor.take_remote_hook_events() {
+            let notification =
+                RemoteNotification::new("hook", json!({ "name": name, "args": args }));
+            for (conn, hooks) in &self.subscriptions {
+                if hooks.contains(&name) {
+                    out.push((*conn, notification.clone()));
+                }
+            }
+        }
+        out
+    }
+
+    /// Tap the hooks any connection subscribed to. Called again for a
+    /// rebuilt editor.
+    pub fn sync(&self, editor: &Editor) {
+        let hooks = self.subscriptions.values().flatten().cloned().collect();
+        editor.set_remote_hook_subscriptions(hooks);
+    }
+}
@@ -16,7 +16,8 @@ use crate::server::capture_backend::{terminal_setup_sequences, terminal_teardown
 use crate::server::input_parser::InputParser;
 use crate::server::ipc::{ServerConnection, ServerListener, SocketPaths};
 use crate::server::protocol::{
-    ClientControl, ServerControl, ServerHello, TermSize, VersionMismatch, PROTOCOL_VERSION,
+    ClientControl, RemoteError, RemoteResponse, ServerControl, ServerHello, TermSize,
+    VersionMismatch, PROTOCOL_VERSION,
 };
 use crossterm::event::Event;
 
@@ -385,6 +386,19 @@ impl Server {
                     client.id
                 );
             }
+            ClientControl::Remote(request) => {
+                // This runner doesn't have an editor to drive
+                let response = ServerControl::RemoteResponse(RemoteResponse::new(
+                    request.id,
+                    Err(RemoteError::new(
+                        RemoteError::INTERNAL_ERROR,
+                        "no editor is running",
+                    )),
+                ));
+                let msg = serde_json::to_string(&response)
+                    .map_err(|e| io::Error::other(e.to_string()))?;
+                client.conn.write_control(&msg)?;
+            }
         }
         Ok(())
     }
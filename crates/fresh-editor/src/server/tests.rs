@@ -1051,6 +1051,101 @@ mod integration_tests {
         result
     }
 
+    /// Parse accumulated ANSI output of a `rows` x `cols` client through
+    /// a VT100 terminal emulator and return its screen rows.
+    fn vt100_screen_rows(output: &[u8], rows: u16, cols: u16) -> Vec<String> {
+        let mut parser = vt100::Parser::new(rows, cols, 0);
+        parser.process(output);
+        parser.screen().rows(0, cols).collect()
+    }
+
+    /// Read from `conn` until the screen of a `rows` x `cols` client
+    /// shows `needle`.
+    fn read_until_screen_contains(
+        conn: &ClientConnection,
+        output: &mut Vec<u8>,
+        rows: u16,
+        cols: u16,
+        needle: &str,
+    ) {
+        let mut buf = [0u8; 8192];
+        loop {
+            if vt100_screen_rows(output, rows, cols)
+                .iter()
+                .any(|row| row.contains(needle))
+            {
+                return;
+            }
+            match conn.data.try_read(&mut buf) {
+                Ok(0) => return,
+                Ok(n) => output.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(5));
+                }
+                Err(_) => return,
+            }
+        }
+    }
+
+    /// Two clients of one daemon each get their own view: the second
+    /// renders at its own size, and a buffer it opens stays out of the
+    /// first client's split, which keeps typing into its own buffer.
+    #[test]
+    fn test_clients_have_independent_views() {
+        let (conn1, mut output1, shutdown_handle, server_handle, socket_paths, temp_dir) =
+            setup_editor_server_e2e("independent-views");
+
+        conn1.write_data(b"FIRST").unwrap();
+        read_until_screen_contains(&conn1, &mut output1, 24, 80, "FIRST");
+
+        let conn2 =
+            ClientConnection::connect(&socket_paths).expect("Second client failed to connect");
+        let hello2 = ClientHello::new(TermSize::new(100, 30));
+        conn2
+            .write_control(&serde_json::to_string(&ClientControl::Hello(hello2)).unwrap())
+            .unwrap();
+        let response2 = conn2.read_control().unwrap().unwrap();
+        assert!(matches!(
+            serde_json::from_str::<ServerControl>(&response2).unwrap(),
+            ServerControl::Hello(_)
+        ));
+
+        // The second client sees the shared buffer, laid out for its own
+        // taller terminal.
+        let mut output2 = Vec::new();
+        read_until_screen_contains(&conn2, &mut output2, 30, 100, "FIRST");
+        let rows2 = vt100_screen_rows(&output2, 30, 100);
+        assert!(
+            rows2[24..].iter().any(|row| !row.trim().is_empty()),
+            "second client should be rendered at 100x30, got:\n{}",
+            rows2.join("\n")
+        );
+
+        // The second client opens a new buffer (Ctrl+N) and types into it.
+        conn2.write_data(b"\x0eSECOND").unwrap();
+        read_until_screen_contains(&conn2, &mut output2, 30, 100, "SECOND");
+
+        // The first client's split still shows its buffer, and its input
+        // lands there.
+        conn1.write_data(b"ONE").unwrap();
+        read_until_screen_contains(&conn1, &mut output1, 24, 80, "FIRSTONE");
+        let screen1 = vt100_screen_rows(&output1, 24, 80).join("\n");
+        assert!(
+            !screen1.contains("SECOND"),
+            "first client should not see the second client's buffer:\n{}",
+            screen1
+        );
+
+        drop(conn2);
+        teardown_editor_server_e2e(
+            conn1,
+            shutdown_handle,
+            server_handle,
+            socket_paths,
+            temp_dir,
+        );
+    }
+
     /// E2E regression test for issue #1089:
     /// ESC followed by mouse event should NOT insert mouse codes as text.
     ///
@@ -156,6 +156,12 @@ pub enum AsyncMessage {
         locations: Vec<Location>,
     },
 
+    /// LSP `prepare{Call,Type}Hierarchy` response, items as raw JSON
+    LspHierarchyPrepared {
+        request_id: u64,
+        items: Vec<serde_json::Value>,
+    },
+
     /// LSP signature help response
     LspSignatureHelp {
         request_id: u64,
@@ -181,6 +187,20 @@ pub enum AsyncMessage {
         edits: Vec<lsp_types::TextEdit>,
     },
 
+    /// LSP textDocument/onTypeFormatting response; empty when the server
+    /// had nothing to change or the request failed
+    LspOnTypeFormatting {
+        request_id: u64,
+        edits: Vec<lsp_types::TextEdit>,
+    },
+
+    /// LSP textDocument/linkedEditingRange response; `None` when there are
+    /// no linked ranges at the position or the request failed
+    LspLinkedEditingRanges {
+        request_id: u64,
+        ranges: Option<lsp_types::LinkedEditingRanges>,
+    },
+
     /// LSP textDocument/prepareRename response
     LspPrepareRename {
         request_id: u64,
@@ -214,6 +234,60 @@ pub enum AsyncMessage {
         ranges: Vec<FoldingRange>,
     },
 
+    /// LSP code lens response (textDocument/codeLens)
+    LspCodeLenses {
+        request_id: u64,
+        uri: String,
+        lenses: Vec<lsp_types::CodeLens>,
+    },
+
+    /// LSP codeLens/resolve response
+    LspCodeLensResolved {
+        request_id: u64,
+        lens: Result<lsp_types::CodeLens, String>,
+    },
+
+    /// LSP document link response (textDocument/documentLink)
+    LspDocumentLinks {
+        request_id: u64,
+        uri: String,
+        links: Vec<lsp_types::DocumentLink>,
+    },
+
+    /// LSP documentLink/resolve response
+    LspDocumentLinkResolved {
+        request_id: u64,
+        link: Result<lsp_types::DocumentLink, String>,
+    },
+
+    /// LSP document color response (textDocument/documentColor)
+    LspDocumentColors {
+        request_id: u64,
+        uri: String,
+        colors: Vec<lsp_types::ColorInformation>,
+    },
+
+    /// LSP color presentation response (textDocument/colorPresentation);
+    /// empty when the request failed
+    LspColorPresentations {
+        request_id: u64,
+        presentations: Vec<lsp_types::ColorPresentation>,
+    },
+
+    /// LSP answer to a workspace/will{Create,Rename,Delete}Files request:
+    /// edits to make before the files change, if any
+    LspWillFileOperation {
+        request_id: u64,
+        edit: Result<Option<lsp_types::WorkspaceEdit>, String>,
+    },
+
+    /// LSP selection range response (textDocument/selectionRange), one
+    /// chain per requested position; empty when the request failed
+    LspSelectionRanges {
+        request_id: u64,
+        ranges: Vec<lsp_types::SelectionRange>,
+    },
+
     /// LSP semantic tokens response (full, full/delta, or range)
     LspSemanticTokens {
         request_id: u64,
@@ -235,6 +309,10 @@ pub enum AsyncMessage {
     /// in file B, which the user never edited so was never otherwise re-pulled).
     LspInlayHintRefresh { language: String },
 
+    /// LSP server requests a code-lens refresh (workspace/codeLens/refresh).
+    /// Client should re-pull code lenses for all open documents.
+    LspCodeLensRefresh { language: String },
+
     /// LSP server requests a semantic-tokens refresh
     /// (workspace/semanticTokens/refresh). Client should re-pull semantic
     /// tokens for all open documents.
@@ -254,7 +332,8 @@ pub enum AsyncMessage {
         registrations: Vec<(String, Option<Value>)>,
     },
 
-    /// File changed externally (future: file watching)
+    /// A path changed on the filesystem, as reported by a watch on a
+    /// directory of the window (see `file_watcher::WatchedDirs`).
     FileChanged { path: String },
 
     /// Git status updated (future: git integration)
@@ -384,6 +463,39 @@ pub enum AsyncMessage {
         result: Result<Value, String>,
     },
 
+    /// Debug adapter replied to a request. `tag` is the editor-side id the
+    /// request was issued with (not the DAP `seq`).
+    DapResponse {
+        session_id: u64,
+        tag: u64,
+        command: String,
+        result: Result<Value, String>,
+    },
+
+    /// Debug adapter event (`stopped`, `output`, `terminated`, ...)
+    DapEvent {
+        session_id: u64,
+        event: String,
+        body: Value,
+    },
+
+    /// Debug adapter process exited or could not be started
+    DapSessionEnded {
+        session_id: u64,
+        error: Option<String>,
+    },
+
+    /// A line of output (stdout or stderr) from a running task
+    TaskOutput { run_id: u64, line: String },
+
+    /// Task process exited, was killed (`exit_code: None`) or could not
+    /// be started (`error`)
+    TaskFinished {
+        run_id: u64,
+        exit_code: Option<i32>,
+        error: Option<String>,
+    },
+
     /// Plugin process completed with output
     PluginProcessOutput {
         /// Unique ID for this process (to match with callback)
@@ -481,6 +593,18 @@ impl PathChangeKind {
             PathChangeKind::Other => "other",
         }
     }
+
+    /// Inverse of [`Self::as_str`], for kinds reported by a remote
+    /// agent's `watch`. Unknown strings are `Other`.
+    pub fn parse(kind: &str) -> Self {
+        match kind {
+            "modify" => PathChangeKind::Modify,
+            "create" => PathChangeKind::Create,
+            "delete" => PathChangeKind::Delete,
+            "rename" => PathChangeKind::Rename,
+            _ => PathChangeKind::Other,
+        }
+    }
 }
 
 /// LSP progress value types
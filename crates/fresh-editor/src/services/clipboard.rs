@@ -7,9 +7,11 @@
 //! - Supports copying HTML-formatted text for rich text editors
 //! - Gracefully falls back to internal clipboard if system clipboard is unavailable
 //! - Respects clipboard configuration to disable problematic methods
+//! - Keeps a bounded history of copied text (the kill ring)
 
 use crossterm::clipboard::CopyToClipboard;
 use crossterm::execute;
+use std::collections::VecDeque;
 use std::io::{stdout, Write};
 use std::sync::Mutex;
 
@@ -177,11 +179,31 @@ pub struct PendingClipboard {
     pub use_system_clipboard: bool,
 }
 
+/// One entry of the clipboard history.
+///
+/// A copy from several cursors is kept as a single entry with one chunk per
+/// cursor, so pasting it with as many cursors gives each its chunk back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardEntry {
+    pub chunks: Vec<String>,
+}
+
+impl ClipboardEntry {
+    /// The entry as plain text, chunks separated by newlines.
+    pub fn text(&self) -> String {
+        self.chunks.join("\n")
+    }
+}
+
 /// Clipboard manager that handles both internal and system clipboard
 #[derive(Debug, Clone, Default)]
 pub struct Clipboard {
     /// Internal clipboard content (always available)
     internal: String,
+    /// Copied entries, newest first
+    history: VecDeque<ClipboardEntry>,
+    /// Maximum number of history entries (0 disables the history)
+    history_size: usize,
     /// When true, paste() uses internal clipboard only (for testing)
     internal_only: bool,
     /// When true, OSC 52 escape sequences are used for clipboard copy
@@ -200,6 +222,8 @@ impl Clipboard {
     pub fn new() -> Self {
         Self {
             internal: String::new(),
+            history: VecDeque::new(),
+            history_size: crate::config::ClipboardConfig::default().history_size,
             internal_only: false,
             use_osc52: true,
             use_system_clipboard: true,
@@ -213,6 +237,8 @@ impl Clipboard {
     pub fn apply_config(&mut self, config: &crate::config::ClipboardConfig) {
         self.use_osc52 = config.use_osc52;
         self.use_system_clipboard = config.use_system_clipboard;
+        self.history_size = config.history_size;
+        self.history.truncate(self.history_size);
     }
 
     /// Enable internal-only mode (for testing)
@@ -254,6 +280,7 @@ impl Clipboard {
     /// Returns true if successful, false otherwise.
     pub fn copy_html(&mut self, html: &str, plain_text: &str) -> bool {
         self.internal = plain_text.to_string();
+        self.push_history(vec![plain_text.to_string()]);
 
         if !self.use_system_clipboard {
             return false;
@@ -296,7 +323,16 @@ impl Clipboard {
     ///
     /// Methods can be disabled via clipboard configuration.
     pub fn copy(&mut self, text: String) {
+        self.copy_chunks(vec![text]);
+    }
+
+    /// Copy the text of several cursors as one history entry
+    ///
+    /// The system clipboard receives the chunks joined by newlines.
+    pub fn copy_chunks(&mut self, chunks: Vec<String>) {
+        let text = chunks.join("\n");
         self.internal = text.clone();
+        self.push_history(chunks);
 
         // In session mode, the server process has no terminal or display server.
         // Queue the text for delivery to clients via a control message instead.
@@ -359,6 +395,55 @@ impl Clipboard {
         }
     }
 
+    /// Add an entry to the front of the history, moving an identical
+    /// older entry instead of keeping a duplicate.
+    fn push_history(&mut self, chunks: Vec<String>) {
+        if self.history_size == 0 || chunks.iter().all(|c| c.is_empty()) {
+            return;
+        }
+        let entry = ClipboardEntry { chunks };
+        self.history.retain(|e| *e != entry);
+        self.history.push_front(entry);
+        self.history.truncate(self.history_size);
+    }
+
+    /// Find the history entry holding `text`, which is about to be pasted.
+    ///
+    /// Text that was copied outside the editor is added as the newest
+    /// entry first. Returns the entry's index, or `None` when the history
+    /// is disabled.
+    pub fn record_paste(&mut self, text: &str) -> Option<usize> {
+        if let Some(index) = self.history.iter().position(|e| e.text() == text) {
+            return Some(index);
+        }
+        self.push_history(vec![text.to_string()]);
+        (!self.history.is_empty()).then_some(0)
+    }
+
+    /// Entries of the clipboard history, newest first
+    pub fn history(&self) -> impl ExactSizeIterator<Item = &ClipboardEntry> {
+        self.history.iter()
+    }
+
+    /// The history entry at `index` (0 is the newest)
+    pub fn history_entry(&self, index: usize) -> Option<&ClipboardEntry> {
+        self.history.get(index)
+    }
+
+    /// Number of entries in the clipboard history
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Replace the history with saved entries (newest first), e.g. from a
+    /// workspace file
+    pub fn set_history(&mut self, entries: Vec<Vec<String>>) {
+        self.history.clear();
+        for chunks in entries.into_iter().rev() {
+            self.push_history(chunks);
+        }
+    }
+
     /// Check if clipboard is empty (checks both internal and system)
     pub fn is_empty(&self) -> bool {
         if !self.internal.is_empty() {
@@ -400,6 +485,7 @@ mod tests {
         let config = crate::config::ClipboardConfig {
             use_osc52: false,
             use_system_clipboard: true,
+            ..Default::default()
         };
         clipboard.apply_config(&config);
         assert!(!clipboard.use_osc52);
@@ -412,6 +498,7 @@ mod tests {
         let config = crate::config::ClipboardConfig {
             use_osc52: true,
             use_system_clipboard: false,
+            ..Default::default()
         };
         clipboard.apply_config(&config);
         assert!(clipboard.use_osc52);
@@ -424,10 +511,66 @@ mod tests {
         let config = crate::config::ClipboardConfig {
             use_osc52: false,
             use_system_clipboard: false,
+            ..Default::default()
         };
         clipboard.apply_config(&config);
 
         clipboard.copy("internal only".to_string());
         assert_eq!(clipboard.get_internal(), "internal only");
     }
+
+    fn history_texts(clipboard: &Clipboard) -> Vec<String> {
+        clipboard.history().map(ClipboardEntry::text).collect()
+    }
+
+    #[test]
+    fn test_clipboard_history_is_bounded_and_deduplicated() {
+        let mut clipboard = Clipboard::new();
+        clipboard.apply_config(&crate::config::ClipboardConfig {
+            use_osc52: false,
+            use_system_clipboard: false,
+            history_size: 3,
+            ..Default::default()
+        });
+
+        for text in ["one", "two", "three", "four"] {
+            clipboard.copy(text.to_string());
+        }
+        assert_eq!(history_texts(&clipboard), vec!["four", "three", "two"]);
+
+        // Copying an older entry again moves it to the front.
+        clipboard.copy("two".to_string());
+        assert_eq!(history_texts(&clipboard), vec!["two", "four", "three"]);
+
+        // Empty copies are not recorded.
+        clipboard.copy(String::new());
+        assert_eq!(clipboard.history_len(), 3);
+    }
+
+    #[test]
+    fn test_clipboard_history_keeps_cursor_chunks() {
+        let mut clipboard = Clipboard::new();
+        clipboard.apply_config(&crate::config::ClipboardConfig {
+            use_osc52: false,
+            use_system_clipboard: false,
+            ..Default::default()
+        });
+
+        clipboard.copy_chunks(vec!["a\nb".to_string(), "c".to_string()]);
+        assert_eq!(clipboard.get_internal(), "a\nb\nc");
+        assert_eq!(
+            clipboard.history_entry(0).unwrap().chunks,
+            vec!["a\nb", "c"]
+        );
+
+        // Pasting text copied by the editor finds its entry; text copied
+        // elsewhere becomes the newest entry.
+        assert_eq!(clipboard.record_paste("a\nb\nc"), Some(0));
+        assert_eq!(clipboard.record_paste("external"), Some(0));
+        assert_eq!(history_texts(&clipboard), vec!["external", "a\nb\nc"]);
+        assert_eq!(clipboard.record_paste("a\nb\nc"), Some(1));
+
+        clipboard.set_history(vec![vec!["x".to_string()], vec!["y".to_string()]]);
+        assert_eq!(history_texts(&clipboard), vec!["x", "y"]);
+    }
 }
@@ -0,0 +1,149 @@
+//! Conventional Commit type completion for commit messages.
+//!
+//! In `git-commit` buffers, typing at the start of the subject line offers
+//! the [Conventional Commits](https://www.conventionalcommits.org) types,
+//! e.g. `fe` completes to `feat: `.
+
+use super::provider::{
+    smart_case_matches, CompletionCandidate, CompletionContext, CompletionProvider,
+    CompletionSourceId, ProviderResult,
+};
+
+/// Language id of commit message buffers (`COMMIT_EDITMSG` and friends).
+const GIT_COMMIT_LANGUAGE: &str = "git-commit";
+
+/// Rank above words picked up from the buffer.
+const TYPE_SCORE: i64 = 1_000_000;
+
+/// Commit types and their descriptions, in the order they are offered.
+const COMMIT_TYPES: &[(&str, &str)] = &[
+    ("feat", "A new feature"),
+    ("fix", "A bug fix"),
+    ("docs", "Documentation only changes"),
+    ("style", "Formatting, no code change"),
+    ("refactor", "Neither fixes a bug nor adds a feature"),
+    ("perf", "A performance improvement"),
+    ("test", "Adding or correcting tests"),
+    ("build", "Build system or dependency changes"),
+    ("ci", "CI configuration changes"),
+    ("chore", "Other changes that don't modify sources or tests"),
+    ("revert", "Reverts a previous commit"),
+];
+
+/// Completion provider for Conventional Commit types.
+#[derive(Debug, Default)]
+pub struct ConventionalCommitProvider;
+
+impl ConventionalCommitProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CompletionProvider for ConventionalCommitProvider {
+    fn id(&self) -> CompletionSourceId {
+        CompletionSourceId("conventional_commits".into())
+    }
+
+    fn display_name(&self) -> &str {
+        "Conventional Commits"
+    }
+
+    fn is_enabled(&self, ctx: &CompletionContext) -> bool {
+        // Only the first word of the subject line is a commit type.
+        ctx.language_id.as_deref() == Some(GIT_COMMIT_LANGUAGE)
+            && ctx.word_start_byte == 0
+            && !ctx.prefix.is_empty()
+    }
+
+    fn provide(&self, ctx: &CompletionContext, _buffer_window: &[u8]) -> ProviderResult {
+        let candidates = COMMIT_TYPES
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, _))| {
+                smart_case_matches(name, &ctx.prefix, ctx.prefix_has_uppercase)
+            })
+            .map(|(i, (name, description))| CompletionCandidate {
+                label: (*name).to_string(),
+                insert_text: Some(format!("{name}: ")),
+                detail: Some((*description).to_string()),
+                icon: Some("t".to_string()),
+                // Keep the conventional order among matches.
+                score: TYPE_SCORE - i as i64,
+                source: None,
+                is_snippet: false,
+                provider_data: None,
+            })
+            .collect();
+        ProviderResult::Ready(candidates)
+    }
+
+    fn priority(&self) -> u32 {
+        10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(prefix: &str, word_start_byte: usize, language: Option<&str>) -> CompletionContext {
+        let cursor = word_start_byte + prefix.len();
+        CompletionContext {
+            prefix: prefix.into(),
+            cursor_byte: cursor,
+            word_start_byte,
+            buffer_len: cursor,
+            is_large_file: false,
+            scan_range: 0..cursor,
+            viewport_top_byte: 0,
+            viewport_bottom_byte: cursor,
+            language_id: language.map(str::to_string),
+            word_chars_extra: String::new(),
+            prefix_has_uppercase: prefix.chars().any(|c| c.is_uppercase()),
+            other_buffers: Vec::new(),
+        }
+    }
+
+    fn labels(ctx: &CompletionContext) -> Vec<String> {
+        match ConventionalCommitProvider::new().provide(ctx, b"") {
+            ProviderResult::Ready(candidates) => candidates.into_iter().map(|c| c.label).collect(),
+            _ => panic!("expected Ready"),
+        }
+    }
+
+    #[test]
+    fn test_enabled_only_at_start_of_commit_message() {
+        let provider = ConventionalCommitProvider::new();
+        assert!(provider.is_enabled(&ctx("fe", 0, Some("git-commit"))));
+        assert!(!provider.is_enabled(&ctx("fe", 0, Some("rust"))));
+        assert!(!provider.is_enabled(&ctx("fe", 0, None)));
+        // Later words and lines are free text.
+        assert!(!provider.is_enabled(&ctx("fe", 12, Some("git-commit"))));
+        assert!(!provider.is_enabled(&ctx("", 0, Some("git-commit"))));
+    }
+
+    #[test]
+    fn test_provide_types_matching_prefix() {
+        assert_eq!(
+            labels(&ctx("f", 0, Some("git-commit"))),
+            vec!["feat", "fix"]
+        );
+        assert_eq!(
+            labels(&ctx("re", 0, Some("git-commit"))),
+            vec!["refactor", "revert"]
+        );
+        assert_eq!(
+            labels(&ctx("xyz", 0, Some("git-commit"))),
+            Vec::<String>::new()
+        );
+
+        let ProviderResult::Ready(candidates) =
+            ConventionalCommitProvider::new().provide(&ctx("fea", 0, Some("git-commit")), b"")
+        else {
+            panic!("expected Ready");
+        };
+        assert_eq!(candidates[0].insert_text.as_deref(), Some("feat: "));
+        assert_eq!(candidates[0].detail.as_deref(), Some("A new feature"));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module provides a framework for composing multiple completion sources
 //! (providers) into a single, ranked completion experience. It ships with
-//! two built-in providers and supports both Rust-native and TypeScript plugin
+//! three built-in providers and supports both Rust-native and TypeScript plugin
 //! providers.
 //!
 //! # Architecture
@@ -31,7 +31,9 @@
 //! | Fuzzy matching / Smith-Waterman scoring | Rust | O(mn) matrix work needs SIMD-friendly code |
 //! | LSP bridge (send request, receive response) | Rust | Already integrated, async I/O via tokio |
 //! | Static index (ctags-style) lookup | Rust | Sub-ms binary search on a sorted Vec |
-//! | Custom snippet / dictionary providers | TypeScript | Extensibility; content is small, latency tolerant |
+//! | User snippet files (`snippets/` in the config dir) | Rust | Parsed once; feeds native snippet sessions |
+//! | Conventional Commit types in commit messages | Rust | Static list, gated on the `git-commit` language |
+//! | Custom dictionary providers | TypeScript | Extensibility; content is small, latency tolerant |
 //! | Provider registration / lifecycle | TypeScript API | Plugins call `registerCompletionProvider()` |
 //! | Ghost-text rendering decision | Rust (view layer) | Must be frame-synchronous |
 //!
@@ -47,9 +49,11 @@
 //! impossible for a provider to trigger an expensive full-buffer scan.
 
 pub mod buffer_words;
+pub mod conventional_commits;
 pub mod dabbrev;
 pub mod provider;
 pub mod service;
+pub mod snippets;
 
 // Re-export the main types that the Editor needs.
 pub use provider::{
@@ -57,3 +61,4 @@ pub use provider::{
     OtherBufferSlice, ProviderResult,
 };
 pub use service::CompletionService;
+pub use snippets::SnippetProvider;
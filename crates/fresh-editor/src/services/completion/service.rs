@@ -6,7 +6,8 @@
 //!
 //! ## Provider lifecycle
 //!
-//! 1. Built-in providers (dabbrev, buffer-words) are registered at startup.
+//! 1. Built-in providers (dabbrev, buffer-words, Conventional Commit types)
+//!    are registered at startup.
 //! 2. The LSP provider is always registered but returns `Pending` — its
 //!    results arrive asynchronously and are fed in via `supply_async_results`.
 //! 3. TypeScript plugins register providers dynamically via the plugin API.
@@ -19,6 +20,7 @@
 //! receive a pre-sliced `&[u8]` and never touch the `Buffer` directly.
 
 use super::buffer_words::BufferWordProvider;
+use super::conventional_commits::ConventionalCommitProvider;
 use super::dabbrev::DabbrevProvider;
 use super::provider::{
     CompletionCandidate, CompletionContext, CompletionProvider, CompletionSourceId, ProviderResult,
@@ -40,6 +42,7 @@ impl CompletionService {
         };
         svc.register(Box::new(BufferWordProvider::new()));
         svc.register(Box::new(DabbrevProvider::new()));
+        svc.register(Box::new(ConventionalCommitProvider::new()));
         svc
     }
 
@@ -0,0 +1,347 @@
+//! User snippet completion provider.
+//!
+//! Offers snippets from the `snippets/` directory in the user config dir,
+//! using the VS Code snippet file format so existing collections can be
+//! copied over unchanged:
+//!
+//! ```jsonc
+//! {
+//!   "For loop": {
+//!     "prefix": ["for", "fori"],
+//!     "body": ["for ${1:i} in ${2:0..n} {", "\t$0", "}"],
+//!     "description": "Range for loop",
+//!   },
+//! }
+//! ```
+//!
+//! # Scoping
+//!
+//! - `<language>.json` applies to that language only (`rust.json`).
+//! - `*.code-snippets` applies to the languages listed in each snippet's
+//!   comma-separated `"scope"`, or to every language when it has none.
+//!
+//! Files are read once when the window is created. Comments and trailing
+//! commas are accepted; invalid files and entries are skipped with a warning.
+
+use std::path::Path;
+
+use super::provider::{
+    case_mismatch_penalty, smart_case_matches, CompletionCandidate, CompletionContext,
+    CompletionProvider, CompletionSourceId, ProviderResult,
+};
+
+/// Extension of multi-language snippet files.
+const GLOBAL_SNIPPETS_EXTENSION: &str = "code-snippets";
+
+/// Base score: snippets are typed on purpose, so they rank above words
+/// picked up from the buffer.
+const SNIPPET_SCORE: i64 = 1_000_000;
+
+/// One snippet definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserSnippet {
+    /// Key of the snippet in its file.
+    pub name: String,
+    /// Words that trigger the snippet.
+    pub prefixes: Vec<String>,
+    /// Snippet text in LSP snippet syntax.
+    pub body: String,
+    pub description: Option<String>,
+    /// Language ids the snippet applies to; `None` means all.
+    pub scope: Option<Vec<String>>,
+}
+
+impl UserSnippet {
+    fn applies_to(&self, language: Option<&str>) -> bool {
+        match (&self.scope, language) {
+            (None, _) => true,
+            (Some(scope), Some(language)) => scope.iter().any(|s| s == language),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Completion provider for user snippet files.
+pub struct SnippetProvider {
+    snippets: Vec<UserSnippet>,
+}
+
+impl SnippetProvider {
+    pub fn new(snippets: Vec<UserSnippet>) -> Self {
+        Self { snippets }
+    }
+
+    /// Read every snippet file in `dir`. A missing directory yields an
+    /// empty provider.
+    pub fn load(dir: &Path) -> Self {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Self::new(Vec::new());
+        };
+        let mut paths: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+        paths.sort();
+
+        let mut snippets = Vec::new();
+        for path in paths {
+            let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+                continue;
+            };
+            let language = match extension {
+                "json" => path.file_stem().and_then(|s| s.to_str()),
+                GLOBAL_SNIPPETS_EXTENSION => None,
+                _ => continue,
+            };
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    tracing::warn!("Failed to read snippet file {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            match parse_snippet_file(&content, language) {
+                Ok(parsed) => snippets.extend(parsed),
+                Err(e) => {
+                    tracing::warn!("Failed to parse snippet file {}: {}", path.display(), e)
+                }
+            }
+        }
+        tracing::debug!(
+            "Loaded {} user snippets from {}",
+            snippets.len(),
+            dir.display()
+        );
+        Self::new(snippets)
+    }
+}
+
+/// Parse a snippet file. `language` scopes every snippet in a
+/// `<language>.json` file; pass `None` for `.code-snippets` files, whose
+/// snippets carry their own `"scope"`.
+pub fn parse_snippet_file(
+    content: &str,
+    language: Option<&str>,
+) -> Result<Vec<UserSnippet>, String> {
+    let value = jsonc_parser::parse_to_serde_value(content, &Default::default())
+        .map_err(|e| e.to_string())?;
+    let serde_json::Value::Object(entries) = value else {
+        return Err("expected an object of snippets".to_string());
+    };
+
+    let mut snippets = Vec::new();
+    for (name, entry) in entries {
+        let Some(prefixes) = entry.get("prefix").and_then(string_or_lines) else {
+            tracing::warn!("Snippet '{}' has no prefix, skipping", name);
+            continue;
+        };
+        let Some(body) = entry.get("body").and_then(string_or_lines) else {
+            tracing::warn!("Snippet '{}' has no body, skipping", name);
+            continue;
+        };
+        let scope = match language {
+            Some(language) => Some(vec![language.to_string()]),
+            None => entry.get("scope").and_then(|s| s.as_str()).map(|s| {
+                s.split(',')
+                    .map(|l| l.trim().to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect()
+            }),
+        };
+        snippets.push(UserSnippet {
+            name,
+            prefixes,
+            body: body.join("\n"),
+            description: entry
+                .get("description")
+                .and_then(|d| d.as_str())
+                .map(str::to_string),
+            scope,
+        });
+    }
+    Ok(snippets)
+}
+
+/// A string or an array of strings, as `prefix` and `body` accept either.
+fn string_or_lines(value: &serde_json::Value) -> Option<Vec<String>> {
+    match value {
+        serde_json::Value::String(s) => Some(vec![s.clone()]),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    }
+}
+
+impl CompletionProvider for SnippetProvider {
+    fn id(&self) -> CompletionSourceId {
+        CompletionSourceId("snippets".into())
+    }
+
+    fn display_name(&self) -> &str {
+        "Snippets"
+    }
+
+    fn is_enabled(&self, ctx: &CompletionContext) -> bool {
+        !ctx.prefix.is_empty() && !self.snippets.is_empty()
+    }
+
+    fn provide(&self, ctx: &CompletionContext, _buffer_window: &[u8]) -> ProviderResult {
+        let language = ctx.language_id.as_deref();
+        let candidates = self
+            .snippets
+            .iter()
+            .filter(|snippet| snippet.applies_to(language))
+            .flat_map(|snippet| {
+                snippet
+                    .prefixes
+                    .iter()
+                    .filter(|prefix| {
+                        smart_case_matches(prefix, &ctx.prefix, ctx.prefix_has_uppercase)
+                    })
+                    .map(move |prefix| {
+                        let mut score = SNIPPET_SCORE
+                            + case_mismatch_penalty(prefix, &ctx.prefix, ctx.prefix_has_uppercase);
+                        // Shorter prefixes are closer to what was typed.
+                        score -= prefix.len().saturating_sub(ctx.prefix.len()) as i64;
+                        CompletionCandidate {
+                            label: prefix.clone(),
+                            insert_text: Some(snippet.body.clone()),
+                            detail: Some(
+                                snippet
+                                    .description
+                                    .clone()
+                                    .unwrap_or_else(|| snippet.name.clone()),
+                            ),
+                            icon: Some("s".to_string()),
+                            score,
+                            source: None,
+                            is_snippet: true,
+                            provider_data: None,
+                        }
+                    })
+            })
+            .collect();
+        ProviderResult::Ready(candidates)
+    }
+
+    fn priority(&self) -> u32 {
+        10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(prefix: &str, language: Option<&str>) -> CompletionContext {
+        CompletionContext {
+            prefix: prefix.into(),
+            cursor_byte: prefix.len(),
+            word_start_byte: 0,
+            buffer_len: prefix.len(),
+            is_large_file: false,
+            scan_range: 0..prefix.len(),
+            viewport_top_byte: 0,
+            viewport_bottom_byte: prefix.len(),
+            language_id: language.map(str::to_string),
+            word_chars_extra: String::new(),
+            prefix_has_uppercase: prefix.chars().any(|c| c.is_uppercase()),
+            other_buffers: Vec::new(),
+        }
+    }
+
+    fn labels(provider: &SnippetProvider, ctx: &CompletionContext) -> Vec<String> {
+        match provider.provide(ctx, b"") {
+            ProviderResult::Ready(candidates) => candidates.into_iter().map(|c| c.label).collect(),
+            _ => panic!("expected Ready"),
+        }
+    }
+
+    #[test]
+    fn test_parse_language_file_with_comments() {
+        let content = r#"{
+            // Loops
+            "For loop": {
+                "prefix": ["for", "fori"],
+                "body": ["for ${1:i} in ${2:0..n} {", "\t$0", "}"],
+                "description": "Range for loop",
+            },
+            "Broken": { "body": "no prefix" },
+        }"#;
+        let snippets = parse_snippet_file(content, Some("rust")).unwrap();
+        assert_eq!(
+            snippets,
+            vec![UserSnippet {
+                name: "For loop".into(),
+                prefixes: vec!["for".into(), "fori".into()],
+                body: "for ${1:i} in ${2:0..n} {\n\t$0\n}".into(),
+                description: Some("Range for loop".into()),
+                scope: Some(vec!["rust".into()]),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_global_file_scope() {
+        let content = r#"{
+            "Log": { "prefix": "log", "body": "console.log($1)", "scope": "javascript, typescript" },
+            "Date": { "prefix": "date", "body": "$CURRENT_DATE" }
+        }"#;
+        let snippets = parse_snippet_file(content, None).unwrap();
+        let scope = |name: &str| {
+            snippets
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.scope.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            scope("Log"),
+            Some(vec!["javascript".into(), "typescript".into()])
+        );
+        assert_eq!(scope("Date"), None);
+        assert!(parse_snippet_file("[1, 2]", None).is_err());
+    }
+
+    #[test]
+    fn test_provide_filters_by_prefix_and_language() {
+        let mut snippets = parse_snippet_file(
+            r#"{ "Fn": { "prefix": ["fn", "pfn"], "body": "fn $1() {}" } }"#,
+            Some("rust"),
+        )
+        .unwrap();
+        snippets.extend(
+            parse_snippet_file(
+                r#"{ "Fixme": { "prefix": "fixme", "body": "FIXME: $0" } }"#,
+                None,
+            )
+            .unwrap(),
+        );
+        let provider = SnippetProvider::new(snippets);
+
+        assert_eq!(
+            labels(&provider, &ctx("f", Some("rust"))),
+            vec!["fn", "fixme"]
+        );
+        assert_eq!(labels(&provider, &ctx("f", Some("python"))), vec!["fixme"]);
+        assert_eq!(labels(&provider, &ctx("pf", None)), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_candidates_are_snippets() {
+        let provider = SnippetProvider::new(
+            parse_snippet_file(
+                r#"{ "Main": { "prefix": "main", "body": "fn main() {\n\t$0\n}" } }"#,
+                Some("rust"),
+            )
+            .unwrap(),
+        );
+        let ProviderResult::Ready(candidates) = provider.provide(&ctx("ma", Some("rust")), b"")
+        else {
+            panic!("expected Ready");
+        };
+        assert_eq!(candidates.len(), 1);
+        assert!(candidates[0].is_snippet);
+        assert_eq!(candidates[0].detail.as_deref(), Some("Main"));
+        assert_eq!(candidates[0].icon.as_deref(), Some("s"));
+    }
+}
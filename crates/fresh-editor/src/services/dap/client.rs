@@ -0,0 +1,239 @@
+//! Debug adapter process handle
+//!
+//! [`DapHandle::spawn`] starts the adapter through the window's
+//! [`LongRunningSpawner`] (so container/remote authorities run the adapter
+//! next to the debuggee) and drives it from two tokio tasks:
+//!
+//! - a reader that turns adapter output into [`AsyncMessage::DapResponse`] /
+//!   [`AsyncMessage::DapEvent`] for the main loop, and
+//! - a writer that assigns DAP sequence numbers to outgoing requests.
+//!
+//! Requests are identified editor-side by a caller-chosen `tag`; the mapping
+//! from DAP `seq` to `tag` never leaves this module.
+
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::dap::protocol::{self, DapMessage};
+use crate::services::remote::LongRunningSpawner;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+/// How to start a debug adapter process.
+#[derive(Debug, Clone)]
+pub struct DapLaunch {
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub cwd: Option<PathBuf>,
+}
+
+enum DapCommand {
+    Request {
+        tag: u64,
+        command: String,
+        arguments: Option<Value>,
+    },
+    Kill,
+}
+
+/// Handle to a running debug adapter.
+///
+/// Dropping the handle closes the command channel, which stops the writer
+/// task and kills the adapter process.
+#[derive(Debug)]
+pub struct DapHandle {
+    session_id: u64,
+    command_tx: mpsc::UnboundedSender<DapCommand>,
+}
+
+impl DapHandle {
+    /// Spawn the adapter on `runtime`. Spawn failures are reported
+    /// asynchronously as [`AsyncMessage::DapSessionEnded`] with an error.
+    pub fn spawn(
+        runtime: &tokio::runtime::Handle,
+        session_id: u64,
+        launch: DapLaunch,
+        async_tx: std::sync::mpsc::Sender<AsyncMessage>,
+        spawner: Arc<dyn LongRunningSpawner>,
+    ) -> Self {
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        runtime.spawn(async move {
+            let error = run_session(session_id, launch, command_rx, async_tx.clone(), spawner)
+                .await
+                .err();
+            if let Some(ref error) = error {
+                tracing::warn!("Debug adapter session {} failed: {}", session_id, error);
+            }
+            #[allow(clippy::let_underscore_must_use)] // Receiver drop means editor shutdown
+            let _ = async_tx.send(AsyncMessage::DapSessionEnded { session_id, error });
+        });
+        Self {
+            session_id,
+            command_tx,
+        }
+    }
+
+    pub fn session_id(&self) -> u64 {
+        self.session_id
+    }
+
+    /// Queue a request. The reply arrives as [`AsyncMessage::DapResponse`]
+    /// carrying the same `tag`.
+    pub fn request(&self, tag: u64, command: &str, arguments: Option<Value>) -> Result<(), String> {
+        self.command_tx
+            .send(DapCommand::Request {
+                tag,
+                command: command.to_string(),
+                arguments,
+            })
+            .map_err(|_| "Debug adapter is not running".to_string())
+    }
+
+    /// Kill the adapter process without waiting for a `disconnect` reply.
+    pub fn kill(&self) {
+        // A closed channel means the session already ended
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.command_tx.send(DapCommand::Kill);
+    }
+}
+
+async fn run_session(
+    session_id: u64,
+    launch: DapLaunch,
+    mut command_rx: mpsc::UnboundedReceiver<DapCommand>,
+    async_tx: std::sync::mpsc::Sender<AsyncMessage>,
+    spawner: Arc<dyn LongRunningSpawner>,
+) -> Result<(), String> {
+    tracing::info!(
+        "Starting debug adapter: {} {}",
+        launch.command,
+        launch.args.join(" ")
+    );
+    let mut child = spawner
+        .spawn_stdio(
+            &launch.command,
+            &launch.args,
+            launch.env,
+            launch.cwd.as_deref(),
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to spawn debug adapter '{}': {}", launch.command, e))?;
+
+    let mut stdin = child
+        .take_stdin()
+        .ok_or_else(|| "Failed to get stdin".to_string())?;
+    let stdout = child
+        .take_stdout()
+        .ok_or_else(|| "Failed to get stdout".to_string())?;
+
+    if let Some(stderr) = child.take_stderr() {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                tracing::debug!("debug adapter stderr: {}", line);
+            }
+        });
+    }
+
+    // seq → tag for requests still awaiting a response.
+    let pending: Arc<Mutex<HashMap<i64, u64>>> = Arc::new(Mutex::new(HashMap::new()));
+    // Reverse requests (adapter → client) are answered by the writer so
+    // that it stays the only owner of the sequence counter.
+    let (reply_tx, mut reply_rx) = mpsc::unbounded_channel::<(i64, String)>();
+
+    let mut reader_task = {
+        let pending = pending.clone();
+        let async_tx = async_tx.clone();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(stdout);
+            loop {
+                let message = match protocol::read_message(&mut reader).await {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::debug!("Debug adapter reader stopped: {}", e);
+                        return;
+                    }
+                };
+                match message {
+                    DapMessage::Response {
+                        request_seq,
+                        success,
+                        command,
+                        message,
+                        body,
+                    } => {
+                        let Some(tag) = pending.lock().unwrap().remove(&request_seq) else {
+                            tracing::debug!("Unmatched DAP response for seq {}", request_seq);
+                            continue;
+                        };
+                        let result = if success {
+                            Ok(body.unwrap_or(Value::Null))
+                        } else {
+                            Err(message.unwrap_or_else(|| format!("{} failed", command)))
+                        };
+                        // Receiver drop means editor shutdown
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = async_tx.send(AsyncMessage::DapResponse {
+                            session_id,
+                            tag,
+                            command,
+                            result,
+                        });
+                    }
+                    DapMessage::Event { event, body } => {
+                        // Receiver drop means editor shutdown
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = async_tx.send(AsyncMessage::DapEvent {
+                            session_id,
+                            event,
+                            body: body.unwrap_or(Value::Null),
+                        });
+                    }
+                    DapMessage::Request { seq, command, .. } => {
+                        // `runInTerminal` / `startDebugging` are optional
+                        // capabilities we do not advertise; refuse politely.
+                        tracing::debug!("Refusing DAP reverse request '{}'", command);
+                        // The session loop is gone once it stops receiving
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = reply_tx.send((seq, command));
+                    }
+                }
+            }
+        })
+    };
+
+    let mut next_seq: i64 = 1;
+    let result = loop {
+        let outgoing = tokio::select! {
+            command = command_rx.recv() => match command {
+                Some(DapCommand::Request { tag, command, arguments }) => {
+                    let seq = next_seq;
+                    pending.lock().unwrap().insert(seq, tag);
+                    protocol::request(seq, &command, arguments)
+                }
+                Some(DapCommand::Kill) | None => break Ok(()),
+            },
+            Some((request_seq, command)) = reply_rx.recv() => {
+                protocol::response(next_seq, request_seq, &command, false, Some("not supported"))
+            }
+            _ = &mut reader_task => break Ok(()),
+        };
+        next_seq += 1;
+
+        if let Err(e) = stdin.write_all(&protocol::encode(&outgoing)).await {
+            break Err(format!("Failed to write to debug adapter: {}", e));
+        }
+        if let Err(e) = stdin.flush().await {
+            break Err(format!("Failed to write to debug adapter: {}", e));
+        }
+    };
+
+    reader_task.abort();
+    #[allow(clippy::let_underscore_must_use)] // Best-effort; the adapter may have exited already
+    let _ = child.kill().await;
+    result
+}
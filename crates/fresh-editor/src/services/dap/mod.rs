@@ -0,0 +1,11 @@
+//! Debug Adapter Protocol (DAP) client
+//!
+//! A debug adapter is a long-lived stdio process, much like an LSP server,
+//! that translates DAP requests into the native debugger of a language
+//! (lldb-dap, debugpy, delve, ...). This module owns the wire format and the
+//! process plumbing; session state and UI live in `app::debugger`.
+
+pub mod client;
+pub mod protocol;
+
+pub use client::{DapHandle, DapLaunch};
@@ -0,0 +1,321 @@
+//! Debug Adapter Protocol wire format.
+//!
+//! DAP frames messages exactly like LSP (a `Content-Length` header, a blank
+//! line, then a JSON body) but uses its own envelope: every message carries a
+//! `seq` and a `type` of `request`, `response` or `event`, and a response
+//! points back at its request through `request_seq` instead of a JSON-RPC id.
+//!
+//! Only the handful of payload shapes the editor itself interprets are typed
+//! here (stack frames, stopped events, breakpoints). Everything else is passed
+//! through as raw JSON so plugins can drive adapter-specific requests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+/// A message received from the debug adapter.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DapMessage {
+    /// A reverse request (adapter → client), e.g. `runInTerminal`.
+    Request {
+        seq: i64,
+        command: String,
+        #[serde(default)]
+        arguments: Option<Value>,
+    },
+    /// The reply to one of our requests.
+    Response {
+        request_seq: i64,
+        success: bool,
+        command: String,
+        /// Short machine-readable error (`"cancelled"`, `"notStopped"`, ...)
+        /// or a human-readable message when `success` is false.
+        #[serde(default)]
+        message: Option<String>,
+        #[serde(default)]
+        body: Option<Value>,
+    },
+    /// An unsolicited notification (`stopped`, `output`, `terminated`, ...).
+    Event {
+        event: String,
+        #[serde(default)]
+        body: Option<Value>,
+    },
+}
+
+/// Build a request envelope.
+pub fn request(seq: i64, command: &str, arguments: Option<Value>) -> Value {
+    let mut message = json!({
+        "seq": seq,
+        "type": "request",
+        "command": command,
+    });
+    if let Some(arguments) = arguments {
+        message["arguments"] = arguments;
+    }
+    message
+}
+
+/// Build a response envelope for a reverse request.
+pub fn response(
+    seq: i64,
+    request_seq: i64,
+    command: &str,
+    success: bool,
+    message: Option<&str>,
+) -> Value {
+    let mut response = json!({
+        "seq": seq,
+        "type": "response",
+        "request_seq": request_seq,
+        "command": command,
+        "success": success,
+    });
+    if let Some(message) = message {
+        response["message"] = Value::String(message.to_string());
+    }
+    response
+}
+
+/// Frame a message for the wire.
+pub fn encode(message: &Value) -> Vec<u8> {
+    let body = message.to_string();
+    let mut out = format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes();
+    out.extend_from_slice(body.as_bytes());
+    out
+}
+
+/// Read one framed message from the adapter's stdout.
+///
+/// Returns `Err` on EOF or on a malformed frame; the caller treats either as
+/// the adapter having gone away.
+pub async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<DapMessage, String> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("Failed to read from debug adapter: {}", e))?;
+
+        if bytes_read == 0 {
+            return Err("Debug adapter closed stdout (EOF)".to_string());
+        }
+
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            // A blank line before any header is stray output; keep reading
+            // until we have seen a Content-Length.
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+
+        if let Some(len_str) = line.strip_prefix("Content-Length:") {
+            content_length = Some(
+                len_str
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid Content-Length: {}", e))?,
+            );
+        }
+    }
+
+    let content_length =
+        content_length.ok_or_else(|| "Missing Content-Length header".to_string())?;
+
+    let mut content = vec![0u8; content_length];
+    reader
+        .read_exact(&mut content)
+        .await
+        .map_err(|e| format!("Failed to read content: {}", e))?;
+
+    let json = String::from_utf8(content).map_err(|e| format!("Invalid UTF-8: {}", e))?;
+
+    tracing::trace!("Received DAP message: {}", json);
+
+    serde_json::from_str(&json).map_err(|e| format!("Failed to deserialize message: {}", e))
+}
+
+/// A source file reference.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Source {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// One entry of a `stackTrace` response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackFrame {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub source: Option<Source>,
+    /// 1-based (we send `linesStartAt1: true` in `initialize`).
+    pub line: i64,
+    /// 1-based (we send `columnsStartAt1: true` in `initialize`).
+    #[serde(default)]
+    pub column: i64,
+}
+
+/// Body of a `stackTrace` response.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackTraceResponseBody {
+    pub stack_frames: Vec<StackFrame>,
+}
+
+/// Body of a `stopped` event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoppedEventBody {
+    /// `"breakpoint"`, `"step"`, `"pause"`, `"exception"`, ...
+    pub reason: String,
+    #[serde(default)]
+    pub thread_id: Option<i64>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Body of a `continued` event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuedEventBody {
+    pub thread_id: i64,
+}
+
+/// Body of an `output` event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputEventBody {
+    #[serde(default)]
+    pub category: Option<String>,
+    pub output: String,
+}
+
+/// Body of an `exited` event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitedEventBody {
+    pub exit_code: i64,
+}
+
+/// A breakpoint as acknowledged by the adapter in a `setBreakpoints`
+/// response or a `breakpoint` event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Breakpoint {
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub line: Option<i64>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Body of a `setBreakpoints` response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetBreakpointsResponseBody {
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_writes_content_length_header() {
+        let bytes = encode(&request(1, "initialize", None));
+        let text = String::from_utf8(bytes).unwrap();
+        let (header, body) = text.split_once("\r\n\r\n").unwrap();
+        assert_eq!(header, format!("Content-Length: {}", body.len()));
+        let parsed: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(parsed["type"], "request");
+        assert_eq!(parsed["command"], "initialize");
+        assert!(parsed.get("arguments").is_none());
+    }
+
+    #[tokio::test]
+    async fn read_message_parses_consecutive_frames() {
+        let mut wire = Vec::new();
+        wire.extend(encode(&json!({
+            "seq": 1,
+            "type": "response",
+            "request_seq": 4,
+            "success": true,
+            "command": "continue",
+            "body": {"allThreadsContinued": true}
+        })));
+        wire.extend(encode(&json!({
+            "seq": 2,
+            "type": "event",
+            "event": "stopped",
+            "body": {"reason": "breakpoint", "threadId": 1}
+        })));
+        let mut reader = tokio::io::BufReader::new(&wire[..]);
+
+        match read_message(&mut reader).await.unwrap() {
+            DapMessage::Response {
+                request_seq,
+                success,
+                command,
+                ..
+            } => {
+                assert_eq!(request_seq, 4);
+                assert!(success);
+                assert_eq!(command, "continue");
+            }
+            other => panic!("expected response, got {:?}", other),
+        }
+
+        match read_message(&mut reader).await.unwrap() {
+            DapMessage::Event { event, body } => {
+                assert_eq!(event, "stopped");
+                let body: StoppedEventBody = serde_json::from_value(body.unwrap()).unwrap();
+                assert_eq!(body.reason, "breakpoint");
+                assert_eq!(body.thread_id, Some(1));
+            }
+            other => panic!("expected event, got {:?}", other),
+        }
+
+        assert!(read_message(&mut reader).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn read_message_skips_stray_blank_lines() {
+        let mut wire = b"\r\n".to_vec();
+        wire.extend(encode(
+            &json!({"seq": 1, "type": "event", "event": "initialized"}),
+        ));
+        let mut reader = tokio::io::BufReader::new(&wire[..]);
+        assert_eq!(
+            read_message(&mut reader).await.unwrap(),
+            DapMessage::Event {
+                event: "initialized".to_string(),
+                body: None
+            }
+        );
+    }
+
+    #[test]
+    fn stack_frame_deserializes_without_source() {
+        let frame: StackFrame = serde_json::from_value(json!({
+            "id": 1000,
+            "name": "main",
+            "line": 3,
+            "column": 1
+        }))
+        .unwrap();
+        assert_eq!(frame.source, None);
+        assert_eq!(frame.line, 3);
+    }
+}
@@ -19,13 +19,21 @@
 //! that happen to watch the same path, which matters once
 //! Orchestrator's collision radar is watching one path per worktree
 //! across N sessions.
+//!
+//! Paths on a remote authority can't be seen by `notify`; those
+//! watches go through [`FileSystem::watch`] instead, which streams
+//! the agent's change events back over the connection. The same
+//! mechanism backs [`WatchedDirs`], which lets a window on a remote
+//! authority hear about changes to its open files and expanded
+//! explorer directories instead of polling their metadata.
 
+use crate::model::filesystem::{FileSystem, FsChangeCallback, FsWatch, FsWatchState};
 use crate::services::async_bridge::{AsyncBridge, AsyncMessage, PathChangeKind};
 use notify::{
     event::{CreateKind, EventKind, ModifyKind, RemoveKind},
     RecommendedWatcher, RecursiveMode, Watcher,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
@@ -43,6 +51,9 @@ pub struct FileWatcherManager {
     /// when notify fires events for a path that was just
     /// unwatched (rare but possible — events are queued).
     handles: HashMap<u64, (PathBuf, RecursiveMode)>,
+    /// Watches on a remote filesystem, by handle. Dropping one
+    /// stops it.
+    remote: HashMap<u64, FsWatch>,
     next_handle: u64,
 }
 
@@ -51,24 +62,31 @@ impl FileWatcherManager {
         Self {
             watcher: None,
             handles: HashMap::new(),
+            remote: HashMap::new(),
             next_handle: 1,
         }
     }
 
     /// Register a watch. `bridge` is needed only on the first call
     /// to construct the `Watcher`; subsequent calls reuse the
-    /// existing Watcher and ignore the parameter.
+    /// existing Watcher and ignore the parameter. Paths on a remote
+    /// `fs` are watched through the filesystem instead.
     ///
     /// Returns the allocated handle on success, or an error string
     /// on `notify` failures (path missing, permission, kernel
     /// limit). Errors are surfaced to the plugin via
-    /// `WatchPathRegistered::result`.
+    /// `WatchPathRegistered::result`. A remote watch starts in the
+    /// background, so one the agent rejects is only logged.
     pub fn watch(
         &mut self,
         bridge: &AsyncBridge,
+        fs: &dyn FileSystem,
         path: &Path,
         recursive: bool,
     ) -> Result<u64, String> {
+        if fs.remote_connection_info().is_some() {
+            return self.watch_remote(bridge, fs, path, recursive);
+        }
         if self.watcher.is_none() {
             self.watcher = Some(build_watcher(bridge.clone())?);
         }
@@ -96,8 +114,38 @@ impl FileWatcherManager {
         Ok(handle)
     }
 
+    fn watch_remote(
+        &mut self,
+        bridge: &AsyncBridge,
+        fs: &dyn FileSystem,
+        path: &Path,
+        recursive: bool,
+    ) -> Result<u64, String> {
+        let handle = self.next_handle;
+        let sender = bridge.sender();
+        let on_change: FsChangeCallback = Box::new(move |path, kind| {
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = sender.send(AsyncMessage::PathChanged { handle, path, kind });
+        });
+        let watch = fs
+            .watch(path, recursive, on_change)
+            .map_err(|e| format!("watchPath({}): {}", path.display(), e))?
+            .ok_or_else(|| {
+                format!(
+                    "watchPath({}): the filesystem does not support watching",
+                    path.display()
+                )
+            })?;
+        self.next_handle += 1;
+        self.remote.insert(handle, watch);
+        Ok(handle)
+    }
+
     /// Drop a registered watcher. Unknown handles are ignored.
     pub fn unwatch(&mut self, handle: u64) {
+        if self.remote.remove(&handle).is_some() {
+            return;
+        }
         if let Some((path, _mode)) = self.handles.remove(&handle) {
             unregister_handle(handle);
             if let Some(w) = self.watcher.as_mut() {
@@ -120,6 +168,91 @@ impl Default for FileWatcherManager {
     }
 }
 
+// ---------------------------------------------------------------
+// Window directory watches
+// ---------------------------------------------------------------
+
+/// Watches on the directories a window cares about — those holding
+/// its open files and those expanded in its file explorer — on a
+/// filesystem with notifications of its own (a remote agent).
+///
+/// Changes arrive as `AsyncMessage::FileChanged` on the window's
+/// bridge: an open file auto-reverts the same way it does when a
+/// poll spots a newer mtime, and an explorer directory is queued in
+/// `changed` for the next tree refresh. Files and directories a
+/// watch covers drop out of the metadata polls, which otherwise cost
+/// a round trip per path per interval. Directories rather than files
+/// are watched because an atomic save replaces the file, which would
+/// end a watch on it.
+#[derive(Debug, Default)]
+pub struct WatchedDirs {
+    /// Watched directory → its watch, or `None` when the filesystem
+    /// couldn't watch it. Neither a failed nor a rejected watch is
+    /// retried while the directory stays wanted.
+    dirs: HashMap<PathBuf, Option<FsWatch>>,
+    /// Watched directories with changes the file explorer hasn't
+    /// picked up yet.
+    pub changed: HashSet<PathBuf>,
+}
+
+impl WatchedDirs {
+    /// Watch each directory of `wanted` not watched yet, and stop
+    /// watching those no longer wanted. Nothing is watched on a
+    /// filesystem without notifications.
+    pub fn sync(&mut self, fs: &dyn FileSystem, bridge: &AsyncBridge, wanted: HashSet<PathBuf>) {
+        self.dirs.retain(|dir, _| wanted.contains(dir));
+        self.changed.retain(|dir| wanted.contains(dir));
+        if fs.remote_connection_info().is_none() || !fs.is_remote_connected() {
+            return;
+        }
+        for dir in wanted {
+            if self.dirs.contains_key(&dir) {
+                continue;
+            }
+            let sender = bridge.sender();
+            let root = dir.clone();
+            let on_change: FsChangeCallback = Box::new(move |path, kind| {
+                // The directory's own mtime moves with every entry
+                // change, already reported for the entry. Anything
+                // else on the directory itself (an overflow, a
+                // reconnect) may hide changes to any file in it.
+                if path == root && !matches!(kind, PathChangeKind::Other) {
+                    return;
+                }
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = sender.send(AsyncMessage::FileChanged {
+                    path: path.to_string_lossy().into_owned(),
+                });
+            });
+            let watch = fs.watch(&dir, false, on_change).unwrap_or_else(|e| {
+                tracing::debug!("Failed to watch {}: {}", dir.display(), e);
+                None
+            });
+            self.dirs.insert(dir, watch);
+        }
+    }
+
+    /// Whether changes to the entries of `dir` arrive as events. A
+    /// watch the agent hasn't accepted yet doesn't count, so `dir`
+    /// stays in the metadata polls until it has.
+    pub fn covers(&self, dir: &Path) -> bool {
+        self.dirs
+            .get(dir)
+            .and_then(Option::as_ref)
+            .is_some_and(|watch| watch.state() == FsWatchState::Running)
+    }
+
+    /// Queue the watched directory `path` is in, or `path` itself
+    /// when it is one, for the next file explorer refresh.
+    pub fn note_change(&mut self, path: &Path) {
+        for dir in [Some(path), path.parent()].into_iter().flatten() {
+            if self.covers(dir) {
+                self.changed.insert(dir.to_path_buf());
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------
 // Notify event → AsyncMessage routing
 //
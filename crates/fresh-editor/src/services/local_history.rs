@@ -0,0 +1,348 @@
+//! Local file history
+//!
+//! Every save of a file stores a snapshot of its content, so earlier
+//! versions can be compared or restored even outside version control.
+//!
+//! ```text
+//! ~/.local/share/fresh/local-history/
+//! └── {path_hash}/
+//!     ├── index.json         # File path and the list of versions
+//!     ├── {id}.snap          # Content of one saved version
+//!     └── ...
+//! ```
+//!
+//! Version ids are the save time in milliseconds since the Unix epoch, so
+//! they sort chronologically. A save whose content is identical to the
+//! latest version is not recorded again. Old versions are pruned on every
+//! save according to [`Retention`].
+
+use crate::services::recovery::path_hash;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Bump when the index format changes; older histories are discarded.
+const FORMAT_VERSION: u32 = 1;
+
+/// How many versions of a file are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    /// Maximum number of versions
+    pub max_entries: usize,
+    /// Versions older than this are dropped (`None` keeps them forever)
+    pub max_age: Option<Duration>,
+    /// Maximum total size of all versions, in bytes
+    pub max_total_bytes: u64,
+}
+
+impl Retention {
+    /// Retention limits from the `local_history_*` editor settings.
+    pub fn from_config(config: &crate::config::EditorConfig) -> Self {
+        Self {
+            max_entries: config.local_history_max_entries,
+            max_age: (config.local_history_max_age_days > 0).then(|| {
+                Duration::from_secs(u64::from(config.local_history_max_age_days) * 24 * 60 * 60)
+            }),
+            max_total_bytes: config.local_history_max_size_bytes,
+        }
+    }
+}
+
+/// One saved version of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Version id: save time in milliseconds since the Unix epoch
+    pub id: u64,
+    /// Size of the content in bytes
+    pub size: u64,
+    /// Fingerprint of the content, used to skip unchanged saves
+    hash: String,
+}
+
+/// Index of the versions of one file, as written to disk
+#[derive(Debug, Serialize, Deserialize)]
+struct HistoryIndex {
+    version: u32,
+    /// File the history belongs to (guards against hash collisions)
+    file_path: PathBuf,
+    /// Versions, oldest first
+    entries: Vec<HistoryEntry>,
+}
+
+/// Reads and writes the local history of files
+#[derive(Debug, Clone)]
+pub struct LocalHistoryStore {
+    dir: PathBuf,
+}
+
+impl LocalHistoryStore {
+    /// Store under `dir` (normally `DirectoryContext::local_history_dir`).
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    fn file_dir(&self, file_path: &Path) -> PathBuf {
+        self.dir.join(path_hash(file_path))
+    }
+
+    fn snapshot_path(&self, file_path: &Path, id: u64) -> PathBuf {
+        self.file_dir(file_path).join(format!("{id}.snap"))
+    }
+
+    fn load_index(&self, file_path: &Path) -> Vec<HistoryEntry> {
+        let path = self.file_dir(file_path).join("index.json");
+        let Ok(data) = fs::read(&path) else {
+            return Vec::new();
+        };
+        match serde_json::from_slice::<HistoryIndex>(&data) {
+            Ok(index) if index.version == FORMAT_VERSION && index.file_path == file_path => {
+                index.entries
+            }
+            Ok(_) => Vec::new(),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring unreadable local history {}: {}",
+                    path.display(),
+                    e
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    fn save_index(&self, file_path: &Path, entries: Vec<HistoryEntry>) -> io::Result<()> {
+        let dir = self.file_dir(file_path);
+        if entries.is_empty() {
+            return match fs::remove_dir_all(&dir) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        let index = HistoryIndex {
+            version: FORMAT_VERSION,
+            file_path: file_path.to_path_buf(),
+            entries,
+        };
+        let json = serde_json::to_vec_pretty(&index).map_err(io::Error::other)?;
+        let target = dir.join("index.json");
+        let temp_path = target.with_extension("tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, &target)
+    }
+
+    /// Record `content` as a new version of `file_path` saved at `now`, then
+    /// prune old versions. Returns the id of the new version, or `None` if
+    /// nothing was recorded because the content didn't change since the
+    /// latest version or doesn't fit the retention limits.
+    pub fn record(
+        &self,
+        file_path: &Path,
+        content: &[u8],
+        now: SystemTime,
+        retention: &Retention,
+    ) -> io::Result<Option<u64>> {
+        let size = content.len() as u64;
+        if retention.max_entries == 0 || size > retention.max_total_bytes {
+            return Ok(None);
+        }
+        let mut entries = self.load_index(file_path);
+        let hash = content_fingerprint(content);
+        if entries.last().is_some_and(|latest| latest.hash == hash) {
+            return Ok(None);
+        }
+
+        // Ids must stay unique and increasing even for saves within the
+        // same millisecond or after the clock went backwards.
+        let mut id = millis_since_epoch(now);
+        if let Some(latest) = entries.last() {
+            id = id.max(latest.id + 1);
+        }
+
+        fs::create_dir_all(self.file_dir(file_path))?;
+        let target = self.snapshot_path(file_path, id);
+        let temp_path = target.with_extension("tmp");
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &target)?;
+
+        entries.push(HistoryEntry { id, size, hash });
+        self.prune_entries(file_path, entries, now, retention)?;
+        Ok(Some(id))
+    }
+
+    /// Versions of `file_path`, newest first.
+    pub fn list(&self, file_path: &Path) -> Vec<HistoryEntry> {
+        let mut entries = self.load_index(file_path);
+        entries.reverse();
+        entries
+    }
+
+    /// Content of version `id` of `file_path`.
+    pub fn read(&self, file_path: &Path, id: u64) -> io::Result<Vec<u8>> {
+        if !self.load_index(file_path).iter().any(|e| e.id == id) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no version {id} of {}", file_path.display()),
+            ));
+        }
+        fs::read(self.snapshot_path(file_path, id))
+    }
+
+    /// Drop the versions of `file_path` that exceed the retention limits.
+    pub fn prune(
+        &self,
+        file_path: &Path,
+        now: SystemTime,
+        retention: &Retention,
+    ) -> io::Result<()> {
+        let entries = self.load_index(file_path);
+        self.prune_entries(file_path, entries, now, retention)
+    }
+
+    fn prune_entries(
+        &self,
+        file_path: &Path,
+        mut entries: Vec<HistoryEntry>,
+        now: SystemTime,
+        retention: &Retention,
+    ) -> io::Result<()> {
+        let mut keep_from = entries.len().saturating_sub(retention.max_entries);
+        if let Some(max_age) = retention.max_age {
+            let cutoff = millis_since_epoch(now).saturating_sub(max_age.as_millis() as u64);
+            keep_from = keep_from.max(entries.partition_point(|e| e.id < cutoff));
+        }
+        let mut total: u64 = entries[keep_from..].iter().map(|e| e.size).sum();
+        while total > retention.max_total_bytes && keep_from < entries.len() {
+            total -= entries[keep_from].size;
+            keep_from += 1;
+        }
+
+        for entry in entries.drain(..keep_from) {
+            if let Err(e) = fs::remove_file(self.snapshot_path(file_path, entry.id)) {
+                if e.kind() != io::ErrorKind::NotFound {
+                    return Err(e);
+                }
+            }
+        }
+        self.save_index(file_path, entries)
+    }
+}
+
+fn millis_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn content_fingerprint(content: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const RETENTION: Retention = Retention {
+        max_entries: 10,
+        max_age: None,
+        max_total_bytes: 1024,
+    };
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn ids(store: &LocalHistoryStore, file: &Path) -> Vec<u64> {
+        store.list(file).iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn test_record_list_and_read() {
+        let temp = TempDir::new().unwrap();
+        let store = LocalHistoryStore::new(temp.path());
+        let file = temp.path().join("a.txt");
+
+        let first = store.record(&file, b"one", at(1), &RETENTION).unwrap();
+        let second = store.record(&file, b"two", at(2), &RETENTION).unwrap();
+        assert_eq!(first, Some(1000));
+        assert_eq!(second, Some(2000));
+
+        // Newest first.
+        assert_eq!(ids(&store, &file), vec![2000, 1000]);
+        assert_eq!(store.list(&file)[0].size, 3);
+        assert_eq!(store.read(&file, 1000).unwrap(), b"one");
+        assert!(store.read(&file, 1234).is_err());
+
+        // Another file has no history.
+        assert!(store.list(&temp.path().join("b.txt")).is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_save_is_not_recorded() {
+        let temp = TempDir::new().unwrap();
+        let store = LocalHistoryStore::new(temp.path());
+        let file = temp.path().join("a.txt");
+
+        store.record(&file, b"same", at(1), &RETENTION).unwrap();
+        assert_eq!(
+            store.record(&file, b"same", at(2), &RETENTION).unwrap(),
+            None
+        );
+        assert_eq!(ids(&store, &file), vec![1000]);
+
+        // Saves in the same millisecond still get distinct ids.
+        let id = store.record(&file, b"other", at(1), &RETENTION).unwrap();
+        assert_eq!(id, Some(1001));
+    }
+
+    #[test]
+    fn test_retention_by_count_age_and_size() {
+        let temp = TempDir::new().unwrap();
+        let store = LocalHistoryStore::new(temp.path());
+        let file = temp.path().join("a.txt");
+
+        let by_count = Retention {
+            max_entries: 2,
+            ..RETENTION
+        };
+        for i in 1..=4 {
+            store
+                .record(&file, i.to_string().as_bytes(), at(i), &by_count)
+                .unwrap();
+        }
+        assert_eq!(ids(&store, &file), vec![4000, 3000]);
+        assert!(!store.snapshot_path(&file, 1000).exists());
+
+        let by_age = Retention {
+            max_age: Some(Duration::from_secs(10)),
+            ..RETENTION
+        };
+        store.prune(&file, at(13), &by_age).unwrap();
+        assert_eq!(ids(&store, &file), vec![4000, 3000]);
+        store.prune(&file, at(14), &by_age).unwrap();
+        assert_eq!(ids(&store, &file), vec![4000]);
+
+        let by_size = Retention {
+            max_total_bytes: 8,
+            ..RETENTION
+        };
+        store.record(&file, b"12345", at(5), &by_size).unwrap();
+        store.record(&file, b"67890", at(6), &by_size).unwrap();
+        assert_eq!(ids(&store, &file), vec![6000]);
+        // Content larger than the limit is not recorded at all.
+        assert_eq!(
+            store.record(&file, b"too large", at(7), &by_size).unwrap(),
+            None
+        );
+
+        // Pruning everything removes the file's directory.
+        store.prune(&file, at(100), &by_age).unwrap();
+        assert!(store.list(&file).is_empty());
+        assert!(!store.file_dir(&file).exists());
+    }
+}
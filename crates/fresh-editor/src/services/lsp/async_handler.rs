@@ -15,6 +15,7 @@ use crate::services::async_bridge::{
     AsyncBridge, AsyncMessage, LspMessageType, LspProgressValue, LspSemanticTokensResponse,
     LspServerStatus,
 };
+use crate::services::lsp::file_operations::FileOperation;
 use crate::services::process_limits::ProcessLimits;
 use lsp_types::{
     notification::{
@@ -59,6 +60,10 @@ const DID_OPEN_GRACE_PERIOD_MS: u64 = 200;
 /// answers) from leaving features wedged in their loading state forever.
 const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 30_000;
 
+/// Timeout for `workspace/will*Files` requests. The file operation waits for
+/// the answers, so a slow server must not hold it up for long.
+const WILL_FILE_OPERATION_TIMEOUT_MS: u64 = 5_000;
+
 /// LSP error codes that should not surface as user-visible warnings.
 ///
 /// From [LSP 3.17 specification](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/):
@@ -357,18 +362,22 @@ impl LspClientState {
 fn create_client_capabilities() -> ClientCapabilities {
     use lsp_types::{
         CodeActionClientCapabilities, CodeActionKindLiteralSupport, CodeActionLiteralSupport,
+        CodeLensClientCapabilities, CodeLensWorkspaceClientCapabilities,
         CompletionClientCapabilities, DiagnosticClientCapabilities, DiagnosticTag,
-        DiagnosticWorkspaceClientCapabilities, DocumentFormattingClientCapabilities,
-        DocumentHighlightClientCapabilities, DocumentRangeFormattingClientCapabilities,
-        DocumentSymbolClientCapabilities, DynamicRegistrationClientCapabilities,
-        FoldingRangeCapability, FoldingRangeClientCapabilities, FoldingRangeKind,
-        FoldingRangeKindCapability, GeneralClientCapabilities, GotoCapability,
-        HoverClientCapabilities, InlayHintClientCapabilities, InlayHintWorkspaceClientCapabilities,
-        MarkupKind, PublishDiagnosticsClientCapabilities, RenameClientCapabilities,
+        DiagnosticWorkspaceClientCapabilities, DocumentColorClientCapabilities,
+        DocumentFormattingClientCapabilities, DocumentHighlightClientCapabilities,
+        DocumentLinkClientCapabilities, DocumentOnTypeFormattingClientCapabilities,
+        DocumentRangeFormattingClientCapabilities, DocumentSymbolClientCapabilities,
+        DynamicRegistrationClientCapabilities, FoldingRangeCapability,
+        FoldingRangeClientCapabilities, FoldingRangeKind, FoldingRangeKindCapability,
+        GeneralClientCapabilities, GotoCapability, HoverClientCapabilities,
+        InlayHintClientCapabilities, InlayHintWorkspaceClientCapabilities,
+        LinkedEditingRangeClientCapabilities, MarkupKind, PublishDiagnosticsClientCapabilities,
+        RenameClientCapabilities, SelectionRangeClientCapabilities,
         SemanticTokensWorkspaceClientCapabilities, SignatureHelpClientCapabilities, TagSupport,
         TextDocumentClientCapabilities, TextDocumentSyncClientCapabilities,
         WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
-        WorkspaceSymbolClientCapabilities,
+        WorkspaceFileOperationsClientCapabilities, WorkspaceSymbolClientCapabilities,
     };
 
     ClientCapabilities {
@@ -418,6 +427,22 @@ fn create_client_capabilities() -> ClientCapabilities {
             semantic_tokens: Some(SemanticTokensWorkspaceClientCapabilities {
                 refresh_support: Some(true),
             }),
+            code_lens: Some(CodeLensWorkspaceClientCapabilities {
+                refresh_support: Some(true),
+            }),
+            // Renames, moves and deletes from the file explorer are
+            // reported before (so servers can update imports) and after;
+            // creations only after, since new files are named after the
+            // fact.
+            file_operations: Some(WorkspaceFileOperationsClientCapabilities {
+                dynamic_registration: Some(true),
+                did_create: Some(true),
+                will_create: None,
+                did_rename: Some(true),
+                will_rename: Some(true),
+                did_delete: Some(true),
+                will_delete: Some(true),
+            }),
             ..Default::default()
         }),
         text_document: Some(TextDocumentClientCapabilities {
@@ -452,6 +477,9 @@ fn create_client_capabilities() -> ClientCapabilities {
             document_highlight: Some(DocumentHighlightClientCapabilities {
                 dynamic_registration: Some(true),
             }),
+            call_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
             document_symbol: Some(DocumentSymbolClientCapabilities {
                 dynamic_registration: Some(true),
                 ..Default::default()
@@ -462,6 +490,12 @@ fn create_client_capabilities() -> ClientCapabilities {
             range_formatting: Some(DocumentRangeFormattingClientCapabilities {
                 dynamic_registration: Some(true),
             }),
+            on_type_formatting: Some(DocumentOnTypeFormattingClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            linked_editing_range: Some(LinkedEditingRangeClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
             code_action: Some(CodeActionClientCapabilities {
                 dynamic_registration: Some(true),
                 // Without `codeActionLiteralSupport`, rust-analyzer (and
@@ -505,6 +539,19 @@ fn create_client_capabilities() -> ClientCapabilities {
                 dynamic_registration: Some(true),
                 ..Default::default()
             }),
+            code_lens: Some(CodeLensClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            document_link: Some(DocumentLinkClientCapabilities {
+                dynamic_registration: Some(true),
+                tooltip_support: Some(true),
+            }),
+            color_provider: Some(DocumentColorClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            selection_range: Some(SelectionRangeClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
             diagnostic: Some(DiagnosticClientCapabilities {
                 dynamic_registration: Some(true),
                 ..Default::default()
@@ -663,6 +710,20 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
                 lsp_types::OneOf::Right(_) => true,
             }
         }),
+        on_type_formatting: caps.document_on_type_formatting_provider.is_some(),
+        on_type_formatting_trigger_characters: caps
+            .document_on_type_formatting_provider
+            .as_ref()
+            .map(|p| {
+                std::iter::once(p.first_trigger_character.clone())
+                    .chain(p.more_trigger_character.iter().flatten().cloned())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        linked_editing_range: bool_or_options(&caps.linked_editing_range_provider, |p| match p {
+            lsp_types::LinkedEditingRangeServerCapabilities::Simple(v) => *v,
+            _ => true,
+        }),
         rename: bool_or_options(&caps.rename_provider, |p| match p {
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
@@ -694,6 +755,26 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             }
             _ => false,
         }),
+        code_lens: caps.code_lens_provider.is_some(),
+        code_lens_resolve: caps
+            .code_lens_provider
+            .as_ref()
+            .and_then(|p| p.resolve_provider)
+            .unwrap_or(false),
+        document_link: caps.document_link_provider.is_some(),
+        document_link_resolve: caps
+            .document_link_provider
+            .as_ref()
+            .and_then(|p| p.resolve_provider)
+            .unwrap_or(false),
+        document_color: bool_or_options(&caps.color_provider, |p| match p {
+            lsp_types::ColorProviderCapability::Simple(v) => *v,
+            _ => true,
+        }),
+        selection_range: bool_or_options(&caps.selection_range_provider, |p| match p {
+            lsp_types::SelectionRangeProviderCapability::Simple(v) => *v,
+            _ => true,
+        }),
         document_symbols: bool_or_options(&caps.document_symbol_provider, |p| match p {
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
@@ -702,15 +783,53 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
         }),
+        call_hierarchy: bool_or_options(&caps.call_hierarchy_provider, |p| match p {
+            lsp_types::CallHierarchyServerCapability::Simple(v) => *v,
+            lsp_types::CallHierarchyServerCapability::Options(_) => true,
+        }),
+        // Not modelled by `ServerCapabilities`; read from the raw
+        // initialize result by `type_hierarchy_provider`.
+        type_hierarchy: false,
         diagnostics: caps.diagnostic_provider.is_some(),
+        file_operations: caps
+            .workspace
+            .as_ref()
+            .and_then(|w| w.file_operations.clone())
+            .unwrap_or_default(),
     }
 }
 
+/// Whether the raw `initialize` result advertises `typeHierarchyProvider`
+/// (`boolean | TypeHierarchyOptions | TypeHierarchyRegistrationOptions`).
+fn type_hierarchy_provider(initialize_result: &Value) -> bool {
+    initialize_result
+        .pointer("/capabilities/typeHierarchyProvider")
+        .is_some_and(|v| !matches!(v, Value::Null | Value::Bool(false)))
+}
+
 /// Helper: check an `Option<T>` capability field using a predicate.
 fn bool_or_options<T>(opt: &Option<T>, check: impl FnOnce(&T) -> bool) -> bool {
     opt.as_ref().is_some_and(check)
 }
 
+/// Which hierarchy a `prepare*Hierarchy` request resolves the symbol for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyKind {
+    /// `textDocument/prepareCallHierarchy` → `CallHierarchyItem[]`
+    Call,
+    /// `textDocument/prepareTypeHierarchy` → `TypeHierarchyItem[]`
+    Type,
+}
+
+impl HierarchyKind {
+    fn prepare_method(self) -> &'static str {
+        match self {
+            Self::Call => "textDocument/prepareCallHierarchy",
+            Self::Type => "textDocument/prepareTypeHierarchy",
+        }
+    }
+}
+
 /// Commands sent from the main loop to the LSP task
 #[derive(Debug)]
 enum LspCommand {
@@ -787,6 +906,15 @@ enum LspCommand {
         character: u32,
     },
 
+    /// Resolve the call- or type-hierarchy item at a position
+    PrepareHierarchy {
+        request_id: u64,
+        kind: HierarchyKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    },
+
     /// Request signature help
     SignatureHelp {
         request_id: u64,
@@ -828,6 +956,55 @@ enum LspCommand {
     /// Request folding ranges for a document
     FoldingRange { request_id: u64, uri: Uri },
 
+    /// Request code lenses for a document (textDocument/codeLens)
+    CodeLens { request_id: u64, uri: Uri },
+
+    /// Resolve a code lens to fill in its command (codeLens/resolve)
+    CodeLensResolve {
+        request_id: u64,
+        lens: Box<lsp_types::CodeLens>,
+    },
+
+    /// Request the links in a document (textDocument/documentLink)
+    DocumentLink { request_id: u64, uri: Uri },
+
+    /// Resolve a document link to fill in its target (documentLink/resolve)
+    DocumentLinkResolve {
+        request_id: u64,
+        link: Box<lsp_types::DocumentLink>,
+    },
+
+    /// Request the color literals in a document (textDocument/documentColor)
+    DocumentColor { request_id: u64, uri: Uri },
+
+    /// Request the ways a color can be written at a range
+    /// (textDocument/colorPresentation)
+    ColorPresentation {
+        request_id: u64,
+        uri: Uri,
+        color: lsp_types::Color,
+        range: lsp_types::Range,
+    },
+
+    /// Ask whether files may be created, renamed or deleted
+    /// (workspace/willCreateFiles, willRenameFiles, willDeleteFiles)
+    WillFileOperation {
+        request_id: u64,
+        operation: FileOperation,
+    },
+
+    /// Report files created, renamed or deleted
+    /// (workspace/didCreateFiles, didRenameFiles, didDeleteFiles)
+    DidFileOperation { operation: FileOperation },
+
+    /// Request the enclosing syntax ranges at each position
+    /// (textDocument/selectionRange)
+    SelectionRange {
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<Position>,
+    },
+
     /// Request semantic tokens for the entire document
     SemanticTokensFull { request_id: u64, uri: Uri },
 
@@ -883,6 +1060,26 @@ enum LspCommand {
         insert_spaces: bool,
     },
 
+    /// Format after typing a trigger character (textDocument/onTypeFormatting)
+    OnTypeFormatting {
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
+    },
+
+    /// Request the ranges to edit together with the one at a position
+    /// (textDocument/linkedEditingRange)
+    LinkedEditingRange {
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    },
+
     /// Prepare rename — validate rename at position (textDocument/prepareRename)
     PrepareRename {
         request_id: u64,
@@ -1267,9 +1464,21 @@ impl LspState {
             ..Default::default()
         };
 
-        let result: InitializeResult = self
+        // Type hierarchy (LSP 3.17) isn't modelled by lsp-types' capability
+        // structs, so the client capability is added to the serialized
+        // params and the server's answer is read from the raw result.
+        let mut params = serde_json::to_value(params)
+            .map_err(|e| format!("Failed to serialize initialize params: {}", e))?;
+        if let Some(text_document) = params.pointer_mut("/capabilities/textDocument") {
+            text_document["typeHierarchy"] = serde_json::json!({ "dynamicRegistration": true });
+        }
+
+        let raw_result: Value = self
             .send_request_sequential(Initialize::METHOD, Some(params), pending)
             .await?;
+        let type_hierarchy = type_hierarchy_provider(&raw_result);
+        let result: InitializeResult = serde_json::from_value(raw_result)
+            .map_err(|e| format!("Failed to parse initialize result: {}", e))?;
 
         tracing::info!(
             "LSP initialize result: position_encoding={:?}",
@@ -1283,7 +1492,8 @@ impl LspState {
 
         self.initialized.store(true, Ordering::SeqCst);
 
-        let capabilities = extract_capability_summary(&result.capabilities);
+        let mut capabilities = extract_capability_summary(&result.capabilities);
+        capabilities.type_hierarchy = type_hierarchy;
 
         // Notify main loop
         let _ = self.async_tx.send(AsyncMessage::LspInitialized {
@@ -1847,6 +2057,46 @@ impl LspState {
         }
     }
 
+    /// Handle a `textDocument/prepare{Call,Type}Hierarchy` request.
+    ///
+    /// Items are forwarded as raw JSON: the follow-up requests
+    /// (`callHierarchy/incomingCalls`, `typeHierarchy/supertypes`, ...) must
+    /// echo each item back verbatim, including server-private `data`.
+    async fn handle_prepare_hierarchy(
+        &self,
+        request_id: u64,
+        kind: HierarchyKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        tracing::trace!(
+            "LSP: {} at {}:{}:{}",
+            kind.prepare_method(),
+            uri.as_str(),
+            line,
+            character
+        );
+
+        let params = TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri },
+            position: Position { line, character },
+        };
+
+        let result = self
+            .send_request_sequential::<_, Value>(kind.prepare_method(), Some(params), pending)
+            .await;
+        let items = match &result {
+            Ok(Value::Array(items)) => items.clone(),
+            _ => Vec::new(),
+        };
+        let _ = self
+            .async_tx
+            .send(AsyncMessage::LspHierarchyPrepared { request_id, items });
+        result.map(|_| ())
+    }
+
     /// Handle signature help request
     async fn handle_signature_help(
         &self,
@@ -2187,6 +2437,93 @@ impl LspState {
         }
     }
 
+    /// Handle textDocument/onTypeFormatting request
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_on_type_formatting(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{DocumentOnTypeFormattingParams, FormattingOptions};
+
+        let params = DocumentOnTypeFormattingParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position::new(line, character),
+            },
+            ch,
+            options: FormattingOptions {
+                tab_size,
+                insert_spaces,
+                ..Default::default()
+            },
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::TextEdit>>>(
+                "textDocument/onTypeFormatting",
+                Some(params),
+                pending,
+            )
+            .await;
+        let edits = match &result {
+            Ok(edits) => edits.clone().unwrap_or_default(),
+            Err(e) => {
+                tracing::debug!("textDocument/onTypeFormatting failed: {}", e);
+                Vec::new()
+            }
+        };
+        let _ = self
+            .async_tx
+            .send(AsyncMessage::LspOnTypeFormatting { request_id, edits });
+        result.map(|_| ())
+    }
+
+    /// Handle textDocument/linkedEditingRange request
+    async fn handle_linked_editing_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::LinkedEditingRangeParams;
+
+        let params = LinkedEditingRangeParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position::new(line, character),
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<lsp_types::LinkedEditingRanges>>(
+                "textDocument/linkedEditingRange",
+                Some(params),
+                pending,
+            )
+            .await;
+        let ranges = match &result {
+            Ok(ranges) => ranges.clone(),
+            Err(e) => {
+                tracing::debug!("Linked editing range request failed: {}", e);
+                None
+            }
+        };
+        let _ = self
+            .async_tx
+            .send(AsyncMessage::LspLinkedEditingRanges { request_id, ranges });
+        result.map(|_| ())
+    }
+
     /// Handle textDocument/prepareRename request
     async fn handle_prepare_rename(
         &self,
@@ -2475,15 +2812,303 @@ impl LspState {
                 Ok(())
             }
             Err(e) => {
-                tracing::debug!("Folding range request failed: {}", e);
-                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
-                    request_id,
-                    uri: uri.as_str().to_string(),
-                    ranges: Vec::new(),
-                });
-                Err(e)
+                tracing::debug!("Folding range request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspFoldingRanges {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    ranges: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle code lens request
+    async fn handle_code_lens(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::CodeLensParams;
+
+        let params = CodeLensParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::CodeLens>>>(
+                "textDocument/codeLens",
+                Some(params),
+                pending,
+            )
+            .await;
+        let lenses = match &result {
+            Ok(lenses) => lenses.clone().unwrap_or_default(),
+            Err(e) => {
+                tracing::debug!("Code lens request failed: {}", e);
+                Vec::new()
+            }
+        };
+        tracing::trace!(
+            "LSP: received {} code lenses for {}",
+            lenses.len(),
+            uri.as_str()
+        );
+        let _ = self.async_tx.send(AsyncMessage::LspCodeLenses {
+            request_id,
+            uri: uri.as_str().to_string(),
+            lenses,
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle codeLens/resolve request
+    async fn handle_code_lens_resolve(
+        &self,
+        request_id: u64,
+        lens: lsp_types::CodeLens,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        let result = self
+            .send_request_sequential::<_, lsp_types::CodeLens>(
+                "codeLens/resolve",
+                Some(lens),
+                pending,
+            )
+            .await;
+        if let Err(e) = &result {
+            tracing::debug!("codeLens/resolve failed: {}", e);
+        }
+        let _ = self.async_tx.send(AsyncMessage::LspCodeLensResolved {
+            request_id,
+            lens: result.clone(),
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle document link request
+    async fn handle_document_link(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::DocumentLinkParams;
+
+        let params = DocumentLinkParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::DocumentLink>>>(
+                "textDocument/documentLink",
+                Some(params),
+                pending,
+            )
+            .await;
+        let links = match &result {
+            Ok(links) => links.clone().unwrap_or_default(),
+            Err(e) => {
+                tracing::debug!("Document link request failed: {}", e);
+                Vec::new()
+            }
+        };
+        tracing::trace!(
+            "LSP: received {} document links for {}",
+            links.len(),
+            uri.as_str()
+        );
+        let _ = self.async_tx.send(AsyncMessage::LspDocumentLinks {
+            request_id,
+            uri: uri.as_str().to_string(),
+            links,
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle documentLink/resolve request
+    async fn handle_document_link_resolve(
+        &self,
+        request_id: u64,
+        link: lsp_types::DocumentLink,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        let result = self
+            .send_request_sequential::<_, lsp_types::DocumentLink>(
+                "documentLink/resolve",
+                Some(link),
+                pending,
+            )
+            .await;
+        if let Err(e) = &result {
+            tracing::debug!("documentLink/resolve failed: {}", e);
+        }
+        let _ = self.async_tx.send(AsyncMessage::LspDocumentLinkResolved {
+            request_id,
+            link: result.clone(),
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle document color request
+    async fn handle_document_color(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::DocumentColorParams;
+
+        let params = DocumentColorParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Vec<lsp_types::ColorInformation>>(
+                "textDocument/documentColor",
+                Some(params),
+                pending,
+            )
+            .await;
+        let colors = match &result {
+            Ok(colors) => colors.clone(),
+            Err(e) => {
+                tracing::debug!("Document color request failed: {}", e);
+                Vec::new()
+            }
+        };
+        tracing::trace!(
+            "LSP: received {} document colors for {}",
+            colors.len(),
+            uri.as_str()
+        );
+        let _ = self.async_tx.send(AsyncMessage::LspDocumentColors {
+            request_id,
+            uri: uri.as_str().to_string(),
+            colors,
+        });
+        result.map(|_| ())
+    }
+
+    /// Handle color presentation request
+    async fn handle_color_presentation(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        color: lsp_types::Color,
+        range: lsp_types::Range,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::ColorPresentationParams;
+
+        let params = ColorPresentationParams {
+            text_document: TextDocumentIdentifier { uri },
+            color,
+            range,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Vec<lsp_types::ColorPresentation>>(
+                "textDocument/colorPresentation",
+                Some(params),
+                pending,
+            )
+            .await;
+        let presentations = match &result {
+            Ok(presentations) => presentations.clone(),
+            Err(e) => {
+                tracing::debug!("Color presentation request failed: {}", e);
+                Vec::new()
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspColorPresentations {
+            request_id,
+            presentations,
+        });
+        result.map(|_| ())
+    }
+
+    /// Ask the server about files about to be created, renamed or deleted.
+    /// It may answer with edits to make first, such as updated imports.
+    async fn handle_will_file_operation(
+        &self,
+        request_id: u64,
+        operation: FileOperation,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        let result = self
+            .send_request_with_timeout::<_, Option<lsp_types::WorkspaceEdit>>(
+                operation.will_method(),
+                Some(operation.params()),
+                pending,
+                None,
+                Duration::from_millis(WILL_FILE_OPERATION_TIMEOUT_MS),
+            )
+            .await;
+        if let Err(e) = &result {
+            tracing::debug!("{} request failed: {}", operation.will_method(), e);
+        }
+        let _ = self.async_tx.send(AsyncMessage::LspWillFileOperation {
+            request_id,
+            edit: result.clone(),
+        });
+        result.map(|_| ())
+    }
+
+    /// Tell the server about files that were created, renamed or deleted.
+    async fn handle_did_file_operation(&self, operation: FileOperation) -> Result<(), String> {
+        let notification = JsonRpcNotification {
+            jsonrpc: "2.0".to_string(),
+            method: operation.did_method().to_string(),
+            params: Some(operation.params()),
+        };
+        self.write_message(&notification).await
+    }
+
+    /// Handle selection range request
+    async fn handle_selection_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<Position>,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::SelectionRangeParams;
+
+        let params = SelectionRangeParams {
+            text_document: TextDocumentIdentifier { uri },
+            positions,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let result = self
+            .send_request_sequential::<_, Option<Vec<lsp_types::SelectionRange>>>(
+                "textDocument/selectionRange",
+                Some(params),
+                pending,
+            )
+            .await;
+        let ranges = match &result {
+            Ok(ranges) => ranges.clone().unwrap_or_default(),
+            Err(e) => {
+                tracing::debug!("Selection range request failed: {}", e);
+                Vec::new()
             }
-        }
+        };
+        let _ = self
+            .async_tx
+            .send(AsyncMessage::LspSelectionRanges { request_id, ranges });
+        result.map(|_| ())
     }
 
     async fn handle_semantic_tokens_full(
@@ -3348,6 +3973,30 @@ impl LspTask {
                         });
                     }
                 }
+                LspCommand::PrepareHierarchy {
+                    request_id,
+                    kind,
+                    uri,
+                    line,
+                    character,
+                } => {
+                    if initialized {
+                        tracing::info!(
+                            "Processing {:?} hierarchy request for {}",
+                            kind,
+                            uri.as_str()
+                        );
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_prepare_hierarchy(request_id, kind, uri, line, character, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot prepare hierarchy");
+                        let _ = state.async_tx.send(AsyncMessage::LspHierarchyPrepared {
+                            request_id,
+                            items: Vec::new(),
+                        });
+                    }
+                }
                 LspCommand::SignatureHelp {
                     request_id,
                     uri,
@@ -3461,6 +4110,137 @@ impl LspTask {
                         });
                     }
                 }
+                LspCommand::CodeLens { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing CodeLens request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_lens(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get code lenses");
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeLenses {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            lenses: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::CodeLensResolve { request_id, lens } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_lens_resolve(request_id, *lens, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot resolve code lens");
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeLensResolved {
+                            request_id,
+                            lens: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
+                LspCommand::DocumentLink { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing DocumentLink request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_document_link(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get document links");
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentLinks {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            links: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::DocumentLinkResolve { request_id, link } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_document_link_resolve(request_id, *link, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot resolve document link");
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentLinkResolved {
+                            request_id,
+                            link: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
+                LspCommand::DocumentColor { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing DocumentColor request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_document_color(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get document colors");
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentColors {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            colors: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::ColorPresentation {
+                    request_id,
+                    uri,
+                    color,
+                    range,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_color_presentation(request_id, uri, color, range, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get color presentations");
+                        let _ = state.async_tx.send(AsyncMessage::LspColorPresentations {
+                            request_id,
+                            presentations: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::WillFileOperation {
+                    request_id,
+                    operation,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_will_file_operation(request_id, operation, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot ask about file operation");
+                        let _ = state.async_tx.send(AsyncMessage::LspWillFileOperation {
+                            request_id,
+                            edit: Err("LSP not initialized".to_string()),
+                        });
+                    }
+                }
+                LspCommand::DidFileOperation { operation } => {
+                    if initialized {
+                        let _ = state.handle_did_file_operation(operation).await;
+                    } else {
+                        // Interest in file operations is only known once the
+                        // server has initialized, so nothing is lost.
+                        tracing::trace!("LSP not initialized, dropping file operation notice");
+                    }
+                }
+                LspCommand::SelectionRange {
+                    request_id,
+                    uri,
+                    positions,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_selection_range(request_id, uri, positions, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get selection ranges");
+                        let _ = state.async_tx.send(AsyncMessage::LspSelectionRanges {
+                            request_id,
+                            ranges: Vec::new(),
+                        });
+                    }
+                }
                 LspCommand::SemanticTokensFull { request_id, uri } => {
                     if initialized {
                         tracing::info!("Processing SemanticTokens request for {}", uri.as_str());
@@ -3607,6 +4387,48 @@ impl LspTask {
                             .await);
                     }
                 }
+                LspCommand::OnTypeFormatting {
+                    request_id,
+                    uri,
+                    line,
+                    character,
+                    ch,
+                    tab_size,
+                    insert_spaces,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_on_type_formatting(
+                                request_id,
+                                uri,
+                                line,
+                                character,
+                                ch,
+                                tab_size,
+                                insert_spaces,
+                                &p,
+                            )
+                            .await);
+                    }
+                }
+                LspCommand::LinkedEditingRange {
+                    request_id,
+                    uri,
+                    line,
+                    character,
+                } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_linked_editing_range(request_id, uri, line, character, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get linked editing ranges");
+                        let _ = state.async_tx.send(AsyncMessage::LspLinkedEditingRanges {
+                            request_id,
+                            ranges: None,
+                        });
+                    }
+                }
                 LspCommand::PrepareRename {
                     request_id,
                     uri,
@@ -4171,6 +4993,16 @@ async fn handle_message_dispatch(
                     });
                     null_response(request.id)
                 }
+                "workspace/codeLens/refresh" => {
+                    tracing::info!(
+                        "LSP ({}) requested code-lens refresh (workspace/codeLens/refresh)",
+                        language
+                    );
+                    let _ = async_tx.send(AsyncMessage::LspCodeLensRefresh {
+                        language: language.to_string(),
+                    });
+                    null_response(request.id)
+                }
                 "workspace/semanticTokens/refresh" => {
                     // Same idea as inlayHint/refresh, for semantic highlighting.
                     tracing::info!(
@@ -4786,6 +5618,26 @@ impl LspHandle {
             .map_err(|_| "Failed to send references command".to_string())
     }
 
+    /// Request the call- or type-hierarchy item at a position
+    pub fn prepare_hierarchy(
+        &self,
+        request_id: u64,
+        kind: HierarchyKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::PrepareHierarchy {
+                request_id,
+                kind,
+                uri,
+                line,
+                character,
+            })
+            .map_err(|_| "Failed to send hierarchy command".to_string())
+    }
+
     /// Request signature help
     pub fn signature_help(
         &self,
@@ -4919,6 +5771,50 @@ impl LspHandle {
             .map_err(|_| "Failed to send document_range_formatting command".to_string())
     }
 
+    /// Format after typing `ch` at a position (textDocument/onTypeFormatting)
+    #[allow(clippy::too_many_arguments)]
+    pub fn on_type_formatting(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        ch: String,
+        tab_size: u32,
+        insert_spaces: bool,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::OnTypeFormatting {
+                request_id,
+                uri,
+                line,
+                character,
+                ch,
+                tab_size,
+                insert_spaces,
+            })
+            .map_err(|_| "Failed to send on_type_formatting command".to_string())
+    }
+
+    /// Request the ranges linked to the one at a position
+    /// (textDocument/linkedEditingRange)
+    pub fn linked_editing_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::LinkedEditingRange {
+                request_id,
+                uri,
+                line,
+                character,
+            })
+            .map_err(|_| "Failed to send linked_editing_range command".to_string())
+    }
+
     /// Validate rename at position (textDocument/prepareRename)
     pub fn prepare_rename(
         &self,
@@ -4987,6 +5883,117 @@ impl LspHandle {
             .map_err(|_| "Failed to send folding_range command".to_string())
     }
 
+    /// Request code lenses for a document
+    pub fn code_lens(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::CodeLens { request_id, uri })
+            .map_err(|_| "Failed to send code_lens command".to_string())
+    }
+
+    /// Resolve a code lens that arrived without a command (codeLens/resolve)
+    ///
+    /// Only call this when the server advertised `codeLensProvider.resolveProvider`.
+    pub fn code_lens_resolve(
+        &self,
+        request_id: u64,
+        lens: lsp_types::CodeLens,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::CodeLensResolve {
+                request_id,
+                lens: Box::new(lens),
+            })
+            .map_err(|_| "Failed to send code_lens_resolve command".to_string())
+    }
+
+    /// Request the links in a document
+    pub fn document_link(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentLink { request_id, uri })
+            .map_err(|_| "Failed to send document_link command".to_string())
+    }
+
+    /// Resolve a document link that arrived without a target
+    /// (documentLink/resolve)
+    ///
+    /// Only call this when the server advertised `documentLinkProvider.resolveProvider`.
+    pub fn document_link_resolve(
+        &self,
+        request_id: u64,
+        link: lsp_types::DocumentLink,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentLinkResolve {
+                request_id,
+                link: Box::new(link),
+            })
+            .map_err(|_| "Failed to send document_link_resolve command".to_string())
+    }
+
+    /// Request the color literals in a document
+    pub fn document_color(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentColor { request_id, uri })
+            .map_err(|_| "Failed to send document_color command".to_string())
+    }
+
+    /// Request the ways `color` can be written in place of the literal at
+    /// `range`
+    pub fn color_presentation(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        color: lsp_types::Color,
+        range: lsp_types::Range,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::ColorPresentation {
+                request_id,
+                uri,
+                color,
+                range,
+            })
+            .map_err(|_| "Failed to send color_presentation command".to_string())
+    }
+
+    /// Ask whether files may be created, renamed or deleted; the answer
+    /// arrives as `AsyncMessage::LspWillFileOperation`
+    pub fn will_file_operation(
+        &self,
+        request_id: u64,
+        operation: FileOperation,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WillFileOperation {
+                request_id,
+                operation,
+            })
+            .map_err(|_| "Failed to send will_file_operation command".to_string())
+    }
+
+    /// Report files that were created, renamed or deleted
+    pub fn did_file_operation(&self, operation: FileOperation) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DidFileOperation { operation })
+            .map_err(|_| "Failed to send did_file_operation command".to_string())
+    }
+
+    /// Request the chain of enclosing syntax ranges at each position
+    pub fn selection_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<Position>,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::SelectionRange {
+                request_id,
+                uri,
+                positions,
+            })
+            .map_err(|_| "Failed to send selection_range command".to_string())
+    }
+
     /// Request semantic tokens for an entire document
     pub fn semantic_tokens_full(&self, request_id: u64, uri: Uri) -> Result<(), String> {
         self.command_tx
@@ -0,0 +1,297 @@
+//! LSP file operations (`workspace.fileOperations`)
+//!
+//! Servers register interest in file creations, renames and deletions done
+//! by the client through filters: a URI scheme, a glob over the path, and
+//! whether the glob is meant for files, folders or both. Each operation is
+//! reported only to the servers whose filters match, and only with the
+//! files that matched.
+//!
+//! Globs follow the LSP syntax: `*` and `?` stay within one path segment,
+//! `**` crosses segments, and `{a,b}` alternates.
+
+use std::path::Path;
+
+use lsp_types::{
+    FileOperationFilter, FileOperationPatternKind, FileOperationRegistrationOptions,
+    WorkspaceFileOperationsServerCapabilities,
+};
+
+use crate::primitives::glob_match::path_glob_matches;
+
+/// What happened to the files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOperationKind {
+    Create,
+    Rename,
+    Delete,
+}
+
+/// One file or folder taking part in an operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileOperationTarget {
+    /// Its `file://` URI, as the server sees it; the old URI for renames
+    pub uri: String,
+    /// Its new URI, for renames
+    pub new_uri: Option<String>,
+    pub is_dir: bool,
+}
+
+/// Files created, renamed or deleted together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileOperation {
+    pub kind: FileOperationKind,
+    pub targets: Vec<FileOperationTarget>,
+}
+
+impl FileOperation {
+    /// Method of the request sent before the operation.
+    pub fn will_method(&self) -> &'static str {
+        match self.kind {
+            FileOperationKind::Create => "workspace/willCreateFiles",
+            FileOperationKind::Rename => "workspace/willRenameFiles",
+            FileOperationKind::Delete => "workspace/willDeleteFiles",
+        }
+    }
+
+    /// Method of the notification sent after the operation.
+    pub fn did_method(&self) -> &'static str {
+        match self.kind {
+            FileOperationKind::Create => "workspace/didCreateFiles",
+            FileOperationKind::Rename => "workspace/didRenameFiles",
+            FileOperationKind::Delete => "workspace/didDeleteFiles",
+        }
+    }
+
+    /// Params of both the `will*` request and the `did*` notification.
+    pub fn params(&self) -> serde_json::Value {
+        let result = match self.kind {
+            FileOperationKind::Create => serde_json::to_value(lsp_types::CreateFilesParams {
+                files: self
+                    .targets
+                    .iter()
+                    .map(|t| lsp_types::FileCreate { uri: t.uri.clone() })
+                    .collect(),
+            }),
+            FileOperationKind::Rename => serde_json::to_value(lsp_types::RenameFilesParams {
+                files: self
+                    .targets
+                    .iter()
+                    .map(|t| lsp_types::FileRename {
+                        old_uri: t.uri.clone(),
+                        new_uri: t.new_uri.clone().unwrap_or_default(),
+                    })
+                    .collect(),
+            }),
+            FileOperationKind::Delete => serde_json::to_value(lsp_types::DeleteFilesParams {
+                files: self
+                    .targets
+                    .iter()
+                    .map(|t| lsp_types::FileDelete { uri: t.uri.clone() })
+                    .collect(),
+            }),
+        };
+        result.unwrap_or_default()
+    }
+
+    /// The part of this operation a server registered with `options` wants
+    /// to hear about, or `None` if it matches nothing.
+    pub fn filtered(&self, options: &FileOperationRegistrationOptions) -> Option<FileOperation> {
+        let targets: Vec<_> = self
+            .targets
+            .iter()
+            .filter(|t| options.filters.iter().any(|f| filter_matches(f, t)))
+            .cloned()
+            .collect();
+        (!targets.is_empty()).then_some(FileOperation {
+            kind: self.kind,
+            targets,
+        })
+    }
+}
+
+/// The filters a server registered for `kind`, either for the request sent
+/// before the operation (`will`) or the notification sent after it.
+pub fn registration(
+    capabilities: &WorkspaceFileOperationsServerCapabilities,
+    kind: FileOperationKind,
+    will: bool,
+) -> Option<&FileOperationRegistrationOptions> {
+    match (kind, will) {
+        (FileOperationKind::Create, true) => capabilities.will_create.as_ref(),
+        (FileOperationKind::Create, false) => capabilities.did_create.as_ref(),
+        (FileOperationKind::Rename, true) => capabilities.will_rename.as_ref(),
+        (FileOperationKind::Rename, false) => capabilities.did_rename.as_ref(),
+        (FileOperationKind::Delete, true) => capabilities.will_delete.as_ref(),
+        (FileOperationKind::Delete, false) => capabilities.did_delete.as_ref(),
+    }
+}
+
+/// Store the filters of a dynamic registration of `method`, or drop them
+/// when it is unregistered. Returns `false` for methods that are not file
+/// operations.
+pub fn apply_registration(
+    capabilities: &mut WorkspaceFileOperationsServerCapabilities,
+    method: &str,
+    options: Option<FileOperationRegistrationOptions>,
+) -> bool {
+    let slot = match method {
+        "workspace/willCreateFiles" => &mut capabilities.will_create,
+        "workspace/didCreateFiles" => &mut capabilities.did_create,
+        "workspace/willRenameFiles" => &mut capabilities.will_rename,
+        "workspace/didRenameFiles" => &mut capabilities.did_rename,
+        "workspace/willDeleteFiles" => &mut capabilities.will_delete,
+        "workspace/didDeleteFiles" => &mut capabilities.did_delete,
+        _ => return false,
+    };
+    *slot = options;
+    true
+}
+
+fn filter_matches(filter: &FileOperationFilter, target: &FileOperationTarget) -> bool {
+    let (scheme, _) = target.uri.split_once(':').unwrap_or(("", ""));
+    if filter.scheme.as_deref().is_some_and(|s| s != scheme) {
+        return false;
+    }
+    match filter.pattern.matches {
+        Some(FileOperationPatternKind::File) if target.is_dir => return false,
+        Some(FileOperationPatternKind::Folder) if !target.is_dir => return false,
+        _ => {}
+    }
+    let Some(path) = fresh_core::file_uri::file_uri_to_path(&target.uri) else {
+        return false;
+    };
+    let ignore_case = filter
+        .pattern
+        .options
+        .as_ref()
+        .and_then(|o| o.ignore_case)
+        .unwrap_or(false);
+    glob_matches(&filter.pattern.glob, &path, ignore_case)
+}
+
+fn glob_matches(glob: &str, path: &Path, ignore_case: bool) -> bool {
+    let path = path.to_string_lossy();
+    let (glob, path) = if ignore_case {
+        (glob.to_lowercase(), path.to_lowercase())
+    } else {
+        (glob.to_string(), path.into_owned())
+    };
+    expand_braces(&glob)
+        .iter()
+        .any(|pattern| path_glob_matches(pattern, &path))
+}
+
+/// Expand `{a,b}` alternatives into one pattern per combination.
+fn expand_braces(glob: &str) -> Vec<String> {
+    let Some(open) = glob.find('{') else {
+        return vec![glob.to_string()];
+    };
+    let Some(close) = glob[open..].find('}').map(|i| open + i) else {
+        return vec![glob.to_string()];
+    };
+    let (prefix, suffix) = (&glob[..open], &glob[close + 1..]);
+    glob[open + 1..close]
+        .split(',')
+        .flat_map(|alternative| expand_braces(&format!("{prefix}{alternative}{suffix}")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{FileOperationPattern, FileOperationPatternOptions};
+
+    fn options(
+        glob: &str,
+        matches: Option<FileOperationPatternKind>,
+    ) -> FileOperationRegistrationOptions {
+        FileOperationRegistrationOptions {
+            filters: vec![FileOperationFilter {
+                scheme: Some("file".to_string()),
+                pattern: FileOperationPattern {
+                    glob: glob.to_string(),
+                    matches,
+                    options: None,
+                },
+            }],
+        }
+    }
+
+    fn target(uri: &str, is_dir: bool) -> FileOperationTarget {
+        FileOperationTarget {
+            uri: uri.to_string(),
+            new_uri: None,
+            is_dir,
+        }
+    }
+
+    #[test]
+    fn only_matching_files_are_reported() {
+        let operation = FileOperation {
+            kind: FileOperationKind::Rename,
+            targets: vec![
+                target("file:///p/src/a.ts", false),
+                target("file:///p/README.md", false),
+                target("file:///p/src/b.tsx", false),
+                target("file:///p/src", true),
+            ],
+        };
+
+        let typescript = operation
+            .filtered(&options(
+                "**/*.{ts,tsx}",
+                Some(FileOperationPatternKind::File),
+            ))
+            .unwrap();
+        assert_eq!(
+            typescript
+                .targets
+                .iter()
+                .map(|t| t.uri.as_str())
+                .collect::<Vec<_>>(),
+            ["file:///p/src/a.ts", "file:///p/src/b.tsx"]
+        );
+
+        let folders = operation
+            .filtered(&options("**", Some(FileOperationPatternKind::Folder)))
+            .unwrap();
+        assert_eq!(folders.targets, [target("file:///p/src", true)]);
+
+        assert!(operation.filtered(&options("**/*.rs", None)).is_none());
+    }
+
+    #[test]
+    fn scheme_and_case_are_respected() {
+        let operation = FileOperation {
+            kind: FileOperationKind::Delete,
+            targets: vec![target("file:///p/Main.RS", false)],
+        };
+        let mut registered = options("**/*.rs", None);
+        assert!(operation.filtered(&registered).is_none());
+
+        registered.filters[0].pattern.options = Some(FileOperationPatternOptions {
+            ignore_case: Some(true),
+        });
+        assert!(operation.filtered(&registered).is_some());
+
+        registered.filters[0].scheme = Some("untitled".to_string());
+        assert!(operation.filtered(&registered).is_none());
+    }
+
+    #[test]
+    fn rename_params_carry_both_uris() {
+        let operation = FileOperation {
+            kind: FileOperationKind::Rename,
+            targets: vec![FileOperationTarget {
+                uri: "file:///p/a.rs".to_string(),
+                new_uri: Some("file:///p/b.rs".to_string()),
+                is_dir: false,
+            }],
+        };
+        assert_eq!(operation.will_method(), "workspace/willRenameFiles");
+        assert_eq!(
+            operation.params(),
+            serde_json::json!({"files": [{"oldUri": "file:///p/a.rs", "newUri": "file:///p/b.rs"}]})
+        );
+    }
+}
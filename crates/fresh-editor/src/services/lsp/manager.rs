@@ -8,6 +8,7 @@
 
 use crate::services::async_bridge::AsyncBridge;
 use crate::services::lsp::async_handler::LspHandle;
+use crate::services::lsp::file_operations::{self, FileOperation};
 use crate::types::{FeatureFilter, LspFeature, LspServerConfig};
 use lsp_types::{SemanticTokensLegend, Uri};
 use std::collections::HashMap;
@@ -202,6 +203,9 @@ pub struct ServerCapabilitySummary {
     pub references: bool,
     pub document_formatting: bool,
     pub document_range_formatting: bool,
+    pub on_type_formatting: bool,
+    pub on_type_formatting_trigger_characters: Vec<String>,
+    pub linked_editing_range: bool,
     pub rename: bool,
     pub signature_help: bool,
     pub inlay_hints: bool,
@@ -211,11 +215,22 @@ pub struct ServerCapabilitySummary {
     pub semantic_tokens_range: bool,
     pub semantic_tokens_legend: Option<SemanticTokensLegend>,
     pub document_highlight: bool,
+    pub call_hierarchy: bool,
+    pub type_hierarchy: bool,
     pub code_action: bool,
     pub code_action_resolve: bool,
+    pub code_lens: bool,
+    pub code_lens_resolve: bool,
+    pub document_link: bool,
+    pub document_link_resolve: bool,
+    pub document_color: bool,
+    pub selection_range: bool,
     pub document_symbols: bool,
     pub workspace_symbols: bool,
     pub diagnostics: bool,
+    /// Filters of the file creations, renames and deletions the server
+    /// wants to hear about
+    pub file_operations: lsp_types::WorkspaceFileOperationsServerCapabilities,
 }
 
 impl ServerCapabilitySummary {
@@ -271,11 +286,35 @@ impl ServerCapabilitySummary {
             "textDocument/references" => self.references = register,
             "textDocument/formatting" => self.document_formatting = register,
             "textDocument/rangeFormatting" => self.document_range_formatting = register,
+            "textDocument/onTypeFormatting" => {
+                self.on_type_formatting = register;
+                self.on_type_formatting_trigger_characters = if register {
+                    register_options
+                        .and_then(|opts| {
+                            serde_json::from_value::<lsp_types::DocumentOnTypeFormattingOptions>(
+                                opts.clone(),
+                            )
+                            .ok()
+                        })
+                        .map(|opts| {
+                            std::iter::once(opts.first_trigger_character)
+                                .chain(opts.more_trigger_character.into_iter().flatten())
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+            }
+            "textDocument/linkedEditingRange" => self.linked_editing_range = register,
             "textDocument/rename" => self.rename = register,
             "textDocument/signatureHelp" => self.signature_help = register,
             "textDocument/inlayHint" => self.inlay_hints = register,
             "textDocument/foldingRange" => self.folding_ranges = register,
             "textDocument/documentHighlight" => self.document_highlight = register,
+            "textDocument/prepareCallHierarchy" => self.call_hierarchy = register,
+            "textDocument/prepareTypeHierarchy" => self.type_hierarchy = register,
+            "textDocument/selectionRange" => self.selection_range = register,
             "textDocument/codeAction" => {
                 self.code_action = register;
                 if register {
@@ -289,6 +328,23 @@ impl ServerCapabilitySummary {
                     self.code_action_resolve = false;
                 }
             }
+            "textDocument/codeLens" => {
+                self.code_lens = register;
+                self.code_lens_resolve = register
+                    && register_options
+                        .and_then(|opts| opts.get("resolveProvider"))
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false);
+            }
+            "textDocument/documentLink" => {
+                self.document_link = register;
+                self.document_link_resolve = register
+                    && register_options
+                        .and_then(|opts| opts.get("resolveProvider"))
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false);
+            }
+            "textDocument/documentColor" => self.document_color = register,
             "textDocument/documentSymbol" => self.document_symbols = register,
             "workspace/symbol" => self.workspace_symbols = register,
             "textDocument/diagnostic" => self.diagnostics = register,
@@ -333,7 +389,17 @@ impl ServerCapabilitySummary {
                     self.semantic_tokens_legend = None;
                 }
             }
-            _ => return false,
+            _ => {
+                // File operations carry their filters in the registration.
+                let options = register_options
+                    .filter(|_| register)
+                    .and_then(|opts| serde_json::from_value(opts.clone()).ok());
+                return file_operations::apply_registration(
+                    &mut self.file_operations,
+                    method,
+                    options,
+                );
+            }
         }
         true
     }
@@ -382,7 +448,15 @@ impl ServerHandle {
                 self.capabilities.semantic_tokens_full || self.capabilities.semantic_tokens_range
             }
             LspFeature::DocumentHighlight => self.capabilities.document_highlight,
+            LspFeature::CallHierarchy => self.capabilities.call_hierarchy,
+            LspFeature::TypeHierarchy => self.capabilities.type_hierarchy,
             LspFeature::CodeAction => self.capabilities.code_action,
+            LspFeature::CodeLens => self.capabilities.code_lens,
+            LspFeature::DocumentLink => self.capabilities.document_link,
+            LspFeature::DocumentColor => self.capabilities.document_color,
+            LspFeature::SelectionRange => self.capabilities.selection_range,
+            LspFeature::OnTypeFormatting => self.capabilities.on_type_formatting,
+            LspFeature::LinkedEditingRange => self.capabilities.linked_editing_range,
             LspFeature::DocumentSymbols => self.capabilities.document_symbols,
             LspFeature::WorkspaceSymbols => self.capabilities.workspace_symbols,
             LspFeature::Diagnostics => self.capabilities.diagnostics,
@@ -699,6 +773,18 @@ impl LspManager {
         })
     }
 
+    /// Check if a character triggers on-type formatting for the language's
+    /// on-type formatting server.
+    pub fn is_on_type_formatting_trigger_char(&self, ch: char, language: &str) -> bool {
+        let ch_str = ch.to_string();
+        self.handle_for_feature(language, LspFeature::OnTypeFormatting)
+            .is_some_and(|sh| {
+                sh.capabilities
+                    .on_type_formatting_trigger_characters
+                    .contains(&ch_str)
+            })
+    }
+
     /// Try to spawn an LSP server, checking auto_start configuration
     ///
     /// This is the main entry point for spawning LSP servers on file open.
@@ -991,6 +1077,28 @@ impl LspManager {
             .collect()
     }
 
+    /// The initialized servers that want to hear about `operation`, each
+    /// with the part of it their filters matched: before it happens
+    /// (`will`) or after.
+    pub fn file_operation_participants(
+        &self,
+        operation: &FileOperation,
+        will: bool,
+    ) -> Vec<(&ServerHandle, FileOperation)> {
+        self.handles
+            .iter()
+            .filter(|sh| sh.capabilities.initialized)
+            .filter_map(|sh| {
+                let options = file_operations::registration(
+                    &sh.capabilities.file_operations,
+                    operation.kind,
+                    will,
+                )?;
+                Some((sh, operation.filtered(options)?))
+            })
+            .collect()
+    }
+
     /// Get the language scope for a server by name.
     ///
     /// Returns `None` if the server is not found.
@@ -2339,6 +2447,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
         languages.insert(
@@ -2364,6 +2474,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
         languages.insert(
@@ -2389,6 +2501,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
         languages
@@ -2455,6 +2569,8 @@ mod tests {
                 on_save: vec![],
                 word_characters: None,
                 indent: None,
+                linked_editing: true,
+                on_type_formatting: true,
             },
         );
 
@@ -2571,6 +2687,8 @@ mod tests {
             on_save: vec![],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         };
         languages.insert(
             "c".to_string(),
@@ -2806,6 +2924,52 @@ mod tests {
         assert!(!caps.inlay_hints, "unregister must disable inlay hints");
     }
 
+    #[test]
+    fn dynamic_registration_of_code_lens_reads_resolve_provider() {
+        let mut caps = ServerCapabilitySummary::default();
+        let opts = serde_json::json!({ "resolveProvider": true });
+
+        assert!(caps.apply_dynamic_registration("textDocument/codeLens", Some(&opts), true));
+        assert!(caps.code_lens);
+        assert!(caps.code_lens_resolve);
+
+        assert!(caps.apply_dynamic_registration("textDocument/codeLens", None, false));
+        assert!(!caps.code_lens);
+        assert!(!caps.code_lens_resolve);
+    }
+
+    #[test]
+    fn dynamic_registration_of_document_link_reads_resolve_provider() {
+        let mut caps = ServerCapabilitySummary::default();
+        let opts = serde_json::json!({ "resolveProvider": true });
+
+        assert!(caps.apply_dynamic_registration("textDocument/documentLink", Some(&opts), true));
+        assert!(caps.document_link);
+        assert!(caps.document_link_resolve);
+        assert!(caps.apply_dynamic_registration("textDocument/documentColor", None, true));
+        assert!(caps.document_color);
+
+        assert!(caps.apply_dynamic_registration("textDocument/documentLink", None, false));
+        assert!(!caps.document_link);
+        assert!(!caps.document_link_resolve);
+    }
+
+    #[test]
+    fn dynamic_registration_of_file_operations_stores_filters() {
+        let mut caps = ServerCapabilitySummary::default();
+        let opts = serde_json::json!({
+            "filters": [{ "scheme": "file", "pattern": { "glob": "**/*.rs" } }]
+        });
+
+        assert!(caps.apply_dynamic_registration("workspace/willRenameFiles", Some(&opts), true));
+        let filters = &caps.file_operations.will_rename.as_ref().unwrap().filters;
+        assert_eq!(filters[0].pattern.glob, "**/*.rs");
+        assert!(caps.file_operations.did_rename.is_none());
+
+        assert!(caps.apply_dynamic_registration("workspace/willRenameFiles", None, false));
+        assert!(caps.file_operations.will_rename.is_none());
+    }
+
     #[test]
     fn dynamic_registration_ignores_unknown_methods() {
         // Methods we don't gate a feature on (e.g. file watching, handled
@@ -2838,6 +3002,28 @@ mod tests {
         assert!(caps.completion_trigger_characters.is_empty());
     }
 
+    #[test]
+    fn dynamic_registration_parses_on_type_formatting_triggers() {
+        let mut caps = ServerCapabilitySummary::default();
+        let opts = serde_json::json!({
+            "documentSelector": [{ "language": "go" }],
+            "firstTriggerCharacter": "\n",
+            "moreTriggerCharacter": [";", "}"],
+        });
+        let recognized =
+            caps.apply_dynamic_registration("textDocument/onTypeFormatting", Some(&opts), true);
+        assert!(recognized);
+        assert!(caps.on_type_formatting);
+        assert_eq!(
+            caps.on_type_formatting_trigger_characters,
+            vec!["\n", ";", "}"]
+        );
+
+        caps.apply_dynamic_registration("textDocument/onTypeFormatting", None, false);
+        assert!(!caps.on_type_formatting);
+        assert!(caps.on_type_formatting_trigger_characters.is_empty());
+    }
+
     #[test]
     fn dynamic_registration_parses_semantic_tokens_legend() {
         let mut caps = ServerCapabilitySummary::default();
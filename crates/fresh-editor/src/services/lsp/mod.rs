@@ -116,6 +116,7 @@
 
 pub mod async_handler;
 pub mod diagnostics;
+pub mod file_operations;
 pub mod manager;
 pub mod semantic_tokens;
 
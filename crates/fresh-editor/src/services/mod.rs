@@ -8,6 +8,7 @@ pub mod authority;
 pub mod clipboard;
 pub mod completion;
 pub mod counters;
+pub mod dap;
 pub mod env_provider;
 pub mod file_watcher;
 pub mod fs;
@@ -16,6 +17,7 @@ pub mod gpm;
 /// Outbound HTTP(S); the only place `ureq`/TLS is used (gated by `http`).
 pub mod http;
 pub mod live_grep_state;
+pub mod local_history;
 pub mod log_dirs;
 pub mod lsp;
 pub mod packages;
@@ -28,6 +30,7 @@ pub mod remote;
 pub mod signal_handler;
 pub mod status_log;
 pub mod styled_html;
+pub mod tasks;
 pub mod telemetry;
 pub mod terminal;
 pub mod terminal_modes;
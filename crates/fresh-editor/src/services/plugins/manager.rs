@@ -8,9 +8,9 @@
 use crate::config_io::DirectoryContext;
 use crate::input::command_registry::CommandRegistry;
 use fresh_core::config::PluginConfig;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 #[cfg(feature = "plugins")]
 use super::bridge::EditorServiceBridge;
@@ -33,6 +33,17 @@ pub struct PluginManager {
     /// integration tests in `tests/` can use it without an extra
     /// feature flag.
     pending_injected_commands: Vec<super::api::PluginCommand>,
+    /// Hooks recorded for remote-control subscribers (`fresh --remote`),
+    /// whether or not any plugin handles them. Empty unless someone
+    /// subscribed, so other hooks cost one lock.
+    hook_tap: Mutex<HookTap>,
+}
+
+/// The hooks to record and what fired since the last drain
+#[derive(Default)]
+struct HookTap {
+    names: HashSet<String>,
+    fired: Vec<(String, serde_json::Value)>,
 }
 
 impl PluginManager {
@@ -59,6 +70,7 @@ impl PluginManager {
                         return Self {
                             inner: Some(handle),
                             pending_injected_commands: Vec::new(),
+                            hook_tap: Mutex::default(),
                         }
                     }
                     Err(e) => {
@@ -73,6 +85,7 @@ impl PluginManager {
             Self {
                 inner: None,
                 pending_injected_commands: Vec::new(),
+                hook_tap: Mutex::default(),
             }
         }
 
@@ -87,6 +100,7 @@ impl PluginManager {
             Self {
                 _phantom: std::marker::PhantomData,
                 pending_injected_commands: Vec::new(),
+                hook_tap: Mutex::default(),
             }
         }
     }
@@ -243,6 +257,7 @@ impl PluginManager {
 
     /// Run a hook (fire-and-forget).
     pub fn run_hook(&self, hook_name: &str, args: super::hooks::HookArgs) {
+        self.tap_hook(hook_name, &args);
         #[cfg(feature = "plugins")]
         {
             if let Some(ref manager) = self.inner {
@@ -255,6 +270,32 @@ impl PluginManager {
         }
     }
 
+    /// Record `hook_name` firing if it's tapped.
+    fn tap_hook(&self, hook_name: &str, args: &super::hooks::HookArgs) {
+        let mut tap = self.hook_tap.lock().unwrap();
+        if !tap.names.contains(hook_name) {
+            return;
+        }
+        match super::hooks::hook_args_to_json(args) {
+            Ok(json) => tap.fired.push((hook_name.to_string(), json)),
+            Err(e) => tracing::warn!("Failed to serialize tapped hook {}: {}", hook_name, e),
+        }
+    }
+
+    /// Record the hooks in `names` as they fire, for
+    /// [`Self::take_tapped_hooks`]. Replaces the previous set.
+    pub fn set_tapped_hooks(&self, names: HashSet<String>) {
+        let mut tap = self.hook_tap.lock().unwrap();
+        tap.fired.retain(|(name, _)| names.contains(name));
+        tap.names = names;
+    }
+
+    /// The tapped hooks fired since the last call, oldest first, with
+    /// their arguments as JSON.
+    pub fn take_tapped_hooks(&self) -> Vec<(String, serde_json::Value)> {
+        std::mem::take(&mut self.hook_tap.lock().unwrap().fired)
+    }
+
     /// Run a hook in one plugin's context only (fire-and-forget).
     /// Handlers registered by other plugins are skipped.
     pub fn run_hook_for_plugin(&self, plugin: &str, hook_name: &str, args: super::hooks::HookArgs) {
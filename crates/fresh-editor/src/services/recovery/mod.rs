@@ -19,7 +19,8 @@
 //! ├── {hash}.meta.json       # Recovery metadata with chunk index
 //! ├── {hash}.chunk.0         # Chunk 0 binary content
 //! ├── {hash}.chunk.1         # Chunk 1 binary content
-//! └── ...
+//! ├── ...
+//! └── undo/                  # Persistent undo histories (see `undo_history`)
 //! ```
 //!
 //! ## Storage Format
@@ -51,6 +52,7 @@
 
 mod storage;
 pub mod types;
+mod undo_history;
 
 pub use storage::{RecoveryScope, RecoveryStorage};
 pub use types::{
@@ -58,6 +60,7 @@ pub use types::{
     InplaceWriteRecovery, RecoveryChunk, RecoveryEntry, RecoveryMetadata, RecoveryResult,
     SessionInfo, MAX_CHUNK_SIZE,
 };
+pub use undo_history::{content_hash, UndoHistoryStore, MAX_PERSISTED_UNDO_STEPS};
 
 use std::collections::HashMap;
 use std::io;
@@ -0,0 +1,289 @@
+//! Persistent undo history
+//!
+//! When `persistent_undo` is enabled, the undo history of a file is written
+//! here on every save and handed back to the event log when the file is
+//! opened again, so undo continues past an editor restart.
+//!
+//! ```text
+//! ~/.local/share/fresh/recovery/undo/
+//! └── {path_hash}.undo.json
+//! ```
+//!
+//! Histories are shared by every working directory and session, since they
+//! belong to the file rather than to a workspace. Files of a remote host are
+//! keyed by host and path, so they never share a history with a local file
+//! at the same path. Each one records a
+//! fingerprint of the file as saved; if the file changes outside the editor
+//! the fingerprint no longer matches and the history is discarded.
+//!
+//! Only edits are stored (see `EventLog::persistable_history`), never the
+//! buffer content, so a history stays small even for large files.
+
+use super::types::path_hash;
+use crate::model::event::Event;
+use crate::model::filesystem::FileSystem;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Bump when the persisted format changes; older files are discarded.
+const FORMAT_VERSION: u32 = 1;
+
+/// Maximum number of undo steps kept per file (the most recent ones).
+pub const MAX_PERSISTED_UNDO_STEPS: usize = 1000;
+
+/// Bytes read from each end of a large file to fingerprint it.
+const LARGE_FILE_SAMPLE_BYTES: usize = 64 * 1024;
+
+/// Undo history of one file, as written to disk
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedUndo {
+    version: u32,
+    /// Remote host of the file, `None` for a local file
+    #[serde(default)]
+    host: Option<String>,
+    /// File the history belongs to (guards against hash collisions)
+    file_path: PathBuf,
+    /// Fingerprint of the file content when the history was saved
+    content_hash: String,
+    /// Undo steps, oldest first
+    steps: Vec<Event>,
+}
+
+/// Reads and writes persisted undo histories of the files of one host
+#[derive(Debug, Clone)]
+pub struct UndoHistoryStore {
+    dir: PathBuf,
+    host: Option<String>,
+}
+
+impl UndoHistoryStore {
+    /// Store under the base recovery directory (not the per-session one),
+    /// for the files of the remote `host`, or local files if `None`.
+    pub fn new(recovery_dir: &Path, host: Option<&str>) -> Self {
+        Self {
+            dir: recovery_dir.join("undo"),
+            host: host.map(str::to_string),
+        }
+    }
+
+    fn history_path(&self, file_path: &Path) -> PathBuf {
+        let hash = match &self.host {
+            Some(host) => path_hash(Path::new(&format!("{}:{}", host, file_path.display()))),
+            None => path_hash(file_path),
+        };
+        self.dir.join(format!("{}.undo.json", hash))
+    }
+
+    /// Save the undo steps of `file_path`, whose content currently has the
+    /// fingerprint `content_hash`. An empty history removes the file.
+    pub fn save(
+        &self,
+        file_path: &Path,
+        content_hash: &str,
+        mut steps: Vec<Event>,
+    ) -> io::Result<()> {
+        if steps.is_empty() {
+            return self.delete(file_path);
+        }
+        if steps.len() > MAX_PERSISTED_UNDO_STEPS {
+            steps.drain(..steps.len() - MAX_PERSISTED_UNDO_STEPS);
+        }
+
+        let persisted = PersistedUndo {
+            version: FORMAT_VERSION,
+            host: self.host.clone(),
+            file_path: file_path.to_path_buf(),
+            content_hash: content_hash.to_string(),
+            steps,
+        };
+        let json = serde_json::to_vec(&persisted).map_err(io::Error::other)?;
+
+        fs::create_dir_all(&self.dir)?;
+        let target = self.history_path(file_path);
+        let temp_path = target.with_extension("tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, &target)
+    }
+
+    /// Load the undo steps of `file_path` if they were saved for content
+    /// with the fingerprint `content_hash`. A stale or unreadable history is
+    /// deleted.
+    pub fn load(&self, file_path: &Path, content_hash: &str) -> Option<Vec<Event>> {
+        let target = self.history_path(file_path);
+        let data = fs::read(&target).ok()?;
+        match serde_json::from_slice::<PersistedUndo>(&data) {
+            Ok(persisted)
+                if persisted.version == FORMAT_VERSION
+                    && persisted.host == self.host
+                    && persisted.file_path == file_path
+                    && persisted.content_hash == content_hash =>
+            {
+                Some(persisted.steps)
+            }
+            Ok(_) => {
+                tracing::debug!("Discarding stale undo history for {}", file_path.display());
+                #[allow(clippy::let_underscore_must_use)] // best-effort removal of stale history
+                let _ = fs::remove_file(&target);
+                None
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Discarding unreadable undo history {}: {}",
+                    target.display(),
+                    e
+                );
+                #[allow(clippy::let_underscore_must_use)] // best-effort removal of stale history
+                let _ = fs::remove_file(&target);
+                None
+            }
+        }
+    }
+
+    /// Delete the persisted history of `file_path`, if any.
+    pub fn delete(&self, file_path: &Path) -> io::Result<()> {
+        match fs::remove_file(self.history_path(file_path)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Fingerprint of the file at `path` as it is on disk.
+///
+/// Files up to `large_file_threshold` bytes are hashed in full. Larger files
+/// are only sampled at both ends, together with their size and modification
+/// time, so opening or saving them never reads the whole file.
+pub fn content_hash(
+    fs: &dyn FileSystem,
+    path: &Path,
+    large_file_threshold: u64,
+) -> io::Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let metadata = fs.metadata(path)?;
+    let mut hasher = Sha256::new();
+    if metadata.size <= large_file_threshold {
+        hasher.update(fs.read_file(path)?);
+    } else {
+        let sample = LARGE_FILE_SAMPLE_BYTES.min(metadata.size as usize);
+        hasher.update(metadata.size.to_le_bytes());
+        if let Some(mtime) = metadata
+            .modified
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        {
+            hasher.update(mtime.as_nanos().to_le_bytes());
+        }
+        hasher.update(fs.read_range(path, 0, sample)?);
+        hasher.update(fs.read_range(path, metadata.size - sample as u64, sample)?);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::event::CursorId;
+    use crate::model::filesystem::StdFileSystem;
+    use tempfile::TempDir;
+
+    fn insert(text: &str) -> Event {
+        Event::Insert {
+            position: 0,
+            text: text.to_string(),
+            cursor_id: CursorId(0),
+        }
+    }
+
+    fn texts(steps: &[Event]) -> Vec<String> {
+        steps
+            .iter()
+            .map(|e| match e {
+                Event::Insert { text, .. } => text.clone(),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let store = UndoHistoryStore::new(temp.path(), None);
+        let file = temp.path().join("a.txt");
+
+        store
+            .save(&file, "hash1", vec![insert("a"), insert("b")])
+            .unwrap();
+        assert!(temp.path().join("undo").is_dir());
+        assert_eq!(texts(&store.load(&file, "hash1").unwrap()), vec!["a", "b"]);
+
+        // Another file has no history.
+        assert!(store.load(&temp.path().join("b.txt"), "hash1").is_none());
+    }
+
+    #[test]
+    fn test_changed_content_discards_history() {
+        let temp = TempDir::new().unwrap();
+        let store = UndoHistoryStore::new(temp.path(), None);
+        let file = temp.path().join("a.txt");
+
+        store.save(&file, "hash1", vec![insert("a")]).unwrap();
+        assert!(store.load(&file, "hash2").is_none());
+        // The stale history is gone for good.
+        assert!(store.load(&file, "hash1").is_none());
+    }
+
+    #[test]
+    fn test_save_keeps_most_recent_steps() {
+        let temp = TempDir::new().unwrap();
+        let store = UndoHistoryStore::new(temp.path(), None);
+        let file = temp.path().join("a.txt");
+
+        let steps = (0..MAX_PERSISTED_UNDO_STEPS + 5)
+            .map(|i| insert(&i.to_string()))
+            .collect();
+        store.save(&file, "h", steps).unwrap();
+        let loaded = store.load(&file, "h").unwrap();
+        assert_eq!(loaded.len(), MAX_PERSISTED_UNDO_STEPS);
+        assert_eq!(texts(&loaded[..1]), vec!["5"]);
+
+        // Saving an empty history removes it.
+        store.save(&file, "h", Vec::new()).unwrap();
+        assert!(store.load(&file, "h").is_none());
+        store.delete(&file).unwrap();
+    }
+
+    #[test]
+    fn test_remote_host_keeps_separate_history() {
+        let temp = TempDir::new().unwrap();
+        let local = UndoHistoryStore::new(temp.path(), None);
+        let remote = UndoHistoryStore::new(temp.path(), Some("user@host"));
+        let file = temp.path().join("a.txt");
+
+        local.save(&file, "local", vec![insert("a")]).unwrap();
+        remote.save(&file, "remote", vec![insert("b")]).unwrap();
+
+        // Neither side discards the other's history as stale.
+        assert_eq!(texts(&local.load(&file, "local").unwrap()), vec!["a"]);
+        assert_eq!(texts(&remote.load(&file, "remote").unwrap()), vec!["b"]);
+    }
+
+    #[test]
+    fn test_content_hash_tracks_content() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        let filesystem = StdFileSystem;
+
+        fs::write(&file, "hello").unwrap();
+        let small = content_hash(&filesystem, &file, 1024).unwrap();
+        assert_eq!(small, content_hash(&filesystem, &file, 1024).unwrap());
+
+        fs::write(&file, "hellO").unwrap();
+        assert_ne!(small, content_hash(&filesystem, &file, 1024).unwrap());
+
+        // Large files are sampled rather than read in full.
+        let large = content_hash(&filesystem, &file, 2).unwrap();
+        assert_ne!(large, content_hash(&filesystem, &file, 1024).unwrap());
+    }
+}
@@ -23,6 +23,8 @@ cancelled = set()
 lock = threading.Lock()
 # Lock for serializing stdout writes (prevents interleaved JSON lines)
 write_lock = threading.Lock()
+# Active watches: request id -> stop Event
+watches = {}
 
 
 def send(id, **kw):
@@ -464,6 +466,247 @@ def cmd_search_file(id, p):
     })
 
 
+# === Change Notifications ===
+
+# inotify event masks (linux/inotify.h)
+IN_MODIFY = 0x00000002
+IN_ATTRIB = 0x00000004
+IN_CLOSE_WRITE = 0x00000008
+IN_MOVED_FROM = 0x00000040
+IN_MOVED_TO = 0x00000080
+IN_CREATE = 0x00000100
+IN_DELETE = 0x00000200
+IN_DELETE_SELF = 0x00000400
+IN_MOVE_SELF = 0x00000800
+IN_Q_OVERFLOW = 0x00004000
+IN_ISDIR = 0x40000000
+IN_NONBLOCK = 0x00000800
+IN_CLOEXEC = 0x00080000
+WATCH_MASK = (IN_MODIFY | IN_ATTRIB | IN_CLOSE_WRITE | IN_MOVED_FROM | IN_MOVED_TO
+              | IN_CREATE | IN_DELETE | IN_DELETE_SELF | IN_MOVE_SELF)
+
+
+def load_inotify():
+    """libc with the inotify calls, or None where they are unavailable."""
+    if not sys.platform.startswith("linux"):
+        return None
+    try:
+        import ctypes
+        import ctypes.util
+        libc = ctypes.CDLL(ctypes.util.find_library("c") or "libc.so.6", use_errno=True)
+        libc.inotify_init1.argtypes = [ctypes.c_int]
+        libc.inotify_add_watch.argtypes = [ctypes.c_int, ctypes.c_char_p, ctypes.c_uint32]
+        return libc
+    except (OSError, AttributeError):
+        return None
+
+
+def event_kind(mask):
+    """Map an inotify mask to the change kinds the editor knows."""
+    if mask & IN_CREATE:
+        return "create"
+    if mask & (IN_DELETE | IN_DELETE_SELF):
+        return "delete"
+    if mask & (IN_MOVED_FROM | IN_MOVED_TO | IN_MOVE_SELF):
+        return "rename"
+    return "modify"
+
+
+class InotifyWatcher:
+    """Kernel notifications for a path, and every directory below it when
+    recursive."""
+
+    backend = "inotify"
+
+    def __init__(self, libc, root, recursive):
+        self.libc = libc
+        self.root = root
+        self.recursive = recursive
+        self.wds = {}
+        self.fd = libc.inotify_init1(IN_NONBLOCK | IN_CLOEXEC)
+        if self.fd < 0:
+            import ctypes
+            raise OSError(ctypes.get_errno(), "inotify_init1 failed")
+        try:
+            self.add(root, required=True)
+            if recursive and os.path.isdir(root):
+                self.add_tree(root)
+        except Exception:
+            os.close(self.fd)
+            raise
+
+    def add(self, path, required=False):
+        wd = self.libc.inotify_add_watch(self.fd, os.fsencode(path), WATCH_MASK)
+        if wd < 0:
+            if required:
+                import ctypes
+                errno = ctypes.get_errno()
+                raise OSError(errno, os.strerror(errno), path)
+            return
+        self.wds[wd] = path
+
+    def add_tree(self, top):
+        for dirpath, dirnames, _ in os.walk(top):
+            for name in dirnames:
+                self.add(os.path.join(dirpath, name))
+
+    def run(self, id, stop):
+        import struct
+        header = struct.Struct("iIII")
+        while not stop.is_set():
+            readable, _, _ = select.select([self.fd], [], [], 0.2)
+            if not readable:
+                continue
+            try:
+                buf = os.read(self.fd, 65536)
+            except BlockingIOError:
+                continue
+            events = []
+            off = 0
+            while off + header.size <= len(buf):
+                wd, mask, _, length = header.unpack_from(buf, off)
+                name = buf[off + header.size:off + header.size + length].rstrip(b"\0")
+                off += header.size + length
+                if mask & IN_Q_OVERFLOW:
+                    # Events were dropped: everything may have changed.
+                    events.append({"path": self.root, "kind": "other"})
+                    continue
+                base = self.wds.get(wd)
+                if base is None:
+                    continue
+                path = os.path.join(base, os.fsdecode(name)) if name else base
+                if self.recursive and mask & IN_ISDIR and mask & (IN_CREATE | IN_MOVED_TO):
+                    self.add(path)
+                    self.add_tree(path)
+                events.append({"path": path, "kind": event_kind(mask)})
+            send_events(id, events)
+
+    def close(self):
+        os.close(self.fd)
+
+
+def snapshot(root, recursive):
+    """(mtime_ns, size) of `root` and of the entries below it."""
+    snap = {}
+    try:
+        st = os.stat(root)
+    except OSError:
+        return snap
+    snap[root] = (st.st_mtime_ns, st.st_size)
+    if not stat.S_ISDIR(st.st_mode):
+        return snap
+    stack = [root]
+    while stack:
+        d = stack.pop()
+        try:
+            entries = list(os.scandir(d))
+        except OSError:
+            continue
+        for entry in entries:
+            try:
+                st = entry.stat(follow_symlinks=False)
+            except OSError:
+                continue
+            snap[entry.path] = (st.st_mtime_ns, st.st_size)
+            if recursive and stat.S_ISDIR(st.st_mode):
+                stack.append(entry.path)
+    return snap
+
+
+class PollWatcher:
+    """mtime scans of a path for hosts without inotify."""
+
+    backend = "poll"
+
+    def __init__(self, root, recursive, interval):
+        self.root = root
+        self.recursive = recursive
+        self.interval = interval
+        self.snap = snapshot(root, recursive)
+        if not self.snap:
+            raise FileNotFoundError(root)
+
+    def run(self, id, stop):
+        while not stop.wait(self.interval):
+            snap = snapshot(self.root, self.recursive)
+            events = [{"path": p, "kind": "create"} for p in snap if p not in self.snap]
+            events += [{"path": p, "kind": "delete"} for p in self.snap if p not in snap]
+            events += [{"path": p, "kind": "modify"} for p, v in snap.items()
+                       if p in self.snap and self.snap[p] != v]
+            self.snap = snap
+            send_events(id, events)
+
+    def close(self):
+        pass
+
+
+def send_events(id, events):
+    """Send a batch of change events, dropping repeats of a path and kind."""
+    seen = set()
+    unique = []
+    for event in events:
+        key = (event["path"], event["kind"])
+        if key not in seen:
+            seen.add(key)
+            unique.append(event)
+    if unique:
+        send(id, d={"events": unique})
+
+
+def cmd_watch(id, p):
+    """Stream change events for a path until `unwatch` or `cancel`.
+
+    Uses inotify when the host has it and falls back to scanning mtimes
+    every `interval` seconds. The first data message names the backend;
+    each later one carries `{"events": [{"path", "kind"}]}` with kind one
+    of create, modify, delete, rename or other.
+    """
+    path = validate_path(p["path"])
+    recursive = p.get("recursive", False)
+    libc = None if p.get("poll") else load_inotify()
+    watcher = None
+    if libc is not None:
+        try:
+            watcher = InotifyWatcher(libc, path, recursive)
+        except FileNotFoundError:
+            raise
+        except OSError:
+            # Watch limit reached or inotify disabled: scan instead.
+            watcher = None
+    if watcher is None:
+        watcher = PollWatcher(path, recursive, p.get("interval", 1.0))
+
+    stop = threading.Event()
+    with lock:
+        watches[id] = stop
+    send(id, d={"backend": watcher.backend})
+
+    def run():
+        try:
+            watcher.run(id, stop)
+            send(id, r={})
+        except Exception as e:
+            send(id, e=str(e))
+        finally:
+            watcher.close()
+            with lock:
+                watches.pop(id, None)
+                cancelled.discard(id)
+
+    threading.Thread(target=run, daemon=True).start()
+
+
+def cmd_unwatch(id, p):
+    """Stop a watch; it ends with an empty result."""
+    with lock:
+        stop = watches.get(p["id"])
+    if stop:
+        stop.set()
+        send(id, r={})
+    else:
+        send(id, e="watch not found")
+
+
 # === Process Operations ===
 
 
@@ -556,8 +799,11 @@ def cmd_cancel(id, p):
 
     with lock:
         proc = procs.get(target_id)
+        stop = watches.get(target_id)
     if proc:
         proc.terminate()
+    if stop:
+        stop.set()
 
     send(id, r={})
 
@@ -643,6 +889,8 @@ METHODS = {
     "exists": cmd_exists,
     "info": cmd_info,
     "search_file": cmd_search_file,
+    "watch": cmd_watch,
+    "unwatch": cmd_unwatch,
     "exec": cmd_exec,
     "kill": cmd_kill,
     "cancel": cmd_cancel,
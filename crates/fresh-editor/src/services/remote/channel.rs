@@ -331,6 +331,12 @@ impl AgentChannel {
         self.connected.load(Ordering::SeqCst)
     }
 
+    /// Runtime the channel's tasks run on, for work that outlives a
+    /// single blocking call.
+    pub(crate) fn runtime_handle(&self) -> &tokio::runtime::Handle {
+        &self.runtime_handle
+    }
+
     /// Replace the underlying transport with a new reader/writer pair.
     ///
     /// This is used for reconnection: after establishing a new SSH connection,
@@ -438,6 +444,26 @@ impl AgentChannel {
             oneshot::Receiver<Result<serde_json::Value, String>>,
         ),
         ChannelError,
+    > {
+        let (_, data_rx, result_rx) = self.request_streaming_with_id(method, params).await?;
+        Ok((data_rx, result_rx))
+    }
+
+    /// Like `request_streaming`, also returning the request's ID so a
+    /// long-lived stream (e.g. `watch`) can be ended by a later request
+    /// naming it.
+    #[allow(clippy::type_complexity)]
+    pub async fn request_streaming_with_id(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<
+        (
+            u64,
+            mpsc::Receiver<serde_json::Value>,
+            oneshot::Receiver<Result<serde_json::Value, String>>,
+        ),
+        ChannelError,
     > {
         if !self.is_connected() {
             return Err(ChannelError::ChannelClosed);
@@ -462,7 +488,7 @@ impl AgentChannel {
             .await
             .map_err(|_| ChannelError::ChannelClosed)?;
 
-        Ok((data_rx, result_rx))
+        Ok((id, data_rx, result_rx))
     }
 
     /// Send a request synchronously (blocking)
@@ -3,19 +3,25 @@
 //! Implements the FileSystem trait for remote operations via SSH agent.
 
 use crate::model::filesystem::{
-    DirEntry, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter, WriteOp,
+    DirEntry, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter,
+    FsChangeCallback, FsWatch, FsWatchStarter, WriteOp,
 };
+use crate::services::async_bridge::PathChangeKind;
 use crate::services::remote::channel::{AgentChannel, ChannelError};
 use crate::services::remote::protocol::{
     append_params, count_lf_params, decode_base64, ls_params, patch_params, read_params,
-    stat_params, sudo_write_params, truncate_params, write_params, PatchOp, RemoteDirEntry,
-    RemoteMetadata,
+    stat_params, sudo_write_params, truncate_params, unwatch_params, watch_params, write_params,
+    PatchOp, RemoteDirEntry, RemoteMetadata,
 };
 use std::io::{self, Cursor, Read, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, UNIX_EPOCH};
 
+/// How often a watch whose connection dropped checks for the reconnect.
+const WATCH_RECONNECT_POLL: Duration = Duration::from_secs(1);
+
 /// Remote filesystem that communicates with the Python agent
 pub struct RemoteFileSystem {
     channel: Arc<AgentChannel>,
@@ -652,6 +658,141 @@ impl FileSystem for RemoteFileSystem {
         drop(result_rx.blocking_recv());
         Ok(())
     }
+
+    fn watch(
+        &self,
+        path: &Path,
+        recursive: bool,
+        on_change: FsChangeCallback,
+    ) -> io::Result<Option<FsWatch>> {
+        let watch = Arc::new(RemoteWatch {
+            channel: self.channel.clone(),
+            root: path.to_path_buf(),
+            params: watch_params(&path.to_string_lossy(), recursive),
+            request_id: AtomicU64::new(0),
+            stopped: AtomicBool::new(false),
+        });
+        // Don't wait for the agent here: callers are on the event loop.
+        // The watch reports `Running` once the agent accepts it.
+        let (fs_watch, starter) = FsWatch::starting({
+            let watch = watch.clone();
+            move || watch.stop()
+        });
+        self.channel
+            .runtime_handle()
+            .spawn(watch.run(on_change, starter));
+        Ok(Some(fs_watch))
+    }
+}
+
+/// A `watch` request kept running on the agent for [`FsWatch`].
+struct RemoteWatch {
+    channel: Arc<AgentChannel>,
+    root: PathBuf,
+    params: serde_json::Value,
+    /// ID of the live `watch` request, 0 before the first is sent.
+    request_id: AtomicU64,
+    stopped: AtomicBool,
+}
+
+impl RemoteWatch {
+    /// Forward the agent's events to `on_change` until stopped. A watch
+    /// lost to a dropped connection is sent again once the channel
+    /// reconnects, and the root is reported as changed since events from
+    /// the outage are gone.
+    async fn run(self: Arc<Self>, on_change: FsChangeCallback, starter: FsWatchStarter) {
+        let mut started = false;
+        loop {
+            let outcome: Result<(), ChannelError> = match self
+                .channel
+                .request_streaming_with_id("watch", self.params.clone())
+                .await
+            {
+                Ok((id, mut data_rx, result_rx)) => {
+                    self.request_id.store(id, Ordering::SeqCst);
+                    // Stopped before the ID was known: end it now.
+                    if self.stopped.load(Ordering::SeqCst) {
+                        self.send_unwatch(id);
+                    }
+                    while let Some(data) = data_rx.recv().await {
+                        if !started {
+                            // The first message names the backend.
+                            started = true;
+                            starter.started();
+                        }
+                        for (path, kind) in parse_watch_events(&data) {
+                            on_change(path, kind);
+                        }
+                    }
+                    match result_rx.await {
+                        Ok(Ok(_)) => return,
+                        Ok(Err(e)) if e != "connection closed" => Err(ChannelError::Remote(e)),
+                        _ => Err(ChannelError::ChannelClosed),
+                    }
+                }
+                Err(e) => Err(e),
+            };
+            if self.stopped.load(Ordering::SeqCst) {
+                return;
+            }
+            match (outcome, started) {
+                // Never started (e.g. the path doesn't exist): callers
+                // keep polling it.
+                (Err(e), false) => {
+                    tracing::debug!("watch {} did not start: {}", self.root.display(), e);
+                    starter.failed();
+                    return;
+                }
+                // Rejected after a reconnect (e.g. the path is gone).
+                (Err(ChannelError::Remote(e)), true) => {
+                    tracing::debug!("watch {} ended: {}", self.root.display(), e);
+                    on_change(self.root.clone(), PathChangeKind::Other);
+                    return;
+                }
+                _ => {}
+            }
+            while !self.channel.is_connected() {
+                tokio::time::sleep(WATCH_RECONNECT_POLL).await;
+                if self.stopped.load(Ordering::SeqCst) {
+                    return;
+                }
+            }
+            on_change(self.root.clone(), PathChangeKind::Other);
+        }
+    }
+
+    fn stop(&self) {
+        if self.stopped.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let id = self.request_id.load(Ordering::SeqCst);
+        if id != 0 {
+            self.send_unwatch(id);
+        }
+    }
+
+    fn send_unwatch(&self, id: u64) {
+        let channel = self.channel.clone();
+        self.channel.runtime_handle().spawn(async move {
+            if let Err(e) = channel.request("unwatch", unwatch_params(id)).await {
+                tracing::debug!("unwatch {}: {}", id, e);
+            }
+        });
+    }
+}
+
+/// `(path, kind)` of each event in a `watch` data message.
+fn parse_watch_events(data: &serde_json::Value) -> Vec<(PathBuf, PathChangeKind)> {
+    data.get("events")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|event| {
+            let path = event.get("path")?.as_str()?;
+            let kind = event.get("kind").and_then(|k| k.as_str()).unwrap_or("");
+            Some((PathBuf::from(path), PathChangeKind::parse(kind)))
+        })
+        .collect()
 }
 
 /// Remote file reader - wraps in-memory data
@@ -816,4 +957,21 @@ mod tests {
         assert_eq!(entry.entry_type, EntryType::File);
         assert!(!entry.is_symlink());
     }
+
+    #[test]
+    fn test_parse_watch_events() {
+        let data = serde_json::json!({"events": [
+            {"path": "/w/a.rs", "kind": "modify"},
+            {"path": "/w/b.rs", "kind": "delete"},
+            {"kind": "create"},
+        ]});
+        let events = parse_watch_events(&data);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].0, PathBuf::from("/w/a.rs"));
+        assert!(matches!(events[0].1, PathChangeKind::Modify));
+        assert!(matches!(events[1].1, PathChangeKind::Delete));
+
+        // The backend announcement carries no events.
+        assert!(parse_watch_events(&serde_json::json!({"backend": "poll"})).is_empty());
+    }
 }
@@ -209,6 +209,19 @@ pub fn cancel_params(request_id: u64) -> serde_json::Value {
     serde_json::json!({"id": request_id})
 }
 
+/// Build params for watch request
+pub fn watch_params(path: &str, recursive: bool) -> serde_json::Value {
+    serde_json::json!({
+        "path": path,
+        "recursive": recursive
+    })
+}
+
+/// Build params for unwatch request
+pub fn unwatch_params(watch_id: u64) -> serde_json::Value {
+    serde_json::json!({"id": watch_id})
+}
+
 /// Build params for append request
 pub fn append_params(path: &str, data: &[u8]) -> serde_json::Value {
     serde_json::json!({
@@ -138,6 +138,16 @@ fn test_cancel_params() {
     assert_eq!(params["id"], 42);
 }
 
+#[test]
+fn test_watch_params() {
+    let params = watch_params("/home/user/project", true);
+    assert_eq!(params["path"], "/home/user/project");
+    assert_eq!(params["recursive"], true);
+
+    let params = unwatch_params(7);
+    assert_eq!(params["id"], 7);
+}
+
 #[test]
 fn test_remote_dir_entry_parsing() {
     let json = r#"{
@@ -429,6 +439,41 @@ mod agent_integration {
         assert!(!path.contains('~'));
     }
 
+    /// Without inotify the agent scans mtimes; a new file shows up as a
+    /// `create` event on the watch's stream.
+    #[test]
+    fn test_agent_watch_poll_fallback() {
+        let Some((mut stdin, mut stdout)) = spawn_agent() else {
+            eprintln!("Skipping test: Python3 not available");
+            return;
+        };
+        let dir = tempfile::tempdir().unwrap();
+        // Events carry the agent's canonical paths.
+        let root = dir.path().canonicalize().unwrap();
+
+        // Read ready message
+        let mut line = String::new();
+        stdout.read_line(&mut line).unwrap();
+
+        let mut params = watch_params(&root.to_string_lossy(), false);
+        params["poll"] = serde_json::json!(true);
+        params["interval"] = serde_json::json!(0.05);
+        let resp = send_request(&mut stdin, &mut stdout, "watch", params).unwrap();
+        assert_eq!(resp.data.unwrap()["backend"], "poll");
+
+        let file = root.join("new.txt");
+        std::fs::write(&file, "hello").unwrap();
+        let mut line = String::new();
+        stdout.read_line(&mut line).unwrap();
+        let resp: AgentResponse = serde_json::from_str(&line).unwrap();
+        let events = resp.data.unwrap()["events"].clone();
+        assert!(events
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|e| e["kind"] == "create" && e["path"].as_str() == file.to_str()));
+    }
+
     #[test]
     fn test_agent_unknown_method() {
         let Some((mut stdin, mut stdout)) = spawn_agent() else {
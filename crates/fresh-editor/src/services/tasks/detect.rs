@@ -0,0 +1,127 @@
+//! Tasks found in a project without any configuration: the usual Cargo
+//! commands next to a `Cargo.toml`, one per script in `package.json`, and
+//! one per target of a Makefile.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::model::filesystem::FileSystem;
+use crate::types::TaskConfig;
+
+const CARGO_COMMANDS: &[&str] = &["build", "check", "clippy", "test", "run"];
+
+const MAKEFILES: &[&str] = &["GNUmakefile", "makefile", "Makefile"];
+
+/// Detect the tasks of the project rooted at `root`, keyed by name.
+pub fn detect_tasks(fs: &dyn FileSystem, root: &Path) -> HashMap<String, TaskConfig> {
+    let mut tasks = HashMap::new();
+    let task = |command: String, matcher: &str| TaskConfig {
+        command,
+        problem_matchers: vec![matcher.to_string()],
+        ..Default::default()
+    };
+
+    if fs.exists(&root.join("Cargo.toml")) {
+        for sub in CARGO_COMMANDS {
+            let command = format!("cargo {}", sub);
+            tasks.insert(command.clone(), task(command, "rustc"));
+        }
+    }
+
+    if let Some(scripts) = read(fs, &root.join("package.json")).and_then(|s| npm_scripts(&s)) {
+        let runner = if fs.exists(&root.join("pnpm-lock.yaml")) {
+            "pnpm"
+        } else if fs.exists(&root.join("yarn.lock")) {
+            "yarn"
+        } else {
+            "npm"
+        };
+        for script in scripts {
+            let command = format!("{} run {}", runner, script);
+            tasks.insert(command.clone(), task(command, "tsc"));
+        }
+    }
+
+    if let Some(makefile) = MAKEFILES.iter().find_map(|name| read(fs, &root.join(name))) {
+        for target in make_targets(&makefile) {
+            let command = format!("make {}", target);
+            tasks.insert(command.clone(), task(command, "gcc"));
+        }
+    }
+
+    tasks
+}
+
+fn read(fs: &dyn FileSystem, path: &Path) -> Option<String> {
+    let bytes = fs.read_file(path).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Names of the `scripts` in a `package.json`.
+fn npm_scripts(package_json: &str) -> Option<Vec<String>> {
+    let value: serde_json::Value = serde_json::from_str(package_json).ok()?;
+    let scripts = value.get("scripts")?.as_object()?;
+    Some(scripts.keys().cloned().collect())
+}
+
+/// Explicit targets of a Makefile, in order of appearance. Special
+/// targets (`.PHONY`), pattern rules and variable assignments are left
+/// out.
+fn make_targets(makefile: &str) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for line in makefile.lines() {
+        if line.starts_with(|c: char| c.is_whitespace() || c == '#') {
+            continue;
+        }
+        let Some((names, rest)) = line.split_once(':') else {
+            continue;
+        };
+        // `X := y`, `X ::= y` and `X = a:b` are assignments.
+        if rest.starts_with('=') || rest.starts_with(":=") || names.contains('=') {
+            continue;
+        }
+        for name in names.split_whitespace() {
+            let special = name.starts_with('.') || name.contains(['%', '$']);
+            if !special && !targets.iter().any(|t| t == name) {
+                targets.push(name.to_string());
+            }
+        }
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn make_targets_skip_special_rules_and_assignments() {
+        let makefile = "\
+CC := gcc
+FLAGS = -O2 -DX=a:b
+.PHONY: all clean
+# comment: not a target
+all: app
+app lib: main.o
+\t$(CC) -o app main.o
+%.o: %.c
+\t$(CC) -c $<
+clean:
+\trm -f app
+install:: app
+";
+        assert_eq!(
+            make_targets(makefile),
+            vec!["all", "app", "lib", "clean", "install"]
+        );
+    }
+
+    #[test]
+    fn npm_scripts_are_listed() {
+        let json = r#"{"name": "x", "scripts": {"build": "tsc", "test": "jest"}}"#;
+        let mut scripts = npm_scripts(json).unwrap();
+        scripts.sort();
+        assert_eq!(scripts, vec!["build", "test"]);
+        assert!(npm_scripts(r#"{"name": "x"}"#).is_none());
+    }
+}
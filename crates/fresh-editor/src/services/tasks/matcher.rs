@@ -0,0 +1,397 @@
+//! Problem matchers: turning task output into diagnostics.
+//!
+//! A matcher is a sequence of regular expressions matched against
+//! consecutive output lines (rustc puts the message and the location on
+//! separate lines; most compilers need one). Named groups pick out the
+//! fields of the problem. Vim-style errorformats are translated into the
+//! same form.
+
+use std::collections::HashMap;
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+use regex::Regex;
+
+use crate::types::{ProblemMatcherConfig, ProblemSeverity};
+
+/// Matchers available to every task without configuration.
+const BUILTIN_MATCHERS: &[(&str, &[&str])] = &[
+    (
+        "rustc",
+        &[
+            r"^(?P<severity>error|warning)(?:\[(?P<code>[^\]]+)\])?: (?P<message>.*)$",
+            r"^\s*--> (?P<file>.+?):(?P<line>\d+):(?P<column>\d+)$",
+        ],
+    ),
+    (
+        "gcc",
+        &[
+            r"^(?P<file>[^:\s][^:]*):(?P<line>\d+):(?:(?P<column>\d+):)? (?:fatal )?(?P<severity>error|warning|note): (?P<message>.*)$",
+        ],
+    ),
+    (
+        "tsc",
+        &[
+            r"^(?P<file>[^\s(][^(]*)\((?P<line>\d+),(?P<column>\d+)\): (?P<severity>error|warning) (?P<code>TS\d+): (?P<message>.*)$",
+        ],
+    ),
+    (
+        "go",
+        &[r"^(?P<file>[^:\s]+\.go):(?P<line>\d+):(?:(?P<column>\d+):)? (?P<message>.*)$"],
+    ),
+];
+
+const FIELDS: &[&str] = &[
+    "file",
+    "line",
+    "column",
+    "end_line",
+    "end_column",
+    "severity",
+    "code",
+    "message",
+];
+
+/// A compiled problem matcher.
+#[derive(Debug, Clone)]
+pub struct ProblemMatcher {
+    source: String,
+    patterns: Vec<Regex>,
+    severity: ProblemSeverity,
+}
+
+/// A problem found in task output. `file` is as the tool printed it and
+/// may be relative to the task's working directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub file: String,
+    pub diagnostic: Diagnostic,
+}
+
+impl ProblemMatcher {
+    /// Look `name` up among the configured matchers, then the built-in
+    /// ones, and compile it.
+    pub fn resolve(
+        name: &str,
+        configured: &HashMap<String, ProblemMatcherConfig>,
+    ) -> Result<Self, String> {
+        if let Some(config) = configured.get(name) {
+            return Self::compile(name, config);
+        }
+        let (_, patterns) = BUILTIN_MATCHERS
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .ok_or_else(|| format!("unknown problem matcher '{}'", name))?;
+        Self::compile(
+            name,
+            &ProblemMatcherConfig {
+                pattern: patterns.iter().map(|p| p.to_string()).collect(),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Compile `config`, named `name` for error messages and the default
+    /// diagnostic source.
+    pub fn compile(name: &str, config: &ProblemMatcherConfig) -> Result<Self, String> {
+        let sources = match &config.errorformat {
+            Some(format) => vec![errorformat_to_regex(format)
+                .map_err(|e| format!("problem matcher '{}': {}", name, e))?],
+            None => config.pattern.clone(),
+        };
+        if sources.is_empty() {
+            return Err(format!("problem matcher '{}' has no pattern", name));
+        }
+        let patterns = sources
+            .iter()
+            .map(|p| Regex::new(p).map_err(|e| format!("problem matcher '{}': {}", name, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let has_group = |field: &str| {
+            patterns
+                .iter()
+                .any(|re| re.capture_names().flatten().any(|n| n == field))
+        };
+        for field in ["file", "line", "message"] {
+            if !has_group(field) {
+                return Err(format!(
+                    "problem matcher '{}' does not capture '{}'",
+                    name, field
+                ));
+            }
+        }
+        Ok(Self {
+            source: config.source.clone().unwrap_or_else(|| name.to_string()),
+            patterns,
+            severity: config.severity,
+        })
+    }
+}
+
+/// A matcher being fed one task's output, line by line.
+#[derive(Debug, Clone)]
+pub struct MatcherState {
+    matcher: ProblemMatcher,
+    /// Index of the pattern the next line has to match
+    step: usize,
+    captured: HashMap<&'static str, String>,
+}
+
+impl MatcherState {
+    pub fn new(matcher: ProblemMatcher) -> Self {
+        Self {
+            matcher,
+            step: 0,
+            captured: HashMap::new(),
+        }
+    }
+
+    /// Feed one output line. Returns a problem when it completes one.
+    pub fn feed(&mut self, line: &str) -> Option<Problem> {
+        let line = line.trim_end_matches('\r');
+        if self.step > 0 && !self.advance(line) {
+            // The sequence broke off; the line may start a new one.
+            self.reset();
+        }
+        if self.step == 0 && !self.advance(line) {
+            return None;
+        }
+        if self.step < self.matcher.patterns.len() {
+            return None;
+        }
+        let problem = self.build();
+        self.reset();
+        problem
+    }
+
+    /// Match `line` against the current pattern, keeping its captures.
+    fn advance(&mut self, line: &str) -> bool {
+        let Some(caps) = self.matcher.patterns[self.step].captures(line) else {
+            return false;
+        };
+        for field in FIELDS {
+            if let Some(m) = caps.name(field) {
+                self.captured.insert(*field, m.as_str().to_string());
+            }
+        }
+        self.step += 1;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.step = 0;
+        self.captured.clear();
+    }
+
+    fn build(&self) -> Option<Problem> {
+        let number = |field: &str| -> Option<u32> { self.captured.get(field)?.parse().ok() };
+        let file = self.captured.get("file")?.trim().to_string();
+        // Tools count from 1, LSP from 0.
+        let line = number("line")?.saturating_sub(1);
+        let range = match number("column") {
+            Some(column) => {
+                let start = Position::new(line, column.saturating_sub(1));
+                let end_line = number("end_line").map_or(line, |l| l.saturating_sub(1));
+                let end = match number("end_column") {
+                    Some(end_column) => Position::new(end_line, end_column.saturating_sub(1)),
+                    None => Position::new(end_line, start.character + 1),
+                };
+                Range::new(start, end)
+            }
+            // No column: the whole line.
+            None => Range::new(Position::new(line, 0), Position::new(line + 1, 0)),
+        };
+        let severity = self
+            .captured
+            .get("severity")
+            .and_then(|s| parse_severity(s))
+            .unwrap_or(self.matcher.severity);
+        Some(Problem {
+            file,
+            diagnostic: Diagnostic {
+                range,
+                severity: Some(to_lsp_severity(severity)),
+                code: self
+                    .captured
+                    .get("code")
+                    .cloned()
+                    .map(NumberOrString::String),
+                source: Some(self.matcher.source.clone()),
+                message: self.captured.get("message")?.trim().to_string(),
+                ..Default::default()
+            },
+        })
+    }
+}
+
+/// Read a severity word (or errorformat `%t` letter) from tool output.
+fn parse_severity(text: &str) -> Option<ProblemSeverity> {
+    match text.trim().chars().next()?.to_ascii_lowercase() {
+        'e' | 'f' => Some(ProblemSeverity::Error),
+        'w' => Some(ProblemSeverity::Warning),
+        'i' | 'n' => Some(ProblemSeverity::Info),
+        'h' => Some(ProblemSeverity::Hint),
+        _ => None,
+    }
+}
+
+fn to_lsp_severity(severity: ProblemSeverity) -> DiagnosticSeverity {
+    match severity {
+        ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+        ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+        ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+        ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+    }
+}
+
+/// Translate a single-line Vim errorformat into an anchored regex with
+/// the named groups [`MatcherState`] reads.
+fn errorformat_to_regex(format: &str) -> Result<String, String> {
+    let mut out = String::from("^");
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push_str(&regex::escape(&c.to_string()));
+            continue;
+        }
+        match chars.next() {
+            Some('f') => out.push_str(r"(?P<file>.+?)"),
+            Some('l') => out.push_str(r"(?P<line>\d+)"),
+            Some('c') => out.push_str(r"(?P<column>\d+)"),
+            Some('t') => out.push_str(r"(?P<severity>[A-Za-z])"),
+            Some('n') => out.push_str(r"(?P<code>\d+)"),
+            Some('m') => out.push_str(r"(?P<message>.*)"),
+            Some('%') => out.push('%'),
+            // `%*{conversion}`: match and skip.
+            Some('*') => match chars.next() {
+                Some('[') => {
+                    let mut class = String::from("[");
+                    loop {
+                        match chars.next() {
+                            Some(']') if class.len() > 1 && class != "[^" => break,
+                            Some(c) => class.push(c),
+                            None => return Err("unterminated %*[".to_string()),
+                        }
+                    }
+                    class.push(']');
+                    out.push_str(&format!("(?:{}+)", class));
+                }
+                Some('d') => out.push_str(r"\d+"),
+                Some('s') => out.push_str(r"\S+"),
+                other => return Err(format!("unsupported %*{}", other.unwrap_or(' '))),
+            },
+            Some(other) => return Err(format!("unsupported %{}", other)),
+            None => return Err("trailing %".to_string()),
+        }
+    }
+    out.push('$');
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(matcher: ProblemMatcher, output: &str) -> Vec<Problem> {
+        let mut state = MatcherState::new(matcher);
+        output.lines().filter_map(|line| state.feed(line)).collect()
+    }
+
+    fn builtin(name: &str) -> ProblemMatcher {
+        ProblemMatcher::resolve(name, &HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn rustc_spans_two_lines() {
+        let output = "   Compiling demo v0.1.0\n\
+                      error[E0425]: cannot find value `x` in this scope\n\
+                      \x20--> src/main.rs:2:13\n\
+                      \x20 |\n\
+                      warning: unused variable: `y`\n\
+                      \x20 --> src/lib.rs:10:9\n\
+                      error: could not compile `demo`\n";
+        let problems = run(builtin("rustc"), output);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].file, "src/main.rs");
+        let d = &problems[0].diagnostic;
+        assert_eq!(d.range.start, Position::new(1, 12));
+        assert_eq!(d.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(d.code, Some(NumberOrString::String("E0425".to_string())));
+        assert_eq!(d.message, "cannot find value `x` in this scope");
+        assert_eq!(d.source.as_deref(), Some("rustc"));
+        assert_eq!(problems[1].file, "src/lib.rs");
+        assert_eq!(
+            problems[1].diagnostic.severity,
+            Some(DiagnosticSeverity::WARNING)
+        );
+    }
+
+    #[test]
+    fn broken_sequence_restarts_on_the_same_line() {
+        let output = "error: first\nerror: second\n --> a.rs:1:1\n";
+        let problems = run(builtin("rustc"), output);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].diagnostic.message, "second");
+    }
+
+    #[test]
+    fn gcc_without_column_covers_the_line() {
+        let problems = run(builtin("gcc"), "main.c:7: warning: implicit declaration\n");
+        assert_eq!(problems.len(), 1);
+        let range = problems[0].diagnostic.range;
+        assert_eq!(range.start, Position::new(6, 0));
+        assert_eq!(range.end, Position::new(7, 0));
+    }
+
+    #[test]
+    fn tsc_reports_code() {
+        let problems = run(
+            builtin("tsc"),
+            "src/app.ts(3,5): error TS2322: Type 'string' is not assignable.\n",
+        );
+        assert_eq!(problems[0].file, "src/app.ts");
+        assert_eq!(
+            problems[0].diagnostic.code,
+            Some(NumberOrString::String("TS2322".to_string()))
+        );
+    }
+
+    #[test]
+    fn errorformat_translates_to_groups() {
+        let config = ProblemMatcherConfig {
+            errorformat: Some("%f:%l:%c: %t%*[^:]: %m".to_string()),
+            severity: ProblemSeverity::Hint,
+            ..Default::default()
+        };
+        let matcher = ProblemMatcher::compile("lint", &config).unwrap();
+        let problems = run(matcher, "a b.py:4:2: Warning: shadowed name\nnoise\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].file, "a b.py");
+        assert_eq!(problems[0].diagnostic.range.start, Position::new(3, 1));
+        assert_eq!(
+            problems[0].diagnostic.severity,
+            Some(DiagnosticSeverity::WARNING)
+        );
+        assert_eq!(problems[0].diagnostic.message, "shadowed name");
+    }
+
+    #[test]
+    fn default_severity_applies_without_group() {
+        let problems = run(builtin("go"), "./main.go:3:2: undefined: foo\n");
+        assert_eq!(
+            problems[0].diagnostic.severity,
+            Some(DiagnosticSeverity::ERROR)
+        );
+    }
+
+    #[test]
+    fn rejects_matchers_without_required_groups() {
+        let config = ProblemMatcherConfig {
+            pattern: vec![r"^(?P<file>\S+): (?P<message>.*)$".to_string()],
+            ..Default::default()
+        };
+        assert!(ProblemMatcher::compile("bad", &config)
+            .unwrap_err()
+            .contains("'line'"));
+        assert!(ProblemMatcher::resolve("nope", &HashMap::new()).is_err());
+        assert!(errorformat_to_regex("%f:%q").is_err());
+    }
+}
@@ -0,0 +1,15 @@
+//! Project tasks
+//!
+//! Tasks are named shell command lines — build, test, lint — taken from
+//! the `tasks` config (usually the project's `.fresh/config.json`) and
+//! detected from the project's Cargo, npm and Make files. This module
+//! finds them, runs them and reads problems out of their output; the
+//! output buffer, diagnostics and commands live in `app::tasks`.
+
+pub mod detect;
+pub mod matcher;
+pub mod runner;
+
+pub use detect::detect_tasks;
+pub use matcher::{MatcherState, Problem, ProblemMatcher};
+pub use runner::{TaskHandle, TaskLaunch};
@@ -0,0 +1,167 @@
+//! Task process handle
+//!
+//! [`TaskHandle::spawn`] runs a task's command line through the window's
+//! [`LongRunningSpawner`] — so on a container or remote authority the task
+//! runs where the project lives — and streams every line of stdout and
+//! stderr to the main loop as [`AsyncMessage::TaskOutput`]. When the
+//! process is gone, [`AsyncMessage::TaskFinished`] carries its exit code.
+
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::remote::LongRunningSpawner;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::mpsc;
+
+/// How to start a task process.
+#[derive(Debug, Clone)]
+pub struct TaskLaunch {
+    /// Shell command line
+    pub command: String,
+    pub env: Vec<(String, String)>,
+    pub cwd: Option<PathBuf>,
+}
+
+/// Handle to a running task.
+///
+/// Dropping the handle kills the process.
+#[derive(Debug)]
+pub struct TaskHandle {
+    run_id: u64,
+    kill_tx: mpsc::UnboundedSender<()>,
+}
+
+impl TaskHandle {
+    /// Spawn the task on `runtime`. Spawn failures are reported
+    /// asynchronously as [`AsyncMessage::TaskFinished`] with an error.
+    pub fn spawn(
+        runtime: &tokio::runtime::Handle,
+        run_id: u64,
+        launch: TaskLaunch,
+        async_tx: std::sync::mpsc::Sender<AsyncMessage>,
+        spawner: Arc<dyn LongRunningSpawner>,
+    ) -> Self {
+        let (kill_tx, kill_rx) = mpsc::unbounded_channel();
+        runtime.spawn(async move {
+            let (exit_code, error) =
+                match run_task(run_id, launch, kill_rx, async_tx.clone(), spawner).await {
+                    Ok(exit_code) => (exit_code, None),
+                    Err(error) => {
+                        tracing::warn!("Task run {} failed: {}", run_id, error);
+                        (None, Some(error))
+                    }
+                };
+            #[allow(clippy::let_underscore_must_use)] // receiver drop means editor shutdown
+            let _ = async_tx.send(AsyncMessage::TaskFinished {
+                run_id,
+                exit_code,
+                error,
+            });
+        });
+        Self { run_id, kill_tx }
+    }
+
+    pub fn run_id(&self) -> u64 {
+        self.run_id
+    }
+
+    /// Kill the task process.
+    pub fn kill(&self) {
+        // A closed channel means the task already finished
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.kill_tx.send(());
+    }
+}
+
+/// The shell and its "run this command line" flag.
+fn shell() -> (&'static str, &'static str) {
+    if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    }
+}
+
+/// Run the task to completion. Returns the exit code, `None` when the
+/// process was killed (by the user or a signal).
+async fn run_task(
+    run_id: u64,
+    launch: TaskLaunch,
+    mut kill_rx: mpsc::UnboundedReceiver<()>,
+    async_tx: std::sync::mpsc::Sender<AsyncMessage>,
+    spawner: Arc<dyn LongRunningSpawner>,
+) -> Result<Option<i32>, String> {
+    tracing::info!("Starting task: {}", launch.command);
+    let (shell, flag) = shell();
+    let args = vec![flag.to_string(), launch.command.clone()];
+    let mut child = spawner
+        .spawn_stdio(shell, &args, launch.env, launch.cwd.as_deref(), None)
+        .await
+        .map_err(|e| format!("Failed to start '{}': {}", launch.command, e))?;
+
+    // Tasks are not interactive; closing stdin lets anything that reads
+    // it see end-of-file instead of hanging.
+    drop(child.take_stdin());
+
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.take_stdout() {
+        readers.push(tokio::spawn(forward_lines(
+            run_id,
+            stdout,
+            async_tx.clone(),
+        )));
+    }
+    if let Some(stderr) = child.take_stderr() {
+        readers.push(tokio::spawn(forward_lines(
+            run_id,
+            stderr,
+            async_tx.clone(),
+        )));
+    }
+
+    let status = tokio::select! {
+        status = child.wait() => status.map_err(|e| format!("Failed to wait for task: {}", e))?,
+        _ = kill_rx.recv() => {
+            #[allow(clippy::let_underscore_must_use)] // best-effort; it may have just exited
+            let _ = child.kill().await;
+            for reader in readers {
+                reader.abort();
+            }
+            return Ok(None);
+        }
+    };
+    // Deliver the output the process wrote just before exiting.
+    for reader in readers {
+        // A reader that panicked or was cancelled has nothing left to deliver
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = reader.await;
+    }
+    Ok(status.code())
+}
+
+/// Send every line of `stream` to the main loop. Invalid UTF-8 is
+/// replaced rather than ending the stream.
+async fn forward_lines(
+    run_id: u64,
+    stream: impl AsyncRead + Unpin,
+    async_tx: std::sync::mpsc::Sender<AsyncMessage>,
+) {
+    let mut reader = BufReader::new(stream);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buf);
+                let line = line.trim_end_matches(['\n', '\r']).to_string();
+                if async_tx
+                    .send(AsyncMessage::TaskOutput { run_id, line })
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    }
+}
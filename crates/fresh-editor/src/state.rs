@@ -9,6 +9,7 @@ use crate::model::event::{
 };
 use crate::model::filesystem::FileSystem;
 use crate::model::marker::{MarkerId, MarkerList};
+use crate::model::snippet_session::SnippetSession;
 use crate::primitives::detected_language::DetectedLanguage;
 use crate::primitives::grammar::GrammarRegistry;
 use crate::primitives::highlight_engine::HighlightEngine;
@@ -16,7 +17,10 @@ use crate::primitives::indent::IndentCalculator;
 use crate::primitives::reference_highlighter::ReferenceHighlighter;
 use crate::primitives::text_property::TextPropertyManager;
 use crate::view::bracket_highlight_overlay::BracketHighlightOverlay;
+use crate::view::code_lens::CodeLenses;
 use crate::view::conceal::ConcealManager;
+use crate::view::document_color::DocumentColors;
+use crate::view::document_link::DocumentLinks;
 use crate::view::folding::LspFoldRanges;
 use crate::view::margin::{MarginAnnotation, MarginContent, MarginManager, MarginPosition};
 use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, UnderlineStyle};
@@ -224,6 +228,20 @@ pub struct EditorState {
     /// (issue #1571).
     pub folding_ranges: LspFoldRanges,
 
+    /// Last-known LSP code lenses for this buffer, drawn as virtual lines
+    /// above their symbols.
+    pub code_lenses: CodeLenses,
+
+    /// Last-known LSP document links for this buffer, underlined.
+    pub document_links: DocumentLinks,
+
+    /// Last-known LSP color literals for this buffer, each drawn with a
+    /// swatch in its color.
+    pub document_colors: DocumentColors,
+
+    /// Snippet whose tabstops Tab and Shift+Tab currently step through.
+    pub snippet_session: Option<SnippetSession>,
+
     /// The detected language ID for this buffer (e.g., "rust", "csharp", "text").
     /// Used for LSP config lookup and internal identification.
     pub language: String,
@@ -251,6 +269,11 @@ pub struct EditorState {
     /// version + geometry).  See
     /// `crate::view::visual_row_index` for invariants.
     pub visual_row_index: crate::view::visual_row_index::VisualRowIndex,
+
+    /// Per-line text shape drawn by the minimap, rebuilt lazily when the
+    /// buffer version changes. Left empty for large files, whose minimap
+    /// shows marks only.
+    pub minimap_index: crate::view::minimap::MinimapIndex,
 }
 
 impl EditorState {
@@ -303,10 +326,15 @@ impl EditorState {
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
             semantic_tokens: None,
             folding_ranges: LspFoldRanges::new(),
+            code_lenses: CodeLenses::new(),
+            document_links: DocumentLinks::new(),
+            document_colors: DocumentColors::new(),
+            snippet_session: None,
             language: "text".to_string(),
             display_name: "Text".to_string(),
             line_wrap_cache: crate::view::line_wrap_cache::LineWrapCache::default(),
             visual_row_index: crate::view::visual_row_index::VisualRowIndex::default(),
+            minimap_index: crate::view::minimap::MinimapIndex::default(),
         }
     }
 
@@ -524,6 +552,53 @@ impl EditorState {
         }
     }
 
+    /// Handle a ReplaceBytes event - like a Delete followed by an Insert at
+    /// the same position, except that markers only move by the net change
+    fn apply_replace_bytes(
+        &mut self,
+        cursors: &mut Cursors,
+        position: usize,
+        del_len: usize,
+        inserted: &[u8],
+        cursor_id: crate::model::event::CursorId,
+    ) {
+        let ins_len = inserted.len();
+
+        // CRITICAL: Adjust markers BEFORE modifying buffer
+        if ins_len > del_len {
+            self.marker_list
+                .adjust_for_insert(position, ins_len - del_len);
+            self.margins.adjust_for_insert(position, ins_len - del_len);
+        } else if del_len > ins_len {
+            self.marker_list
+                .adjust_for_delete(position, del_len - ins_len);
+            self.margins.adjust_for_delete(position, del_len - ins_len);
+        }
+
+        if del_len > 0 {
+            self.buffer.delete_bytes(position, del_len);
+        }
+        if ins_len > 0 {
+            self.buffer.insert_bytes(position, inserted.to_vec());
+        }
+
+        self.highlighter
+            .notify_edits(&[(position, del_len, ins_len)]);
+        self.highlighter
+            .invalidate_range(position..position + ins_len);
+
+        cursors.adjust_for_edit(position, del_len, ins_len);
+        if let Some(cursor) = cursors.get_mut(cursor_id) {
+            cursor.position = position + ins_len;
+            cursor.clear_selection();
+        }
+
+        // The bytes may add or drop line breaks, so count the line afresh.
+        if let Some(pos) = self.buffer.offset_to_position(cursors.primary().position) {
+            self.primary_cursor_line_number = LineNumber::Absolute(pos.line);
+        }
+    }
+
     /// Apply an event to the state - THE ONLY WAY TO MODIFY STATE
     /// This is the heart of the event-driven architecture
     pub fn apply(&mut self, cursors: &mut Cursors, event: &Event) {
@@ -540,6 +615,13 @@ impl EditorState {
                 deleted_text,
             } => self.apply_delete(cursors, range, *cursor_id, deleted_text),
 
+            Event::ReplaceBytes {
+                position,
+                deleted,
+                inserted,
+                cursor_id,
+            } => self.apply_replace_bytes(cursors, *position, deleted.len(), inserted, *cursor_id),
+
             Event::MoveCursor {
                 cursor_id,
                 new_position,
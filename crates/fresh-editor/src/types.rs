@@ -39,6 +39,7 @@ pub mod context_keys {
     pub const SESSION_MODE: &str = "session_mode";
     pub const VERTICAL_SCROLLBAR: &str = "vertical_scrollbar";
     pub const HORIZONTAL_SCROLLBAR: &str = "horizontal_scrollbar";
+    pub const MINIMAP: &str = "minimap";
     pub const SCROLL_SYNC: &str = "scroll_sync";
     pub const HAS_SAME_BUFFER_SPLITS: &str = "has_same_buffer_splits";
     pub const KEYMAP_DEFAULT: &str = "keymap_default";
@@ -143,6 +144,22 @@ pub enum LspFeature {
     SemanticTokens,
     /// Document highlight (exclusive)
     DocumentHighlight,
+    /// Call hierarchy: incoming and outgoing calls (exclusive)
+    CallHierarchy,
+    /// Type hierarchy: supertypes and subtypes (exclusive)
+    TypeHierarchy,
+    /// Code lenses (exclusive)
+    CodeLens,
+    /// Document links, such as import paths and URLs (exclusive)
+    DocumentLink,
+    /// Color literals and their presentations (exclusive)
+    DocumentColor,
+    /// Selection ranges for structural expand/shrink selection (exclusive)
+    SelectionRange,
+    /// Formatting after typing a trigger character (exclusive)
+    OnTypeFormatting,
+    /// Linked editing ranges, such as matching HTML tags (exclusive)
+    LinkedEditingRange,
 }
 
 impl LspFeature {
@@ -420,6 +437,149 @@ impl LspServerConfig {
     }
 }
 
+/// Whether a debug session starts a new debuggee or connects to a running one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DebugRequestKind {
+    #[default]
+    Launch,
+    Attach,
+}
+
+impl DebugRequestKind {
+    /// The DAP request name (`"launch"` / `"attach"`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DebugRequestKind::Launch => "launch",
+            DebugRequestKind::Attach => "attach",
+        }
+    }
+}
+
+/// Debug adapter configuration (Debug Adapter Protocol)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/command"))]
+pub struct DebuggerConfig {
+    /// Command that starts the debug adapter (e.g. "lldb-dap", "python").
+    #[serde(default)]
+    #[schemars(extend("x-order" = 1))]
+    pub command: String,
+
+    /// Arguments to pass to the adapter (e.g. ["-m", "debugpy.adapter"])
+    #[serde(default)]
+    #[schemars(extend("x-order" = 2))]
+    pub args: Vec<String>,
+
+    /// Whether to `launch` a new program or `attach` to a running one
+    #[serde(default)]
+    #[schemars(extend("x-order" = 3))]
+    pub request: DebugRequestKind,
+
+    /// Arguments of the launch/attach request, passed to the adapter as-is.
+    /// `${file}`, `${fileDirname}`, `${fileBasenameNoExtension}` and
+    /// `${workspaceFolder}` are expanded in every string value.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 4))]
+    pub configuration: Option<serde_json::Value>,
+
+    /// Adapter ID sent in the `initialize` request (e.g. "lldb", "debugpy").
+    /// Defaults to the command basename.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 10))]
+    pub adapter_id: Option<String>,
+
+    /// Environment variables to set for the adapter process.
+    /// These are added to (or override) the inherited parent environment.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 11))]
+    pub env: HashMap<String, String>,
+}
+
+impl DebuggerConfig {
+    /// Adapter ID for the `initialize` request: the explicit `adapter_id`,
+    /// otherwise the basename of the command.
+    pub fn adapter_id(&self) -> String {
+        if let Some(ref id) = self.adapter_id {
+            return id.clone();
+        }
+        std::path::Path::new(&self.command)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&self.command)
+            .to_string()
+    }
+}
+
+/// A named project task (build, test, lint, ...) started with "Run Task"
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/command"))]
+pub struct TaskConfig {
+    /// Shell command line to run (e.g. "cargo build --release").
+    /// Run with `sh -c` (`cmd /C` on Windows).
+    #[serde(default)]
+    #[schemars(extend("x-order" = 1))]
+    pub command: String,
+
+    /// Problem matchers that turn the task's output into diagnostics.
+    /// Names of built-in matchers ("rustc", "gcc", "tsc", "go") or of
+    /// entries under "problem_matchers".
+    #[serde(default)]
+    #[schemars(extend("x-order" = 2))]
+    pub problem_matchers: Vec<String>,
+
+    /// Working directory, relative to the workspace root.
+    /// Defaults to the workspace root.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 10))]
+    pub cwd: Option<String>,
+
+    /// Environment variables to set for the task.
+    /// These are added to (or override) the inherited parent environment.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 11))]
+    pub env: HashMap<String, String>,
+}
+
+/// Severity of a problem reported by a task
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+/// Turns compiler or linter output into diagnostics
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProblemMatcherConfig {
+    /// Regular expressions matched against consecutive output lines; a
+    /// problem is reported when the last one matches. Named groups give
+    /// the fields: `file`, `line`, `column`, `end_line`, `end_column`,
+    /// `severity`, `code` and `message`.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 1))]
+    pub pattern: Vec<String>,
+
+    /// Vim-style errorformat, used instead of `pattern` (e.g.
+    /// "%f:%l:%c: %m"). Supports `%f`, `%l`, `%c`, `%t`, `%n`, `%m`, `%*`
+    /// and `%%`.
+    #[serde(default)]
+    #[schemars(extend("x-order" = 2))]
+    pub errorformat: Option<String>,
+
+    /// Severity of problems whose line does not say (no `severity` group).
+    #[serde(default)]
+    #[schemars(extend("x-order" = 3))]
+    pub severity: ProblemSeverity,
+
+    /// Source shown with each diagnostic. Defaults to the matcher name.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Advanced", "x-order" = 10))]
+    pub source: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,6 +603,14 @@ mod tests {
         assert!(!LspFeature::FoldingRange.is_merged());
         assert!(!LspFeature::SemanticTokens.is_merged());
         assert!(!LspFeature::DocumentHighlight.is_merged());
+        assert!(!LspFeature::CallHierarchy.is_merged());
+        assert!(!LspFeature::TypeHierarchy.is_merged());
+        assert!(!LspFeature::CodeLens.is_merged());
+        assert!(!LspFeature::DocumentLink.is_merged());
+        assert!(!LspFeature::DocumentColor.is_merged());
+        assert!(!LspFeature::SelectionRange.is_merged());
+        assert!(!LspFeature::OnTypeFormatting.is_merged());
+        assert!(!LspFeature::LinkedEditingRange.is_merged());
     }
 
     #[test]
@@ -0,0 +1,330 @@
+//! LSP code lens infrastructure
+//!
+//! Holds the code lenses a server reported for one buffer. Lenses that carry
+//! a command are drawn as one virtual line above their symbol — titles
+//! joined by ` | ` and indented like the symbol's line — and lenses still
+//! waiting on `codeLens/resolve` stay hidden until their resolved copy
+//! replaces them.
+//!
+//! Hit-testing goes through the drawn rows rather than the lenses' LSP
+//! ranges: a row's virtual-text marker follows edits made after the
+//! response arrived, the ranges don't.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use ratatui::style::{Color, Style};
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::MarkerList;
+use crate::view::virtual_text::{
+    VirtualTextId, VirtualTextManager, VirtualTextNamespace, VirtualTextPosition,
+};
+
+/// Virtual-text namespace of the drawn lens rows.
+pub const CODE_LENS_NAMESPACE: &str = "lsp-code-lens";
+
+/// Drawn between the titles of lenses that share a line.
+const SEPARATOR: &str = " | ";
+
+/// Longest indentation copied from the symbol's line.
+const MAX_INDENT_SCAN: usize = 256;
+
+/// Code lenses of one buffer and the rows they are drawn as.
+#[derive(Debug, Clone, Default)]
+pub struct CodeLenses {
+    lenses: Vec<lsp_types::CodeLens>,
+    rows: Vec<LensRow>,
+}
+
+/// One drawn lens line.
+#[derive(Debug, Clone)]
+struct LensRow {
+    vtext: VirtualTextId,
+    /// Char range of each title within the row text, with its lens index
+    titles: Vec<(Range<usize>, usize)>,
+}
+
+impl CodeLenses {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if no lenses are stored.
+    pub fn is_empty(&self) -> bool {
+        self.lenses.is_empty()
+    }
+
+    /// The lens at `index`, as last reported or resolved.
+    pub fn get(&self, index: usize) -> Option<&lsp_types::CodeLens> {
+        self.lenses.get(index)
+    }
+
+    /// Replace the stored lenses. Call [`Self::render`] afterwards.
+    pub fn set(&mut self, lenses: Vec<lsp_types::CodeLens>) {
+        self.lenses = lenses;
+    }
+
+    /// Indices of lenses that arrived without a command.
+    pub fn unresolved(&self) -> Vec<usize> {
+        self.lenses
+            .iter()
+            .enumerate()
+            .filter(|(_, lens)| lens.command.is_none())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Store the resolved copy of the lens at `index`. Returns `true` when
+    /// it now has a command to draw.
+    pub fn resolve(&mut self, index: usize, lens: lsp_types::CodeLens) -> bool {
+        match self.lenses.get_mut(index) {
+            Some(slot) if lens.command.is_some() => {
+                *slot = lens;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Drop every lens and remove the drawn rows.
+    pub fn clear(&mut self, virtual_texts: &mut VirtualTextManager, marker_list: &mut MarkerList) {
+        self.lenses.clear();
+        self.rows.clear();
+        virtual_texts.clear_namespace(
+            marker_list,
+            &VirtualTextNamespace::from_string(CODE_LENS_NAMESPACE.to_string()),
+        );
+    }
+
+    /// Redraw the rows from the stored lenses.
+    pub fn render(
+        &mut self,
+        buffer: &Buffer,
+        virtual_texts: &mut VirtualTextManager,
+        marker_list: &mut MarkerList,
+        tab_size: usize,
+    ) {
+        let namespace = VirtualTextNamespace::from_string(CODE_LENS_NAMESPACE.to_string());
+        virtual_texts.clear_namespace(marker_list, &namespace);
+        self.rows.clear();
+
+        let mut by_line: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        for (index, lens) in self.lenses.iter().enumerate() {
+            if lens.command.is_some() {
+                by_line
+                    .entry(lens.range.start.line)
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        // Same dimmed look as inlay hints; the theme key wins when defined.
+        let style = Style::default().fg(Color::Rgb(128, 128, 128));
+        let fg_theme_key = Some("editor.line_number_fg".to_string());
+
+        for (line, indices) in by_line {
+            let Some(line_start) = buffer.line_start_offset(line as usize) else {
+                continue;
+            };
+            let scan_end = buffer.len().min(line_start + MAX_INDENT_SCAN);
+            let mut text = indent_of(&buffer.slice_bytes(line_start..scan_end), tab_size);
+            let mut titles = Vec::with_capacity(indices.len());
+            for (n, index) in indices.into_iter().enumerate() {
+                if n > 0 {
+                    text.push_str(SEPARATOR);
+                }
+                let title = self.lenses[index]
+                    .command
+                    .as_ref()
+                    .map(|c| c.title.as_str())
+                    .unwrap_or_default();
+                let start = text.chars().count();
+                text.push_str(title);
+                titles.push((start..start + title.chars().count(), index));
+            }
+            let vtext = virtual_texts.add_line_with_theme_keys(
+                marker_list,
+                line_start,
+                text,
+                style,
+                fg_theme_key.clone(),
+                None,
+                VirtualTextPosition::LineAbove,
+                namespace.clone(),
+                0,
+                None,
+                None,
+                Vec::new(),
+            );
+            self.rows.push(LensRow { vtext, titles });
+        }
+    }
+
+    /// Commands of the lenses drawn above `line` (0-based).
+    pub fn commands_on_line(
+        &self,
+        buffer: &Buffer,
+        virtual_texts: &VirtualTextManager,
+        marker_list: &MarkerList,
+        line: usize,
+    ) -> Vec<lsp_types::Command> {
+        self.row_above(buffer, virtual_texts, marker_list, line)
+            .map(|row| {
+                row.titles
+                    .iter()
+                    .filter_map(|(_, index)| self.command(*index))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Command of the title under char `column` of the row drawn above
+    /// `line` (0-based).
+    pub fn command_at(
+        &self,
+        buffer: &Buffer,
+        virtual_texts: &VirtualTextManager,
+        marker_list: &MarkerList,
+        line: usize,
+        column: usize,
+    ) -> Option<lsp_types::Command> {
+        let row = self.row_above(buffer, virtual_texts, marker_list, line)?;
+        let (_, index) = row
+            .titles
+            .iter()
+            .find(|(range, _)| range.contains(&column))?;
+        self.command(*index)
+    }
+
+    fn row_above(
+        &self,
+        buffer: &Buffer,
+        virtual_texts: &VirtualTextManager,
+        marker_list: &MarkerList,
+        line: usize,
+    ) -> Option<&LensRow> {
+        self.rows.iter().find(|row| {
+            virtual_texts
+                .marker_id_of(row.vtext)
+                .and_then(|marker| marker_list.get_position(marker))
+                .is_some_and(|pos| buffer.get_line_number(pos) == line)
+        })
+    }
+
+    fn command(&self, index: usize) -> Option<lsp_types::Command> {
+        self.lenses.get(index)?.command.clone()
+    }
+}
+
+/// Leading whitespace of `line`, with tabs expanded to `tab_size` columns.
+fn indent_of(line: &[u8], tab_size: usize) -> String {
+    let mut indent = String::new();
+    for &b in line {
+        match b {
+            b' ' => indent.push(' '),
+            b'\t' => {
+                let width = tab_size.max(1);
+                let pad = width - indent.len() % width;
+                indent.extend(std::iter::repeat_n(' ', pad));
+            }
+            _ => break,
+        }
+    }
+    indent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{CodeLens, Command, Position};
+
+    fn lens(line: u32, title: Option<&str>) -> CodeLens {
+        CodeLens {
+            range: lsp_types::Range {
+                start: Position { line, character: 4 },
+                end: Position { line, character: 8 },
+            },
+            command: title.map(|t| Command {
+                title: t.to_string(),
+                command: format!("cmd.{t}"),
+                arguments: None,
+            }),
+            data: None,
+        }
+    }
+
+    fn rendered(
+        source: &str,
+        lenses: Vec<CodeLens>,
+    ) -> (Buffer, MarkerList, VirtualTextManager, CodeLenses) {
+        let buffer = Buffer::from_str_test(source);
+        let mut marker_list = MarkerList::new();
+        let mut virtual_texts = VirtualTextManager::new();
+        let mut store = CodeLenses::new();
+        store.set(lenses);
+        store.render(&buffer, &mut virtual_texts, &mut marker_list, 4);
+        (buffer, marker_list, virtual_texts, store)
+    }
+
+    #[test]
+    fn lenses_on_one_line_share_a_row() {
+        let (buffer, marker_list, virtual_texts, _) = rendered(
+            "mod m {\n\tfn a() {}\n}\n",
+            vec![lens(1, Some("Run")), lens(1, Some("Debug")), lens(1, None)],
+        );
+        let rows = virtual_texts.query_lines_in_range(&marker_list, 0, buffer.len());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].1.text, "    Run | Debug");
+        assert_eq!(rows[0].1.position, VirtualTextPosition::LineAbove);
+    }
+
+    #[test]
+    fn command_at_maps_columns_to_titles() {
+        let (buffer, marker_list, virtual_texts, store) = rendered(
+            "fn a() {}\n",
+            vec![lens(0, Some("Run")), lens(0, Some("Debug"))],
+        );
+        let at = |column| {
+            store
+                .command_at(&buffer, &virtual_texts, &marker_list, 0, column)
+                .map(|c| c.title)
+        };
+        assert_eq!(at(0).as_deref(), Some("Run"));
+        assert_eq!(at(2).as_deref(), Some("Run"));
+        assert_eq!(at(4), None, "separator is not a title");
+        assert_eq!(at(6).as_deref(), Some("Debug"));
+        assert_eq!(at(11), None);
+        assert!(store
+            .command_at(&buffer, &virtual_texts, &marker_list, 1, 0)
+            .is_none());
+    }
+
+    #[test]
+    fn resolved_lens_is_drawn_after_rerender() {
+        let (buffer, mut marker_list, mut virtual_texts, mut store) =
+            rendered("fn a() {}\n", vec![lens(0, None)]);
+        assert!(virtual_texts.is_empty());
+        assert_eq!(store.unresolved(), vec![0]);
+
+        assert!(!store.resolve(0, lens(0, None)), "still no command");
+        assert!(store.resolve(0, lens(0, Some("3 references"))));
+        store.render(&buffer, &mut virtual_texts, &mut marker_list, 4);
+
+        let commands = store.commands_on_line(&buffer, &virtual_texts, &marker_list, 0);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].title, "3 references");
+        assert!(store.unresolved().is_empty());
+    }
+
+    #[test]
+    fn clear_removes_rows() {
+        let (_, mut marker_list, mut virtual_texts, mut store) =
+            rendered("fn a() {}\n", vec![lens(0, Some("Run"))]);
+        assert_eq!(virtual_texts.len(), 1);
+        store.clear(&mut virtual_texts, &mut marker_list);
+        assert!(virtual_texts.is_empty());
+        assert!(store.is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//! LSP document colors
+//!
+//! Holds the color literals a server reported for one buffer
+//! (`textDocument/documentColor`) — hex codes, `rgb()` calls, CSS variables,
+//! Tailwind classes — and draws a swatch in each color just before its
+//! literal.
+//!
+//! Like code lens rows, the swatches' markers follow edits made after the
+//! response arrived, so a literal is located through its swatch rather than
+//! its LSP range.
+
+use std::ops::Range;
+
+use ratatui::style::{Color, Style};
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::MarkerList;
+use crate::view::virtual_text::{
+    VirtualTextId, VirtualTextManager, VirtualTextNamespace, VirtualTextPosition,
+};
+
+/// Virtual-text namespace of the swatches.
+pub const DOCUMENT_COLOR_NAMESPACE: &str = "lsp-document-color";
+
+/// Drawn in the literal's color in front of it.
+const SWATCH: &str = "■";
+
+/// Color literals of one buffer and the swatches drawn for them.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentColors {
+    colors: Vec<Swatch>,
+}
+
+#[derive(Debug, Clone)]
+struct Swatch {
+    vtext: VirtualTextId,
+    /// Byte length of the literal when the swatch was drawn
+    len: usize,
+    color: lsp_types::Color,
+}
+
+impl DocumentColors {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if no colors are stored.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Replace the stored colors and redraw the swatches.
+    pub fn set(
+        &mut self,
+        colors: Vec<lsp_types::ColorInformation>,
+        buffer: &Buffer,
+        virtual_texts: &mut VirtualTextManager,
+        marker_list: &mut MarkerList,
+    ) {
+        let namespace = namespace();
+        virtual_texts.clear_namespace(marker_list, &namespace);
+        self.colors = colors
+            .into_iter()
+            .filter_map(|info| {
+                let start = buffer.lsp_position_to_byte(
+                    info.range.start.line as usize,
+                    info.range.start.character as usize,
+                );
+                let end = buffer.lsp_position_to_byte(
+                    info.range.end.line as usize,
+                    info.range.end.character as usize,
+                );
+                if end <= start {
+                    return None;
+                }
+                let vtext = virtual_texts.add_in_namespace(
+                    marker_list,
+                    start,
+                    SWATCH.to_string(),
+                    Style::default().fg(to_terminal_color(&info.color)),
+                    VirtualTextPosition::BeforeChar,
+                    0,
+                    namespace.clone(),
+                );
+                Some(Swatch {
+                    vtext,
+                    len: end - start,
+                    color: info.color,
+                })
+            })
+            .collect();
+    }
+
+    /// Byte range and color of the literal covering byte `position`. The
+    /// end of a literal counts as inside it, so a cursor right after
+    /// `#fff` finds it.
+    pub fn at(
+        &self,
+        position: usize,
+        virtual_texts: &VirtualTextManager,
+        marker_list: &MarkerList,
+    ) -> Option<(Range<usize>, lsp_types::Color)> {
+        self.colors.iter().find_map(|swatch| {
+            let start = virtual_texts
+                .marker_id_of(swatch.vtext)
+                .and_then(|marker| marker_list.get_position(marker))?;
+            let range = start..start + swatch.len;
+            (range.start <= position && position <= range.end).then_some((range, swatch.color))
+        })
+    }
+
+    /// Drop every color and remove the swatches.
+    pub fn clear(&mut self, virtual_texts: &mut VirtualTextManager, marker_list: &mut MarkerList) {
+        self.colors.clear();
+        virtual_texts.clear_namespace(marker_list, &namespace());
+    }
+}
+
+fn namespace() -> VirtualTextNamespace {
+    VirtualTextNamespace::from_string(DOCUMENT_COLOR_NAMESPACE.to_string())
+}
+
+/// The terminal color of an LSP color, with its components in `0.0..=1.0`.
+/// Alpha is ignored: the swatch is drawn over whatever background the
+/// terminal has.
+pub fn to_terminal_color(color: &lsp_types::Color) -> Color {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color::Rgb(
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{ColorInformation, Position};
+
+    fn info(line: u32, start: u32, end: u32, red: f32) -> ColorInformation {
+        ColorInformation {
+            range: lsp_types::Range {
+                start: Position {
+                    line,
+                    character: start,
+                },
+                end: Position {
+                    line,
+                    character: end,
+                },
+            },
+            color: lsp_types::Color {
+                red,
+                green: 0.0,
+                blue: 0.5,
+                alpha: 1.0,
+            },
+        }
+    }
+
+    #[test]
+    fn swatches_are_drawn_in_the_literal_color() {
+        let buffer = Buffer::from_str_test("a { color: #ff0080; }\n");
+        let mut marker_list = MarkerList::new();
+        let mut virtual_texts = VirtualTextManager::new();
+        let mut store = DocumentColors::new();
+        store.set(
+            vec![info(0, 11, 18, 1.0), info(0, 3, 3, 0.0)],
+            &buffer,
+            &mut virtual_texts,
+            &mut marker_list,
+        );
+        assert_eq!(virtual_texts.len(), 1, "empty ranges are dropped");
+
+        let swatches = virtual_texts.query_inline_in_range(&marker_list, 0, buffer.len());
+        assert_eq!(swatches.len(), 1);
+        assert_eq!(swatches[0].0, 11);
+        assert_eq!(swatches[0].1.style.fg, Some(Color::Rgb(255, 0, 128)));
+
+        assert_eq!(
+            store
+                .at(18, &virtual_texts, &marker_list)
+                .map(|(range, _)| range),
+            Some(11..18)
+        );
+        assert!(store.at(10, &virtual_texts, &marker_list).is_none());
+
+        // Inlay hints are replaced without touching the swatches.
+        virtual_texts.clear_inline(&mut marker_list);
+        assert_eq!(virtual_texts.len(), 1);
+
+        store.clear(&mut virtual_texts, &mut marker_list);
+        assert!(store.is_empty());
+        assert!(virtual_texts.is_empty());
+    }
+}
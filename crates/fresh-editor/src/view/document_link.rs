@@ -0,0 +1,229 @@
+//! LSP document links
+//!
+//! Holds the links a server reported for one buffer (`textDocument/documentLink`)
+//! — import paths, URLs in manifests, and the like — each drawn as an
+//! underline overlay. The overlays' markers follow edits made after the
+//! response arrived, so hit-testing goes through them rather than the links'
+//! LSP ranges.
+//!
+//! Links may arrive without a target, to be filled in by
+//! `documentLink/resolve` when one is followed.
+
+use std::ops::Range;
+
+use ratatui::style::{Modifier, Style};
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::MarkerList;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayHandle, OverlayManager, OverlayNamespace};
+
+/// Overlay namespace of the link underlines.
+pub const DOCUMENT_LINK_NAMESPACE: &str = "lsp-document-link";
+
+/// Document links of one buffer and the overlays drawing them.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentLinks {
+    links: Vec<(OverlayHandle, lsp_types::DocumentLink)>,
+}
+
+impl DocumentLinks {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if no links are stored.
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// The link at `index`, as last reported or resolved.
+    pub fn get(&self, index: usize) -> Option<&lsp_types::DocumentLink> {
+        self.links.get(index).map(|(_, link)| link)
+    }
+
+    /// Replace the stored links and underline them.
+    pub fn set(
+        &mut self,
+        links: Vec<lsp_types::DocumentLink>,
+        buffer: &Buffer,
+        overlays: &mut OverlayManager,
+        marker_list: &mut MarkerList,
+    ) {
+        let namespace = namespace();
+        overlays.clear_namespace(&namespace, marker_list);
+        let face = OverlayFace::Style {
+            style: Style::default().add_modifier(Modifier::UNDERLINED),
+        };
+        self.links = links
+            .into_iter()
+            .filter_map(|link| {
+                let range = byte_range(buffer, &link.range);
+                if range.is_empty() {
+                    return None;
+                }
+                let mut overlay =
+                    Overlay::with_namespace(marker_list, range, face.clone(), namespace.clone());
+                overlay.message = link.tooltip.clone();
+                overlay.url = link.target.as_ref().map(|t| t.as_str().to_string());
+                Some((overlays.add(overlay), link))
+            })
+            .collect();
+    }
+
+    /// Store the resolved copy of the link at `index`. Returns `true` when
+    /// it now has a target.
+    pub fn resolve(
+        &mut self,
+        index: usize,
+        link: lsp_types::DocumentLink,
+        overlays: &mut OverlayManager,
+    ) -> bool {
+        let Some((handle, slot)) = self.links.get_mut(index) else {
+            return false;
+        };
+        let Some(target) = link.target.as_ref() else {
+            return false;
+        };
+        if let Some(overlay) = overlays.get_by_handle_mut(handle) {
+            overlay.url = Some(target.as_str().to_string());
+        }
+        *slot = link;
+        true
+    }
+
+    /// Index of the link covering byte `position`.
+    pub fn index_at(
+        &self,
+        position: usize,
+        overlays: &OverlayManager,
+        marker_list: &MarkerList,
+    ) -> Option<usize> {
+        self.links.iter().position(|(handle, _)| {
+            overlays
+                .get_by_handle(handle)
+                .is_some_and(|o| o.contains(position, marker_list))
+        })
+    }
+
+    /// Drop every link and remove the underlines.
+    pub fn clear(&mut self, overlays: &mut OverlayManager, marker_list: &mut MarkerList) {
+        self.links.clear();
+        overlays.clear_namespace(&namespace(), marker_list);
+    }
+}
+
+fn namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string(DOCUMENT_LINK_NAMESPACE.to_string())
+}
+
+fn byte_range(buffer: &Buffer, range: &lsp_types::Range) -> Range<usize> {
+    let start =
+        buffer.lsp_position_to_byte(range.start.line as usize, range.start.character as usize);
+    let end = buffer.lsp_position_to_byte(range.end.line as usize, range.end.character as usize);
+    start..end.max(start)
+}
+
+/// Split a link target into the URI to open and the 1-based line and
+/// column named by its fragment, as in `file:///src/lib.rs#L12` or
+/// `file:///src/lib.rs#L12,5`.
+pub fn split_target_location(target: &str) -> (&str, Option<usize>, Option<usize>) {
+    let Some((uri, fragment)) = target.split_once('#') else {
+        return (target, None, None);
+    };
+    let Some(location) = fragment.strip_prefix('L') else {
+        return (target, None, None);
+    };
+    let (line, column) = match location.split_once(',') {
+        Some((line, column)) => (line, Some(column)),
+        None => (location, None),
+    };
+    match line.parse() {
+        Ok(line) => (uri, Some(line), column.and_then(|c| c.parse().ok())),
+        Err(_) => (target, None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{DocumentLink, Position};
+
+    fn link(line: u32, start: u32, end: u32, target: Option<&str>) -> DocumentLink {
+        DocumentLink {
+            range: lsp_types::Range {
+                start: Position {
+                    line,
+                    character: start,
+                },
+                end: Position {
+                    line,
+                    character: end,
+                },
+            },
+            target: target.map(|t| t.parse().unwrap()),
+            tooltip: None,
+            data: None,
+        }
+    }
+
+    #[test]
+    fn links_follow_edits_and_resolve() {
+        let mut buffer = Buffer::from_str_test("use a;\nuse b;\n");
+        let mut marker_list = MarkerList::new();
+        let mut overlays = OverlayManager::new();
+        let mut store = DocumentLinks::new();
+        store.set(
+            vec![
+                link(0, 4, 5, Some("file:///a.rs")),
+                link(1, 4, 5, None),
+                link(1, 5, 5, None),
+            ],
+            &buffer,
+            &mut overlays,
+            &mut marker_list,
+        );
+        assert_eq!(overlays.len(), 2, "empty ranges are dropped");
+        assert_eq!(store.index_at(4, &overlays, &marker_list), Some(0));
+        assert_eq!(store.index_at(5, &overlays, &marker_list), None);
+
+        buffer.insert(0, "// x\n");
+        marker_list.adjust_for_insert(0, 5);
+        assert_eq!(store.index_at(4, &overlays, &marker_list), None);
+        assert_eq!(store.index_at(16, &overlays, &marker_list), Some(1));
+
+        assert!(!store.resolve(1, link(1, 4, 5, None), &mut overlays));
+        assert!(store.resolve(1, link(1, 4, 5, Some("https://b.example/")), &mut overlays));
+        assert_eq!(
+            store
+                .get(1)
+                .and_then(|l| l.target.as_ref())
+                .map(|t| t.as_str()),
+            Some("https://b.example/")
+        );
+
+        store.clear(&mut overlays, &mut marker_list);
+        assert!(store.is_empty());
+        assert!(overlays.is_empty());
+    }
+
+    #[test]
+    fn target_fragment_names_line_and_column() {
+        assert_eq!(
+            split_target_location("file:///a.rs#L12,5"),
+            ("file:///a.rs", Some(12), Some(5))
+        );
+        assert_eq!(
+            split_target_location("file:///a.rs#L12"),
+            ("file:///a.rs", Some(12), None)
+        );
+        assert_eq!(
+            split_target_location("https://x.example/#intro"),
+            ("https://x.example/#intro", None, None)
+        );
+        assert_eq!(
+            split_target_location("file:///a.rs"),
+            ("file:///a.rs", None, None)
+        );
+    }
+}
@@ -381,6 +381,30 @@ impl LspFoldRanges {
             })
             .collect()
     }
+
+    /// Byte ranges of the code scopes among the tracked folds, for sticky
+    /// scroll: `header..end` where `header` is the header line's start and
+    /// `end` the start of the fold's last line.
+    ///
+    /// Comment and import folds aren't scopes and are skipped, as are ranges
+    /// whose markers no longer form a valid span.
+    pub fn scope_ranges(&self, marker_list: &MarkerList) -> Vec<std::ops::Range<usize>> {
+        self.ranges
+            .iter()
+            .filter(|r| {
+                !matches!(
+                    r.kind,
+                    Some(lsp_types::FoldingRangeKind::Comment)
+                        | Some(lsp_types::FoldingRangeKind::Imports)
+                )
+            })
+            .filter_map(|r| {
+                let start = marker_list.get_position(r.start_marker)?;
+                let end = marker_list.get_position(r.end_marker)?;
+                (start < end).then_some(start..end)
+            })
+            .collect()
+    }
 }
 
 impl Default for FoldManager {
@@ -436,7 +460,8 @@ pub mod indent_folding {
     }
 
     /// Measure leading indent of a line given as a byte slice (no trailing `\n`).
-    fn slice_indent(line: &[u8], tab_size: usize) -> (usize, bool) {
+    /// Returns `(indent, all_blank)`.
+    pub fn slice_indent(line: &[u8], tab_size: usize) -> (usize, bool) {
         let mut indent = 0;
         let mut all_blank = true;
         for &b in line {
@@ -0,0 +1,326 @@
+//! Hex view of a buffer's bytes.
+//!
+//! A split can show its buffer as rows of `offset  hex bytes  |ASCII|`
+//! instead of text. Only the rows on screen are read from the piece tree, so
+//! the view costs the same for a 4 KB file as for a 4 GB one. This module
+//! holds the per-view state and the pure layout and parsing helpers; drawing
+//! lives in `ui::split_rendering::orchestration::hex_view` and key handling
+//! and editing in `app::hex_view_actions`.
+
+use std::ops::Range;
+
+use regex::bytes::Regex;
+
+/// Widest row tried; narrower splits fall back to 8 or 4 bytes per row.
+const MAX_BYTES_PER_ROW: usize = 16;
+
+/// Bytes read per step when searching for a byte pattern.
+const SEARCH_CHUNK_BYTES: usize = 1 << 20;
+
+/// Hex view state of one buffer in one split.
+#[derive(Debug, Clone)]
+pub struct HexViewState {
+    /// First row on screen.
+    pub top_row: usize,
+    /// Scroll the cursor's row into view on the next render. Set by
+    /// keyboard moves; mouse-wheel scrolling leaves it unset so the view
+    /// can move away from the cursor.
+    pub reveal_cursor: bool,
+    /// The cursor is on the second (low) hex digit of its byte.
+    pub low_nibble: bool,
+    /// Typing overwrites bytes with characters from the ASCII column
+    /// instead of hex digits.
+    pub ascii_focus: bool,
+    /// Layout as of the last render, for vertical moves and paging.
+    pub bytes_per_row: usize,
+    pub visible_rows: usize,
+    /// Last byte pattern searched for, repeated by Find Next.
+    pub last_search: Option<String>,
+}
+
+impl Default for HexViewState {
+    fn default() -> Self {
+        Self {
+            top_row: 0,
+            reveal_cursor: true,
+            low_nibble: false,
+            ascii_focus: false,
+            bytes_per_row: MAX_BYTES_PER_ROW,
+            visible_rows: 16,
+            last_search: None,
+        }
+    }
+}
+
+impl HexViewState {
+    /// Scroll by `delta` rows, keeping at least one row of a
+    /// `total_bytes`-long buffer on screen.
+    pub fn scroll(&mut self, delta: isize, total_bytes: usize) {
+        let last_row = total_bytes / self.bytes_per_row.max(1);
+        self.top_row = self.top_row.saturating_add_signed(delta).min(last_row);
+    }
+
+    /// Adjust `top_row` so the row holding byte `cursor` is on screen.
+    pub fn reveal(&mut self, cursor: usize) {
+        let row = cursor / self.bytes_per_row.max(1);
+        let rows = self.visible_rows.max(1);
+        if row < self.top_row {
+            self.top_row = row;
+        } else if row >= self.top_row + rows {
+            self.top_row = row + 1 - rows;
+        }
+    }
+}
+
+/// Column layout of a hex view row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexLayout {
+    pub bytes_per_row: usize,
+    /// Hex digits in the offset column; enough for the last offset and
+    /// never fewer than 8.
+    pub offset_digits: usize,
+}
+
+impl HexLayout {
+    /// The widest layout that fits in `width` columns.
+    pub fn new(width: u16, total_bytes: usize) -> Self {
+        let offset_digits = hex_digits(total_bytes).max(8);
+        let mut layout = Self {
+            bytes_per_row: MAX_BYTES_PER_ROW,
+            offset_digits,
+        };
+        while layout.bytes_per_row > 4 && layout.row_width() > width as usize {
+            layout.bytes_per_row /= 2;
+        }
+        layout
+    }
+
+    /// Extra space between the two halves of a row.
+    fn middle_gap(&self) -> usize {
+        usize::from(self.bytes_per_row >= 8)
+    }
+
+    /// Column of the first hex digit of the `index`th byte in a row.
+    pub fn hex_column(&self, index: usize) -> usize {
+        let gap = if index >= self.bytes_per_row / 2 {
+            self.middle_gap()
+        } else {
+            0
+        };
+        self.offset_digits + 2 + index * 3 + gap
+    }
+
+    /// Column of the `index`th byte's character in the ASCII column.
+    pub fn ascii_column(&self, index: usize) -> usize {
+        self.offset_digits + 2 + self.bytes_per_row * 3 + self.middle_gap() + 1 + index
+    }
+
+    /// Columns needed for a full row.
+    pub fn row_width(&self) -> usize {
+        self.ascii_column(self.bytes_per_row) + 1
+    }
+}
+
+/// Number of hex digits needed to print `value`.
+fn hex_digits(value: usize) -> usize {
+    (usize::BITS - value.leading_zeros()).div_ceil(4) as usize
+}
+
+/// The character shown for `byte` in the ASCII column.
+pub fn ascii_char(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+/// `bytes` as space-separated lowercase hex pairs, e.g. `"de ad be ef"`.
+pub fn to_hex_string(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse a byte offset typed by the user: decimal, or hex with a `0x`
+/// prefix or `h` suffix.
+pub fn parse_offset(text: &str) -> Option<usize> {
+    let text = text.trim().replace('_', "");
+    if let Some(hex) = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .or_else(|| text.strip_suffix('h'))
+        .or_else(|| text.strip_suffix('H'))
+    {
+        return usize::from_str_radix(hex, 16).ok();
+    }
+    text.parse().ok()
+}
+
+/// Parse a byte pattern: hex pairs with optional whitespace, where `??`
+/// matches any byte (`"7f 45 4c 46"`, `"de??beef"`), or a double-quoted
+/// ASCII string (`"\"ELF\""`). `None` entries are wildcards.
+pub fn parse_byte_pattern(text: &str) -> Result<Vec<Option<u8>>, String> {
+    let text = text.trim();
+    if let Some(literal) = text.strip_prefix('"') {
+        let literal = literal.strip_suffix('"').unwrap_or(literal);
+        if literal.is_empty() {
+            return Err("empty pattern".to_string());
+        }
+        return Ok(literal.bytes().map(Some).collect());
+    }
+
+    let digits: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if digits.is_empty() {
+        return Err("empty pattern".to_string());
+    }
+    if digits.len() % 2 != 0 {
+        return Err("odd number of hex digits".to_string());
+    }
+    digits
+        .chunks(2)
+        .map(|pair| match pair {
+            ['?', '?'] => Ok(None),
+            [hi, lo] => match (hi.to_digit(16), lo.to_digit(16)) {
+                (Some(hi), Some(lo)) => Ok(Some((hi * 16 + lo) as u8)),
+                _ => Err(format!("'{}{}' is not a hex byte", hi, lo)),
+            },
+            _ => unreachable!("chunks(2) of an even-length slice"),
+        })
+        .collect()
+}
+
+/// A regex matching `pattern` against raw bytes.
+pub fn pattern_regex(pattern: &[Option<u8>]) -> Regex {
+    let mut source = String::from("(?s-u)");
+    for byte in pattern {
+        match byte {
+            Some(b) => source.push_str(&format!("\\x{:02X}", b)),
+            None => source.push('.'),
+        }
+    }
+    Regex::new(&source).expect("escaped byte pattern is a valid regex")
+}
+
+/// Find the first match of `regex` (matching `pattern_len` bytes) that
+/// starts at or after `from`, wrapping around to the start of the data if
+/// there is none. `read(offset, len)` returns bytes of the `total`-byte
+/// data; it is called one chunk at a time so the data never has to be in
+/// memory at once.
+pub fn find_pattern(
+    mut read: impl FnMut(usize, usize) -> Option<Vec<u8>>,
+    total: usize,
+    from: usize,
+    regex: &Regex,
+    pattern_len: usize,
+) -> Option<Range<usize>> {
+    let from = from.min(total);
+    let overlap = pattern_len.saturating_sub(1);
+    let mut scan = |start: usize, end: usize| -> Option<Range<usize>> {
+        let mut chunk_start = start;
+        while chunk_start < end {
+            let chunk_end = (chunk_start + SEARCH_CHUNK_BYTES).min(end);
+            // Read past the chunk so matches straddling its end are found.
+            let read_end = (chunk_end + overlap).min(total);
+            let bytes = read(chunk_start, read_end - chunk_start)?;
+            if let Some(m) = regex.find(&bytes) {
+                if chunk_start + m.start() < chunk_end {
+                    return Some(chunk_start + m.start()..chunk_start + m.end());
+                }
+            }
+            chunk_start = chunk_end;
+        }
+        None
+    };
+    scan(from, total).or_else(|| scan(0, from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_narrows_to_fit() {
+        let wide = HexLayout::new(120, 1000);
+        assert_eq!(wide.bytes_per_row, 16);
+        assert_eq!(wide.offset_digits, 8);
+        assert_eq!(wide.row_width(), 77);
+        assert_eq!(wide.hex_column(0), 10);
+        assert_eq!(wide.hex_column(8), 35);
+        assert_eq!(wide.ascii_column(0), 60);
+
+        assert_eq!(HexLayout::new(60, 1000).bytes_per_row, 8);
+        assert_eq!(HexLayout::new(10, 1000).bytes_per_row, 4);
+        assert_eq!(HexLayout::new(120, 1 << 36).offset_digits, 10);
+    }
+
+    #[test]
+    fn parses_offsets() {
+        assert_eq!(parse_offset("4096"), Some(4096));
+        assert_eq!(parse_offset(" 0x1000 "), Some(4096));
+        assert_eq!(parse_offset("1000h"), Some(4096));
+        assert_eq!(parse_offset("0x_ff_ff"), Some(0xffff));
+        assert_eq!(parse_offset("zz"), None);
+    }
+
+    #[test]
+    fn parses_byte_patterns() {
+        assert_eq!(
+            parse_byte_pattern("7f 45 4C46").unwrap(),
+            vec![Some(0x7f), Some(0x45), Some(0x4c), Some(0x46)]
+        );
+        assert_eq!(
+            parse_byte_pattern("de??ef").unwrap(),
+            vec![Some(0xde), None, Some(0xef)]
+        );
+        assert_eq!(
+            parse_byte_pattern("\"ELF\"").unwrap(),
+            vec![Some(b'E'), Some(b'L'), Some(b'F')]
+        );
+        assert!(parse_byte_pattern("abc").is_err());
+        assert!(parse_byte_pattern("zz").is_err());
+        assert!(parse_byte_pattern("  ").is_err());
+    }
+
+    #[test]
+    fn finds_patterns_across_chunks_and_wraps() {
+        let mut data = vec![0u8; SEARCH_CHUNK_BYTES + 16];
+        // Straddles the first chunk boundary.
+        data[SEARCH_CHUNK_BYTES - 1..SEARCH_CHUNK_BYTES + 1].copy_from_slice(&[0xde, 0xad]);
+        data[4..6].copy_from_slice(&[0xde, 0xad]);
+        let read = |offset: usize, len: usize| Some(data[offset..offset + len].to_vec());
+        let pattern = parse_byte_pattern("de ad").unwrap();
+        let regex = pattern_regex(&pattern);
+
+        assert_eq!(find_pattern(read, data.len(), 0, &regex, 2), Some(4..6));
+        assert_eq!(
+            find_pattern(read, data.len(), 5, &regex, 2),
+            Some(SEARCH_CHUNK_BYTES - 1..SEARCH_CHUNK_BYTES + 1)
+        );
+        assert_eq!(
+            find_pattern(read, data.len(), SEARCH_CHUNK_BYTES, &regex, 2),
+            Some(4..6)
+        );
+
+        let wildcard = pattern_regex(&parse_byte_pattern("de ??").unwrap());
+        assert_eq!(find_pattern(read, data.len(), 0, &wildcard, 2), Some(4..6));
+    }
+
+    #[test]
+    fn reveal_scrolls_just_enough() {
+        let mut state = HexViewState {
+            visible_rows: 4,
+            ..Default::default()
+        };
+        state.reveal(16 * 10);
+        assert_eq!(state.top_row, 7);
+        state.reveal(16 * 2);
+        assert_eq!(state.top_row, 2);
+        state.scroll(-5, 1000);
+        assert_eq!(state.top_row, 0);
+        state.scroll(1000, 1000);
+        assert_eq!(state.top_row, 1000 / 16);
+    }
+}
@@ -0,0 +1,259 @@
+//! Minimap / overview ruler beside a split's scrollbar.
+//!
+//! The minimap is a narrow column showing the whole document at once: one
+//! column of marks (diagnostics, search matches, git changes, cursors)
+//! followed by a Braille rendering of the text's shape, where every dot
+//! stands for a few columns of one or more lines. Buffers above the
+//! large-file threshold have no line index, so they get the marks column
+//! only, placed by byte offset.
+//!
+//! This module holds the pure geometry and glyph helpers; drawing lives in
+//! `ui::split_rendering::minimap` and click handling in
+//! `app::scrollbar_input`.
+
+/// Total width of the minimap in cells, marks column included.
+pub const MINIMAP_WIDTH: u16 = 10;
+
+/// Text columns represented by one Braille dot column.
+const COLUMNS_PER_DOT: usize = 4;
+
+/// Braille dot columns in the shape part of the minimap (two per cell).
+const DOT_COLUMNS: usize = 2 * (MINIMAP_WIDTH as usize - 1);
+
+/// Braille dot rows per cell.
+const DOTS_PER_CELL_ROW: usize = 4;
+
+/// Per-line shape of a buffer: bit `i` of a line's mask is set when the
+/// line has a non-blank character among the text columns of dot column `i`.
+/// Rebuilt whenever the buffer version changes.
+#[derive(Debug, Clone, Default)]
+pub struct MinimapIndex {
+    version: Option<u64>,
+    lines: Vec<u32>,
+}
+
+impl MinimapIndex {
+    /// Whether the index was built from buffer version `version`.
+    pub fn is_built_for(&self, version: u64) -> bool {
+        self.version == Some(version)
+    }
+
+    /// Rebuild from the full buffer `text` at `version`.
+    pub fn rebuild(&mut self, version: u64, text: &[u8], tab_size: usize) {
+        self.version = Some(version);
+        self.lines = text
+            .split(|&b| b == b'\n')
+            .map(|line| line_mask(line, tab_size))
+            .collect();
+    }
+
+    /// Shape masks, one per line.
+    pub fn lines(&self) -> &[u32] {
+        &self.lines
+    }
+}
+
+/// Shape mask of one line (see [`MinimapIndex`]).
+fn line_mask(line: &[u8], tab_size: usize) -> u32 {
+    let mut mask = 0u32;
+    let mut column = 0usize;
+    for &byte in line {
+        match byte {
+            b'\t' => column += tab_size.max(1) - column % tab_size.max(1),
+            b' ' | b'\r' => column += 1,
+            // UTF-8 continuation bytes don't start a new column.
+            0x80..=0xbf => {}
+            _ => {
+                let dot = column / COLUMNS_PER_DOT;
+                if dot >= DOT_COLUMNS {
+                    break;
+                }
+                mask |= 1 << dot;
+                column += 1;
+            }
+        }
+        if column / COLUMNS_PER_DOT >= DOT_COLUMNS {
+            break;
+        }
+    }
+    mask
+}
+
+/// How minimap rows map onto the document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimapScale {
+    /// Each row covers `lines_per_row` lines, starting from line 0.
+    Lines {
+        total_lines: usize,
+        lines_per_row: usize,
+    },
+    /// Large files: `rows` rows split the bytes evenly.
+    Bytes { total_bytes: usize, rows: usize },
+}
+
+/// Where a click on a minimap row should scroll to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimapTarget {
+    Line(usize),
+    Byte(usize),
+}
+
+impl MinimapScale {
+    /// Fit `total_lines` lines into `rows` rows, at most one line per dot
+    /// row, so short documents stay at the top at full detail.
+    pub fn for_lines(total_lines: usize, rows: usize) -> Self {
+        let dot_rows = rows.max(1) * DOTS_PER_CELL_ROW;
+        let lines_per_dot = total_lines.max(1).div_ceil(dot_rows);
+        Self::Lines {
+            total_lines,
+            lines_per_row: lines_per_dot * DOTS_PER_CELL_ROW,
+        }
+    }
+
+    pub fn for_bytes(total_bytes: usize, rows: usize) -> Self {
+        Self::Bytes {
+            total_bytes,
+            rows: rows.max(1),
+        }
+    }
+
+    /// Row showing byte `byte`; `line_of` converts it to a line number in
+    /// line mode.
+    pub fn row_for_byte(&self, byte: usize, line_of: impl FnOnce(usize) -> usize) -> usize {
+        match *self {
+            Self::Lines { lines_per_row, .. } => line_of(byte) / lines_per_row,
+            Self::Bytes { total_bytes, rows } => {
+                if total_bytes == 0 {
+                    0
+                } else {
+                    (byte.min(total_bytes - 1) as u128 * rows as u128 / total_bytes as u128)
+                        as usize
+                }
+            }
+        }
+    }
+
+    /// The document position at the top of `row`.
+    pub fn target(&self, row: usize) -> MinimapTarget {
+        match *self {
+            Self::Lines {
+                total_lines,
+                lines_per_row,
+            } => MinimapTarget::Line((row * lines_per_row).min(total_lines.saturating_sub(1))),
+            Self::Bytes { total_bytes, rows } => {
+                MinimapTarget::Byte((row as u128 * total_bytes as u128 / rows as u128) as usize)
+            }
+        }
+    }
+
+    /// Lines per Braille dot row, or `None` in byte mode.
+    pub fn lines_per_dot(&self) -> Option<usize> {
+        match *self {
+            Self::Lines { lines_per_row, .. } => Some(lines_per_row / DOTS_PER_CELL_ROW),
+            Self::Bytes { .. } => None,
+        }
+    }
+
+    /// The Braille glyphs of `row`, merging the shapes of the lines under
+    /// each dot row. `None` in byte mode, which has no shape.
+    pub fn shape(&self, row: usize, index: &MinimapIndex) -> Option<String> {
+        let lines_per_dot = self.lines_per_dot()?;
+        Some(braille_row(std::array::from_fn(|dot| {
+            let first = (row * DOTS_PER_CELL_ROW + dot) * lines_per_dot;
+            index
+                .lines()
+                .get(first..)
+                .unwrap_or_default()
+                .iter()
+                .take(lines_per_dot)
+                .fold(0, |acc, mask| acc | mask)
+        })))
+    }
+}
+
+/// The Braille glyphs for one minimap row, given the shape masks of the
+/// row's four dot rows.
+fn braille_row(dot_rows: [u32; DOTS_PER_CELL_ROW]) -> String {
+    // Bit for (dot column within the cell, dot row) in the Braille block.
+    const DOT_BITS: [[u32; DOTS_PER_CELL_ROW]; 2] =
+        [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+    (0..DOT_COLUMNS / 2)
+        .map(|cell| {
+            let mut bits = 0u32;
+            for (row, mask) in dot_rows.iter().enumerate() {
+                for (side, side_bits) in DOT_BITS.iter().enumerate() {
+                    if mask & (1 << (cell * 2 + side)) != 0 {
+                        bits |= side_bits[row];
+                    }
+                }
+            }
+            char::from_u32(0x2800 + bits).unwrap_or(' ')
+        })
+        .collect()
+}
+
+/// Kinds of minimap marks, in increasing priority: when several land on
+/// one row, the highest is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MinimapMark {
+    Change,
+    SearchMatch,
+    Info,
+    Warning,
+    Error,
+    Cursor,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_masks_follow_text_columns() {
+        assert_eq!(line_mask(b"", 4), 0);
+        assert_eq!(line_mask(b"    ", 4), 0);
+        assert_eq!(line_mask(b"abcd", 4), 0b1);
+        assert_eq!(line_mask(b"    x", 4), 0b10);
+        assert_eq!(line_mask(b"\tx", 4), 0b10);
+        assert_eq!(line_mask("é".as_bytes(), 4), 0b1);
+        let long = vec![b'x'; 1000];
+        assert_eq!(line_mask(&long, 4), (1 << DOT_COLUMNS) - 1);
+    }
+
+    #[test]
+    fn braille_rows_encode_dots() {
+        assert_eq!(braille_row([0; 4]), "⠀".repeat(DOT_COLUMNS / 2));
+        let row = braille_row([0b01, 0b10, 0, 0b11]);
+        assert_eq!(
+            row.chars().next(),
+            Some(char::from_u32(0x2800 + 0x01 + 0x10 + 0x40 + 0x80).unwrap())
+        );
+    }
+
+    #[test]
+    fn scales_map_rows_both_ways() {
+        let short = MinimapScale::for_lines(10, 20);
+        assert_eq!(short.lines_per_dot(), Some(1));
+        assert_eq!(short.row_for_byte(0, |_| 9), 2);
+        assert_eq!(short.target(1), MinimapTarget::Line(4));
+        assert_eq!(short.target(100), MinimapTarget::Line(9));
+
+        let long = MinimapScale::for_lines(1000, 10);
+        assert_eq!(long.lines_per_dot(), Some(25));
+        assert_eq!(long.row_for_byte(0, |_| 999), 9);
+        assert_eq!(long.target(5), MinimapTarget::Line(500));
+
+        let mut index = MinimapIndex::default();
+        index.rebuild(1, b"ab\n\n    cd\n", 4);
+        assert_eq!(index.lines(), &[0b1, 0, 0b10, 0]);
+        let shape = short.shape(0, &index).unwrap();
+        assert_eq!(shape.chars().next(), char::from_u32(0x2800 + 0x01 + 0x20));
+        assert_eq!(short.shape(1, &index), Some(braille_row([0; 4])));
+
+        let bytes = MinimapScale::for_bytes(1000, 10);
+        assert_eq!(bytes.row_for_byte(999, |_| unreachable!()), 9);
+        assert_eq!(bytes.row_for_byte(5000, |_| unreachable!()), 9);
+        assert_eq!(bytes.target(3), MinimapTarget::Byte(300));
+        assert_eq!(bytes.shape(0, &index), None);
+    }
+}
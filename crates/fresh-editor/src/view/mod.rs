@@ -15,6 +15,8 @@ pub mod theme;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod animation;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod code_lens;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod color_support;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod composite_view;
@@ -25,18 +27,28 @@ pub mod controls;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod dimming;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod document_color;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod document_link;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod folding;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod hex_view;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod line_wrap_cache;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod margin;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod minimap;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod overlay;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod scroll_sync;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod soft_break;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod sticky_scroll;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod ui;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod viewport;
@@ -119,6 +119,12 @@ pub enum PopupResolver {
     /// ("trusted" / "restricted" / "blocked") through
     /// `handle_workspace_trust_action`.
     WorkspaceTrust,
+    /// Options of a snippet choice tabstop (`${1|a,b|}`). Confirm replaces
+    /// the tabstop with the selected row's `data`.
+    SnippetChoice,
+    /// LSP color presentation picker. Selected row's `data` is the index
+    /// into the presentations held by `Editor::color_presentation_picker`.
+    ColorPresentation,
 }
 
 /// Content of a popup window
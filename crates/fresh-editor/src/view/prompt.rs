@@ -68,6 +68,18 @@ pub enum PromptType {
     RecordMacro,
     /// Play a macro - prompts for register (0-9)
     PlayMacro,
+    /// Jump to a byte offset in a hex view
+    GotoOffset,
+    /// Search for a byte pattern in a hex view
+    SearchBytes,
+    /// Name the last recorded macro, saving it to the workspace or the config
+    SaveMacro { workspace: bool },
+    /// Pick a named macro to play
+    PlayNamedMacro,
+    /// Pick a named macro to edit as JSON
+    EditNamedMacro,
+    /// Pick a task to run
+    RunTask,
     /// Set a bookmark - prompts for register (0-9)
     SetBookmark,
     /// Jump to a bookmark - prompts for register (0-9)
@@ -161,11 +173,16 @@ pub enum PromptType {
         pending: Vec<(std::path::PathBuf, std::path::PathBuf)>,
         is_cut: bool,
     },
+    /// Confirm applying the edits language servers asked for before an
+    /// explorer rename, move or delete (held in `pending_file_operation`)
+    ConfirmFileOperationEdits,
     /// Confirm loading a large file with non-resynchronizable encoding
     /// (like GB18030, GBK, Shift-JIS, EUC-KR) that requires full file loading
     ConfirmLargeFileEncoding { path: std::path::PathBuf },
     /// Switch to a tab by name (from the current split's open buffers)
     SwitchToTab,
+    /// Pick a clipboard history entry to paste
+    PasteFromHistory,
     /// Run shell command on buffer/selection
     /// If replace is true, replace the input with the output
     /// If replace is false, output goes to a new buffer
@@ -28,6 +28,7 @@ use crate::model::cursor::Cursors;
 use crate::model::event::{BufferId, ContainerId, LeafId, SplitDirection, SplitId};
 use crate::model::marker::MarkerList;
 use crate::view::folding::FoldManager;
+use crate::view::hex_view::HexViewState;
 use crate::view::ui::view_pipeline::Layout;
 use crate::view::viewport::Viewport;
 use crate::{services::plugins::api::ViewTransformPayload, state::ViewMode};
@@ -194,6 +195,11 @@ pub struct BufferViewState {
 
     /// Collapsed folding ranges for this buffer/view.
     pub folds: FoldManager,
+
+    /// Hex view of the buffer's bytes, when toggled on for this buffer in
+    /// this split. Replaces the text rendering; the primary cursor's byte
+    /// offset stays the cursor position.
+    pub hex_view: Option<HexViewState>,
 }
 
 impl BufferViewState {
@@ -230,6 +236,7 @@ impl BufferViewState {
             view_transform_stale: false,
             plugin_state: std::collections::HashMap::new(),
             folds: FoldManager::new(),
+            hex_view: None,
         }
     }
 
@@ -291,6 +298,7 @@ impl Clone for BufferViewState {
             plugin_state: self.plugin_state.clone(),
             // Fold markers are per-view; clones start with no folded ranges.
             folds: FoldManager::new(),
+            hex_view: self.hex_view.clone(),
         }
     }
 }
@@ -1113,6 +1121,27 @@ impl SplitManager {
         }
     }
 
+    /// Create a split manager with a single buffer whose split IDs
+    /// continue after `other`'s, so the two layouts never share an ID
+    /// (each client of the session daemon has its own layout of a
+    /// window).
+    pub fn new_after(buffer_id: BufferId, other: &SplitManager) -> Self {
+        let split_id = SplitId(other.next_split_id);
+        Self {
+            root: SplitNode::leaf(buffer_id, split_id),
+            active_split: LeafId(split_id),
+            next_split_id: other.next_split_id + 1,
+            maximized_split: None,
+            labels: HashMap::new(),
+            focus_history: vec![LeafId(split_id)],
+        }
+    }
+
+    /// Allocate future split IDs after every ID `other` has allocated.
+    pub fn reserve_split_ids(&mut self, other: &SplitManager) {
+        self.next_split_id = self.next_split_id.max(other.next_split_id);
+    }
+
     /// Get the root split node
     pub fn root(&self) -> &SplitNode {
         &self.root
@@ -0,0 +1,250 @@
+//! Sticky scroll: which scope headers to pin at the top of a viewport.
+//!
+//! When the top of a viewport is inside a function, impl, class, … whose
+//! header line has scrolled out of view, that header is pinned over the
+//! first rows so the reader keeps their bearings. Nested scopes stack,
+//! outermost first, up to [`MAX_STICKY_LINES`].
+//!
+//! Scopes come from the LSP folding ranges when the server provided any,
+//! otherwise from the indentation structure. The indent scan only reads a
+//! bounded window above the viewport, and shrinks the window until it lies
+//! in loaded bytes, so it is safe on the huge-file lazy loading path.
+//!
+//! The computation works on *visual rows*: `rows[i]` is the line-start byte
+//! of the source line drawn on row `i`. Wrapped lines repeat their line
+//! start, and lines hidden by folds simply don't appear, so the result
+//! matches what is actually on screen.
+
+use std::ops::Range;
+
+use crate::model::buffer::Buffer;
+use crate::view::folding::indent_folding::slice_indent;
+
+/// Upper bound on the number of pinned header rows.
+pub const MAX_STICKY_LINES: usize = 5;
+
+/// Scan windows tried, largest first, when looking for indent scopes above
+/// the viewport. Smaller windows are the fallback when the larger ones reach
+/// into bytes that aren't loaded yet.
+const INDENT_SCAN_WINDOWS: [usize; 3] = [64 * 1024, 16 * 1024, 4 * 1024];
+
+/// Bytes read past the last row so a blank row can borrow the indent of the
+/// line that follows it.
+const INDENT_LOOKAHEAD: usize = 4 * 1024;
+
+/// Where scope structure comes from.
+#[derive(Debug, Clone, Copy)]
+pub enum ScopeSource<'a> {
+    /// `header..end` byte ranges: `header` is the header line's start and
+    /// `end` the start of the scope's last line (see
+    /// [`LspFoldRanges::scope_ranges`](crate::view::folding::LspFoldRanges::scope_ranges)).
+    Ranges(&'a [Range<usize>]),
+    /// Lines indented deeper than a preceding line belong to its scope.
+    Indent { tab_size: usize },
+}
+
+/// Headers to pin above the viewport, outermost first, as line-start bytes.
+///
+/// Header `d` covers visual row `d`, so it is only pinned while the line on
+/// that row is still inside it (and inside every header above it). This is
+/// what makes nested headers slide away one by one as their scopes end.
+pub fn sticky_headers(
+    buffer: &Buffer,
+    source: ScopeSource<'_>,
+    rows: &[usize],
+    max_lines: usize,
+) -> Vec<usize> {
+    let rows = &rows[..rows.len().min(max_lines)];
+    let Some(&first_row) = rows.first() else {
+        return Vec::new();
+    };
+
+    let window = match source {
+        ScopeSource::Indent { tab_size } => {
+            let last_row = rows.iter().copied().max().unwrap_or(first_row);
+            IndentWindow::load(buffer, first_row, last_row, tab_size)
+        }
+        ScopeSource::Ranges(_) => None,
+    };
+
+    let mut headers = Vec::new();
+    for (depth, &row) in rows.iter().enumerate() {
+        let chain = match source {
+            ScopeSource::Ranges(ranges) => enclosing_ranges(ranges, row),
+            ScopeSource::Indent { .. } => window
+                .as_ref()
+                .map_or_else(Vec::new, |window| window.enclosing(row)),
+        };
+        if chain.len() <= depth || chain[..depth] != headers[..] {
+            break;
+        }
+        headers.push(chain[depth]);
+    }
+    headers
+}
+
+/// Headers of the ranges strictly enclosing the line starting at `row`.
+fn enclosing_ranges(ranges: &[Range<usize>], row: usize) -> Vec<usize> {
+    let mut headers: Vec<usize> = ranges
+        .iter()
+        .filter(|r| r.start < row && row <= r.end)
+        .map(|r| r.start)
+        .collect();
+    headers.sort_unstable();
+    headers.dedup();
+    headers
+}
+
+/// One parsed line of an [`IndentWindow`].
+#[derive(Debug)]
+struct IndentLine {
+    start: usize,
+    indent: usize,
+    blank: bool,
+}
+
+/// The lines around the viewport with their indentation.
+#[derive(Debug)]
+struct IndentWindow {
+    lines: Vec<IndentLine>,
+}
+
+impl IndentWindow {
+    fn load(buffer: &Buffer, first_row: usize, last_row: usize, tab_size: usize) -> Option<Self> {
+        let end = last_row.saturating_add(INDENT_LOOKAHEAD).min(buffer.len());
+        INDENT_SCAN_WINDOWS.iter().find_map(|&size| {
+            let start = first_row.saturating_sub(size);
+            // Empty means the range isn't fully loaded (or is empty).
+            let bytes = buffer.slice_bytes(start..end);
+            (!bytes.is_empty()).then(|| Self::parse(&bytes, start, tab_size))
+        })
+    }
+
+    fn parse(bytes: &[u8], base: usize, tab_size: usize) -> Self {
+        let mut lines = Vec::new();
+        let mut offset = base;
+        for (i, line) in bytes.split(|&b| b == b'\n').enumerate() {
+            // A window that doesn't start at the top of the buffer begins
+            // mid-line; that partial line's indent is meaningless.
+            if i > 0 || base == 0 {
+                let (indent, blank) = slice_indent(line, tab_size);
+                lines.push(IndentLine {
+                    start: offset,
+                    indent,
+                    blank,
+                });
+            }
+            offset += line.len() + 1;
+        }
+        Self { lines }
+    }
+
+    /// Headers of the indent scopes enclosing the line starting at `row`.
+    fn enclosing(&self, row: usize) -> Vec<usize> {
+        let Ok(idx) = self.lines.binary_search_by_key(&row, |l| l.start) else {
+            return Vec::new();
+        };
+        // A blank line belongs to the scope of the code that follows it.
+        let mut indent = self.lines[idx..]
+            .iter()
+            .find(|l| !l.blank)
+            .map_or(0, |l| l.indent);
+
+        let mut chain = Vec::new();
+        for line in self.lines[..idx].iter().rev() {
+            if indent == 0 {
+                break;
+            }
+            if !line.blank && line.indent < indent {
+                chain.push(line.start);
+                indent = line.indent;
+            }
+        }
+        chain.reverse();
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+impl Foo {
+    fn bar() {
+        let a = 1;
+
+        let b = 2;
+    }
+
+    fn baz() {}
+}
+fn main() {}
+";
+
+    fn line(n: usize) -> usize {
+        SOURCE.split_inclusive('\n').take(n).map(str::len).sum()
+    }
+
+    fn rows(lines: &[usize]) -> Vec<usize> {
+        lines.iter().map(|&n| line(n)).collect()
+    }
+
+    const INDENT: ScopeSource<'static> = ScopeSource::Indent { tab_size: 4 };
+
+    #[test]
+    fn nested_indent_scopes_stack_outermost_first() {
+        let buffer = Buffer::from_str_test(SOURCE);
+        let headers = sticky_headers(&buffer, INDENT, &rows(&[2, 3, 4, 5]), 5);
+        assert_eq!(headers, vec![line(0), line(1)]);
+    }
+
+    #[test]
+    fn blank_row_takes_indent_of_following_line() {
+        let buffer = Buffer::from_str_test(SOURCE);
+        let headers = sticky_headers(&buffer, INDENT, &rows(&[3, 3, 4]), 5);
+        assert_eq!(headers, vec![line(0), line(1)]);
+    }
+
+    #[test]
+    fn inner_header_drops_when_its_scope_ends_under_it() {
+        let buffer = Buffer::from_str_test(SOURCE);
+        // Row 1 shows `}` closing `bar`, which is no longer inside it.
+        let headers = sticky_headers(&buffer, INDENT, &rows(&[4, 5, 6]), 5);
+        assert_eq!(headers, vec![line(0)]);
+    }
+
+    #[test]
+    fn visible_header_is_not_pinned() {
+        let buffer = Buffer::from_str_test(SOURCE);
+        assert!(sticky_headers(&buffer, INDENT, &rows(&[0, 1]), 5).is_empty());
+        assert!(sticky_headers(&buffer, INDENT, &rows(&[9]), 5).is_empty());
+    }
+
+    #[test]
+    fn wrapped_rows_repeat_their_line() {
+        let buffer = Buffer::from_str_test(SOURCE);
+        // Line 2 wraps over three rows.
+        let headers = sticky_headers(&buffer, INDENT, &rows(&[2, 2, 2, 3]), 5);
+        assert_eq!(headers, vec![line(0), line(1)]);
+    }
+
+    #[test]
+    fn max_lines_caps_the_stack() {
+        let buffer = Buffer::from_str_test(SOURCE);
+        let headers = sticky_headers(&buffer, INDENT, &rows(&[2, 3, 4]), 1);
+        assert_eq!(headers, vec![line(0)]);
+    }
+
+    #[test]
+    fn folding_ranges_drive_headers() {
+        let buffer = Buffer::from_str_test(SOURCE);
+        let ranges = [line(0)..line(8), line(1)..line(5)];
+        let source = ScopeSource::Ranges(&ranges);
+        let headers = sticky_headers(&buffer, source, &rows(&[2, 3, 4]), 5);
+        assert_eq!(headers, vec![line(0), line(1)]);
+        // The range's last line is still inside it; the next one isn't.
+        let headers = sticky_headers(&buffer, source, &rows(&[5, 6]), 5);
+        assert_eq!(headers, vec![line(0)]);
+    }
+}
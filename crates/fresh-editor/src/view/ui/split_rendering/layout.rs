@@ -104,6 +104,22 @@ pub(super) fn split_layout(
     }
 }
 
+/// Take a minimap column of `width` cells off the right edge of
+/// `content_rect`. Returns `None`, leaving the rect alone, when the split is
+/// too narrow to spare it.
+pub(super) fn carve_minimap(content_rect: &mut Rect, width: u16) -> Option<Rect> {
+    if content_rect.width < width.saturating_mul(3) {
+        return None;
+    }
+    content_rect.width -= width;
+    Some(Rect::new(
+        content_rect.x + content_rect.width,
+        content_rect.y,
+        width,
+        content_rect.height,
+    ))
+}
+
 /// Return the open-buffer list and tab scroll offset for a split.
 pub(super) fn split_buffers_for_tabs(
     split_view_states: Option<&HashMap<LeafId, SplitViewState>>,
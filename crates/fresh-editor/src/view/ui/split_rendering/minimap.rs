@@ -0,0 +1,155 @@
+//! Minimap rendering: the text's shape in Braille plus a column of marks.
+//!
+//! See [`crate::view::minimap`] for the geometry. Marks come from the
+//! state's diagnostic and search overlays, its margin line indicators (git
+//! gutter, live diff) and the split's cursors.
+
+use crate::model::cursor::Cursors;
+use crate::state::EditorState;
+use crate::view::minimap::{MinimapMark, MinimapScale};
+use crate::view::overlay::OverlayNamespace;
+use crate::view::theme::Theme;
+use crate::view::viewport::Viewport;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// Glyph drawn in the marks column.
+const MARK_GLYPH: &str = "▐";
+
+/// Render the minimap for `state` into `area` and return the scale used,
+/// for mapping clicks back to document positions.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_minimap(
+    frame: &mut Frame,
+    state: &mut EditorState,
+    cursors: &Cursors,
+    viewport: &Viewport,
+    area: Rect,
+    theme: &Theme,
+    search_namespace: &OverlayNamespace,
+    large_file_threshold_bytes: u64,
+    use_terminal_bg: bool,
+) -> MinimapScale {
+    let rows = area.height as usize;
+    let buffer_len = state.buffer.len();
+    let line_mode =
+        buffer_len <= large_file_threshold_bytes as usize && state.buffer.line_count().is_some();
+
+    let scale = if line_mode {
+        let version = state.buffer.version();
+        if !state.minimap_index.is_built_for(version) {
+            let _span = tracing::trace_span!("minimap_index_rebuild").entered();
+            let text = state
+                .buffer
+                .get_text_range_mut(0, buffer_len)
+                .unwrap_or_default();
+            let tab_size = state.buffer_settings.tab_size;
+            state.minimap_index.rebuild(version, &text, tab_size);
+        }
+        MinimapScale::for_lines(state.minimap_index.lines().len(), rows)
+    } else {
+        MinimapScale::for_bytes(buffer_len, rows)
+    };
+
+    let marks = collect_marks(state, cursors, &scale, rows, theme, search_namespace);
+
+    // Rows covering the lines on screen get a highlighted background.
+    let top_row = scale.row_for_byte(viewport.top_byte, |b| state.buffer.get_line_number(b));
+    let band = match scale {
+        MinimapScale::Lines { lines_per_row, .. } => {
+            let top_line = state.buffer.get_line_number(viewport.top_byte);
+            let last_line = top_line + (viewport.height as usize).saturating_sub(1);
+            top_row..=last_line / lines_per_row
+        }
+        MinimapScale::Bytes { .. } => top_row..=top_row,
+    };
+
+    let bg = if use_terminal_bg {
+        Color::Reset
+    } else {
+        theme.editor_bg
+    };
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let row_bg = if band.contains(&row) {
+                theme.current_line_bg
+            } else {
+                bg
+            };
+            let mark = match marks[row] {
+                Some((_, color)) => Span::styled(MARK_GLYPH, Style::default().fg(color).bg(row_bg)),
+                None => Span::styled(" ", Style::default().bg(row_bg)),
+            };
+            let shape = scale
+                .shape(row, &state.minimap_index)
+                .unwrap_or_else(|| " ".repeat(area.width.saturating_sub(1) as usize));
+            Line::from(vec![
+                mark,
+                Span::styled(shape, Style::default().fg(theme.line_number_fg).bg(row_bg)),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).style(Style::default().bg(bg)), area);
+    scale
+}
+
+/// The highest-priority mark of each row, with its color.
+fn collect_marks(
+    state: &EditorState,
+    cursors: &Cursors,
+    scale: &MinimapScale,
+    rows: usize,
+    theme: &Theme,
+    search_namespace: &OverlayNamespace,
+) -> Vec<Option<(MinimapMark, Color)>> {
+    let mut marks: Vec<Option<(MinimapMark, Color)>> = vec![None; rows];
+    let mut place = |byte: usize, kind: MinimapMark, color: Color| {
+        let row = scale.row_for_byte(byte, |b| state.buffer.get_line_number(b));
+        if let Some(slot) = marks.get_mut(row) {
+            if slot.is_none_or(|(existing, _)| kind > existing) {
+                *slot = Some((kind, color));
+            }
+        }
+    };
+
+    let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
+    for overlay in state.overlays.all() {
+        let Some(ns) = overlay.namespace.as_ref() else {
+            continue;
+        };
+        let mark = if *ns == diagnostic_ns {
+            match overlay.theme_key {
+                Some("diagnostic.error_bg") => (MinimapMark::Error, theme.diagnostic_error_fg),
+                Some("diagnostic.warning_bg") => {
+                    (MinimapMark::Warning, theme.diagnostic_warning_fg)
+                }
+                Some("diagnostic.info_bg") => (MinimapMark::Info, theme.diagnostic_info_fg),
+                _ => continue,
+            }
+        } else if ns == search_namespace {
+            (MinimapMark::SearchMatch, theme.search_match_bg)
+        } else {
+            continue;
+        };
+        let start = overlay.range(&state.marker_list).start;
+        place(start, mark.0, mark.1);
+    }
+
+    for (byte, indicator) in
+        state
+            .margins
+            .get_indicators_for_viewport(0, state.buffer.len() + 1, |byte| byte)
+    {
+        place(byte, MinimapMark::Change, indicator.color);
+    }
+
+    for (_, cursor) in cursors.iter() {
+        place(cursor.position, MinimapMark::Cursor, theme.cursor);
+    }
+
+    marks
+}
@@ -3,8 +3,8 @@
 //! This module is organized into two tiers:
 //!
 //! - **Self-contained leaves** (`spans`, `style`, `char_style`, `base_tokens`,
-//!   `transforms`, `view_data`, `folding`, `scrollbar`, `layout`, `gutter`,
-//!   `post_pass`) — none of these depend on any shared render-time carrier.
+//!   `transforms`, `view_data`, `folding`, `scrollbar`, `minimap`, `layout`,
+//!   `gutter`, `post_pass`) — none of these depend on any shared render-time carrier.
 //! - **Orchestration** (`orchestration::*`) — the only files that share
 //!   `SelectionContext` / `DecorationContext`. Quarantined in a subdirectory
 //!   so the coupling is visible from `ls` alone.
@@ -17,6 +17,7 @@ mod char_style;
 mod folding;
 mod gutter;
 mod layout;
+mod minimap;
 mod orchestration;
 mod post_pass;
 mod scrollbar;
@@ -88,9 +89,12 @@ impl SplitRenderer {
         software_cursor_only: bool,
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
+        show_minimap: bool,
+        search_namespace: &crate::view::overlay::OverlayNamespace,
         diagnostics_inline_text: bool,
         show_tilde: bool,
         highlight_current_column: bool,
+        sticky_scroll: bool,
         hide_current_line_on_selection: bool,
         cell_theme_map: &mut Vec<crate::app::types::CellThemeInfo>,
         screen_width: u16,
@@ -105,6 +109,7 @@ impl SplitRenderer {
         Vec<(LeafId, u16, u16, u16)>,
         HashMap<LeafId, Vec<ViewLineMapping>>,
         Vec<(LeafId, BufferId, Rect, usize, usize, usize)>,
+        Vec<(LeafId, BufferId, Rect, crate::view::minimap::MinimapScale)>,
         Vec<(
             crate::model::event::ContainerId,
             SplitDirection,
@@ -145,9 +150,12 @@ impl SplitRenderer {
             software_cursor_only,
             show_vertical_scrollbar,
             show_horizontal_scrollbar,
+            show_minimap,
+            search_namespace,
             diagnostics_inline_text,
             show_tilde,
             highlight_current_column,
+            sticky_scroll,
             hide_current_line_on_selection,
             cell_theme_map,
             screen_width,
@@ -173,6 +181,7 @@ impl SplitRenderer {
         tab_bar_visible: bool,
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
+        show_minimap: bool,
         diagnostics_inline_text: bool,
         show_tilde: bool,
     ) -> HashMap<LeafId, Vec<ViewLineMapping>> {
@@ -192,6 +201,7 @@ impl SplitRenderer {
             tab_bar_visible,
             show_vertical_scrollbar,
             show_horizontal_scrollbar,
+            show_minimap,
             diagnostics_inline_text,
             show_tilde,
         )
@@ -278,6 +288,7 @@ impl SplitRenderer {
             diagnostics_inline_text,
             show_tilde,
             highlight_current_column,
+            /* sticky_scroll */ false,
             cell_theme_map,
             screen_width,
             &mut sink,
@@ -405,6 +416,7 @@ mod tests {
             &ViewMode::Source, // Tests use source mode
             false,             // inline diagnostics off for test
             &[],
+            None,
         );
 
         let mut dummy_theme_map = Vec::new();
@@ -2332,6 +2344,7 @@ mod tests {
             &ViewMode::Source,
             false,
             &[],
+            None,
         );
 
         render_view_lines(LineRenderInput {
@@ -0,0 +1,168 @@
+//! Hex view rendering for a buffer split.
+//!
+//! Draws a column header followed by one `offset  hex bytes  |ASCII|` row
+//! per `bytes_per_row` bytes. Only the bytes of the rows on screen are read
+//! from the piece tree.
+
+use super::super::spans::compress_chars;
+use crate::model::cursor::Cursors;
+use crate::state::EditorState;
+use crate::view::hex_view::{ascii_char, HexLayout, HexViewState};
+use crate::view::theme::Theme;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// Render `state`'s bytes as a hex view into `area`.
+///
+/// Updates `hex` with the layout used, and scrolls it to the cursor first
+/// if a keyboard move asked for that.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_hex_view(
+    frame: &mut Frame,
+    area: Rect,
+    state: &mut EditorState,
+    cursors: &Cursors,
+    hex: &mut HexViewState,
+    theme: &Theme,
+    is_active: bool,
+    hide_cursor: bool,
+    use_terminal_bg: bool,
+    software_cursor_only: bool,
+    pending_hardware_cursor: &mut Option<(u16, u16)>,
+) {
+    let total = state.buffer.len();
+    let layout = HexLayout::new(area.width, total);
+    let per_row = layout.bytes_per_row;
+    let rows = area.height.saturating_sub(1) as usize;
+
+    let cursor = cursors.primary();
+    let position = cursor.position.min(total);
+    let selection = cursor.selection_range();
+
+    hex.bytes_per_row = per_row;
+    hex.visible_rows = rows;
+    hex.top_row = hex.top_row.min(total / per_row);
+    if hex.reveal_cursor {
+        hex.reveal(position);
+        hex.reveal_cursor = false;
+    }
+
+    let first = hex.top_row * per_row;
+    let bytes = state
+        .buffer
+        .get_text_range_mut(first, rows * per_row)
+        .unwrap_or_default();
+
+    let bg = if use_terminal_bg {
+        Color::Reset
+    } else {
+        theme.editor_bg
+    };
+    let base = Style::default().fg(theme.editor_fg).bg(bg);
+    let dim = base.fg(theme.line_number_fg);
+    let width = area.width as usize;
+
+    let mut lines = Vec::with_capacity(rows + 1);
+    let mut header = vec![(' ', dim); width];
+    for i in 0..per_row {
+        let label = format!("{:02x}", i);
+        put_str(&mut header, layout.hex_column(i), &label, dim);
+    }
+    lines.push(Line::from(compress_chars(header)));
+
+    let mut cursor_cell = None;
+    for row in 0..rows {
+        let row_start = first + row * per_row;
+        // A row starting exactly at the end of the file is still drawn, so
+        // there is a cell to put the cursor on to append bytes.
+        if row_start > total {
+            break;
+        }
+        let mut cells = vec![(' ', base); width];
+        put_str(
+            &mut cells,
+            0,
+            &format!("{:0w$x}", row_start, w = layout.offset_digits),
+            dim,
+        );
+        put_str(&mut cells, layout.ascii_column(0) - 1, "|", dim);
+        let row_len = total.saturating_sub(row_start).min(per_row);
+        put_str(&mut cells, layout.ascii_column(row_len), "|", dim);
+
+        for i in 0..per_row {
+            let offset = row_start + i;
+            let byte = bytes.get(offset - first).copied();
+            let selected = selection.as_ref().is_some_and(|r| r.contains(&offset));
+            let mut style = match byte {
+                Some(0) => dim,
+                _ => base,
+            };
+            if selected {
+                style = style.bg(theme.selection_bg);
+            }
+
+            let hex_col = layout.hex_column(i);
+            let ascii_col = layout.ascii_column(i);
+            if let Some(byte) = byte {
+                put_str(&mut cells, hex_col, &format!("{:02x}", byte), style);
+                put_str(&mut cells, ascii_col, &ascii_char(byte).to_string(), style);
+            }
+
+            if offset == position {
+                // The column being typed into gets the cursor; the other
+                // column marks the same byte.
+                let (focus_col, other_col) = if hex.ascii_focus {
+                    (ascii_col, hex_col)
+                } else {
+                    (hex_col + usize::from(hex.low_nibble), ascii_col)
+                };
+                let mark = style.bg(theme.current_line_bg);
+                restyle(
+                    &mut cells,
+                    other_col,
+                    if hex.ascii_focus { 2 } else { 1 },
+                    mark,
+                );
+                if !is_active || hide_cursor {
+                    restyle(&mut cells, focus_col, 1, style.bg(theme.inactive_cursor));
+                } else {
+                    cursor_cell = Some((focus_col, row + 1));
+                    if software_cursor_only {
+                        restyle(
+                            &mut cells,
+                            focus_col,
+                            1,
+                            style.add_modifier(Modifier::REVERSED),
+                        );
+                    }
+                }
+            }
+        }
+        lines.push(Line::from(compress_chars(cells)));
+    }
+
+    frame.render_widget(Paragraph::new(lines).style(base), area);
+
+    if let Some((col, row)) = cursor_cell {
+        if col < width && row < area.height as usize {
+            *pending_hardware_cursor = Some((area.x + col as u16, area.y + row as u16));
+        }
+    }
+}
+
+/// Write `text` into `cells` from column `col`, clipped to the row.
+fn put_str(cells: &mut [(char, Style)], col: usize, text: &str, style: Style) {
+    for (cell, ch) in cells.iter_mut().skip(col).zip(text.chars()) {
+        *cell = (ch, style);
+    }
+}
+
+/// Replace the style of `len` cells starting at `col`.
+fn restyle(cells: &mut [(char, Style)], col: usize, len: usize, style: Style) {
+    for cell in cells.iter_mut().skip(col).take(len) {
+        cell.1 = style;
+    }
+}
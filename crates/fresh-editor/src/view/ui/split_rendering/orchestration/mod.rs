@@ -11,19 +11,22 @@
 //! is a thin façade that re-exports them via the `SplitRenderer` struct.
 
 pub(super) mod contexts;
+pub(super) mod hex_view;
 pub(super) mod overlay_sweep;
 pub(super) mod overlays;
 pub(super) mod render_buffer;
 pub(super) mod render_composite;
 pub(super) mod render_line;
 pub(super) mod selection_sweep;
+pub(super) mod sticky_scroll;
 pub(super) mod tail_fill;
 
 use super::base_tokens::build_base_tokens;
 use super::layout::{
-    render_separator, resolve_view_preferences, split_buffers_for_tabs, split_layout,
-    sync_viewport_to_content, SplitLayout,
+    carve_minimap, render_separator, resolve_view_preferences, split_buffers_for_tabs,
+    split_layout, sync_viewport_to_content, SplitLayout,
 };
+use super::minimap::render_minimap;
 use super::scrollbar::{
     compute_max_line_length, render_composite_scrollbar, render_horizontal_scrollbar,
     render_scrollbar, scrollbar_line_counts,
@@ -35,8 +38,10 @@ use crate::model::event::{BufferId, EventLog, LeafId, SplitDirection};
 use crate::primitives::ansi_background::AnsiBackground;
 use crate::state::EditorState;
 use crate::view::folding::FoldManager;
+use crate::view::minimap::{MinimapScale, MINIMAP_WIDTH};
 use crate::view::split::SplitManager;
 use crate::view::ui::tabs::TabsRenderer;
+use hex_view::render_hex_view;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::widgets::Paragraph;
@@ -90,9 +95,13 @@ pub(crate) fn render_content(
     software_cursor_only: bool,
     show_vertical_scrollbar: bool,
     show_horizontal_scrollbar: bool,
+    show_minimap: bool,
+    // Namespace of the window's search-match overlays, marked on the minimap
+    search_namespace: &crate::view::overlay::OverlayNamespace,
     diagnostics_inline_text: bool,
     show_tilde: bool,
     highlight_current_column: bool,
+    sticky_scroll: bool,
     hide_current_line_on_selection: bool,
     cell_theme_map: &mut Vec<crate::app::types::CellThemeInfo>,
     screen_width: u16,
@@ -107,6 +116,7 @@ pub(crate) fn render_content(
     Vec<(LeafId, u16, u16, u16)>,                      // maximize split button areas
     HashMap<LeafId, Vec<ViewLineMapping>>,             // view line mappings for mouse clicks
     Vec<(LeafId, BufferId, Rect, usize, usize, usize)>, // horizontal scrollbar areas (rect + max_content_width + thumb_start + thumb_end)
+    Vec<(LeafId, BufferId, Rect, MinimapScale)>,        // minimap areas
     Vec<(
         crate::model::event::ContainerId,
         SplitDirection,
@@ -209,6 +219,7 @@ pub(crate) fn render_content(
     let mut split_areas = Vec::new();
     let mut horizontal_scrollbar_areas: Vec<(LeafId, BufferId, Rect, usize, usize, usize)> =
         Vec::new();
+    let mut minimap_areas: Vec<(LeafId, BufferId, Rect, MinimapScale)> = Vec::new();
     let mut tab_layouts: HashMap<LeafId, crate::view::ui::tabs::TabLayout> = HashMap::new();
     let mut close_split_areas = Vec::new();
     let mut maximize_split_areas = Vec::new();
@@ -267,7 +278,7 @@ pub(crate) fn render_content(
         let is_non_scrollable = buffers.get(&buffer_id).is_some_and(|s| !s.scrollable);
         let panel_show_vscroll = show_vertical_scrollbar && !is_non_scrollable;
 
-        let layout = if is_inner_group_leaf {
+        let mut layout = if is_inner_group_leaf {
             // Inner leaf: split_area IS the content rect already.
             SplitLayout {
                 tabs_rect: Rect::new(split_area.x, split_area.y, 0, 0),
@@ -545,6 +556,71 @@ pub(crate) fn render_content(
                 continue;
             }
 
+            // A buffer toggled to hex view in this split skips the text
+            // pipeline entirely.
+            let hex_view = split_view_states
+                .as_deref_mut()
+                .and_then(|vs| vs.get_mut(&split_id))
+                .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+                .and_then(|bs| bs.hex_view.as_mut().map(|hex| (hex, bs.cursors.clone())));
+            if let Some((hex, cursors)) = hex_view {
+                render_hex_view(
+                    frame,
+                    layout.content_rect,
+                    state,
+                    &cursors,
+                    hex,
+                    theme,
+                    panel_focused,
+                    hide_cursor,
+                    use_terminal_bg,
+                    software_cursor_only,
+                    pending_hardware_cursor,
+                );
+                let total_rows = state.buffer.len() / hex.bytes_per_row + 1;
+                let (thumb_start, thumb_end) = if show_vertical_scrollbar && !is_non_scrollable {
+                    render_composite_scrollbar(
+                        frame,
+                        layout.scrollbar_rect,
+                        total_rows,
+                        hex.top_row,
+                        hex.visible_rows,
+                        is_active,
+                        theme,
+                    )
+                } else {
+                    (0, 0)
+                };
+                split_areas.push((
+                    split_id,
+                    buffer_id,
+                    layout.content_rect,
+                    layout.scrollbar_rect,
+                    thumb_start,
+                    thumb_end,
+                ));
+                if show_horizontal_scrollbar {
+                    horizontal_scrollbar_areas.push((
+                        split_id,
+                        buffer_id,
+                        layout.horizontal_scrollbar_rect,
+                        0,
+                        0,
+                        0,
+                    ));
+                }
+                view_line_mappings.insert(split_id, Vec::new());
+                continue;
+            }
+
+            // The minimap takes its columns from the text area only;
+            // composite and hex views above keep the full width.
+            let minimap_rect = if show_minimap && !is_non_scrollable && !is_inner_group_leaf {
+                carve_minimap(&mut layout.content_rect, MINIMAP_WIDTH)
+            } else {
+                None
+            };
+
             // Get viewport from SplitViewState (authoritative source)
             // We need to get it mutably for sync operations
             // Use as_deref() to get Option<&HashMap> for read-only operations
@@ -663,6 +739,7 @@ pub(crate) fn render_content(
                 diagnostics_inline_text,
                 split_show_tilde,
                 highlight_current_column && state.show_cursors,
+                sticky_scroll && !is_virtual_buffer,
                 cell_theme_map,
                 screen_width,
                 pending_hardware_cursor,
@@ -698,6 +775,21 @@ pub(crate) fn render_content(
                 (0, 0)
             };
 
+            if let Some(minimap_rect) = minimap_rect {
+                let scale = render_minimap(
+                    frame,
+                    state,
+                    &split_cursors,
+                    &viewport,
+                    minimap_rect,
+                    theme,
+                    search_namespace,
+                    large_file_threshold_bytes,
+                    use_terminal_bg,
+                );
+                minimap_areas.push((split_id, buffer_id, minimap_rect, scale));
+            }
+
             // Compute the actual max line length for horizontal scrollbar
             let max_content_width = if show_horizontal_scrollbar && !viewport.line_wrap_enabled {
                 let mcw = compute_max_line_length(state, &mut viewport);
@@ -848,6 +940,7 @@ pub(crate) fn render_content(
         maximize_split_areas,
         view_line_mappings,
         horizontal_scrollbar_areas,
+        minimap_areas,
         grouped_separator_areas,
     )
 }
@@ -871,6 +964,7 @@ pub(crate) fn compute_content_layout(
     tab_bar_visible: bool,
     show_vertical_scrollbar: bool,
     show_horizontal_scrollbar: bool,
+    show_minimap: bool,
     diagnostics_inline_text: bool,
     show_tilde: bool,
 ) -> HashMap<LeafId, Vec<ViewLineMapping>> {
@@ -887,7 +981,7 @@ pub(crate) fn compute_content_layout(
                 .get(&split_id)
                 .is_some_and(|vs| vs.suppress_chrome);
 
-        let layout = split_layout(
+        let mut layout = split_layout(
             split_area,
             split_tab_bar_visible,
             show_vertical_scrollbar,
@@ -899,11 +993,19 @@ pub(crate) fn compute_content_layout(
             None => continue,
         };
 
-        // Skip composite buffers — they don't produce view_line_mappings
-        if state.is_composite_buffer {
+        // Skip composite buffers and hex views — they don't produce
+        // view_line_mappings
+        let is_hex_view = split_view_states
+            .get(&split_id)
+            .and_then(|vs| vs.keyed_states.get(&buffer_id))
+            .is_some_and(|bs| bs.hex_view.is_some());
+        if state.is_composite_buffer || is_hex_view {
             view_line_mappings.insert(split_id, Vec::new());
             continue;
         }
+        if show_minimap && state.scrollable {
+            carve_minimap(&mut layout.content_rect, MINIMAP_WIDTH);
+        }
 
         // Get viewport from SplitViewState (authoritative source)
         let viewport_clone = split_view_states
@@ -981,7 +1083,8 @@ pub(crate) fn compute_content_layout(
             effective_highlight_current_line,
             diagnostics_inline_text,
             show_tilde,
-            None, // No cell theme map for layout-only computation
+            false, // Sticky headers are drawn over rows, not part of the layout
+            None,  // No cell theme map for layout-only computation
         );
 
         view_line_mappings.insert(split_id, layout_output.view_line_mappings);
@@ -82,6 +82,10 @@ pub(crate) fn selection_context(state: &EditorState, cursors: &Cursors) -> Selec
 /// Build the [`DecorationContext`] for the current viewport: syntax
 /// highlights, LSP overlays, diagnostics, virtual text, line indicators
 /// (git gutter + diff-since-saved), and fold indicators.
+///
+/// `sticky_header_start` is the first byte of the topmost sticky scroll
+/// header, if any; syntax highlighting is widened to cover it so the pinned
+/// headers are colored from the same spans as the viewport.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn decoration_context(
     state: &mut EditorState,
@@ -94,6 +98,7 @@ pub(crate) fn decoration_context(
     view_mode: &ViewMode,
     diagnostics_inline_text: bool,
     view_lines: &[ViewLine],
+    sticky_header_start: Option<usize>,
 ) -> DecorationContext {
     use crate::view::folding::indent_folding;
 
@@ -102,6 +107,7 @@ pub(crate) fn decoration_context(
     // viewport boundaries.
     let viewport_size = viewport_end.saturating_sub(viewport_start);
     let highlight_start = viewport_start.saturating_sub(viewport_size);
+    let highlight_start = sticky_header_start.map_or(highlight_start, |h| h.min(highlight_start));
     let highlight_end = viewport_end
         .saturating_add(viewport_size)
         .min(state.buffer.len());
@@ -18,6 +18,7 @@ use super::super::view_data::build_view_data;
 use super::contexts::SelectionContext;
 use super::overlays::{decoration_context, selection_context};
 use super::render_line::{render_view_lines, LastLineEnd, LineRenderInput, LineRenderOutput};
+use super::sticky_scroll::{render_sticky_lines, sticky_headers_for_view, STICKY_HIGHLIGHT_REACH};
 use crate::app::types::{CellThemeInfo, ViewLineMapping};
 use crate::model::cursor::Cursors;
 use crate::model::event::{BufferId, EventLog};
@@ -110,6 +111,7 @@ pub(crate) fn compute_buffer_layout(
     highlight_current_line: bool,
     diagnostics_inline_text: bool,
     show_tilde: bool,
+    sticky_scroll: bool,
     cell_theme_map: Option<(&mut Vec<CellThemeInfo>, u16)>,
 ) -> BufferLayoutOutput {
     let _span = tracing::trace_span!("compute_buffer_layout").entered();
@@ -319,19 +321,6 @@ pub(crate) fn compute_buffer_layout(
         adjusted_visible_count,
     );
 
-    let decorations = decoration_context(
-        state,
-        viewport_start,
-        viewport_end,
-        selection.primary_cursor_position,
-        folds,
-        theme,
-        highlight_context_bytes,
-        &view_mode,
-        diagnostics_inline_text,
-        &view_data.lines,
-    );
-
     let calculated_offset = viewport.top_view_line_offset;
 
     tracing::trace!(
@@ -350,6 +339,30 @@ pub(crate) fn compute_buffer_layout(
             (&view_data.lines[..], view_anchor)
         };
 
+    let sticky_headers = if sticky_scroll {
+        sticky_headers_for_view(state, view_lines_to_render, visible_count)
+    } else {
+        Vec::new()
+    };
+    let sticky_header_start = sticky_headers
+        .first()
+        .copied()
+        .filter(|&header| viewport_start.saturating_sub(header) <= STICKY_HIGHLIGHT_REACH);
+
+    let decorations = decoration_context(
+        state,
+        viewport_start,
+        viewport_end,
+        selection.primary_cursor_position,
+        folds,
+        theme,
+        highlight_context_bytes,
+        &view_mode,
+        diagnostics_inline_text,
+        &view_data.lines,
+        sticky_header_start,
+    );
+
     // Use provided cell theme map or a temporary dummy
     let mut dummy_map = Vec::new();
     let (map_ref, sw) = match cell_theme_map {
@@ -357,7 +370,7 @@ pub(crate) fn compute_buffer_layout(
         None => (&mut dummy_map, 0u16),
     };
 
-    let render_output = render_view_lines(LineRenderInput {
+    let mut render_output = render_view_lines(LineRenderInput {
         state,
         theme,
         view_lines: view_lines_to_render,
@@ -383,6 +396,28 @@ pub(crate) fn compute_buffer_layout(
         screen_width: sw,
     });
 
+    // Pinned headers replace the top rows, but never the cursor's row.
+    let sticky_count = render_output.cursor.map_or(sticky_headers.len(), |(_, y)| {
+        sticky_headers.len().min(y as usize)
+    });
+    let sticky_headers = &sticky_headers[..sticky_count];
+    let sticky_lines = render_sticky_lines(
+        state,
+        theme,
+        &decorations,
+        sticky_headers,
+        render_area.width as usize,
+        viewport.left_column,
+        estimated_lines,
+        show_line_numbers,
+        byte_offset_mode,
+        effective_editor_bg,
+    );
+    for (row, line) in render_output.lines.iter_mut().zip(sticky_lines) {
+        *row = line;
+    }
+    viewport.sticky_lines = sticky_headers.to_vec();
+
     let view_line_mappings = render_output.view_line_mappings.clone();
 
     let buffer_ends_with_newline = if !state.buffer.is_empty() {
@@ -598,6 +633,7 @@ pub(crate) fn render_buffer_in_split(
     diagnostics_inline_text: bool,
     show_tilde: bool,
     highlight_current_column: bool,
+    sticky_scroll: bool,
     cell_theme_map: &mut Vec<CellThemeInfo>,
     screen_width: u16,
     pending_hardware_cursor: &mut Option<(u16, u16)>,
@@ -624,6 +660,7 @@ pub(crate) fn render_buffer_in_split(
         highlight_current_line,
         diagnostics_inline_text,
         show_tilde,
+        sticky_scroll,
         Some((cell_theme_map, screen_width)),
     );
 
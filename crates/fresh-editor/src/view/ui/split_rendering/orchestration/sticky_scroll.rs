@@ -0,0 +1,198 @@
+//! Sticky scroll rows for a buffer split.
+//!
+//! [`sticky_headers_for_view`] picks the scope headers to pin from the rows
+//! about to be drawn, before the decoration context is built so syntax
+//! highlighting can be widened to cover them. Once the content is rendered,
+//! [`render_sticky_lines`] draws those headers (gutter included) to replace
+//! the topmost rows.
+
+use super::super::gutter::{render_left_margin, LeftMarginContext};
+use super::super::spans::{compress_chars, span_color_at};
+use super::contexts::DecorationContext;
+use crate::model::buffer::Buffer;
+use crate::primitives::display_width::char_width;
+use crate::state::EditorState;
+use crate::view::folding::indent_folding::find_line_start_byte;
+use crate::view::sticky_scroll::{sticky_headers, ScopeSource, MAX_STICKY_LINES};
+use crate::view::theme::Theme;
+use crate::view::ui::view_pipeline::ViewLine;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+
+/// Headers further than this above the viewport are drawn without syntax
+/// colors instead of stretching the highlight pass all the way up to them.
+pub(super) const STICKY_HIGHLIGHT_REACH: usize = 256 * 1024;
+
+/// Bytes of a header line read from the buffer; the rest is off-screen.
+const MAX_HEADER_BYTES: usize = 1024;
+
+/// Scope headers to pin over `view_lines`, outermost first, as line-start
+/// bytes. Short splits get fewer rows so the content keeps most of them.
+pub(super) fn sticky_headers_for_view(
+    state: &EditorState,
+    view_lines: &[ViewLine],
+    visible_count: usize,
+) -> Vec<usize> {
+    let max_lines = MAX_STICKY_LINES.min(visible_count / 4);
+    let rows = visual_rows(&state.buffer, view_lines, max_lines);
+
+    let ranges = state.folding_ranges.scope_ranges(&state.marker_list);
+    let source = if ranges.is_empty() {
+        ScopeSource::Indent {
+            tab_size: state.buffer_settings.tab_size,
+        }
+    } else {
+        ScopeSource::Ranges(&ranges)
+    };
+    sticky_headers(&state.buffer, source, &rows, max_lines)
+}
+
+/// Line-start byte of the source line on each of the first `count` rows.
+///
+/// Wrapped continuation rows repeat their line; virtual rows (code lenses,
+/// plugin lines) belong to the source line below them.
+fn visual_rows(buffer: &Buffer, view_lines: &[ViewLine], count: usize) -> Vec<usize> {
+    let source_byte = |line: &ViewLine| {
+        line.source_start_byte
+            .or_else(|| line.char_source_bytes.iter().find_map(|b| *b))
+    };
+
+    let mut rows: Vec<usize> = Vec::with_capacity(count);
+    for (i, line) in view_lines.iter().enumerate().take(count) {
+        let continued = if line.line_start.is_continuation() {
+            rows.last().copied()
+        } else {
+            None
+        };
+        let row = continued.or_else(|| {
+            view_lines[i..]
+                .iter()
+                .find_map(source_byte)
+                .map(|byte| find_line_start_byte(buffer, byte))
+        });
+        let Some(row) = row else {
+            break;
+        };
+        rows.push(row);
+    }
+    rows
+}
+
+/// Draw the pinned header rows. The last one is underlined to separate the
+/// headers from the scrolling content below.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_sticky_lines(
+    state: &EditorState,
+    theme: &Theme,
+    decorations: &DecorationContext,
+    headers: &[usize],
+    width: usize,
+    left_column: usize,
+    estimated_lines: usize,
+    show_line_numbers: bool,
+    byte_offset_mode: bool,
+    bg: Color,
+) -> Vec<Line<'static>> {
+    let tab_size = state.buffer_settings.tab_size.max(1);
+    // Headers are ascending, so one cursor walks the spans for all of them.
+    let mut hl_cursor = 0usize;
+
+    headers
+        .iter()
+        .enumerate()
+        .map(|(i, &header)| {
+            let mut spans = Vec::new();
+            let mut view_map = Vec::new();
+            render_left_margin(
+                &LeftMarginContext {
+                    state,
+                    theme,
+                    is_continuation: false,
+                    line_start_byte: Some(header),
+                    gutter_num: if byte_offset_mode {
+                        header
+                    } else {
+                        state.buffer.get_line_number(header)
+                    },
+                    estimated_lines,
+                    diagnostic_lines: &decorations.diagnostic_lines,
+                    line_indicators: &decorations.line_indicators,
+                    fold_indicators: &decorations.fold_indicators,
+                    cursor_line_start_byte: usize::MAX,
+                    cursor_line_number: state.primary_cursor_line_number.value(),
+                    relative_line_numbers: false,
+                    show_line_numbers,
+                    byte_offset_mode,
+                    highlight_current_line: false,
+                    is_active: false,
+                    virtual_gutter_glyph: None,
+                },
+                &mut spans,
+                &mut view_map,
+            );
+            let content_width = width.saturating_sub(view_map.len());
+
+            let mut modifier = Modifier::empty();
+            if i + 1 == headers.len() {
+                modifier.insert(Modifier::UNDERLINED);
+            }
+            let pad_style = Style::default().bg(bg).add_modifier(modifier);
+
+            let end = state
+                .buffer
+                .len()
+                .min(header.saturating_add(MAX_HEADER_BYTES));
+            let bytes = state.buffer.slice_bytes(header..end);
+            let bytes = bytes.split(|&b| b == b'\n').next().unwrap_or_default();
+            let text = match std::str::from_utf8(bytes) {
+                Ok(text) => text,
+                // Cut mid-character by MAX_HEADER_BYTES (or not UTF-8 at all).
+                Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+            };
+
+            let mut cells = Vec::with_capacity(content_width);
+            let mut used = 0usize;
+            let mut col = 0usize;
+            for (offset, ch) in text.char_indices() {
+                if used >= content_width {
+                    break;
+                }
+                let fg = span_color_at(
+                    &decorations.highlight_spans,
+                    &mut hl_cursor,
+                    header + offset,
+                )
+                .unwrap_or(theme.editor_fg);
+                let style = pad_style.fg(fg);
+                if ch == '\t' {
+                    let cw = tab_size - col % tab_size;
+                    for c in col..col + cw {
+                        if c >= left_column && used < content_width {
+                            cells.push((' ', style));
+                            used += 1;
+                        }
+                    }
+                    col += cw;
+                    continue;
+                }
+                let (shown, cw) = if ch.is_control() {
+                    (' ', 1)
+                } else {
+                    (ch, char_width(ch))
+                };
+                if col >= left_column && used + cw <= content_width {
+                    cells.push((shown, style));
+                    used += cw;
+                }
+                col += cw;
+            }
+            cells.extend(std::iter::repeat_n(
+                (' ', pad_style.fg(theme.editor_fg)),
+                content_width.saturating_sub(used),
+            ));
+
+            spans.extend(compress_chars(cells));
+            Line::from(spans)
+        })
+        .collect()
+}
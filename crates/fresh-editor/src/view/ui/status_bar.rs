@@ -1095,11 +1095,20 @@ impl StatusBarRenderer {
                 kind: ElementKind::LineEnding,
                 token_key: None,
             }),
-            StatusBarElement::Encoding => Some(RenderedElement {
-                text: ctx.state.buffer.encoding().display_name().to_string(),
-                kind: ElementKind::Encoding,
-                token_key: None,
-            }),
+            StatusBarElement::Encoding => {
+                let encoding = ctx.state.buffer.encoding().display_name();
+                // Compressed files show their format next to the encoding,
+                // e.g. "UTF-8 [gzip]"
+                let text = match ctx.state.buffer.compression() {
+                    Some(compression) => format!("{} [{}]", encoding, compression.display_name()),
+                    None => encoding.to_string(),
+                };
+                Some(RenderedElement {
+                    text,
+                    kind: ElementKind::Encoding,
+                    token_key: None,
+                })
+            }
             StatusBarElement::Language => {
                 let text = if ctx.state.language == "text"
                     && ctx.state.display_name != "Text"
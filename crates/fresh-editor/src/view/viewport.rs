@@ -64,6 +64,11 @@ pub struct Viewport {
     /// `SplitViewState::show_line_numbers`.
     pub show_line_numbers: bool,
 
+    /// Line-start bytes of the scope headers pinned over the top rows at the
+    /// last render (sticky scroll), outermost first. Row `i` of the content
+    /// area shows `sticky_lines[i]`; used to route clicks on those rows.
+    pub sticky_lines: Vec<usize>,
+
     /// Whether viewport needs synchronization with cursor positions
     /// When true, ensure_visible needs to be called before rendering
     /// This allows batching multiple cursor movements into a single viewport update
@@ -135,6 +140,7 @@ impl Viewport {
             wrap_column: None,
             compose_width: None,
             show_line_numbers: true,
+            sticky_lines: Vec::new(),
             needs_sync: false,
             skip_resize_sync: false,
             skip_ensure_visible: false,
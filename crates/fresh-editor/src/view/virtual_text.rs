@@ -241,6 +241,27 @@ impl VirtualTextManager {
         id
     }
 
+    /// Add an inline virtual text entry in `namespace`, for bulk removal
+    /// with [`Self::clear_namespace`]. Namespaced entries survive
+    /// [`Self::clear_inline`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_in_namespace(
+        &mut self,
+        marker_list: &mut MarkerList,
+        position: usize,
+        text: String,
+        style: Style,
+        vtext_position: VirtualTextPosition,
+        priority: i32,
+        namespace: VirtualTextNamespace,
+    ) -> VirtualTextId {
+        let id = self.add(marker_list, position, text, style, vtext_position, priority);
+        if let Some(vtext) = self.texts.get_mut(&id) {
+            vtext.namespace = Some(namespace);
+        }
+        id
+    }
+
     /// Add an inline virtual text entry whose foreground/background colours
     /// are stored as theme keys (resolved at render time so theme changes
     /// apply live).
@@ -551,6 +572,25 @@ impl VirtualTextManager {
         }
     }
 
+    /// Clear the inline entries (`BeforeChar`/`AfterChar`) outside any
+    /// namespace, keeping virtual lines and namespaced entries. Used to
+    /// replace inlay hints without wiping code lenses, color swatches or
+    /// plugin-owned lines.
+    pub fn clear_inline(&mut self, marker_list: &mut MarkerList) {
+        let before = self.texts.len();
+        self.texts.retain(|_, vtext| {
+            if vtext.position.is_inline() && vtext.namespace.is_none() {
+                marker_list.delete(vtext.marker_id);
+                false
+            } else {
+                true
+            }
+        });
+        if self.texts.len() != before {
+            self.bump_version();
+        }
+    }
+
     /// Remove all virtual text entries whose marker position lies within the
     /// half-open byte range `[start, end)`.
     ///
@@ -807,6 +847,37 @@ mod tests {
         Style::default().fg(Color::DarkGray)
     }
 
+    #[test]
+    fn test_clear_inline_keeps_lines() {
+        let mut marker_list = MarkerList::new();
+        let mut manager = VirtualTextManager::new();
+
+        manager.add(
+            &mut marker_list,
+            3,
+            ": i32".to_string(),
+            hint_style(),
+            VirtualTextPosition::AfterChar,
+            0,
+        );
+        manager.add_line(
+            &mut marker_list,
+            0,
+            "Run | Debug".to_string(),
+            hint_style(),
+            VirtualTextPosition::LineAbove,
+            VirtualTextNamespace::from_string("lens".to_string()),
+            0,
+        );
+
+        manager.clear_inline(&mut marker_list);
+        assert_eq!(manager.len(), 1);
+        assert!(manager
+            .query_inline_in_range(&marker_list, 0, 10)
+            .is_empty());
+        assert_eq!(manager.query_lines_in_range(&marker_list, 0, 10).len(), 1);
+    }
+
     #[test]
     fn test_new_manager() {
         let manager = VirtualTextManager::new();
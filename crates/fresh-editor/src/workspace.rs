@@ -24,7 +24,7 @@
 //! This ensures the workspace file is never left in a corrupted state.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -74,6 +74,15 @@ pub struct Workspace {
     #[serde(default)]
     pub bookmarks: HashMap<char, SerializedBookmark>,
 
+    /// Clipboard history, newest first, as the cursor chunks of each entry.
+    /// Only written when `clipboard.persist_history` is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clipboard_history: Vec<Vec<String>>,
+
+    /// Named macros saved to this workspace, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub macros: BTreeMap<String, Vec<crate::input::keybindings::Action>>,
+
     /// Open terminal workspaces (for restoration)
     #[serde(default)]
     pub terminals: Vec<SerializedTerminalWorkspace>,
@@ -1016,6 +1025,8 @@ impl Workspace {
             histories: WorkspaceHistories::default(),
             search_options: SearchOptions::default(),
             bookmarks: HashMap::new(),
+            clipboard_history: Vec::new(),
+            macros: BTreeMap::new(),
             terminals: Vec::new(),
             external_files: Vec::new(),
             read_only_files: Vec::new(),
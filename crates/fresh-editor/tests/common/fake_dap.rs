@@ -0,0 +1,160 @@
+//! Fake Debug Adapter Protocol server for E2E testing
+//!
+//! A Bash script that speaks just enough DAP to drive a session: it stops at
+//! the first breakpoint it is given, reports a single-frame call stack in the
+//! breakpoint's file, advances one line per step and terminates on
+//! `continue`. Tests point `config.debuggers` at [`FakeDebugAdapter::script_path`].
+
+const SCRIPT: &str = r#"#!/bin/bash
+
+OUT_SEQ=0
+BP_PATH=""
+BP_LINE=""
+CUR_LINE=1
+
+read_message() {
+    local content_length=0
+    while IFS= read -r line; do
+        line="${line%$'\r'}"
+        if [ -z "$line" ]; then
+            break
+        fi
+        case "$line" in
+            Content-Length:*)
+                content_length="${line#Content-Length:}"
+                content_length="${content_length// /}"
+                ;;
+        esac
+    done
+
+    if [ "$content_length" -gt 0 ] 2>/dev/null; then
+        dd bs=1 count="$content_length" 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: %d\r\n\r\n%s" "$length" "$message"
+}
+
+respond() {
+    OUT_SEQ=$((OUT_SEQ + 1))
+    send_message '{"seq":'$OUT_SEQ',"type":"response","request_seq":'$seq',"success":true,"command":"'$command'","body":'"$1"'}'
+}
+
+fail() {
+    OUT_SEQ=$((OUT_SEQ + 1))
+    send_message '{"seq":'$OUT_SEQ',"type":"response","request_seq":'$seq',"success":false,"command":"'$command'","message":"'"$1"'"}'
+}
+
+event() {
+    OUT_SEQ=$((OUT_SEQ + 1))
+    send_message '{"seq":'$OUT_SEQ',"type":"event","event":"'$1'","body":'"$2"'}'
+}
+
+stopped() {
+    event stopped '{"reason":"'$1'","threadId":1,"allThreadsStopped":true}'
+}
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    command=$(echo "$msg" | grep -o '"command":"[^"]*"' | head -1 | cut -d'"' -f4)
+    seq=$(echo "$msg" | grep -o '"seq":[0-9]*' | head -1 | cut -d':' -f2)
+
+    case "$command" in
+        "initialize")
+            respond '{"supportsConfigurationDoneRequest":true}'
+            event initialized '{}'
+            ;;
+        "launch"|"attach")
+            respond '{}'
+            ;;
+        "setBreakpoints")
+            path=$(echo "$msg" | grep -o '"path":"[^"]*"' | head -1 | cut -d'"' -f4)
+            lines=$(echo "$msg" | grep -o '"line":[0-9]*' | cut -d':' -f2)
+            bps=""
+            for l in $lines; do
+                if [ -z "$BP_LINE" ]; then
+                    BP_PATH="$path"
+                    BP_LINE="$l"
+                fi
+                bps="$bps${bps:+,}{\"verified\":true,\"line\":$l}"
+            done
+            respond '{"breakpoints":['"$bps"']}'
+            ;;
+        "configurationDone")
+            respond '{}'
+            if [ -n "$BP_LINE" ]; then
+                CUR_LINE=$BP_LINE
+                stopped breakpoint
+            else
+                event terminated '{}'
+            fi
+            ;;
+        "threads")
+            respond '{"threads":[{"id":1,"name":"main"}]}'
+            ;;
+        "stackTrace")
+            respond '{"stackFrames":[{"id":1000,"name":"main","source":{"name":"main","path":"'"$BP_PATH"'"},"line":'$CUR_LINE',"column":1}],"totalFrames":1}'
+            ;;
+        "scopes")
+            respond '{"scopes":[{"name":"Locals","variablesReference":1,"expensive":false}]}'
+            ;;
+        "variables")
+            respond '{"variables":[{"name":"counter","value":"'$CUR_LINE'","type":"int","variablesReference":0}]}'
+            ;;
+        "next"|"stepIn"|"stepOut")
+            respond '{}'
+            CUR_LINE=$((CUR_LINE + 1))
+            stopped step
+            ;;
+        "pause")
+            respond '{}'
+            stopped pause
+            ;;
+        "continue")
+            respond '{"allThreadsContinued":true}'
+            event terminated '{}'
+            ;;
+        "disconnect"|"terminate")
+            respond '{}'
+            break
+            ;;
+        *)
+            fail "unsupported"
+            ;;
+    esac
+done
+"#;
+
+/// Writes the fake debug adapter script into a test directory.
+pub struct FakeDebugAdapter;
+
+impl FakeDebugAdapter {
+    /// Write the adapter script into `dir` and make it executable.
+    pub fn create(dir: &std::path::Path) -> anyhow::Result<std::path::PathBuf> {
+        let script_path = Self::script_path(dir);
+        std::fs::write(&script_path, SCRIPT)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(&script_path)?.permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&script_path, perms)?;
+        }
+
+        Ok(script_path)
+    }
+
+    /// Path of the adapter script inside `dir`.
+    pub fn script_path(dir: &std::path::Path) -> std::path::PathBuf {
+        dir.join("fake_debug_adapter.sh")
+    }
+}
@@ -101,6 +101,14 @@ pub fn test_temp_dir(test_name: &str) -> anyhow::Result<PathBuf> {
     Ok(path)
 }
 
+/// Byte offset of the start of `line` (1-based) in `text`.
+pub fn line_start(text: &str, line: usize) -> usize {
+    text.split_inclusive('\n')
+        .take(line - 1)
+        .map(str::len)
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
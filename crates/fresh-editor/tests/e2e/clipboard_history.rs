@@ -0,0 +1,141 @@
+//! E2E tests for the clipboard history
+//!
+//! - Multi-cursor copies paste back one chunk per cursor
+//! - Yank-pop (Emacs `M-y`) cycles the text just pasted
+//! - Paste from History picks an older entry
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::input::keybindings::Action;
+
+/// Create a harness with the Emacs keybinding map
+fn emacs_harness() -> EditorTestHarness {
+    let config = Config {
+        active_keybinding_map: "emacs".into(),
+        ..Default::default()
+    };
+    EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_preserved_keybinding_map(),
+    )
+    .unwrap()
+}
+
+/// Select the current line's text before the cursor and copy it.
+fn copy_line_before_cursor(harness: &mut EditorTestHarness) {
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::SelectLineStart);
+    harness.editor_mut().dispatch_action_for_tests(Action::Copy);
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::MoveLineEnd);
+}
+
+/// Copying with two cursors whose selections span lines keeps the two
+/// selections apart: pasting with two cursors gives each its own chunk
+/// rather than the whole text or a line per cursor.
+#[test]
+fn test_multi_cursor_copy_pastes_one_chunk_per_cursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    harness.type_text("k1\nv1\nk2\nv2\nend").unwrap();
+
+    // Select "k" at the start of the buffer, add a cursor at the next "k",
+    // then extend both selections down a line.
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+        .unwrap();
+    harness.editor_mut().add_cursor_at_next_match();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.editor().active_cursors().count(), 2);
+
+    harness.editor_mut().copy_selection();
+    assert_eq!(
+        harness.editor().clipboard_content_for_test(),
+        "k1\nv\nk2\nv"
+    );
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.editor_mut().paste_for_test();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("k1\nv1k1\nv\nk2\nv2k2\nv\nend");
+}
+
+/// In the Emacs keymap, `M-y` right after `C-y` replaces the pasted text
+/// with the previous kill, wrapping around at the end of the history.
+#[test]
+fn test_emacs_yank_pop_cycles_clipboard_history() {
+    let mut harness = emacs_harness();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+
+    harness.type_text("alpha").unwrap();
+    copy_line_before_cursor(&mut harness);
+    harness.type_text("\nbeta").unwrap();
+    copy_line_before_cursor(&mut harness);
+    harness.type_text("\n").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("alpha\nbeta\nbeta");
+
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::ALT)
+        .unwrap();
+    harness.assert_buffer_content("alpha\nbeta\nalpha");
+
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::ALT)
+        .unwrap();
+    harness.assert_buffer_content("alpha\nbeta\nbeta");
+
+    // Once the cursor moved, M-y no longer rewrites the pasted text; it
+    // opens the history picker instead.
+    harness
+        .send_key(KeyCode::Char('b'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::ALT)
+        .unwrap();
+    harness.assert_buffer_content("alpha\nbeta\nbeta");
+    assert!(harness.editor().is_prompting());
+}
+
+/// Paste from History lists the copied entries newest first and pastes the
+/// one picked by typing part of it.
+#[test]
+fn test_paste_from_history_picker() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+
+    harness.type_text("first entry").unwrap();
+    copy_line_before_cursor(&mut harness);
+    harness.type_text("\nsecond entry").unwrap();
+    copy_line_before_cursor(&mut harness);
+    harness.type_text("\n").unwrap();
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::PasteFromHistory);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Paste from history:");
+    harness.assert_screen_contains("second entry");
+
+    harness.type_text("first").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness.assert_buffer_content("first entry\nsecond entry\nfirst entry");
+}
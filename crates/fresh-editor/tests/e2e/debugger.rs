@@ -0,0 +1,129 @@
+//! E2E tests for the Debug Adapter Protocol client.
+//!
+//! Drives a full session against `FakeDebugAdapter`: a breakpoint toggled in
+//! the gutter is sent to the adapter, the adapter stops there, the editor
+//! reveals the frame, steps advance it and `continue` ends the session.
+
+use crate::common::fake_dap::FakeDebugAdapter;
+use crate::common::fixtures::line_start;
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const SOURCE: &str = "fn main() {\n    let a = 1;\n    let b = 2;\n    let c = a + b;\n}\n";
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn harness_with_fake_adapter(temp_dir: &std::path::Path) -> anyhow::Result<EditorTestHarness> {
+    let adapter = FakeDebugAdapter::create(temp_dir)?;
+    let mut config = fresh::config::Config::default();
+    config.debuggers.insert(
+        "rust".to_string(),
+        fresh::types::DebuggerConfig {
+            command: adapter.to_string_lossy().to_string(),
+            configuration: Some(serde_json::json!({ "program": "${fileDirname}/main" })),
+            ..Default::default()
+        },
+    );
+    EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.to_path_buf()),
+    )
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "FakeDebugAdapter uses Bash")]
+fn test_breakpoint_stop_step_and_continue() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let file = temp_dir.path().join("main.rs");
+    std::fs::write(&file, SOURCE)?;
+
+    let mut harness = harness_with_fake_adapter(temp_dir.path())?;
+    harness.open_file(&file)?;
+    harness.render()?;
+
+    // Breakpoint on `let a = 1;` (line 2).
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.render()?;
+    harness.assert_screen_contains("●");
+    harness.assert_screen_contains("Breakpoint set at line 2");
+
+    // Move away so revealing the frame visibly moves the cursor.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.render()?;
+
+    run_command(&mut harness, "Debug: Start");
+    harness.wait_until(|h| {
+        h.screen_to_string()
+            .contains("Stopped (breakpoint) in main")
+    })?;
+    harness.assert_screen_contains("▶");
+    assert_eq!(harness.cursor_position(), line_start(SOURCE, 2));
+
+    run_command(&mut harness, "Debug: Step Over");
+    harness.wait_until(|h| h.screen_to_string().contains("Stopped (step) in main"))?;
+    assert_eq!(harness.cursor_position(), line_start(SOURCE, 3));
+
+    run_command(&mut harness, "Debug: Continue");
+    harness.wait_until(|h| h.screen_to_string().contains("Debug session ended"))?;
+    harness.assert_screen_not_contains("▶");
+    // The breakpoint outlives the session.
+    harness.assert_screen_contains("●");
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "FakeDebugAdapter uses Bash")]
+fn test_toggle_breakpoint_twice_removes_it() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let file = temp_dir.path().join("main.rs");
+    std::fs::write(&file, SOURCE)?;
+
+    let mut harness = harness_with_fake_adapter(temp_dir.path())?;
+    harness.open_file(&file)?;
+    harness.render()?;
+
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.render()?;
+    harness.assert_screen_contains("●");
+
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.render()?;
+    harness.assert_screen_not_contains("●");
+    harness.assert_screen_contains("Breakpoint removed from line 1");
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "FakeDebugAdapter uses Bash")]
+fn test_start_without_debugger_config_reports_it() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "hello\n")?;
+
+    let mut harness = harness_with_fake_adapter(temp_dir.path())?;
+    harness.open_file(&file)?;
+    harness.render()?;
+
+    run_command(&mut harness, "Debug: Start");
+    harness.assert_screen_contains("No debugger configured");
+
+    Ok(())
+}
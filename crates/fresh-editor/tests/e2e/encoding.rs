@@ -2680,3 +2680,40 @@ fn test_utf8_cjk_file_larger_than_8kb_displays_correctly() {
         );
     }
 }
+
+/// Rotated, compressed logs open as plain text with the compression shown
+/// next to the encoding, and saving writes them back compressed.
+#[test]
+fn test_compressed_log_edited_transparently() {
+    use fresh::model::compression::Compression;
+    use std::io::Read;
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("app.log.1.gz");
+    let compressed = Compression::Gzip
+        .compress(b"first entry\nsecond entry\n")
+        .unwrap();
+    std::fs::write(&file_path, compressed).unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("second entry");
+    let status = harness.get_status_bar();
+    assert!(status.contains("UTF-8 [gzip]"), "status bar: {}", status);
+
+    harness.type_text("new ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    let _ = harness.wait_until(|h| !h.editor().active_state().buffer.is_modified());
+
+    let mut saved = String::new();
+    Compression::Gzip
+        .decoder(std::fs::File::open(&file_path).unwrap())
+        .unwrap()
+        .read_to_string(&mut saved)
+        .unwrap();
+    assert_eq!(saved, "new first entry\nsecond entry\n");
+}
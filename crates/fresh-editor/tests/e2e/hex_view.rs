@@ -0,0 +1,130 @@
+// End-to-end tests for the hex view: rendering, nibble editing, offset
+// jumps and byte-pattern search
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::input::keybindings::Action;
+
+const ELF_HEADER: &[u8] = &[
+    0x7f, b'E', b'L', b'F', 0x02, 0x01, 0x01, 0x00, 0xde, 0xad, 0xbe, 0xef,
+];
+
+fn open_in_hex_view(bytes: &[u8]) -> (EditorTestHarness, std::path::PathBuf) {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let path = harness.project_dir().unwrap().join("blob.bin");
+    std::fs::write(&path, bytes).unwrap();
+    harness.open_file(&path).unwrap();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleHexView);
+    harness.render().unwrap();
+    (harness, path)
+}
+
+fn cursor_offset(harness: &EditorTestHarness) -> usize {
+    harness.editor().active_cursors().primary().position
+}
+
+/// The hex view shows offsets, hex pairs and the ASCII column.
+#[test]
+fn test_hex_view_renders_offset_hex_and_ascii() {
+    let (harness, _path) = open_in_hex_view(ELF_HEADER);
+
+    harness.assert_screen_contains("00000000  7f 45 4c 46 02 01 01 00  de ad be ef");
+    harness.assert_screen_contains("|.ELF........|");
+}
+
+/// Typing hex digits overwrites one nibble at a time; undo restores the
+/// byte and saving writes the raw bytes back.
+#[test]
+fn test_hex_view_nibble_edit_undo_and_save() {
+    let (mut harness, path) = open_in_hex_view(ELF_HEADER);
+
+    // Binary files open read-only; typing is refused until that is lifted.
+    harness.type_text("a").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("7f 45 4c 46");
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleReadOnly);
+    harness.type_text("a").unwrap();
+    assert_eq!(cursor_offset(&harness), 0, "high nibble stays on the byte");
+    harness.type_text("b").unwrap();
+    assert_eq!(
+        cursor_offset(&harness),
+        1,
+        "low nibble moves to the next byte"
+    );
+    harness.render().unwrap();
+    harness.assert_screen_contains("00000000  ab 45 4c 46");
+
+    // Each nibble is its own undo step.
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("00000000  af 45 4c 46");
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("00000000  7f 45 4c 46");
+
+    harness
+        .send_key_repeat(KeyCode::Char('y'), KeyModifiers::CONTROL, 2)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    let mut expected = ELF_HEADER.to_vec();
+    expected[0] = 0xab;
+    assert_eq!(std::fs::read(&path).unwrap(), expected);
+}
+
+/// Go to Line becomes Go to Offset, and Find searches for byte patterns.
+#[test]
+fn test_hex_view_goto_offset_and_byte_search() {
+    let (mut harness, _path) = open_in_hex_view(ELF_HEADER);
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::GotoLine);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Go to offset:");
+    harness.type_text("0x4").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(cursor_offset(&harness), 4);
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::Search);
+    harness.type_text("de ?? be").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Found at offset 0x8");
+    let selection = harness
+        .editor()
+        .active_cursors()
+        .primary()
+        .selection_range();
+    assert_eq!(selection, Some(8..11));
+
+    // Find Next wraps around to the same single match.
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::FindNext);
+    let selection = harness
+        .editor()
+        .active_cursors()
+        .primary()
+        .selection_range();
+    assert_eq!(selection, Some(8..11));
+
+    harness.editor_mut().dispatch_action_for_tests(Action::Copy);
+    assert_eq!(harness.editor().clipboard_content_for_test(), "de ad be");
+}
@@ -0,0 +1,213 @@
+//! E2E tests for LSP code lenses: textDocument/codeLens, codeLens/resolve
+//! and running a lens through workspace/executeCommand.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Fake server reporting two lenses on the `fn helper` line (line 4): one
+/// with a command, one that only gets its command from `codeLens/resolve`.
+/// Every received method (and executeCommand bodies) is logged.
+const FAKE_LSP_SCRIPT: &str = r##"#!/bin/bash
+
+LOG_FILE="$1"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+RANGE='{"start":{"line":4,"character":3},"end":{"line":4,"character":9}}'
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+    if [ "$method" = "workspace/executeCommand" ]; then
+        echo "BODY:$msg" >> "$LOG_FILE"
+    fi
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":1,"codeLensProvider":{"resolveProvider":true},"executeCommandProvider":{"commands":["test.run","test.references"]}}}}'
+            ;;
+        "textDocument/codeLens")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"range":'"$RANGE"',"command":{"title":"Run test","command":"test.run","arguments":["helper"]}},{"range":'"$RANGE"',"data":7}]}'
+            ;;
+        "codeLens/resolve")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"range":'"$RANGE"',"command":{"title":"2 references","command":"test.references","arguments":[]},"data":7}}'
+            ;;
+        "workspace/executeCommand")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+    esac
+done
+"##;
+
+const SOURCE: &str = "fn main() {\n    helper();\n}\n\nfn helper() {}\n";
+
+fn setup(
+    temp_dir: &tempfile::TempDir,
+    log_file: &std::path::Path,
+) -> anyhow::Result<EditorTestHarness> {
+    let script_path = temp_dir.path().join("fake_lsp_code_lens.sh");
+    std::fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, SOURCE)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: vec![log_file.to_string_lossy().to_string()],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.path().to_path_buf()),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.render()?;
+
+    // Both lenses are drawn once the second one has been resolved.
+    harness.wait_for_screen_contains("Run test | 2 references")?;
+
+    Ok(harness)
+}
+
+fn executed_commands(log_file: &std::path::Path) -> Vec<String> {
+    std::fs::read_to_string(log_file)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.strip_prefix("BODY:"))
+        .filter_map(|body| {
+            let value: serde_json::Value = serde_json::from_str(body).ok()?;
+            Some(value["params"]["command"].as_str()?.to_string())
+        })
+        .collect()
+}
+
+/// Lenses are drawn on a virtual line directly above their symbol, and the
+/// one that arrived without a command is filled in by `codeLens/resolve`.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_code_lenses_render_above_symbol() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_log.txt");
+    let harness = setup(&temp_dir, &log_file)?;
+
+    let (_, lens_row) = harness
+        .find_text_on_screen("Run test | 2 references")
+        .expect("lens row on screen");
+    assert!(
+        harness
+            .screen_row_text(lens_row + 1)
+            .contains("fn helper() {}"),
+        "lens row should sit directly above its symbol:\n{}",
+        harness.screen_to_string()
+    );
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert!(log.contains("METHOD:codeLens/resolve"));
+
+    Ok(())
+}
+
+/// Clicking a lens title sends its command through workspace/executeCommand.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_click_code_lens_executes_command() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_log.txt");
+    let mut harness = setup(&temp_dir, &log_file)?;
+
+    let (col, row) = harness
+        .find_text_on_screen("2 references")
+        .expect("lens title on screen");
+    harness.mouse_click(col + 1, row)?;
+
+    harness.wait_until(|_| executed_commands(&log_file) == ["test.references"])?;
+
+    Ok(())
+}
+
+/// With several lenses on the cursor's line, "Run Code Lens" offers them in
+/// a popup and runs the chosen one.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_run_code_lens_from_keyboard() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_log.txt");
+    let mut harness = setup(&temp_dir, &log_file)?;
+
+    // No lens above `fn main`.
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.type_text("Run Code Lens")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("No code lens on this line")?;
+
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    }
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.type_text("Run Code Lens")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("Code Lenses")?;
+    harness.assert_screen_contains("1. Run test");
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|_| executed_commands(&log_file) == ["test.run"])?;
+
+    Ok(())
+}
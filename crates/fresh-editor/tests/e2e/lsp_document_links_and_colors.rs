@@ -0,0 +1,217 @@
+//! E2E tests for `textDocument/documentLink` (following an import path to
+//! the file it names) and `textDocument/documentColor` with
+//! `textDocument/colorPresentation` (a swatch in front of a color literal,
+//! and rewriting the literal in another format as one undo step).
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use std::path::Path;
+
+/// Fake server with document links and colors for `main.css`, which holds
+/// `@import "other.css";` on line 0 and `a { color: #ff0000; }` on line 1.
+/// The import path links to the file given as the second argument, at
+/// line 2; the hex code is red and can also be written `rgb(255, 0, 0)`.
+/// Every received method is logged.
+const FAKE_LSP_SCRIPT: &str = r##"#!/bin/bash
+
+LOG_FILE="$1"
+TARGET="$2"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+IMPORT='{"start":{"line":0,"character":9},"end":{"line":0,"character":18}}'
+HEX='{"start":{"line":1,"character":11},"end":{"line":1,"character":18}}'
+RED='{"red":1,"green":0,"blue":0,"alpha":1}'
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":2,"documentLinkProvider":{"resolveProvider":false},"colorProvider":true}}}'
+            ;;
+        "textDocument/documentLink")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"range":'"$IMPORT"',"target":"file://'"$TARGET"'#L2"}]}'
+            ;;
+        "textDocument/documentColor")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"range":'"$HEX"',"color":'"$RED"'}]}'
+            ;;
+        "textDocument/colorPresentation")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"label":"#ff0000"},{"label":"rgb(255, 0, 0)"}]}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+    esac
+done
+"##;
+
+const MAIN_CSS: &str = "@import \"other.css\";\na { color: #ff0000; }\n";
+const OTHER_CSS: &str = "b {}\ni {}\n";
+
+/// Open `main.css` with the fake server attached for CSS, and wait until
+/// its links and colors have arrived.
+fn open_with_fake_server(dir: &Path) -> anyhow::Result<EditorTestHarness> {
+    let log_file = dir.join("lsp_log.txt");
+    let script_path = dir.join("fake_lsp_document_links.sh");
+    std::fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let main = dir.join("main.css");
+    let other = dir.join("other.css");
+    std::fs::write(&main, MAIN_CSS)?;
+    std::fs::write(&other, OTHER_CSS)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "css".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: vec![
+                log_file.to_string_lossy().to_string(),
+                other.to_string_lossy().to_string(),
+            ],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(dir.to_path_buf()),
+    )?;
+    harness.open_file(&main)?;
+    harness.render()?;
+    harness.wait_until(|_| {
+        let log = std::fs::read_to_string(&log_file).unwrap_or_default();
+        log.contains("METHOD:textDocument/documentLink")
+            && log.contains("METHOD:textDocument/documentColor")
+    })?;
+    harness.wait_for_screen_contains("■#ff0000")?;
+    Ok(harness)
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)
+}
+
+/// Ctrl+clicking an import path opens the file it names, at the line in
+/// the link's `#L` fragment.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_ctrl_click_follows_document_link() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let mut harness = open_with_fake_server(temp_dir.path())?;
+
+    let (col, row) = harness
+        .find_text_on_screen("other.css")
+        .expect("import path on screen");
+    for kind in [
+        MouseEventKind::Down(MouseButton::Left),
+        MouseEventKind::Up(MouseButton::Left),
+    ] {
+        harness.send_mouse(MouseEvent {
+            kind,
+            column: col + 2,
+            row,
+            modifiers: KeyModifiers::CONTROL,
+        })?;
+    }
+    harness.render()?;
+
+    harness.wait_for_buffer_content(OTHER_CSS)?;
+    assert_eq!(harness.cursor_position(), OTHER_CSS.find("i {}").unwrap());
+
+    Ok(())
+}
+
+/// "Open Document Link" with the cursor off any link says so.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_open_document_link_without_link_at_cursor() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let mut harness = open_with_fake_server(temp_dir.path())?;
+
+    run_command(&mut harness, "Open Document Link")?;
+    harness.wait_for_screen_contains("No link at cursor")?;
+    assert_eq!(harness.get_buffer_content().unwrap(), MAIN_CSS);
+
+    Ok(())
+}
+
+/// Picking a presentation rewrites the literal under the cursor, and one
+/// undo brings the original back.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_color_presentation_rewrites_literal() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_log.txt");
+    let mut harness = open_with_fake_server(temp_dir.path())?;
+
+    // Cursor inside `#ff0000`.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    for _ in 0..13 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    run_command(&mut harness, "Pick Color Presentation")?;
+    harness.wait_for_screen_contains("Color Presentation")?;
+    assert!(std::fs::read_to_string(&log_file)?.contains("METHOD:textDocument/colorPresentation"));
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_buffer_content("@import \"other.css\";\na { color: rgb(255, 0, 0); }\n")?;
+
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    assert_eq!(harness.get_buffer_content().unwrap(), MAIN_CSS);
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+//! E2E tests for `workspace/willRenameFiles` and `workspace/didRenameFiles`:
+//! renaming a module from the file explorer offers the server's import
+//! updates for confirmation before the file moves, and the server hears
+//! about the rename afterwards.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::{Path, PathBuf};
+
+/// Fake server interested in renames of `.rs` files. Asked about one, it
+/// answers with an edit rewriting `mod a;` on line 0 of the file given as
+/// the second argument to `mod b;`. Every received method is logged.
+const FAKE_LSP_SCRIPT: &str = r##"#!/bin/bash
+
+LOG_FILE="$1"
+LIB="$2"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+FILTERS='{"filters":[{"scheme":"file","pattern":{"glob":"**/*.rs","matches":"file"}}]}'
+MOD_NAME='{"start":{"line":0,"character":4},"end":{"line":0,"character":5}}'
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":2,"workspace":{"fileOperations":{"willRename":'"$FILTERS"',"didRename":'"$FILTERS"'}}}}}'
+            ;;
+        "workspace/willRenameFiles")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"changes":{"file://'"$LIB"'":[{"range":'"$MOD_NAME"',"newText":"b"}]}}}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+    esac
+done
+"##;
+
+const LIB_RS: &str = "mod a;\n";
+
+/// Open `lib.rs` of a project holding it and `a.rs`, with the fake server
+/// attached for Rust. Returns the harness, the project dir and the log.
+fn open_with_fake_server(dir: &Path) -> anyhow::Result<(EditorTestHarness, PathBuf, PathBuf)> {
+    let log_file = dir.join("lsp_log.txt");
+    let script_path = dir.join("fake_lsp_file_operations.sh");
+    std::fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let project = dir.join("project");
+    std::fs::create_dir(&project)?;
+    let lib = project.join("lib.rs");
+    std::fs::write(&lib, LIB_RS)?;
+    std::fs::write(project.join("a.rs"), "pub fn a() {}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: vec![
+                log_file.to_string_lossy().to_string(),
+                lib.to_string_lossy().to_string(),
+            ],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness = EditorTestHarness::create(
+        100,
+        30,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(project.clone()),
+    )?;
+    harness.open_file(&lib)?;
+    harness.render()?;
+    harness.wait_until(|_| {
+        std::fs::read_to_string(&log_file)
+            .unwrap_or_default()
+            .contains("METHOD:textDocument/didOpen")
+    })?;
+    Ok((harness, project, log_file))
+}
+
+/// Rename `a.rs` to `b.rs` from the explorer, up to the server's answer.
+fn rename_a_to_b(harness: &mut EditorTestHarness, project: &Path) -> anyhow::Result<()> {
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer()?;
+    harness.wait_for_file_explorer_item("a.rs")?;
+    harness
+        .editor_mut()
+        .file_explorer_mut()
+        .unwrap()
+        .navigate_to_path(&project.join("a.rs"));
+    harness.editor_mut().file_explorer_rename();
+    harness.wait_for_prompt()?;
+    harness.send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)?;
+    harness.type_text("b.rs")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("Language servers suggest")
+}
+
+/// Accepting the server's edits updates the import, then moves the file
+/// and reports the rename.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_explorer_rename_applies_server_edits() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, project, log_file) = open_with_fake_server(temp_dir.path())?;
+
+    rename_a_to_b(&mut harness, &project)?;
+    assert!(
+        project.join("a.rs").exists(),
+        "file must wait for the answer"
+    );
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_prompt_closed()?;
+    harness.wait_for_buffer_content("mod b;\n")?;
+    assert!(project.join("b.rs").exists());
+    assert!(!project.join("a.rs").exists());
+    harness.wait_until(|_| {
+        std::fs::read_to_string(&log_file)
+            .unwrap_or_default()
+            .contains("METHOD:workspace/didRenameFiles")
+    })?;
+
+    Ok(())
+}
+
+/// Declining the edits still renames the file, leaving other files alone.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_explorer_rename_without_server_edits() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, project, _log_file) = open_with_fake_server(temp_dir.path())?;
+
+    rename_a_to_b(&mut harness, &project)?;
+    harness.type_text("n")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_prompt_closed()?;
+
+    assert!(project.join("b.rs").exists());
+    assert!(!project.join("a.rs").exists());
+    assert_eq!(harness.get_buffer_content().unwrap(), LIB_RS);
+
+    Ok(())
+}
+
+/// Escape on the confirmation leaves the file where it was.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_explorer_rename_cancelled_at_confirmation() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, project, _log_file) = open_with_fake_server(temp_dir.path())?;
+
+    rename_a_to_b(&mut harness, &project)?;
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("File operation cancelled")?;
+
+    assert!(project.join("a.rs").exists());
+    assert!(!project.join("b.rs").exists());
+    assert_eq!(harness.get_buffer_content().unwrap(), LIB_RS);
+
+    Ok(())
+}
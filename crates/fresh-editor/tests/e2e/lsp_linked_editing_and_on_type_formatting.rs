@@ -0,0 +1,188 @@
+//! E2E tests for `textDocument/linkedEditingRange` (renaming an HTML tag
+//! mirrors into its closing tag) and `textDocument/onTypeFormatting`
+//! (the server reformats after a trigger character), both of which undo
+//! in one step.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::Path;
+
+/// Fake server with linked editing and on-type formatting. Linked ranges
+/// are always the two tag names of `<div>x</div>`; on-type formatting
+/// always rewrites the first line to `a = 1;`. Every received method is
+/// logged.
+const FAKE_LSP_SCRIPT: &str = r##"#!/bin/bash
+
+LOG_FILE="$1"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+OPEN_TAG='{"start":{"line":0,"character":1},"end":{"line":0,"character":4}}'
+CLOSE_TAG='{"start":{"line":0,"character":8},"end":{"line":0,"character":11}}'
+LINE='{"start":{"line":0,"character":0},"end":{"line":0,"character":8}}'
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":2,"linkedEditingRangeProvider":true,"documentOnTypeFormattingProvider":{"firstTriggerCharacter":";"}}}}'
+            ;;
+        "textDocument/linkedEditingRange")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"ranges":['"$OPEN_TAG"','"$CLOSE_TAG"']}}'
+            ;;
+        "textDocument/onTypeFormatting")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"range":'"$LINE"',"newText":"a = 1;"}]}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+    esac
+done
+"##;
+
+/// Open `file_name` holding `content` with the fake server attached for
+/// HTML and Go, and wait until the server has seen the document.
+fn open_with_fake_server(
+    dir: &Path,
+    file_name: &str,
+    content: &str,
+) -> anyhow::Result<(EditorTestHarness, std::path::PathBuf)> {
+    let log_file = dir.join("lsp_log.txt");
+    let script_path = dir.join("fake_lsp_type_assist.sh");
+    std::fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let file = dir.join(file_name);
+    std::fs::write(&file, content)?;
+
+    let mut config = fresh::config::Config::default();
+    for language in ["html", "go"] {
+        config.lsp.insert(
+            language.to_string(),
+            fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+                command: script_path.to_string_lossy().to_string(),
+                args: vec![log_file.to_string_lossy().to_string()],
+                enabled: true,
+                auto_start: true,
+                process_limits: fresh::services::process_limits::ProcessLimits::default(),
+                initialization_options: None,
+                env: Default::default(),
+                language_id_overrides: Default::default(),
+                root_markers: Default::default(),
+                name: None,
+                only_features: None,
+                except_features: None,
+            }]),
+        );
+    }
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(dir.to_path_buf()),
+    )?;
+    harness.open_file(&file)?;
+    harness.render()?;
+    harness.wait_until(|_| {
+        std::fs::read_to_string(&log_file)
+            .unwrap_or_default()
+            .contains("METHOD:textDocument/didOpen")
+    })?;
+    Ok((harness, log_file))
+}
+
+fn undo(harness: &mut EditorTestHarness) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+}
+
+/// Retyping the opening tag name renames the closing tag along with it,
+/// each keystroke undoes in both places at once, and a space (starting an
+/// attribute) is not mirrored.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_linked_editing_renames_closing_tag() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, _log) =
+        open_with_fake_server(temp_dir.path(), "index.html", "<div>x</div>\n")?;
+
+    // Cursor right after the opening `div`.
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    harness.wait_until(|h| !h.editor().linked_editing_ranges_for_tests().is_empty())?;
+
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Backspace, KeyModifiers::NONE)?;
+    }
+    assert_eq!(harness.get_buffer_content().unwrap(), "<>x</>\n");
+    harness.type_text("span")?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "<span>x</span>\n");
+
+    harness.type_text(" ")?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "<span >x</span>\n");
+
+    undo(&mut harness)?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "<span>x</span>\n");
+    undo(&mut harness)?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "<spa>x</spa>\n");
+
+    Ok(())
+}
+
+/// Typing the server's trigger character applies its edits, and one undo
+/// reverts all of them while keeping the typed character.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_on_type_formatting_applies_as_one_undo_step() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, log_file) = open_with_fake_server(temp_dir.path(), "main.go", "a  =  1\n")?;
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE)?;
+    harness.type_text(";")?;
+    harness.wait_for_buffer_content("a = 1;\n")?;
+    assert!(std::fs::read_to_string(&log_file)?.contains("METHOD:textDocument/onTypeFormatting"));
+
+    undo(&mut harness)?;
+    assert_eq!(harness.get_buffer_content().unwrap(), "a  =  1;\n");
+
+    Ok(())
+}
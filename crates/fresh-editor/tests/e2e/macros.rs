@@ -429,3 +429,74 @@ fn test_macro_playback_is_undoable() {
         abc_count, abc_count_after, screen_after_undo
     );
 }
+
+/// Record a macro on register `q` that inserts a dash at the cursor.
+fn record_dash_macro(harness: &mut EditorTestHarness) {
+    use fresh::input::keybindings::Action;
+    let editor = harness.editor_mut();
+    editor.dispatch_action_for_tests(Action::ToggleMacroRecording('q'));
+    editor.dispatch_action_for_tests(Action::InsertChar('-'));
+    editor.dispatch_action_for_tests(Action::ToggleMacroRecording('q'));
+}
+
+/// Apply Macro to Selected Lines replays the last macro at the start of
+/// every selected line, and the whole run undoes in one step.
+#[test]
+fn test_apply_macro_to_selected_lines() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use fresh::input::keybindings::Action;
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("a\nb\nc\nd").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    record_dash_macro(&mut harness);
+    harness.assert_buffer_content("-a\nb\nc\nd");
+
+    // Select from the middle of "b" into "c".
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ApplyMacroToLines);
+    harness.assert_buffer_content("-a\n-b\n-c\nd");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("-a\nb\nc\nd");
+}
+
+/// A macro saved to the workspace under a name can be replayed by name.
+#[test]
+fn test_save_and_play_named_macro() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use fresh::input::keybindings::Action;
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("x").unwrap();
+    record_dash_macro(&mut harness);
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::SaveMacroToWorkspace);
+    harness.render().unwrap();
+    harness.assert_screen_contains("Save macro to workspace as:");
+    harness.type_text("dash").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::PlayNamedMacro("dash".into()));
+    harness.assert_buffer_content("x--");
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::PlayNamedMacro("missing".into()));
+    harness.render().unwrap();
+    harness.assert_screen_contains("No saved macro named 'missing'");
+    harness.assert_buffer_content("x--");
+}
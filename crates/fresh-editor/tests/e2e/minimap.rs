@@ -0,0 +1,121 @@
+// End-to-end tests for the minimap: shape and marks rendering, click and
+// drag scrolling, and the marks-only fallback for large files
+
+use crate::common::harness::EditorTestHarness;
+use fresh::config::{Config, EditorConfig};
+use fresh::input::keybindings::Action;
+use fresh::view::minimap::MINIMAP_WIDTH;
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 24;
+
+/// Column of the minimap's marks; its shape follows, then the scrollbar.
+const MARKS_COL: u16 = WIDTH - 1 - MINIMAP_WIDTH;
+
+fn numbered_lines(count: usize) -> String {
+    (0..count)
+        .map(|i| format!("line {} with some text", i))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_braille(cell: Option<String>) -> bool {
+    cell.and_then(|s| s.chars().next())
+        .is_some_and(|c| ('\u{2800}'..='\u{28ff}').contains(&c))
+}
+
+fn minimap_rows(harness: &EditorTestHarness) -> (u16, u16) {
+    let (first, last) = harness.content_area_rows();
+    (first as u16, last as u16)
+}
+
+/// Toggling the minimap draws the text's shape in Braille beside the
+/// scrollbar, with the cursor marked on the first row.
+#[test]
+fn test_minimap_renders_shape_and_cursor_mark() {
+    let mut harness = EditorTestHarness::new(WIDTH, HEIGHT).unwrap();
+    let _fixture = harness.load_buffer_from_text(&numbered_lines(200)).unwrap();
+    harness.render().unwrap();
+    let (first, last) = minimap_rows(&harness);
+    assert!(!is_braille(harness.get_cell(MARKS_COL + 1, first)));
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::ToggleMinimap);
+    harness.render().unwrap();
+    assert!(harness.config().editor.show_minimap);
+
+    for row in first..=last {
+        assert!(
+            is_braille(harness.get_cell(MARKS_COL + 1, row)),
+            "row {} should show the document's shape: {:?}",
+            row,
+            harness.screen_row_text(row)
+        );
+    }
+    assert_eq!(harness.get_cell(MARKS_COL, first).as_deref(), Some("▐"));
+    assert_ne!(harness.get_cell(MARKS_COL, last).as_deref(), Some("▐"));
+}
+
+/// Clicking a minimap row scrolls there; dragging back to the top returns.
+#[test]
+fn test_minimap_click_and_drag_scroll() {
+    let mut config = Config::default();
+    config.editor.show_minimap = true;
+    let mut harness = EditorTestHarness::with_config(WIDTH, HEIGHT, config).unwrap();
+    let _fixture = harness.load_buffer_from_text(&numbered_lines(500)).unwrap();
+    harness.render().unwrap();
+    let (first, last) = minimap_rows(&harness);
+    assert_eq!(harness.top_byte(), 0);
+
+    harness.mouse_click(MARKS_COL + 3, last).unwrap();
+    harness.render().unwrap();
+    assert!(
+        harness.top_byte() > 0,
+        "clicking the bottom row scrolls down"
+    );
+    harness.assert_screen_contains("line 499 with some text");
+    // The cursor follows into view, so its mark leaves the first row.
+    assert_ne!(harness.get_cell(MARKS_COL, first).as_deref(), Some("▐"));
+
+    harness
+        .mouse_drag(MARKS_COL + 3, last, MARKS_COL + 3, first)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.top_byte(), 0, "dragging to the top scrolls back up");
+}
+
+/// Files above the large-file threshold have no line index, so the minimap
+/// shows marks only, but still scrolls.
+#[test]
+fn test_minimap_large_file_shows_marks_only() {
+    let mut harness = EditorTestHarness::with_temp_project_and_config(
+        WIDTH,
+        HEIGHT,
+        Config {
+            editor: EditorConfig {
+                large_file_threshold_bytes: 500,
+                show_minimap: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let path = harness.project_dir().unwrap().join("big.txt");
+    std::fs::write(&path, numbered_lines(2000)).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    let (first, last) = minimap_rows(&harness);
+
+    for row in first..=last {
+        for col in MARKS_COL + 1..WIDTH - 1 {
+            assert!(!is_braille(harness.get_cell(col, row)));
+        }
+    }
+    assert_eq!(harness.get_cell(MARKS_COL, first).as_deref(), Some("▐"));
+
+    harness.mouse_click(MARKS_COL + 3, last).unwrap();
+    harness.render().unwrap();
+    assert!(harness.top_byte() > 0);
+}
@@ -16,6 +16,7 @@ pub mod buffer_lifecycle;
 pub mod buffer_settings_commands;
 pub mod capslock_shortcuts;
 pub mod cargo_config_editing;
+pub mod clipboard_history;
 pub mod command_palette;
 pub mod config_language_selector;
 pub mod copy_buffer_path;
@@ -24,6 +25,7 @@ pub mod csharp_language_coherence;
 pub mod csi_u_session_input;
 pub mod cursor_style_rendering;
 pub mod dabbrev_completion;
+pub mod debugger;
 #[cfg(feature = "plugins")]
 pub mod dock_focus_stuck_born_attached;
 pub mod dock_panel_routing;
@@ -44,6 +46,7 @@ pub mod folding;
 pub mod glob_language_detection;
 #[cfg(feature = "gui")]
 pub mod gui;
+pub mod hex_view;
 pub mod hot_exit_flows;
 pub mod hot_exit_recovery_lsp_sync;
 pub mod indent_dedent;
@@ -96,6 +99,7 @@ pub mod lsp_bulk_edit_undo_desync;
 pub mod lsp_code_action_diagnostic_context;
 pub mod lsp_code_action_modal;
 pub mod lsp_code_action_resolve_and_commands;
+pub mod lsp_code_lens;
 pub mod lsp_completion_duplicate_entries_1514;
 pub mod lsp_completion_dynamic_registration;
 pub mod lsp_completion_french_locale;
@@ -105,7 +109,9 @@ pub mod lsp_config;
 pub mod lsp_crash_loop;
 pub mod lsp_cross_language_diagnostic_pull;
 pub mod lsp_diagnostic_flow;
+pub mod lsp_document_links_and_colors;
 pub mod lsp_env;
+pub mod lsp_file_operations;
 pub mod lsp_global_disable;
 pub mod lsp_goto_definition_readonly;
 #[cfg(feature = "plugins")]
@@ -113,6 +119,7 @@ pub mod lsp_indicator_click_bugs;
 pub mod lsp_indicator_click_to_open;
 pub mod lsp_inlay_hints_capability;
 pub mod lsp_lifecycle_visibility;
+pub mod lsp_linked_editing_and_on_type_formatting;
 pub mod lsp_missing_binary_and_dismiss;
 pub mod lsp_multi_semantic_tokens;
 pub mod lsp_no_config;
@@ -135,6 +142,7 @@ pub mod menu_cursor_bleed;
 pub mod menu_render_golden;
 pub mod menu_tab_color_bleed;
 pub mod merge_conflict;
+pub mod minimap;
 pub mod mouse;
 pub mod movement;
 pub mod multi_file_opening;
@@ -151,6 +159,7 @@ pub mod panel_mode_window_switch_leak;
 pub mod paste;
 pub mod per_buffer_view_settings;
 pub mod per_session_authority;
+pub mod persistent_undo;
 #[cfg(feature = "plugins")]
 pub mod plugins;
 pub mod popup_selection;
@@ -163,6 +172,7 @@ pub mod preview_tabs;
 pub mod prompt;
 pub mod prompt_editing;
 pub mod recovery;
+pub mod remote_control;
 pub mod remote_fs_test;
 pub mod remote_indicator_popup;
 pub mod rendering;
@@ -201,6 +211,7 @@ pub mod shift_backspace;
 pub mod slow_filesystem;
 pub mod smart_editing;
 pub mod smart_home;
+pub mod snippets;
 pub mod split_focus_tab_click;
 pub mod split_tabs;
 pub mod split_view;
@@ -210,6 +221,8 @@ pub mod status_bar_config;
 #[cfg(feature = "plugins")]
 pub mod status_bar_message_click;
 pub mod stdin_input;
+pub mod sticky_scroll;
+pub mod structural_selection;
 pub mod sudo_save_prompt;
 #[cfg(unix)]
 pub mod symlinks;
@@ -220,6 +233,7 @@ pub mod syntax_variable_builtin;
 pub mod tab_config;
 pub mod tab_drag;
 pub mod tab_new_button;
+pub mod tasks;
 pub mod terminal;
 pub mod terminal_close;
 pub mod terminal_link;
@@ -57,6 +57,8 @@ fn test_format_on_save() {
             on_save: vec![],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         },
     );
 
@@ -127,6 +129,8 @@ fn test_on_save_linter_style() {
             on_save: vec![action],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         },
     );
 
@@ -197,6 +201,8 @@ fn test_on_save_action_failure() {
             on_save: vec![action],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         },
     );
 
@@ -276,6 +282,8 @@ fn test_on_save_file_placeholder() {
             on_save: vec![action],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         },
     );
 
@@ -349,6 +357,8 @@ fn test_formatter_stdin_mode() {
             on_save: vec![],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         },
     );
 
@@ -429,6 +439,8 @@ fn test_on_save_stops_on_failure() {
             on_save: vec![action1, action2],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         },
     );
 
@@ -530,6 +542,8 @@ fn test_formatter_not_found_shows_message() {
             on_save: vec![],
             word_characters: None,
             indent: None,
+            linked_editing: true,
+            on_type_formatting: true,
         },
     );
 
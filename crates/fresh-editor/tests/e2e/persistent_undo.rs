@@ -0,0 +1,112 @@
+//! E2E tests for persistent undo: history saved with the file is restored
+//! when it is opened again in a new editor, unless the file changed on disk.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn harness(dir_context: &DirectoryContext, persistent_undo: bool) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.persistent_undo = persistent_undo;
+    EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_shared_dir_context(dir_context.clone()),
+    )
+    .unwrap()
+}
+
+/// First session: append two edits to `file` and save.
+fn edit_and_save(dir_context: &DirectoryContext, file: &Path) {
+    let mut harness = harness(dir_context, true);
+    harness.open_file(file).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" world").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("again").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("hello world\nagain");
+}
+
+#[test]
+fn test_undo_continues_after_reopen() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "hello").unwrap();
+    edit_and_save(&dir_context, &file);
+
+    let mut harness = harness(&dir_context, true);
+    harness.open_file(&file).unwrap();
+    harness.assert_buffer_content("hello world\nagain");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_ne!(
+        content, "hello world\nagain",
+        "undo should reach the saved history"
+    );
+    assert!(content.starts_with("hello"));
+
+    // Undo all the way back to the original file content.
+    for _ in 0..20 {
+        harness
+            .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+    harness.assert_buffer_content("hello");
+    assert_eq!(harness.cursor_position(), 5);
+
+    // And redo forward again.
+    for _ in 0..20 {
+        harness
+            .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+    harness.assert_buffer_content("hello world\nagain");
+}
+
+#[test]
+fn test_external_change_discards_history() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "hello").unwrap();
+    edit_and_save(&dir_context, &file);
+
+    std::fs::write(&file, "rewritten elsewhere").unwrap();
+
+    let mut harness = harness(&dir_context, true);
+    harness.open_file(&file).unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("rewritten elsewhere");
+}
+
+#[test]
+fn test_history_not_restored_when_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "hello").unwrap();
+    edit_and_save(&dir_context, &file);
+
+    let mut harness = harness(&dir_context, false);
+    harness.open_file(&file).unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("hello world\nagain");
+}
@@ -0,0 +1,173 @@
+//! E2E tests for the call_hierarchy plugin
+
+use crate::common::fixtures::line_start;
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// Answers `prepareCallHierarchy` with `helper` (line 5) and reports `main`
+/// as its only caller, called from line 2. `main` itself has no callers.
+const FAKE_LSP_SCRIPT: &str = r#"#!/bin/bash
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+send_message() {
+    local message="$1"
+    local length=${#message}
+    echo -en "Content-Length: $length\r\n\r\n$message"
+}
+range() {
+    echo '{"start":{"line":'$1',"character":'$2'},"end":{"line":'$1',"character":'$3'}}'
+}
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then
+        break
+    fi
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"callHierarchyProvider":true,"textDocumentSync":1}}}'
+            ;;
+        "initialized") ;;
+        "textDocument/didOpen"|"textDocument/didChange"|"textDocument/didSave") ;;
+        "textDocument/prepareCallHierarchy")
+            uri=$(echo "$msg" | grep -o '"uri":"[^"]*"' | head -1 | cut -d'"' -f4)
+            echo "$uri" > "$(dirname "$0")/uri"
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"name":"helper","kind":12,"uri":"'$uri'","range":'"$(range 4 0 14)"',"selectionRange":'"$(range 4 3 9)"',"data":42}]}'
+            ;;
+        "callHierarchy/incomingCalls")
+            uri=$(cat "$(dirname "$0")/uri")
+            if echo "$msg" | grep -q '"name":"helper"' && echo "$msg" | grep -q '"data":42'; then
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"from":{"name":"main","kind":12,"uri":"'$uri'","range":'"$(range 0 0 11)"',"selectionRange":'"$(range 0 3 7)"'},"fromRanges":['"$(range 1 4 10)"']}]}'
+            else
+                send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[]}'
+            fi
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+    esac
+done
+"#;
+
+const SOURCE: &str = "fn main() {\n    helper();\n}\n\nfn helper() {}\n";
+
+fn setup() -> anyhow::Result<(EditorTestHarness, tempfile::TempDir)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().to_path_buf();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "call_hierarchy");
+    copy_plugin_lib(&plugins_dir);
+
+    let script_path = project_root.join("fake_lsp.sh");
+    fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&script_path, perms)?;
+    }
+
+    let test_file = project_root.join("test.rs");
+    fs::write(&test_file, SOURCE)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+
+    harness.open_file(&test_file)?;
+    harness.process_async_and_render()?;
+    harness.wait_until(|h| h.screen_to_string().contains("LSP (on)"))?;
+
+    Ok((harness, temp_dir))
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.process_async_and_render()?;
+    Ok(())
+}
+
+/// The panel lists the callers of the symbol under the cursor (loaded
+/// lazily with the item echoed back verbatim), and Enter on a caller jumps
+/// to the call site.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_incoming_calls_tree_jumps_to_call_site() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = setup()?;
+
+    // Cursor on the `fn helper() {}` line (the fake server ignores the column).
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    }
+    harness.process_async_and_render()?;
+
+    run_command(&mut harness, "Show Incoming Calls")?;
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("Incoming calls: helper") && screen.contains("main  test.rs:1")
+    })?;
+    harness.assert_screen_contains("helper  test.rs:5");
+
+    // Select the caller and jump to where it calls `helper`.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.cursor_position() == line_start(SOURCE, 2) + 4)?;
+
+    Ok(())
+}
+
+/// A hierarchy the server doesn't advertise is reported in the status bar
+/// instead of opening an empty panel.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_unsupported_hierarchy_reports_status() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = setup()?;
+
+    // The fake server advertises call hierarchy only.
+    run_command(&mut harness, "Show Supertypes")?;
+    harness.assert_screen_contains("No language server for this buffer supports this hierarchy");
+
+    Ok(())
+}
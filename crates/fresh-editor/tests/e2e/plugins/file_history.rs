@@ -0,0 +1,91 @@
+//! E2E tests for the file history plugin
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+fn setup(config: Config) -> anyhow::Result<(EditorTestHarness, tempfile::TempDir)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().to_path_buf();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "file_history");
+    copy_plugin_lib(&plugins_dir);
+
+    let test_file = project_root.join("test.txt");
+    fs::write(&test_file, "one\n")?;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+    harness.open_file(&test_file)?;
+    harness.process_async_and_render()?;
+
+    Ok((harness, temp_dir))
+}
+
+fn type_and_save(harness: &mut EditorTestHarness, text: &str) -> anyhow::Result<()> {
+    harness.type_text(text)?;
+    harness.send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    Ok(())
+}
+
+/// Every save is listed as a version; an older one can be diffed against
+/// the buffer and restored as a single undoable edit.
+#[test]
+fn test_file_history_diffs_and_restores_saved_version() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = setup(Config::default())?;
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE)?;
+    type_and_save(&mut harness, "x")?;
+    type_and_save(&mut harness, "y")?;
+    harness.assert_buffer_content("onexy\n");
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    harness.type_text("File History")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("File History: test.txt") && screen.matches("just now").count() == 2
+    })?;
+
+    // The older version ("onex") is second; open its diff.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("[r] restore") && screen.contains("Current")
+    })?;
+
+    // `r` restores it and closes the diff.
+    harness.type_text("r")?;
+    harness.wait_until(|h| h.get_buffer_content().as_deref() == Some("onex\n"))?;
+    harness.assert_screen_contains("Restored version from");
+
+    // The restore is one undo step.
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    harness.assert_buffer_content("onexy\n");
+
+    Ok(())
+}
+
+/// With local history disabled, saves aren't recorded.
+#[test]
+fn test_file_history_empty_when_disabled() -> anyhow::Result<()> {
+    let mut config = Config::default();
+    config.editor.local_history_enabled = false;
+    let (mut harness, _temp_dir) = setup(config)?;
+
+    type_and_save(&mut harness, "x")?;
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    harness.type_text("File History")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("No saved versions yet"))?;
+
+    Ok(())
+}
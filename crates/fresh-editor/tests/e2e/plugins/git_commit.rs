@@ -0,0 +1,130 @@
+//! E2E tests for the git commit composer plugin
+
+use crate::common::git_test_helper::{git_command, DirGuard, GitTestRepo};
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+/// A repository with one commit and a staged change to `greeting.txt`.
+/// Identity and signing are set in the repository's own config, since the
+/// editor runs git without the test helper's `-c` overrides.
+fn setup() -> anyhow::Result<(EditorTestHarness, GitTestRepo)> {
+    let repo = GitTestRepo::new();
+    for (key, value) in [
+        ("user.name", "Test User"),
+        ("user.email", "test@example.com"),
+        ("commit.gpgsign", "false"),
+    ] {
+        git_command(&repo.path)
+            .args(["config", key, value])
+            .output()?;
+    }
+    repo.create_file("greeting.txt", "hello\n");
+    repo.git_add(&["greeting.txt"]);
+    repo.git_commit("Initial commit");
+    repo.create_file("greeting.txt", "hello\nworld\n");
+    repo.git_add(&["greeting.txt"]);
+
+    let plugins_dir = repo.path.join("plugins");
+    fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "git_commit");
+    copy_plugin_lib(&plugins_dir);
+
+    let harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )?;
+    Ok((harness, repo))
+}
+
+fn last_commit_message(repo: &GitTestRepo) -> String {
+    let output = git_command(&repo.path)
+        .args(["log", "-1", "--format=%B"])
+        .output()
+        .expect("Failed to run git log");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn open_composer(harness: &mut EditorTestHarness) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.wait_for_prompt()?;
+    harness.type_text("Git: Commit")?;
+    harness.wait_for_screen_contains("Git: Commit")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("Changes to be committed:") && screen.contains("greeting.txt")
+    })?;
+    Ok(())
+}
+
+/// The composer summarises the staged change, and Ctrl+Enter commits the
+/// message with the chosen options, leaving out the comment block.
+// TODO: git command output differs on Windows; the other git plugin tests skip it too.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_git_commit_composer_commits_with_signoff() -> anyhow::Result<()> {
+    let (mut harness, repo) = setup()?;
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    open_composer(&mut harness)?;
+    harness.type_text("feat: greet the world")?;
+
+    harness.send_key(KeyCode::Char('s'), KeyModifiers::ALT)?;
+    harness.wait_for_screen_contains("Sign-off: on")?;
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::CONTROL)?;
+    harness.wait_until(|_| last_commit_message(&repo).starts_with("feat: greet the world"))?;
+
+    let message = last_commit_message(&repo);
+    assert!(
+        message.contains("Signed-off-by: Test User <test@example.com>"),
+        "Sign-off trailer missing: {message}"
+    );
+    assert!(
+        !message.contains("Changes to be committed"),
+        "Comment block was committed: {message}"
+    );
+    harness.wait_until(|h| !h.screen_to_string().contains("Changes to be committed:"))?;
+    Ok(())
+}
+
+/// A rejecting commit-msg hook leaves the message open and reports the
+/// hook's output as a diagnostic.
+#[test]
+#[cfg(unix)]
+fn test_git_commit_composer_reports_hook_failure() -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (mut harness, repo) = setup()?;
+    let hook = repo.path.join(".git/hooks/commit-msg");
+    fs::create_dir_all(hook.parent().unwrap())?;
+    fs::write(
+        &hook,
+        "#!/bin/sh\necho 'subject must reference a ticket' >&2\nexit 1\n",
+    )?;
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755))?;
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    open_composer(&mut harness)?;
+    harness.type_text("fix: something")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::CONTROL)?;
+
+    harness.wait_until(|h| {
+        h.editor()
+            .get_stored_diagnostics()
+            .values()
+            .flatten()
+            .any(|d| d.message.contains("subject must reference a ticket"))
+    })?;
+    assert_eq!(last_commit_message(&repo).trim(), "Initial commit");
+    assert!(harness
+        .screen_to_string()
+        .contains("Changes to be committed:"));
+    Ok(())
+}
@@ -6,6 +6,7 @@ pub mod asm_lsp_config;
 pub mod audit_mode;
 pub mod authority_snapshot;
 pub mod buffer_info_splits;
+pub mod call_hierarchy;
 pub mod command_keybinding_editor;
 pub mod dashboard;
 // The three modules below drive the in-tree fake-devcontainer
@@ -32,8 +33,10 @@ pub mod diagnostics_panel_jump;
 pub mod diff_cursor;
 pub mod env_manager;
 pub mod file_explorer_slots;
+pub mod file_history;
 pub mod find_file;
 pub mod git;
+pub mod git_commit;
 pub mod git_log_current_file;
 pub mod git_log_split_tab_focus;
 pub mod git_statusbar;
@@ -50,17 +53,21 @@ pub mod orchestrator_attach_worktree;
 pub mod orchestrator_new_dialog;
 pub mod orchestrator_new_session_renders;
 pub mod orchestrator_open_cross_project;
+pub mod outline;
 pub mod package_manager;
 pub mod plugin;
 pub mod plugin_config_registration;
 pub mod plugin_keybinding_execution;
 pub mod plugins_dir_in_working_dir;
+pub mod refactor_preview;
 pub mod review_diff_hunk_parity;
 pub mod review_diff_ux_bugs;
 pub mod tab_actions;
 pub mod terminal_hooks;
+pub mod test_explorer;
 pub mod theme_editor;
 pub mod trust_lockdown;
+pub mod undo_history;
 pub mod unified_keybindings;
 pub mod vi_mode_autostart;
 pub mod watch_path;
@@ -0,0 +1,182 @@
+//! E2E tests for the outline plugin
+
+use crate::common::fixtures::line_start;
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// Answers `documentSymbol` with a flat `SymbolInformation` list, which the
+/// plugin nests by range: `Shape` (lines 1-7) contains `area`.
+const FAKE_LSP_SCRIPT: &str = r#"#!/bin/bash
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+send_message() {
+    local message="$1"
+    local length=${#message}
+    echo -en "Content-Length: $length\r\n\r\n$message"
+}
+symbol() {
+    echo '{"name":"'$1'","kind":'$2',"location":{"uri":"'$uri'","range":{"start":{"line":'$3',"character":0},"end":{"line":'$4',"character":1}}}}'
+}
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then
+        break
+    fi
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"documentSymbolProvider":true,"textDocumentSync":1}}}'
+            ;;
+        "initialized") ;;
+        "textDocument/didOpen"|"textDocument/didChange"|"textDocument/didSave") ;;
+        "textDocument/documentSymbol")
+            uri=$(echo "$msg" | grep -o '"uri":"[^"]*"' | head -1 | cut -d'"' -f4)
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":['"$(symbol Shape 23 0 6),$(symbol area 6 1 3),$(symbol main 12 8 10)"']}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+    esac
+done
+"#;
+
+const SOURCE: &str = "\
+struct Point {
+    x: i32,
+}
+
+impl Point {
+    fn new() -> Self {
+        Point { x: 0 }
+    }
+}
+
+fn main() {
+    let p = Point::new();
+}
+";
+
+fn setup(config: fresh::config::Config) -> anyhow::Result<(EditorTestHarness, tempfile::TempDir)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().to_path_buf();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "outline");
+    copy_plugin_lib(&plugins_dir);
+
+    let test_file = project_root.join("test.rs");
+    fs::write(&test_file, SOURCE)?;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+    harness.open_file(&test_file)?;
+    harness.process_async_and_render()?;
+
+    Ok((harness, temp_dir))
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.process_async_and_render()?;
+    Ok(())
+}
+
+/// Without a language server the symbols come from the file itself; typing
+/// filters the tree and Enter jumps to the symbol in the file.
+#[test]
+fn test_outline_syntax_fallback_filter_and_jump() -> anyhow::Result<()> {
+    let mut config = fresh::config::Config::default();
+    config.lsp.clear();
+    let (mut harness, _temp_dir) = setup(config)?;
+
+    run_command(&mut harness, "Toggle Outline")?;
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("Outline: test.rs  (syntax)") && screen.contains("main  function")
+    })?;
+    harness.assert_screen_contains("Point  struct");
+    harness.assert_screen_contains("new  function");
+
+    harness.type_text("mai")?;
+    harness.wait_until(|h| h.screen_to_string().contains("Filter: mai"))?;
+    harness.assert_screen_not_contains("Point  struct");
+    harness.assert_screen_contains("main  function");
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.cursor_position() == line_start(SOURCE, 11) + 3)?;
+
+    Ok(())
+}
+
+/// A server's flat `SymbolInformation` answer is nested by range and wins
+/// over the local symbols.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_outline_uses_server_symbols() -> anyhow::Result<()> {
+    let script_dir = tempfile::TempDir::new()?;
+    let script_path = script_dir.path().join("fake_lsp.sh");
+    fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&script_path, perms)?;
+    }
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+    let (mut harness, _temp_dir) = setup(config)?;
+    harness.wait_until(|h| h.screen_to_string().contains("LSP (on)"))?;
+
+    run_command(&mut harness, "Toggle Outline")?;
+    harness.wait_until(|h| h.screen_to_string().contains("Shape  struct"))?;
+    harness.assert_screen_contains("area  method");
+    harness.assert_screen_not_contains("(syntax)");
+
+    // Collapsing `Shape` hides its child.
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE)?;
+    harness.wait_until(|h| !h.screen_to_string().contains("area  method"))?;
+    harness.assert_screen_contains("main  function");
+
+    Ok(())
+}
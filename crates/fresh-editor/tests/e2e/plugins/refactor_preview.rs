@@ -0,0 +1,244 @@
+//! E2E tests for the refactor_preview plugin: a rename spanning two files
+//! is shown for review, applied without the changes left unchecked, and
+//! undone in one step across both files.
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Answers any rename with `foo` → `bar` at both of its uses in the
+/// renamed file and at the import in the file given as first argument.
+const FAKE_LSP_SCRIPT: &str = r#"#!/bin/bash
+OTHER="$1"
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+send_message() {
+    local message="$1"
+    local length=${#message}
+    echo -en "Content-Length: $length\r\n\r\n$message"
+}
+edit() {
+    echo '{"range":{"start":{"line":'$1',"character":'$2'},"end":{"line":'$1',"character":'$3'}},"newText":"bar"}'
+}
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then
+        break
+    fi
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"renameProvider":true,"textDocumentSync":1}}}'
+            ;;
+        "initialized") ;;
+        "textDocument/didOpen"|"textDocument/didChange"|"textDocument/didSave") ;;
+        "textDocument/rename")
+            uri=$(echo "$msg" | grep -o '"uri":"[^"]*"' | head -1 | cut -d'"' -f4)
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"changes":{"'$uri'":['"$(edit 0 3 6)"','"$(edit 1 12 15)"'],"file://'$OTHER'":['"$(edit 0 11 14)"']}}}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+    esac
+done
+"#;
+
+const MAIN_RS: &str = "fn foo() {}\nfn main() { foo(); }\n";
+const OTHER_RS: &str = "use crate::foo;\n";
+
+/// Open `main.rs` with the plugin loaded and the fake server attached.
+/// Returns the harness, the temp dir and the path of `other.rs`.
+fn setup() -> anyhow::Result<(EditorTestHarness, tempfile::TempDir, PathBuf)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().to_path_buf();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "refactor_preview");
+    copy_plugin_lib(&plugins_dir);
+
+    let script_path = project_root.join("fake_lsp.sh");
+    fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&script_path, perms)?;
+    }
+
+    let main_rs = project_root.join("main.rs");
+    let other_rs = project_root.join("other.rs");
+    fs::write(&main_rs, MAIN_RS)?;
+    fs::write(&other_rs, OTHER_RS)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: vec![other_rs.to_string_lossy().to_string()],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, config, project_root)?;
+
+    harness.open_file(&main_rs)?;
+    harness.process_async_and_render()?;
+    harness.wait_until(|h| h.screen_to_string().contains("LSP (on)"))?;
+
+    Ok((harness, temp_dir, other_rs))
+}
+
+/// Rename `foo` from its definition, up to the preview showing.
+fn rename_foo(harness: &mut EditorTestHarness) -> anyhow::Result<()> {
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    harness.type_text("Rename Symbol")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_prompt()?;
+    // The fake server ignores the new name; it only has to differ.
+    harness.type_text("bar")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("Rename symbol: 3 of 3 changes selected")
+}
+
+/// Content of the buffer showing `path`, switching to it.
+fn buffer_content(harness: &mut EditorTestHarness, path: &Path) -> anyhow::Result<String> {
+    harness.open_file(path)?;
+    Ok(harness.get_buffer_content().unwrap())
+}
+
+/// Unchecking a change leaves it out; the rest lands in both files, and
+/// nothing is written before the preview is applied.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_preview_applies_selected_changes() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir, other_rs) = setup()?;
+
+    rename_foo(&mut harness)?;
+    harness.assert_screen_contains("main.rs");
+    harness.assert_screen_contains("other.rs");
+    // main.rs is shown as a diff, before on the left and after on the right.
+    harness.wait_for_screen_contains("fn bar() {}")?;
+    harness.assert_screen_contains("fn foo() {}");
+
+    // Row 0 is main.rs, row 1 its first change: the definition.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Char(' '), KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("Rename symbol: 2 of 3 changes selected")?;
+    // The diff only shows what is still checked.
+    harness.wait_until(|h| !h.screen_to_string().contains("fn bar() {}"))?;
+    harness.assert_screen_contains("fn main() { bar(); }");
+
+    // Moving to other.rs shows its diff.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("use crate::bar;")?;
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_buffer_content("fn foo() {}\nfn main() { bar(); }\n")?;
+    assert_eq!(
+        buffer_content(&mut harness, &other_rs)?,
+        "use crate::bar;\n"
+    );
+
+    Ok(())
+}
+
+/// One undo in either file takes back the whole rename.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_preview_undo_reverts_all_files() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir, other_rs) = setup()?;
+
+    rename_foo(&mut harness)?;
+    harness.send_key(KeyCode::Char('a'), KeyModifiers::NONE)?;
+    harness.wait_for_buffer_content("fn bar() {}\nfn main() { bar(); }\n")?;
+
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    harness.wait_for_buffer_content(MAIN_RS)?;
+    assert_eq!(buffer_content(&mut harness, &other_rs)?, OTHER_RS);
+
+    // Redo from the other file brings both back.
+    harness.send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)?;
+    harness.wait_for_buffer_content("use crate::bar;\n")?;
+    harness.open_file(&other_rs.with_file_name("main.rs"))?;
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn bar() {}\nfn main() { bar(); }\n"
+    );
+
+    Ok(())
+}
+
+/// Escape drops the edit without touching any file.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_preview_discard_leaves_files_alone() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir, other_rs) = setup()?;
+
+    rename_foo(&mut harness)?;
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("Edit discarded")?;
+
+    assert_eq!(harness.get_buffer_content().unwrap(), MAIN_RS);
+    assert_eq!(fs::read_to_string(&other_rs)?, OTHER_RS);
+
+    Ok(())
+}
+
+/// A file that wasn't open and changed on disk while the preview was up
+/// refuses the edit rather than applying it at stale positions.
+#[test]
+#[cfg_attr(windows, ignore)] // Uses bash script for fake LSP server
+fn test_preview_refused_when_unopened_file_changed_on_disk() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir, other_rs) = setup()?;
+
+    rename_foo(&mut harness)?;
+    fs::write(&other_rs, "// moved\nuse crate::foo;\n")?;
+    harness.send_key(KeyCode::Char('a'), KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("Files changed since the preview opened")?;
+
+    assert_eq!(harness.get_buffer_content().unwrap(), MAIN_RS);
+    assert_eq!(
+        fs::read_to_string(&other_rs)?,
+        "// moved\nuse crate::foo;\n"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,379 @@
+//! E2E tests for the test_explorer plugin
+//!
+//! Runs use the fake runners in `tests/fixtures/fake-test-runner`, which
+//! replay output captured from the real tools.
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SOURCE: &str = "package calc\n\nimport \"testing\"\n\nfunc TestAdd(t *testing.T) {\n}\n\nfunc TestSub(t *testing.T) {\n\tt.Errorf(\"got 1, want 2\")\n}\n";
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.process_async_and_render()?;
+    Ok(())
+}
+
+/// A project holding the test_explorer plugin and `files`, opened on the
+/// first of them.
+fn open_project(
+    files: &[(&str, &str)],
+) -> anyhow::Result<(tempfile::TempDir, PathBuf, EditorTestHarness)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().canonicalize()?;
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "test_explorer");
+    copy_plugin_lib(&plugins_dir);
+
+    for (name, content) in files {
+        let path = project_root.join(name);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, content)?;
+    }
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        30,
+        fresh::config::Config::default(),
+        project_root.clone(),
+    )?;
+    harness.open_file(&project_root.join(files[0].0))?;
+    harness.process_async_and_render()?;
+    Ok((temp_dir, project_root, harness))
+}
+
+/// Put the fake runners ahead of the real tools on PATH. They only stand
+/// in for a tool in directories holding canned output for it, so the
+/// process-global change is harmless to other tests.
+#[cfg(unix)]
+fn use_fake_runners() {
+    static PATH_INIT: std::sync::Once = std::sync::Once::new();
+    PATH_INIT.call_once(|| {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/fake-test-runner")
+            .canonicalize()
+            .expect("tests/fixtures/fake-test-runner must exist");
+        let old = std::env::var("PATH").unwrap_or_default();
+        std::env::set_var("PATH", format!("{}:{old}", dir.display()));
+    });
+}
+
+/// Make the fake `tool` replay `stdout`, `stderr` and `exit_code` when run
+/// in `dir`.
+#[cfg(unix)]
+fn can_run(dir: &Path, tool: &str, stdout: &str, stderr: &str, exit_code: i32) {
+    use_fake_runners();
+    fs::write(dir.join(format!(".fake-{tool}.stdout")), stdout).unwrap();
+    fs::write(dir.join(format!(".fake-{tool}.stderr")), stderr).unwrap();
+    fs::write(
+        dir.join(format!(".fake-{tool}.exit")),
+        exit_code.to_string(),
+    )
+    .unwrap();
+}
+
+/// The arguments the fake `tool` was last run with in `dir`.
+#[cfg(unix)]
+fn run_args(dir: &Path, tool: &str) -> String {
+    fs::read_to_string(dir.join(format!(".fake-{tool}.args")))
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// Run every test of the open file and wait for the result summary.
+#[cfg(unix)]
+fn run_tests_in_file(harness: &mut EditorTestHarness, summary: &str) -> anyhow::Result<()> {
+    run_command(harness, "Test: Run Tests in File")?;
+    harness.wait_until(|h| h.get_status_bar().contains(summary))
+}
+
+/// The failure diagnostics of the run as (0-indexed line, message).
+#[cfg(unix)]
+fn failures(harness: &EditorTestHarness) -> Vec<(u32, String)> {
+    let mut failures: Vec<(u32, String)> = harness
+        .editor()
+        .get_stored_diagnostics()
+        .values()
+        .flatten()
+        .filter(|d| d.source.as_deref() == Some("tests"))
+        .map(|d| (d.range.start.line, d.message.clone()))
+        .collect();
+    failures.sort();
+    failures
+}
+
+/// The screen row showing `text`.
+#[cfg(unix)]
+fn row_with(harness: &EditorTestHarness, text: &str) -> String {
+    harness
+        .screen_to_string()
+        .lines()
+        .find(|line| line.contains(text))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// The explorer lists the tests discovered in the file that was open when
+/// it was shown.
+#[test]
+fn test_explorer_lists_discovered_tests() -> anyhow::Result<()> {
+    let (_temp_dir, _root, mut harness) = open_project(&[("calc_test.go", SOURCE)])?;
+
+    run_command(&mut harness, "Test: Show Test Explorer")?;
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("calc_test.go") && screen.contains("TestAdd") && screen.contains("TestSub")
+    })?;
+
+    Ok(())
+}
+
+/// A run marks each test's line in the gutter with its outcome and puts
+/// the failure output on the line it points to.
+#[cfg(unix)]
+#[test]
+fn test_run_marks_results_and_reports_failures() -> anyhow::Result<()> {
+    let (_temp_dir, root, mut harness) = open_project(&[("calc_test.go", SOURCE)])?;
+    can_run(
+        &root,
+        "go",
+        r#"{"Action":"start","Package":"calc"}
+{"Action":"run","Package":"calc","Test":"TestAdd"}
+{"Action":"output","Package":"calc","Test":"TestAdd","Output":"=== RUN   TestAdd\n"}
+{"Action":"output","Package":"calc","Test":"TestAdd","Output":"--- PASS: TestAdd (0.00s)\n"}
+{"Action":"pass","Package":"calc","Test":"TestAdd","Elapsed":0}
+{"Action":"run","Package":"calc","Test":"TestSub"}
+{"Action":"output","Package":"calc","Test":"TestSub","Output":"=== RUN   TestSub\n"}
+{"Action":"output","Package":"calc","Test":"TestSub","Output":"    calc_test.go:9: got 1, want 2\n"}
+{"Action":"output","Package":"calc","Test":"TestSub","Output":"--- FAIL: TestSub (0.00s)\n"}
+{"Action":"fail","Package":"calc","Test":"TestSub","Elapsed":0}
+{"Action":"output","Package":"calc","Output":"FAIL\n"}
+{"Action":"fail","Package":"calc","Elapsed":0.002}
+"#,
+        "",
+        1,
+    );
+
+    run_tests_in_file(&mut harness, "1 of 2 test(s) failed")?;
+    assert_eq!(
+        run_args(&root, "go"),
+        "test -json -run ^(TestAdd|TestSub)$ ."
+    );
+
+    harness.wait_until(|h| {
+        row_with(h, "func TestAdd").contains('✓') && row_with(h, "func TestSub").contains('✗')
+    })?;
+    assert_eq!(
+        failures(&harness),
+        vec![(8, "calc_test.go:9: got 1, want 2".to_string())]
+    );
+    Ok(())
+}
+
+/// cargo: results come from the `test x ... ok` lines, the message from the
+/// `---- x stdout ----` section and the line from the panic location.
+#[cfg(unix)]
+#[test]
+fn test_parses_cargo_test_output() -> anyhow::Result<()> {
+    let lib = "#[cfg(test)]\nmod tests {\n    #[test]\n    fn adds() {\n        assert_eq!(1 + 1, 2);\n    }\n\n    #[test]\n    fn subtracts() {\n        assert_eq!(2 - 1, 2);\n    }\n}\n";
+    let (_temp_dir, root, mut harness) = open_project(&[
+        ("src/lib.rs", lib),
+        (
+            "Cargo.toml",
+            "[package]\nname = \"calc\"\nversion = \"0.1.0\"\n",
+        ),
+    ])?;
+    can_run(
+        &root,
+        "cargo",
+        "
+running 2 tests
+test tests::adds ... ok
+test tests::subtracts ... FAILED
+
+failures:
+
+---- tests::subtracts stdout ----
+
+thread 'tests::subtracts' panicked at src/lib.rs:10:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::subtracts
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+",
+        "   Compiling calc v0.1.0\n    Finished `test` profile [unoptimized + debuginfo] target(s) in 0.31s\n     Running unittests src/lib.rs (target/debug/deps/calc-1a2b3c4d)\nerror: test failed, to rerun pass `--lib`\n",
+        101,
+    );
+
+    run_tests_in_file(&mut harness, "1 of 2 test(s) failed")?;
+    assert_eq!(
+        run_args(&root, "cargo"),
+        "test -- --exact tests::adds tests::subtracts"
+    );
+    assert_eq!(
+        failures(&harness),
+        vec![(
+            9,
+            "assertion `left == right` failed\n  left: 1\n right: 2".to_string()
+        )]
+    );
+    Ok(())
+}
+
+/// pytest: outcomes come from the `-v` lines, the message from the `E`
+/// lines of the `-rfE` traceback and the line from its location.
+#[cfg(unix)]
+#[test]
+fn test_parses_pytest_output() -> anyhow::Result<()> {
+    let source = "def test_add():\n    assert 1 + 1 == 2\n\n\nclass TestMath:\n    def test_sub(self):\n        assert 2 - 1 == 2\n";
+    let (_temp_dir, root, mut harness) = open_project(&[("test_calc.py", source)])?;
+    can_run(
+        &root,
+        "pytest",
+        "============================= test session starts ==============================
+collecting ... collected 2 items
+
+test_calc.py::test_add PASSED                                            [ 50%]
+test_calc.py::TestMath::test_sub FAILED                                  [100%]
+
+=================================== FAILURES ===================================
+______________________________ TestMath.test_sub _______________________________
+test_calc.py:7: in test_sub
+    assert 2 - 1 == 2
+E   assert (2 - 1) == 2
+=========================== short test summary info ============================
+FAILED test_calc.py::TestMath::test_sub - assert (2 - 1) == 2
+========================= 1 failed, 1 passed in 0.01s ==========================
+",
+        "",
+        1,
+    );
+
+    run_tests_in_file(&mut harness, "1 of 2 test(s) failed")?;
+    assert!(run_args(&root, "pytest")
+        .ends_with("test_calc.py::test_add test_calc.py::TestMath::test_sub"));
+    assert_eq!(
+        failures(&harness),
+        vec![(6, "assert (2 - 1) == 2".to_string())]
+    );
+    Ok(())
+}
+
+/// go: outcomes and output come from the `-json` events; the message drops
+/// the `=== RUN`/`--- FAIL` lines and keeps the `file.go:N:` location.
+#[cfg(unix)]
+#[test]
+fn test_parses_go_test_output_with_subtests() -> anyhow::Result<()> {
+    let (_temp_dir, root, mut harness) = open_project(&[("calc_test.go", SOURCE)])?;
+    can_run(
+        &root,
+        "go",
+        r#"{"Action":"run","Package":"calc","Test":"TestAdd"}
+{"Action":"output","Package":"calc","Test":"TestAdd","Output":"=== RUN   TestAdd\n"}
+{"Action":"run","Package":"calc","Test":"TestAdd/small"}
+{"Action":"output","Package":"calc","Test":"TestAdd/small","Output":"    --- PASS: TestAdd/small (0.00s)\n"}
+{"Action":"pass","Package":"calc","Test":"TestAdd/small","Elapsed":0}
+{"Action":"output","Package":"calc","Test":"TestAdd","Output":"--- PASS: TestAdd (0.00s)\n"}
+{"Action":"pass","Package":"calc","Test":"TestAdd","Elapsed":0}
+{"Action":"run","Package":"calc","Test":"TestSub"}
+{"Action":"run","Package":"calc","Test":"TestSub/negative"}
+{"Action":"output","Package":"calc","Test":"TestSub/negative","Output":"=== RUN   TestSub/negative\n"}
+{"Action":"output","Package":"calc","Test":"TestSub/negative","Output":"    calc_test.go:9: got 1, want 2\n"}
+{"Action":"output","Package":"calc","Test":"TestSub/negative","Output":"    --- FAIL: TestSub/negative (0.00s)\n"}
+{"Action":"fail","Package":"calc","Test":"TestSub/negative","Elapsed":0}
+{"Action":"output","Package":"calc","Test":"TestSub","Output":"--- FAIL: TestSub (0.00s)\n"}
+{"Action":"fail","Package":"calc","Test":"TestSub","Elapsed":0}
+{"Action":"fail","Package":"calc","Elapsed":0.002}
+"#,
+        "",
+        1,
+    );
+
+    run_tests_in_file(&mut harness, "1 of 2 test(s) failed")?;
+    assert_eq!(
+        failures(&harness),
+        vec![(8, "calc_test.go:9: got 1, want 2".to_string())]
+    );
+    Ok(())
+}
+
+/// jest: results come from the `--json` report, keyed by full name; the
+/// message is the failure without ANSI colours or stack, and the line comes
+/// from the stack frame in the test file.
+#[cfg(unix)]
+#[test]
+fn test_parses_jest_json_report() -> anyhow::Result<()> {
+    let source = "describe(\"calc\", () => {\n  test(\"adds\", () => {\n    expect(1 + 1).toBe(2);\n  });\n\n  test(\"subtracts\", () => {\n    expect(2 - 1).toBe(2);\n  });\n});\n";
+    let (_temp_dir, root, mut harness) = open_project(&[("calc.test.js", source)])?;
+    can_run(
+        &root,
+        "npx",
+        r#"{"numFailedTests":1,"numPassedTests":1,"success":false,"testResults":[{"assertionResults":[{"ancestorTitles":["calc"],"fullName":"calc adds","status":"passed","title":"adds","failureMessages":[]},{"ancestorTitles":["calc"],"fullName":"calc subtracts","status":"failed","title":"subtracts","failureMessages":["Error: \u001b[2mexpect(\u001b[22m\u001b[31mreceived\u001b[39m\u001b[2m).\u001b[22mtoBe\u001b[2m(\u001b[22m\u001b[32mexpected\u001b[39m\u001b[2m) // Object.is equality\u001b[22m\n\nExpected: \u001b[32m2\u001b[39m\nReceived: \u001b[31m1\u001b[39m\n    at Object.toBe (/project/calc.test.js:7:19)\n    at Promise.then.completed (/project/node_modules/jest-circus/build/utils.js:298:28)"]}],"name":"/project/calc.test.js","status":"failed"}]}
+"#,
+        "FAIL ./calc.test.js\n  calc\n    ✓ adds (2 ms)\n    ✕ subtracts (3 ms)\n",
+        1,
+    );
+
+    run_tests_in_file(&mut harness, "1 of 2 test(s) failed")?;
+    assert!(run_args(&root, "npx").ends_with("-t ^(calc adds|calc subtracts)$"));
+    assert_eq!(
+        failures(&harness),
+        vec![(
+            6,
+            "Error: expect(received).toBe(expected) // Object.is equality\n\nExpected: 2\nReceived: 1"
+                .to_string()
+        )]
+    );
+    Ok(())
+}
+
+/// A run that fails without reporting any result (here a build error)
+/// fails every test with the error, so it is visible on each of them.
+#[cfg(unix)]
+#[test]
+fn test_build_error_fails_every_test() -> anyhow::Result<()> {
+    let lib = "#[cfg(test)]\nmod tests {\n    #[test]\n    fn adds() {\n        assert_eq!(1 + 1, 2);\n    }\n\n    #[test]\n    fn subtracts() {\n        assert_eq!(subtract(2, 1), 1);\n    }\n}\n";
+    let (_temp_dir, root, mut harness) = open_project(&[
+        ("src/lib.rs", lib),
+        (
+            "Cargo.toml",
+            "[package]\nname = \"calc\"\nversion = \"0.1.0\"\n",
+        ),
+    ])?;
+    can_run(
+        &root,
+        "cargo",
+        "",
+        "   Compiling calc v0.1.0
+error[E0425]: cannot find function `subtract` in this scope
+  --> src/lib.rs:10:20
+   |
+10 |         assert_eq!(subtract(2, 1), 1);
+   |                    ^^^^^^^^ not found in this scope
+
+For more information about this error, try `rustc --explain E0425`.
+error: could not compile `calc` (lib test) due to 1 previous error
+",
+        101,
+    );
+
+    run_tests_in_file(&mut harness, "2 of 2 test(s) failed")?;
+    let error = "error[E0425]: cannot find function `subtract` in this scope".to_string();
+    assert_eq!(failures(&harness), vec![(3, error.clone()), (8, error)]);
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! E2E tests for the undo history plugin
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+fn setup() -> anyhow::Result<(EditorTestHarness, tempfile::TempDir)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let project_root = temp_dir.path().to_path_buf();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir)?;
+    copy_plugin(&plugins_dir, "undo_history");
+    copy_plugin_lib(&plugins_dir);
+
+    let test_file = project_root.join("test.txt");
+    fs::write(&test_file, "hello\n")?;
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        30,
+        fresh::config::Config::default(),
+        project_root,
+    )?;
+    harness.open_file(&test_file)?;
+    harness.process_async_and_render()?;
+
+    Ok((harness, temp_dir))
+}
+
+/// Undoing and then typing keeps the undone edit as a branch, which the
+/// panel previews and jumps back to.
+#[test]
+fn test_undo_history_jumps_to_abandoned_branch() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = setup()?;
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE)?;
+    harness.type_text("a")?;
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    harness.type_text("b")?;
+    harness.assert_buffer_content("hellob\n");
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.process_async_and_render()?;
+    harness.type_text("Toggle Undo History")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| {
+        let screen = h.screen_to_string();
+        screen.contains("Undo History: test.txt")
+            && screen.contains("Insert \"a\"")
+            && screen.contains("Insert \"b\"")
+    })?;
+    harness.assert_screen_contains("Original");
+
+    // The branch with "a" sits above the current state; selecting it
+    // previews the change.
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("+helloa"))?;
+    harness.assert_screen_contains("-hellob");
+
+    // Enter jumps there, leaving nothing to preview.
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("Same as the buffer"))?;
+
+    // Escape returns to the file, now on the other branch.
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.get_buffer_content().as_deref() == Some("helloa\n"))?;
+    harness.send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)?;
+    harness.assert_buffer_content("helloa\n");
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Tests for the remote-control API (`fresh --remote`): JSON-RPC requests
+//! answered by `Editor::handle_remote_request`, as the session daemon and
+//! the direct-mode control listener route them.
+
+use crate::common::harness::EditorTestHarness;
+use fresh::server::protocol::{RemoteError, RemoteRequest, RemoteResponse};
+use serde_json::{json, Value};
+use std::fs;
+
+fn request(harness: &mut EditorTestHarness, method: &str, params: Value) -> RemoteResponse {
+    let request: RemoteRequest = serde_json::from_value(
+        json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params }),
+    )
+    .unwrap();
+    let response = harness.editor_mut().handle_remote_request(&request);
+    assert_eq!(response.id, json!(7));
+    response
+}
+
+fn result(harness: &mut EditorTestHarness, method: &str, params: Value) -> Value {
+    let response = request(harness, method, params);
+    assert!(
+        response.error.is_none(),
+        "{method} failed: {:?}",
+        response.error
+    );
+    response.result.unwrap()
+}
+
+#[test]
+fn test_remote_reads_and_edits_buffers() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("notes.txt");
+    fs::write(&file_path, "hello").unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    let buffers = result(&mut harness, "listBuffers", Value::Null);
+    let open = buffers
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["active"] == json!(true))
+        .expect("an active buffer");
+    assert_eq!(open["path"], json!(file_path));
+
+    // Paths are relative to the working directory
+    let text = result(
+        &mut harness,
+        "getBufferText",
+        json!({ "path": "notes.txt" }),
+    );
+    assert_eq!(text, json!("hello"));
+
+    result(
+        &mut harness,
+        "executeAction",
+        json!({ "name": "move_document_end" }),
+    );
+    assert_eq!(
+        result(&mut harness, "insertAtCursor", json!({ "text": ", world" })),
+        json!(true)
+    );
+    harness.render().unwrap();
+    harness.assert_buffer_content("hello, world");
+
+    let cursors = result(&mut harness, "getAllCursors", Value::Null);
+    assert_eq!(cursors[0]["position"], json!(12));
+    assert_eq!(cursors[0]["line"], json!(0));
+}
+
+#[test]
+fn test_remote_reports_errors() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+
+    let response = request(&mut harness, "noSuchMethod", Value::Null);
+    assert!(response.result.is_none());
+    assert_eq!(response.error.unwrap().code, RemoteError::METHOD_NOT_FOUND);
+
+    // Missing required params
+    let response = request(&mut harness, "insertAtCursor", Value::Null);
+    assert_eq!(response.error.unwrap().code, RemoteError::INVALID_PARAMS);
+
+    let response = request(
+        &mut harness,
+        "getBufferText",
+        json!({ "path": "missing.txt" }),
+    );
+    assert_eq!(response.error.unwrap().code, RemoteError::INVALID_PARAMS);
+}
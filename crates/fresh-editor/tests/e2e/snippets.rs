@@ -0,0 +1,125 @@
+//! E2E tests for snippet sessions: user snippets offered in the completion
+//! popup, Tab/Shift+Tab through tabstops, mirrors, and choice popups.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+
+const SNIPPETS: &str = r#"{
+    // Mirrors $1 in the trailing comment.
+    "Function": {
+        "prefix": "func",
+        "body": ["function ${1:name}(${2:args}) {", "\t$0", "}", "// end ${1}"],
+    },
+    "Let": {
+        "prefix": "letc",
+        "body": "let ${1|alpha,beta|} = $0;",
+        "description": "Binding with a choice",
+    },
+}"#;
+
+/// Harness with `SNIPPETS` in the config dir and Ctrl+Space bound to
+/// completion, which falls back to the buffer providers without LSP.
+fn snippet_harness() -> anyhow::Result<(EditorTestHarness, tempfile::TempDir)> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+    let snippets_dir = dir_context.snippets_dir();
+    std::fs::create_dir_all(&snippets_dir)?;
+    std::fs::write(snippets_dir.join("test.code-snippets"), SNIPPETS)?;
+
+    let mut config = Config::default();
+    config.keybindings.push(fresh::config::Keybinding {
+        key: " ".to_string(),
+        modifiers: vec!["ctrl".to_string()],
+        keys: vec![],
+        action: "lsp_completion".to_string(),
+        args: std::collections::HashMap::new(),
+        when: None,
+    });
+    let harness = EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_shared_dir_context(dir_context),
+    )?;
+    Ok((harness, temp_dir))
+}
+
+/// Type `prefix`, open the completion popup and accept the first item.
+fn accept_snippet(harness: &mut EditorTestHarness, prefix: &str) -> anyhow::Result<()> {
+    harness.type_text(prefix)?;
+    harness.send_key(KeyCode::Char(' '), KeyModifiers::CONTROL)?;
+    harness.render()?;
+    assert!(harness.editor().active_state().popups.is_visible());
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
+    harness.render()?;
+    Ok(())
+}
+
+#[test]
+fn test_snippet_tabstops_and_mirrors() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = snippet_harness()?;
+    accept_snippet(&mut harness, "func")?;
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "function name(args) {\n\t\n}\n// end name"
+    );
+    // The placeholder and its mirror are edited together.
+    assert_eq!(harness.cursor_count(), 2);
+    harness.type_text("run")?;
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "function run(args) {\n\t\n}\n// end run"
+    );
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
+    assert_eq!(harness.cursor_count(), 1);
+    harness.type_text("a, b")?;
+
+    // Shift+Tab goes back to $1 with its text selected.
+    harness.send_key(KeyCode::BackTab, KeyModifiers::SHIFT)?;
+    harness.type_text("go")?;
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
+
+    // Tab from the last tabstop lands on $0 and ends the session.
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
+    harness.type_text("body")?;
+    harness.assert_buffer_content("function go(a, b) {\n\tbody\n}\n// end go");
+
+    Ok(())
+}
+
+#[test]
+fn test_snippet_choice_popup() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = snippet_harness()?;
+    accept_snippet(&mut harness, "letc")?;
+
+    harness.assert_buffer_content("let alpha = ;");
+    harness.assert_screen_contains("beta");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.assert_buffer_content("let beta = ;");
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
+    harness.type_text("1")?;
+    harness.assert_buffer_content("let beta = 1;");
+
+    Ok(())
+}
+
+#[test]
+fn test_escape_ends_snippet_session() -> anyhow::Result<()> {
+    let (mut harness, _temp_dir) = snippet_harness()?;
+    accept_snippet(&mut harness, "func")?;
+    assert_eq!(harness.cursor_count(), 2);
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    assert_eq!(harness.cursor_count(), 1);
+    assert!(harness.editor().active_state().snippet_session.is_none());
+
+    Ok(())
+}
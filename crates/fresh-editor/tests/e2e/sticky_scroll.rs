@@ -0,0 +1,65 @@
+//! E2E tests for sticky scroll: enclosing scope headers pinned over the top
+//! of the viewport, and clicking one to jump to it.
+
+use crate::common::harness::{layout, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// `Outer` and `inner` enclose a body far longer than the viewport.
+fn source() -> String {
+    let mut text = String::from("class Outer:\n    def inner(self):\n");
+    for i in 0..60 {
+        text.push_str(&format!("        value_{i} = {i}\n"));
+    }
+    text.push_str("    def other(self):\n        pass\n");
+    text
+}
+
+/// Open the source and move the cursor 40 lines down, deep inside `inner`.
+fn open_scrolled(
+    config: fresh::config::Config,
+) -> anyhow::Result<(EditorTestHarness, crate::common::fixtures::TestFixture)> {
+    let mut harness = EditorTestHarness::with_config(80, 24, config)?;
+    let fixture = harness.load_buffer_from_text(&source())?;
+    for _ in 0..40 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    }
+    harness.render()?;
+    Ok((harness, fixture))
+}
+
+fn top_row(harness: &EditorTestHarness, offset: usize) -> String {
+    harness.get_row_text((layout::CONTENT_START_ROW + offset) as u16)
+}
+
+#[test]
+fn test_sticky_scroll_pins_headers_and_click_jumps() -> anyhow::Result<()> {
+    let mut config = fresh::config::Config::default();
+    config.editor.sticky_scroll = true;
+    let (mut harness, _fixture) = open_scrolled(config)?;
+
+    assert!(top_row(&harness, 0).contains("class Outer:"));
+    assert!(top_row(&harness, 1).contains("def inner(self):"));
+    assert!(top_row(&harness, 2).contains("value_"));
+
+    harness.mouse_click(20, (layout::CONTENT_START_ROW + 1) as u16)?;
+    assert_eq!(harness.cursor_position(), "class Outer:\n    ".len());
+    harness.assert_screen_contains("def inner(self):");
+
+    Ok(())
+}
+
+#[test]
+fn test_sticky_scroll_toggle_command() -> anyhow::Result<()> {
+    let (mut harness, _fixture) = open_scrolled(fresh::config::Config::default())?;
+    assert!(!top_row(&harness, 0).contains("class Outer:"));
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.type_text("Toggle Sticky Scroll")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+
+    assert!(top_row(&harness, 0).contains("class Outer:"));
+    assert!(top_row(&harness, 1).contains("def inner(self):"));
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! E2E tests for structural expand/shrink selection: the server's
+//! `textDocument/selectionRange` chain, and the local fallback used when
+//! no server provides one.
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const EXPAND: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::ALT);
+
+/// Fake server answering every `textDocument/selectionRange` with one chain:
+/// `helper` → the whole (untrimmed) line → the function. The middle step is
+/// one the local ranges never produce. Every received method is logged.
+const FAKE_LSP_SCRIPT: &str = r##"#!/bin/bash
+
+LOG_FILE="$1"
+> "$LOG_FILE"
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: $length\r\n\r\n%s" "$message"
+}
+
+FUNCTION='{"range":{"start":{"line":0,"character":0},"end":{"line":2,"character":1}}}'
+LINE='{"range":{"start":{"line":1,"character":0},"end":{"line":1,"character":14}},"parent":'"$FUNCTION"'}'
+WORD='{"range":{"start":{"line":1,"character":4},"end":{"line":1,"character":10}},"parent":'"$LINE"'}'
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then break; fi
+
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    echo "METHOD:$method" >> "$LOG_FILE"
+
+    case "$method" in
+        "initialize")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":1,"selectionRangeProvider":true}}}'
+            ;;
+        "textDocument/selectionRange")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":['"$WORD"']}'
+            ;;
+        "shutdown")
+            send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+            break
+            ;;
+    esac
+done
+"##;
+
+/// Selected text of every cursor, in cursor order.
+fn selections(harness: &mut EditorTestHarness) -> Vec<String> {
+    let ranges: Vec<_> = harness
+        .editor()
+        .active_cursors()
+        .iter()
+        .map(|(_, c)| c.selection_range().unwrap_or(c.position..c.position))
+        .collect();
+    let state = harness.editor_mut().active_state_mut();
+    ranges
+        .into_iter()
+        .map(|r| state.get_text_range(r.start, r.end))
+        .collect()
+}
+
+/// Without a server every cursor walks the local ranges on its own, and
+/// shrink retraces the steps back to the bare cursors.
+#[test]
+fn test_structural_selection_fallback_multi_cursor() -> anyhow::Result<()> {
+    let mut harness = EditorTestHarness::new(80, 24)?;
+    harness.type_text("foo(bar, baz);\nfoo(bar, baz);")?;
+    harness.send_key(KeyCode::Home, KeyModifiers::CONTROL)?;
+    for _ in 0..5 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    harness.send_key(KeyCode::Down, EXPAND)?;
+    assert_eq!(harness.cursor_count(), 2);
+
+    harness.send_key(KeyCode::Right, EXPAND)?;
+    assert_eq!(selections(&mut harness), ["bar", "bar"]);
+    harness.send_key(KeyCode::Right, EXPAND)?;
+    assert_eq!(selections(&mut harness), ["bar, baz", "bar, baz"]);
+    harness.send_key(KeyCode::Right, EXPAND)?;
+    assert_eq!(selections(&mut harness), ["(bar, baz)", "(bar, baz)"]);
+
+    harness.send_key(KeyCode::Left, EXPAND)?;
+    assert_eq!(selections(&mut harness), ["bar, baz", "bar, baz"]);
+    harness.send_key(KeyCode::Left, EXPAND)?;
+    harness.send_key(KeyCode::Left, EXPAND)?;
+    assert_eq!(selections(&mut harness), ["", ""]);
+    harness.assert_no_selection();
+
+    Ok(())
+}
+
+/// Moving a cursor starts a new sequence: shrink has nothing to undo.
+#[test]
+fn test_structural_selection_resets_after_cursor_move() -> anyhow::Result<()> {
+    let mut harness = EditorTestHarness::new(80, 24)?;
+    harness.type_text("call(first, second)")?;
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE)?;
+
+    harness.send_key(KeyCode::Right, EXPAND)?;
+    harness.send_key(KeyCode::Right, EXPAND)?;
+    assert_eq!(selections(&mut harness), ["first, second"]);
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Left, EXPAND)?;
+    harness.assert_no_selection();
+
+    Ok(())
+}
+
+/// The server's chain is asked for once per sequence and wins over the
+/// local ranges.
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses bash script for fake LSP server
+fn test_structural_selection_uses_server_ranges() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_log.txt");
+    let script_path = temp_dir.path().join("fake_lsp_selection_range.sh");
+    std::fs::write(&script_path, FAKE_LSP_SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms)?;
+    }
+
+    let source = "fn main() {\n    helper(1);\n}\n";
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, source)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::types::LspLanguageConfig::Multi(vec![fresh::services::lsp::LspServerConfig {
+            command: script_path.to_string_lossy().to_string(),
+            args: vec![log_file.to_string_lossy().to_string()],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            env: Default::default(),
+            language_id_overrides: Default::default(),
+            root_markers: Default::default(),
+            name: None,
+            only_features: None,
+            except_features: None,
+        }]),
+    );
+
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        crate::common::harness::HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.path().to_path_buf()),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+    harness.wait_until(|_| {
+        std::fs::read_to_string(&log_file)
+            .unwrap_or_default()
+            .contains("METHOD:textDocument/didOpen")
+    })?;
+
+    // Cursor inside `helper`.
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    for _ in 0..6 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+
+    harness.send_key(KeyCode::Right, EXPAND)?;
+    harness.wait_until(|h| h.has_selection())?;
+    assert_eq!(selections(&mut harness), ["helper"]);
+
+    harness.send_key(KeyCode::Right, EXPAND)?;
+    assert_eq!(selections(&mut harness), ["    helper(1);"]);
+    harness.send_key(KeyCode::Right, EXPAND)?;
+    assert_eq!(selections(&mut harness), [source.trim_end()]);
+
+    harness.send_key(KeyCode::Left, EXPAND)?;
+    assert_eq!(selections(&mut harness), ["    helper(1);"]);
+
+    let log = std::fs::read_to_string(&log_file)?;
+    assert_eq!(
+        log.matches("METHOD:textDocument/selectionRange").count(),
+        1,
+        "one request per expansion sequence:\n{log}"
+    );
+
+    Ok(())
+}
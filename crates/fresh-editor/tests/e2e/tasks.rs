@@ -0,0 +1,99 @@
+//! E2E tests for the task runner.
+//!
+//! Runs a configured task whose output looks like a compiler error and
+//! checks that the problem matcher turns it into a diagnostic reachable with
+//! Next Error, and that Makefile targets are offered without configuration.
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::HashMap;
+
+const SOURCE: &str = "int main() {\n    return x;\n}\n";
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn harness_with_tasks(
+    temp_dir: &std::path::Path,
+    tasks: HashMap<String, fresh::types::TaskConfig>,
+) -> anyhow::Result<EditorTestHarness> {
+    let mut config = fresh::config::Config::default();
+    config.tasks = tasks;
+    EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.to_path_buf()),
+    )
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "task command uses a POSIX shell")]
+fn test_task_problems_become_diagnostics() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let file = temp_dir.path().join("main.c");
+    std::fs::write(&file, SOURCE)?;
+
+    let mut tasks = HashMap::new();
+    tasks.insert(
+        "build".to_string(),
+        fresh::types::TaskConfig {
+            command: "echo 'main.c:2:12: error: x undeclared'; exit 1".to_string(),
+            problem_matchers: vec!["gcc".to_string()],
+            ..Default::default()
+        },
+    );
+    let mut harness = harness_with_tasks(temp_dir.path(), tasks)?;
+    harness.open_file(&file)?;
+    harness.render()?;
+
+    run_command(&mut harness, "Tasks: Run Task");
+    harness.assert_screen_contains("Run task:");
+    harness.type_text("build")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| {
+        h.screen_to_string()
+            .contains("Task 'build' failed (exit code 1, 1 problem(s))")
+    })?;
+    harness.assert_screen_contains("*Task: build*");
+
+    // Focus stayed in the source file; Next Error lands on `x`.
+    assert_eq!(harness.cursor_position(), 0);
+    harness.send_key(KeyCode::F(8), KeyModifiers::NONE)?;
+    harness.render()?;
+    assert_eq!(harness.cursor_position(), SOURCE.find('x').unwrap());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(target_os = "windows", ignore = "task command uses a POSIX shell")]
+fn test_makefile_targets_are_detected() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    std::fs::write(
+        temp_dir.path().join("Makefile"),
+        ".PHONY: hello\nhello:\n\t@echo hello from make\n",
+    )?;
+
+    let mut harness = harness_with_tasks(temp_dir.path(), HashMap::new())?;
+    harness.render()?;
+
+    run_command(&mut harness, "Tasks: Run Task");
+    harness.assert_screen_contains("make hello");
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("Task 'make hello' succeeded"))?;
+    harness.assert_screen_contains("hello from make");
+
+    Ok(())
+}
@@ -9,7 +9,7 @@
 //! - RemoteFileSystem for file operations
 
 use fresh::model::buffer::TextBuffer;
-use fresh::model::filesystem::{FileSystem, WriteOp};
+use fresh::model::filesystem::{FileSystem, FsWatchState, WriteOp};
 use fresh::services::remote::{
     spawn_local_agent, spawn_local_agent_with_capacity, RemoteFileSystem, TEST_RECV_DELAY_US,
 };
@@ -1611,6 +1611,67 @@ fn test_concurrent_mixed_requests() {
     );
 }
 
+/// A watch on a remote directory reports a file created in it, and
+/// dropping the watch stops it.
+#[test]
+fn test_watch_reports_changes() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let dir = temp_dir.path().canonicalize().unwrap();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let watch = fs
+        .watch(
+            &dir,
+            false,
+            Box::new(move |path, kind| {
+                let _ = tx.send((path, kind.as_str()));
+            }),
+        )
+        .unwrap()
+        .expect("remote filesystem supports watching");
+
+    // The watch starts in the background; changes before the agent
+    // accepts it aren't reported.
+    while watch.state() == FsWatchState::Starting {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(watch.state(), FsWatchState::Running);
+
+    let new_file = dir.join("new.txt");
+    std::fs::write(&new_file, "hello").unwrap();
+    loop {
+        let (path, _kind) = rx.recv().expect("watch ended before the change");
+        if path == new_file {
+            break;
+        }
+    }
+
+    drop(watch);
+}
+
+/// Watching a missing directory returns at once and then fails in the
+/// background.
+#[test]
+fn test_watch_missing_dir_fails_without_blocking() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let missing = temp_dir.path().canonicalize().unwrap().join("missing");
+
+    let watch = fs
+        .watch(&missing, false, Box::new(|_, _| {}))
+        .unwrap()
+        .expect("remote filesystem supports watching");
+    while watch.state() == FsWatchState::Starting {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(watch.state(), FsWatchState::Failed);
+}
+
 #[test]
 fn test_unique_temp_path_uses_system_temp_dir() {
     let Some((fs, _temp_dir, _rt)) = create_test_filesystem() else {
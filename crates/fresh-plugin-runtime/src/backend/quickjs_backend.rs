@@ -1667,6 +1667,175 @@ impl JsEditorApi {
         id
     }
 
+    /// Symbols of a buffer without asking a language server: declarations
+    /// from its tree-sitter parse, or from per-language line patterns
+    /// (Markdown headings included). Resolves with the same shape as a
+    /// `textDocument/documentSymbol` answer — nested `DocumentSymbol`s with
+    /// 0-indexed LSP positions — or an empty array when nothing is found.
+    #[plugin_api(async_promise, js_name = "getSyntaxSymbols", ts_return = "unknown[]")]
+    #[qjs(rename = "_getSyntaxSymbolsStart")]
+    pub fn get_syntax_symbols_start(&self, _ctx: rquickjs::Ctx<'_>, buffer_id: u32) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::GetSyntaxSymbols {
+            buffer_id: BufferId(buffer_id as usize),
+            request_id: id,
+        });
+        id
+    }
+
+    /// Undo tree of a buffer: every state reachable by undo, redo, or a
+    /// redo branch abandoned by editing after an undo. Each node is the
+    /// state after one undo step; `parent` is the state it was made from
+    /// (0 is the initial state, which has no node). `current` is the
+    /// buffer's state and `saved` the state last saved to disk, if still
+    /// in the tree. Resolves with `null` for an unknown buffer.
+    #[plugin_api(
+        async_promise,
+        js_name = "getUndoTree",
+        ts_return = "{ nodes: Array<{ id: number; parent: number; timestamp: number; description: string }>; current: number; saved: number | null } | null"
+    )]
+    #[qjs(rename = "_getUndoTreeStart")]
+    pub fn get_undo_tree_start(&self, _ctx: rquickjs::Ctx<'_>, buffer_id: u32) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::GetUndoTree {
+            buffer_id: BufferId(buffer_id as usize),
+            request_id: id,
+        });
+        id
+    }
+
+    /// Unified diff (`@@` headers and ` `/`-`/`+` lines) from a buffer's
+    /// current content to state `node_id` of its undo tree. Resolves with
+    /// `null` when the state doesn't exist or the buffer isn't fully loaded.
+    #[plugin_api(
+        async_promise,
+        js_name = "getUndoStateDiff",
+        ts_return = "string[] | null"
+    )]
+    #[qjs(rename = "_getUndoStateDiffStart")]
+    pub fn get_undo_state_diff_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        node_id: u64,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::GetUndoStateDiff {
+            buffer_id: BufferId(buffer_id as usize),
+            node_id,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Line diff from `oldText` to `newText`: the hunks of changed lines,
+    /// with per-line ops, ready for `createCompositeBuffer`. A final
+    /// newline doesn't start an extra line. Resolves with `[]` when the
+    /// texts are equal.
+    #[plugin_api(async_promise, js_name = "diffLines", ts_return = "TsCompositeHunk[]")]
+    #[qjs(rename = "_diffLinesStart")]
+    pub fn diff_lines_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        old_text: String,
+        new_text: String,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::DiffLines {
+            old_text,
+            new_text,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Move a buffer to state `node_id` of its undo tree, as a series of
+    /// undos and redos. The buffer must be the active one: focus its split
+    /// first when calling from a panel.
+    pub fn jump_to_undo_state(&self, buffer_id: u32, node_id: u64) -> bool {
+        self.command_sender
+            .send(PluginCommand::JumpToUndoState {
+                buffer_id: BufferId(buffer_id as usize),
+                node_id,
+            })
+            .is_ok()
+    }
+
+    /// Saved versions of `path` in local history, newest first. A version
+    /// is recorded on every save that changed the file; `id` identifies it
+    /// and `timestamp` is its save time in milliseconds since the epoch.
+    #[plugin_api(
+        async_promise,
+        js_name = "getFileHistory",
+        ts_return = "Array<{ id: number; timestamp: number; size: number }>"
+    )]
+    #[qjs(rename = "_getFileHistoryStart")]
+    pub fn get_file_history_start(&self, _ctx: rquickjs::Ctx<'_>, path: String) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self.command_sender.send(PluginCommand::GetFileHistory {
+            path: PathBuf::from(path),
+            request_id: id,
+        });
+        id
+    }
+
+    /// Text of version `version_id` of `path` from local history. Resolves
+    /// with `null` if the version doesn't exist or isn't valid UTF-8.
+    #[plugin_api(
+        async_promise,
+        js_name = "readFileHistoryVersion",
+        ts_return = "string | null"
+    )]
+    #[qjs(rename = "_readFileHistoryVersionStart")]
+    pub fn read_file_history_version_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        path: String,
+        version_id: u64,
+    ) -> u64 {
+        let id = self.alloc_request_id();
+        let _ = self
+            .command_sender
+            .send(PluginCommand::ReadFileHistoryVersion {
+                path: PathBuf::from(path),
+                version_id,
+                request_id: id,
+            });
+        id
+    }
+
+    /// Replace a buffer's content with version `version_id` of its file
+    /// from local history, as a single undoable edit. The buffer must be
+    /// the active one: focus its split first when calling from a panel.
+    pub fn restore_file_history_version(&self, buffer_id: u32, version_id: u64) -> bool {
+        self.command_sender
+            .send(PluginCommand::RestoreFileHistoryVersion {
+                buffer_id: BufferId(buffer_id as usize),
+                version_id,
+            })
+            .is_ok()
+    }
+
+    /// Apply the workspace edit announced by the `workspace_edit_preview`
+    /// hook under `id`, leaving out the changes whose ids are in
+    /// `excluded`. Text changes land as one undo step across all files.
+    pub fn apply_workspace_edit_preview(&self, id: u64, excluded: Vec<u32>) -> bool {
+        self.command_sender
+            .send(PluginCommand::ApplyWorkspaceEditPreview {
+                id,
+                excluded: excluded.into_iter().map(|c| c as usize).collect(),
+            })
+            .is_ok()
+    }
+
+    /// Drop the workspace edit announced by the `workspace_edit_preview`
+    /// hook under `id` without applying any of it.
+    pub fn cancel_workspace_edit_preview(&self, id: u64) -> bool {
+        self.command_sender
+            .send(PluginCommand::CancelWorkspaceEditPreview { id })
+            .is_ok()
+    }
+
     /// Cursor info for the active composite (side-by-side diff) buffer.
     ///
     /// Resolves with `null` when the active buffer is not a composite
@@ -4411,17 +4580,24 @@ impl JsEditorApi {
         }
 
         // If allow_text_input is set, register a wildcard handler for text input
-        // so the plugin can receive arbitrary character input
+        // so the plugin can receive arbitrary character input. The per-mode
+        // entry keeps typing in this mode routed here even after another
+        // plugin defines a text-input mode of its own.
         let allow_text = allow_text_input.0.unwrap_or(false);
         if allow_text {
             let mut registered = self.registered_actions.borrow_mut();
-            registered.insert(
+            for action in [
                 "mode_text_input".to_string(),
-                PluginHandler {
-                    plugin_name: self.plugin_name.clone(),
-                    handler_name: "mode_text_input".to_string(),
-                },
-            );
+                format!("mode_text_input@{}", name),
+            ] {
+                registered.insert(
+                    action,
+                    PluginHandler {
+                        plugin_name: self.plugin_name.clone(),
+                        handler_name: "mode_text_input".to_string(),
+                    },
+                );
+            }
         }
 
         self.command_sender
@@ -4839,6 +5015,34 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Replace the vertical rulers shown for a buffer (column numbers)
+    pub fn set_rulers(&self, buffer_id: u32, columns: Vec<u32>) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetRulers {
+                buffer_id: BufferId(buffer_id as usize),
+                columns: columns.into_iter().map(|c| c as usize).collect(),
+            })
+            .is_ok()
+    }
+
+    /// Replace the diagnostics this plugin reports for a buffer under
+    /// `source`. They are shown like LSP diagnostics; pass an empty list to
+    /// clear them.
+    pub fn set_diagnostics(
+        &self,
+        buffer_id: u32,
+        source: String,
+        diagnostics: Vec<fresh_core::api::PluginDiagnostic>,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetDiagnostics {
+                buffer_id: BufferId(buffer_id as usize),
+                source,
+                diagnostics,
+            })
+            .is_ok()
+    }
+
     /// Set the view mode for a buffer ("source" or "compose")
     pub fn set_view_mode(&self, buffer_id: u32, mode: String) -> bool {
         self.command_sender
@@ -6250,6 +6454,47 @@ impl JsEditorApi {
         Ok(id)
     }
 
+    /// Send a request to the active debug session (async, resolves with the
+    /// adapter's response body; rejects when no session is running)
+    #[plugin_api(async_promise, js_name = "debugRequest", ts_return = "unknown")]
+    #[qjs(rename = "_debugRequestStart")]
+    pub fn debug_request_start<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        command: String,
+        args: Option<rquickjs::Object<'js>>,
+    ) -> rquickjs::Result<u64> {
+        let id = self.alloc_request_id();
+        let arguments_json: Option<serde_json::Value> = args.map(|obj| {
+            let val = obj.into_value();
+            js_to_json(&ctx, val)
+        });
+        let _ = self.command_sender.send(PluginCommand::DebugRequest {
+            request_id: id,
+            command,
+            arguments: arguments_json,
+        });
+        Ok(id)
+    }
+
+    /// Start a debug session for the active buffer's language. Top-level
+    /// keys of `configuration` replace those of the configured launch/attach
+    /// arguments and `null` removes one (e.g. to debug a single test).
+    pub fn debug_start<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        #[plugin_api(ts_type = "Record<string, unknown> | null")] configuration: Value<'js>,
+    ) -> bool {
+        let configuration = if configuration.is_undefined() || configuration.is_null() {
+            None
+        } else {
+            Some(js_to_json(&ctx, configuration))
+        };
+        self.command_sender
+            .send(PluginCommand::DebugStart { configuration })
+            .is_ok()
+    }
+
     /// Spawn a background process (async, returns request_id which is also process_id)
     #[plugin_api(
         async_thenable,
@@ -7120,6 +7365,7 @@ impl QuickJsBackend {
                 editor.createVirtualBufferInExistingSplit = _wrapAsync("_createVirtualBufferInExistingSplitStart", "createVirtualBufferInExistingSplit");
                 editor.createBufferGroup = _wrapAsync("_createBufferGroupStart", "createBufferGroup");
                 editor.sendLspRequest = _wrapAsync("_sendLspRequestStart", "sendLspRequest");
+                editor.debugRequest = _wrapAsync("_debugRequestStart", "debugRequest");
                 editor.spawnBackgroundProcess = _wrapAsyncThenable("_spawnBackgroundProcessStart", "spawnBackgroundProcess");
                 editor.httpFetch = _wrapAsyncThenable("_httpFetchStart", "httpFetch");
                 editor.spawnProcessWait = _wrapAsync("_spawnProcessWaitStart", "spawnProcessWait");
@@ -7135,6 +7381,12 @@ impl QuickJsBackend {
                 editor.getNextKey = _wrapAsync("_getNextKeyStart", "getNextKey");
                 editor.getLineStartPosition = _wrapAsync("_getLineStartPositionStart", "getLineStartPosition");
                 editor.getLineEndPosition = _wrapAsync("_getLineEndPositionStart", "getLineEndPosition");
+                editor.getSyntaxSymbols = _wrapAsync("_getSyntaxSymbolsStart", "getSyntaxSymbols");
+                editor.getUndoTree = _wrapAsync("_getUndoTreeStart", "getUndoTree");
+                editor.getUndoStateDiff = _wrapAsync("_getUndoStateDiffStart", "getUndoStateDiff");
+                editor.diffLines = _wrapAsync("_diffLinesStart", "diffLines");
+                editor.getFileHistory = _wrapAsync("_getFileHistoryStart", "getFileHistory");
+                editor.readFileHistoryVersion = _wrapAsync("_readFileHistoryVersionStart", "readFileHistoryVersion");
                 editor.createTerminal = _wrapAsync("_createTerminalStart", "createTerminal");
                 editor.createWindowWithTerminal = _wrapAsync("_createWindowWithTerminalStart", "createWindowWithTerminal");
                 editor.reloadGrammars = _wrapAsync("_reloadGrammarsStart", "reloadGrammars");
@@ -7603,19 +7855,30 @@ impl QuickJsBackend {
     /// This is useful when the calling thread needs to continue processing
     /// ResolveCallback requests that the action may be waiting for.
     pub fn start_action(&mut self, action_name: &str) -> Result<()> {
-        // Handle mode_text_input:<char> — route to the plugin that registered
-        // "mode_text_input" and pass the character as an argument.
-        let (lookup_name, text_input_char) =
-            if let Some(ch) = action_name.strip_prefix("mode_text_input:") {
-                ("mode_text_input", Some(ch.to_string()))
+        // Handle mode_text_input@<mode>:<char> — route to the plugin that
+        // defined <mode> — and the older mode_text_input:<char>, routed to
+        // the last plugin that registered "mode_text_input". Either way the
+        // character is passed as an argument.
+        let (lookup_name, text_input_char) = if let Some((mode, ch)) = action_name
+            .strip_prefix("mode_text_input@")
+            .and_then(|rest| rest.split_once(':'))
+        {
+            let per_mode = format!("mode_text_input@{}", mode);
+            if self.registered_actions.borrow().contains_key(&per_mode) {
+                (per_mode, Some(ch.to_string()))
             } else {
-                (action_name, None)
-            };
+                ("mode_text_input".to_string(), Some(ch.to_string()))
+            }
+        } else if let Some(ch) = action_name.strip_prefix("mode_text_input:") {
+            ("mode_text_input".to_string(), Some(ch.to_string()))
+        } else {
+            (action_name.to_string(), None)
+        };
 
-        let pair = self.registered_actions.borrow().get(lookup_name).cloned();
+        let pair = self.registered_actions.borrow().get(&lookup_name).cloned();
         let (plugin_name, function_name) = match pair {
             Some(handler) => (handler.plugin_name, handler.handler_name),
-            None => ("main".to_string(), lookup_name.to_string()),
+            None => ("main".to_string(), lookup_name),
         };
 
         let plugin_contexts = self.plugin_contexts.borrow();
@@ -22,10 +22,10 @@ use fresh_core::api::{
     CreateVirtualBufferOptions, CursorInfo, DirEntry, FormatterPackConfig, GrammarInfoSnapshot,
     GrepMatch, JsDiagnostic, JsPosition, JsRange, JsTextPropertyEntry, KeyEventPayload,
     LanguagePackConfig, LayoutHints, LspServerPackConfig, OverlayColorSpec, OverlayOptions,
-    PluginAnimationEdge, PluginAnimationKind, ProcessLimitsPackConfig, ReplaceResult, ScreenSize,
-    SearchTakeResult, SpawnResult, SplitSnapshot, TerminalResult, TextPropertiesAtCursor,
-    TokenColor, TsHighlightSpan, ViewTokenStyle, ViewTokenWire, ViewTokenWireKind, ViewportInfo,
-    VirtualBufferResult, WindowInfo,
+    PluginAnimationEdge, PluginAnimationKind, PluginDiagnostic, ProcessLimitsPackConfig,
+    ReplaceResult, ScreenSize, SearchTakeResult, SpawnResult, SplitSnapshot, TerminalResult,
+    TextPropertiesAtCursor, TokenColor, TsHighlightSpan, ViewTokenStyle, ViewTokenWire,
+    ViewTokenWireKind, ViewportInfo, VirtualBufferResult, WindowInfo,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::{
@@ -133,6 +133,7 @@ fn get_type_decl(type_name: &str) -> Option<String> {
 
         // Diagnostic types
         "JsDiagnostic" => Some(JsDiagnostic::decl(&cfg)),
+        "PluginDiagnostic" => Some(PluginDiagnostic::decl(&cfg)),
         "JsRange" => Some(JsRange::decl(&cfg)),
         "JsPosition" => Some(JsPosition::decl(&cfg)),
 
@@ -318,6 +319,7 @@ const DEPENDENCY_TYPES: &[&str] = &[
     "BufferInfo",                      // Used by listBuffers, getBufferInfo
     "WindowInfo",                      // Used by listWindows
     "JsDiagnostic",                    // Used by getAllDiagnostics
+    "PluginDiagnostic",                // Used by setDiagnostics
     "JsRange",                         // Used by JsDiagnostic
     "JsPosition",                      // Used by JsRange
     "ActionSpec",                      // Used by executeActions
@@ -622,6 +624,27 @@ interface HookEventMap {
     symbol: string;
     locations: { file: string; line: number; column: number }[];
   };
+  lsp_hierarchy: {
+    direction: "incoming" | "outgoing" | "supertypes" | "subtypes";
+    language: string;
+    items: unknown[];
+  };
+  workspace_edit_preview: {
+    id: number;
+    title: string;
+    changes: {
+      id: number;
+      kind: "edit" | "create" | "rename" | "delete";
+      path: string;
+      new_path: string | null;
+      range: {
+        start: { line: number; character: number };
+        end: { line: number; character: number };
+      } | null;
+      new_text: string;
+    }[];
+    texts: Record<string, string>;
+  };
   lsp_server_request: {
     language: string;
     method: string;
@@ -641,6 +664,15 @@ interface HookEventMap {
     user_dismissed: boolean;
   };
 
+  // ── debugger (DAP) ───────────────────────────────────────────────────────
+  debug_stopped: {
+    reason: string;
+    thread_id: number;
+    frames: { id: number; name: string; path: string | null; line: number; column: number }[];
+  };
+  debug_continued: { thread_id: number | null };
+  debug_terminated: { exit_code: number | null };
+
   // ── UI events ────────────────────────────────────────────────────────────
   action_popup_result: { popup_id: string; action_id: string };
   /**
@@ -890,6 +922,7 @@ mod tests {
             "PromptSuggestion",
             "DirEntry",
             "JsDiagnostic",
+            "PluginDiagnostic",
             "JsRange",
             "JsPosition",
             "LanguagePackConfig",
@@ -1272,6 +1305,16 @@ mod tests {
             "getLineStartPosition",
             "getLineEndPosition",
             "getBufferLineCount",
+            "getSyntaxSymbols",
+            "getUndoTree",
+            "getUndoStateDiff",
+            "diffLines",
+            "jumpToUndoState",
+            "getFileHistory",
+            "readFileHistoryVersion",
+            "restoreFileHistoryVersion",
+            "applyWorkspaceEditPreview",
+            "cancelWorkspaceEditPreview",
             "scrollToLineCenter",
             "findBufferByPath",
             "getBufferSavedDiff",
@@ -1395,6 +1438,8 @@ mod tests {
             "setLineIndicator",
             "clearLineIndicators",
             "setLineNumbers",
+            "setRulers",
+            "setDiagnostics",
             "setViewMode",
             "setViewState",
             "getViewState",
@@ -1427,6 +1472,8 @@ mod tests {
             "getBufferText",
             "delay",
             "sendLspRequest",
+            "debugRequest",
+            "debugStart",
             "spawnBackgroundProcess",
             "killBackgroundProcess",
             "createTerminal",
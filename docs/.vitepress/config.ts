@@ -67,6 +67,9 @@ export default defineConfig({
           { text: "Search and Replace", link: "/features/search-replace" },
           { text: "Integrated Terminal", link: "/features/terminal" },
           { text: "LSP Integration", link: "/features/lsp" },
+          { text: "Debugging", link: "/features/debugging" },
+          { text: "Tasks", link: "/features/tasks" },
+          { text: "Testing", link: "/features/testing" },
           { text: "Git", link: "/features/git" },
           { text: "Themes", link: "/features/themes" },
           { text: "Encoding", link: "/features/encoding" },